@@ -14,7 +14,6 @@ use llmx_protocol::parse_command::ParsedCommand;
 use llmx_protocol::protocol::FileChange;
 use llmx_protocol::protocol::ReviewDecision;
 use llmx_protocol::protocol::SandboxCommandAssessment;
-use paste::paste;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -121,6 +120,10 @@ client_request_definitions! {
         params: v2::ThreadArchiveParams,
         response: v2::ThreadArchiveResponse,
     },
+    ThreadUnsubscribe => "thread/unsubscribe" {
+        params: v2::ThreadUnsubscribeParams,
+        response: v2::ThreadUnsubscribeResponse,
+    },
     ThreadList => "thread/list" {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
@@ -129,6 +132,22 @@ client_request_definitions! {
         params: v2::ThreadCompactParams,
         response: v2::ThreadCompactResponse,
     },
+    ThreadCheckpointList => "thread/checkpoints/list" {
+        params: v2::ThreadCheckpointListParams,
+        response: v2::ThreadCheckpointListResponse,
+    },
+    ThreadCheckpointDiff => "thread/checkpoints/diff" {
+        params: v2::ThreadCheckpointDiffParams,
+        response: v2::ThreadCheckpointDiffResponse,
+    },
+    ThreadCheckpointRestore => "thread/checkpoints/restore" {
+        params: v2::ThreadCheckpointRestoreParams,
+        response: v2::ThreadCheckpointRestoreResponse,
+    },
+    ThreadUndo => "thread/undo" {
+        params: v2::ThreadUndoParams,
+        response: v2::ThreadUndoResponse,
+    },
     TurnStart => "turn/start" {
         params: v2::TurnStartParams,
         response: v2::TurnStartResponse,
@@ -173,6 +192,26 @@ client_request_definitions! {
         response: v2::GetAccountResponse,
     },
 
+    McpList => "mcp/list" {
+        params: v2::McpListParams,
+        response: v2::McpListResponse,
+    },
+
+    ConfigRead => "config/read" {
+        params: v2::ConfigReadParams,
+        response: v2::ConfigReadResponse,
+    },
+
+    ConfigWrite => "config/write" {
+        params: v2::ConfigWriteParams,
+        response: v2::ConfigWriteResponse,
+    },
+
+    PromptsList => "prompts/list" {
+        params: v2::PromptsListParams,
+        response: v2::PromptsListResponse,
+    },
+
     /// DEPRECATED APIs below
     NewConversation {
         params: v1::NewConversationParams,
@@ -277,34 +316,36 @@ macro_rules! server_request_definitions {
     (
         $(
             $(#[$variant_meta:meta])*
-            $variant:ident
+            $variant:ident $(=> $wire:literal)? {
+                params: $params:ty,
+                response: $response:ty,
+            }
         ),* $(,)?
     ) => {
-        paste! {
-            /// Request initiated from the server and sent to the client.
-            #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-            #[serde(tag = "method", rename_all = "camelCase")]
-            pub enum ServerRequest {
-                $(
-                    $(#[$variant_meta])*
-                    $variant {
-                        #[serde(rename = "id")]
-                        request_id: RequestId,
-                        params: [<$variant Params>],
-                    },
-                )*
-            }
+        /// Request initiated from the server and sent to the client.
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+        #[serde(tag = "method", rename_all = "camelCase")]
+        pub enum ServerRequest {
+            $(
+                $(#[$variant_meta])*
+                $(#[serde(rename = $wire)] #[ts(rename = $wire)])?
+                $variant {
+                    #[serde(rename = "id")]
+                    request_id: RequestId,
+                    params: $params,
+                },
+            )*
+        }
 
-            #[derive(Debug, Clone, PartialEq, JsonSchema)]
-            pub enum ServerRequestPayload {
-                $( $variant([<$variant Params>]), )*
-            }
+        #[derive(Debug, Clone, PartialEq, JsonSchema)]
+        pub enum ServerRequestPayload {
+            $( $variant($params), )*
+        }
 
-            impl ServerRequestPayload {
-                pub fn request_with_id(self, request_id: RequestId) -> ServerRequest {
-                    match self {
-                        $(Self::$variant(params) => ServerRequest::$variant { request_id, params },)*
-                    }
+        impl ServerRequestPayload {
+            pub fn request_with_id(self, request_id: RequestId) -> ServerRequest {
+                match self {
+                    $(Self::$variant(params) => ServerRequest::$variant { request_id, params },)*
                 }
             }
         }
@@ -312,9 +353,7 @@ macro_rules! server_request_definitions {
         pub fn export_server_responses(
             out_dir: &::std::path::Path,
         ) -> ::std::result::Result<(), ::ts_rs::ExportError> {
-            paste! {
-                $(<[<$variant Response>] as ::ts_rs::TS>::export_all_to(out_dir)?;)*
-            }
+            $(<$response as ::ts_rs::TS>::export_all_to(out_dir)?;)*
             Ok(())
         }
 
@@ -323,9 +362,7 @@ macro_rules! server_request_definitions {
             out_dir: &Path,
         ) -> ::anyhow::Result<Vec<GeneratedSchema>> {
             let mut schemas = Vec::new();
-            paste! {
-                $(schemas.push(crate::export::write_json_schema::<[<$variant Response>]>(out_dir, stringify!([<$variant Response>]))?);)*
-            }
+            $(schemas.push(write_json_schema::<$response>(out_dir, stringify!($response))?);)*
             Ok(schemas)
         }
 
@@ -334,9 +371,7 @@ macro_rules! server_request_definitions {
             out_dir: &Path,
         ) -> ::anyhow::Result<Vec<GeneratedSchema>> {
             let mut schemas = Vec::new();
-            paste! {
-                $(schemas.push(crate::export::write_json_schema::<[<$variant Params>]>(out_dir, stringify!([<$variant Params>]))?);)*
-            }
+            $(schemas.push(write_json_schema::<$params>(out_dir, stringify!($params))?);)*
             Ok(schemas)
         }
     };
@@ -426,10 +461,29 @@ impl TryFrom<JSONRPCRequest> for ServerRequest {
 }
 
 server_request_definitions! {
+    /// NEW APIs
+    /// Request to approve the file changes of a `fileChange` item.
+    FileChangeApproval => "item/fileChange/approval" {
+        params: v2::FileChangeApprovalParams,
+        response: v2::FileChangeApprovalResponse,
+    },
+    /// Request to approve running a `commandExecution` item.
+    CommandExecutionApproval => "item/commandExecution/approval" {
+        params: v2::CommandExecutionApprovalParams,
+        response: v2::CommandExecutionApprovalResponse,
+    },
+
+    /// DEPRECATED APIs below
     /// Request to approve a patch.
-    ApplyPatchApproval,
+    ApplyPatchApproval {
+        params: ApplyPatchApprovalParams,
+        response: ApplyPatchApprovalResponse,
+    },
     /// Request to exec a command.
-    ExecCommandApproval,
+    ExecCommandApproval {
+        params: ExecCommandApprovalParams,
+        response: ExecCommandApprovalResponse,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -508,6 +562,8 @@ server_notification_definitions! {
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
+    TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
+    TurnPlanUpdated => "turn/plan/updated" (v2::TurnPlanUpdatedNotification),
 
     #[serde(rename = "account/login/completed")]
     #[ts(rename = "account/login/completed")]
@@ -656,6 +712,123 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_v2_file_change_approval() -> Result<()> {
+        let params = v2::FileChangeApprovalParams {
+            thread_id: "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+            turn_id: "1".to_string(),
+            item_id: "call-7".to_string(),
+            changes: vec![v2::FileUpdateChange {
+                path: "README.md".to_string(),
+                kind: v2::PatchChangeKind::Add,
+                diff: "hello\n".to_string(),
+            }],
+            reason: None,
+            grant_root: None,
+        };
+        let request = ServerRequest::FileChangeApproval {
+            request_id: RequestId::Integer(8),
+            params: params.clone(),
+        };
+
+        assert_eq!(
+            json!({
+                "method": "item/fileChange/approval",
+                "id": 8,
+                "params": {
+                    "threadId": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                    "turnId": "1",
+                    "itemId": "call-7",
+                    "changes": [
+                        {
+                            "path": "README.md",
+                            "kind": "add",
+                            "diff": "hello\n"
+                        }
+                    ],
+                    "reason": null,
+                    "grantRoot": null
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+
+        let payload = ServerRequestPayload::FileChangeApproval(params);
+        assert_eq!(payload.request_with_id(RequestId::Integer(8)), request);
+
+        let response: v2::CommandExecutionApprovalResponse =
+            serde_json::from_value(json!({ "decision": "approvedForSession" }))?;
+        assert_eq!(
+            response.decision.to_core(),
+            ReviewDecision::ApprovedForSession
+        );
+
+        let patch = "*** Begin Patch\n*** Delete File: old.txt\n*** End Patch".to_string();
+        let response: v2::FileChangeApprovalResponse = serde_json::from_value(
+            json!({ "decision": { "approvedPartial": { "patch": patch } } }),
+        )?;
+        assert_eq!(
+            response.decision.to_core(),
+            ReviewDecision::ApprovedPartial { patch }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_turn_plan_updated_notification() -> Result<()> {
+        let notification = ServerNotification::TurnPlanUpdated(v2::TurnPlanUpdatedNotification {
+            thread_id: "thread-1".to_string(),
+            turn_id: "2".to_string(),
+            explanation: None,
+            plan: vec![v2::TurnPlanStep {
+                step: "Write tests".to_string(),
+                status: v2::TurnPlanStepStatus::InProgress,
+            }],
+        });
+        assert_eq!(notification.to_string(), "turn/plan/updated");
+        assert_eq!(
+            json!({
+                "method": "turn/plan/updated",
+                "params": {
+                    "threadId": "thread-1",
+                    "turnId": "2",
+                    "explanation": null,
+                    "plan": [
+                        {
+                            "step": "Write tests",
+                            "status": "inProgress"
+                        }
+                    ]
+                }
+            }),
+            serde_json::to_value(&notification)?,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn serialize_config_write() -> Result<()> {
+        let request = ClientRequest::ConfigWrite {
+            request_id: RequestId::Integer(9),
+            params: v2::ConfigWriteParams {
+                key_path: "profiles.fast.model".to_string(),
+                value: json!("gpt-5-mini"),
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "config/write",
+                "id": 9,
+                "params": {
+                    "keyPath": "profiles.fast.model",
+                    "value": "gpt-5-mini"
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize_get_account_rate_limits() -> Result<()> {
         let request = ClientRequest::GetAccountRateLimits {
@@ -4,12 +4,21 @@ use std::path::PathBuf;
 use crate::protocol::common::AuthMode;
 use llmx_protocol::ConversationId;
 use llmx_protocol::account::PlanType;
+use llmx_protocol::approvals::SandboxCommandAssessment as CoreSandboxCommandAssessment;
 use llmx_protocol::config_types::ReasoningEffort;
 use llmx_protocol::config_types::ReasoningSummary;
+use llmx_protocol::custom_prompts::CustomPrompt as CoreCustomPrompt;
+use llmx_protocol::custom_prompts::PromptArgument as CorePromptArgument;
+use llmx_protocol::plan_tool::PlanItemArg as CorePlanItemArg;
+use llmx_protocol::protocol::Checkpoint as CoreCheckpoint;
+use llmx_protocol::protocol::McpAuthStatus as CoreMcpAuthStatus;
 use llmx_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use llmx_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
 use llmx_protocol::user_input::UserInput as CoreUserInput;
 use mcp_types::ContentBlock as McpContentBlock;
+use mcp_types::Resource as McpResource;
+use mcp_types::ResourceTemplate as McpResourceTemplate;
+use mcp_types::Tool as McpTool;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -53,6 +62,87 @@ v2_enum_from_core!(
     }
 );
 
+/// Mirrors `ReviewDecision`. Written out by hand because
+/// `v2_enum_from_core!` only supports unit variants.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ApprovalDecision {
+    Approved,
+    ApprovedForSession,
+    /// Apply only `patch`, a subset of the proposed file changes in
+    /// `apply_patch` format. Only valid for file change approvals.
+    ApprovedPartial {
+        patch: String,
+    },
+    /// Approve and save an allow rule for `pattern` in the project.
+    ApprovedAlways {
+        pattern: String,
+    },
+    Denied,
+    /// Deny and save a deny rule for `pattern` in the project.
+    DeniedAlways {
+        pattern: String,
+    },
+    Abort,
+}
+
+impl ApprovalDecision {
+    pub fn to_core(self) -> llmx_protocol::protocol::ReviewDecision {
+        use llmx_protocol::protocol::ReviewDecision;
+        match self {
+            ApprovalDecision::Approved => ReviewDecision::Approved,
+            ApprovalDecision::ApprovedForSession => ReviewDecision::ApprovedForSession,
+            ApprovalDecision::ApprovedPartial { patch } => {
+                ReviewDecision::ApprovedPartial { patch }
+            }
+            ApprovalDecision::ApprovedAlways { pattern } => {
+                ReviewDecision::ApprovedAlways { pattern }
+            }
+            ApprovalDecision::Denied => ReviewDecision::Denied,
+            ApprovalDecision::DeniedAlways { pattern } => ReviewDecision::DeniedAlways { pattern },
+            ApprovalDecision::Abort => ReviewDecision::Abort,
+        }
+    }
+}
+
+impl From<llmx_protocol::protocol::ReviewDecision> for ApprovalDecision {
+    fn from(value: llmx_protocol::protocol::ReviewDecision) -> Self {
+        use llmx_protocol::protocol::ReviewDecision;
+        match value {
+            ReviewDecision::Approved => ApprovalDecision::Approved,
+            ReviewDecision::ApprovedForSession => ApprovalDecision::ApprovedForSession,
+            ReviewDecision::ApprovedPartial { patch } => {
+                ApprovalDecision::ApprovedPartial { patch }
+            }
+            ReviewDecision::ApprovedAlways { pattern } => {
+                ApprovalDecision::ApprovedAlways { pattern }
+            }
+            ReviewDecision::Denied => ApprovalDecision::Denied,
+            ReviewDecision::DeniedAlways { pattern } => ApprovalDecision::DeniedAlways { pattern },
+            ReviewDecision::Abort => ApprovalDecision::Abort,
+        }
+    }
+}
+
+v2_enum_from_core!(
+    pub enum CommandRiskLevel from llmx_protocol::approvals::SandboxRiskLevel {
+        Low, Medium, High
+    }
+);
+
+v2_enum_from_core!(
+    pub enum PromptArgumentType from llmx_protocol::custom_prompts::PromptArgumentType {
+        String, Number, Boolean, Enum
+    }
+);
+
+v2_enum_from_core!(
+    pub enum TurnPlanStepStatus from llmx_protocol::plan_tool::StepStatus {
+        Pending, InProgress, Completed
+    }
+);
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(tag = "mode", rename_all = "camelCase")]
 #[ts(tag = "mode")]
@@ -265,6 +355,163 @@ pub struct FeedbackUploadResponse {
     pub thread_id: String,
 }
 
+// === MCP ===
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpListParams {
+    /// MCP servers are started per thread; with a thread the listing includes
+    /// the tools and resources its servers expose. Without one, only the
+    /// configured servers and their auth status are returned.
+    pub thread_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpListResponse {
+    /// Sorted by server name.
+    pub servers: Vec<McpServer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServer {
+    pub name: String,
+    pub auth_status: McpAuthStatus,
+    pub tools: Vec<McpTool>,
+    pub resources: Vec<McpResource>,
+    pub resource_templates: Vec<McpResourceTemplate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum McpAuthStatus {
+    Unsupported,
+    NotLoggedIn,
+    BearerToken,
+    #[serde(rename = "oauth")]
+    #[ts(rename = "oauth")]
+    OAuth,
+}
+
+impl From<CoreMcpAuthStatus> for McpAuthStatus {
+    fn from(value: CoreMcpAuthStatus) -> Self {
+        match value {
+            CoreMcpAuthStatus::Unsupported => McpAuthStatus::Unsupported,
+            CoreMcpAuthStatus::NotLoggedIn => McpAuthStatus::NotLoggedIn,
+            CoreMcpAuthStatus::BearerToken => McpAuthStatus::BearerToken,
+            CoreMcpAuthStatus::OAuth => McpAuthStatus::OAuth,
+        }
+    }
+}
+
+// === Config ===
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigReadParams {
+    /// Dotted key such as `profiles.fast.model`. Returns the whole config
+    /// when omitted.
+    pub key_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigReadResponse {
+    /// The effective `config.toml` (including managed layers) as JSON, or the
+    /// value at `keyPath`. `null` when the key is not set.
+    pub config: JsonValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigWriteParams {
+    /// Dotted key such as `profiles.fast.model`.
+    pub key_path: String,
+    /// Value to store in the user's `config.toml`; `null` removes the key.
+    pub value: JsonValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigWriteResponse {}
+
+// === Prompts ===
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PromptsListParams {
+    /// Project prompts depend on the thread's working directory. Without a
+    /// thread, prompts are discovered from the server's configuration.
+    pub thread_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PromptsListResponse {
+    pub prompts: Vec<CustomPrompt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CustomPrompt {
+    pub name: String,
+    pub path: PathBuf,
+    pub content: String,
+    pub description: Option<String>,
+    pub argument_hint: Option<String>,
+    pub arguments: Vec<PromptArgument>,
+}
+
+impl From<CoreCustomPrompt> for CustomPrompt {
+    fn from(value: CoreCustomPrompt) -> Self {
+        Self {
+            name: value.name,
+            path: value.path,
+            content: value.content,
+            description: value.description,
+            argument_hint: value.argument_hint,
+            arguments: value.arguments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(rename = "type")]
+    #[ts(rename = "type")]
+    pub kind: PromptArgumentType,
+    pub default: Option<String>,
+    pub required: bool,
+    /// Accepted values for `enum` arguments.
+    pub choices: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl From<CorePromptArgument> for PromptArgument {
+    fn from(value: CorePromptArgument) -> Self {
+        Self {
+            name: value.name,
+            kind: value.kind.into(),
+            default: value.default,
+            required: value.required,
+            choices: value.choices,
+            description: value.description,
+        }
+    }
+}
+
 // === Threads, Turns, and Items ===
 // Thread APIs
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -314,6 +561,20 @@ pub struct ThreadArchiveParams {
 #[ts(export_to = "v2/")]
 pub struct ThreadArchiveResponse {}
 
+/// Stops the notifications of a thread on this connection. The thread keeps
+/// running and can be picked up again with `thread/resume`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnsubscribeParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUnsubscribeResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -349,6 +610,99 @@ pub struct ThreadCompactParams {
 #[ts(export_to = "v2/")]
 pub struct ThreadCompactResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointListResponse {
+    /// Oldest first.
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointDiffParams {
+    pub thread_id: String,
+    pub from: String,
+    /// Checkpoint to compare against. Defaults to the current working tree.
+    pub to: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointDiffResponse {
+    pub unified_diff: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointRestoreParams {
+    pub thread_id: String,
+    pub id: String,
+    /// Files to restore, relative to the thread's working directory. Restores
+    /// the whole tree when empty.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCheckpointRestoreResponse {
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadUndoResponse {
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+/// A snapshot of the working tree taken at the start of a turn.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Checkpoint {
+    pub id: String,
+    /// Unix timestamp (in seconds) when the checkpoint was taken.
+    pub created_at: Option<i64>,
+    /// The user message that started the turn.
+    pub prompt: Option<String>,
+    /// Files changed between this checkpoint and the next one, or the current
+    /// working tree for the latest checkpoint.
+    pub changed_files: Vec<PathBuf>,
+}
+
+impl From<CoreCheckpoint> for Checkpoint {
+    fn from(value: CoreCheckpoint) -> Self {
+        Self {
+            id: value.id,
+            created_at: value.created_at,
+            prompt: value.prompt,
+            changed_files: value.changed_files,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -582,6 +936,68 @@ pub struct TodoItem {
     pub completed: bool,
 }
 
+// === Server Requests ===
+// Approvals are keyed by the item they gate; `turnId` is the id returned by
+// `turn/start`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangeApprovalParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub item_id: String,
+    pub changes: Vec<FileUpdateChange>,
+    /// Optional explanatory reason (e.g. request for extra write access).
+    pub reason: Option<String>,
+    /// When set, the agent is asking the user to allow writes under this root
+    /// for the remainder of the session.
+    pub grant_root: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangeApprovalResponse {
+    pub decision: ApprovalDecision,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CommandExecutionApprovalParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub item_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    pub reason: Option<String>,
+    pub risk: Option<CommandRiskAssessment>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CommandExecutionApprovalResponse {
+    pub decision: ApprovalDecision,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CommandRiskAssessment {
+    pub description: String,
+    pub risk_level: CommandRiskLevel,
+}
+
+impl From<CoreSandboxCommandAssessment> for CommandRiskAssessment {
+    fn from(value: CoreSandboxCommandAssessment) -> Self {
+        Self {
+            description: value.description,
+            risk_level: value.risk_level.into(),
+        }
+    }
+}
+
 // === Server Notifications ===
 // Thread/Turn lifecycle notifications and item progress events
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -616,6 +1032,43 @@ pub struct TurnCompletedNotification {
     pub usage: Usage,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnDiffUpdatedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// Aggregated unified diff of every file changed so far in the turn.
+    pub diff: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnPlanUpdatedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub explanation: Option<String>,
+    pub plan: Vec<TurnPlanStep>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnPlanStep {
+    pub step: String,
+    pub status: TurnPlanStepStatus,
+}
+
+impl From<CorePlanItemArg> for TurnPlanStep {
+    fn from(value: CorePlanItemArg) -> Self {
+        Self {
+            step: value.step,
+            status: value.status.into(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -9,11 +9,14 @@ use std::io::Result as IoResult;
 use std::path::PathBuf;
 
 use crate::message_processor::MessageProcessor;
+use crate::message_processor::SharedState;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
 use llmx_app_server_protocol::JSONRPCMessage;
 use llmx_feedback::LlmxFeedback;
 use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
@@ -34,43 +37,23 @@ mod llmx_message_processor;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod thread_hub;
+mod transport;
+
+pub use transport::AUTH_TOKEN_ENV_VAR;
+pub use transport::ListenAddress;
+pub use transport::ListenArgs;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
 /// plenty for an interactive CLI.
-const CHANNEL_CAPACITY: usize = 128;
+pub(crate) const CHANNEL_CAPACITY: usize = 128;
 
 pub async fn run_main(
     llmx_linux_sandbox_exe: Option<PathBuf>,
     cli_config_overrides: CliConfigOverrides,
+    listen_args: ListenArgs,
 ) -> IoResult<()> {
-    // Set up channels.
-    let (incoming_tx, mut incoming_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
-    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<OutgoingMessage>();
-
-    // Task: read from stdin, push to `incoming_tx`.
-    let stdin_reader_handle = tokio::spawn({
-        async move {
-            let stdin = io::stdin();
-            let reader = BufReader::new(stdin);
-            let mut lines = reader.lines();
-
-            while let Some(line) = lines.next_line().await.unwrap_or_default() {
-                match serde_json::from_str::<JSONRPCMessage>(&line) {
-                    Ok(msg) => {
-                        if incoming_tx.send(msg).await.is_err() {
-                            // Receiver gone – nothing left to do.
-                            break;
-                        }
-                    }
-                    Err(e) => error!("Failed to deserialize JSONRPCMessage: {e}"),
-                }
-            }
-
-            debug!("stdin reader finished (EOF)");
-        }
-    });
-
     // Parse CLI overrides once and derive the base Config eagerly so later
     // components do not need to work with raw TOML values.
     let cli_kv_overrides = cli_config_overrides.parse_overrides().map_err(|e| {
@@ -117,56 +100,107 @@ pub async fn run_main(
         }))
         .try_init();
 
-    // Task: process incoming messages.
-    let processor_handle = tokio::spawn({
-        let outgoing_message_sender = OutgoingMessageSender::new(outgoing_tx);
-        let mut processor = MessageProcessor::new(
-            outgoing_message_sender,
-            llmx_linux_sandbox_exe,
-            std::sync::Arc::new(config),
-            feedback.clone(),
-        );
-        async move {
-            while let Some(msg) = incoming_rx.recv().await {
-                match msg {
-                    JSONRPCMessage::Request(r) => processor.process_request(r).await,
-                    JSONRPCMessage::Response(r) => processor.process_response(r).await,
-                    JSONRPCMessage::Notification(n) => processor.process_notification(n).await,
-                    JSONRPCMessage::Error(e) => processor.process_error(e),
+    // Conversations are shared by every client, so a thread started by one
+    // connection can be picked up by another.
+    let shared = SharedState::new(
+        llmx_linux_sandbox_exe,
+        std::sync::Arc::new(config),
+        feedback,
+    );
+
+    match listen_args.listen.clone() {
+        Some(address) => transport::serve(address, &listen_args, shared).await,
+        None => {
+            serve_lines(io::stdin(), io::stdout(), shared).await;
+            Ok(())
+        }
+    }
+}
+
+/// Serves one client speaking newline-delimited JSON-RPC, until `reader`
+/// reaches EOF.
+pub(crate) async fn serve_lines<R, W>(reader: R, mut writer: W, shared: SharedState)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    // Set up channels.
+    let (incoming_tx, incoming_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
+    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<OutgoingMessage>();
+
+    // Task: read lines, push to `incoming_tx`.
+    let reader_handle = tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Some(line) = lines.next_line().await.unwrap_or_default() {
+            match serde_json::from_str::<JSONRPCMessage>(&line) {
+                Ok(msg) => {
+                    if incoming_tx.send(msg).await.is_err() {
+                        // Receiver gone – nothing left to do.
+                        break;
+                    }
                 }
+                Err(e) => error!("Failed to deserialize JSONRPCMessage: {e}"),
             }
-
-            info!("processor task exited (channel closed)");
         }
+
+        debug!("reader finished (EOF)");
     });
 
-    // Task: write outgoing messages to stdout.
-    let stdout_writer_handle = tokio::spawn(async move {
-        let mut stdout = io::stdout();
+    // Task: process incoming messages.
+    let processor_handle = tokio::spawn(run_processor(incoming_rx, outgoing_tx, shared));
+
+    // Task: write outgoing messages.
+    let writer_handle = tokio::spawn(async move {
         while let Some(outgoing_message) = outgoing_rx.recv().await {
-            let Ok(value) = serde_json::to_value(outgoing_message) else {
-                error!("Failed to convert OutgoingMessage to JSON value");
+            let Some(mut json) = serialize_outgoing(outgoing_message) else {
                 continue;
             };
-            match serde_json::to_string(&value) {
-                Ok(mut json) => {
-                    json.push('\n');
-                    if let Err(e) = stdout.write_all(json.as_bytes()).await {
-                        error!("Failed to write to stdout: {e}");
-                        break;
-                    }
-                }
-                Err(e) => error!("Failed to serialize JSONRPCMessage: {e}"),
+            json.push('\n');
+            if let Err(e) = writer.write_all(json.as_bytes()).await {
+                error!("Failed to write outgoing message: {e}");
+                break;
             }
         }
 
-        info!("stdout writer exited (channel closed)");
+        info!("writer exited (channel closed)");
     });
 
-    // Wait for all tasks to finish.  The typical exit path is the stdin reader
+    // Wait for all tasks to finish.  The typical exit path is the reader
     // hitting EOF which, once it drops `incoming_tx`, propagates shutdown to
-    // the processor and then to the stdout task.
-    let _ = tokio::join!(stdin_reader_handle, processor_handle, stdout_writer_handle);
+    // the processor and then to the writer task.
+    let _ = tokio::join!(reader_handle, processor_handle, writer_handle);
+}
+
+/// Processes the messages of one client until its incoming channel closes.
+pub(crate) async fn run_processor(
+    mut incoming_rx: mpsc::Receiver<JSONRPCMessage>,
+    outgoing_tx: mpsc::UnboundedSender<OutgoingMessage>,
+    shared: SharedState,
+) {
+    let mut processor = MessageProcessor::new(OutgoingMessageSender::new(outgoing_tx), shared);
+    while let Some(msg) = incoming_rx.recv().await {
+        match msg {
+            JSONRPCMessage::Request(r) => processor.process_request(r).await,
+            JSONRPCMessage::Response(r) => processor.process_response(r).await,
+            JSONRPCMessage::Notification(n) => processor.process_notification(n).await,
+            JSONRPCMessage::Error(e) => processor.process_error(e),
+        }
+    }
+
+    info!("processor task exited (channel closed)");
+}
 
-    Ok(())
+pub(crate) fn serialize_outgoing(outgoing_message: OutgoingMessage) -> Option<String> {
+    let Ok(value) = serde_json::to_value(outgoing_message) else {
+        error!("Failed to convert OutgoingMessage to JSON value");
+        return None;
+    };
+    match serde_json::to_string(&value) {
+        Ok(json) => Some(json),
+        Err(e) => {
+            error!("Failed to serialize JSONRPCMessage: {e}");
+            None
+        }
+    }
 }
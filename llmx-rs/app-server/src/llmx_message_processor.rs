@@ -4,6 +4,8 @@ use crate::fuzzy_file_search::run_fuzzy_file_search;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
+use crate::thread_hub::ThreadHub;
+use crate::thread_hub::ThreadSubscription;
 use chrono::DateTime;
 use chrono::Utc;
 use llmx_app_server_protocol::Account;
@@ -23,6 +25,12 @@ use llmx_app_server_protocol::CancelLoginAccountParams;
 use llmx_app_server_protocol::CancelLoginAccountResponse;
 use llmx_app_server_protocol::CancelLoginChatGptResponse;
 use llmx_app_server_protocol::ClientRequest;
+use llmx_app_server_protocol::CommandExecutionApprovalParams;
+use llmx_app_server_protocol::CommandExecutionApprovalResponse;
+use llmx_app_server_protocol::ConfigReadParams;
+use llmx_app_server_protocol::ConfigReadResponse;
+use llmx_app_server_protocol::ConfigWriteParams;
+use llmx_app_server_protocol::ConfigWriteResponse;
 use llmx_app_server_protocol::ConversationGitInfo;
 use llmx_app_server_protocol::ConversationSummary;
 use llmx_app_server_protocol::ExecCommandApprovalParams;
@@ -31,6 +39,9 @@ use llmx_app_server_protocol::ExecOneOffCommandParams;
 use llmx_app_server_protocol::ExecOneOffCommandResponse;
 use llmx_app_server_protocol::FeedbackUploadParams;
 use llmx_app_server_protocol::FeedbackUploadResponse;
+use llmx_app_server_protocol::FileChangeApprovalParams;
+use llmx_app_server_protocol::FileChangeApprovalResponse;
+use llmx_app_server_protocol::FileUpdateChange;
 use llmx_app_server_protocol::FuzzyFileSearchParams;
 use llmx_app_server_protocol::FuzzyFileSearchResponse;
 use llmx_app_server_protocol::GetAccountParams;
@@ -56,10 +67,16 @@ use llmx_app_server_protocol::LoginChatGptCompleteNotification;
 use llmx_app_server_protocol::LoginChatGptResponse;
 use llmx_app_server_protocol::LogoutAccountResponse;
 use llmx_app_server_protocol::LogoutChatGptResponse;
+use llmx_app_server_protocol::McpListParams;
+use llmx_app_server_protocol::McpListResponse;
+use llmx_app_server_protocol::McpServer;
 use llmx_app_server_protocol::ModelListParams;
 use llmx_app_server_protocol::ModelListResponse;
 use llmx_app_server_protocol::NewConversationParams;
 use llmx_app_server_protocol::NewConversationResponse;
+use llmx_app_server_protocol::PatchChangeKind;
+use llmx_app_server_protocol::PromptsListParams;
+use llmx_app_server_protocol::PromptsListResponse;
 use llmx_app_server_protocol::RemoveConversationListenerParams;
 use llmx_app_server_protocol::RemoveConversationSubscriptionResponse;
 use llmx_app_server_protocol::RequestId;
@@ -79,6 +96,12 @@ use llmx_app_server_protocol::SetDefaultModelResponse;
 use llmx_app_server_protocol::Thread;
 use llmx_app_server_protocol::ThreadArchiveParams;
 use llmx_app_server_protocol::ThreadArchiveResponse;
+use llmx_app_server_protocol::ThreadCheckpointDiffParams;
+use llmx_app_server_protocol::ThreadCheckpointDiffResponse;
+use llmx_app_server_protocol::ThreadCheckpointListParams;
+use llmx_app_server_protocol::ThreadCheckpointListResponse;
+use llmx_app_server_protocol::ThreadCheckpointRestoreParams;
+use llmx_app_server_protocol::ThreadCheckpointRestoreResponse;
 use llmx_app_server_protocol::ThreadItem;
 use llmx_app_server_protocol::ThreadListParams;
 use llmx_app_server_protocol::ThreadListResponse;
@@ -87,9 +110,15 @@ use llmx_app_server_protocol::ThreadResumeResponse;
 use llmx_app_server_protocol::ThreadStartParams;
 use llmx_app_server_protocol::ThreadStartResponse;
 use llmx_app_server_protocol::ThreadStartedNotification;
+use llmx_app_server_protocol::ThreadUndoParams;
+use llmx_app_server_protocol::ThreadUndoResponse;
+use llmx_app_server_protocol::ThreadUnsubscribeParams;
+use llmx_app_server_protocol::ThreadUnsubscribeResponse;
 use llmx_app_server_protocol::Turn;
+use llmx_app_server_protocol::TurnDiffUpdatedNotification;
 use llmx_app_server_protocol::TurnInterruptParams;
 use llmx_app_server_protocol::TurnInterruptResponse;
+use llmx_app_server_protocol::TurnPlanUpdatedNotification;
 use llmx_app_server_protocol::TurnStartParams;
 use llmx_app_server_protocol::TurnStartResponse;
 use llmx_app_server_protocol::TurnStartedNotification;
@@ -113,20 +142,30 @@ use llmx_core::config::Config;
 use llmx_core::config::ConfigOverrides;
 use llmx_core::config::ConfigToml;
 use llmx_core::config::edit::ConfigEditsBuilder;
+use llmx_core::config::edit::parse_key_path;
+use llmx_core::config::edit::parse_value_item;
+use llmx_core::config::effective::redact_secrets;
 use llmx_core::config_loader::load_config_as_toml;
+use llmx_core::custom_prompts::default_prompts_dir;
+use llmx_core::custom_prompts::discover_prompts_in_dirs;
 use llmx_core::default_client::get_llmx_user_agent;
 use llmx_core::exec::ExecParams;
 use llmx_core::exec_env::create_env;
 use llmx_core::find_conversation_path_by_id_str;
 use llmx_core::get_platform_sandbox;
 use llmx_core::git_info::git_diff_to_remote;
+use llmx_core::mcp::auth::compute_auth_statuses;
 use llmx_core::parse_cursor;
 use llmx_core::protocol::ApplyPatchApprovalRequestEvent;
 use llmx_core::protocol::Event;
 use llmx_core::protocol::EventMsg;
 use llmx_core::protocol::ExecApprovalRequestEvent;
+use llmx_core::protocol::FileChange;
+use llmx_core::protocol::McpAuthStatus as CoreMcpAuthStatus;
+use llmx_core::protocol::McpListToolsResponseEvent;
 use llmx_core::protocol::Op;
 use llmx_core::protocol::ReviewDecision;
+use llmx_core::protocol::TurnDiffEvent;
 use llmx_core::read_head_for_summary;
 use llmx_feedback::LlmxFeedback;
 use llmx_login::ServerOptions as LoginServerOptions;
@@ -136,6 +175,7 @@ use llmx_protocol::ConversationId;
 use llmx_protocol::config_types::ForcedLoginMethod;
 use llmx_protocol::items::TurnItem;
 use llmx_protocol::models::ResponseItem;
+use llmx_protocol::plan_tool::UpdatePlanArgs;
 use llmx_protocol::protocol::GitInfo;
 use llmx_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use llmx_protocol::protocol::RolloutItem;
@@ -154,6 +194,7 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tokio::sync::oneshot;
 use tracing::error;
 use tracing::info;
@@ -162,6 +203,8 @@ use uuid::Uuid;
 
 type PendingInterruptQueue = Vec<(RequestId, ApiVersion)>;
 type PendingInterrupts = Arc<Mutex<HashMap<ConversationId, PendingInterruptQueue>>>;
+// Requests answered by a core event, keyed by conversation and the id of the submitted op.
+type PendingOpRequests = Arc<Mutex<HashMap<(ConversationId, String), RequestId>>>;
 
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -184,9 +227,14 @@ pub(crate) struct LlmxMessageProcessor {
     llmx_linux_sandbox_exe: Option<PathBuf>,
     config: Arc<Config>,
     conversation_listeners: HashMap<Uuid, oneshot::Sender<()>>,
+    // Subscription created by `thread/start` or `thread/resume` for each thread.
+    thread_subscriptions: HashMap<ConversationId, Uuid>,
+    thread_hub: Arc<ThreadHub>,
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
+    // Requests (checkpoints, undo, MCP and prompt listings) awaiting their response event from core.
+    pending_op_requests: PendingOpRequests,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     feedback: LlmxFeedback,
 }
@@ -225,6 +273,7 @@ impl LlmxMessageProcessor {
     pub fn new(
         auth_manager: Arc<AuthManager>,
         conversation_manager: Arc<ConversationManager>,
+        thread_hub: Arc<ThreadHub>,
         outgoing: Arc<OutgoingMessageSender>,
         llmx_linux_sandbox_exe: Option<PathBuf>,
         config: Arc<Config>,
@@ -237,8 +286,11 @@ impl LlmxMessageProcessor {
             llmx_linux_sandbox_exe,
             config,
             conversation_listeners: HashMap::new(),
+            thread_subscriptions: HashMap::new(),
+            thread_hub,
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
+            pending_op_requests: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             feedback,
         }
@@ -259,6 +311,9 @@ impl LlmxMessageProcessor {
             ClientRequest::ThreadArchive { request_id, params } => {
                 self.thread_archive(request_id, params).await;
             }
+            ClientRequest::ThreadUnsubscribe { request_id, params } => {
+                self.thread_unsubscribe(request_id, params).await;
+            }
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(request_id, params).await;
             }
@@ -269,6 +324,18 @@ impl LlmxMessageProcessor {
                 self.send_unimplemented_error(request_id, "thread/compact")
                     .await;
             }
+            ClientRequest::ThreadCheckpointList { request_id, params } => {
+                self.thread_checkpoint_list(request_id, params).await;
+            }
+            ClientRequest::ThreadCheckpointDiff { request_id, params } => {
+                self.thread_checkpoint_diff(request_id, params).await;
+            }
+            ClientRequest::ThreadCheckpointRestore { request_id, params } => {
+                self.thread_checkpoint_restore(request_id, params).await;
+            }
+            ClientRequest::ThreadUndo { request_id, params } => {
+                self.thread_undo(request_id, params).await;
+            }
             ClientRequest::TurnStart { request_id, params } => {
                 self.turn_start(request_id, params).await;
             }
@@ -305,6 +372,18 @@ impl LlmxMessageProcessor {
             ClientRequest::GetAccount { request_id, params } => {
                 self.get_account(request_id, params).await;
             }
+            ClientRequest::McpList { request_id, params } => {
+                self.mcp_list(request_id, params).await;
+            }
+            ClientRequest::ConfigRead { request_id, params } => {
+                self.config_read(request_id, params).await;
+            }
+            ClientRequest::ConfigWrite { request_id, params } => {
+                self.config_write(request_id, params).await;
+            }
+            ClientRequest::PromptsList { request_id, params } => {
+                self.prompts_list(request_id, params).await;
+            }
             ClientRequest::ResumeConversation { request_id, params } => {
                 self.handle_resume_conversation(request_id, params).await;
             }
@@ -437,6 +516,7 @@ impl LlmxMessageProcessor {
             &self.config.llmx_home,
             &params.api_key,
             self.config.cli_auth_credentials_store_mode,
+            self.config.credential_helper.as_deref(),
         ) {
             Ok(()) => {
                 self.auth_manager.reload();
@@ -521,6 +601,7 @@ impl LlmxMessageProcessor {
                 CLIENT_ID.to_string(),
                 config.forced_chatgpt_workspace_id.clone(),
                 config.cli_auth_credentials_store_mode,
+                config.credential_helper.clone(),
             )
         })
     }
@@ -1049,6 +1130,100 @@ impl LlmxMessageProcessor {
         }
     }
 
+    async fn config_read(&self, request_id: RequestId, params: ConfigReadParams) {
+        let mut toml_value = match load_config_as_toml(&self.config.llmx_home).await {
+            Ok(val) => val,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to load config.toml: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        // Tokens, API keys and MCP `env`/`http_headers` values never leave the
+        // server; clients that need them read `config.toml` themselves.
+        redact_secrets(&mut toml_value);
+
+        let value = match params.key_path.as_deref() {
+            Some(key_path) => {
+                let segments = match parse_key_path(key_path) {
+                    Ok(segments) => segments,
+                    Err(err) => {
+                        let error = JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!("{err:#}"),
+                            data: None,
+                        };
+                        self.outgoing.send_error(request_id, error).await;
+                        return;
+                    }
+                };
+                segments
+                    .iter()
+                    .try_fold(&toml_value, |value, segment| value.get(segment.as_str()))
+                    .cloned()
+            }
+            None => Some(toml_value),
+        };
+
+        let config = match value.map(serde_json::to_value).transpose() {
+            Ok(config) => config.unwrap_or_default(),
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to serialize config: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        self.outgoing
+            .send_response(request_id, ConfigReadResponse { config })
+            .await;
+    }
+
+    async fn config_write(&self, request_id: RequestId, params: ConfigWriteParams) {
+        let ConfigWriteParams { key_path, value } = params;
+        let segments = match parse_key_path(&key_path) {
+            Ok(segments) => segments,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("{err:#}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let edits = ConfigEditsBuilder::new(&self.config.llmx_home);
+        let edits = if value.is_null() {
+            edits.clear_path(segments)
+        } else {
+            edits.set_path(segments, parse_value_item(&json_to_toml(value).to_string()))
+        };
+        match edits.apply().await {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(request_id, ConfigWriteResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to write config.toml: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn exec_one_off_command(&self, request_id: RequestId, params: ExecOneOffCommandParams) {
         tracing::debug!("ExecOneOffCommand params: {params:?}");
 
@@ -1252,18 +1427,8 @@ impl LlmxMessageProcessor {
                     thread: thread.clone(),
                 };
 
-                // Auto-attach a conversation listener when starting a thread.
-                // Use the same behavior as the v1 API with experimental_raw_events=false.
-                if let Err(err) = self
-                    .attach_conversation_listener(conversation_id, false)
-                    .await
-                {
-                    tracing::warn!(
-                        "failed to attach listener for conversation {}: {}",
-                        conversation_id,
-                        err.message
-                    );
-                }
+                // Auto-subscribe this connection when starting a thread.
+                self.subscribe_to_thread(conversation_id).await;
 
                 self.outgoing.send_response(request_id, response).await;
 
@@ -1329,6 +1494,11 @@ impl LlmxMessageProcessor {
             .await
         {
             Ok(()) => {
+                if let Some(subscription_id) = self.thread_subscriptions.remove(&conversation_id)
+                    && let Some(sender) = self.conversation_listeners.remove(&subscription_id)
+                {
+                    let _ = sender.send(());
+                }
                 let response = ThreadArchiveResponse {};
                 self.outgoing.send_response(request_id, response).await;
             }
@@ -1419,6 +1589,23 @@ impl LlmxMessageProcessor {
             }
         };
 
+        // The thread is still loaded (e.g. another client started it, or this
+        // client reconnected): subscribe to it and replay the running turn
+        // instead of loading a second copy from the rollout.
+        if self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+            .is_ok()
+        {
+            self.subscribe_to_thread(conversation_id).await;
+            let response = ThreadResumeResponse {
+                thread: summary_to_thread(summary),
+            };
+            self.outgoing.send_response(request_id, response).await;
+            return;
+        }
+
         let initial_history = match RolloutRecorder::get_rollout_history(&summary.path).await {
             Ok(initial_history) => initial_history,
             Err(err) => {
@@ -1447,17 +1634,8 @@ impl LlmxMessageProcessor {
             Ok(_) => {
                 let thread = summary_to_thread(summary);
 
-                // Auto-attach a conversation listener when resuming a thread.
-                if let Err(err) = self
-                    .attach_conversation_listener(conversation_id, false)
-                    .await
-                {
-                    tracing::warn!(
-                        "failed to attach listener for conversation {}: {}",
-                        conversation_id,
-                        err.message
-                    );
-                }
+                // Auto-subscribe this connection when resuming a thread.
+                self.subscribe_to_thread(conversation_id).await;
 
                 let response = ThreadResumeResponse { thread };
                 self.outgoing.send_response(request_id, response).await;
@@ -1958,12 +2136,15 @@ impl LlmxMessageProcessor {
             .await
         {
             info!("conversation {conversation_id} was active; shutting down");
-            let conversation_clone = conversation.clone();
             let notify = Arc::new(tokio::sync::Notify::new());
             let notify_clone = notify.clone();
 
             // Establish the listener for ShutdownComplete before submitting
             // Shutdown so it is not missed.
+            let ThreadSubscription { mut receiver, .. } = self
+                .thread_hub
+                .subscribe(conversation_id, conversation.clone())
+                .await;
             let is_shutdown = tokio::spawn(async move {
                 // Create the notified future outside the loop to avoid losing notifications.
                 let notified = notify_clone.notified();
@@ -1971,13 +2152,14 @@ impl LlmxMessageProcessor {
                 loop {
                     select! {
                         _ = &mut notified => { break; }
-                        event = conversation_clone.next_event() => {
+                        event = receiver.recv() => {
                             match event {
                                 Ok(event) => {
                                     if matches!(event.msg, EventMsg::ShutdownComplete) { break; }
                                 }
-                                // Break on errors to avoid tight loops when the agent loop has exited.
-                                Err(_) => { break; }
+                                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                                // Break once the event stream has ended.
+                                Err(broadcast::error::RecvError::Closed) => { break; }
                             }
                         }
                     }
@@ -2255,6 +2437,129 @@ impl LlmxMessageProcessor {
         let _ = conversation.submit(Op::Interrupt).await;
     }
 
+    async fn thread_checkpoint_list(
+        &self,
+        request_id: RequestId,
+        params: ThreadCheckpointListParams,
+    ) {
+        self.submit_op_for_event(request_id, &params.thread_id, Op::ListCheckpoints)
+            .await;
+    }
+
+    async fn thread_checkpoint_diff(
+        &self,
+        request_id: RequestId,
+        params: ThreadCheckpointDiffParams,
+    ) {
+        let ThreadCheckpointDiffParams {
+            thread_id,
+            from,
+            to,
+        } = params;
+        self.submit_op_for_event(request_id, &thread_id, Op::DiffCheckpoints { from, to })
+            .await;
+    }
+
+    async fn thread_checkpoint_restore(
+        &self,
+        request_id: RequestId,
+        params: ThreadCheckpointRestoreParams,
+    ) {
+        let ThreadCheckpointRestoreParams {
+            thread_id,
+            id,
+            paths,
+        } = params;
+        self.submit_op_for_event(request_id, &thread_id, Op::RestoreCheckpoint { id, paths })
+            .await;
+    }
+
+    async fn thread_undo(&self, request_id: RequestId, params: ThreadUndoParams) {
+        self.submit_op_for_event(request_id, &params.thread_id, Op::Undo)
+            .await;
+    }
+
+    async fn mcp_list(&self, request_id: RequestId, params: McpListParams) {
+        if let Some(thread_id) = params.thread_id {
+            self.submit_op_for_event(request_id, &thread_id, Op::ListMcpTools)
+                .await;
+            return;
+        }
+
+        // Without a thread no servers are running, so only the configured
+        // servers and their auth status can be reported.
+        let auth_statuses = compute_auth_statuses(
+            self.config.mcp_servers.iter(),
+            self.config.mcp_oauth_credentials_store_mode,
+            self.config.credential_helper.as_deref(),
+        )
+        .await
+        .into_iter()
+        .map(|(name, entry)| (name, entry.auth_status))
+        .collect();
+        let listing = McpListToolsResponseEvent {
+            tools: HashMap::new(),
+            resources: HashMap::new(),
+            resource_templates: HashMap::new(),
+            auth_statuses,
+        };
+        let response = McpListResponse {
+            servers: mcp_servers_from_listing(listing),
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn prompts_list(&self, request_id: RequestId, params: PromptsListParams) {
+        if let Some(thread_id) = params.thread_id {
+            self.submit_op_for_event(request_id, &thread_id, Op::ListCustomPrompts)
+                .await;
+            return;
+        }
+
+        // Same lookup order as core: project prompts shadow `$LLMX_HOME/prompts`.
+        let mut dirs = self.config.project_prompt_dirs.clone();
+        dirs.extend(default_prompts_dir());
+        let response = PromptsListResponse {
+            prompts: discover_prompts_in_dirs(&dirs)
+                .await
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    /// Submits an op whose answer arrives as an event; the response is sent from
+    /// `apply_bespoke_event_handling` when core answers the submission.
+    async fn submit_op_for_event(&self, request_id: RequestId, thread_id: &str, op: Op) {
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
+
+        // Hold the lock across the submit so the listener cannot observe the
+        // response before the request is registered.
+        let mut map = self.pending_op_requests.lock().await;
+        match conversation.submit(op).await {
+            Ok(submission_id) => {
+                map.insert((conversation_id, submission_id), request_id);
+            }
+            Err(err) => {
+                drop(map);
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to submit request: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     async fn add_conversation_listener(
         &mut self,
         request_id: RequestId,
@@ -2265,7 +2570,7 @@ impl LlmxMessageProcessor {
             experimental_raw_events,
         } = params;
         match self
-            .attach_conversation_listener(conversation_id, experimental_raw_events)
+            .attach_conversation_listener(conversation_id, experimental_raw_events, ApiVersion::V1)
             .await
         {
             Ok(subscription_id) => {
@@ -2302,10 +2607,67 @@ impl LlmxMessageProcessor {
         }
     }
 
+    /// Subscribes this connection to the v2 notifications of a thread, unless
+    /// it already is.
+    async fn subscribe_to_thread(&mut self, conversation_id: ConversationId) {
+        if self.thread_subscriptions.contains_key(&conversation_id) {
+            return;
+        }
+        // Use the same behavior as the v1 API with experimental_raw_events=false.
+        match self
+            .attach_conversation_listener(conversation_id, false, ApiVersion::V2)
+            .await
+        {
+            Ok(subscription_id) => {
+                self.thread_subscriptions
+                    .insert(conversation_id, subscription_id);
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "failed to attach listener for conversation {}: {}",
+                    conversation_id,
+                    err.message
+                );
+            }
+        }
+    }
+
+    async fn thread_unsubscribe(&mut self, request_id: RequestId, params: ThreadUnsubscribeParams) {
+        let conversation_id = match ConversationId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("invalid thread id: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let Some(subscription_id) = self.thread_subscriptions.remove(&conversation_id) else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("not subscribed to thread: {conversation_id}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        };
+        if let Some(sender) = self.conversation_listeners.remove(&subscription_id) {
+            let _ = sender.send(());
+        }
+        self.outgoing
+            .send_response(request_id, ThreadUnsubscribeResponse {})
+            .await;
+    }
+
     async fn attach_conversation_listener(
         &mut self,
         conversation_id: ConversationId,
         experimental_raw_events: bool,
+        api_version: ApiVersion,
     ) -> Result<Uuid, JSONRPCErrorError> {
         let conversation = match self
             .conversation_manager
@@ -2327,65 +2689,42 @@ impl LlmxMessageProcessor {
         self.conversation_listeners
             .insert(subscription_id, cancel_tx);
 
-        let outgoing_for_task = self.outgoing.clone();
-        let pending_interrupts = self.pending_interrupts.clone();
+        let ThreadSubscription {
+            replay,
+            mut receiver,
+        } = self
+            .thread_hub
+            .subscribe(conversation_id, conversation.clone())
+            .await;
+        let forwarder = EventForwarder {
+            conversation_id,
+            conversation,
+            outgoing: self.outgoing.clone(),
+            pending_interrupts: self.pending_interrupts.clone(),
+            pending_op_requests: self.pending_op_requests.clone(),
+            thread_hub: self.thread_hub.clone(),
+            experimental_raw_events,
+            api_version,
+        };
         tokio::spawn(async move {
+            // Catch up on the running turn before forwarding live events.
+            for event in replay {
+                forwarder.forward(event).await;
+            }
             loop {
                 tokio::select! {
                     _ = &mut cancel_rx => {
-                        // User has unsubscribed, so exit this task.
+                        // User has unsubscribed or disconnected, so exit this task.
                         break;
                     }
-                    event = conversation.next_event() => {
-                        let event = match event {
-                            Ok(event) => event,
-                            Err(err) => {
-                                tracing::warn!("conversation.next_event() failed with: {err}");
-                                break;
-                            }
-                        };
-
-                        if let EventMsg::RawResponseItem(_) = &event.msg
-                            && !experimental_raw_events {
-                                continue;
-                            }
-
-                        // For now, we send a notification for every event,
-                        // JSON-serializing the `Event` as-is, but these should
-                        // be migrated to be variants of `ServerNotification`
-                        // instead.
-                        let method = format!("llmx/event/{}", event.msg);
-                        let mut params = match serde_json::to_value(event.clone()) {
-                            Ok(serde_json::Value::Object(map)) => map,
-                            Ok(_) => {
-                                error!("event did not serialize to an object");
-                                continue;
-                            }
-                            Err(err) => {
-                                error!("failed to serialize event: {err}");
-                                continue;
+                    event = receiver.recv() => {
+                        match event {
+                            Ok(event) => forwarder.forward(event).await,
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                warn!("listener for conversation {conversation_id} skipped {skipped} events");
                             }
-                        };
-                        params.insert(
-                            "conversationId".to_string(),
-                            conversation_id.to_string().into(),
-                        );
-
-                        outgoing_for_task
-                            .send_notification(OutgoingNotification {
-                                method,
-                                params: Some(params.into()),
-                            })
-                            .await;
-
-                        apply_bespoke_event_handling(
-                            event.clone(),
-                            conversation_id,
-                            conversation.clone(),
-                            outgoing_for_task.clone(),
-                            pending_interrupts.clone(),
-                        )
-                        .await;
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        }
                     }
                 }
             }
@@ -2526,15 +2865,197 @@ impl LlmxMessageProcessor {
     }
 }
 
+/// Forwards the events of one conversation to one connection.
+struct EventForwarder {
+    conversation_id: ConversationId,
+    conversation: Arc<LlmxConversation>,
+    outgoing: Arc<OutgoingMessageSender>,
+    pending_interrupts: PendingInterrupts,
+    pending_op_requests: PendingOpRequests,
+    thread_hub: Arc<ThreadHub>,
+    experimental_raw_events: bool,
+    api_version: ApiVersion,
+}
+
+impl EventForwarder {
+    async fn forward(&self, event: Event) {
+        if let EventMsg::RawResponseItem(_) = &event.msg
+            && !self.experimental_raw_events
+        {
+            return;
+        }
+
+        // For now, we send a notification for every event,
+        // JSON-serializing the `Event` as-is, but these should
+        // be migrated to be variants of `ServerNotification`
+        // instead.
+        let method = format!("llmx/event/{}", event.msg);
+        let mut params = match serde_json::to_value(event.clone()) {
+            Ok(serde_json::Value::Object(map)) => map,
+            Ok(_) => {
+                error!("event did not serialize to an object");
+                return;
+            }
+            Err(err) => {
+                error!("failed to serialize event: {err}");
+                return;
+            }
+        };
+        params.insert(
+            "conversationId".to_string(),
+            self.conversation_id.to_string().into(),
+        );
+
+        self.outgoing
+            .send_notification(OutgoingNotification {
+                method,
+                params: Some(params.into()),
+            })
+            .await;
+
+        apply_bespoke_event_handling(
+            event,
+            self.conversation_id,
+            self.conversation.clone(),
+            self.outgoing.clone(),
+            self.pending_interrupts.clone(),
+            self.pending_op_requests.clone(),
+            self.thread_hub.clone(),
+            self.api_version,
+        )
+        .await;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn apply_bespoke_event_handling(
     event: Event,
     conversation_id: ConversationId,
     conversation: Arc<LlmxConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
+    pending_op_requests: PendingOpRequests,
+    thread_hub: Arc<ThreadHub>,
+    api_version: ApiVersion,
 ) {
     let Event { id: event_id, msg } = event;
     match msg {
+        EventMsg::ListCheckpointsResponse(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = ThreadCheckpointListResponse {
+                    checkpoints: ev.checkpoints.into_iter().map(Into::into).collect(),
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::CheckpointDiffResponse(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = ThreadCheckpointDiffResponse {
+                    unified_diff: ev.unified_diff,
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::CheckpointRestored(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = ThreadCheckpointRestoreResponse {
+                    success: ev.success,
+                    message: ev.message,
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::UndoCompleted(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = ThreadUndoResponse {
+                    success: ev.success,
+                    message: ev.message,
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::McpListToolsResponse(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = McpListResponse {
+                    servers: mcp_servers_from_listing(ev),
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::ListCustomPromptsResponse(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let response = PromptsListResponse {
+                    prompts: ev.custom_prompts.into_iter().map(Into::into).collect(),
+                };
+                outgoing.send_response(request_id, response).await;
+            }
+        }
+        EventMsg::Error(ev) => {
+            if let Some(request_id) = pending_op_requests
+                .lock()
+                .await
+                .remove(&(conversation_id, event_id))
+            {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: ev.message,
+                    data: None,
+                };
+                outgoing.send_error(request_id, error).await;
+            }
+        }
+        EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
+            call_id,
+            changes,
+            reason,
+            grant_root,
+        }) if matches!(api_version, ApiVersion::V2) => {
+            let params = FileChangeApprovalParams {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_id.clone(),
+                item_id: call_id,
+                changes: file_update_changes(changes),
+                reason,
+                grant_root,
+            };
+            let rx = outgoing
+                .send_request(ServerRequestPayload::FileChangeApproval(params))
+                .await;
+            tokio::spawn(async move {
+                on_file_change_approval_response(
+                    event_id,
+                    rx,
+                    conversation_id,
+                    conversation,
+                    thread_hub,
+                )
+                .await;
+            });
+        }
         EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             changes,
@@ -2553,7 +3074,39 @@ async fn apply_bespoke_event_handling(
                 .await;
             // TODO(mbolin): Enforce a timeout so this task does not live indefinitely?
             tokio::spawn(async move {
-                on_patch_approval_response(event_id, rx, conversation).await;
+                on_patch_approval_response(event_id, rx, conversation_id, conversation, thread_hub)
+                    .await;
+            });
+        }
+        EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+            call_id,
+            command,
+            cwd,
+            reason,
+            risk,
+            parsed_cmd: _,
+        }) if matches!(api_version, ApiVersion::V2) => {
+            let params = CommandExecutionApprovalParams {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_id.clone(),
+                item_id: call_id,
+                command,
+                cwd,
+                reason,
+                risk: risk.map(Into::into),
+            };
+            let rx = outgoing
+                .send_request(ServerRequestPayload::CommandExecutionApproval(params))
+                .await;
+            tokio::spawn(async move {
+                on_command_execution_approval_response(
+                    event_id,
+                    rx,
+                    conversation_id,
+                    conversation,
+                    thread_hub,
+                )
+                .await;
             });
         }
         EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
@@ -2579,9 +3132,37 @@ async fn apply_bespoke_event_handling(
 
             // TODO(mbolin): Enforce a timeout so this task does not live indefinitely?
             tokio::spawn(async move {
-                on_exec_approval_response(event_id, rx, conversation).await;
+                on_exec_approval_response(event_id, rx, conversation_id, conversation, thread_hub)
+                    .await;
             });
         }
+        EventMsg::TurnDiff(TurnDiffEvent { unified_diff })
+            if matches!(api_version, ApiVersion::V2) =>
+        {
+            outgoing
+                .send_server_notification(ServerNotification::TurnDiffUpdated(
+                    TurnDiffUpdatedNotification {
+                        thread_id: conversation_id.to_string(),
+                        turn_id: event_id,
+                        diff: unified_diff,
+                    },
+                ))
+                .await;
+        }
+        EventMsg::PlanUpdate(UpdatePlanArgs { explanation, plan })
+            if matches!(api_version, ApiVersion::V2) =>
+        {
+            outgoing
+                .send_server_notification(ServerNotification::TurnPlanUpdated(
+                    TurnPlanUpdatedNotification {
+                        thread_id: conversation_id.to_string(),
+                        turn_id: event_id,
+                        explanation,
+                        plan: plan.into_iter().map(Into::into).collect(),
+                    },
+                ))
+                .await;
+        }
         EventMsg::TokenCount(token_count_event) => {
             if let Some(rate_limits) = token_count_event.rate_limits {
                 outgoing
@@ -2637,24 +3218,12 @@ async fn derive_config_from_params(
 async fn on_patch_approval_response(
     event_id: String,
     receiver: oneshot::Receiver<JsonRpcResult>,
+    conversation_id: ConversationId,
     llmx: Arc<LlmxConversation>,
+    thread_hub: Arc<ThreadHub>,
 ) {
-    let response = receiver.await;
-    let value = match response {
-        Ok(value) => value,
-        Err(err) => {
-            error!("request failed: {err:?}");
-            if let Err(submit_err) = llmx
-                .submit(Op::PatchApproval {
-                    id: event_id.clone(),
-                    decision: ReviewDecision::Denied,
-                })
-                .await
-            {
-                error!("failed to submit denied PatchApproval after request failure: {submit_err}");
-            }
-            return;
-        }
+    let Some(value) = await_approval_response(receiver).await else {
+        return;
     };
 
     let response =
@@ -2665,6 +3234,12 @@ async fn on_patch_approval_response(
             }
         });
 
+    if !thread_hub
+        .resolve_approval(conversation_id, &event_id)
+        .await
+    {
+        return;
+    }
     if let Err(err) = llmx
         .submit(Op::PatchApproval {
             id: event_id,
@@ -2679,15 +3254,12 @@ async fn on_patch_approval_response(
 async fn on_exec_approval_response(
     event_id: String,
     receiver: oneshot::Receiver<JsonRpcResult>,
+    conversation_id: ConversationId,
     conversation: Arc<LlmxConversation>,
+    thread_hub: Arc<ThreadHub>,
 ) {
-    let response = receiver.await;
-    let value = match response {
-        Ok(value) => value,
-        Err(err) => {
-            error!("request failed: {err:?}");
-            return;
-        }
+    let Some(value) = await_approval_response(receiver).await else {
+        return;
     };
 
     // Try to deserialize `value` and then make the appropriate call to `llmx`.
@@ -2701,6 +3273,12 @@ async fn on_exec_approval_response(
             }
         });
 
+    if !thread_hub
+        .resolve_approval(conversation_id, &event_id)
+        .await
+    {
+        return;
+    }
     if let Err(err) = conversation
         .submit(Op::ExecApproval {
             id: event_id,
@@ -2712,6 +3290,157 @@ async fn on_exec_approval_response(
     }
 }
 
+async fn on_file_change_approval_response(
+    event_id: String,
+    receiver: oneshot::Receiver<JsonRpcResult>,
+    conversation_id: ConversationId,
+    conversation: Arc<LlmxConversation>,
+    thread_hub: Arc<ThreadHub>,
+) {
+    let Some(value) = await_approval_response(receiver).await else {
+        return;
+    };
+    let decision = serde_json::from_value::<FileChangeApprovalResponse>(value)
+        .map(|response| response.decision.to_core())
+        .unwrap_or_else(|err| {
+            error!("failed to deserialize FileChangeApprovalResponse: {err}");
+            ReviewDecision::Denied
+        });
+
+    if !thread_hub
+        .resolve_approval(conversation_id, &event_id)
+        .await
+    {
+        return;
+    }
+    if let Err(err) = conversation
+        .submit(Op::PatchApproval {
+            id: event_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit PatchApproval: {err}");
+    }
+}
+
+async fn on_command_execution_approval_response(
+    event_id: String,
+    receiver: oneshot::Receiver<JsonRpcResult>,
+    conversation_id: ConversationId,
+    conversation: Arc<LlmxConversation>,
+    thread_hub: Arc<ThreadHub>,
+) {
+    let Some(value) = await_approval_response(receiver).await else {
+        return;
+    };
+    let decision = serde_json::from_value::<CommandExecutionApprovalResponse>(value)
+        .map(|response| response.decision.to_core())
+        .unwrap_or_else(|err| {
+            error!("failed to deserialize CommandExecutionApprovalResponse: {err}");
+            ReviewDecision::Denied
+        });
+
+    if !thread_hub
+        .resolve_approval(conversation_id, &event_id)
+        .await
+    {
+        return;
+    }
+    if let Err(err) = conversation
+        .submit(Op::ExecApproval {
+            id: event_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit ExecApproval: {err}");
+    }
+}
+
+/// Waits for the client's answer to an approval request. The receiver only
+/// fails when the connection went away, in which case the approval stays
+/// pending so a client that reattaches to the thread can answer it.
+async fn await_approval_response(
+    receiver: oneshot::Receiver<JsonRpcResult>,
+) -> Option<JsonRpcResult> {
+    match receiver.await {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("approval request was not answered: {err:?}");
+            None
+        }
+    }
+}
+
+/// Flattens core's patch into v2 `FileUpdateChange`s, ordered by path.
+fn file_update_changes(changes: HashMap<PathBuf, FileChange>) -> Vec<FileUpdateChange> {
+    let mut changes: Vec<FileUpdateChange> = changes
+        .into_iter()
+        .map(|(path, change)| {
+            let (kind, diff) = match change {
+                FileChange::Add { content } => (PatchChangeKind::Add, content),
+                FileChange::Delete { content } => (PatchChangeKind::Delete, content),
+                FileChange::Update { unified_diff, .. } => (PatchChangeKind::Update, unified_diff),
+            };
+            FileUpdateChange {
+                path: path.display().to_string(),
+                kind,
+                diff,
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Groups core's flat MCP listing by server. Tools are keyed
+/// `mcp__<server>__<tool>`, so the prefix is stripped again.
+fn mcp_servers_from_listing(listing: McpListToolsResponseEvent) -> Vec<McpServer> {
+    let McpListToolsResponseEvent {
+        tools,
+        mut resources,
+        mut resource_templates,
+        auth_statuses,
+    } = listing;
+
+    let mut names: Vec<String> = auth_statuses
+        .keys()
+        .chain(resources.keys())
+        .chain(resource_templates.keys())
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let prefix = format!("mcp__{name}__");
+            let mut server_tools: Vec<_> = tools
+                .iter()
+                .filter_map(|(qualified, tool)| {
+                    qualified
+                        .strip_prefix(&prefix)
+                        .map(|short| (short.to_string(), tool.clone()))
+                })
+                .collect();
+            server_tools.sort_by(|(a, _), (b, _)| a.cmp(b));
+            McpServer {
+                auth_status: auth_statuses
+                    .get(&name)
+                    .copied()
+                    .unwrap_or(CoreMcpAuthStatus::Unsupported)
+                    .into(),
+                tools: server_tools.into_iter().map(|(_, tool)| tool).collect(),
+                resources: resources.remove(&name).unwrap_or_default(),
+                resource_templates: resource_templates.remove(&name).unwrap_or_default(),
+                name,
+            }
+        })
+        .collect()
+}
+
 async fn read_summary_from_rollout(
     path: &Path,
     fallback_provider: &str,
@@ -2963,4 +3692,45 @@ mod tests {
         assert_eq!(summary, expected);
         Ok(())
     }
+
+    #[test]
+    fn mcp_listing_is_grouped_by_server() -> Result<()> {
+        let listing: McpListToolsResponseEvent = serde_json::from_value(json!({
+            "tools": {
+                "mcp__docs__search": { "name": "search", "inputSchema": { "type": "object" } },
+                "mcp__docs__fetch": { "name": "fetch", "inputSchema": { "type": "object" } },
+                "mcp__github__issues": { "name": "issues", "inputSchema": { "type": "object" } },
+            },
+            "resources": {},
+            "resource_templates": {},
+            "auth_statuses": { "docs": "unsupported", "github": "o_auth" },
+        }))?;
+
+        let servers = serde_json::to_value(mcp_servers_from_listing(listing))?;
+        assert_eq!(
+            servers,
+            json!([
+                {
+                    "name": "docs",
+                    "authStatus": "unsupported",
+                    "tools": [
+                        { "name": "fetch", "inputSchema": { "type": "object" } },
+                        { "name": "search", "inputSchema": { "type": "object" } },
+                    ],
+                    "resources": [],
+                    "resourceTemplates": [],
+                },
+                {
+                    "name": "github",
+                    "authStatus": "oauth",
+                    "tools": [
+                        { "name": "issues", "inputSchema": { "type": "object" } },
+                    ],
+                    "resources": [],
+                    "resourceTemplates": [],
+                },
+            ])
+        );
+        Ok(())
+    }
 }
@@ -1,10 +1,24 @@
+use clap::Parser;
+use llmx_app_server::ListenArgs;
 use llmx_app_server::run_main;
 use llmx_arg0::arg0_dispatch_or_else;
 use llmx_common::CliConfigOverrides;
 
+#[derive(Debug, Parser)]
+struct AppServerCli {
+    #[clap(flatten)]
+    listen: ListenArgs,
+}
+
 fn main() -> anyhow::Result<()> {
     arg0_dispatch_or_else(|llmx_linux_sandbox_exe| async move {
-        run_main(llmx_linux_sandbox_exe, CliConfigOverrides::default()).await?;
+        let cli = AppServerCli::parse();
+        run_main(
+            llmx_linux_sandbox_exe,
+            CliConfigOverrides::default(),
+            cli.listen,
+        )
+        .await?;
         Ok(())
     })
 }
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::llmx_message_processor::LlmxMessageProcessor;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::thread_hub::ThreadHub;
 use llmx_app_server_protocol::ClientInfo;
 use llmx_app_server_protocol::ClientRequest;
 use llmx_app_server_protocol::InitializeResponse;
@@ -21,34 +22,68 @@ use llmx_feedback::LlmxFeedback;
 use llmx_protocol::protocol::SessionSource;
 use std::sync::Arc;
 
-pub(crate) struct MessageProcessor {
-    outgoing: Arc<OutgoingMessageSender>,
-    llmx_message_processor: LlmxMessageProcessor,
-    initialized: bool,
+/// State shared by every connection of the app server, so all clients see
+/// the same loaded threads.
+#[derive(Clone)]
+pub(crate) struct SharedState {
+    auth_manager: Arc<AuthManager>,
+    conversation_manager: Arc<ConversationManager>,
+    thread_hub: Arc<ThreadHub>,
+    llmx_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+    feedback: LlmxFeedback,
 }
 
-impl MessageProcessor {
-    /// Create a new `MessageProcessor`, retaining a handle to the outgoing
-    /// `Sender` so handlers can enqueue messages to be written to stdout.
+impl SharedState {
     pub(crate) fn new(
-        outgoing: OutgoingMessageSender,
         llmx_linux_sandbox_exe: Option<PathBuf>,
         config: Arc<Config>,
         feedback: LlmxFeedback,
     ) -> Self {
-        let outgoing = Arc::new(outgoing);
         let auth_manager = AuthManager::shared(
             config.llmx_home.clone(),
             false,
             config.cli_auth_credentials_store_mode,
+            config.credential_helper.clone(),
         );
         let conversation_manager = Arc::new(ConversationManager::new(
             auth_manager.clone(),
             SessionSource::VSCode,
         ));
+        Self {
+            auth_manager,
+            conversation_manager,
+            thread_hub: Arc::new(ThreadHub::default()),
+            llmx_linux_sandbox_exe,
+            config,
+            feedback,
+        }
+    }
+}
+
+pub(crate) struct MessageProcessor {
+    outgoing: Arc<OutgoingMessageSender>,
+    llmx_message_processor: LlmxMessageProcessor,
+    initialized: bool,
+}
+
+impl MessageProcessor {
+    /// Create a new `MessageProcessor` for one connection, retaining a handle
+    /// to the outgoing `Sender` so handlers can enqueue messages for the client.
+    pub(crate) fn new(outgoing: OutgoingMessageSender, shared: SharedState) -> Self {
+        let outgoing = Arc::new(outgoing);
+        let SharedState {
+            auth_manager,
+            conversation_manager,
+            thread_hub,
+            llmx_linux_sandbox_exe,
+            config,
+            feedback,
+        } = shared;
         let llmx_message_processor = LlmxMessageProcessor::new(
             auth_manager,
             conversation_manager,
+            thread_hub,
             outgoing.clone(),
             llmx_linux_sandbox_exe,
             config,
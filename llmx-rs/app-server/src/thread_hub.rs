@@ -0,0 +1,295 @@
+//! Fans the events of each loaded conversation out to every connection
+//! subscribed to it. Core only supports a single consumer of
+//! `LlmxConversation::next_event()`, so one pump task per conversation reads
+//! the events and republishes them on a broadcast channel.
+//!
+//! The pump also remembers the events of the running turn, so a client that
+//! reconnects (or subscribes late) can catch up before it receives live
+//! events, including approval requests nobody has answered yet.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+
+use llmx_core::LlmxConversation;
+use llmx_core::protocol::Event;
+use llmx_core::protocol::EventMsg;
+use llmx_protocol::ConversationId;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Events buffered per conversation for subscribers that fall behind.
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+type ThreadChannels = Arc<Mutex<HashMap<ConversationId, Arc<ThreadChannel>>>>;
+
+/// Shared by every connection of the app server.
+#[derive(Default)]
+pub(crate) struct ThreadHub {
+    threads: ThreadChannels,
+}
+
+/// What a new subscriber receives: the events of the running turn so far,
+/// followed by everything published after it subscribed.
+pub(crate) struct ThreadSubscription {
+    pub replay: Vec<Event>,
+    pub receiver: broadcast::Receiver<Event>,
+}
+
+struct ThreadChannel {
+    sender: broadcast::Sender<Event>,
+    turn: StdMutex<TurnBuffer>,
+}
+
+impl ThreadHub {
+    /// Subscribes to the events of `conversation`, starting its pump if this
+    /// is the first subscriber.
+    pub(crate) async fn subscribe(
+        &self,
+        conversation_id: ConversationId,
+        conversation: Arc<LlmxConversation>,
+    ) -> ThreadSubscription {
+        let channel = {
+            let mut threads = self.threads.lock().await;
+            threads
+                .entry(conversation_id)
+                .or_insert_with(|| spawn_pump(conversation_id, conversation, self.threads.clone()))
+                .clone()
+        };
+        channel.subscribe()
+    }
+
+    /// Marks the approval request `event_id` as answered. Returns `false`
+    /// when another connection already answered it, in which case the
+    /// decision must not be submitted again.
+    pub(crate) async fn resolve_approval(
+        &self,
+        conversation_id: ConversationId,
+        event_id: &str,
+    ) -> bool {
+        let threads = self.threads.lock().await;
+        match threads.get(&conversation_id) {
+            Some(channel) => channel.lock_turn().resolve_approval(event_id),
+            None => true,
+        }
+    }
+}
+
+impl ThreadChannel {
+    fn lock_turn(&self) -> std::sync::MutexGuard<'_, TurnBuffer> {
+        self.turn
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn publish(&self, event: Event) {
+        // Record and send under the same lock so a concurrent subscriber sees
+        // each event exactly once: either in its replay or on its receiver.
+        let mut turn = self.lock_turn();
+        turn.record(&event);
+        let _ = self.sender.send(event);
+    }
+
+    fn subscribe(&self) -> ThreadSubscription {
+        let turn = self.lock_turn();
+        ThreadSubscription {
+            replay: turn.replay(),
+            receiver: self.sender.subscribe(),
+        }
+    }
+}
+
+fn spawn_pump(
+    conversation_id: ConversationId,
+    conversation: Arc<LlmxConversation>,
+    threads: ThreadChannels,
+) -> Arc<ThreadChannel> {
+    let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+    let channel = Arc::new(ThreadChannel {
+        sender,
+        turn: StdMutex::new(TurnBuffer::default()),
+    });
+
+    let channel_for_task = channel.clone();
+    tokio::spawn(async move {
+        loop {
+            match conversation.next_event().await {
+                Ok(event) => {
+                    let shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+                    channel_for_task.publish(event);
+                    if shutdown {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    warn!("conversation.next_event() failed with: {err}");
+                    break;
+                }
+            }
+        }
+
+        // Dropping the channel closes every subscriber's receiver. Leave the
+        // map alone if the conversation was replaced in the meantime.
+        let mut threads = threads.lock().await;
+        if threads
+            .get(&conversation_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &channel_for_task))
+        {
+            threads.remove(&conversation_id);
+        }
+    });
+
+    channel
+}
+
+/// Events of the running turn, kept until the turn completes or aborts.
+#[derive(Default)]
+struct TurnBuffer {
+    running: bool,
+    events: Vec<Event>,
+    /// Ids of the approval requests of this turn still awaiting a decision.
+    pending_approvals: HashSet<String>,
+}
+
+impl TurnBuffer {
+    fn record(&mut self, event: &Event) {
+        match &event.msg {
+            EventMsg::TaskStarted(_) => {
+                self.running = true;
+                self.events.clear();
+                self.pending_approvals.clear();
+            }
+            EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_) => {
+                self.running = false;
+                self.events.clear();
+                self.pending_approvals.clear();
+                return;
+            }
+            EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_) => {
+                self.pending_approvals.insert(event.id.clone());
+            }
+            _ => {}
+        }
+
+        // Streaming deltas are superseded by the completed items, so a
+        // reattaching client does not need them.
+        if self.running && !is_delta(&event.msg) {
+            self.events.push(event.clone());
+        }
+    }
+
+    fn replay(&self) -> Vec<Event> {
+        self.events
+            .iter()
+            .filter(|event| !is_approval(&event.msg) || self.pending_approvals.contains(&event.id))
+            .cloned()
+            .collect()
+    }
+
+    fn resolve_approval(&mut self, event_id: &str) -> bool {
+        // Approvals from a finished turn are no longer tracked; core ignores
+        // stale decisions, so let them through.
+        if !self.running {
+            return true;
+        }
+        self.pending_approvals.remove(event_id)
+    }
+}
+
+fn is_approval(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_)
+    )
+}
+
+fn is_delta(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::AgentMessageDelta(_)
+            | EventMsg::AgentReasoningDelta(_)
+            | EventMsg::AgentReasoningRawContentDelta(_)
+            | EventMsg::ExecCommandOutputDelta(_)
+            | EventMsg::AgentMessageContentDelta(_)
+            | EventMsg::ReasoningContentDelta(_)
+            | EventMsg::ReasoningRawContentDelta(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_core::protocol::AgentMessageDeltaEvent;
+    use llmx_core::protocol::AgentMessageEvent;
+    use llmx_core::protocol::ExecApprovalRequestEvent;
+    use llmx_core::protocol::TaskCompleteEvent;
+    use llmx_core::protocol::TaskStartedEvent;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn event(id: &str, msg: EventMsg) -> Event {
+        Event {
+            id: id.to_string(),
+            msg,
+        }
+    }
+
+    fn approval(id: &str) -> Event {
+        event(
+            id,
+            EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: format!("call-{id}"),
+                command: vec!["ls".to_string()],
+                cwd: PathBuf::from("/tmp"),
+                reason: None,
+                risk: None,
+                parsed_cmd: Vec::new(),
+            }),
+        )
+    }
+
+    fn ids(events: &[Event]) -> Vec<&str> {
+        events.iter().map(|event| event.id.as_str()).collect()
+    }
+
+    #[test]
+    fn replays_running_turn_without_answered_approvals() {
+        let mut turn = TurnBuffer::default();
+        turn.record(&event(
+            "0",
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "before the turn".to_string(),
+            }),
+        ));
+        turn.record(&event(
+            "1",
+            EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+            }),
+        ));
+        turn.record(&event(
+            "2",
+            EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: "hel".to_string(),
+            }),
+        ));
+        turn.record(&approval("3"));
+        turn.record(&approval("4"));
+        assert_eq!(ids(&turn.replay()), vec!["1", "3", "4"]);
+
+        assert!(turn.resolve_approval("3"));
+        assert!(!turn.resolve_approval("3"));
+        assert_eq!(ids(&turn.replay()), vec!["1", "4"]);
+
+        turn.record(&event(
+            "5",
+            EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: None,
+            }),
+        ));
+        assert_eq!(ids(&turn.replay()), Vec::<&str>::new());
+        assert!(turn.resolve_approval("4"));
+    }
+}
@@ -0,0 +1,501 @@
+//! Network transports for the app server. By default the server speaks
+//! newline-delimited JSON-RPC over stdio to a single client; `--listen`
+//! serves any number of clients instead:
+//!
+//! - `ws://HOST:PORT` — WebSocket, one JSON-RPC message per text frame,
+//!   authenticated with a bearer token.
+//! - `wss://HOST:PORT` — the same over TLS, authenticated with a bearer token
+//!   and/or client certificates (mutual TLS).
+//! - `unix:///PATH` — newline-delimited JSON-RPC over a Unix domain socket
+//!   (compatible with `llmx stdio-to-uds`), restricted to the current user by
+//!   the socket's file permissions.
+
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures::SinkExt;
+use futures::StreamExt;
+use llmx_app_server_protocol::JSONRPCMessage;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::RootCertStore;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::pki_types::PrivateKeyDer;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::server::ErrorResponse;
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+use tokio_tungstenite::tungstenite::handshake::server::Response;
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
+use tracing::debug;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::CHANNEL_CAPACITY;
+use crate::message_processor::SharedState;
+use crate::outgoing_message::OutgoingMessage;
+use crate::run_processor;
+use crate::serialize_outgoing;
+
+/// Environment variable holding the bearer token when `--auth-token-file` is
+/// not given.
+pub const AUTH_TOKEN_ENV_VAR: &str = "LLMX_APP_SERVER_TOKEN";
+
+/// Query parameter accepted in place of the `Authorization` header, for
+/// browsers that cannot set headers on WebSocket requests.
+const ACCESS_TOKEN_QUERY_PARAM: &str = "access_token";
+
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ListenArgs {
+    /// Serve clients on `ws://HOST:PORT`, `wss://HOST:PORT` or
+    /// `unix:///PATH` instead of stdio.
+    #[arg(long = "listen", value_name = "URL", value_parser = ListenAddress::parse)]
+    pub listen: Option<ListenAddress>,
+
+    /// File containing the bearer token WebSocket clients must send. Defaults
+    /// to the LLMX_APP_SERVER_TOKEN environment variable.
+    #[arg(long = "auth-token-file", value_name = "FILE")]
+    pub auth_token_file: Option<PathBuf>,
+
+    /// PEM certificate chain served by a `wss://` listener.
+    #[arg(long = "tls-cert", value_name = "FILE", requires = "tls_key")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key for `--tls-cert`.
+    #[arg(long = "tls-key", value_name = "FILE", requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// PEM bundle of CAs; clients of a `wss://` listener must present a
+    /// certificate signed by one of them (mutual TLS).
+    #[arg(long = "tls-client-ca", value_name = "FILE")]
+    pub tls_client_ca: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListenAddress {
+    /// `HOST:PORT` to bind, served over TLS when `tls` is set.
+    WebSocket {
+        addr: String,
+        tls: bool,
+    },
+    Unix(PathBuf),
+}
+
+impl ListenAddress {
+    pub fn parse(url: &str) -> Result<Self, String> {
+        if let Some(path) = url.strip_prefix("unix://") {
+            if !path.starts_with('/') {
+                return Err(format!(
+                    "unix socket path must be absolute (unix:///PATH): {url}"
+                ));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+
+        let (rest, tls) = if let Some(rest) = url.strip_prefix("wss://") {
+            (rest, true)
+        } else if let Some(rest) = url.strip_prefix("ws://") {
+            (rest, false)
+        } else {
+            return Err(format!(
+                "unsupported listen URL `{url}`: expected ws://, wss:// or unix://"
+            ));
+        };
+        let addr = rest.strip_suffix('/').unwrap_or(rest);
+        let has_port = addr
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+        if !has_port || addr.contains('/') {
+            return Err(format!("expected HOST:PORT in listen URL `{url}`"));
+        }
+        Ok(Self::WebSocket {
+            addr: addr.to_string(),
+            tls,
+        })
+    }
+}
+
+/// Serves clients on `address` until the process is interrupted.
+pub(crate) async fn serve(
+    address: ListenAddress,
+    args: &ListenArgs,
+    shared: SharedState,
+) -> IoResult<()> {
+    match address {
+        ListenAddress::WebSocket { addr, tls } => {
+            if !tls && (args.tls_cert.is_some() || args.tls_client_ca.is_some()) {
+                return Err(IoError::new(
+                    ErrorKind::InvalidInput,
+                    "TLS options require a wss:// listen URL",
+                ));
+            }
+            let token = load_token(args.auth_token_file.as_deref())?;
+            let acceptor = if tls { Some(tls_acceptor(args)?) } else { None };
+            let mutual_tls = acceptor.is_some() && args.tls_client_ca.is_some();
+            if token.is_none() && !mutual_tls {
+                return Err(IoError::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "WebSocket listeners require authentication: pass --auth-token-file or set \
+                         {AUTH_TOKEN_ENV_VAR} (or use wss:// with --tls-client-ca)"
+                    ),
+                ));
+            }
+            let listener = TcpListener::bind(&addr).await?;
+            let local_addr = listener.local_addr()?;
+            info!(
+                "app server listening on {}://{local_addr}",
+                if tls { "wss" } else { "ws" },
+            );
+            if !tls && !local_addr.ip().is_loopback() {
+                warn!(
+                    "bearer tokens sent to ws://{local_addr} travel in plain text; use wss:// when \
+                     listening on a non-loopback address"
+                );
+            }
+            serve_tcp(listener, acceptor, token.map(Arc::from), shared).await
+        }
+        ListenAddress::Unix(path) => {
+            if args.auth_token_file.is_some() || args.tls_cert.is_some() {
+                warn!(
+                    "unix sockets are authenticated by file permissions; ignoring token and TLS options"
+                );
+            }
+            serve_unix(&path, shared).await
+        }
+    }
+}
+
+async fn serve_tcp(
+    listener: TcpListener,
+    acceptor: Option<TlsAcceptor>,
+    token: Option<Arc<str>>,
+    shared: SharedState,
+) -> IoResult<()> {
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        debug!("accepted connection from {peer}");
+        let acceptor = acceptor.clone();
+        let token = token.clone();
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            match acceptor {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(stream) => serve_websocket(stream, token, shared).await,
+                    Err(err) => warn!("TLS handshake with {peer} failed: {err}"),
+                },
+                None => serve_websocket(stream, token, shared).await,
+            }
+            debug!("connection from {peer} closed");
+        });
+    }
+}
+
+async fn serve_websocket<S>(stream: S, token: Option<Arc<str>>, shared: SharedState)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let callback = |request: &Request, response: Response| {
+        if token
+            .as_deref()
+            .is_none_or(|token| request_is_authorized(request, token))
+        {
+            Ok(response)
+        } else {
+            let mut response = ErrorResponse::new(Some("unauthorized".to_string()));
+            *response.status_mut() = StatusCode::UNAUTHORIZED;
+            Err(response)
+        }
+    };
+    let websocket = match tokio_tungstenite::accept_hdr_async(stream, callback).await {
+        Ok(websocket) => websocket,
+        Err(err) => {
+            warn!("WebSocket handshake failed: {err}");
+            return;
+        }
+    };
+    let (mut sink, mut source) = websocket.split();
+
+    let (incoming_tx, incoming_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
+    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<OutgoingMessage>();
+    let processor_handle = tokio::spawn(run_processor(incoming_rx, outgoing_tx, shared));
+
+    tokio::spawn(async move {
+        while let Some(outgoing_message) = outgoing_rx.recv().await {
+            let Some(json) = serialize_outgoing(outgoing_message) else {
+                continue;
+            };
+            if let Err(err) = sink.send(Message::text(json)).await {
+                debug!("failed to write to WebSocket: {err}");
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    while let Some(frame) = source.next().await {
+        let msg = match frame {
+            Ok(Message::Text(text)) => serde_json::from_str::<JSONRPCMessage>(text.as_str()),
+            Ok(Message::Binary(bytes)) => serde_json::from_slice::<JSONRPCMessage>(&bytes),
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(err) => {
+                debug!("WebSocket read failed: {err}");
+                break;
+            }
+        };
+        match msg {
+            Ok(msg) => {
+                if incoming_tx.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            Err(err) => error!("Failed to deserialize JSONRPCMessage: {err}"),
+        }
+    }
+
+    // Closing the incoming channel stops the processor, which in turn drops
+    // this connection's thread subscriptions. Running turns keep going.
+    drop(incoming_tx);
+    let _ = processor_handle.await;
+}
+
+#[cfg(unix)]
+async fn serve_unix(path: &Path, shared: SharedState) -> IoResult<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::net::UnixListener;
+
+    // Replace a socket left behind by a previous run, but never clobber
+    // anything else.
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(IoError::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    // Create the socket as 0600 rather than restricting it after `bind`, so
+    // there is no window in which other users can connect.
+    // SAFETY: umask only swaps the process file-creation mask.
+    let previous_umask = unsafe { libc::umask(0o177) };
+    let listener = UnixListener::bind(path);
+    // SAFETY: restores the mask read above.
+    unsafe { libc::umask(previous_umask) };
+    let listener = listener?;
+    info!("app server listening on unix://{}", path.display());
+
+    let result = loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(err) => break Err(err),
+            },
+            _ = tokio::signal::ctrl_c() => break Ok(()),
+        };
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            let (reader, writer) = stream.into_split();
+            crate::serve_lines(reader, writer, shared).await;
+        });
+    };
+    let _ = std::fs::remove_file(path);
+    result
+}
+
+#[cfg(not(unix))]
+async fn serve_unix(_path: &Path, _shared: SharedState) -> IoResult<()> {
+    Err(IoError::new(
+        ErrorKind::Unsupported,
+        "unix:// listeners are not supported on this platform",
+    ))
+}
+
+fn load_token(token_file: Option<&Path>) -> IoResult<Option<String>> {
+    let token = match token_file {
+        Some(path) => std::fs::read_to_string(path).map_err(|err| {
+            IoError::new(
+                err.kind(),
+                format!("failed to read auth token from {}: {err}", path.display()),
+            )
+        })?,
+        None => std::env::var(AUTH_TOKEN_ENV_VAR).unwrap_or_default(),
+    };
+    let token = token.trim();
+    Ok((!token.is_empty()).then(|| token.to_string()))
+}
+
+fn request_is_authorized(request: &Request, token: &str) -> bool {
+    let from_header = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    // Browsers percent-encode the token when building the URL.
+    let from_query = request.uri().query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == ACCESS_TOKEN_QUERY_PARAM)
+            .map(|(_, value)| value.into_owned())
+    });
+    from_header
+        .into_iter()
+        .chain(from_query)
+        .any(|candidate| constant_time_eq(candidate.trim().as_bytes(), token.as_bytes()))
+}
+
+/// Compares secrets without leaking where they first differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn tls_acceptor(args: &ListenArgs) -> IoResult<TlsAcceptor> {
+    let (Some(cert_path), Some(key_path)) = (&args.tls_cert, &args.tls_key) else {
+        return Err(IoError::new(
+            ErrorKind::InvalidInput,
+            "wss:// listeners require --tls-cert and --tls-key",
+        ));
+    };
+    let certs = read_certificates(cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|err| invalid_pem(key_path, &err.to_string()))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?;
+    let builder = match &args.tls_client_ca {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certificates(ca_path)? {
+                roots
+                    .add(cert)
+                    .map_err(|err| invalid_pem(ca_path, &err.to_string()))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(tls_error)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder.with_single_cert(certs, key).map_err(tls_error)?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+fn read_certificates(path: &Path) -> IoResult<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| invalid_pem(path, &err.to_string()))?;
+    if certs.is_empty() {
+        return Err(invalid_pem(path, "no certificates found"));
+    }
+    Ok(certs)
+}
+
+fn invalid_pem(path: &Path, err: &str) -> IoError {
+    IoError::new(
+        ErrorKind::InvalidData,
+        format!("failed to load {}: {err}", path.display()),
+    )
+}
+
+fn tls_error(err: impl std::fmt::Display) -> IoError {
+    IoError::new(ErrorKind::InvalidInput, format!("invalid TLS setup: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_listen_urls() {
+        assert_eq!(
+            ListenAddress::parse("ws://0.0.0.0:4500"),
+            Ok(ListenAddress::WebSocket {
+                addr: "0.0.0.0:4500".to_string(),
+                tls: false,
+            })
+        );
+        assert_eq!(
+            ListenAddress::parse("wss://[::]:4500/"),
+            Ok(ListenAddress::WebSocket {
+                addr: "[::]:4500".to_string(),
+                tls: true,
+            })
+        );
+        assert_eq!(
+            ListenAddress::parse("unix:///tmp/llmx.sock"),
+            Ok(ListenAddress::Unix(PathBuf::from("/tmp/llmx.sock")))
+        );
+        assert!(ListenAddress::parse("unix://relative.sock").is_err());
+        assert!(ListenAddress::parse("ws://localhost").is_err());
+        assert!(ListenAddress::parse("ws://localhost:4500/path").is_err());
+        assert!(ListenAddress::parse("http://localhost:4500").is_err());
+    }
+
+    #[test]
+    fn checks_bearer_token_from_header_or_query() {
+        let request = |uri: &str, authorization: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(value) = authorization {
+                builder = builder.header(AUTHORIZATION, value);
+            }
+            builder.body(()).expect("request")
+        };
+
+        assert!(request_is_authorized(
+            &request("/", Some("Bearer s3cret")),
+            "s3cret"
+        ));
+        assert!(request_is_authorized(
+            &request("/?client=web&access_token=s3cret", None),
+            "s3cret"
+        ));
+        assert!(request_is_authorized(
+            &request("/?access_token=s3%2Bcr%3Det%2F", None),
+            "s3+cr=et/"
+        ));
+        assert!(!request_is_authorized(
+            &request("/?access_token=s3cret_suffix", None),
+            "s3cret"
+        ));
+        assert!(!request_is_authorized(
+            &request("/", Some("Bearer wrong")),
+            "s3cret"
+        ));
+        assert!(!request_is_authorized(
+            &request("/", Some("Basic s3cret")),
+            "s3cret"
+        ));
+        assert!(!request_is_authorized(&request("/", None), "s3cret"));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_whole_input() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"token2"));
+    }
+}
@@ -131,5 +131,5 @@ pub fn write_chatgpt_auth(
         last_refresh,
     };
 
-    save_auth(llmx_home, &auth, cli_auth_credentials_store_mode).context("write auth.json")
+    save_auth(llmx_home, &auth, cli_auth_credentials_store_mode, None).context("write auth.json")
 }
@@ -18,6 +18,8 @@ use llmx_app_server_protocol::CancelLoginAccountParams;
 use llmx_app_server_protocol::CancelLoginChatGptParams;
 use llmx_app_server_protocol::ClientInfo;
 use llmx_app_server_protocol::ClientNotification;
+use llmx_app_server_protocol::ConfigReadParams;
+use llmx_app_server_protocol::ConfigWriteParams;
 use llmx_app_server_protocol::FeedbackUploadParams;
 use llmx_app_server_protocol::GetAccountParams;
 use llmx_app_server_protocol::GetAuthStatusParams;
@@ -30,8 +32,10 @@ use llmx_app_server_protocol::JSONRPCRequest;
 use llmx_app_server_protocol::JSONRPCResponse;
 use llmx_app_server_protocol::ListConversationsParams;
 use llmx_app_server_protocol::LoginApiKeyParams;
+use llmx_app_server_protocol::McpListParams;
 use llmx_app_server_protocol::ModelListParams;
 use llmx_app_server_protocol::NewConversationParams;
+use llmx_app_server_protocol::PromptsListParams;
 use llmx_app_server_protocol::RemoveConversationListenerParams;
 use llmx_app_server_protocol::RequestId;
 use llmx_app_server_protocol::ResumeConversationParams;
@@ -43,6 +47,7 @@ use llmx_app_server_protocol::ThreadArchiveParams;
 use llmx_app_server_protocol::ThreadListParams;
 use llmx_app_server_protocol::ThreadResumeParams;
 use llmx_app_server_protocol::ThreadStartParams;
+use llmx_app_server_protocol::ThreadUndoParams;
 use llmx_app_server_protocol::TurnInterruptParams;
 use llmx_app_server_protocol::TurnStartParams;
 use std::process::Command as StdCommand;
@@ -327,6 +332,48 @@ impl McpProcess {
         self.send_request("thread/list", params).await
     }
 
+    /// Send a `thread/undo` JSON-RPC request.
+    pub async fn send_thread_undo_request(
+        &mut self,
+        params: ThreadUndoParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/undo", params).await
+    }
+
+    /// Send a `config/read` JSON-RPC request.
+    pub async fn send_config_read_request(
+        &mut self,
+        params: ConfigReadParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("config/read", params).await
+    }
+
+    /// Send a `config/write` JSON-RPC request.
+    pub async fn send_config_write_request(
+        &mut self,
+        params: ConfigWriteParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("config/write", params).await
+    }
+
+    /// Send a `prompts/list` JSON-RPC request.
+    pub async fn send_prompts_list_request(
+        &mut self,
+        params: PromptsListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("prompts/list", params).await
+    }
+
+    /// Send a `mcp/list` JSON-RPC request.
+    pub async fn send_mcp_list_request(&mut self, params: McpListParams) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("mcp/list", params).await
+    }
+
     /// Send a `model/list` JSON-RPC request.
     pub async fn send_list_models_request(
         &mut self,
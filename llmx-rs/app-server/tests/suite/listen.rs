@@ -0,0 +1,211 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use llmx_app_server::AUTH_TOKEN_ENV_VAR;
+use tempfile::TempDir;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn app_server_command(llmx_home: &Path, listen: &str) -> Result<Command> {
+    let program = std::process::Command::cargo_bin("llmx-app-server")
+        .context("should find binary for llmx-app-server")?
+        .get_program()
+        .to_owned();
+    let mut cmd = Command::new(program);
+    cmd.arg("--listen")
+        .arg(listen)
+        .env("LLMX_HOME", llmx_home)
+        .env_remove(AUTH_TOKEN_ENV_VAR)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    Ok(cmd)
+}
+
+#[tokio::test]
+async fn websocket_listener_requires_authentication() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    let output = timeout(
+        DEFAULT_READ_TIMEOUT,
+        app_server_command(llmx_home.path(), "ws://127.0.0.1:0")?.output(),
+    )
+    .await??;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("WebSocket listeners require authentication"),
+        "unexpected stderr: {stderr}"
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+mod unix_socket {
+    use super::*;
+    use app_test_support::create_mock_chat_completions_server;
+    use app_test_support::to_response;
+    use llmx_app_server_protocol::JSONRPCMessage;
+    use llmx_app_server_protocol::JSONRPCResponse;
+    use llmx_app_server_protocol::RequestId;
+    use llmx_app_server_protocol::ThreadResumeResponse;
+    use llmx_app_server_protocol::ThreadStartResponse;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::io::BufReader;
+    use tokio::io::Lines;
+    use tokio::net::UnixStream;
+    use tokio::net::unix::OwnedReadHalf;
+    use tokio::net::unix::OwnedWriteHalf;
+    use tokio::process::Child;
+
+    /// Minimal newline-delimited JSON-RPC client for one socket connection.
+    struct SocketClient {
+        lines: Lines<BufReader<OwnedReadHalf>>,
+        writer: OwnedWriteHalf,
+        next_request_id: i64,
+    }
+
+    impl SocketClient {
+        async fn connect(path: &Path) -> Result<Self> {
+            let (reader, writer) = UnixStream::connect(path).await?.into_split();
+            let mut client = Self {
+                lines: BufReader::new(reader).lines(),
+                writer,
+                next_request_id: 0,
+            };
+            client
+                .request(
+                    "initialize",
+                    json!({ "clientInfo": { "name": "listen-test", "version": "0.0.0" } }),
+                )
+                .await?;
+            Ok(client)
+        }
+
+        /// Sends a request and waits for its response (or error), skipping
+        /// notifications.
+        async fn request(&mut self, method: &str, params: Value) -> Result<JSONRPCMessage> {
+            let id = self.next_request_id;
+            self.next_request_id += 1;
+            let mut line = serde_json::to_string(&json!({
+                "id": id,
+                "method": method,
+                "params": params,
+            }))?;
+            line.push('\n');
+            self.writer.write_all(line.as_bytes()).await?;
+
+            loop {
+                let line = timeout(DEFAULT_READ_TIMEOUT, self.lines.next_line())
+                    .await??
+                    .context("connection closed")?;
+                let message: JSONRPCMessage = serde_json::from_str(&line)?;
+                match &message {
+                    JSONRPCMessage::Response(response) if response.id == RequestId::Integer(id) => {
+                        return Ok(message);
+                    }
+                    JSONRPCMessage::Error(error) if error.id == RequestId::Integer(id) => {
+                        return Ok(message);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn expect_response(message: JSONRPCMessage) -> Result<JSONRPCResponse> {
+        match message {
+            JSONRPCMessage::Response(response) => Ok(response),
+            other => anyhow::bail!("expected a response, got {other:?}"),
+        }
+    }
+
+    async fn spawn_server(llmx_home: &Path, socket: &Path) -> Result<Child> {
+        let child =
+            app_server_command(llmx_home, &format!("unix://{}", socket.display()))?.spawn()?;
+        timeout(DEFAULT_READ_TIMEOUT, async {
+            while !socket.exists() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await?;
+        Ok(child)
+    }
+
+    #[tokio::test]
+    async fn clients_share_threads_over_unix_socket() -> Result<()> {
+        let server = create_mock_chat_completions_server(vec![]).await;
+        let llmx_home = TempDir::new()?;
+        create_config_toml(llmx_home.path(), &server.uri())?;
+        let socket = llmx_home.path().join("app-server.sock");
+        let _child = spawn_server(llmx_home.path(), &socket).await?;
+
+        let mut first = SocketClient::connect(&socket).await?;
+        let mut second = SocketClient::connect(&socket).await?;
+
+        let ThreadStartResponse { thread } = to_response(expect_response(
+            first
+                .request("thread/start", json!({ "model": "mock-model" }))
+                .await?,
+        )?)?;
+
+        // The second client picks up the thread the first one started.
+        let ThreadResumeResponse { thread: resumed } = to_response(expect_response(
+            second
+                .request("thread/resume", json!({ "threadId": thread.id }))
+                .await?,
+        )?)?;
+        assert_eq!(resumed.id, thread.id);
+
+        let unsubscribe = json!({ "threadId": thread.id });
+        expect_response(
+            second
+                .request("thread/unsubscribe", unsubscribe.clone())
+                .await?,
+        )?;
+        let JSONRPCMessage::Error(error) =
+            second.request("thread/unsubscribe", unsubscribe).await?
+        else {
+            anyhow::bail!("expected an error for a second unsubscribe");
+        };
+        assert_eq!(
+            error.error.message,
+            format!("not subscribed to thread: {}", thread.id)
+        );
+
+        Ok(())
+    }
+
+    fn create_config_toml(llmx_home: &Path, server_uri: &str) -> std::io::Result<()> {
+        std::fs::write(
+            llmx_home.join("config.toml"),
+            format!(
+                r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+            ),
+        )
+    }
+}
@@ -49,6 +49,7 @@ async fn logout_chatgpt_removes_auth() -> Result<()> {
         llmx_home.path(),
         "sk-test-key",
         AuthCredentialsStoreMode::File,
+        None,
     )?;
     assert!(llmx_home.path().join("auth.json").exists());
 
@@ -5,6 +5,7 @@ mod create_conversation;
 mod fuzzy_file_search;
 mod interrupt;
 mod list_resume;
+mod listen;
 mod llmx_message_processor_flow;
 mod login;
 mod send_message;
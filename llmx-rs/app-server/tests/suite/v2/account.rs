@@ -85,6 +85,7 @@ async fn logout_account_removes_auth_and_notifies() -> Result<()> {
         llmx_home.path(),
         "sk-test-key",
         AuthCredentialsStoreMode::File,
+        None,
     )?;
     assert!(llmx_home.path().join("auth.json").exists());
 
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use llmx_app_server_protocol::ConfigReadParams;
+use llmx_app_server_protocol::ConfigReadResponse;
+use llmx_app_server_protocol::ConfigWriteParams;
+use llmx_app_server_protocol::ConfigWriteResponse;
+use llmx_app_server_protocol::JSONRPCError;
+use llmx_app_server_protocol::JSONRPCResponse;
+use llmx_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn config_write_then_read_round_trips_values() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    create_config_toml(llmx_home.path())?;
+
+    let mut mcp = McpProcess::new(llmx_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    for (key_path, value) in [
+        ("profiles.fast.model", json!("gpt-5-mini")),
+        ("profiles.fast.model_reasoning_effort", json!("low")),
+        ("sandbox_mode", json!(null)),
+    ] {
+        let write_id = mcp
+            .send_config_write_request(ConfigWriteParams {
+                key_path: key_path.to_string(),
+                value,
+            })
+            .await?;
+        let write_resp: JSONRPCResponse = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(write_id)),
+        )
+        .await??;
+        let _: ConfigWriteResponse = to_response(write_resp)?;
+    }
+
+    let read_id = mcp
+        .send_config_read_request(ConfigReadParams {
+            key_path: Some("profiles.fast".to_string()),
+        })
+        .await?;
+    let read_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ConfigReadResponse { config } = to_response(read_resp)?;
+    assert_eq!(
+        config,
+        json!({
+            "model": "gpt-5-mini",
+            "model_reasoning_effort": "low",
+        })
+    );
+
+    let read_id = mcp
+        .send_config_read_request(ConfigReadParams::default())
+        .await?;
+    let read_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ConfigReadResponse { config } = to_response(read_resp)?;
+    assert_eq!(config["model"], json!("mock-model"));
+    assert_eq!(config.get("sandbox_mode"), None);
+
+    let read_id = mcp
+        .send_config_read_request(ConfigReadParams {
+            key_path: Some("profiles.missing".to_string()),
+        })
+        .await?;
+    let read_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ConfigReadResponse { config } = to_response(read_resp)?;
+    assert_eq!(config, json!(null));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_read_redacts_secrets() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    std::fs::write(
+        llmx_home.path().join("config.toml"),
+        format!(
+            r#"{}
+[mcp_servers.docs]
+command = "docs-server"
+env = {{ DOCS_TOKEN = "secret-value" }}
+
+[mcp_servers.docs.http_headers]
+Authorization = "Bearer secret-value"
+"#,
+            config_contents()
+        ),
+    )?;
+
+    let mut mcp = McpProcess::new(llmx_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let read_id = mcp
+        .send_config_read_request(ConfigReadParams {
+            key_path: Some("mcp_servers.docs".to_string()),
+        })
+        .await?;
+    let read_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let ConfigReadResponse { config } = to_response(read_resp)?;
+    assert_eq!(
+        config,
+        json!({
+            "command": "docs-server",
+            "env": { "DOCS_TOKEN": "<redacted>" },
+            "http_headers": { "Authorization": "<redacted>" },
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_write_rejects_invalid_key_path() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    create_config_toml(llmx_home.path())?;
+
+    let mut mcp = McpProcess::new(llmx_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let write_id = mcp
+        .send_config_write_request(ConfigWriteParams {
+            key_path: "profiles..model".to_string(),
+            value: json!("gpt-5"),
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(write_id)),
+    )
+    .await??;
+    assert!(
+        err.error.message.contains("profiles..model"),
+        "unexpected error: {}",
+        err.error.message
+    );
+
+    let contents = std::fs::read_to_string(llmx_home.path().join("config.toml"))?;
+    assert_eq!(contents, config_contents());
+    Ok(())
+}
+
+fn create_config_toml(llmx_home: &Path) -> std::io::Result<()> {
+    let config_toml = llmx_home.join("config.toml");
+    std::fs::write(config_toml, config_contents())
+}
+
+fn config_contents() -> &'static str {
+    r#"model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#
+}
@@ -1,5 +1,7 @@
 mod account;
+mod config;
 mod model_list;
+mod prompts_list;
 mod rate_limits;
 mod thread_archive;
 mod thread_list;
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use llmx_app_server_protocol::CustomPrompt;
+use llmx_app_server_protocol::JSONRPCResponse;
+use llmx_app_server_protocol::PromptArgument;
+use llmx_app_server_protocol::PromptArgumentType;
+use llmx_app_server_protocol::PromptsListParams;
+use llmx_app_server_protocol::PromptsListResponse;
+use llmx_app_server_protocol::RequestId;
+use llmx_app_server_protocol::ThreadStartParams;
+use llmx_app_server_protocol::ThreadStartResponse;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn prompts_list_returns_custom_prompts() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    create_config_toml(llmx_home.path())?;
+    let prompts_dir = llmx_home.path().join("prompts");
+    std::fs::create_dir(&prompts_dir)?;
+    let prompt_path = prompts_dir.join("review.md");
+    std::fs::write(
+        &prompt_path,
+        "---\ndescription: Review a file\narguments:\n  - FILE: required\n---\nReview $FILE\n",
+    )?;
+
+    let mut mcp = McpProcess::new(llmx_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread } = to_response::<ThreadStartResponse>(start_resp)?;
+
+    let list_id = mcp
+        .send_prompts_list_request(PromptsListParams {
+            thread_id: Some(thread.id),
+        })
+        .await?;
+    let list_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let PromptsListResponse { prompts } = to_response::<PromptsListResponse>(list_resp)?;
+
+    assert_eq!(
+        prompts,
+        vec![CustomPrompt {
+            name: "review".to_string(),
+            // LLMX_HOME is canonicalized when resolving the prompts directory.
+            path: prompt_path.canonicalize()?,
+            content: "Review $FILE\n".to_string(),
+            description: Some("Review a file".to_string()),
+            argument_hint: None,
+            arguments: vec![PromptArgument {
+                name: "FILE".to_string(),
+                kind: PromptArgumentType::String,
+                default: None,
+                required: true,
+                choices: Vec::new(),
+                description: None,
+            }],
+        }]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn prompts_list_without_thread_uses_server_config() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    create_config_toml(llmx_home.path())?;
+    let prompts_dir = llmx_home.path().join("prompts");
+    std::fs::create_dir(&prompts_dir)?;
+    let prompt_path = prompts_dir.join("fix.md");
+    std::fs::write(&prompt_path, "Fix the failing tests\n")?;
+
+    let mut mcp = McpProcess::new(llmx_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let list_id = mcp
+        .send_prompts_list_request(PromptsListParams { thread_id: None })
+        .await?;
+    let list_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let PromptsListResponse { prompts } = to_response::<PromptsListResponse>(list_resp)?;
+
+    assert_eq!(
+        prompts,
+        vec![CustomPrompt {
+            name: "fix".to_string(),
+            path: prompt_path.canonicalize()?,
+            content: "Fix the failing tests\n".to_string(),
+            description: None,
+            argument_hint: None,
+            arguments: Vec::new(),
+        }]
+    );
+    Ok(())
+}
+
+fn create_config_toml(llmx_home: &Path) -> std::io::Result<()> {
+    let config_toml = llmx_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        r#"model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+    )
+}
@@ -6,6 +6,8 @@ use app_test_support::create_mock_chat_completions_server_unchecked;
 use app_test_support::create_shell_sse_response;
 use app_test_support::to_response;
 use core_test_support::skip_if_no_network;
+use llmx_app_server_protocol::ApprovalDecision;
+use llmx_app_server_protocol::CommandExecutionApprovalResponse;
 use llmx_app_server_protocol::JSONRPCNotification;
 use llmx_app_server_protocol::JSONRPCResponse;
 use llmx_app_server_protocol::RequestId;
@@ -18,7 +20,6 @@ use llmx_app_server_protocol::TurnStartedNotification;
 use llmx_app_server_protocol::UserInput as V2UserInput;
 use llmx_core::protocol_config_types::ReasoningEffort;
 use llmx_core::protocol_config_types::ReasoningSummary;
-use llmx_protocol::parse_command::ParsedCommand;
 use llmx_protocol::protocol::Event;
 use llmx_protocol::protocol::EventMsg;
 use pretty_assertions::assert_eq;
@@ -235,7 +236,7 @@ async fn turn_start_exec_approval_toggle_v2() -> Result<()> {
     .await??;
     let ThreadStartResponse { thread } = to_response::<ThreadStartResponse>(start_resp)?;
 
-    // turn/start — expect ExecCommandApproval request from server
+    // turn/start — expect item/commandExecution/approval request from server
     let first_turn_id = mcp
         .send_turn_start_request(TurnStartParams {
             thread_id: thread.id.clone(),
@@ -258,21 +259,26 @@ async fn turn_start_exec_approval_toggle_v2() -> Result<()> {
         mcp.read_stream_until_request_message(),
     )
     .await??;
-    let ServerRequest::ExecCommandApproval { request_id, params } = server_req else {
-        panic!("expected ExecCommandApproval request");
+    let ServerRequest::CommandExecutionApproval { request_id, params } = server_req else {
+        panic!("expected CommandExecutionApproval request");
     };
-    assert_eq!(params.call_id, "call1");
+    assert_eq!(params.thread_id, thread.id);
+    assert_eq!(params.item_id, "call1");
     assert_eq!(
-        params.parsed_cmd,
-        vec![ParsedCommand::Unknown {
-            cmd: "python3 -c 'print(42)'".to_string()
-        }]
+        params.command,
+        vec![
+            "python3".to_string(),
+            "-c".to_string(),
+            "print(42)".to_string(),
+        ]
     );
 
     // Approve and wait for task completion
     mcp.send_response(
         request_id,
-        serde_json::json!({ "decision": llmx_core::protocol::ReviewDecision::Approved }),
+        serde_json::to_value(CommandExecutionApprovalResponse {
+            decision: ApprovalDecision::Approved,
+        })?,
     )
     .await?;
     timeout(
@@ -302,7 +308,7 @@ async fn turn_start_exec_approval_toggle_v2() -> Result<()> {
     )
     .await??;
 
-    // Ensure we do NOT receive an ExecCommandApproval request before task completes
+    // Ensure we do NOT receive an approval request before task completes
     timeout(
         DEFAULT_READ_TIMEOUT,
         mcp.read_stream_until_notification_message("llmx/event/task_complete"),
@@ -13,7 +13,12 @@ pub(crate) async fn chatgpt_get_request<T: DeserializeOwned>(
     path: String,
 ) -> anyhow::Result<T> {
     let chatgpt_base_url = &config.chatgpt_base_url;
-    init_chatgpt_token_from_auth(&config.llmx_home, config.cli_auth_credentials_store_mode).await?;
+    init_chatgpt_token_from_auth(
+        &config.llmx_home,
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    )
+    .await?;
 
     // Make direct HTTP request to ChatGPT backend API with the token
     let client = create_client();
@@ -22,8 +22,10 @@ pub fn set_chatgpt_token_data(value: TokenData) {
 pub async fn init_chatgpt_token_from_auth(
     llmx_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<()> {
-    let auth = LlmxAuth::from_auth_storage(llmx_home, auth_credentials_store_mode)?;
+    let auth =
+        LlmxAuth::from_auth_storage(llmx_home, auth_credentials_store_mode, credential_helper)?;
     if let Some(auth) = auth {
         let token_data = auth.get_token_data().await?;
         set_chatgpt_token_data(token_data);
@@ -0,0 +1,408 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use llmx_common::CliConfigOverrides;
+use llmx_core::config::CONFIG_TOML_FILE;
+use llmx_core::config::Config;
+use llmx_core::config::ConfigOverrides;
+use llmx_core::config::edit::ConfigEditsBuilder;
+use llmx_core::config::edit::parse_key_path;
+use llmx_core::config::edit::parse_value_item;
+use llmx_core::config::effective::redact_secrets;
+use llmx_core::config::effective::render_effective_config;
+use llmx_core::config::explain_config_key;
+use llmx_core::config::find_llmx_home;
+use llmx_core::config::load_merged_config_toml;
+use llmx_core::config::validate::ConfigDiagnostic;
+use llmx_core::config::validate::DiagnosticSeverity;
+use llmx_core::config::validate::validate_config_toml;
+use llmx_core::editor::editor_command;
+use llmx_core::editor::run_editor;
+use toml::Value as TomlValue;
+
+/// Inspect and edit the layered LLMX configuration.
+///
+/// Subcommands:
+/// - `get`      — print the effective value of a key
+/// - `set`      — write a key to `config.toml`
+/// - `unset`    — remove a key from `config.toml`
+/// - `edit`     — open `config.toml` in `$VISUAL`/`$EDITOR`
+/// - `validate` — type-check `config.toml` and warn about unknown keys
+/// - `show`     — print the merged or fully resolved configuration
+/// - `explain`  — show which layer each value of a key came from
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Print the effective value of a key after merging every layer.
+    Get(GetArgs),
+
+    /// Set a key in `config.toml`, keeping comments and formatting.
+    Set(SetArgs),
+
+    /// Remove a key from `config.toml`.
+    Unset(UnsetArgs),
+
+    /// Open `config.toml` in `$VISUAL`/`$EDITOR` and validate it afterwards.
+    Edit,
+
+    /// Check `config.toml` for type errors and unknown keys.
+    Validate(ValidateArgs),
+
+    /// Print the merged configuration.
+    Show(ShowArgs),
+
+    /// Show the effective value of a key and every layer that sets it.
+    Explain(ExplainArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct GetArgs {
+    /// Dotted config key, e.g. `model` or `mcp_servers.docs.command`.
+    pub key: String,
+
+    /// Prefer the value from `profiles.<PROFILE>`, falling back to the top level.
+    #[arg(long, short = 'p')]
+    pub profile: Option<String>,
+
+    /// Resolve project config as if LLMX were started in this directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Dotted config key, e.g. `model` or `tui.notifications`.
+    pub key: String,
+
+    /// Value parsed as TOML (`true`, `42`, `["a", "b"]`); anything that does
+    /// not parse is stored as a string.
+    pub value: String,
+
+    /// Write the key under `profiles.<PROFILE>` instead of the top level.
+    #[arg(long, short = 'p')]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct UnsetArgs {
+    /// Dotted config key to remove.
+    pub key: String,
+
+    /// Remove the key from `profiles.<PROFILE>` instead of the top level.
+    #[arg(long, short = 'p')]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// File to check. Defaults to `$LLMX_HOME/config.toml`.
+    #[arg(value_name = "FILE")]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Print the fully resolved settings, including defaults and legacy
+    /// feature aliases, instead of the merged TOML layers.
+    #[arg(long)]
+    pub effective: bool,
+
+    /// Resolve settings with this profile active.
+    #[arg(long, short = 'p', requires = "effective")]
+    pub profile: Option<String>,
+
+    /// Resolve project config as if LLMX were started in this directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExplainArgs {
+    /// Dotted config key, e.g. `model` or `mcp_servers.docs.command`.
+    pub key: String,
+
+    /// Resolve project config as if LLMX were started in this directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+}
+
+impl ConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let ConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            ConfigSubcommand::Get(args) => {
+                run_get(&config_overrides, args).await?;
+            }
+            ConfigSubcommand::Set(args) => {
+                run_set(args).await?;
+            }
+            ConfigSubcommand::Unset(args) => {
+                run_unset(args).await?;
+            }
+            ConfigSubcommand::Edit => {
+                run_edit().await?;
+            }
+            ConfigSubcommand::Validate(args) => {
+                run_validate(args)?;
+            }
+            ConfigSubcommand::Show(args) => {
+                run_show(&config_overrides, args).await?;
+            }
+            ConfigSubcommand::Explain(args) => {
+                run_explain(&config_overrides, args).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_get(config_overrides: &CliConfigOverrides, args: GetArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let GetArgs { key, profile, cwd } = args;
+    let segments = parse_key_path(&key)?;
+
+    let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+    let mut merged = load_merged_config_toml(&llmx_home, cwd, overrides)
+        .await
+        .context("failed to load configuration")?;
+    redact_secrets(&mut merged);
+
+    let profile_value = profile.as_ref().and_then(|profile| {
+        let mut scoped = vec!["profiles".to_string(), profile.clone()];
+        scoped.extend(segments.iter().cloned());
+        lookup(&merged, &scoped)
+    });
+    let Some(value) = profile_value.or_else(|| lookup(&merged, &segments)) else {
+        bail!("{key} is not set; the built-in default applies");
+    };
+
+    match value {
+        TomlValue::String(text) => println!("{text}"),
+        TomlValue::Table(_) => print!("{}", toml::to_string_pretty(value)?),
+        other => println!("{other}"),
+    }
+    Ok(())
+}
+
+async fn run_set(args: SetArgs) -> Result<()> {
+    let SetArgs {
+        key,
+        value,
+        profile,
+    } = args;
+    let segments = scoped_segments(&key, profile.as_deref())?;
+    let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+
+    let edits = ConfigEditsBuilder::new(&llmx_home).set_path(segments, parse_value_item(&value));
+    update_config_file(&llmx_home, edits).await?;
+    println!(
+        "Set {} in {}",
+        segments_label(&key, profile.as_deref()),
+        config_path(&llmx_home).display()
+    );
+    Ok(())
+}
+
+async fn run_unset(args: UnsetArgs) -> Result<()> {
+    let UnsetArgs { key, profile } = args;
+    let segments = scoped_segments(&key, profile.as_deref())?;
+    let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+
+    let edits = ConfigEditsBuilder::new(&llmx_home).clear_path(segments);
+    update_config_file(&llmx_home, edits).await?;
+    println!(
+        "Removed {} from {}",
+        segments_label(&key, profile.as_deref()),
+        config_path(&llmx_home).display()
+    );
+    Ok(())
+}
+
+async fn run_edit() -> Result<()> {
+    let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+    let path = config_path(&llmx_home);
+    if !path.exists() {
+        std::fs::create_dir_all(&llmx_home)
+            .with_context(|| format!("failed to create {}", llmx_home.display()))?;
+        std::fs::write(&path, "")
+            .with_context(|| format!("failed to create {}", path.display()))?;
+    }
+
+    let argv = editor_command().unwrap_or_else(|| vec![default_editor().to_string()]);
+    let editor_path = path.clone();
+    tokio::task::spawn_blocking(move || run_editor(&argv, &editor_path)).await??;
+
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    report_diagnostics(&path, &validate_config_toml(&contents))
+}
+
+fn run_validate(args: ValidateArgs) -> Result<()> {
+    let path = match args.file {
+        Some(path) => path,
+        None => config_path(&find_llmx_home().context("failed to resolve LLMX_HOME")?),
+    };
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let diagnostics = validate_config_toml(&contents);
+    report_diagnostics(&path, &diagnostics)?;
+    if diagnostics.is_empty() {
+        println!("{}: ok", path.display());
+    }
+    Ok(())
+}
+
+async fn run_show(config_overrides: &CliConfigOverrides, args: ShowArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let ShowArgs {
+        effective,
+        profile,
+        cwd,
+    } = args;
+
+    if effective {
+        let config = Config::load_with_cli_overrides(
+            overrides,
+            ConfigOverrides {
+                config_profile: profile,
+                cwd,
+                ..Default::default()
+            },
+        )
+        .await
+        .context("failed to load configuration")?;
+        print!("{}", render_effective_config(&config)?);
+    } else {
+        let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+        let mut merged = load_merged_config_toml(&llmx_home, cwd, overrides)
+            .await
+            .context("failed to load configuration")?;
+        redact_secrets(&mut merged);
+        print!("{}", toml::to_string_pretty(&merged)?);
+    }
+    Ok(())
+}
+
+async fn run_explain(config_overrides: &CliConfigOverrides, args: ExplainArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let ExplainArgs { key, cwd } = args;
+
+    let llmx_home = find_llmx_home().context("failed to resolve LLMX_HOME")?;
+    let origins = explain_config_key(&llmx_home, cwd, overrides, &key)
+        .await
+        .context("failed to load configuration")?;
+
+    let Some((effective, overridden)) = origins.split_last() else {
+        println!("{key} is not set; the built-in default applies.");
+        return Ok(());
+    };
+
+    println!("{key} = {}", effective.value);
+    println!("  from: {}", effective.source);
+    if !overridden.is_empty() {
+        println!("  overrides:");
+        for origin in overridden.iter().rev() {
+            println!("    {} = {}", origin.source, origin.value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies `edits`, then validates the result and restores the previous file
+/// if the edit introduced an error.
+async fn update_config_file(llmx_home: &Path, edits: ConfigEditsBuilder) -> Result<()> {
+    let path = config_path(llmx_home);
+    let previous = std::fs::read_to_string(&path).ok();
+
+    edits.apply().await?;
+
+    let contents = std::fs::read_to_string(&path).unwrap_or_default();
+    let diagnostics = validate_config_toml(&contents);
+    if diagnostics.iter().any(ConfigDiagnostic::is_error) {
+        match previous {
+            Some(previous) => std::fs::write(&path, previous),
+            None => std::fs::remove_file(&path),
+        }
+        .with_context(|| format!("failed to restore {}", path.display()))?;
+    }
+    report_diagnostics(&path, &diagnostics)
+}
+
+/// Prints diagnostics compiler-style and fails if any of them is an error.
+fn report_diagnostics(path: &Path, diagnostics: &[ConfigDiagnostic]) -> Result<()> {
+    for diagnostic in diagnostics {
+        let severity = match diagnostic.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        match diagnostic.location {
+            Some((line, column)) => eprintln!(
+                "{}:{line}:{column}: {severity}: {}",
+                path.display(),
+                diagnostic.message
+            ),
+            None => eprintln!("{}: {severity}: {}", path.display(), diagnostic.message),
+        }
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    if errors > 0 {
+        bail!("{} has {errors} error(s)", path.display());
+    }
+    Ok(())
+}
+
+fn lookup<'a>(root: &'a TomlValue, segments: &[String]) -> Option<&'a TomlValue> {
+    segments
+        .iter()
+        .try_fold(root, |value, segment| value.as_table()?.get(segment))
+}
+
+fn scoped_segments(key: &str, profile: Option<&str>) -> Result<Vec<String>> {
+    let segments = parse_key_path(key)?;
+    Ok(match profile {
+        Some(profile) => ["profiles".to_string(), profile.to_string()]
+            .into_iter()
+            .chain(segments)
+            .collect(),
+        None => segments,
+    })
+}
+
+fn segments_label(key: &str, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("`{key}` (profile `{profile}`)"),
+        None => format!("`{key}`"),
+    }
+}
+
+fn config_path(llmx_home: &Path) -> PathBuf {
+    llmx_home.join(CONFIG_TOML_FILE)
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(windows) { "notepad" } else { "vi" }
+}
@@ -1,30 +1,41 @@
 use llmx_app_server_protocol::AuthMode;
 use llmx_common::CliConfigOverrides;
 use llmx_core::LlmxAuth;
+use llmx_core::ModelProviderInfo;
 use llmx_core::auth::AuthCredentialsStoreMode;
 use llmx_core::auth::CLIENT_ID;
+use llmx_core::auth::ProviderKeyStore;
+use llmx_core::auth::ProviderKeys;
 use llmx_core::auth::login_with_api_key;
 use llmx_core::auth::logout;
+use llmx_core::auth::migrate_auth_store;
+use llmx_core::auth::migrate_provider_keys;
 use llmx_core::config::Config;
 use llmx_core::config::ConfigOverrides;
+use llmx_core::config::types::McpServerTransportConfig;
 use llmx_login::ServerOptions;
 use llmx_login::run_device_code_login;
 use llmx_login::run_login_server;
 use llmx_protocol::config_types::ForcedLoginMethod;
+use llmx_rmcp_client::OAuthCredentialsStoreMode;
+use llmx_rmcp_client::migrate_oauth_tokens;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::io::Write;
 use std::path::PathBuf;
 
 pub async fn login_with_chatgpt(
     llmx_home: PathBuf,
     forced_chatgpt_workspace_id: Option<String>,
     cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<Vec<String>>,
 ) -> std::io::Result<()> {
     let opts = ServerOptions::new(
         llmx_home,
         CLIENT_ID.to_string(),
         forced_chatgpt_workspace_id,
         cli_auth_credentials_store_mode,
+        credential_helper,
     );
     let server = run_login_server(opts)?;
 
@@ -50,6 +61,7 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
         config.llmx_home,
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper,
     )
     .await
     {
@@ -79,6 +91,7 @@ pub async fn run_login_with_api_key(
         &config.llmx_home,
         &api_key,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
     ) {
         Ok(_) => {
             eprintln!("Successfully logged in");
@@ -135,6 +148,7 @@ pub async fn run_login_with_device_code(
         client_id.unwrap_or(CLIENT_ID.to_string()),
         forced_chatgpt_workspace_id,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper,
     );
     if let Some(iss) = issuer_base_url {
         opts.issuer = iss;
@@ -154,29 +168,273 @@ pub async fn run_login_with_device_code(
 pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
-    match LlmxAuth::from_auth_storage(&config.llmx_home, config.cli_auth_credentials_store_mode) {
+    let openai_logged_in = match LlmxAuth::from_auth_storage(
+        &config.llmx_home,
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    ) {
         Ok(Some(auth)) => match auth.mode {
             AuthMode::ApiKey => match auth.get_token().await {
                 Ok(api_key) => {
                     eprintln!("Logged in using an API key - {}", safe_format_key(&api_key));
-                    std::process::exit(0);
+                    true
                 }
                 Err(e) => {
                     eprintln!("Unexpected error retrieving API key: {e}");
-                    std::process::exit(1);
+                    false
                 }
             },
             AuthMode::ChatGPT => {
                 eprintln!("Logged in using ChatGPT");
-                std::process::exit(0);
+                true
             }
         },
         Ok(None) => {
             eprintln!("Not logged in");
-            std::process::exit(1);
+            false
         }
         Err(e) => {
             eprintln!("Error checking login status: {e}");
+            false
+        }
+    };
+
+    let stored_keys = ProviderKeyStore::new(
+        config.llmx_home.clone(),
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    )
+    .load_all()
+    .unwrap_or_else(|e| {
+        eprintln!("Error reading stored provider keys: {e}");
+        ProviderKeys::new()
+    });
+
+    let mut providers: Vec<_> = config.model_providers.iter().collect();
+    providers.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let mut active_ready = openai_logged_in;
+    eprintln!("Model providers:");
+    for (id, provider) in providers {
+        let (status, ready) = provider_status(id, provider, &stored_keys, openai_logged_in);
+        let active = *id == config.model_provider_id;
+        if active {
+            active_ready = ready;
+        }
+        let marker = if active { " (active)" } else { "" };
+        eprintln!("  {id}{marker}: {status}");
+    }
+
+    std::process::exit(if active_ready { 0 } else { 1 });
+}
+
+/// Describes where requests to `provider` get their credentials from, and
+/// whether they would carry any.
+fn provider_status(
+    id: &str,
+    provider: &ModelProviderInfo,
+    stored_keys: &ProviderKeys,
+    openai_logged_in: bool,
+) -> (String, bool) {
+    let env_var_set = |var: &Option<String>| {
+        var.as_ref()
+            .filter(|var| std::env::var(var).is_ok_and(|value| !value.trim().is_empty()))
+            .cloned()
+    };
+    let has_stored_key = stored_keys.contains_key(id);
+
+    if provider.experimental_bearer_token.is_some() {
+        return ("bearer token from config".to_string(), true);
+    }
+    if let Some(oauth2) = &provider.oauth2 {
+        if let Some(var) = env_var_set(&oauth2.client_secret_env) {
+            return (
+                format!("OAuth2 client credentials, secret from ${var}"),
+                true,
+            );
+        }
+        if has_stored_key {
+            return (
+                "OAuth2 client credentials, secret in credential store".to_string(),
+                true,
+            );
+        }
+        return (
+            format!(
+                "OAuth2 client credentials, no client secret (run `llmx login --provider {id}`)"
+            ),
+            false,
+        );
+    }
+    if provider.requires_openai_auth {
+        return if openai_logged_in {
+            ("OpenAI login".to_string(), true)
+        } else {
+            ("not logged in (run `llmx login`)".to_string(), false)
+        };
+    }
+    let Some(env_key) = &provider.env_key else {
+        return ("no credentials required".to_string(), true);
+    };
+    if let Some(var) = env_var_set(&provider.env_key) {
+        return (format!("API key from ${var}"), true);
+    }
+    if has_stored_key {
+        return ("API key in credential store".to_string(), true);
+    }
+    (
+        format!("no API key (set ${env_key} or run `llmx login --provider {id}`)"),
+        false,
+    )
+}
+
+/// Save an API key, or the OAuth2 client secret, for a model provider in the
+/// configured credential store.
+pub async fn run_login_with_provider(
+    cli_config_overrides: CliConfigOverrides,
+    provider_id: String,
+    from_stdin: bool,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+
+    let Some(provider) = config.model_providers.get(&provider_id) else {
+        let mut known: Vec<_> = config.model_providers.keys().map(String::as_str).collect();
+        known.sort_unstable();
+        eprintln!(
+            "Unknown model provider '{provider_id}'. Known providers: {}",
+            known.join(", ")
+        );
+        std::process::exit(1);
+    };
+    if provider.requires_openai_auth && provider.oauth2.is_none() {
+        eprintln!("Provider '{provider_id}' uses OpenAI login. Run `llmx login` instead.");
+        std::process::exit(1);
+    }
+    if !provider.accepts_stored_key() {
+        eprintln!(
+            "Provider '{provider_id}' does not declare an `env_key` or `oauth2` table, so a stored key would never be used."
+        );
+        std::process::exit(1);
+    }
+
+    let what = if provider.oauth2.is_some() {
+        "OAuth2 client secret"
+    } else {
+        "API key"
+    };
+    let secret = if from_stdin || !std::io::stdin().is_terminal() {
+        read_api_key_from_stdin()
+    } else {
+        match prompt_for_secret(&format!("{what} for {} ({provider_id}): ", provider.name)) {
+            Ok(secret) if !secret.is_empty() => secret,
+            Ok(_) => {
+                eprintln!("No {what} entered.");
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to read {what}: {e}");
+                std::process::exit(1);
+            }
+        }
+    };
+
+    let store = ProviderKeyStore::new(
+        config.llmx_home.clone(),
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    );
+    match store.save(&provider_id, &secret) {
+        Ok(()) => {
+            eprintln!("Saved {what} for provider '{provider_id}'");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error saving {what}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prompts on stderr and reads one line from the terminal without echoing it.
+fn prompt_for_secret(prompt: &str) -> std::io::Result<String> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    {
+        let _echo = EchoGuard::disable();
+        std::io::stdin().read_line(&mut line)?;
+    }
+    eprintln!();
+    Ok(line.trim().to_string())
+}
+
+/// Turns terminal echo off for stdin until dropped.
+#[cfg(unix)]
+struct EchoGuard(Option<libc::termios>);
+
+#[cfg(unix)]
+impl EchoGuard {
+    fn disable() -> Self {
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: tcgetattr only writes into the provided termios struct.
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) } != 0 {
+            return Self(None);
+        }
+        // SAFETY: tcgetattr succeeded, so the struct is initialized.
+        let original = unsafe { termios.assume_init() };
+        let mut silent = original;
+        silent.c_lflag &= !libc::ECHO;
+        // SAFETY: `silent` is a valid termios copied from the current settings.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &silent) } != 0 {
+            return Self(None);
+        }
+        Self(Some(original))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        if let Some(original) = &self.0 {
+            // SAFETY: restores the settings read in `disable`.
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct EchoGuard;
+
+#[cfg(not(unix))]
+impl EchoGuard {
+    fn disable() -> Self {
+        Self
+    }
+}
+
+/// Remove the key saved by `llmx login --provider` for one provider.
+pub async fn run_logout_provider(
+    cli_config_overrides: CliConfigOverrides,
+    provider_id: String,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let store = ProviderKeyStore::new(
+        config.llmx_home.clone(),
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    );
+    match store.delete(&provider_id) {
+        Ok(true) => {
+            eprintln!("Removed stored key for provider '{provider_id}'");
+            std::process::exit(0);
+        }
+        Ok(false) => {
+            eprintln!("No stored key for provider '{provider_id}'");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error removing stored key: {e}");
             std::process::exit(1);
         }
     }
@@ -185,7 +443,11 @@ pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
 pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides).await;
 
-    match logout(&config.llmx_home, config.cli_auth_credentials_store_mode) {
+    match logout(
+        &config.llmx_home,
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    ) {
         Ok(true) => {
             eprintln!("Successfully logged out");
             std::process::exit(0);
@@ -201,6 +463,142 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
+/// Credential store backend accepted by `llmx login migrate-store`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CredentialStore {
+    File,
+    Keyring,
+    Auto,
+    EncryptedFile,
+    CredentialHelper,
+}
+
+impl CredentialStore {
+    fn name(self) -> &'static str {
+        match self {
+            CredentialStore::File => "file",
+            CredentialStore::Keyring => "keyring",
+            CredentialStore::Auto => "auto",
+            CredentialStore::EncryptedFile => "encrypted-file",
+            CredentialStore::CredentialHelper => "credential-helper",
+        }
+    }
+
+    fn auth_mode(self) -> AuthCredentialsStoreMode {
+        match self {
+            CredentialStore::File => AuthCredentialsStoreMode::File,
+            CredentialStore::Keyring => AuthCredentialsStoreMode::Keyring,
+            CredentialStore::Auto => AuthCredentialsStoreMode::Auto,
+            CredentialStore::EncryptedFile => AuthCredentialsStoreMode::EncryptedFile,
+            CredentialStore::CredentialHelper => AuthCredentialsStoreMode::CredentialHelper,
+        }
+    }
+
+    fn oauth_mode(self) -> OAuthCredentialsStoreMode {
+        match self {
+            CredentialStore::File => OAuthCredentialsStoreMode::File,
+            CredentialStore::Keyring => OAuthCredentialsStoreMode::Keyring,
+            CredentialStore::Auto => OAuthCredentialsStoreMode::Auto,
+            CredentialStore::EncryptedFile => OAuthCredentialsStoreMode::EncryptedFile,
+            CredentialStore::CredentialHelper => OAuthCredentialsStoreMode::CredentialHelper,
+        }
+    }
+}
+
+/// Move CLI login credentials, stored provider keys, and MCP OAuth tokens
+/// into `to`. When `from` is omitted, each kind of credential is read from
+/// its configured store.
+pub async fn run_login_migrate_store(
+    cli_config_overrides: CliConfigOverrides,
+    from: Option<CredentialStore>,
+    to: CredentialStore,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides).await;
+    let uses_helper = [Some(to), from].contains(&Some(CredentialStore::CredentialHelper));
+    if uses_helper && config.credential_helper.is_none() {
+        eprintln!(
+            "Set `credential_helper` in config.toml before using the credential-helper store."
+        );
+        std::process::exit(1);
+    }
+    let mut failed = false;
+
+    let auth_from = from.map_or(
+        config.cli_auth_credentials_store_mode,
+        CredentialStore::auth_mode,
+    );
+    let auth_to = to.auth_mode();
+    if auth_from == auth_to {
+        eprintln!("CLI login credentials already use the {} store", to.name());
+    } else {
+        match migrate_auth_store(
+            &config.llmx_home,
+            auth_from,
+            auth_to,
+            config.credential_helper.as_deref(),
+        ) {
+            Ok(true) => eprintln!("Moved CLI login credentials to the {} store", to.name()),
+            Ok(false) => eprintln!("No CLI login credentials to move"),
+            Err(e) => {
+                eprintln!("Error moving CLI login credentials: {e}");
+                failed = true;
+            }
+        }
+        match migrate_provider_keys(
+            &config.llmx_home,
+            auth_from,
+            auth_to,
+            config.credential_helper.as_deref(),
+        ) {
+            Ok(true) => eprintln!("Moved stored provider keys to the {} store", to.name()),
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Error moving stored provider keys: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    let oauth_from = from.map_or(
+        config.mcp_oauth_credentials_store_mode,
+        CredentialStore::oauth_mode,
+    );
+    let oauth_to = to.oauth_mode();
+    let mut servers: Vec<_> = config.mcp_servers.iter().collect();
+    servers.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if oauth_from == oauth_to {
+        servers.clear();
+    }
+    for (name, server) in servers {
+        let McpServerTransportConfig::StreamableHttp { url, .. } = &server.transport else {
+            continue;
+        };
+        match migrate_oauth_tokens(
+            name,
+            url,
+            oauth_from,
+            oauth_to,
+            config.credential_helper.as_deref(),
+        ) {
+            Ok(true) => eprintln!("Moved OAuth tokens for MCP server '{name}'"),
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Error moving OAuth tokens for MCP server '{name}': {e:#}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    eprintln!(
+        "Set `cli_auth_credentials_store = \"{name}\"` and `mcp_oauth_credentials_store = \"{name}\"` in config.toml to keep using the new store.",
+        name = to.name()
+    );
+    std::process::exit(0);
+}
+
 async fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
@@ -231,7 +629,11 @@ fn safe_format_key(key: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::ProviderKeys;
+    use super::provider_status;
     use super::safe_format_key;
+    use llmx_core::create_oss_provider_with_base_url;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn formats_long_key() {
@@ -244,4 +646,36 @@ mod tests {
         let key = "sk-proj-12345";
         assert_eq!(safe_format_key(key), "***");
     }
+
+    #[test]
+    fn provider_status_prefers_env_key_over_stored_key() {
+        let mut provider = create_oss_provider_with_base_url("http://localhost:4000/v1");
+        provider.env_key = Some("LLMX_TEST_PROVIDER_KEY_THAT_IS_NOT_SET".to_string());
+
+        let (status, ready) = provider_status("litellm", &provider, &ProviderKeys::new(), false);
+        assert_eq!(
+            status,
+            "no API key (set $LLMX_TEST_PROVIDER_KEY_THAT_IS_NOT_SET or run `llmx login --provider litellm`)"
+        );
+        assert!(!ready);
+
+        let stored = ProviderKeys::from([("litellm".to_string(), "sk-stored".to_string())]);
+        let (status, ready) = provider_status("litellm", &provider, &stored, false);
+        assert_eq!(status, "API key in credential store");
+        assert!(ready);
+    }
+
+    #[test]
+    fn provider_status_reports_openai_login() {
+        let mut provider = create_oss_provider_with_base_url("https://api.openai.com/v1");
+        provider.requires_openai_auth = true;
+
+        let (status, ready) = provider_status("openai", &provider, &ProviderKeys::new(), false);
+        assert_eq!(status, "not logged in (run `llmx login`)");
+        assert!(!ready);
+
+        let (status, ready) = provider_status("openai", &provider, &ProviderKeys::new(), true);
+        assert_eq!(status, "OpenAI login");
+        assert!(ready);
+    }
 }
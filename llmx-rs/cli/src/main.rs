@@ -9,15 +9,20 @@ use llmx_chatgpt::apply_command::run_apply_command;
 use llmx_cli::LandlockCommand;
 use llmx_cli::SeatbeltCommand;
 use llmx_cli::WindowsCommand;
+use llmx_cli::login::CredentialStore;
 use llmx_cli::login::read_api_key_from_stdin;
+use llmx_cli::login::run_login_migrate_store;
 use llmx_cli::login::run_login_status;
 use llmx_cli::login::run_login_with_api_key;
 use llmx_cli::login::run_login_with_chatgpt;
 use llmx_cli::login::run_login_with_device_code;
+use llmx_cli::login::run_login_with_provider;
 use llmx_cli::login::run_logout;
+use llmx_cli::login::run_logout_provider;
 use llmx_cloud_tasks::Cli as CloudTasksCli;
 use llmx_common::CliConfigOverrides;
 use llmx_exec::Cli as ExecCli;
+use llmx_exec::ReviewCli;
 use llmx_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use llmx_tui::AppExitInfo;
 use llmx_tui::Cli as TuiCli;
@@ -26,11 +31,15 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod config_cmd;
 mod mcp_cmd;
+mod sessions_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
 
 use llmx_core::config::Config;
 use llmx_core::config::ConfigOverrides;
@@ -71,6 +80,9 @@ enum Subcommand {
     #[clap(visible_alias = "e")]
     Exec(ExecCli),
 
+    /// Review code changes non-interactively and report findings.
+    Review(ReviewCli),
+
     /// Manage login.
     Login(LoginCommand),
 
@@ -100,6 +112,9 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Export recorded sessions as Markdown, HTML, or JSON.
+    Sessions(SessionsCli),
+
     /// [EXPERIMENTAL] Browse tasks from LLMX Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -114,6 +129,9 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Inspect and edit the layered configuration.
+    Config(ConfigCli),
 }
 
 #[derive(Debug, Parser)]
@@ -180,6 +198,15 @@ struct LoginCommand {
     #[arg(long = "device-auth")]
     use_device_code: bool,
 
+    /// Store an API key (or OAuth2 client secret) for a configured model
+    /// provider instead of logging in to OpenAI.
+    #[arg(
+        long = "provider",
+        value_name = "PROVIDER_ID",
+        conflicts_with = "use_device_code"
+    )]
+    provider: Option<String>,
+
     /// EXPERIMENTAL: Use custom OAuth issuer base URL (advanced)
     /// Override the OAuth issuer base URL (advanced)
     #[arg(long = "experimental_issuer", value_name = "URL", hide = true)]
@@ -197,12 +224,31 @@ struct LoginCommand {
 enum LoginSubcommand {
     /// Show login status.
     Status,
+
+    /// Move stored credentials to a different credential store.
+    MigrateStore(MigrateStoreCommand),
+}
+
+#[derive(Debug, Args)]
+struct MigrateStoreCommand {
+    /// Store to read credentials from. Defaults to the configured stores.
+    #[arg(long, value_enum)]
+    from: Option<CredentialStore>,
+
+    /// Store to move credentials into.
+    #[arg(long, value_enum)]
+    to: CredentialStore,
 }
 
 #[derive(Debug, Parser)]
 struct LogoutCommand {
     #[clap(skip)]
     config_overrides: CliConfigOverrides,
+
+    /// Remove the stored key for this model provider instead of logging out
+    /// of OpenAI.
+    #[arg(long = "provider", value_name = "PROVIDER_ID")]
+    provider: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -210,6 +256,9 @@ struct AppServerCommand {
     /// Omit to run the app server; specify a subcommand for tooling.
     #[command(subcommand)]
     subcommand: Option<AppServerSubcommand>,
+
+    #[clap(flatten)]
+    listen: llmx_app_server::ListenArgs,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -423,9 +472,23 @@ async fn cli_main(llmx_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
             );
             llmx_exec::run_main(exec_cli, llmx_linux_sandbox_exe).await?;
         }
+        Some(Subcommand::Review(mut review_cli)) => {
+            prepend_config_flags(
+                &mut review_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            llmx_exec::run_review(review_cli, llmx_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::McpServer) => {
             llmx_mcp_server::run_main(llmx_linux_sandbox_exe, root_config_overrides).await?;
         }
+        Some(Subcommand::Config(mut config_cli)) => {
+            prepend_config_flags(
+                &mut config_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            config_cli.run().await?;
+        }
         Some(Subcommand::Mcp(mut mcp_cli)) => {
             // Propagate any root-level config overrides (e.g. `-c key=value`).
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
@@ -433,7 +496,12 @@ async fn cli_main(llmx_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
         }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
-                llmx_app_server::run_main(llmx_linux_sandbox_exe, root_config_overrides).await?;
+                llmx_app_server::run_main(
+                    llmx_linux_sandbox_exe,
+                    root_config_overrides,
+                    app_server_cli.listen,
+                )
+                .await?;
             }
             Some(AppServerSubcommand::GenerateTs(gen_cli)) => {
                 llmx_app_server_protocol::generate_ts(
@@ -460,6 +528,9 @@ async fn cli_main(llmx_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
             let exit_info = llmx_tui::run_main(interactive, llmx_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Sessions(sessions_cli)) => {
+            sessions_cli.run().await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -469,8 +540,19 @@ async fn cli_main(llmx_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                 Some(LoginSubcommand::Status) => {
                     run_login_status(login_cli.config_overrides).await;
                 }
+                Some(LoginSubcommand::MigrateStore(migrate)) => {
+                    run_login_migrate_store(login_cli.config_overrides, migrate.from, migrate.to)
+                        .await;
+                }
                 None => {
-                    if login_cli.use_device_code {
+                    if let Some(provider_id) = login_cli.provider {
+                        run_login_with_provider(
+                            login_cli.config_overrides,
+                            provider_id,
+                            login_cli.with_api_key,
+                        )
+                        .await;
+                    } else if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
@@ -496,7 +578,11 @@ async fn cli_main(llmx_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                 &mut logout_cli.config_overrides,
                 root_config_overrides.clone(),
             );
-            run_logout(logout_cli.config_overrides).await;
+            if let Some(provider_id) = logout_cli.provider {
+                run_logout_provider(logout_cli.config_overrides, provider_id).await;
+            } else {
+                run_logout(logout_cli.config_overrides).await;
+            }
         }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
@@ -659,6 +745,9 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
     if resume_cli.web_search {
         interactive.web_search = true;
     }
+    if resume_cli.plan {
+        interactive.plan = true;
+    }
     if !resume_cli.images.is_empty() {
         interactive.images = resume_cli.images;
     }
@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -15,11 +17,25 @@ use llmx_core::config::load_global_mcp_servers;
 use llmx_core::config::types::McpServerConfig;
 use llmx_core::config::types::McpServerTransportConfig;
 use llmx_core::features::Feature;
+use llmx_core::mcp::DEFAULT_STARTUP_TIMEOUT;
+use llmx_core::mcp::DEFAULT_TOOL_TIMEOUT;
 use llmx_core::mcp::auth::compute_auth_statuses;
+use llmx_core::mcp::connect_mcp_server;
 use llmx_core::protocol::McpAuthStatus;
 use llmx_rmcp_client::delete_oauth_tokens;
 use llmx_rmcp_client::perform_oauth_login;
 use llmx_rmcp_client::supports_oauth_login;
+use mcp_types::CallToolResult;
+use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListResourceTemplatesRequestParams;
+use mcp_types::ListResourcesRequestParams;
+use mcp_types::ListToolsRequestParams;
+use mcp_types::Prompt;
+use mcp_types::Resource;
+use mcp_types::ResourceTemplate;
+use mcp_types::Tool;
+use serde::Serialize;
 
 /// [experimental] Launch Llmx as an MCP server or manage configured MCP servers.
 ///
@@ -29,6 +45,7 @@ use llmx_rmcp_client::supports_oauth_login;
 /// - `get`    — show a single server (with `--json`)
 /// - `add`    — add a server launcher entry to `~/.llmx/config.toml`
 /// - `remove` — delete a server entry
+/// - `test`   — start a server and report its tools, resources, and prompts
 #[derive(Debug, clap::Parser)]
 pub struct McpCli {
     #[clap(flatten)]
@@ -59,6 +76,9 @@ pub enum McpSubcommand {
     /// [experimental] Remove stored OAuth credentials for a server.
     /// Requires experimental_use_rmcp_client = true in config.toml.
     Logout(LogoutArgs),
+
+    /// [experimental] Start a configured MCP server and report what it offers.
+    Test(TestArgs),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -162,6 +182,29 @@ pub struct LogoutArgs {
     pub name: String,
 }
 
+#[derive(Debug, clap::Parser)]
+pub struct TestArgs {
+    /// Name of the MCP server to test.
+    pub name: String,
+
+    /// Call this tool after listing the server's tools, resources, and prompts.
+    #[arg(long = "call", value_name = "TOOL")]
+    pub call: Option<String>,
+
+    /// JSON object passed as the arguments of the `--call` tool.
+    #[arg(
+        long = "args",
+        value_name = "JSON",
+        requires = "call",
+        value_parser = parse_tool_args
+    )]
+    pub args: Option<serde_json::Value>,
+
+    /// Output the results as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
 impl McpCli {
     pub async fn run(self) -> Result<()> {
         let McpCli {
@@ -188,6 +231,9 @@ impl McpCli {
             McpSubcommand::Logout(args) => {
                 run_logout(&config_overrides, args).await?;
             }
+            McpSubcommand::Test(args) => {
+                run_test(&config_overrides, args).await?;
+            }
         }
 
         Ok(())
@@ -293,6 +339,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
                         &name,
                         &url,
                         config.mcp_oauth_credentials_store_mode,
+                        config.credential_helper.as_deref(),
                         http_headers.clone(),
                         env_http_headers.clone(),
                         &Vec::new(),
@@ -378,6 +425,7 @@ async fn run_login(config_overrides: &CliConfigOverrides, login_args: LoginArgs)
         &name,
         &url,
         config.mcp_oauth_credentials_store_mode,
+        config.credential_helper.as_deref(),
         http_headers,
         env_http_headers,
         &scopes,
@@ -407,7 +455,12 @@ async fn run_logout(config_overrides: &CliConfigOverrides, logout_args: LogoutAr
         _ => bail!("OAuth logout is only supported for streamable_http transports."),
     };
 
-    match delete_oauth_tokens(&name, &url, config.mcp_oauth_credentials_store_mode) {
+    match delete_oauth_tokens(
+        &name,
+        &url,
+        config.mcp_oauth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    ) {
         Ok(true) => println!("Removed OAuth credentials for '{name}'."),
         Ok(false) => println!("No OAuth credentials stored for '{name}'."),
         Err(err) => return Err(anyhow!("failed to delete OAuth credentials: {err}")),
@@ -429,6 +482,7 @@ async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) ->
     let auth_statuses = compute_auth_statuses(
         config.mcp_servers.iter(),
         config.mcp_oauth_credentials_store_mode,
+        config.credential_helper.as_deref(),
     )
     .await;
 
@@ -827,6 +881,476 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
     Ok(())
 }
 
+/// Guards against servers that keep returning a `nextCursor`.
+const MAX_LIST_PAGES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum McpTestStatus {
+    Ok,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+struct McpTestStep {
+    step: String,
+    status: McpTestStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<u128>,
+    /// Error message for failed steps, reason for skipped ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct McpTestReport {
+    server: String,
+    ok: bool,
+    steps: Vec<McpTestStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    initialize: Option<InitializeResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<Tool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resources: Option<Vec<Resource>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_templates: Option<Vec<ResourceTemplate>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompts: Option<Vec<Prompt>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call: Option<CallToolResult>,
+}
+
+impl McpTestReport {
+    /// Records a step and, for human-readable output, prints it right away so
+    /// a hanging server shows where it got stuck.
+    fn record(
+        &mut self,
+        print: bool,
+        step: impl Into<String>,
+        elapsed: Option<Duration>,
+        result: std::result::Result<(), String>,
+    ) {
+        let (status, detail) = match result {
+            Ok(()) => (McpTestStatus::Ok, None),
+            Err(err) => (McpTestStatus::Failed, Some(err)),
+        };
+        self.push(
+            print,
+            McpTestStep {
+                step: step.into(),
+                status,
+                duration_ms: elapsed.map(|elapsed| elapsed.as_millis()),
+                detail,
+            },
+        );
+    }
+
+    fn skip(&mut self, print: bool, step: &str, reason: &str) {
+        self.push(
+            print,
+            McpTestStep {
+                step: step.to_string(),
+                status: McpTestStatus::Skipped,
+                duration_ms: None,
+                detail: Some(reason.to_string()),
+            },
+        );
+    }
+
+    fn push(&mut self, print: bool, step: McpTestStep) {
+        if print {
+            let status = match step.status {
+                McpTestStatus::Ok => "ok",
+                McpTestStatus::Failed => "failed",
+                McpTestStatus::Skipped => "skipped",
+            };
+            let mut line = format!("[{status}] {}", step.step);
+            if let Some(duration_ms) = step.duration_ms {
+                line.push_str(&format!(" ({duration_ms} ms)"));
+            }
+            if let Some(detail) = &step.detail {
+                line.push_str(&format!(": {detail}"));
+            }
+            println!("{line}");
+        }
+        self.steps.push(step);
+    }
+
+    fn failed_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status == McpTestStatus::Failed)
+            .count()
+    }
+}
+
+async fn run_test(config_overrides: &CliConfigOverrides, test_args: TestArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+        .await
+        .context("failed to load configuration")?;
+
+    let TestArgs {
+        name,
+        call,
+        args,
+        json,
+    } = test_args;
+    let Some(server) = config.mcp_servers.get(&name).cloned() else {
+        bail!("No MCP server named '{name}' found.");
+    };
+    let print = !json;
+
+    if !server.enabled {
+        eprintln!("MCP server '{name}' is disabled in config.toml; testing it anyway.");
+    }
+    if print {
+        let transport = match &server.transport {
+            McpServerTransportConfig::Stdio { command, args, .. } => {
+                let mut command_line = command.clone();
+                for arg in args {
+                    command_line.push(' ');
+                    command_line.push_str(arg);
+                }
+                format!("stdio: {command_line}")
+            }
+            McpServerTransportConfig::StreamableHttp { url, .. } => {
+                format!("streamable_http: {url}")
+            }
+        };
+        println!("Testing MCP server '{name}' ({transport})");
+    }
+
+    let list_timeout = server
+        .startup_timeout_sec
+        .unwrap_or(DEFAULT_STARTUP_TIMEOUT);
+    let tool_timeout = server.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT);
+    let mut report = McpTestReport {
+        server: name.clone(),
+        ..Default::default()
+    };
+
+    let started = Instant::now();
+    let connected = connect_mcp_server(
+        &name,
+        server,
+        config.mcp_oauth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    )
+    .await;
+    let elapsed = Some(started.elapsed());
+    let connected = match connected {
+        Ok((client, initialize)) => {
+            report.record(print, "initialize", elapsed, Ok(()));
+            if print {
+                print_initialize(&initialize);
+            }
+            let capabilities = initialize.capabilities.clone();
+            report.initialize = Some(initialize);
+            Some((client, capabilities))
+        }
+        Err(err) => {
+            report.record(print, "initialize", elapsed, Err(format!("{err:#}")));
+            None
+        }
+    };
+
+    if let Some((client, capabilities)) = &connected {
+        if capabilities.tools.is_some() {
+            let started = Instant::now();
+            let result = collect_pages(|cursor| async move {
+                let page = client
+                    .list_tools(
+                        cursor.map(|cursor| ListToolsRequestParams {
+                            cursor: Some(cursor),
+                        }),
+                        Some(list_timeout),
+                    )
+                    .await?;
+                Ok((page.tools, page.next_cursor))
+            })
+            .await;
+            let elapsed = Some(started.elapsed());
+            match result {
+                Ok(tools) => {
+                    report.record(print, "tools/list", elapsed, Ok(()));
+                    if print {
+                        print_tools(&tools);
+                    }
+                    report.tools = Some(tools);
+                }
+                Err(err) => {
+                    report.record(print, "tools/list", elapsed, Err(format!("{err:#}")));
+                }
+            }
+        } else {
+            report.skip(print, "tools/list", "server does not advertise tools");
+        }
+
+        if capabilities.resources.is_some() {
+            let started = Instant::now();
+            let result = collect_pages(|cursor| async move {
+                let page = client
+                    .list_resources(
+                        cursor.map(|cursor| ListResourcesRequestParams {
+                            cursor: Some(cursor),
+                        }),
+                        Some(list_timeout),
+                    )
+                    .await?;
+                Ok((page.resources, page.next_cursor))
+            })
+            .await;
+            let elapsed = Some(started.elapsed());
+            match result {
+                Ok(resources) => {
+                    report.record(print, "resources/list", elapsed, Ok(()));
+                    if print {
+                        for resource in &resources {
+                            print_listing(
+                                &resource.uri,
+                                Some(&resource.name),
+                                resource.description.as_deref(),
+                            );
+                        }
+                    }
+                    report.resources = Some(resources);
+                }
+                Err(err) => {
+                    report.record(print, "resources/list", elapsed, Err(format!("{err:#}")));
+                }
+            }
+
+            let started = Instant::now();
+            let result = collect_pages(|cursor| async move {
+                let page = client
+                    .list_resource_templates(
+                        cursor.map(|cursor| ListResourceTemplatesRequestParams {
+                            cursor: Some(cursor),
+                        }),
+                        Some(list_timeout),
+                    )
+                    .await?;
+                Ok((page.resource_templates, page.next_cursor))
+            })
+            .await;
+            let elapsed = Some(started.elapsed());
+            match result {
+                Ok(templates) => {
+                    report.record(print, "resources/templates/list", elapsed, Ok(()));
+                    if print {
+                        for template in &templates {
+                            print_listing(
+                                &template.uri_template,
+                                Some(&template.name),
+                                template.description.as_deref(),
+                            );
+                        }
+                    }
+                    report.resource_templates = Some(templates);
+                }
+                Err(err) => {
+                    report.record(
+                        print,
+                        "resources/templates/list",
+                        elapsed,
+                        Err(format!("{err:#}")),
+                    );
+                }
+            }
+        } else {
+            report.skip(
+                print,
+                "resources/list",
+                "server does not advertise resources",
+            );
+            report.skip(
+                print,
+                "resources/templates/list",
+                "server does not advertise resources",
+            );
+        }
+
+        if capabilities.prompts.is_some() {
+            let started = Instant::now();
+            let result = collect_pages(|cursor| async move {
+                let page = client
+                    .list_prompts(
+                        cursor.map(|cursor| ListPromptsRequestParams {
+                            cursor: Some(cursor),
+                        }),
+                        Some(list_timeout),
+                    )
+                    .await?;
+                Ok((page.prompts, page.next_cursor))
+            })
+            .await;
+            let elapsed = Some(started.elapsed());
+            match result {
+                Ok(prompts) => {
+                    report.record(print, "prompts/list", elapsed, Ok(()));
+                    if print {
+                        print_prompts(&prompts);
+                    }
+                    report.prompts = Some(prompts);
+                }
+                Err(err) => {
+                    report.record(print, "prompts/list", elapsed, Err(format!("{err:#}")));
+                }
+            }
+        } else {
+            report.skip(print, "prompts/list", "server does not advertise prompts");
+        }
+
+        if let Some(tool) = call {
+            let step = format!("tools/call {tool}");
+            let started = Instant::now();
+            let result = client.call_tool(tool, args, Some(tool_timeout)).await;
+            let elapsed = Some(started.elapsed());
+            match result {
+                Ok(result) => {
+                    let outcome = if result.is_error == Some(true) {
+                        Err("tool reported an error".to_string())
+                    } else {
+                        Ok(())
+                    };
+                    report.record(print, step, elapsed, outcome);
+                    if print {
+                        let output = serde_json::to_string_pretty(&result)?;
+                        for line in output.lines() {
+                            println!("  {line}");
+                        }
+                    }
+                    report.tool_call = Some(result);
+                }
+                Err(err) => {
+                    report.record(print, step, elapsed, Err(format!("{err:#}")));
+                }
+            }
+        }
+    } else if call.is_some() {
+        report.skip(print, "tools/call", "server did not start");
+    }
+
+    let failed = report.failed_steps();
+    report.ok = failed == 0;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.ok {
+        println!("All checks passed.");
+    }
+    if failed > 0 {
+        bail!("{failed} check(s) failed for MCP server '{name}'.");
+    }
+    Ok(())
+}
+
+/// Fetches every page of a paginated MCP list request.
+async fn collect_pages<T, F, Fut>(mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>)>>,
+{
+    let mut items = Vec::new();
+    let mut cursor = None;
+    for _ in 0..MAX_LIST_PAGES {
+        let (page, next_cursor) = fetch(cursor).await?;
+        items.extend(page);
+        match next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => return Ok(items),
+        }
+    }
+    bail!("server returned more than {MAX_LIST_PAGES} pages")
+}
+
+fn print_initialize(initialize: &InitializeResult) {
+    let server_info = &initialize.server_info;
+    println!("  server: {} {}", server_info.name, server_info.version);
+    println!("  protocol: {}", initialize.protocol_version);
+    let capabilities = &initialize.capabilities;
+    let advertised: Vec<&str> = [
+        ("tools", capabilities.tools.is_some()),
+        ("resources", capabilities.resources.is_some()),
+        ("prompts", capabilities.prompts.is_some()),
+        ("logging", capabilities.logging.is_some()),
+        ("completions", capabilities.completions.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, present)| present.then_some(name))
+    .collect();
+    let advertised = if advertised.is_empty() {
+        "-".to_string()
+    } else {
+        advertised.join(", ")
+    };
+    println!("  capabilities: {advertised}");
+    if let Some(instructions) = &initialize.instructions {
+        println!("  instructions:");
+        for line in instructions.lines() {
+            println!("    {line}");
+        }
+    }
+}
+
+fn print_tools(tools: &[Tool]) {
+    for tool in tools {
+        print_listing(&tool.name, None, tool.description.as_deref());
+        let schema = serde_json::to_string(&tool.input_schema).unwrap_or_default();
+        println!("      input schema: {schema}");
+        if let Some(output_schema) = &tool.output_schema {
+            let schema = serde_json::to_string(output_schema).unwrap_or_default();
+            println!("      output schema: {schema}");
+        }
+    }
+}
+
+fn print_prompts(prompts: &[Prompt]) {
+    for prompt in prompts {
+        print_listing(&prompt.name, None, prompt.description.as_deref());
+        let arguments: Vec<String> = prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|argument| {
+                if argument.required == Some(true) {
+                    format!("{} (required)", argument.name)
+                } else {
+                    argument.name.clone()
+                }
+            })
+            .collect();
+        if !arguments.is_empty() {
+            println!("      arguments: {}", arguments.join(", "));
+        }
+    }
+}
+
+fn print_listing(id: &str, name: Option<&str>, description: Option<&str>) {
+    let mut line = format!("  - {id}");
+    if let Some(name) = name.filter(|name| *name != id) {
+        line.push_str(&format!(" ({name})"));
+    }
+    if let Some(description) = description.and_then(|description| description.lines().next()) {
+        line.push_str(&format!(": {description}"));
+    }
+    println!("{line}");
+}
+
+fn parse_tool_args(raw: &str) -> Result<serde_json::Value, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|err| format!("invalid JSON: {err}"))?;
+    if value.is_object() {
+        Ok(value)
+    } else {
+        Err("tool arguments must be a JSON object".to_string())
+    }
+}
+
 fn parse_env_pair(raw: &str) -> Result<(String, String), String> {
     let mut parts = raw.splitn(2, '=');
     let key = parts
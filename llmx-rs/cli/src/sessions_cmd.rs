@@ -0,0 +1,103 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use llmx_core::ExportFormat;
+use llmx_core::config::find_llmx_home;
+use llmx_core::find_conversation_path_by_id_str;
+use llmx_core::load_session_export;
+
+/// Work with recorded sessions.
+///
+/// Subcommands:
+/// - `export` — render a session as Markdown, HTML, or JSON
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Render a recorded session for sharing (e.g. in a PR or incident write-up).
+    Export(ExportArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// Session id (UUID) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "ID|PATH")]
+    pub session: String,
+
+    /// Output format.
+    #[arg(long, short = 'f', value_enum, default_value_t = ExportFormatArg::Md)]
+    pub format: ExportFormatArg,
+
+    /// Mask environment variable values and paths outside the session's repository.
+    #[arg(long, default_value_t = false)]
+    pub redact: bool,
+
+    /// Write the export to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormatArg {
+    Md,
+    Html,
+    Json,
+}
+
+impl From<ExportFormatArg> for ExportFormat {
+    fn from(value: ExportFormatArg) -> Self {
+        match value {
+            ExportFormatArg::Md => ExportFormat::Markdown,
+            ExportFormatArg::Html => ExportFormat::Html,
+            ExportFormatArg::Json => ExportFormat::Json,
+        }
+    }
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        match self.subcommand {
+            SessionsSubcommand::Export(args) => run_export(args).await,
+        }
+    }
+}
+
+async fn run_export(args: ExportArgs) -> Result<()> {
+    let rollout_path = resolve_session(&args.session).await?;
+    let mut export = load_session_export(&rollout_path)
+        .await
+        .with_context(|| format!("failed to read session {}", rollout_path.display()))?;
+    if args.redact {
+        export.redact();
+    }
+    let rendered = export.render(args.format.into())?;
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("Exported session to {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// Accepts either a path to a rollout file or a session id under `$LLMX_HOME/sessions`.
+async fn resolve_session(session: &str) -> Result<PathBuf> {
+    let path = Path::new(session);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let llmx_home = find_llmx_home().context("failed to locate LLMX home directory")?;
+    match find_conversation_path_by_id_str(&llmx_home, session).await? {
+        Some(path) => Ok(path),
+        None => bail!("no session found for `{session}` (expected a session id or rollout path)"),
+    }
+}
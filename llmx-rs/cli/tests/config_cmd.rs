@@ -0,0 +1,86 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn llmx_command(llmx_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("llmx")?;
+    cmd.env("LLMX_HOME", llmx_home);
+    Ok(cmd)
+}
+
+fn run(llmx_home: &Path, args: &[&str]) -> Result<String> {
+    let output = llmx_command(llmx_home)?.args(args).output()?;
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// A user config and a trusted project config that both set `model`.
+fn write_layers(root: &Path) -> Result<(PathBuf, PathBuf)> {
+    let llmx_home = root.join("home");
+    let project = root.join("project");
+    std::fs::create_dir_all(&llmx_home)?;
+    std::fs::create_dir_all(project.join(".llmx"))?;
+    std::fs::write(
+        llmx_home.join("config.toml"),
+        format!(
+            r#"model = "user-model"
+
+[projects."{}"]
+trust_level = "trusted"
+"#,
+            project.display()
+        ),
+    )?;
+    std::fs::write(
+        project.join(".llmx").join("config.toml"),
+        "model = \"project-model\"\n",
+    )?;
+    Ok((llmx_home, project))
+}
+
+#[test]
+fn get_show_and_explain_agree_on_layered_key() -> Result<()> {
+    let tmp = TempDir::new()?;
+    let (llmx_home, project) = write_layers(&tmp.path().canonicalize()?)?;
+    let project = project.to_string_lossy().to_string();
+
+    let get = run(&llmx_home, &["config", "get", "model", "--cd", &project])?;
+    assert_eq!(get, "user-model\n");
+
+    let show = run(&llmx_home, &["config", "show", "--cd", &project])?;
+    assert!(
+        show.lines().any(|line| line == r#"model = "user-model""#),
+        "unexpected show output: {show}"
+    );
+
+    let effective = run(
+        &llmx_home,
+        &["config", "show", "--effective", "--cd", &project],
+    )?;
+    assert!(
+        effective
+            .lines()
+            .any(|line| line == r#"model = "user-model""#),
+        "unexpected effective output: {effective}"
+    );
+
+    let explain = run(
+        &llmx_home,
+        &["config", "explain", "model", "--cd", &project],
+    )?;
+    assert_eq!(
+        explain.lines().next(),
+        Some(r#"model = "user-model""#),
+        "unexpected explain output: {explain}"
+    );
+    assert!(explain.contains("project ("), "{explain}");
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use anyhow::Result;
+use escargot::CargoBuild;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn llmx_command(llmx_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("llmx")?;
+    cmd.env("LLMX_HOME", llmx_home);
+    Ok(cmd)
+}
+
+fn step_statuses(report: &JsonValue) -> Vec<(String, String)> {
+    report["steps"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|step| {
+            (
+                step["step"].as_str().unwrap_or_default().to_string(),
+                step["status"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_reports_capabilities_and_calls_tool() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    let server_bin = CargoBuild::new()
+        .package("llmx-rmcp-client")
+        .bin("test_stdio_server")
+        .run()?
+        .path()
+        .to_string_lossy()
+        .into_owned();
+
+    let mut add = llmx_command(llmx_home.path())?;
+    add.args([
+        "mcp",
+        "add",
+        "echo",
+        "--env",
+        "MCP_TEST_VALUE=from-config",
+        "--",
+        &server_bin,
+    ])
+    .assert()
+    .success();
+
+    let mut test_cmd = llmx_command(llmx_home.path())?;
+    let output = test_cmd
+        .args([
+            "mcp",
+            "test",
+            "echo",
+            "--json",
+            "--call",
+            "echo",
+            "--args",
+            r#"{"message":"hi"}"#,
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let report: JsonValue = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(report["ok"], json!(true));
+    assert_eq!(
+        step_statuses(&report),
+        vec![
+            ("initialize".to_string(), "ok".to_string()),
+            ("tools/list".to_string(), "ok".to_string()),
+            ("resources/list".to_string(), "ok".to_string()),
+            ("resources/templates/list".to_string(), "ok".to_string()),
+            ("prompts/list".to_string(), "skipped".to_string()),
+            ("tools/call echo".to_string(), "ok".to_string()),
+        ]
+    );
+    let tool_names: Vec<&str> = report["tools"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(tool_names, vec!["echo", "image"]);
+    assert_eq!(
+        report["tool_call"]["structuredContent"],
+        json!({ "echo": "ECHOING: hi", "env": "from-config" })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fails_when_server_cannot_start() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+
+    let mut add = llmx_command(llmx_home.path())?;
+    add.args([
+        "mcp",
+        "add",
+        "broken",
+        "--",
+        "/nonexistent/llmx-mcp-test-server",
+    ])
+    .assert()
+    .success();
+
+    let mut test_cmd = llmx_command(llmx_home.path())?;
+    let output = test_cmd
+        .args(["mcp", "test", "broken", "--json"])
+        .output()?;
+    assert!(!output.status.success());
+    let report: JsonValue = serde_json::from_slice(&output.stdout)?;
+
+    assert_eq!(report["ok"], json!(false));
+    assert_eq!(
+        step_statuses(&report),
+        vec![("initialize".to_string(), "failed".to_string())]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn llmx_command(llmx_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::cargo_bin("llmx")?;
+    cmd.env("LLMX_HOME", llmx_home);
+    Ok(cmd)
+}
+
+fn write_rollout(dir: &Path) -> Result<std::path::PathBuf> {
+    let path = dir.join("rollout-2025-01-01T00-00-00-00000000-0000-0000-0000-000000000001.jsonl");
+    let mut file = std::fs::File::create(&path)?;
+    let lines = [
+        json!({
+            "timestamp": "2025-01-01T00:00:00Z",
+            "type": "session_meta",
+            "payload": {
+                "id": "00000000-0000-0000-0000-000000000001",
+                "timestamp": "2025-01-01T00:00:00Z",
+                "cwd": "/repo",
+                "originator": "llmx_cli_rs",
+                "cli_version": "0.0.0",
+                "instructions": null
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-01T00:00:01Z",
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "run the tests", "kind": "plain" }
+        }),
+        json!({
+            "timestamp": "2025-01-01T00:00:02Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"bash\",\"-lc\",\"TOKEN=secret cat /etc/hosts\"]}",
+                "call_id": "call-1"
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-01T00:00:03Z",
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "{\"output\":\"127.0.0.1 localhost\\n\",\"metadata\":{\"exit_code\":0,\"duration_seconds\":0.1}}"
+            }
+        }),
+        json!({
+            "timestamp": "2025-01-01T00:00:04Z",
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "All green." }
+        }),
+    ];
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    Ok(path)
+}
+
+#[test]
+fn exports_session_as_markdown() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    let rollout = write_rollout(llmx_home.path())?;
+
+    llmx_command(llmx_home.path())?
+        .args(["sessions", "export"])
+        .arg(&rollout)
+        .args(["--format", "md"])
+        .assert()
+        .success()
+        .stdout(contains("## User\n\nrun the tests"))
+        .stdout(contains("**Command** (exit 0)"))
+        .stdout(contains("$ TOKEN=secret cat /etc/hosts"))
+        .stdout(contains("## Assistant\n\nAll green."));
+    Ok(())
+}
+
+#[test]
+fn redacted_export_masks_env_values_and_outside_paths() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+    let rollout = write_rollout(llmx_home.path())?;
+    let output = llmx_home.path().join("session.json");
+
+    llmx_command(llmx_home.path())?
+        .args(["sessions", "export"])
+        .arg(&rollout)
+        .args(["--format", "json", "--redact", "--output"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    let exported: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    assert_eq!(
+        exported["entries"][1]["command"],
+        json!("TOKEN=*** cat <redacted path>")
+    );
+    assert_eq!(exported["entries"][1]["exit_code"], json!(0));
+    Ok(())
+}
+
+#[test]
+fn unknown_session_id_fails() -> Result<()> {
+    let llmx_home = TempDir::new()?;
+
+    llmx_command(llmx_home.path())?
+        .args(["sessions", "export", "not-a-session"])
+        .assert()
+        .failure()
+        .stderr(contains("no session found"));
+    Ok(())
+}
@@ -67,6 +67,7 @@ pub async fn load_auth_manager() -> Option<AuthManager> {
         config.llmx_home,
         false,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper.clone(),
     ))
 }
 
@@ -1,3 +1,4 @@
+use crate::approval_rules::RuleVerdict;
 use crate::function_tool::FunctionCallError;
 use crate::llmx::Session;
 use crate::llmx::TurnContext;
@@ -7,7 +8,9 @@ use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use llmx_apply_patch::ApplyPatchAction;
 use llmx_apply_patch::ApplyPatchFileChange;
+use llmx_apply_patch::MaybeApplyPatchVerified;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const LLMX_APPLY_PATCH_ARG1: &str = "--llmx-run-as-apply-patch";
@@ -31,6 +34,20 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Set when the user approved only part of the proposed patch. Describes
+    /// the changes that were not applied so the model does not retry them.
+    pub(crate) partial_approval_note: Option<String>,
+}
+
+impl ApplyPatchExec {
+    /// Appends [`ApplyPatchExec::partial_approval_note`], if any, to the
+    /// output returned to the model.
+    pub(crate) fn with_partial_approval_note(&self, content: String) -> String {
+        match &self.partial_approval_note {
+            Some(note) => format!("{content}\n\n{note}"),
+            None => content,
+        }
+    }
 }
 
 pub(crate) async fn apply_patch(
@@ -39,6 +56,23 @@ pub(crate) async fn apply_patch(
     call_id: &str,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
+    if turn_context.plan_mode {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            "patch rejected: file edits are unavailable in plan mode; record the change in the plan instead".to_string(),
+        )));
+    }
+    let verdict = sess
+        .services
+        .tool_approvals
+        .lock()
+        .await
+        .rules()
+        .check_patch(&patch_paths(&action));
+    if let Some(RuleVerdict::Deny(pattern)) = &verdict {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            format!("patch rejected by approval rule `{pattern}`"),
+        )));
+    }
     match assess_patch_safety(
         &action,
         turn_context.approval_policy,
@@ -51,7 +85,15 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            partial_approval_note: None,
         }),
+        SafetyCheck::AskUser if matches!(verdict, Some(RuleVerdict::Allow(_))) => {
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action,
+                user_explicitly_approved_this_action: true,
+                partial_approval_note: None,
+            })
+        }
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
             // approval request so the user can make an informed decision.
@@ -70,17 +112,36 @@ pub(crate) async fn apply_patch(
                 )
                 .await;
             match rx_approve.await.unwrap_or_default() {
-                ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+                ReviewDecision::Approved
+                | ReviewDecision::ApprovedForSession
+                | ReviewDecision::ApprovedAlways { .. } => {
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        partial_approval_note: None,
                     })
                 }
-                ReviewDecision::Denied | ReviewDecision::Abort => {
-                    InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
-                        "patch rejected by user".to_string(),
-                    )))
+                ReviewDecision::ApprovedPartial { patch } => {
+                    match parse_partial_approval(&action, &patch) {
+                        Ok(approved) => {
+                            let partial_approval_note =
+                                describe_unapplied_changes(&action, &approved);
+                            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                                action: approved,
+                                user_explicitly_approved_this_action: true,
+                                partial_approval_note,
+                            })
+                        }
+                        Err(message) => InternalApplyPatchInvocation::Output(Err(
+                            FunctionCallError::RespondToModel(message),
+                        )),
+                    }
                 }
+                ReviewDecision::Denied
+                | ReviewDecision::DeniedAlways { .. }
+                | ReviewDecision::Abort => InternalApplyPatchInvocation::Output(Err(
+                    FunctionCallError::RespondToModel("patch rejected by user".to_string()),
+                )),
             }
         }
         SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
@@ -89,6 +150,152 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Parses the patch the user approved in place of `original`. It may only
+/// touch files that `original` touches.
+fn parse_partial_approval(
+    original: &ApplyPatchAction,
+    patch: &str,
+) -> Result<ApplyPatchAction, String> {
+    let argv = ["apply_patch".to_string(), patch.to_string()];
+    let approved = match llmx_apply_patch::maybe_parse_apply_patch_verified(&argv, &original.cwd) {
+        MaybeApplyPatchVerified::Body(approved) => approved,
+        MaybeApplyPatchVerified::CorrectnessError(err) => {
+            return Err(format!(
+                "patch rejected: the part of the patch approved by the user could not be applied: {err}"
+            ));
+        }
+        MaybeApplyPatchVerified::ShellParseError(_) | MaybeApplyPatchVerified::NotApplyPatch => {
+            return Err(
+                "patch rejected: the part of the patch approved by the user is not a valid patch"
+                    .to_string(),
+            );
+        }
+    };
+    if approved.is_empty() {
+        return Err("patch rejected by user".to_string());
+    }
+    if let Some(path) = approved
+        .changes()
+        .keys()
+        .find(|path| !original.changes().contains_key(*path))
+    {
+        return Err(format!(
+            "patch rejected: the part of the patch approved by the user touches {}, which the proposed patch does not",
+            path.display()
+        ));
+    }
+    Ok(approved)
+}
+
+/// Lists the changes of `original` that `approved` does not apply verbatim,
+/// or `None` when every proposed change was applied.
+fn describe_unapplied_changes(
+    original: &ApplyPatchAction,
+    approved: &ApplyPatchAction,
+) -> Option<String> {
+    let mut paths: Vec<&PathBuf> = original.changes().keys().collect();
+    paths.sort();
+
+    let mut sections = Vec::new();
+    for path in paths {
+        let display = display_path(path, &original.cwd);
+        let proposed = &original.changes()[path];
+        let Some(applied) = approved.changes().get(path) else {
+            sections.push(format!("{display}: all changes rejected"));
+            continue;
+        };
+        match (proposed, applied) {
+            (
+                ApplyPatchFileChange::Update {
+                    unified_diff: proposed_diff,
+                    move_path: proposed_move,
+                    ..
+                },
+                ApplyPatchFileChange::Update {
+                    unified_diff: applied_diff,
+                    move_path: applied_move,
+                    ..
+                },
+            ) => {
+                let applied_hunks = diff_hunks(applied_diff);
+                let unapplied: Vec<&str> = diff_hunks(proposed_diff)
+                    .into_iter()
+                    .filter(|hunk| {
+                        !applied_hunks
+                            .iter()
+                            .any(|applied| hunk_body(applied) == hunk_body(hunk))
+                    })
+                    .collect();
+                let mut section = Vec::new();
+                if proposed_move != applied_move {
+                    section.push(format!("{display}: rename rejected"));
+                }
+                if !unapplied.is_empty() {
+                    section.push(format!(
+                        "{display}: these hunks were rejected or edited by the user:"
+                    ));
+                    section.extend(unapplied.iter().map(|hunk| hunk.trim_end().to_string()));
+                }
+                if !section.is_empty() {
+                    sections.push(section.join("\n"));
+                }
+            }
+            (proposed, applied) if proposed != applied => {
+                sections.push(format!("{display}: contents edited by the user"));
+            }
+            _ => {}
+        }
+    }
+
+    if sections.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "The user approved only part of this patch. The following proposed changes were NOT applied as written; do not re-apply them unless the user asks you to:\n{}",
+        sections.join("\n")
+    ))
+}
+
+/// Splits a unified diff into its hunks, each starting at its `@@` header.
+fn diff_hunks(unified_diff: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = unified_diff
+        .match_indices("@@ ")
+        .filter(|(idx, _)| *idx == 0 || unified_diff[..*idx].ends_with('\n'))
+        .map(|(idx, _)| idx)
+        .collect();
+    starts.push(unified_diff.len());
+    starts
+        .windows(2)
+        .map(|range| &unified_diff[range[0]..range[1]])
+        .collect()
+}
+
+/// The lines of a hunk without its header, whose line numbers shift when
+/// earlier hunks are left out.
+fn hunk_body(hunk: &str) -> &str {
+    hunk.split_once('\n').map_or("", |(_, body)| body)
+}
+
+fn display_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+/// Every path `action` writes, including move destinations.
+fn patch_paths(action: &ApplyPatchAction) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for (path, change) in action.changes() {
+        paths.push(path.clone());
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(dest),
+            ..
+        } = change
+        {
+            paths.push(dest.clone());
+        }
+    }
+    paths
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -139,4 +346,63 @@ mod tests {
             })
         );
     }
+
+    fn parse_action(patch: &str, cwd: &Path) -> ApplyPatchAction {
+        let argv = ["apply_patch".to_string(), patch.to_string()];
+        match llmx_apply_patch::maybe_parse_apply_patch_verified(&argv, cwd) {
+            MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected a valid patch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn partial_approval_reports_rejected_hunks() {
+        let tmp = tempdir().expect("tmp");
+        std::fs::write(tmp.path().join("f.txt"), "a\nb\nc\nd\ne\nf\ng\nh\n").expect("write");
+        std::fs::write(tmp.path().join("g.txt"), "x\n").expect("write");
+        let original = parse_action(
+            "*** Begin Patch\n*** Update File: f.txt\n@@\n a\n-b\n+B\n@@\n g\n-h\n+H\n*** Delete File: g.txt\n*** End Patch",
+            tmp.path(),
+        );
+
+        let approved = parse_partial_approval(
+            &original,
+            "*** Begin Patch\n*** Update File: f.txt\n@@\n a\n-b\n+B\n*** End Patch",
+        )
+        .expect("subset of the proposed patch");
+        assert_eq!(
+            describe_unapplied_changes(&original, &approved),
+            Some(
+                "The user approved only part of this patch. The following proposed changes were NOT applied as written; do not re-apply them unless the user asks you to:\n\
+                 f.txt: these hunks were rejected or edited by the user:\n\
+                 @@ -7,2 +7,2 @@\n g\n-h\n+H\n\
+                 g.txt: all changes rejected"
+                    .to_string()
+            )
+        );
+        assert_eq!(describe_unapplied_changes(&original, &original), None);
+    }
+
+    #[test]
+    fn partial_approval_must_stay_within_the_proposed_patch() {
+        let tmp = tempdir().expect("tmp");
+        std::fs::write(tmp.path().join("f.txt"), "a\n").expect("write");
+        let original = parse_action(
+            "*** Begin Patch\n*** Update File: f.txt\n@@\n-a\n+b\n*** End Patch",
+            tmp.path(),
+        );
+
+        let err = parse_partial_approval(
+            &original,
+            "*** Begin Patch\n*** Add File: other.txt\n+x\n*** End Patch",
+        )
+        .expect_err("touches a file the model did not propose to change");
+        assert_eq!(
+            err,
+            format!(
+                "patch rejected: the part of the patch approved by the user touches {}, which the proposed patch does not",
+                tmp.path().join("other.txt").display()
+            )
+        );
+    }
 }
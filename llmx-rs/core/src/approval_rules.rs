@@ -0,0 +1,708 @@
+//! Persistent, pattern-based approval rules.
+//!
+//! Rules are read from `approvals.toml` files and apply across sessions:
+//! - `$LLMX_HOME/projects/<key>/approvals.toml`, one per project, where rules
+//!   picked from an approval prompt are saved, and
+//! - `approvals.toml` in the `.llmx/` directories of a trusted project, for
+//!   rules checked into the repository.
+//!
+//! ```toml
+//! [commands]
+//! allow = ["cargo test *", "npm run lint"]
+//! deny = ["git push *"]
+//!
+//! [patches]
+//! allow = ["src/*"]
+//! deny = [".github/*"]
+//! ```
+//!
+//! Command patterns are globs over the command line, where `*` matches any
+//! text and a trailing ` *` also matches the bare prefix. A `bash -lc` script
+//! is allowed only when every command in it is allowed, and scripts that
+//! cannot be split into plain commands are never allowed by a rule. Patch
+//! patterns are globs over the paths a patch touches, relative to the project
+//! root. Deny rules win over allow rules.
+
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use llmx_protocol::protocol::ApprovalRule;
+use llmx_protocol::protocol::ApprovalRuleAction;
+use llmx_protocol::protocol::ApprovalRuleEntry;
+use llmx_protocol::protocol::ApprovalRuleKind;
+use serde::Deserialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+use toml_edit::DocumentMut;
+use wildmatch::WildMatchPattern;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::config_loader::find_project_config_dirs;
+use crate::git_info::get_git_repo_root;
+
+/// Name of the file rules are stored in.
+pub const APPROVALS_TOML_FILE: &str = "approvals.toml";
+
+/// Directory under `$LLMX_HOME` holding per-project state.
+const PROJECTS_DIR: &str = "projects";
+
+/// Tools whose first argument picks a subcommand, so `git status` and
+/// `git push` get separate suggestions.
+const SUBCOMMAND_TOOLS: &[&str] = &[
+    "bun", "cargo", "deno", "docker", "dotnet", "gh", "git", "go", "gradle", "kubectl", "make",
+    "mvn", "npm", "pip", "pnpm", "poetry", "rustup", "uv", "yarn",
+];
+
+/// Subcommands whose next argument names a script (`npm run lint`).
+const SCRIPT_SUBCOMMANDS: &[&str] = &["run", "exec"];
+
+type Pattern = WildMatchPattern<'*', '?'>;
+
+#[derive(Deserialize, Debug, Default)]
+struct ApprovalsToml {
+    #[serde(default)]
+    commands: RuleLists,
+    #[serde(default)]
+    patches: RuleLists,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct RuleLists {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+/// Outcome of checking a request against the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RuleVerdict {
+    /// Allowed by the rule with this pattern.
+    Allow(String),
+    /// Rejected by the rule with this pattern.
+    Deny(String),
+}
+
+/// The rules in effect for one project.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ApprovalRules {
+    project_root: PathBuf,
+    /// Where new rules are saved. `None` keeps them in memory only.
+    user_file: Option<PathBuf>,
+    /// Files rules are read from, `user_file` first.
+    files: Vec<PathBuf>,
+    entries: Vec<ApprovalRuleEntry>,
+}
+
+impl ApprovalRules {
+    /// Loads the rules for the project containing `cwd`. Checked-in rules are
+    /// only read when the project is trusted.
+    pub(crate) fn load(llmx_home: &Path, cwd: &Path, trusted: bool) -> Self {
+        let project_root = project_root(cwd);
+        let user_file = user_rules_path(llmx_home, &project_root);
+        let mut files = vec![user_file.clone()];
+        if trusted {
+            files.extend(
+                find_project_config_dirs(cwd, llmx_home)
+                    .into_iter()
+                    .rev()
+                    .map(|dir| dir.join(APPROVALS_TOML_FILE)),
+            );
+        }
+        let mut rules = Self {
+            project_root,
+            user_file: Some(user_file),
+            files,
+            entries: Vec::new(),
+        };
+        rules.reload();
+        rules
+    }
+
+    fn reload(&mut self) {
+        self.entries = self
+            .files
+            .iter()
+            .flat_map(|path| read_rules(path))
+            .collect();
+    }
+
+    pub(crate) fn entries(&self) -> &[ApprovalRuleEntry] {
+        &self.entries
+    }
+
+    fn patterns(
+        &self,
+        kind: ApprovalRuleKind,
+        action: ApprovalRuleAction,
+    ) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .map(|entry| &entry.rule)
+            .filter(move |rule| rule.kind == kind && rule.action == action)
+            .map(|rule| rule.pattern.as_str())
+    }
+
+    pub(crate) fn check_command(&self, command: &[String]) -> Option<RuleVerdict> {
+        let Some(commands) = split_commands(command) else {
+            // Only deny rules apply to scripts we cannot split; look for a
+            // match anywhere in the script.
+            let text = command_text(command);
+            return self
+                .patterns(ApprovalRuleKind::Command, ApprovalRuleAction::Deny)
+                .find(|pattern| Pattern::new(&format!("*{pattern}*")).matches(&text))
+                .map(|pattern| RuleVerdict::Deny(pattern.to_string()));
+        };
+        let texts: Vec<String> = commands.iter().map(|words| command_text(words)).collect();
+        if let Some(pattern) = self
+            .patterns(ApprovalRuleKind::Command, ApprovalRuleAction::Deny)
+            .find(|pattern| texts.iter().any(|text| command_matches(pattern, text)))
+        {
+            return Some(RuleVerdict::Deny(pattern.to_string()));
+        }
+        let mut allowed_by = None;
+        for text in &texts {
+            let pattern = self
+                .patterns(ApprovalRuleKind::Command, ApprovalRuleAction::Allow)
+                .find(|pattern| command_matches(pattern, text))?;
+            allowed_by.get_or_insert(pattern);
+        }
+        allowed_by.map(|pattern| RuleVerdict::Allow(pattern.to_string()))
+    }
+
+    /// Checks a patch touching `paths`. Paths outside the project root are
+    /// never allowed by a rule.
+    pub(crate) fn check_patch(&self, paths: &[PathBuf]) -> Option<RuleVerdict> {
+        let relative: Vec<Option<String>> = paths
+            .iter()
+            .map(|path| relative_path(&self.project_root, path))
+            .collect();
+        if let Some(pattern) = self
+            .patterns(ApprovalRuleKind::Patch, ApprovalRuleAction::Deny)
+            .find(|pattern| {
+                let pattern = Pattern::new(pattern);
+                relative.iter().flatten().any(|path| pattern.matches(path))
+            })
+        {
+            return Some(RuleVerdict::Deny(pattern.to_string()));
+        }
+        if relative.is_empty() {
+            return None;
+        }
+        let mut allowed_by = None;
+        for path in &relative {
+            let path = path.as_deref()?;
+            let pattern = self
+                .patterns(ApprovalRuleKind::Patch, ApprovalRuleAction::Allow)
+                .find(|pattern| Pattern::new(pattern).matches(path))?;
+            allowed_by.get_or_insert(pattern);
+        }
+        allowed_by.map(|pattern| RuleVerdict::Allow(pattern.to_string()))
+    }
+
+    /// Saves `rule` to the project's rules file under `$LLMX_HOME`.
+    pub(crate) fn add(&mut self, rule: ApprovalRule) -> io::Result<()> {
+        if self.entries.iter().any(|entry| entry.rule == rule) {
+            return Ok(());
+        }
+        match self.user_file.clone() {
+            Some(path) => {
+                edit_rules_file(&path, &self.project_root, &rule, true)?;
+                self.reload();
+            }
+            None => self.entries.push(ApprovalRuleEntry {
+                rule,
+                path: PathBuf::new(),
+            }),
+        }
+        Ok(())
+    }
+
+    /// Removes `rule` from every file that holds it.
+    pub(crate) fn revoke(&mut self, rule: &ApprovalRule) -> io::Result<()> {
+        if self.user_file.is_none() {
+            self.entries.retain(|entry| &entry.rule != rule);
+            return Ok(());
+        }
+        let mut paths: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|entry| &entry.rule == rule)
+            .map(|entry| entry.path.clone())
+            .collect();
+        paths.dedup();
+        let result = paths
+            .iter()
+            .try_for_each(|path| edit_rules_file(path, &self.project_root, rule, false));
+        self.reload();
+        result
+    }
+}
+
+/// The directory rules are scoped to: the git repository root, or `cwd`
+/// outside a repository.
+pub fn project_root(cwd: &Path) -> PathBuf {
+    get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf())
+}
+
+/// `$LLMX_HOME/projects/<key>/approvals.toml` for the project at `root`.
+pub fn user_rules_path(llmx_home: &Path, root: &Path) -> PathBuf {
+    let canonical = dunce::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let digest = Sha256::digest(canonical.to_string_lossy().as_bytes());
+    let hex = format!("{digest:x}");
+    let key = hex.get(..16).unwrap_or(&hex);
+    llmx_home
+        .join(PROJECTS_DIR)
+        .join(key)
+        .join(APPROVALS_TOML_FILE)
+}
+
+/// Proposes a rule covering `command` and similar invocations, e.g.
+/// `cargo test *` for `cargo test -p foo`. Returns `None` for scripts with
+/// more than one command.
+pub fn suggest_command_pattern(command: &[String]) -> Option<String> {
+    let mut commands = split_commands(command)?;
+    if commands.len() != 1 {
+        return None;
+    }
+    let words = commands.pop()?;
+    let program = words.first()?;
+    let program_name = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    let is_subcommand =
+        |word: &String| !word.starts_with('-') && !word.contains(['/', '\\', '.', '=']);
+    let mut keep = 1;
+    if SUBCOMMAND_TOOLS.contains(&program_name) && words.get(1).is_some_and(is_subcommand) {
+        keep = 2;
+        if SCRIPT_SUBCOMMANDS.contains(&words[1].as_str())
+            && words.get(2).is_some_and(is_subcommand)
+        {
+            keep = 3;
+        }
+    }
+    let prefix = command_text(&words[..keep]);
+    Some(if words.len() > keep {
+        format!("{prefix} *")
+    } else {
+        prefix
+    })
+}
+
+/// Proposes a path glob covering the files a patch touches: their common
+/// directory under the project root, or the file itself for a single file at
+/// the root.
+pub fn suggest_patch_pattern(cwd: &Path, paths: &[PathBuf]) -> Option<String> {
+    let root = project_root(cwd);
+    let relative = paths
+        .iter()
+        .map(|path| relative_path(&root, &cwd.join(path)))
+        .collect::<Option<Vec<String>>>()?;
+    let mut dirs = relative.iter().map(|path| match path.rsplit_once('/') {
+        Some((dir, _)) => dir,
+        None => "",
+    });
+    let first = dirs.next()?;
+    let common = dirs.fold(first.to_string(), |common, dir| {
+        let mut shared = Vec::new();
+        for (a, b) in common.split('/').zip(dir.split('/')) {
+            if a != b {
+                break;
+            }
+            shared.push(a);
+        }
+        shared.join("/")
+    });
+    match (common.is_empty(), relative.as_slice()) {
+        (false, _) => Some(format!("{common}/*")),
+        (true, [only]) => Some(only.clone()),
+        (true, _) => None,
+    }
+}
+
+/// The plain commands in `command`, or `None` for a shell script that is not
+/// just plain commands joined by `&&`, `||`, `;` or `|`.
+fn split_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
+    if let Some(commands) = parse_shell_lc_plain_commands(command) {
+        return (!commands.is_empty()).then_some(commands);
+    }
+    if extract_bash_command(command).is_some() || command.is_empty() {
+        return None;
+    }
+    Some(vec![command.to_vec()])
+}
+
+fn command_text(words: &[String]) -> String {
+    shlex::try_join(words.iter().map(String::as_str)).unwrap_or_else(|_| words.join(" "))
+}
+
+fn command_matches(pattern: &str, text: &str) -> bool {
+    Pattern::new(pattern).matches(text)
+        || pattern
+            .strip_suffix(" *")
+            .is_some_and(|prefix| prefix == text)
+}
+
+/// `path` relative to `root` with `/` separators, or `None` if it is outside.
+/// `.` and `..` are resolved lexically first, so `src/../.github/ci.yml` is
+/// matched as `.github/ci.yml`.
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    let relative = normalized.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn read_rules(path: &Path) -> Vec<ApprovalRuleEntry> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => {
+            tracing::warn!("failed to read {}: {err}", path.display());
+            return Vec::new();
+        }
+    };
+    let parsed: ApprovalsToml = match toml::from_str(&contents) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            tracing::warn!("ignoring {}: {err}", path.display());
+            return Vec::new();
+        }
+    };
+    let lists = [
+        (
+            ApprovalRuleKind::Command,
+            ApprovalRuleAction::Allow,
+            parsed.commands.allow,
+        ),
+        (
+            ApprovalRuleKind::Command,
+            ApprovalRuleAction::Deny,
+            parsed.commands.deny,
+        ),
+        (
+            ApprovalRuleKind::Patch,
+            ApprovalRuleAction::Allow,
+            parsed.patches.allow,
+        ),
+        (
+            ApprovalRuleKind::Patch,
+            ApprovalRuleAction::Deny,
+            parsed.patches.deny,
+        ),
+    ];
+    lists
+        .into_iter()
+        .flat_map(|(kind, action, patterns)| {
+            patterns.into_iter().map(move |pattern| ApprovalRuleEntry {
+                rule: ApprovalRule {
+                    kind,
+                    action,
+                    pattern,
+                },
+                path: path.to_path_buf(),
+            })
+        })
+        .collect()
+}
+
+/// Adds `rule` to, or removes it from, the rules file at `path`, keeping the
+/// rest of the file (including comments) intact.
+fn edit_rules_file(path: &Path, root: &Path, rule: &ApprovalRule, add: bool) -> io::Result<()> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let mut doc = contents
+        .parse::<DocumentMut>()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    if contents.is_empty() {
+        doc["project"] = toml_edit::value(root.to_string_lossy().as_ref());
+    }
+    let table = match rule.kind {
+        ApprovalRuleKind::Command => "commands",
+        ApprovalRuleKind::Patch => "patches",
+    };
+    let key = match rule.action {
+        ApprovalRuleAction::Allow => "allow",
+        ApprovalRuleAction::Deny => "deny",
+    };
+    let item = &mut doc[table][key];
+    if item.is_none() {
+        if !add {
+            return Ok(());
+        }
+        *item = toml_edit::value(toml_edit::Array::new());
+    }
+    let Some(array) = item.as_array_mut() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("`{table}.{key}` in {} is not an array", path.display()),
+        ));
+    };
+    if add {
+        array.push(rule.pattern.as_str());
+    } else {
+        array.retain(|value| value.as_str() != Some(rule.pattern.as_str()));
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let tmp = NamedTempFile::new_in(dir)?;
+    std::fs::write(tmp.path(), doc.to_string())?;
+    tmp.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn words(command: &[&str]) -> Vec<String> {
+        command.iter().map(ToString::to_string).collect()
+    }
+
+    fn rule(kind: ApprovalRuleKind, action: ApprovalRuleAction, pattern: &str) -> ApprovalRule {
+        ApprovalRule {
+            kind,
+            action,
+            pattern: pattern.to_string(),
+        }
+    }
+
+    #[test]
+    fn suggests_generalized_command_patterns() {
+        assert_eq!(
+            suggest_command_pattern(&words(&["cargo", "test", "-p", "foo"])),
+            Some("cargo test *".to_string())
+        );
+        assert_eq!(
+            suggest_command_pattern(&words(&["bash", "-lc", "npm run lint"])),
+            Some("npm run lint".to_string())
+        );
+        assert_eq!(
+            suggest_command_pattern(&words(&["echo", "hello", "world"])),
+            Some("echo *".to_string())
+        );
+        assert_eq!(
+            suggest_command_pattern(&words(&["bash", "-lc", "cd app && make"])),
+            None
+        );
+    }
+
+    #[test]
+    fn command_rules_match_every_command_in_a_script() {
+        let mut rules = ApprovalRules::default();
+        for added in [
+            rule(
+                ApprovalRuleKind::Command,
+                ApprovalRuleAction::Allow,
+                "cargo test *",
+            ),
+            rule(ApprovalRuleKind::Command, ApprovalRuleAction::Allow, "cd *"),
+            rule(
+                ApprovalRuleKind::Command,
+                ApprovalRuleAction::Deny,
+                "git push *",
+            ),
+        ] {
+            rules.add(added).expect("add rule");
+        }
+
+        let allow = Some(RuleVerdict::Allow("cargo test *".to_string()));
+        assert_eq!(rules.check_command(&words(&["cargo", "test"])), allow);
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "cargo test -p foo"])),
+            allow
+        );
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "cd core && cargo test"])),
+            Some(RuleVerdict::Allow("cd *".to_string()))
+        );
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "cargo test; rm -rf target"])),
+            None
+        );
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "cargo test > $(mktemp)"])),
+            None
+        );
+        let deny = Some(RuleVerdict::Deny("git push *".to_string()));
+        assert_eq!(
+            rules.check_command(&words(&["git", "push", "origin", "main"])),
+            deny
+        );
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "cargo test && git push origin"])),
+            deny
+        );
+        assert_eq!(
+            rules.check_command(&words(&["bash", "-lc", "git push origin $(git branch)"])),
+            deny
+        );
+    }
+
+    #[test]
+    fn patch_rules_are_scoped_to_the_project() {
+        let mut rules = ApprovalRules {
+            project_root: PathBuf::from("/repo"),
+            ..Default::default()
+        };
+        rules
+            .add(rule(
+                ApprovalRuleKind::Patch,
+                ApprovalRuleAction::Allow,
+                "src/*",
+            ))
+            .expect("add rule");
+        rules
+            .add(rule(
+                ApprovalRuleKind::Patch,
+                ApprovalRuleAction::Deny,
+                "src/generated/*",
+            ))
+            .expect("add rule");
+
+        let allow = Some(RuleVerdict::Allow("src/*".to_string()));
+        assert_eq!(
+            rules.check_patch(&[
+                PathBuf::from("/repo/src/lib.rs"),
+                PathBuf::from("/repo/src/a/b.rs")
+            ]),
+            allow
+        );
+        assert_eq!(
+            rules.check_patch(&[
+                PathBuf::from("/repo/src/lib.rs"),
+                PathBuf::from("/repo/README.md")
+            ]),
+            None
+        );
+        assert_eq!(
+            rules.check_patch(&[PathBuf::from("/elsewhere/src/lib.rs")]),
+            None
+        );
+        assert_eq!(
+            rules.check_patch(&[PathBuf::from("/repo/src/generated/api.rs")]),
+            Some(RuleVerdict::Deny("src/generated/*".to_string()))
+        );
+        assert_eq!(
+            rules.check_patch(&[PathBuf::from("/repo/src/../.github/workflows/ci.yml")]),
+            None
+        );
+        assert_eq!(
+            rules.check_patch(&[PathBuf::from("/repo/src/./../src/generated/api.rs")]),
+            Some(RuleVerdict::Deny("src/generated/*".to_string()))
+        );
+        assert_eq!(
+            rules.check_patch(&[PathBuf::from("/repo/src/../../elsewhere/lib.rs")]),
+            None
+        );
+
+        assert_eq!(
+            suggest_patch_pattern(
+                Path::new("/repo"),
+                &[
+                    PathBuf::from("/repo/src/a/b.rs"),
+                    PathBuf::from("src/a/c/d.rs")
+                ]
+            ),
+            Some("src/a/*".to_string())
+        );
+        assert_eq!(
+            suggest_patch_pattern(Path::new("/repo"), &[PathBuf::from("README.md")]),
+            Some("README.md".to_string())
+        );
+        assert_eq!(
+            suggest_patch_pattern(
+                Path::new("/repo"),
+                &[PathBuf::from("README.md"), PathBuf::from("src/lib.rs")]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn rules_persist_across_loads_and_can_be_revoked() {
+        let llmx_home = tempdir().expect("llmx home");
+        let project = tempdir().expect("project");
+        std::fs::create_dir(project.path().join(".git")).expect("git dir");
+        let checked_in = project.path().join(".llmx");
+        std::fs::create_dir(&checked_in).expect(".llmx dir");
+        std::fs::write(
+            checked_in.join(APPROVALS_TOML_FILE),
+            "# Shared rules\n[commands]\ndeny = [\"git push *\"]\n",
+        )
+        .expect("write project rules");
+
+        let allow = rule(
+            ApprovalRuleKind::Command,
+            ApprovalRuleAction::Allow,
+            "cargo test *",
+        );
+        let mut rules = ApprovalRules::load(llmx_home.path(), project.path(), true);
+        rules.add(allow.clone()).expect("add rule");
+
+        let user_file = user_rules_path(llmx_home.path(), project.path());
+        let reloaded = ApprovalRules::load(llmx_home.path(), project.path(), true);
+        assert_eq!(
+            reloaded.entries(),
+            &[
+                ApprovalRuleEntry {
+                    rule: allow.clone(),
+                    path: user_file.clone(),
+                },
+                ApprovalRuleEntry {
+                    rule: rule(
+                        ApprovalRuleKind::Command,
+                        ApprovalRuleAction::Deny,
+                        "git push *"
+                    ),
+                    path: checked_in.join(APPROVALS_TOML_FILE),
+                },
+            ]
+        );
+        assert_eq!(
+            ApprovalRules::load(llmx_home.path(), project.path(), false).entries(),
+            &[ApprovalRuleEntry {
+                rule: allow.clone(),
+                path: user_file,
+            }]
+        );
+
+        let mut rules = reloaded;
+        rules
+            .revoke(&rule(
+                ApprovalRuleKind::Command,
+                ApprovalRuleAction::Deny,
+                "git push *",
+            ))
+            .expect("revoke rule");
+        let shared = std::fs::read_to_string(checked_in.join(APPROVALS_TOML_FILE)).expect("read");
+        assert!(shared.starts_with("# Shared rules"), "{shared}");
+        assert!(!shared.contains("git push"), "{shared}");
+        assert_eq!(rules.entries().len(), 1);
+    }
+}
@@ -1,3 +1,5 @@
+pub(crate) mod client_credentials;
+mod provider_keys;
 mod storage;
 
 use chrono::Utc;
@@ -18,10 +20,14 @@ use std::time::Duration;
 use llmx_app_server_protocol::AuthMode;
 use llmx_protocol::config_types::ForcedLoginMethod;
 
+pub use crate::auth::provider_keys::ProviderKeyStore;
+pub use crate::auth::provider_keys::ProviderKeys;
+pub use crate::auth::provider_keys::migrate_provider_keys;
 pub use crate::auth::storage::AuthCredentialsStoreMode;
 pub use crate::auth::storage::AuthDotJson;
 use crate::auth::storage::AuthStorageBackend;
 use crate::auth::storage::create_auth_storage;
+use crate::auth::storage::migrate_auth_storage;
 use crate::config::Config;
 use crate::default_client::LlmxHttpClient;
 use crate::error::RefreshTokenFailedError;
@@ -131,8 +137,14 @@ impl LlmxAuth {
     pub fn from_auth_storage(
         llmx_home: &Path,
         auth_credentials_store_mode: AuthCredentialsStoreMode,
+        credential_helper: Option<&[String]>,
     ) -> std::io::Result<Option<LlmxAuth>> {
-        load_auth(llmx_home, false, auth_credentials_store_mode)
+        load_auth(
+            llmx_home,
+            false,
+            auth_credentials_store_mode,
+            credential_helper,
+        )
     }
 
     pub async fn get_token_data(&self) -> Result<TokenData, std::io::Error> {
@@ -262,7 +274,7 @@ impl LlmxAuth {
         Self {
             api_key: None,
             mode: AuthMode::ChatGPT,
-            storage: create_auth_storage(PathBuf::new(), AuthCredentialsStoreMode::File),
+            storage: create_auth_storage(PathBuf::new(), AuthCredentialsStoreMode::File, None),
             auth_dot_json,
             client: crate::default_client::create_client(),
         }
@@ -272,7 +284,7 @@ impl LlmxAuth {
         Self {
             api_key: Some(api_key.to_owned()),
             mode: AuthMode::ApiKey,
-            storage: create_auth_storage(PathBuf::new(), AuthCredentialsStoreMode::File),
+            storage: create_auth_storage(PathBuf::new(), AuthCredentialsStoreMode::File, None),
             auth_dot_json: Arc::new(Mutex::new(None)),
             client,
         }
@@ -305,8 +317,13 @@ pub fn read_llmx_api_key_from_env() -> Option<String> {
 pub fn logout(
     llmx_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<bool> {
-    let storage = create_auth_storage(llmx_home.to_path_buf(), auth_credentials_store_mode);
+    let storage = create_auth_storage(
+        llmx_home.to_path_buf(),
+        auth_credentials_store_mode,
+        credential_helper,
+    );
     storage.delete()
 }
 
@@ -315,13 +332,19 @@ pub fn login_with_api_key(
     llmx_home: &Path,
     api_key: &str,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<()> {
     let auth_dot_json = AuthDotJson {
         openai_api_key: Some(api_key.to_string()),
         tokens: None,
         last_refresh: None,
     };
-    save_auth(llmx_home, &auth_dot_json, auth_credentials_store_mode)
+    save_auth(
+        llmx_home,
+        &auth_dot_json,
+        auth_credentials_store_mode,
+        credential_helper,
+    )
 }
 
 /// Persist the provided auth payload using the specified backend.
@@ -329,18 +352,41 @@ pub fn save_auth(
     llmx_home: &Path,
     auth: &AuthDotJson,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<()> {
-    let storage = create_auth_storage(llmx_home.to_path_buf(), auth_credentials_store_mode);
+    let storage = create_auth_storage(
+        llmx_home.to_path_buf(),
+        auth_credentials_store_mode,
+        credential_helper,
+    );
     storage.save(auth)
 }
 
+/// Move stored CLI auth from one credential store backend to another.
+/// Returns `Ok(false)` when the source store holds no credentials.
+pub fn migrate_auth_store(
+    llmx_home: &Path,
+    from: AuthCredentialsStoreMode,
+    to: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> std::io::Result<bool> {
+    let from = create_auth_storage(llmx_home.to_path_buf(), from, credential_helper);
+    let to = create_auth_storage(llmx_home.to_path_buf(), to, credential_helper);
+    migrate_auth_storage(from.as_ref(), to.as_ref())
+}
+
 /// Load CLI auth data using the configured credential store backend.
 /// Returns `None` when no credentials are stored.
 pub fn load_auth_dot_json(
     llmx_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<Option<AuthDotJson>> {
-    let storage = create_auth_storage(llmx_home.to_path_buf(), auth_credentials_store_mode);
+    let storage = create_auth_storage(
+        llmx_home.to_path_buf(),
+        auth_credentials_store_mode,
+        credential_helper,
+    );
     storage.load()
 }
 
@@ -349,6 +395,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
         &config.llmx_home,
         true,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
     )?
     else {
         return Ok(());
@@ -373,6 +420,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
                 &config.llmx_home,
                 message,
                 config.cli_auth_credentials_store_mode,
+                config.credential_helper.as_deref(),
             );
         }
     }
@@ -391,6 +439,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
                         "Failed to load ChatGPT credentials while enforcing workspace restrictions: {err}. Logging out."
                     ),
                     config.cli_auth_credentials_store_mode,
+                    config.credential_helper.as_deref(),
                 );
             }
         };
@@ -410,6 +459,7 @@ pub async fn enforce_login_restrictions(config: &Config) -> std::io::Result<()>
                 &config.llmx_home,
                 message,
                 config.cli_auth_credentials_store_mode,
+                config.credential_helper.as_deref(),
             );
         }
     }
@@ -421,8 +471,9 @@ fn logout_with_message(
     llmx_home: &Path,
     message: String,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<()> {
-    match logout(llmx_home, auth_credentials_store_mode) {
+    match logout(llmx_home, auth_credentials_store_mode, credential_helper) {
         Ok(_) => Err(std::io::Error::other(message)),
         Err(err) => Err(std::io::Error::other(format!(
             "{message}. Failed to remove auth.json: {err}"
@@ -434,6 +485,7 @@ fn load_auth(
     llmx_home: &Path,
     enable_llmx_api_key_env: bool,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> std::io::Result<Option<LlmxAuth>> {
     if enable_llmx_api_key_env && let Some(api_key) = read_llmx_api_key_from_env() {
         let client = crate::default_client::create_client();
@@ -443,7 +495,11 @@ fn load_auth(
         )));
     }
 
-    let storage = create_auth_storage(llmx_home.to_path_buf(), auth_credentials_store_mode);
+    let storage = create_auth_storage(
+        llmx_home.to_path_buf(),
+        auth_credentials_store_mode,
+        credential_helper,
+    );
 
     let client = crate::default_client::create_client();
     let auth_dot_json = match storage.load()? {
@@ -665,6 +721,7 @@ mod tests {
         let storage = create_auth_storage(
             llmx_home.path().to_path_buf(),
             AuthCredentialsStoreMode::File,
+            None,
         );
         let updated = super::update_tokens(
             &storage,
@@ -700,7 +757,7 @@ mod tests {
         )
         .unwrap();
 
-        super::login_with_api_key(dir.path(), "sk-new", AuthCredentialsStoreMode::File)
+        super::login_with_api_key(dir.path(), "sk-new", AuthCredentialsStoreMode::File, None)
             .expect("login_with_api_key should succeed");
 
         let storage = FileAuthStorage::new(dir.path().to_path_buf());
@@ -714,7 +771,7 @@ mod tests {
     #[test]
     fn missing_auth_json_returns_none() {
         let dir = tempdir().unwrap();
-        let auth = LlmxAuth::from_auth_storage(dir.path(), AuthCredentialsStoreMode::File)
+        let auth = LlmxAuth::from_auth_storage(dir.path(), AuthCredentialsStoreMode::File, None)
             .expect("call should succeed");
         assert_eq!(auth, None);
     }
@@ -739,9 +796,14 @@ mod tests {
             auth_dot_json,
             storage: _,
             ..
-        } = super::load_auth(llmx_home.path(), false, AuthCredentialsStoreMode::File)
-            .unwrap()
-            .unwrap();
+        } = super::load_auth(
+            llmx_home.path(),
+            false,
+            AuthCredentialsStoreMode::File,
+            None,
+        )
+        .unwrap()
+        .unwrap();
         assert_eq!(None, api_key);
         assert_eq!(AuthMode::ChatGPT, mode);
 
@@ -782,7 +844,7 @@ mod tests {
         )
         .unwrap();
 
-        let auth = super::load_auth(dir.path(), false, AuthCredentialsStoreMode::File)
+        let auth = super::load_auth(dir.path(), false, AuthCredentialsStoreMode::File, None)
             .unwrap()
             .unwrap();
         assert_eq!(auth.mode, AuthMode::ApiKey);
@@ -799,10 +861,15 @@ mod tests {
             tokens: None,
             last_refresh: None,
         };
-        super::save_auth(dir.path(), &auth_dot_json, AuthCredentialsStoreMode::File)?;
+        super::save_auth(
+            dir.path(),
+            &auth_dot_json,
+            AuthCredentialsStoreMode::File,
+            None,
+        )?;
         let auth_file = get_auth_file(dir.path());
         assert!(auth_file.exists());
-        assert!(logout(dir.path(), AuthCredentialsStoreMode::File)?);
+        assert!(logout(dir.path(), AuthCredentialsStoreMode::File, None)?);
         assert!(!auth_file.exists());
         Ok(())
     }
@@ -919,8 +986,13 @@ mod tests {
     #[tokio::test]
     async fn enforce_login_restrictions_logs_out_for_method_mismatch() {
         let llmx_home = tempdir().unwrap();
-        login_with_api_key(llmx_home.path(), "sk-test", AuthCredentialsStoreMode::File)
-            .expect("seed api key");
+        login_with_api_key(
+            llmx_home.path(),
+            "sk-test",
+            AuthCredentialsStoreMode::File,
+            None,
+        )
+        .expect("seed api key");
 
         let config = build_config(llmx_home.path(), Some(ForcedLoginMethod::Chatgpt), None);
 
@@ -995,8 +1067,13 @@ mod tests {
     async fn enforce_login_restrictions_allows_api_key_if_login_method_not_set_but_forced_chatgpt_workspace_id_is_set()
      {
         let llmx_home = tempdir().unwrap();
-        login_with_api_key(llmx_home.path(), "sk-test", AuthCredentialsStoreMode::File)
-            .expect("seed api key");
+        login_with_api_key(
+            llmx_home.path(),
+            "sk-test",
+            AuthCredentialsStoreMode::File,
+            None,
+        )
+        .expect("seed api key");
 
         let config = build_config(llmx_home.path(), None, Some("org_mine".to_string()));
 
@@ -1039,9 +1116,14 @@ mod tests {
         )
         .expect("failed to write auth file");
 
-        let auth = super::load_auth(llmx_home.path(), false, AuthCredentialsStoreMode::File)
-            .expect("load auth")
-            .expect("auth available");
+        let auth = super::load_auth(
+            llmx_home.path(),
+            false,
+            AuthCredentialsStoreMode::File,
+            None,
+        )
+        .expect("load auth")
+        .expect("auth available");
 
         pretty_assertions::assert_eq!(auth.account_plan_type(), Some(AccountPlanType::Pro));
         pretty_assertions::assert_eq!(
@@ -1063,9 +1145,14 @@ mod tests {
         )
         .expect("failed to write auth file");
 
-        let auth = super::load_auth(llmx_home.path(), false, AuthCredentialsStoreMode::File)
-            .expect("load auth")
-            .expect("auth available");
+        let auth = super::load_auth(
+            llmx_home.path(),
+            false,
+            AuthCredentialsStoreMode::File,
+            None,
+        )
+        .expect("load auth")
+        .expect("auth available");
 
         pretty_assertions::assert_eq!(auth.account_plan_type(), Some(AccountPlanType::Unknown));
         pretty_assertions::assert_eq!(
@@ -1089,6 +1176,7 @@ pub struct AuthManager {
     inner: RwLock<CachedAuth>,
     enable_llmx_api_key_env: bool,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<Vec<String>>,
 }
 
 impl AuthManager {
@@ -1100,11 +1188,13 @@ impl AuthManager {
         llmx_home: PathBuf,
         enable_llmx_api_key_env: bool,
         auth_credentials_store_mode: AuthCredentialsStoreMode,
+        credential_helper: Option<Vec<String>>,
     ) -> Self {
         let auth = load_auth(
             &llmx_home,
             enable_llmx_api_key_env,
             auth_credentials_store_mode,
+            credential_helper.as_deref(),
         )
         .ok()
         .flatten();
@@ -1113,6 +1203,7 @@ impl AuthManager {
             inner: RwLock::new(CachedAuth { auth }),
             enable_llmx_api_key_env,
             auth_credentials_store_mode,
+            credential_helper,
         }
     }
 
@@ -1124,6 +1215,7 @@ impl AuthManager {
             inner: RwLock::new(cached),
             enable_llmx_api_key_env: false,
             auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+            credential_helper: None,
         })
     }
 
@@ -1139,6 +1231,7 @@ impl AuthManager {
             &self.llmx_home,
             self.enable_llmx_api_key_env,
             self.auth_credentials_store_mode,
+            self.credential_helper.as_deref(),
         )
         .ok()
         .flatten();
@@ -1164,11 +1257,13 @@ impl AuthManager {
         llmx_home: PathBuf,
         enable_llmx_api_key_env: bool,
         auth_credentials_store_mode: AuthCredentialsStoreMode,
+        credential_helper: Option<Vec<String>>,
     ) -> Arc<Self> {
         Arc::new(Self::new(
             llmx_home,
             enable_llmx_api_key_env,
             auth_credentials_store_mode,
+            credential_helper,
         ))
     }
 
@@ -1199,7 +1294,11 @@ impl AuthManager {
     /// reloads the in‑memory auth cache so callers immediately observe the
     /// unauthenticated state.
     pub fn logout(&self) -> std::io::Result<bool> {
-        let removed = super::auth::logout(&self.llmx_home, self.auth_credentials_store_mode)?;
+        let removed = super::auth::logout(
+            &self.llmx_home,
+            self.auth_credentials_store_mode,
+            self.credential_helper.as_deref(),
+        )?;
         // Always reload to clear any cached auth (even if file absent).
         self.reload();
         Ok(removed)
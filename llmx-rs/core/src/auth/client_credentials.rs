@@ -0,0 +1,196 @@
+//! OAuth2 client-credentials grant for model providers that issue
+//! short-lived bearer tokens instead of static API keys (for example Azure
+//! OpenAI behind Entra ID).
+//!
+//! Tokens are cached per process and refreshed shortly before they expire.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+use crate::default_client::LlmxHttpClient;
+use crate::error::ConnectionFailedError;
+use crate::error::LlmxErr;
+use crate::error::Result;
+use crate::model_provider_info::ProviderOAuth2Config;
+
+/// Tokens are refreshed this long before the expiry the server reported.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+/// Lifetime assumed when the token response has no `expires_in`.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(300);
+
+struct CachedToken {
+    access_token: String,
+    refresh_at: Instant,
+}
+
+static TOKEN_CACHE: LazyLock<Mutex<HashMap<String, CachedToken>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// Returns a bearer token for `config`, requesting a new one from the token
+/// endpoint when the cached token is missing or about to expire.
+pub(crate) async fn access_token(
+    client: &LlmxHttpClient,
+    config: &ProviderOAuth2Config,
+    client_secret: &str,
+) -> Result<String> {
+    let cache_key = cache_key(config);
+    if let Some(token) = cached_token(&cache_key) {
+        return Ok(token);
+    }
+
+    let mut form = vec![
+        ("grant_type", "client_credentials".to_string()),
+        ("client_id", config.client_id.clone()),
+        ("client_secret", client_secret.to_string()),
+    ];
+    if !config.scopes.is_empty() {
+        form.push(("scope", config.scopes.join(" ")));
+    }
+    if let Some(audience) = &config.audience {
+        form.push(("audience", audience.clone()));
+    }
+
+    let response = client
+        .post(&config.token_url)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|source| LlmxErr::ConnectionFailed(ConnectionFailedError { source }))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|source| LlmxErr::ConnectionFailed(ConnectionFailedError { source }))?;
+    if !status.is_success() {
+        return Err(LlmxErr::Fatal(format!(
+            "OAuth2 token request to {} failed with status {status}: {body}",
+            config.token_url
+        )));
+    }
+    let token: TokenResponse = serde_json::from_str(&body).map_err(|err| {
+        LlmxErr::Fatal(format!(
+            "invalid OAuth2 token response from {}: {err}",
+            config.token_url
+        ))
+    })?;
+
+    let lifetime = token
+        .expires_in
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+    let refresh_at = Instant::now() + lifetime.saturating_sub(EXPIRY_MARGIN);
+    TOKEN_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(
+            cache_key,
+            CachedToken {
+                access_token: token.access_token.clone(),
+                refresh_at,
+            },
+        );
+    Ok(token.access_token)
+}
+
+fn cache_key(config: &ProviderOAuth2Config) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        config.token_url,
+        config.client_id,
+        config.scopes.join(" "),
+        config.audience.as_deref().unwrap_or_default()
+    )
+}
+
+fn cached_token(cache_key: &str) -> Option<String> {
+    let cache = TOKEN_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+    cache
+        .get(cache_key)
+        .filter(|token| Instant::now() < token.refresh_at)
+        .map(|token| token.access_token.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_client::create_client;
+    use pretty_assertions::assert_eq;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_string_contains;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    #[tokio::test]
+    async fn fetches_and_caches_client_credentials_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .and(body_string_contains("client_secret=s3cret"))
+            .and(body_string_contains("scope=api%3A%2F%2Fllm%2F.default"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "token-1",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = ProviderOAuth2Config {
+            token_url: format!("{}/token", server.uri()),
+            client_id: "client".to_string(),
+            client_secret_env: None,
+            scopes: vec!["api://llm/.default".to_string()],
+            audience: None,
+        };
+        let client = create_client();
+
+        let first = access_token(&client, &config, "s3cret")
+            .await
+            .expect("token");
+        let second = access_token(&client, &config, "s3cret")
+            .await
+            .expect("token");
+
+        assert_eq!(first, "token-1");
+        assert_eq!(second, "token-1");
+    }
+
+    #[tokio::test]
+    async fn token_endpoint_errors_are_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("invalid_client"))
+            .mount(&server)
+            .await;
+        let config = ProviderOAuth2Config {
+            token_url: format!("{}/token", server.uri()),
+            client_id: "client".to_string(),
+            client_secret_env: None,
+            scopes: Vec::new(),
+            audience: None,
+        };
+
+        let err = access_token(&create_client(), &config, "wrong")
+            .await
+            .expect_err("401 should fail");
+
+        assert!(err.to_string().contains("invalid_client"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Secrets saved by `llmx login --provider <id>` for model providers that do
+//! not use OpenAI auth: plain API keys, or the client secret of a provider
+//! configured for OAuth2 client credentials.
+//!
+//! All provider secrets for one `LLMX_HOME` are kept as a single JSON map in
+//! the same credential store as the CLI login.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use llmx_keyring_store::KeyringStore;
+use tracing::warn;
+
+use super::storage::AuthCredentialsStoreMode;
+use super::storage::compute_store_key;
+use super::storage::keyring_store_for_mode;
+
+const KEYRING_SERVICE: &str = "LLMX Provider Keys";
+const PROVIDER_KEYS_FILE: &str = "provider_keys.json";
+
+/// provider id -> secret
+pub type ProviderKeys = BTreeMap<String, String>;
+
+#[derive(Clone, Debug)]
+pub struct ProviderKeyStore {
+    llmx_home: PathBuf,
+    mode: AuthCredentialsStoreMode,
+    keyring_store: Option<Arc<dyn KeyringStore>>,
+}
+
+impl ProviderKeyStore {
+    pub fn new(
+        llmx_home: PathBuf,
+        mode: AuthCredentialsStoreMode,
+        credential_helper: Option<&[String]>,
+    ) -> Self {
+        let keyring_store = keyring_store_for_mode(&llmx_home, mode, credential_helper);
+        Self {
+            llmx_home,
+            mode,
+            keyring_store,
+        }
+    }
+
+    pub fn load(&self, provider_id: &str) -> std::io::Result<Option<String>> {
+        Ok(self.load_all()?.remove(provider_id))
+    }
+
+    pub fn save(&self, provider_id: &str, secret: &str) -> std::io::Result<()> {
+        let mut keys = self.load_all()?;
+        keys.insert(provider_id.to_string(), secret.to_string());
+        self.save_all(&keys)
+    }
+
+    /// Returns `Ok(true)` when a secret was stored for `provider_id`.
+    pub fn delete(&self, provider_id: &str) -> std::io::Result<bool> {
+        let mut keys = self.load_all()?;
+        if keys.remove(provider_id).is_none() {
+            return Ok(false);
+        }
+        self.save_all(&keys)?;
+        Ok(true)
+    }
+
+    pub fn load_all(&self) -> std::io::Result<ProviderKeys> {
+        let Some(store) = &self.keyring_store else {
+            return read_keys_file(&self.llmx_home);
+        };
+        match self.load_from_store(store.as_ref()) {
+            Ok(Some(keys)) => Ok(keys),
+            Ok(None) if self.mode == AuthCredentialsStoreMode::Auto => {
+                read_keys_file(&self.llmx_home)
+            }
+            Ok(None) => Ok(ProviderKeys::new()),
+            Err(err) if self.mode == AuthCredentialsStoreMode::Auto => {
+                warn!("failed to load provider keys from keyring, falling back to file: {err}");
+                read_keys_file(&self.llmx_home)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Replaces the stored map. An empty map removes the entry.
+    pub fn save_all(&self, keys: &ProviderKeys) -> std::io::Result<()> {
+        let Some(store) = &self.keyring_store else {
+            return write_keys_file(&self.llmx_home, keys);
+        };
+        match self.save_to_store(store.as_ref(), keys) {
+            Ok(()) => {
+                if let Err(err) = write_keys_file(&self.llmx_home, &ProviderKeys::new()) {
+                    warn!("failed to remove provider keys fallback file: {err}");
+                }
+                Ok(())
+            }
+            Err(err) if self.mode == AuthCredentialsStoreMode::Auto => {
+                warn!("failed to save provider keys to keyring, falling back to file: {err}");
+                write_keys_file(&self.llmx_home, keys)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn load_from_store(&self, store: &dyn KeyringStore) -> std::io::Result<Option<ProviderKeys>> {
+        let account = compute_store_key(&self.llmx_home)?;
+        match store.load(KEYRING_SERVICE, &account) {
+            Ok(Some(serialized)) => serde_json::from_str(&serialized).map(Some).map_err(|err| {
+                std::io::Error::other(format!("failed to deserialize provider keys: {err}"))
+            }),
+            Ok(None) => Ok(None),
+            Err(error) => Err(std::io::Error::other(format!(
+                "failed to load provider keys: {}",
+                error.message()
+            ))),
+        }
+    }
+
+    fn save_to_store(&self, store: &dyn KeyringStore, keys: &ProviderKeys) -> std::io::Result<()> {
+        let account = compute_store_key(&self.llmx_home)?;
+        let result = if keys.is_empty() {
+            store.delete(KEYRING_SERVICE, &account).map(|_| ())
+        } else {
+            let serialized = serde_json::to_string(keys).map_err(std::io::Error::other)?;
+            store.save(KEYRING_SERVICE, &account, &serialized)
+        };
+        result.map_err(|error| {
+            std::io::Error::other(format!(
+                "failed to write provider keys: {}",
+                error.message()
+            ))
+        })
+    }
+}
+
+/// Moves every stored provider secret from one credential store to another.
+/// Returns `Ok(false)` when the source holds none.
+pub fn migrate_provider_keys(
+    llmx_home: &Path,
+    from: AuthCredentialsStoreMode,
+    to: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> std::io::Result<bool> {
+    let from = ProviderKeyStore::new(llmx_home.to_path_buf(), from, credential_helper);
+    let to = ProviderKeyStore::new(llmx_home.to_path_buf(), to, credential_helper);
+    let keys = from.load_all()?;
+    if keys.is_empty() {
+        return Ok(false);
+    }
+    from.save_all(&ProviderKeys::new())?;
+    if let Err(err) = to.save_all(&keys) {
+        if let Err(restore_err) = from.save_all(&keys) {
+            return Err(std::io::Error::other(format!(
+                "{err}; restoring the original store also failed: {restore_err}"
+            )));
+        }
+        return Err(err);
+    }
+    Ok(true)
+}
+
+fn read_keys_file(llmx_home: &Path) -> std::io::Result<ProviderKeys> {
+    match std::fs::read_to_string(llmx_home.join(PROVIDER_KEYS_FILE)) {
+        Ok(contents) => Ok(serde_json::from_str(&contents)?),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(ProviderKeys::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_keys_file(llmx_home: &Path, keys: &ProviderKeys) -> std::io::Result<()> {
+    let path = llmx_home.join(PROVIDER_KEYS_FILE);
+    if keys.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        };
+    }
+    std::fs::create_dir_all(llmx_home)?;
+    let json_data = serde_json::to_string_pretty(keys)?;
+    let mut options = OpenOptions::new();
+    options.truncate(true).write(true).create(true);
+    #[cfg(unix)]
+    {
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(json_data.as_bytes())?;
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn store_with_keyring(
+        llmx_home: &Path,
+        mode: AuthCredentialsStoreMode,
+        keyring: &MockKeyringStore,
+    ) -> ProviderKeyStore {
+        ProviderKeyStore {
+            llmx_home: llmx_home.to_path_buf(),
+            mode,
+            keyring_store: Some(Arc::new(keyring.clone())),
+        }
+    }
+
+    #[test]
+    fn file_store_round_trips_keys() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        let store = ProviderKeyStore::new(
+            llmx_home.path().to_path_buf(),
+            AuthCredentialsStoreMode::File,
+            None,
+        );
+
+        store.save("litellm", "sk-litellm")?;
+        store.save("anthropic", "sk-ant")?;
+
+        assert_eq!(store.load("litellm")?, Some("sk-litellm".to_string()));
+        assert!(store.delete("litellm")?);
+        assert!(!store.delete("litellm")?);
+        assert_eq!(
+            store.load_all()?,
+            ProviderKeys::from([("anthropic".to_string(), "sk-ant".to_string())])
+        );
+        assert!(store.delete("anthropic")?);
+        assert!(!llmx_home.path().join(PROVIDER_KEYS_FILE).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn keyring_store_keeps_keys_out_of_the_file() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        let keyring = MockKeyringStore::default();
+        let store = store_with_keyring(
+            llmx_home.path(),
+            AuthCredentialsStoreMode::Keyring,
+            &keyring,
+        );
+
+        store.save("azure", "client-secret")?;
+
+        let account = compute_store_key(llmx_home.path())?;
+        assert_eq!(
+            keyring.saved_value(&account),
+            Some(r#"{"azure":"client-secret"}"#.to_string())
+        );
+        assert!(!llmx_home.path().join(PROVIDER_KEYS_FILE).exists());
+        assert_eq!(store.load("azure")?, Some("client-secret".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn auto_store_reads_file_when_keyring_is_empty() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        write_keys_file(
+            llmx_home.path(),
+            &ProviderKeys::from([("litellm".to_string(), "sk-file".to_string())]),
+        )?;
+        let keyring = MockKeyringStore::default();
+        let store = store_with_keyring(llmx_home.path(), AuthCredentialsStoreMode::Auto, &keyring);
+
+        assert_eq!(store.load("litellm")?, Some("sk-file".to_string()));
+        Ok(())
+    }
+}
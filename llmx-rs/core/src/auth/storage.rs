@@ -17,7 +17,10 @@ use std::sync::Arc;
 use tracing::warn;
 
 use crate::token_data::TokenData;
+use llmx_keyring_store::CredentialHelperKeyringStore;
 use llmx_keyring_store::DefaultKeyringStore;
+use llmx_keyring_store::ENCRYPTED_STORE_FILENAME;
+use llmx_keyring_store::EncryptedFileKeyringStore;
 use llmx_keyring_store::KeyringStore;
 
 /// Determine where Llmx should store CLI auth credentials.
@@ -31,6 +34,13 @@ pub enum AuthCredentialsStoreMode {
     Keyring,
     /// Use keyring when available; otherwise, fall back to a file in LLMX_HOME.
     Auto,
+    /// Persist credentials in LLMX_HOME/credentials.enc, encrypted with the
+    /// passphrase from `LLMX_CREDENTIALS_PASSPHRASE`.
+    #[serde(rename = "encrypted-file")]
+    EncryptedFile,
+    /// Delegate to the external command configured as `credential_helper`.
+    #[serde(rename = "credential-helper")]
+    CredentialHelper,
 }
 
 /// Expected structure for $LLMX_HOME/auth.json.
@@ -125,7 +135,7 @@ impl AuthStorageBackend for FileAuthStorage {
 const KEYRING_SERVICE: &str = "LLMX Auth";
 
 // turns llmx_home path into a stable, short key string
-fn compute_store_key(llmx_home: &Path) -> std::io::Result<String> {
+pub(super) fn compute_store_key(llmx_home: &Path) -> std::io::Result<String> {
     let canonical = llmx_home
         .canonicalize()
         .unwrap_or_else(|_| llmx_home.to_path_buf());
@@ -255,17 +265,42 @@ impl AuthStorageBackend for AutoAuthStorage {
     }
 }
 
+/// Secret store backing `mode`, or `None` for the plain file store. `Auto`
+/// uses the OS keyring and leaves the file fallback to the caller.
+/// `credential_helper` is the configured helper command, used only by
+/// `CredentialHelper`.
+pub(super) fn keyring_store_for_mode(
+    llmx_home: &Path,
+    mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> Option<Arc<dyn KeyringStore>> {
+    match mode {
+        AuthCredentialsStoreMode::File => None,
+        AuthCredentialsStoreMode::Keyring | AuthCredentialsStoreMode::Auto => {
+            Some(Arc::new(DefaultKeyringStore))
+        }
+        AuthCredentialsStoreMode::EncryptedFile => Some(Arc::new(EncryptedFileKeyringStore::new(
+            llmx_home.join(ENCRYPTED_STORE_FILENAME),
+        ))),
+        AuthCredentialsStoreMode::CredentialHelper => Some(Arc::new(
+            CredentialHelperKeyringStore::new(credential_helper.map(<[String]>::to_vec)),
+        )),
+    }
+}
+
 pub(super) fn create_auth_storage(
     llmx_home: PathBuf,
     mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Arc<dyn AuthStorageBackend> {
     let keyring_store: Arc<dyn KeyringStore> = Arc::new(DefaultKeyringStore);
-    create_auth_storage_with_keyring_store(llmx_home, mode, keyring_store)
+    create_auth_storage_with_keyring_store(llmx_home, mode, credential_helper, keyring_store)
 }
 
 fn create_auth_storage_with_keyring_store(
     llmx_home: PathBuf,
     mode: AuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
     keyring_store: Arc<dyn KeyringStore>,
 ) -> Arc<dyn AuthStorageBackend> {
     match mode {
@@ -274,9 +309,41 @@ fn create_auth_storage_with_keyring_store(
             Arc::new(KeyringAuthStorage::new(llmx_home, keyring_store))
         }
         AuthCredentialsStoreMode::Auto => Arc::new(AutoAuthStorage::new(llmx_home, keyring_store)),
+        AuthCredentialsStoreMode::EncryptedFile => {
+            let store = EncryptedFileKeyringStore::new(llmx_home.join(ENCRYPTED_STORE_FILENAME));
+            Arc::new(KeyringAuthStorage::new(llmx_home, Arc::new(store)))
+        }
+        AuthCredentialsStoreMode::CredentialHelper => {
+            let store =
+                CredentialHelperKeyringStore::new(credential_helper.map(<[String]>::to_vec));
+            Arc::new(KeyringAuthStorage::new(llmx_home, Arc::new(store)))
+        }
     }
 }
 
+/// Moves the stored auth from `from` to `to`. The source is cleared before
+/// writing so backends that share the `auth.json` fallback do not delete the
+/// freshly written copy; if the write fails the auth is put back. Returns
+/// `Ok(false)` when `from` holds nothing.
+pub(super) fn migrate_auth_storage(
+    from: &dyn AuthStorageBackend,
+    to: &dyn AuthStorageBackend,
+) -> std::io::Result<bool> {
+    let Some(auth) = from.load()? else {
+        return Ok(false);
+    };
+    from.delete()?;
+    if let Err(err) = to.save(&auth) {
+        if let Err(restore_err) = from.save(&auth) {
+            return Err(std::io::Error::other(format!(
+                "{err}; restoring the original store also failed: {restore_err}"
+            )));
+        }
+        return Err(err);
+    }
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,7 +406,11 @@ mod tests {
             tokens: None,
             last_refresh: None,
         };
-        let storage = create_auth_storage(dir.path().to_path_buf(), AuthCredentialsStoreMode::File);
+        let storage = create_auth_storage(
+            dir.path().to_path_buf(),
+            AuthCredentialsStoreMode::File,
+            None,
+        );
         storage.save(&auth_dot_json)?;
         assert!(dir.path().join("auth.json").exists());
         let storage = FileAuthStorage::new(dir.path().to_path_buf());
@@ -681,4 +752,92 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn store_modes_use_kebab_case_names() -> anyhow::Result<()> {
+        let modes: Vec<AuthCredentialsStoreMode> =
+            serde_json::from_value(json!(["encrypted-file", "credential-helper", "auto"]))?;
+        assert_eq!(
+            modes,
+            vec![
+                AuthCredentialsStoreMode::EncryptedFile,
+                AuthCredentialsStoreMode::CredentialHelper,
+                AuthCredentialsStoreMode::Auto,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn encrypted_file_storage_round_trips_auth() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        let store = EncryptedFileKeyringStore::with_passphrase(
+            llmx_home.path().join(ENCRYPTED_STORE_FILENAME),
+            "passphrase".to_string(),
+        )
+        .with_iterations(2);
+        let storage = KeyringAuthStorage::new(llmx_home.path().to_path_buf(), Arc::new(store));
+        let auth_dot_json = AuthDotJson {
+            openai_api_key: Some("sk-encrypted".to_string()),
+            tokens: None,
+            last_refresh: None,
+        };
+
+        storage.save(&auth_dot_json)?;
+
+        assert!(llmx_home.path().join(ENCRYPTED_STORE_FILENAME).exists());
+        assert!(!get_auth_file(llmx_home.path()).exists());
+        assert_eq!(storage.load()?, Some(auth_dot_json));
+        assert!(storage.delete()?);
+        assert!(!llmx_home.path().join(ENCRYPTED_STORE_FILENAME).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_auth_storage_moves_file_auth_into_keyring() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        let mock_keyring = MockKeyringStore::default();
+        let file_storage = FileAuthStorage::new(llmx_home.path().to_path_buf());
+        let keyring_storage = KeyringAuthStorage::new(
+            llmx_home.path().to_path_buf(),
+            Arc::new(mock_keyring.clone()),
+        );
+        let auth_dot_json = AuthDotJson {
+            openai_api_key: Some("sk-migrate".to_string()),
+            tokens: None,
+            last_refresh: None,
+        };
+        file_storage.save(&auth_dot_json)?;
+
+        assert!(migrate_auth_storage(&file_storage, &keyring_storage)?);
+
+        assert!(!get_auth_file(llmx_home.path()).exists());
+        assert_eq!(keyring_storage.load()?, Some(auth_dot_json));
+        assert!(!migrate_auth_storage(&file_storage, &keyring_storage)?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_auth_storage_restores_source_when_write_fails() -> anyhow::Result<()> {
+        let llmx_home = tempdir()?;
+        let mock_keyring = MockKeyringStore::default();
+        let file_storage = FileAuthStorage::new(llmx_home.path().to_path_buf());
+        let keyring_storage = KeyringAuthStorage::new(
+            llmx_home.path().to_path_buf(),
+            Arc::new(mock_keyring.clone()),
+        );
+        let key = compute_store_key(llmx_home.path())?;
+        mock_keyring.set_error(&key, KeyringError::Invalid("error".into(), "save".into()));
+        let auth_dot_json = AuthDotJson {
+            openai_api_key: Some("sk-migrate".to_string()),
+            tokens: None,
+            last_refresh: None,
+        };
+        file_storage.save(&auth_dot_json)?;
+
+        assert!(migrate_auth_storage(&file_storage, &keyring_storage).is_err());
+
+        assert_eq!(file_storage.load()?, Some(auth_dot_json));
+        Ok(())
+    }
 }
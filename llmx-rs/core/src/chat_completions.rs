@@ -43,6 +43,7 @@ pub(crate) async fn stream_chat_completions(
     model_family: &ModelFamily,
     client: &LlmxHttpClient,
     provider: &ModelProviderInfo,
+    stored_provider_key: Option<&str>,
     otel_event_manager: &OtelEventManager,
     session_source: &SessionSource,
 ) -> Result<ResponseStream> {
@@ -461,7 +462,9 @@ pub(crate) async fn stream_chat_completions(
     loop {
         attempt += 1;
 
-        let mut req_builder = provider.create_request_builder(client, &None).await?;
+        let mut req_builder = provider
+            .create_request_builder(client, &None, stored_provider_key)
+            .await?;
 
         // Include subagent header only for subagent sessions.
         if let SessionSource::SubAgent(sub) = session_source.clone() {
@@ -0,0 +1,151 @@
+//! Timeline of the ghost snapshots captured at the start of each turn.
+//!
+//! Snapshots live in the conversation history as
+//! [`ResponseItem::GhostSnapshot`] items; this module pairs each one with the
+//! prompt that triggered its turn and asks git for the remaining metadata.
+
+use std::path::Path;
+
+use llmx_git::CreateGhostCommitOptions;
+use llmx_git::GhostCommit;
+use llmx_git::GitToolingError;
+use llmx_git::changed_paths_between;
+use llmx_git::changed_paths_since;
+use llmx_git::create_ghost_commit;
+use llmx_git::ghost_commit_timestamp;
+use llmx_protocol::items::TurnItem;
+use llmx_protocol::models::ResponseItem;
+use llmx_protocol::protocol::Checkpoint;
+
+use crate::parse_turn_item;
+
+/// A ghost snapshot found in the history together with the user message that
+/// started its turn.
+pub(crate) struct CheckpointEntry {
+    pub(crate) ghost_commit: GhostCommit,
+    pub(crate) prompt: Option<String>,
+}
+
+/// Collects the ghost snapshots in `items`, oldest first.
+pub(crate) fn collect_checkpoints(items: &[ResponseItem]) -> Vec<CheckpointEntry> {
+    let mut last_prompt: Option<String> = None;
+    let mut entries = Vec::new();
+    for item in items {
+        match item {
+            ResponseItem::GhostSnapshot { ghost_commit } => entries.push(CheckpointEntry {
+                ghost_commit: ghost_commit.clone(),
+                prompt: last_prompt.clone(),
+            }),
+            _ => {
+                if let Some(TurnItem::UserMessage(user_message)) = parse_turn_item(item) {
+                    last_prompt = Some(user_message.message());
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Finds the snapshot with the given commit id. Accepts unambiguous prefixes
+/// so users can type the short ids shown in the UI.
+pub(crate) fn find_checkpoint<'a>(
+    entries: &'a [CheckpointEntry],
+    id: &str,
+) -> Option<&'a CheckpointEntry> {
+    if id.is_empty() {
+        return None;
+    }
+    let mut matches = entries
+        .iter()
+        .filter(|entry| entry.ghost_commit.id().starts_with(id));
+    let first = matches.next()?;
+    matches.next().is_none().then_some(first)
+}
+
+/// Snapshots the current working tree without recording it, so it can be
+/// compared against earlier checkpoints.
+pub(crate) fn snapshot_worktree(cwd: &Path) -> Result<GhostCommit, GitToolingError> {
+    create_ghost_commit(&CreateGhostCommitOptions::new(cwd))
+}
+
+/// Resolves the timestamp and changed files of every checkpoint. The files
+/// for a checkpoint are those that differ from the next checkpoint, or from
+/// the current working tree for the latest one. Read-only; blocking.
+pub(crate) fn describe_checkpoints(
+    cwd: &Path,
+    entries: &[CheckpointEntry],
+) -> Result<Vec<Checkpoint>, GitToolingError> {
+    let mut checkpoints = Vec::with_capacity(entries.len());
+    for (idx, entry) in entries.iter().enumerate() {
+        let id = entry.ghost_commit.id();
+        let changed_files = match entries.get(idx + 1) {
+            Some(next) => changed_paths_between(cwd, id, next.ghost_commit.id())?,
+            None => changed_paths_since(cwd, id)?,
+        };
+        checkpoints.push(Checkpoint {
+            id: id.to_string(),
+            created_at: ghost_commit_timestamp(cwd, id).ok(),
+            prompt: entry.prompt.clone(),
+            changed_files,
+        });
+    }
+    Ok(checkpoints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn snapshot(id: &str) -> ResponseItem {
+        ResponseItem::GhostSnapshot {
+            ghost_commit: GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new()),
+        }
+    }
+
+    #[test]
+    fn pairs_snapshots_with_their_prompts() {
+        let items = vec![
+            user_message("fix the parser"),
+            snapshot("aaa111"),
+            user_message("now add tests"),
+            snapshot("bbb222"),
+        ];
+
+        let entries = collect_checkpoints(&items);
+        let summary: Vec<(&str, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.ghost_commit.id(), entry.prompt.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("aaa111", Some("fix the parser")),
+                ("bbb222", Some("now add tests")),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_checkpoint_requires_unambiguous_prefix() {
+        let entries = collect_checkpoints(&[snapshot("abc123"), snapshot("abd456")]);
+
+        assert!(find_checkpoint(&entries, "ab").is_none());
+        assert!(find_checkpoint(&entries, "").is_none());
+        assert_eq!(
+            find_checkpoint(&entries, "abd").map(|entry| entry.ghost_commit.id()),
+            Some("abd456")
+        );
+    }
+}
@@ -31,6 +31,7 @@ use tracing::warn;
 
 use crate::AuthManager;
 use crate::auth::LlmxAuth;
+use crate::auth::ProviderKeyStore;
 use crate::auth::RefreshTokenError;
 use crate::chat_completions::AggregateStreamExt;
 use crate::chat_completions::stream_chat_completions;
@@ -85,6 +86,8 @@ pub struct ModelClient {
     otel_event_manager: OtelEventManager,
     client: LlmxHttpClient,
     provider: ModelProviderInfo,
+    /// Secret saved by `llmx login --provider` for this provider, if needed.
+    stored_provider_key: Option<String>,
     conversation_id: ConversationId,
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
@@ -111,6 +114,7 @@ impl ModelClient {
             otel_event_manager,
             client,
             provider,
+            stored_provider_key: None,
             conversation_id,
             effort,
             summary,
@@ -118,6 +122,14 @@ impl ModelClient {
         }
     }
 
+    /// Uses `key`, the secret saved by `llmx login --provider`, when the
+    /// environment does not supply a credential. Sessions resolve it once with
+    /// [`load_stored_provider_key`] and hand it to every client they create.
+    pub(crate) fn with_stored_provider_key(mut self, key: Option<String>) -> Self {
+        self.stored_provider_key = key;
+        self
+    }
+
     pub fn get_model_context_window(&self) -> Option<i64> {
         let pct = self.config.model_family.effective_context_window_percent;
         self.config
@@ -150,6 +162,7 @@ impl ModelClient {
                     &self.config.model_family,
                     &self.client,
                     &self.provider,
+                    self.stored_provider_key.as_deref(),
                     &self.otel_event_manager,
                     &self.session_source,
                 )
@@ -302,7 +315,7 @@ impl ModelClient {
 
         let mut req_builder = self
             .provider
-            .create_request_builder(&self.client, &auth)
+            .create_request_builder(&self.client, &auth, self.stored_provider_key.as_deref())
             .await
             .map_err(StreamAttemptError::Fatal)?;
 
@@ -598,6 +611,40 @@ struct ResponseCompletedOutputTokensDetails {
     reasoning_tokens: i64,
 }
 
+/// Reads the secret saved by `llmx login --provider` for the configured
+/// provider, but only when the provider authenticates with a key and the
+/// environment does not already provide one. The credential store may block
+/// (keyring, credential helper), so the lookup runs off the async runtime.
+pub(crate) async fn load_stored_provider_key(
+    config: &Config,
+    provider: &ModelProviderInfo,
+) -> Option<String> {
+    if !provider.wants_stored_key() {
+        return None;
+    }
+    let provider_id = config.model_provider_id.clone();
+    let store = ProviderKeyStore::new(
+        config.llmx_home.clone(),
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    );
+    let result = tokio::task::spawn_blocking(move || store.load(&provider_id)).await;
+    match result {
+        Ok(Ok(key)) => key,
+        Ok(Err(err)) => {
+            warn!(
+                "failed to load stored key for provider {}: {err}",
+                config.model_provider_id
+            );
+            None
+        }
+        Err(err) => {
+            warn!("stored provider key lookup panicked: {err}");
+            None
+        }
+    }
+}
+
 fn attach_item_ids(payload_json: &mut Value, original_items: &[ResponseItem]) {
     let Some(input_value) = payload_json.get_mut("input") else {
         return;
@@ -1125,6 +1172,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1190,6 +1238,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1228,6 +1277,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1268,6 +1318,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1304,6 +1355,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1340,6 +1392,7 @@ mod tests {
             stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let otel_event_manager = otel_event_manager();
@@ -1445,6 +1498,7 @@ mod tests {
                 stream_idle_timeout_ms: Some(1000),
         max_tokens: None,
                 requires_openai_auth: false,
+                oauth2: None,
             };
 
             let otel_event_manager = otel_event_manager();
@@ -379,6 +379,29 @@ impl ConfigDocument {
     }
 }
 
+/// Splits a dotted key such as `profiles.fast.model` or
+/// `projects."/work/repo".trust_level` into its segments.
+pub fn parse_key_path(key: &str) -> anyhow::Result<Vec<String>> {
+    let keys = toml_edit::Key::parse(key).with_context(|| format!("invalid key `{key}`"))?;
+    Ok(keys.iter().map(|key| key.get().to_string()).collect())
+}
+
+/// Parses a command-line value as TOML, falling back to a plain string so
+/// `gpt-5` does not need to be quoted.
+pub fn parse_value_item(raw: &str) -> TomlItem {
+    let parsed = format!("value = {raw}")
+        .parse::<DocumentMut>()
+        .ok()
+        .and_then(|mut doc| doc.remove("value"));
+    match parsed {
+        Some(TomlItem::Value(mut parsed)) => {
+            parsed.decor_mut().clear();
+            TomlItem::Value(parsed)
+        }
+        _ => value(raw),
+    }
+}
+
 /// Persist edits using a blocking strategy.
 pub fn apply_blocking(
     llmx_home: &Path,
@@ -517,6 +540,26 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Set `tui.theme.name`, the color theme the TUI starts with.
+    pub fn set_tui_theme(self, name: &str) -> Self {
+        self.set_path(
+            vec!["tui".to_string(), "theme".to_string(), "name".to_string()],
+            value(name),
+        )
+    }
+
+    /// Set the value at `segments`, creating intermediate tables as needed.
+    pub fn set_path(mut self, segments: Vec<String>, value: TomlItem) -> Self {
+        self.edits.push(ConfigEdit::SetPath { segments, value });
+        self
+    }
+
+    /// Remove the value at `segments` if present.
+    pub fn clear_path(mut self, segments: Vec<String>) -> Self {
+        self.edits.push(ConfigEdit::ClearPath { segments });
+        self
+    }
+
     /// Apply edits on a blocking thread.
     pub fn apply_blocking(self) -> anyhow::Result<()> {
         apply_blocking(&self.llmx_home, self.profile.as_deref(), &self.edits)
@@ -542,6 +585,57 @@ mod tests {
     use tokio::runtime::Builder;
     use toml::Value as TomlValue;
 
+    #[test]
+    fn set_and_clear_parsed_paths() {
+        let tmp = tempdir().expect("tmpdir");
+        let llmx_home = tmp.path();
+
+        ConfigEditsBuilder::new(llmx_home)
+            .set_path(
+                parse_key_path("profiles.fast.model").expect("key"),
+                parse_value_item("gpt-5-mini"),
+            )
+            .set_path(
+                parse_key_path("projects.\"/work/repo\".trust_level").expect("key"),
+                parse_value_item("\"trusted\""),
+            )
+            .set_path(
+                parse_key_path("model_context_window").expect("key"),
+                parse_value_item("200000"),
+            )
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(llmx_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected: TomlValue = toml::from_str(
+            r#"model_context_window = 200000
+
+[profiles.fast]
+model = "gpt-5-mini"
+
+[projects."/work/repo"]
+trust_level = "trusted"
+"#,
+        )
+        .expect("parse expected");
+        let value: TomlValue = toml::from_str(&contents).expect("parse config");
+        assert_eq!(value, expected);
+
+        ConfigEditsBuilder::new(llmx_home)
+            .clear_path(parse_key_path("profiles.fast.model").expect("key"))
+            .apply_blocking()
+            .expect("persist");
+        let contents =
+            std::fs::read_to_string(llmx_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let value: TomlValue = toml::from_str(&contents).expect("parse config");
+        let model = value
+            .get("profiles")
+            .and_then(|profiles| profiles.get("fast"))
+            .and_then(|fast| fast.get("model"));
+        assert_eq!(model, None);
+    }
+
     #[test]
     fn blocking_set_model_top_level() {
         let tmp = tempdir().expect("tmpdir");
@@ -904,6 +998,29 @@ B = \"2\"
         assert_eq!(notifications, Some(false));
     }
 
+    #[test]
+    fn builder_set_tui_theme_keeps_sibling_keys() {
+        let tmp = tempdir().expect("tmpdir");
+        let llmx_home = tmp.path();
+        std::fs::write(
+            llmx_home.join(CONFIG_TOML_FILE),
+            "[tui.theme]\nlight = \"paper\"\n",
+        )
+        .expect("seed config");
+
+        ConfigEditsBuilder::new(llmx_home)
+            .set_tui_theme("dark")
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(llmx_home.join(CONFIG_TOML_FILE)).expect("read config");
+        assert_eq!(
+            contents,
+            "[tui.theme]\nlight = \"paper\"\nname = \"dark\"\n"
+        );
+    }
+
     #[tokio::test]
     async fn async_builder_set_model_persists() {
         let tmp = tempdir().expect("tmpdir");
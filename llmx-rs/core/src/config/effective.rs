@@ -0,0 +1,149 @@
+//! Renders a resolved [`Config`] back to TOML for `llmx config show --effective`.
+
+use crate::config::Config;
+use toml::Value as TomlValue;
+
+const REDACTED: &str = "<redacted>";
+
+/// Tables whose values are all treated as secrets: environment variables
+/// passed to MCP servers and literal HTTP headers.
+const SECRET_TABLES: &[&str] = &["env", "http_headers"];
+
+/// Key suffixes that mark a string value as a secret.
+const SECRET_KEY_SUFFIXES: &[&str] = &["token", "secret", "password", "api_key", "apikey"];
+
+/// Returns the settings that the session will actually use, with defaults
+/// filled in and legacy aliases folded into `[features]`. Legacy keys that
+/// contributed to the result are listed as leading comments.
+pub fn render_effective_config(config: &Config) -> Result<String, toml::ser::Error> {
+    let mut root = TomlValue::try_from(config)?;
+    redact_secrets(&mut root);
+
+    let mut out = String::new();
+    for (alias, feature) in config.features.legacy_feature_usages() {
+        out.push_str(&format!(
+            "# legacy key `{alias}` was applied as `features.{}`\n",
+            feature.key()
+        ));
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(&toml::to_string_pretty(&root)?);
+    Ok(out)
+}
+
+/// Replaces API keys, tokens, MCP server environments, and literal HTTP
+/// headers in `value` with a placeholder so configuration can be printed.
+pub fn redact_secrets(value: &mut TomlValue) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if SECRET_TABLES.contains(&key.as_str())
+                    && let TomlValue::Table(secrets) = value
+                {
+                    secrets.values_mut().for_each(redact_value);
+                } else if is_secret_key(key) {
+                    redact_value(value);
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        TomlValue::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SECRET_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| key.ends_with(suffix))
+}
+
+fn redact_value(value: &mut TomlValue) {
+    match value {
+        TomlValue::String(_) => *value = TomlValue::String(REDACTED.to_string()),
+        other => redact_secrets(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use tempfile::TempDir;
+
+    #[test]
+    fn renders_defaults_and_legacy_feature_aliases() {
+        let llmx_home = TempDir::new().expect("tempdir");
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+model = "gpt-5"
+experimental_use_rmcp_client = true
+"#,
+        )
+        .expect("parse");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(llmx_home.path().to_path_buf()),
+                ..Default::default()
+            },
+            llmx_home.path().to_path_buf(),
+        )
+        .expect("load config");
+
+        let rendered = render_effective_config(&config).expect("render");
+        let parsed: TomlValue = toml::from_str(&rendered).expect("rendered config is TOML");
+
+        assert!(rendered.starts_with(
+            "# legacy key `experimental_use_rmcp_client` was applied as `features.rmcp_client`\n"
+        ));
+        assert_eq!(parsed["model"].as_str(), Some("gpt-5"));
+        assert_eq!(parsed["features"]["rmcp_client"].as_bool(), Some(true));
+        assert_eq!(parsed["features"]["view_image_tool"].as_bool(), Some(true));
+        assert_eq!(parsed["history_pruning"].as_str(), Some("selective"));
+    }
+
+    #[test]
+    fn redacts_tokens_env_and_headers() {
+        let mut value: TomlValue = toml::from_str(
+            r#"
+model_auto_compact_token_limit = 1000
+
+[model_providers.corp]
+experimental_bearer_token = "sk-secret"
+env_key = "CORP_API_KEY"
+http_headers = { "X-Api-Key" = "abc" }
+
+[mcp_servers.docs]
+command = "docs-server"
+env = { DATABASE_URL = "postgres://user:pw@host/db" }
+"#,
+        )
+        .expect("parse");
+
+        redact_secrets(&mut value);
+
+        assert_eq!(
+            value["model_auto_compact_token_limit"].as_integer(),
+            Some(1000)
+        );
+        let provider = &value["model_providers"]["corp"];
+        assert_eq!(
+            provider["experimental_bearer_token"].as_str(),
+            Some(REDACTED)
+        );
+        assert_eq!(provider["env_key"].as_str(), Some("CORP_API_KEY"));
+        assert_eq!(
+            provider["http_headers"]["X-Api-Key"].as_str(),
+            Some(REDACTED)
+        );
+        let server = &value["mcp_servers"]["docs"];
+        assert_eq!(server["command"].as_str(), Some("docs-server"));
+        assert_eq!(server["env"]["DATABASE_URL"].as_str(), Some(REDACTED));
+    }
+}
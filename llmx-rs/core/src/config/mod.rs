@@ -1,9 +1,15 @@
 use crate::auth::AuthCredentialsStoreMode;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::EditingMode;
 use crate::config::types::History;
+use crate::config::types::HistoryPruning;
+use crate::config::types::ImageProtocol;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
+use crate::config::types::NotificationEvent;
+use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::NotificationsToml;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
@@ -12,11 +18,14 @@ use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::Tui;
+use crate::config::types::TuiKeymap;
+use crate::config::types::TuiTheme;
 use crate::config::types::UriBasedFileOpener;
-use crate::config_loader::LoadedConfigLayers;
+use crate::config_loader::ConfigLayerSource;
+use crate::config_loader::find_project_prompt_dirs;
 use crate::config_loader::load_config_as_toml;
-use crate::config_loader::load_config_layers_with_overrides;
-use crate::config_loader::merge_toml_values;
+use crate::config_loader::load_config_layer_stack;
+use crate::config_loader::merge_config_layers;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
@@ -43,20 +52,25 @@ use llmx_protocol::config_types::SandboxMode;
 use llmx_protocol::config_types::Verbosity;
 use llmx_rmcp_client::OAuthCredentialsStoreMode;
 use serde::Deserialize;
+use serde::Serialize;
+use serde::Serializer;
 use similar::DiffableStr;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::config::profile::ConfigProfile;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
 
 pub mod edit;
+pub mod effective;
 pub mod profile;
 pub mod types;
+pub mod validate;
 
 // Default models for LLMX using LiteLLM format (provider/model)
 #[cfg(target_os = "windows")]
@@ -71,10 +85,21 @@ pub const GPT_5_LLMX_MEDIUM_MODEL: &str = "anthropic/claude-sonnet-4-20250514";
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
-pub(crate) const CONFIG_TOML_FILE: &str = "config.toml";
+pub const CONFIG_TOML_FILE: &str = "config.toml";
+
+fn serialize_duration_secs<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+}
 
 /// Application configuration loaded from disk and merged with overrides.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// The `Serialize` impl backs `llmx config show --effective`: fields that are
+/// derived state rather than settings are skipped, and the rest are written
+/// under their `config.toml` key where that differs from the field name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Config {
     /// Optional override of model selection.
     pub model: String,
@@ -82,6 +107,7 @@ pub struct Config {
     /// Model used specifically for review sessions. Defaults to "gpt-5-llmx".
     pub review_model: String,
 
+    #[serde(skip)]
     pub model_family: ModelFamily,
 
     /// Size of the context window for the model, in tokens.
@@ -93,10 +119,15 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Strategy used to free context once the auto-compaction limit is reached.
+    pub history_pruning: HistoryPruning,
+
     /// Key into the model_providers map that specifies which provider to use.
+    #[serde(rename = "model_provider")]
     pub model_provider_id: String,
 
     /// Info needed to make an API request to the model.
+    #[serde(skip)]
     pub model_provider: ModelProviderInfo,
 
     /// Approval policy for executing commands.
@@ -106,12 +137,15 @@ pub struct Config {
 
     /// True if the user passed in an override or set a value in config.toml
     /// for either of approval_policy or sandbox_mode.
+    #[serde(skip)]
     pub did_user_set_custom_approval_policy_or_sandbox_mode: bool,
 
     /// On Windows, indicates that a previously configured workspace-write sandbox
     /// was coerced to read-only because native auto mode is unsupported.
+    #[serde(skip)]
     pub forced_auto_mode_downgraded_on_windows: bool,
 
+    #[serde(skip)]
     pub shell_environment_policy: ShellEnvironmentPolicy,
 
     /// When `true`, `AgentReasoning` events emitted by the backend will be
@@ -124,15 +158,19 @@ pub struct Config {
     pub show_raw_agent_reasoning: bool,
 
     /// User-provided instructions from AGENTS.md.
+    #[serde(skip)]
     pub user_instructions: Option<String>,
 
     /// Base instructions override.
+    #[serde(skip)]
     pub base_instructions: Option<String>,
 
     /// Developer instructions override injected as a separate message.
+    #[serde(skip)]
     pub developer_instructions: Option<String>,
 
     /// Compact prompt override.
+    #[serde(skip)]
     pub compact_prompt: Option<String>,
 
     /// Optional external notifier command. When set, Llmx will spawn this
@@ -157,10 +195,36 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
-    /// TUI notifications preference. When set, the TUI will send OSC 9 notifications on approvals
-    /// and turn completions when not focused.
+    /// Events reported to `notify`, and by the TUI when `tui.notifications`
+    /// is `true`.
+    pub notification_events: Vec<NotificationEvent>,
+
+    /// Turns and commands that finish faster than this are not reported.
+    #[serde(
+        rename = "notification_min_duration_secs",
+        serialize_with = "serialize_duration_secs"
+    )]
+    pub notification_min_duration: Duration,
+
+    /// TUI notifications preference. When set, the TUI sends OSC 9/777 notifications for the
+    /// configured events when not focused.
     pub tui_notifications: Notifications,
 
+    /// Escape sequence the TUI uses for desktop notifications.
+    pub tui_notification_method: NotificationMethod,
+
+    /// Key bindings configured under `[tui.keymap]`.
+    pub tui_keymap: TuiKeymap,
+
+    /// Editing mode of the TUI chat composer.
+    pub tui_editing_mode: EditingMode,
+
+    /// Color theme configured under `[tui.theme]`.
+    pub tui_theme: TuiTheme,
+
+    /// Graphics protocol for inline image thumbnails in the TUI.
+    pub tui_image_protocol: ImageProtocol,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -170,6 +234,9 @@ pub struct Config {
     /// file (default): Use a file in the Llmx home directory.
     /// keyring: Use an OS-specific keyring service.
     /// auto: Use the OS-specific keyring service if available, otherwise use a file.
+    /// encrypted-file: Use LLMX_HOME/credentials.enc, encrypted with a passphrase.
+    /// credential-helper: Use the `credential_helper` command.
+    #[serde(rename = "cli_auth_credentials_store")]
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
 
     /// Definition for MCP servers that Llmx can reach out to for tool calls.
@@ -182,8 +249,14 @@ pub struct Config {
     /// file: LLMX_HOME/.credentials.json
     ///       This file will be readable to Llmx and other applications running as the same user.
     /// auto (default): keyring if available, otherwise file.
+    /// encrypted-file / credential-helper: as for `cli_auth_credentials_store_mode`.
+    #[serde(rename = "mcp_oauth_credentials_store")]
     pub mcp_oauth_credentials_store_mode: OAuthCredentialsStoreMode,
 
+    /// External command used by the `credential-helper` stores, invoked with
+    /// `get`, `store`, or `erase` appended.
+    pub credential_helper: Option<Vec<String>>,
+
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
@@ -210,6 +283,7 @@ pub struct Config {
     /// [`ConfigOverrides`].
     ///
     /// When this program is invoked, arg0 will be set to `llmx-linux-sandbox`.
+    #[serde(skip)]
     pub llmx_linux_sandbox_exe: Option<PathBuf>,
 
     /// Value to use for `reasoning.effort` when making a request using the
@@ -237,32 +311,45 @@ pub struct Config {
     /// model family's default preference.
     pub include_apply_patch_tool: bool,
 
+    #[serde(skip)]
     pub tools_web_search_request: bool,
 
     /// When `true`, run a model-based assessment for commands denied by the sandbox.
+    #[serde(skip)]
     pub experimental_sandbox_command_assessment: bool,
 
     /// If set to `true`, used only the experimental unified exec tool.
+    #[serde(skip)]
     pub use_experimental_unified_exec_tool: bool,
 
     /// If set to `true`, use the experimental official Rust MCP client.
     /// https://github.com/modelcontextprotocol/rust-sdk
+    #[serde(skip)]
     pub use_experimental_use_rmcp_client: bool,
 
     /// Centralized feature flags; source of truth for feature gating.
     pub features: Features,
 
     /// The active profile name used to derive this `Config` (if any).
+    #[serde(rename = "profile")]
     pub active_profile: Option<String>,
 
     /// The currently active project config, resolved by checking if cwd:
     /// is (1) part of a git repo, (2) a git worktree, or (3) just using the cwd
+    #[serde(skip)]
     pub active_project: ProjectConfig,
 
+    /// `.llmx/prompts` directories of a trusted project, nearest to cwd
+    /// first. Prompts here shadow same-named prompts in `$LLMX_HOME/prompts`.
+    #[serde(skip)]
+    pub project_prompt_dirs: Vec<PathBuf>,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
+    #[serde(skip)]
     pub windows_wsl_setup_acknowledged: bool,
 
     /// Collection of various notices we show the user
+    #[serde(skip)]
     pub notices: Notice,
 
     /// When true, disables burst-paste detection for typed input entirely.
@@ -271,6 +358,7 @@ pub struct Config {
     pub disable_paste_burst: bool,
 
     /// OTEL configuration (exporter type, endpoint, headers, etc.).
+    #[serde(skip)]
     pub otel: crate::config::types::OtelConfig,
 }
 
@@ -281,12 +369,11 @@ impl Config {
     ) -> std::io::Result<Self> {
         let llmx_home = find_llmx_home()?;
 
-        let root_value = load_resolved_config(
-            &llmx_home,
-            cli_overrides,
-            crate::config_loader::LoaderOverrides::default(),
-        )
-        .await?;
+        let loader_overrides = crate::config_loader::LoaderOverrides {
+            cwd: Some(resolve_cwd(overrides.cwd.clone())?),
+            ..Default::default()
+        };
+        let root_value = load_resolved_config(&llmx_home, cli_overrides, loader_overrides).await?;
 
         let cfg: ConfigToml = root_value.try_into().map_err(|e| {
             tracing::error!("Failed to deserialize overridden config: {e}");
@@ -316,34 +403,83 @@ pub async fn load_config_as_toml_with_cli_overrides(
     Ok(cfg)
 }
 
+/// Merges the same layer stack that `llmx config explain` reports, so the
+/// session, `config get`/`show` and `explain` agree on every value.
 async fn load_resolved_config(
     llmx_home: &Path,
     cli_overrides: Vec<(String, TomlValue)>,
     overrides: crate::config_loader::LoaderOverrides,
 ) -> std::io::Result<TomlValue> {
-    let layers = load_config_layers_with_overrides(llmx_home, overrides).await?;
-    Ok(apply_overlays(layers, cli_overrides))
+    let stack = load_config_layer_stack(llmx_home, overrides, session_flags(cli_overrides)).await?;
+    Ok(merge_config_layers(stack))
 }
 
-fn apply_overlays(
-    layers: LoadedConfigLayers,
+/// Expands `-c/--config` overrides into a single layer.
+fn session_flags(cli_overrides: Vec<(String, TomlValue)>) -> Option<TomlValue> {
+    (!cli_overrides.is_empty()).then(|| {
+        let mut flags = TomlValue::Table(Default::default());
+        for (path, value) in cli_overrides {
+            apply_toml_override(&mut flags, &path, value);
+        }
+        flags
+    })
+}
+
+/// Returns the raw TOML obtained by merging every config layer that applies
+/// in `cwd`, before defaults are filled in.
+pub async fn load_merged_config_toml(
+    llmx_home: &Path,
+    cwd: Option<PathBuf>,
     cli_overrides: Vec<(String, TomlValue)>,
-) -> TomlValue {
-    let LoadedConfigLayers {
-        mut base,
-        managed_config,
-        managed_preferences,
-    } = layers;
+) -> std::io::Result<TomlValue> {
+    let overrides = crate::config_loader::LoaderOverrides {
+        cwd: Some(resolve_cwd(cwd)?),
+        ..Default::default()
+    };
+    load_resolved_config(llmx_home, cli_overrides, overrides).await
+}
 
-    for (path, value) in cli_overrides.into_iter() {
-        apply_toml_override(&mut base, &path, value);
-    }
+/// A value for a config key contributed by one layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigKeyOrigin {
+    pub source: ConfigLayerSource,
+    pub value: TomlValue,
+}
 
-    for overlay in [managed_config, managed_preferences].into_iter().flatten() {
-        merge_toml_values(&mut base, &overlay);
-    }
+/// Returns every layer that sets `key` (a dotted path such as
+/// `mcp_servers.docs.command`), lowest precedence first. The last entry holds
+/// the effective value; an empty result means the built-in default applies.
+/// Secrets are redacted as in `llmx config show`.
+pub async fn explain_config_key(
+    llmx_home: &Path,
+    cwd: Option<PathBuf>,
+    cli_overrides: Vec<(String, TomlValue)>,
+    key: &str,
+) -> std::io::Result<Vec<ConfigKeyOrigin>> {
+    let overrides = crate::config_loader::LoaderOverrides {
+        cwd: Some(resolve_cwd(cwd)?),
+        ..Default::default()
+    };
+    let key_path = crate::config::edit::parse_key_path(key)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string()))?;
+    let stack = load_config_layer_stack(llmx_home, overrides, session_flags(cli_overrides)).await?;
+
+    Ok(stack
+        .into_iter()
+        .filter_map(|mut layer| {
+            crate::config::effective::redact_secrets(&mut layer.config);
+            let value = lookup_key_path(&layer.config, &key_path)?.clone();
+            Some(ConfigKeyOrigin {
+                source: layer.source,
+                value,
+            })
+        })
+        .collect())
+}
 
-    base
+fn lookup_key_path<'a>(root: &'a TomlValue, path: &[String]) -> Option<&'a TomlValue> {
+    path.iter()
+        .try_fold(root, |value, segment| value.as_table()?.get(segment))
 }
 
 pub async fn load_global_mcp_servers(
@@ -524,6 +660,10 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Strategy used to free context once the auto-compaction limit is
+    /// reached: `selective` (default) or `summarize`.
+    pub history_pruning: Option<HistoryPruning>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -540,6 +680,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Which events are reported to `notify` and the TUI, and after how long.
+    #[serde(default)]
+    pub notifications: Option<NotificationsToml>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -562,6 +706,8 @@ pub struct ConfigToml {
     /// file (default): Use a file in the Llmx home directory.
     /// keyring: Use an OS-specific keyring service.
     /// auto: Use the keyring if available, otherwise use a file.
+    /// encrypted-file: Use LLMX_HOME/credentials.enc, encrypted with a passphrase.
+    /// credential-helper: Use the `credential_helper` command.
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
@@ -577,6 +723,12 @@ pub struct ConfigToml {
     #[serde(default)]
     pub mcp_oauth_credentials_store: Option<OAuthCredentialsStoreMode>,
 
+    /// Command for the `credential-helper` stores, e.g.
+    /// `["llmx-vault-helper", "--mount", "secret/llmx"]`. It is run with
+    /// `get`, `store`, or `erase` appended, like a git credential helper.
+    #[serde(default)]
+    pub credential_helper: Option<Vec<String>>,
+
     /// User-defined provider entries that extend/override the built-in list.
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
@@ -699,6 +851,29 @@ impl ProjectConfig {
     }
 }
 
+/// Looks up the entry in `projects` for `resolved_cwd`, falling back to the
+/// root of its git repository.
+pub(crate) fn find_project_for_cwd(
+    projects: &HashMap<String, ProjectConfig>,
+    resolved_cwd: &Path,
+) -> Option<ProjectConfig> {
+    if let Some(project_config) = projects.get(&resolved_cwd.to_string_lossy().to_string()) {
+        return Some(project_config.clone());
+    }
+
+    // If cwd lives inside a git repo/worktree, check whether the root git project
+    // (the primary repository working directory) is trusted. This lets
+    // worktrees inherit trust from the main project.
+    if let Some(repo_root) = resolve_root_git_project_for_trust(resolved_cwd)
+        && let Some(project_config_for_root) =
+            projects.get(&repo_root.to_string_lossy().to_string_lossy().to_string())
+    {
+        return Some(project_config_for_root.clone());
+    }
+
+    None
+}
+
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct ToolsToml {
     #[serde(default, alias = "web_search_request")]
@@ -783,22 +958,7 @@ impl ConfigToml {
     /// does not contain a project corresponding to cwd or a git repo for cwd
     pub fn get_active_project(&self, resolved_cwd: &Path) -> Option<ProjectConfig> {
         let projects = self.projects.clone().unwrap_or_default();
-
-        if let Some(project_config) = projects.get(&resolved_cwd.to_string_lossy().to_string()) {
-            return Some(project_config.clone());
-        }
-
-        // If cwd lives inside a git repo/worktree, check whether the root git project
-        // (the primary repository working directory) is trusted. This lets
-        // worktrees inherit trust from the main project.
-        if let Some(repo_root) = resolve_root_git_project_for_trust(resolved_cwd)
-            && let Some(project_config_for_root) =
-                projects.get(&repo_root.to_string_lossy().to_string_lossy().to_string())
-        {
-            return Some(project_config_for_root.clone());
-        }
-
-        None
+        find_project_for_cwd(&projects, resolved_cwd)
     }
 
     pub fn get_config_profile(
@@ -845,6 +1005,27 @@ pub struct ConfigOverrides {
     pub additional_writable_roots: Vec<PathBuf>,
 }
 
+/// Resolves the session cwd, defaulting to the current directory and making
+/// relative paths absolute.
+fn resolve_cwd(cwd: Option<PathBuf>) -> std::io::Result<PathBuf> {
+    use std::env;
+
+    match cwd {
+        None => {
+            tracing::info!("cwd not set, using current dir");
+            env::current_dir()
+        }
+        Some(p) if p.is_absolute() => Ok(p),
+        Some(p) => {
+            // Resolve relative path against the current working directory.
+            tracing::info!("cwd is relative, resolving against current dir");
+            let mut current = env::current_dir()?;
+            current.push(p);
+            Ok(current)
+        }
+    }
+}
+
 impl Config {
     /// Meant to be used exclusively for tests: `load_with_overrides()` should
     /// be used in all other cases.
@@ -905,24 +1086,7 @@ impl Config {
             crate::safety::set_windows_sandbox_enabled(features.enabled(Feature::WindowsSandbox));
         }
 
-        let resolved_cwd = {
-            use std::env;
-
-            match cwd {
-                None => {
-                    tracing::info!("cwd not set, using current dir");
-                    env::current_dir()?
-                }
-                Some(p) if p.is_absolute() => p,
-                Some(p) => {
-                    // Resolve relative path against the current working directory.
-                    tracing::info!("cwd is relative, resolving against current dir");
-                    let mut current = env::current_dir()?;
-                    current.push(p);
-                    current
-                }
-            }
-        };
+        let resolved_cwd = resolve_cwd(cwd)?;
         let additional_writable_roots: Vec<PathBuf> = additional_writable_roots
             .into_iter()
             .map(|path| {
@@ -940,6 +1104,11 @@ impl Config {
         let active_project = cfg
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
+        let project_prompt_dirs = if active_project.is_trusted() {
+            find_project_prompt_dirs(&resolved_cwd, &llmx_home)
+        } else {
+            Vec::new()
+        };
 
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
@@ -1002,6 +1171,19 @@ impl Config {
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
+        let cli_auth_credentials_store_mode = cfg.cli_auth_credentials_store.unwrap_or_default();
+        let mcp_oauth_credentials_store_mode = cfg.mcp_oauth_credentials_store.unwrap_or_default();
+        let credential_helper = cfg.credential_helper.filter(|command| !command.is_empty());
+        if credential_helper.is_none()
+            && (cli_auth_credentials_store_mode == AuthCredentialsStoreMode::CredentialHelper
+                || mcp_oauth_credentials_store_mode == OAuthCredentialsStoreMode::CredentialHelper)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "a credential store is set to `credential-helper` but `credential_helper` is not configured",
+            ));
+        }
+
         let history = cfg.history.unwrap_or_default();
 
         let include_apply_patch_tool_flag = features.enabled(Feature::ApplyPatchFreeform);
@@ -1087,6 +1269,7 @@ impl Config {
             "experimental compact prompt file",
         )?;
         let compact_prompt = compact_prompt.or(file_compact_prompt);
+        let notifications = cfg.notifications.clone().unwrap_or_default();
 
         // Default review model when not set in config; allow CLI override to take precedence.
         let review_model = override_review_model
@@ -1100,6 +1283,7 @@ impl Config {
             model_context_window,
             model_max_output_tokens,
             model_auto_compact_token_limit,
+            history_pruning: cfg.history_pruning.unwrap_or_default(),
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
@@ -1109,17 +1293,24 @@ impl Config {
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
             notify: cfg.notify,
+            notification_events: notifications
+                .events
+                .unwrap_or_else(|| NotificationEvent::DEFAULT.to_vec()),
+            notification_min_duration: Duration::from_secs(
+                notifications.min_duration_secs.unwrap_or(0),
+            ),
             user_instructions,
             base_instructions,
             developer_instructions,
             compact_prompt,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
-            cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
+            cli_auth_credentials_store_mode,
             mcp_servers: cfg.mcp_servers,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
-            mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
+            mcp_oauth_credentials_store_mode,
+            credential_helper,
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
@@ -1167,6 +1358,7 @@ impl Config {
             features,
             active_profile: active_profile_name,
             active_project,
+            project_prompt_dirs,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
@@ -1175,6 +1367,27 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_notification_method: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.notification_method)
+                .unwrap_or_default(),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keymap.clone())
+                .unwrap_or_default(),
+            tui_editing_mode: cfg.tui.as_ref().map(|t| t.editing_mode).unwrap_or_default(),
+            tui_theme: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.theme.clone())
+                .unwrap_or_default(),
+            tui_image_protocol: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.image_protocol)
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1290,8 +1503,10 @@ mod tests {
     use crate::config::edit::ConfigEditsBuilder;
     use crate::config::edit::apply_blocking;
     use crate::config::types::HistoryPersistence;
+    use crate::config::types::KeyChords;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
+    use crate::config::types::ThemePalette;
     use crate::features::Feature;
 
     use super::*;
@@ -1550,6 +1765,87 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn tui_theme_loads_from_toml() -> std::io::Result<()> {
+        let llmx_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r##"
+[tui.theme]
+name = "auto"
+light = "paper"
+
+[tui.theme.palettes.paper]
+base = "light"
+diff_add = "#116329"
+accent = "blue"
+"##,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            llmx_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.tui_theme,
+            TuiTheme {
+                name: Some("auto".to_string()),
+                light: Some("paper".to_string()),
+                dark: None,
+                palettes: BTreeMap::from([(
+                    "paper".to_string(),
+                    ThemePalette {
+                        base: Some("light".to_string()),
+                        diff_add: Some("#116329".to_string()),
+                        accent: Some("blue".to_string()),
+                        ..Default::default()
+                    },
+                )]),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tui_keymap_and_editing_mode_load_from_toml() -> std::io::Result<()> {
+        let llmx_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[tui]
+editing_mode = "vi"
+
+[tui.keymap]
+submit = "ctrl+enter"
+newline = ["enter", "ctrl+j"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            llmx_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.tui_editing_mode, EditingMode::Vi);
+        assert_eq!(
+            config.tui_keymap,
+            TuiKeymap {
+                submit: Some(KeyChords::One("ctrl+enter".to_string())),
+                newline: Some(KeyChords::Many(vec![
+                    "enter".to_string(),
+                    "ctrl+j".to_string()
+                ])),
+                ..Default::default()
+            }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn config_honors_explicit_keyring_auth_store_mode() -> std::io::Result<()> {
         let llmx_home = TempDir::new()?;
@@ -1779,6 +2075,7 @@ trust_level = "trusted"
             managed_config_path: Some(managed_path.clone()),
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
+            cwd: None,
         };
 
         let root_value = load_resolved_config(llmx_home.path(), Vec::new(), overrides).await?;
@@ -1893,6 +2190,7 @@ trust_level = "trusted"
             managed_config_path: Some(managed_path),
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
+            cwd: None,
         };
 
         let root_value = load_resolved_config(
@@ -2811,6 +3109,7 @@ model_verbosity = "high"
             stream_idle_timeout_ms: Some(300_000),
         max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -2870,6 +3169,7 @@ model_verbosity = "high"
                 model_context_window: Some(200_000),
                 model_max_output_tokens: Some(100_000),
                 model_auto_compact_token_limit: Some(180_000),
+                history_pruning: HistoryPruning::default(),
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: AskForApproval::Never,
@@ -2879,10 +3179,13 @@ model_verbosity = "high"
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                notification_events: NotificationEvent::DEFAULT.to_vec(),
+                notification_min_duration: Duration::ZERO,
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
+                credential_helper: None,
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
@@ -2909,10 +3212,16 @@ model_verbosity = "high"
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
                 active_project: ProjectConfig { trust_level: None },
+                project_prompt_dirs: Vec::new(),
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_keymap: Default::default(),
+                tui_editing_mode: Default::default(),
+                tui_theme: Default::default(),
+                tui_image_protocol: Default::default(),
+                tui_notification_method: Default::default(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -2941,6 +3250,7 @@ model_verbosity = "high"
             model_context_window: Some(16_385),
             model_max_output_tokens: Some(4_096),
             model_auto_compact_token_limit: Some(14_746),
+            history_pruning: HistoryPruning::default(),
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: AskForApproval::UnlessTrusted,
@@ -2950,10 +3260,13 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notification_events: NotificationEvent::DEFAULT.to_vec(),
+            notification_min_duration: Duration::ZERO,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            credential_helper: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -2980,10 +3293,16 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            project_prompt_dirs: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_editing_mode: Default::default(),
+            tui_theme: Default::default(),
+            tui_image_protocol: Default::default(),
+            tui_notification_method: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3027,6 +3346,7 @@ model_verbosity = "high"
             model_context_window: Some(200_000),
             model_max_output_tokens: Some(100_000),
             model_auto_compact_token_limit: Some(180_000),
+            history_pruning: HistoryPruning::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
@@ -3036,10 +3356,13 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notification_events: NotificationEvent::DEFAULT.to_vec(),
+            notification_min_duration: Duration::ZERO,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            credential_helper: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -3066,10 +3389,16 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            project_prompt_dirs: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_editing_mode: Default::default(),
+            tui_theme: Default::default(),
+            tui_image_protocol: Default::default(),
+            tui_notification_method: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3099,6 +3428,7 @@ model_verbosity = "high"
             model_context_window: Some(272_000),
             model_max_output_tokens: Some(128_000),
             model_auto_compact_token_limit: Some(244_800),
+            history_pruning: HistoryPruning::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: AskForApproval::OnFailure,
@@ -3108,10 +3438,13 @@ model_verbosity = "high"
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notification_events: NotificationEvent::DEFAULT.to_vec(),
+            notification_min_duration: Duration::ZERO,
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            credential_helper: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
@@ -3138,10 +3471,16 @@ model_verbosity = "high"
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
             active_project: ProjectConfig { trust_level: None },
+            project_prompt_dirs: Vec::new(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_keymap: Default::default(),
+            tui_editing_mode: Default::default(),
+            tui_theme: Default::default(),
+            tui_image_protocol: Default::default(),
+            tui_notification_method: Default::default(),
             otel: OtelConfig::default(),
         };
 
@@ -3263,12 +3602,86 @@ trust_level = "trusted"
 
         Ok(())
     }
+
+    /// Points `LLMX_HOME` at a test directory for the guard's lifetime.
+    struct LlmxHomeGuard {
+        original: Option<std::ffi::OsString>,
+    }
+
+    impl LlmxHomeGuard {
+        fn set(path: &Path) -> Self {
+            let original = std::env::var_os("LLMX_HOME");
+            unsafe {
+                std::env::set_var("LLMX_HOME", path);
+            }
+            Self { original }
+        }
+    }
+
+    impl Drop for LlmxHomeGuard {
+        fn drop(&mut self) {
+            unsafe {
+                match &self.original {
+                    Some(value) => std::env::set_var("LLMX_HOME", value),
+                    None => std::env::remove_var("LLMX_HOME"),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(llmx_home)]
+    async fn user_config_wins_over_trusted_project_config() -> std::io::Result<()> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path().canonicalize()?;
+        let llmx_home = root.join("home");
+        let project = root.join("project");
+        let project_config_dir = project.join(crate::config_loader::PROJECT_CONFIG_DIR);
+        std::fs::create_dir_all(&llmx_home)?;
+        std::fs::create_dir_all(&project_config_dir)?;
+        std::fs::write(
+            llmx_home.join(CONFIG_TOML_FILE),
+            format!(
+                r#"model = "user-model"
+
+[projects."{}"]
+trust_level = "trusted"
+"#,
+                project.display()
+            ),
+        )?;
+        std::fs::write(
+            project_config_dir.join(CONFIG_TOML_FILE),
+            r#"model = "project-model"
+model_reasoning_effort = "high"
+"#,
+        )?;
+        let _guard = LlmxHomeGuard::set(&llmx_home);
+
+        let config = Config::load_with_cli_overrides(
+            Vec::new(),
+            ConfigOverrides {
+                cwd: Some(project),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        assert_eq!(config.model, "user-model");
+        // Keys the user leaves unset still come from the project.
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::High));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod notifications_tests {
+    use super::ConfigToml;
+    use crate::config::types::NotificationEvent;
     use crate::config::types::Notifications;
+    use crate::config::types::NotificationsToml;
     use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
     use serde::Deserialize;
 
     #[derive(Deserialize, Debug, PartialEq)]
@@ -3304,4 +3717,23 @@ mod notifications_tests {
             Notifications::Custom(ref v) if v == &vec!["foo".to_string()]
         );
     }
+    #[test]
+    fn notifications_table_filters_events() {
+        let toml = r#"
+            [notifications]
+            events = ["approval-requested", "command-finished"]
+            min_duration_secs = 120
+        "#;
+        let parsed: ConfigToml = toml::from_str(toml).expect("deserialize [notifications]");
+        assert_eq!(
+            parsed.notifications,
+            Some(NotificationsToml {
+                events: Some(vec![
+                    NotificationEvent::ApprovalRequested,
+                    NotificationEvent::CommandFinished,
+                ]),
+                min_duration_secs: Some(120),
+            })
+        );
+    }
 }
@@ -4,6 +4,7 @@
 // definitions that do not contain business logic.
 
 use serde::Deserializer;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -219,7 +220,7 @@ mod option_duration_secs {
     }
 }
 
-#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq)]
 pub enum UriBasedFileOpener {
     #[serde(rename = "vscode")]
     VsCode,
@@ -251,7 +252,7 @@ impl UriBasedFileOpener {
 }
 
 /// Settings that govern if and what will be written to `~/.llmx/history.jsonl`.
-#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
 pub struct History {
     /// If true, history entries will not be written to disk.
     pub persistence: HistoryPersistence,
@@ -261,7 +262,7 @@ pub struct History {
     pub max_bytes: Option<usize>,
 }
 
-#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum HistoryPersistence {
     /// Save all history entries to disk.
@@ -271,6 +272,18 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// How the session frees context once the prompt nears
+/// `model_auto_compact_token_limit`.
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryPruning {
+    /// Elide stale tool outputs first and summarize only if that is not enough.
+    #[default]
+    Selective,
+    /// Always summarize the whole conversation.
+    Summarize,
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -329,7 +342,7 @@ impl Default for OtelConfig {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Notifications {
     Enabled(bool),
@@ -342,6 +355,72 @@ impl Default for Notifications {
     }
 }
 
+/// Events reported to the `notify` program and as TUI notifications.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationEvent {
+    /// The agent finished a turn.
+    AgentTurnComplete,
+    /// A command or patch is waiting for approval.
+    ApprovalRequested,
+    /// A turn ended with an error.
+    TurnFailed,
+    /// A usage limit, quota or rate limit stopped the turn.
+    RateLimitReached,
+    /// A command run by the agent finished.
+    CommandFinished,
+}
+
+impl NotificationEvent {
+    /// Events reported when `[notifications] events` is not set. Command
+    /// completions are opt-in since agents run many short commands.
+    pub const DEFAULT: [NotificationEvent; 4] = [
+        NotificationEvent::AgentTurnComplete,
+        NotificationEvent::ApprovalRequested,
+        NotificationEvent::TurnFailed,
+        NotificationEvent::RateLimitReached,
+    ];
+
+    /// The name used in config and in `notify` payloads.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationEvent::AgentTurnComplete => "agent-turn-complete",
+            NotificationEvent::ApprovalRequested => "approval-requested",
+            NotificationEvent::TurnFailed => "turn-failed",
+            NotificationEvent::RateLimitReached => "rate-limit-reached",
+            NotificationEvent::CommandFinished => "command-finished",
+        }
+    }
+}
+
+/// `[notifications]`: which events are reported and how long turns and
+/// commands must run before their completion is reported.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct NotificationsToml {
+    /// Events sent to `notify`, and to the TUI when `tui.notifications` is
+    /// `true`. Defaults to [`NotificationEvent::DEFAULT`].
+    pub events: Option<Vec<NotificationEvent>>,
+
+    /// Turns and commands that finish faster than this are not reported.
+    /// Defaults to 0.
+    pub min_duration_secs: Option<u64>,
+}
+
+/// Terminal escape sequence used for TUI notifications.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationMethod {
+    /// OSC 777 in terminals known to support it (VTE-based terminals, foot,
+    /// rxvt-unicode), OSC 9 elsewhere.
+    #[default]
+    Auto,
+    /// OSC 9 (iTerm2, WezTerm, Windows Terminal, Ghostty, kitty).
+    Osc9,
+    /// OSC 777 (GNOME Terminal and other VTE-based terminals, foot,
+    /// rxvt-unicode, Ghostty, WezTerm).
+    Osc777,
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Tui {
@@ -349,6 +428,129 @@ pub struct Tui {
     /// Defaults to `false`.
     #[serde(default)]
     pub notifications: Notifications,
+
+    /// Escape sequence used for desktop notifications. Defaults to `auto`.
+    #[serde(default)]
+    pub notification_method: NotificationMethod,
+
+    /// Key bindings for composer and transcript actions. Actions that are not
+    /// listed keep their default keys.
+    #[serde(default)]
+    pub keymap: TuiKeymap,
+
+    /// Editing mode of the chat composer. Defaults to `emacs`.
+    #[serde(default)]
+    pub editing_mode: EditingMode,
+
+    /// Color theme for diffs, borders, headers, accents and syntax
+    /// highlighting.
+    #[serde(default)]
+    pub theme: TuiTheme,
+
+    /// Terminal graphics protocol used to show image thumbnails in the
+    /// transcript. Defaults to `auto`.
+    #[serde(default)]
+    pub image_protocol: ImageProtocol,
+}
+
+/// `[tui.theme]`: selects a built-in or user-defined color theme.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TuiTheme {
+    /// Theme to use: `auto` (the default), a built-in theme (`default`,
+    /// `light`, `dark`) or the name of one of `palettes`.
+    pub name: Option<String>,
+    /// Theme `auto` picks when the terminal background is light. Defaults to
+    /// `light`.
+    pub light: Option<String>,
+    /// Theme `auto` picks when the terminal background is dark or could not
+    /// be detected. Defaults to `default`.
+    pub dark: Option<String>,
+    /// User-defined themes, keyed by name.
+    #[serde(default)]
+    pub palettes: BTreeMap<String, ThemePalette>,
+}
+
+/// `[tui.theme.palettes.<name>]`: a user-defined theme. Colors are ANSI
+/// names such as `"green"` or `"light-red"`, or `"#rrggbb"`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ThemePalette {
+    /// Built-in theme that supplies every color not set here. Defaults to
+    /// `default`.
+    pub base: Option<String>,
+    pub diff_add: Option<String>,
+    pub diff_add_bg: Option<String>,
+    pub diff_remove: Option<String>,
+    pub diff_remove_bg: Option<String>,
+    pub border: Option<String>,
+    pub header: Option<String>,
+    pub accent: Option<String>,
+    pub syntax_keyword: Option<String>,
+    pub syntax_string: Option<String>,
+    pub syntax_comment: Option<String>,
+    pub syntax_function: Option<String>,
+    pub syntax_constant: Option<String>,
+}
+
+/// `[tui.keymap]`: maps composer and transcript actions to one or more key
+/// chords such as `"ctrl+j"`, `"alt+enter"` or `["up", "ctrl+p"]`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TuiKeymap {
+    pub submit: Option<KeyChords>,
+    pub newline: Option<KeyChords>,
+    pub history_up: Option<KeyChords>,
+    pub history_down: Option<KeyChords>,
+    pub open_transcript: Option<KeyChords>,
+    pub interrupt: Option<KeyChords>,
+    pub backtrack: Option<KeyChords>,
+    pub external_editor: Option<KeyChords>,
+    pub new_session: Option<KeyChords>,
+    pub close_session: Option<KeyChords>,
+    pub next_session: Option<KeyChords>,
+    pub prev_session: Option<KeyChords>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum KeyChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChords {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            KeyChords::One(chord) => std::slice::from_ref(chord),
+            KeyChords::Many(chords) => chords,
+        }
+    }
+}
+
+/// How the chat composer interprets keys.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EditingMode {
+    /// Modeless editing with emacs-style control keys.
+    #[default]
+    Emacs,
+    /// Modal editing with vi-style normal and insert states.
+    Vi,
+}
+
+/// How the TUI draws image thumbnails.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    /// Detect the terminal's graphics support from its environment.
+    #[default]
+    Auto,
+    /// Kitty graphics protocol (kitty, Ghostty, Konsole).
+    Kitty,
+    /// iTerm2 inline images (iTerm2, WezTerm).
+    Iterm2,
+    /// DEC Sixel graphics (foot, mlterm, xterm with sixel enabled).
+    Sixel,
+    /// Never draw images; show a text label instead.
+    None,
 }
 
 /// Settings for notices we display to users via the tui and app-server clients
@@ -0,0 +1,345 @@
+//! Static checks for `config.toml` used by `llmx config validate`.
+//!
+//! Type errors are reported by deserializing into [`ConfigToml`], so they stay
+//! in sync with what LLMX actually accepts. Unknown keys are not an error for
+//! serde, so they are found by walking the parsed document and comparing each
+//! table against the field names the corresponding `Deserialize` impl expects.
+
+use crate::config::ConfigToml;
+use crate::config::ToolsToml;
+use crate::config::profile::ConfigProfile;
+use crate::config::types::History;
+use crate::config::types::Notice;
+use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::ThemePalette;
+use crate::config::types::Tui;
+use crate::config::types::TuiKeymap;
+use crate::config::types::TuiTheme;
+use crate::features::is_known_feature_key;
+use crate::model_provider_info::ModelProviderInfo;
+use serde::Deserialize;
+use serde::de;
+use serde::de::Visitor;
+use std::fmt;
+use std::ops::Range;
+use toml_edit::Item;
+use toml_edit::TableLike;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A single problem found in a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// 1-based line and column of the offending text, when known.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == DiagnosticSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        match self.location {
+            Some((line, column)) => write!(f, "{label}: {line}:{column}: {}", self.message),
+            None => write!(f, "{label}: {}", self.message),
+        }
+    }
+}
+
+/// Checks `contents` as a `config.toml`, returning errors for invalid TOML or
+/// values of the wrong type and warnings for keys LLMX does not recognize.
+pub fn validate_config_toml(contents: &str) -> Vec<ConfigDiagnostic> {
+    let document = match toml_edit::Document::parse(contents) {
+        Ok(document) => document,
+        Err(err) => {
+            return vec![ConfigDiagnostic {
+                severity: DiagnosticSeverity::Error,
+                location: err.span().map(|span| line_column(contents, span.start)),
+                message: first_line(err.message()),
+            }];
+        }
+    };
+
+    let mut diagnostics = Vec::new();
+    if let Err(err) = toml::from_str::<ConfigToml>(contents) {
+        diagnostics.push(ConfigDiagnostic {
+            severity: DiagnosticSeverity::Error,
+            location: err.span().map(|span| line_column(contents, span.start)),
+            message: first_line(err.message()),
+        });
+    }
+
+    let mut checker = UnknownKeyChecker {
+        contents,
+        diagnostics: &mut diagnostics,
+    };
+    let root = document.as_table();
+    checker.check_fields(root, "", struct_fields::<ConfigToml>());
+    checker.check_nested(root, "tui", struct_fields::<Tui>());
+    if let Some(keymap) = root
+        .get("tui")
+        .and_then(|tui| tui.get("keymap"))
+        .and_then(Item::as_table_like)
+    {
+        checker.check_fields(keymap, "tui.keymap.", struct_fields::<TuiKeymap>());
+    }
+    if let Some(theme) = root
+        .get("tui")
+        .and_then(|tui| tui.get("theme"))
+        .and_then(Item::as_table_like)
+    {
+        checker.check_fields(theme, "tui.theme.", struct_fields::<TuiTheme>());
+        checker.check_entries(
+            theme,
+            "tui.theme.",
+            "palettes",
+            struct_fields::<ThemePalette>(),
+        );
+    }
+    checker.check_nested(root, "tools", struct_fields::<ToolsToml>());
+    checker.check_nested(root, "history", struct_fields::<History>());
+    checker.check_nested(root, "notice", struct_fields::<Notice>());
+    checker.check_nested(
+        root,
+        "sandbox_workspace_write",
+        struct_fields::<SandboxWorkspaceWrite>(),
+    );
+    checker.check_nested(
+        root,
+        "shell_environment_policy",
+        struct_fields::<ShellEnvironmentPolicyToml>(),
+    );
+    checker.check_features(root, "");
+    checker.check_entries(
+        root,
+        "",
+        "model_providers",
+        struct_fields::<ModelProviderInfo>(),
+    );
+    if let Some(profiles) = root.get("profiles").and_then(Item::as_table_like) {
+        for (name, profile) in profiles.iter() {
+            let Some(profile) = profile.as_table_like() else {
+                continue;
+            };
+            let prefix = format!("profiles.{name}.");
+            checker.check_fields(profile, &prefix, struct_fields::<ConfigProfile>());
+            checker.check_features(profile, &prefix);
+        }
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.location);
+    diagnostics
+}
+
+struct UnknownKeyChecker<'a> {
+    contents: &'a str,
+    diagnostics: &'a mut Vec<ConfigDiagnostic>,
+}
+
+impl UnknownKeyChecker<'_> {
+    fn check_fields(&mut self, table: &dyn TableLike, prefix: &str, fields: &[&str]) {
+        for (key, _) in table.iter() {
+            if !fields.contains(&key) {
+                self.warn_unknown(table, prefix, key, "unknown key");
+            }
+        }
+    }
+
+    fn check_nested(&mut self, root: &dyn TableLike, name: &str, fields: &[&str]) {
+        if let Some(table) = root.get(name).and_then(Item::as_table_like) {
+            self.check_fields(table, &format!("{name}."), fields);
+        }
+    }
+
+    fn check_entries(&mut self, parent: &dyn TableLike, prefix: &str, name: &str, fields: &[&str]) {
+        let Some(entries) = parent.get(name).and_then(Item::as_table_like) else {
+            return;
+        };
+        for (entry_name, entry) in entries.iter() {
+            if let Some(entry) = entry.as_table_like() {
+                self.check_fields(entry, &format!("{prefix}{name}.{entry_name}."), fields);
+            }
+        }
+    }
+
+    fn check_features(&mut self, parent: &dyn TableLike, prefix: &str) {
+        let Some(features) = parent.get("features").and_then(Item::as_table_like) else {
+            return;
+        };
+        let prefix = format!("{prefix}features.");
+        for (key, _) in features.iter() {
+            if !is_known_feature_key(key) {
+                self.warn_unknown(features, &prefix, key, "unknown feature");
+            }
+        }
+    }
+
+    fn warn_unknown(&mut self, table: &dyn TableLike, prefix: &str, key: &str, what: &str) {
+        let location = table
+            .get_key_value(key)
+            .and_then(|(key, _)| key.span())
+            .map(|span: Range<usize>| line_column(self.contents, span.start));
+        self.diagnostics.push(ConfigDiagnostic {
+            severity: DiagnosticSeverity::Warning,
+            location,
+            message: format!("{what} `{prefix}{key}` will be ignored"),
+        });
+    }
+}
+
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = contents.get(..offset).unwrap_or(contents);
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |text| text.chars().count())
+        + 1;
+    (line, column)
+}
+
+fn first_line(message: &str) -> String {
+    message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Returns the field names (including aliases) that `T`'s derived
+/// `Deserialize` impl accepts.
+fn struct_fields<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    let mut capture = FieldCapture::default();
+    let _ = T::deserialize(&mut capture);
+    capture.fields.unwrap_or(&[])
+}
+
+/// Deserializer that records the `fields` passed to `deserialize_struct` and
+/// then bails out without producing a value.
+#[derive(Default)]
+struct FieldCapture {
+    fields: Option<&'static [&'static str]>,
+}
+
+impl<'de> de::Deserializer<'de> for &mut FieldCapture {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.fields = Some(fields);
+        Err(de::Error::custom("fields captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn valid_config_has_no_diagnostics() {
+        let contents = r#"
+model = "gpt-5"
+approval_policy = "on-request"
+
+[tui]
+notifications = true
+
+[features]
+rmcp_client = true
+
+[profiles.fast]
+model = "gpt-5-mini"
+"#;
+        assert_eq!(validate_config_toml(contents), Vec::new());
+    }
+
+    #[test]
+    fn reports_syntax_error_location() {
+        let diagnostics = validate_config_toml("model = \"gpt-5\"\nmodel_verbosity = \n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].is_error());
+        assert_eq!(diagnostics[0].location.map(|(line, _)| line), Some(2));
+    }
+
+    #[test]
+    fn reports_type_error_location() {
+        let diagnostics =
+            validate_config_toml("model = \"gpt-5\"\nmodel_context_window = \"large\"\n");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].is_error());
+        assert_eq!(diagnostics[0].location, Some((2, 24)));
+    }
+
+    #[test]
+    fn warns_on_unknown_keys() {
+        let contents = r#"modle = "gpt-5"
+
+[tui]
+notifications = true
+colour_scheme = "dark"
+
+[tui.keymap]
+sumbit = "ctrl+enter"
+
+[tui.theme.palettes.paper]
+diff_added = "green"
+
+[features]
+not_a_feature = true
+
+[profiles.fast]
+aproval_policy = "never"
+"#;
+        let warnings: Vec<String> = validate_config_toml(contents)
+            .into_iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "warning: 1:1: unknown key `modle` will be ignored".to_string(),
+                "warning: 5:1: unknown key `tui.colour_scheme` will be ignored".to_string(),
+                "warning: 8:1: unknown key `tui.keymap.sumbit` will be ignored".to_string(),
+                "warning: 11:1: unknown key `tui.theme.palettes.paper.diff_added` will be ignored"
+                    .to_string(),
+                "warning: 14:1: unknown feature `features.not_a_feature` will be ignored"
+                    .to_string(),
+                "warning: 17:1: unknown key `profiles.fast.aproval_policy` will be ignored"
+                    .to_string(),
+            ]
+        );
+    }
+}
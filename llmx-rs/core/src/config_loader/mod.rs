@@ -1,7 +1,18 @@
 mod macos;
+mod project;
 
 use crate::config::CONFIG_TOML_FILE;
+use crate::config::ProjectConfig;
+use crate::config::find_project_for_cwd;
 use macos::load_managed_admin_config_layer;
+pub use project::PROJECT_CONFIG_DIR;
+pub(crate) use project::ProjectConfigLayer;
+pub use project::RESTRICTED_PROJECT_KEYS;
+pub use project::find_project_config_dirs;
+pub use project::find_project_prompt_dirs;
+use project::load_project_layers;
+use std::collections::HashMap;
+use std::fmt;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
@@ -14,8 +25,12 @@ const LLMX_MANAGED_CONFIG_SYSTEM_PATH: &str = "/etc/llmx/managed_config.toml";
 #[derive(Debug)]
 pub(crate) struct LoadedConfigLayers {
     pub base: TomlValue,
+    /// Project layers for a trusted `cwd`, outermost first.
+    pub project: Vec<ProjectConfigLayer>,
     pub managed_config: Option<TomlValue>,
     pub managed_preferences: Option<TomlValue>,
+    pub user_config_path: PathBuf,
+    pub managed_config_path: PathBuf,
 }
 
 #[derive(Debug, Default)]
@@ -23,6 +38,40 @@ pub(crate) struct LoaderOverrides {
     pub managed_config_path: Option<PathBuf>,
     #[cfg(target_os = "macos")]
     pub managed_preferences_base64: Option<String>,
+    /// Directory to discover project config from. `None` skips project
+    /// layers entirely.
+    pub cwd: Option<PathBuf>,
+}
+
+/// Where a configuration layer was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigLayerSource {
+    User { file: PathBuf },
+    Project { file: PathBuf },
+    SessionFlags,
+    ManagedConfig { file: PathBuf },
+    ManagedPreferences,
+}
+
+impl fmt::Display for ConfigLayerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLayerSource::User { file } => write!(f, "user ({})", file.display()),
+            ConfigLayerSource::Project { file } => write!(f, "project ({})", file.display()),
+            ConfigLayerSource::SessionFlags => write!(f, "session flags (-c/--config)"),
+            ConfigLayerSource::ManagedConfig { file } => {
+                write!(f, "managed config ({})", file.display())
+            }
+            ConfigLayerSource::ManagedPreferences => write!(f, "managed preferences"),
+        }
+    }
+}
+
+/// One layer of the configuration stack.
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub source: ConfigLayerSource,
+    pub config: TomlValue,
 }
 
 // Configuration layering pipeline (top overrides bottom):
@@ -38,10 +87,21 @@ pub(crate) struct LoaderOverrides {
 //                    ^
 //                    |
 //        +-------------------------+
+//        |  -c/--config overrides  |
+//        +-------------------------+
+//                    ^
+//                    |
+//        +-------------------------+
 //        |    config.toml (base)   |
 //        +-------------------------+
+//                    ^
+//                    |
+//        +-------------------------+
+//        | .llmx/config.toml (**)  |
+//        +-------------------------+
 //
 // (*) Only available on macOS via managed device profiles.
+// (**) Only for trusted projects; nearer directories override outer ones.
 
 pub async fn load_config_as_toml(llmx_home: &Path) -> io::Result<TomlValue> {
     load_config_as_toml_with_overrides(llmx_home, LoaderOverrides::default()).await
@@ -51,19 +111,12 @@ fn default_empty_table() -> TomlValue {
     TomlValue::Table(Default::default())
 }
 
-pub(crate) async fn load_config_layers_with_overrides(
-    llmx_home: &Path,
-    overrides: LoaderOverrides,
-) -> io::Result<LoadedConfigLayers> {
-    load_config_layers_internal(llmx_home, overrides).await
-}
-
 async fn load_config_as_toml_with_overrides(
     llmx_home: &Path,
     overrides: LoaderOverrides,
 ) -> io::Result<TomlValue> {
-    let layers = load_config_layers_internal(llmx_home, overrides).await?;
-    Ok(apply_managed_layers(layers))
+    let stack = load_config_layer_stack(llmx_home, overrides, None).await?;
+    Ok(merge_config_layers(stack))
 }
 
 async fn load_config_layers_internal(
@@ -74,11 +127,13 @@ async fn load_config_layers_internal(
     let LoaderOverrides {
         managed_config_path,
         managed_preferences_base64,
+        cwd,
     } = overrides;
 
     #[cfg(not(target_os = "macos"))]
     let LoaderOverrides {
         managed_config_path,
+        cwd,
     } = overrides;
 
     let managed_config_path =
@@ -95,13 +150,106 @@ async fn load_config_layers_internal(
     #[cfg(not(target_os = "macos"))]
     let managed_preferences = load_managed_admin_config_layer(None).await?;
 
+    let base = user_config.unwrap_or_else(default_empty_table);
+    let project = match cwd {
+        Some(cwd)
+            if is_trusted_project(
+                &cwd,
+                [
+                    Some(&base),
+                    managed_config.as_ref(),
+                    managed_preferences.as_ref(),
+                ],
+            ) =>
+        {
+            load_project_layers(&cwd, llmx_home).await?
+        }
+        _ => Vec::new(),
+    };
+
     Ok(LoadedConfigLayers {
-        base: user_config.unwrap_or_else(default_empty_table),
+        base,
+        project,
         managed_config,
         managed_preferences,
+        user_config_path,
+        managed_config_path,
     })
 }
 
+/// Trust comes from the `projects` tables of the user and managed layers,
+/// never from the project config itself.
+fn is_trusted_project<'a>(
+    cwd: &Path,
+    layers: impl IntoIterator<Item = Option<&'a TomlValue>>,
+) -> bool {
+    let mut projects: HashMap<String, ProjectConfig> = HashMap::new();
+    for layer in layers.into_iter().flatten() {
+        let Some(table) = layer.get("projects") else {
+            continue;
+        };
+        let entries: Result<HashMap<String, ProjectConfig>, _> = table.clone().try_into();
+        match entries {
+            Ok(entries) => projects.extend(entries),
+            Err(err) => tracing::warn!("ignoring malformed `projects` table: {err}"),
+        }
+    }
+    find_project_for_cwd(&projects, cwd).is_some_and(|project| project.is_trusted())
+}
+
+/// Returns every layer that contributes to the effective config, lowest
+/// precedence first. `session_flags` holds the `-c/--config` overrides, if
+/// any, already expanded into a table.
+pub(crate) async fn load_config_layer_stack(
+    llmx_home: &Path,
+    overrides: LoaderOverrides,
+    session_flags: Option<TomlValue>,
+) -> io::Result<Vec<ConfigLayer>> {
+    let LoadedConfigLayers {
+        base,
+        project,
+        managed_config,
+        managed_preferences,
+        user_config_path,
+        managed_config_path,
+    } = load_config_layers_internal(llmx_home, overrides).await?;
+
+    let mut stack: Vec<ConfigLayer> = project
+        .into_iter()
+        .map(|layer| ConfigLayer {
+            source: ConfigLayerSource::Project { file: layer.path },
+            config: layer.config,
+        })
+        .collect();
+    stack.push(ConfigLayer {
+        source: ConfigLayerSource::User {
+            file: user_config_path,
+        },
+        config: base,
+    });
+    if let Some(config) = session_flags {
+        stack.push(ConfigLayer {
+            source: ConfigLayerSource::SessionFlags,
+            config,
+        });
+    }
+    if let Some(config) = managed_config {
+        stack.push(ConfigLayer {
+            source: ConfigLayerSource::ManagedConfig {
+                file: managed_config_path,
+            },
+            config,
+        });
+    }
+    if let Some(config) = managed_preferences {
+        stack.push(ConfigLayer {
+            source: ConfigLayerSource::ManagedPreferences,
+            config,
+        });
+    }
+    Ok(stack)
+}
+
 async fn read_config_from_path(
     path: &Path,
     log_missing_as_info: bool,
@@ -129,6 +277,16 @@ async fn read_config_from_path(
     }
 }
 
+/// Merges a layer stack from [`load_config_layer_stack`] into the effective
+/// config; later layers take precedence.
+pub(crate) fn merge_config_layers(stack: Vec<ConfigLayer>) -> TomlValue {
+    let mut merged = default_empty_table();
+    for layer in stack {
+        merge_toml_values(&mut merged, &layer.config);
+    }
+    merged
+}
+
 /// Merge config `overlay` into `base`, giving `overlay` precedence.
 pub(crate) fn merge_toml_values(base: &mut TomlValue, overlay: &TomlValue) {
     if let TomlValue::Table(overlay_table) = overlay
@@ -159,20 +317,6 @@ fn managed_config_default_path(llmx_home: &Path) -> PathBuf {
     }
 }
 
-fn apply_managed_layers(layers: LoadedConfigLayers) -> TomlValue {
-    let LoadedConfigLayers {
-        mut base,
-        managed_config,
-        managed_preferences,
-    } = layers;
-
-    for overlay in [managed_config, managed_preferences].into_iter().flatten() {
-        merge_toml_values(&mut base, &overlay);
-    }
-
-    base
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,6 +351,7 @@ extra = true
             managed_config_path: Some(managed_path),
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
+            cwd: None,
         };
 
         let loaded = load_config_as_toml_with_overrides(tmp.path(), overrides)
@@ -234,9 +379,10 @@ extra = true
             managed_config_path: Some(managed_path),
             #[cfg(target_os = "macos")]
             managed_preferences_base64: None,
+            cwd: None,
         };
 
-        let layers = load_config_layers_with_overrides(tmp.path(), overrides)
+        let layers = load_config_layers_internal(tmp.path(), overrides)
             .await
             .expect("load layers");
         let base_table = layers.base.as_table().expect("base table expected");
@@ -260,6 +406,80 @@ extra = true
         }
     }
 
+    fn write_project(tmp: &Path, trust_level: &str) -> (PathBuf, PathBuf) {
+        let llmx_home = tmp.join("home");
+        let project = tmp.join("project");
+        std::fs::create_dir_all(&llmx_home).expect("llmx home");
+        std::fs::create_dir_all(project.join(PROJECT_CONFIG_DIR)).expect("project .llmx");
+        std::fs::write(
+            llmx_home.join(CONFIG_TOML_FILE),
+            format!(
+                r#"model = "user"
+
+[projects."{}"]
+trust_level = "{trust_level}"
+"#,
+                project.display()
+            ),
+        )
+        .expect("write user config");
+        std::fs::write(
+            project.join(PROJECT_CONFIG_DIR).join(CONFIG_TOML_FILE),
+            r#"model = "project"
+model_reasoning_effort = "high"
+approval_policy = "never"
+"#,
+        )
+        .expect("write project config");
+        (llmx_home, project)
+    }
+
+    #[tokio::test]
+    async fn user_config_overrides_trusted_project_layer() {
+        let tmp = tempdir().expect("tempdir");
+        let (llmx_home, project) = write_project(tmp.path(), "trusted");
+        let overrides = LoaderOverrides {
+            managed_config_path: Some(tmp.path().join("managed_config.toml")),
+            #[cfg(target_os = "macos")]
+            managed_preferences_base64: None,
+            cwd: Some(project),
+        };
+
+        let loaded = load_config_as_toml_with_overrides(&llmx_home, overrides)
+            .await
+            .expect("load config");
+
+        assert_eq!(
+            loaded.get("model"),
+            Some(&TomlValue::String("user".to_string()))
+        );
+        // Keys the user leaves unset come from the project.
+        assert_eq!(
+            loaded.get("model_reasoning_effort"),
+            Some(&TomlValue::String("high".to_string()))
+        );
+        // Restricted keys in the project layer are ignored.
+        assert_eq!(loaded.get("approval_policy"), None);
+    }
+
+    #[tokio::test]
+    async fn untrusted_project_layer_is_ignored() {
+        let tmp = tempdir().expect("tempdir");
+        let (llmx_home, project) = write_project(tmp.path(), "untrusted");
+        let overrides = LoaderOverrides {
+            managed_config_path: Some(tmp.path().join("managed_config.toml")),
+            #[cfg(target_os = "macos")]
+            managed_preferences_base64: None,
+            cwd: Some(project),
+        };
+
+        let layers = load_config_layers_internal(&llmx_home, overrides)
+            .await
+            .expect("load layers");
+
+        assert!(layers.project.is_empty(), "untrusted project was layered");
+    }
+
     #[cfg(target_os = "macos")]
     #[tokio::test]
     async fn managed_preferences_take_highest_precedence() {
@@ -293,6 +513,7 @@ flag = true
         let overrides = LoaderOverrides {
             managed_config_path: Some(managed_path),
             managed_preferences_base64: Some(encoded),
+            cwd: None,
         };
 
         let loaded = load_config_as_toml_with_overrides(tmp.path(), overrides)
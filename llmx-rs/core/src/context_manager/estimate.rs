@@ -0,0 +1,264 @@
+//! Local, tokenizer-based estimation of prompt size.
+//!
+//! Providers report usage only after a response completes (and many Chat
+//! Completions backends never report it while streaming), so the session
+//! estimates the prompt locally before each request to decide whether it has
+//! to trim or compact the history first.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use llmx_protocol::models::ContentItem;
+use llmx_protocol::models::FunctionCallOutputContentItem;
+use llmx_protocol::models::ReasoningItemContent;
+use llmx_protocol::models::ReasoningItemReasoningSummary;
+use llmx_protocol::models::ResponseItem;
+use llmx_protocol::protocol::ContextEstimate;
+use llmx_utils_tokenizer::Tokenizer;
+
+/// Approximate framing overhead (role markers, separators) added per item.
+const PER_ITEM_OVERHEAD_TOKENS: i64 = 4;
+
+/// Rough cost of an inline image; providers bill images separately from text.
+const IMAGE_TOKENS: i64 = 765;
+
+/// Token counts of the instructions and tool definitions. Both stay the same
+/// for every request of a turn, so they are counted once per turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PromptOverhead {
+    pub(crate) system_tokens: i64,
+    pub(crate) tools_tokens: i64,
+}
+
+/// Counts tokens with the model's tokenizer, falling back to a
+/// 4-bytes-per-token estimate when no tokenizer can be loaded.
+#[derive(Clone)]
+pub(crate) struct TokenEstimator {
+    tokenizer: Option<Arc<Tokenizer>>,
+}
+
+impl TokenEstimator {
+    pub(crate) fn for_model(model: &str) -> Self {
+        Self {
+            tokenizer: cached_tokenizer(model),
+        }
+    }
+
+    pub(crate) fn count(&self, text: &str) -> i64 {
+        if text.is_empty() {
+            return 0;
+        }
+        match &self.tokenizer {
+            Some(tokenizer) => tokenizer.count(text),
+            None => approx_tokens_from_bytes(text.len()),
+        }
+    }
+
+    /// Estimates the token cost of a single history item.
+    pub(crate) fn count_item(&self, item: &ResponseItem) -> i64 {
+        let content = match item {
+            ResponseItem::Message { content, .. } => content
+                .iter()
+                .map(|c| match c {
+                    ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                        self.count(text)
+                    }
+                    ContentItem::InputImage { .. } => IMAGE_TOKENS,
+                })
+                .sum(),
+            ResponseItem::Reasoning {
+                summary,
+                content,
+                encrypted_content,
+                ..
+            } => {
+                let summary_tokens: i64 = summary
+                    .iter()
+                    .map(|ReasoningItemReasoningSummary::SummaryText { text }| self.count(text))
+                    .sum();
+                let content_tokens: i64 = content
+                    .iter()
+                    .flatten()
+                    .map(|c| match c {
+                        ReasoningItemContent::ReasoningText { text }
+                        | ReasoningItemContent::Text { text } => self.count(text),
+                    })
+                    .sum();
+                // Encrypted reasoning is opaque; base64 inflates the payload by 4/3.
+                let encrypted_tokens = encrypted_content
+                    .as_ref()
+                    .map(|c| approx_tokens_from_bytes(c.len() * 3 / 4))
+                    .unwrap_or(0);
+                summary_tokens + content_tokens + encrypted_tokens
+            }
+            ResponseItem::LocalShellCall { action, .. } => {
+                self.count(&serde_json::to_string(action).unwrap_or_default())
+            }
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => self.count(name) + self.count(arguments),
+            ResponseItem::CustomToolCall { name, input, .. } => {
+                self.count(name) + self.count(input)
+            }
+            ResponseItem::FunctionCallOutput { output, .. } => match &output.content_items {
+                Some(items) => items
+                    .iter()
+                    .map(|item| match item {
+                        FunctionCallOutputContentItem::InputText { text } => self.count(text),
+                        FunctionCallOutputContentItem::InputImage { .. } => IMAGE_TOKENS,
+                    })
+                    .sum(),
+                None => self.count(&output.content),
+            },
+            ResponseItem::CustomToolCallOutput { output, .. } => self.count(output),
+            ResponseItem::WebSearchCall { action, .. } => {
+                self.count(&serde_json::to_string(action).unwrap_or_default())
+            }
+            ResponseItem::GhostSnapshot { .. } | ResponseItem::Other => return 0,
+        };
+        content + PER_ITEM_OVERHEAD_TOKENS
+    }
+
+    /// Counts the instructions and serialized tool definitions.
+    pub(crate) fn prompt_overhead(&self, instructions: &str, tools_json: &str) -> PromptOverhead {
+        PromptOverhead {
+            system_tokens: self.count(instructions),
+            tools_tokens: self.count(tools_json),
+        }
+    }
+
+    /// Estimates the full prompt: the turn's `overhead` plus every history
+    /// item, grouped into the buckets shown by `/status`.
+    pub(crate) fn estimate_prompt(
+        &self,
+        overhead: PromptOverhead,
+        input: &[ResponseItem],
+    ) -> ContextEstimate {
+        let mut estimate = ContextEstimate {
+            system_tokens: overhead.system_tokens,
+            tools_tokens: overhead.tools_tokens,
+            ..ContextEstimate::default()
+        };
+        for item in input {
+            let tokens = self.count_item(item);
+            match item {
+                ResponseItem::Message { role, .. } if role == "developer" || role == "system" => {
+                    estimate.system_tokens += tokens;
+                }
+                ResponseItem::FunctionCallOutput { .. }
+                | ResponseItem::CustomToolCallOutput { .. } => {
+                    estimate.tool_output_tokens += tokens;
+                }
+                _ => estimate.history_tokens += tokens,
+            }
+        }
+        estimate
+    }
+}
+
+fn approx_tokens_from_bytes(bytes: usize) -> i64 {
+    i64::try_from(bytes.div_ceil(4)).unwrap_or(i64::MAX)
+}
+
+/// Building a BPE is expensive, so tokenizers are cached per model slug.
+fn cached_tokenizer(model: &str) -> Option<Arc<Tokenizer>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<Arc<Tokenizer>>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut guard = cache.lock().ok()?;
+    guard
+        .entry(model.to_string())
+        .or_insert_with(|| Tokenizer::for_model(model).ok().map(Arc::new))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn estimate_groups_items_into_buckets() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let items = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "be terse".to_string(),
+                }],
+            },
+            user_message("hello world"),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"ls\"]}".to_string(),
+                call_id: "call-1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "Cargo.toml\nsrc".to_string(),
+                    ..Default::default()
+                },
+            },
+        ];
+
+        let overhead = estimator.prompt_overhead("You are a helpful agent.", "[]");
+        let estimate = estimator.estimate_prompt(overhead, &items);
+
+        assert_eq!(
+            estimate.system_tokens,
+            estimator.count("You are a helpful agent.") + estimator.count_item(&items[0])
+        );
+        assert_eq!(estimate.tools_tokens, estimator.count("[]"));
+        assert_eq!(
+            estimate.history_tokens,
+            estimator.count_item(&items[1]) + estimator.count_item(&items[2])
+        );
+        assert_eq!(estimate.tool_output_tokens, estimator.count_item(&items[3]));
+        assert_eq!(
+            estimate.total_tokens(),
+            estimate.system_tokens
+                + estimate.tools_tokens
+                + estimate.history_tokens
+                + estimate.tool_output_tokens
+        );
+    }
+
+    #[test]
+    fn images_cost_a_flat_rate_regardless_of_payload_size() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let image_url = format!("data:image/png;base64,{}", "A".repeat(400_000));
+        let screenshot = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: image_url.clone(),
+                content_items: Some(vec![
+                    FunctionCallOutputContentItem::InputText {
+                        text: "screenshot".to_string(),
+                    },
+                    FunctionCallOutputContentItem::InputImage { image_url },
+                ]),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            estimator.count_item(&screenshot),
+            estimator.count("screenshot") + IMAGE_TOKENS + PER_ITEM_OVERHEAD_TOKENS
+        );
+    }
+}
@@ -1,12 +1,20 @@
+use llmx_protocol::models::FunctionCallOutputContentItem;
 use llmx_protocol::models::FunctionCallOutputPayload;
 use llmx_protocol::models::ResponseItem;
 use llmx_protocol::protocol::TokenUsage;
 use llmx_protocol::protocol::TokenUsageInfo;
 use std::ops::Deref;
 
+use crate::context_manager::TokenEstimator;
 use crate::context_manager::normalize;
+use crate::context_manager::prune::prune_stale_tool_outputs;
 use crate::context_manager::truncate::format_output_for_model_body;
 use crate::context_manager::truncate::globally_truncate_function_output_items;
+use crate::truncate::truncate_middle;
+
+/// Byte budget an old tool output is shrunk to when the prompt is over the
+/// auto-compaction limit.
+const TRIMMED_TOOL_OUTPUT_MAX_BYTES: usize = 1024;
 
 /// Transcript of conversation history
 #[derive(Debug, Clone, Default)]
@@ -14,6 +22,8 @@ pub(crate) struct ContextManager {
     /// The oldest items are at the beginning of the vector.
     items: Vec<ResponseItem>,
     token_info: Option<TokenUsageInfo>,
+    /// Tokens freed so far by eliding or trimming tool outputs.
+    reclaimed_tokens: i64,
 }
 
 impl ContextManager {
@@ -21,6 +31,7 @@ impl ContextManager {
         Self {
             items: Vec::new(),
             token_info: TokenUsageInfo::new_or_append(&None, &None, None),
+            reclaimed_tokens: 0,
         }
     }
 
@@ -80,6 +91,72 @@ impl ContextManager {
         }
     }
 
+    pub(crate) fn reclaimed_tokens(&self) -> i64 {
+        self.reclaimed_tokens
+    }
+
+    /// Replaces stale tool outputs (superseded file reads, repeated searches,
+    /// old shell logs) with short stubs until roughly `tokens_to_reclaim`
+    /// tokens have been freed. Returns the number of tokens reclaimed.
+    pub(crate) fn prune_stale_tool_outputs(
+        &mut self,
+        estimator: &TokenEstimator,
+        tokens_to_reclaim: i64,
+    ) -> i64 {
+        let reclaimed = prune_stale_tool_outputs(&mut self.items, estimator, tokens_to_reclaim);
+        self.reclaimed_tokens += reclaimed;
+        reclaimed
+    }
+
+    /// Shrinks tool outputs, oldest first, until roughly `tokens_to_reclaim`
+    /// tokens have been freed. The most recent output is left untouched since
+    /// the model is most likely still reasoning about it. Returns the number
+    /// of tokens reclaimed.
+    pub(crate) fn trim_tool_outputs(
+        &mut self,
+        estimator: &TokenEstimator,
+        tokens_to_reclaim: i64,
+    ) -> i64 {
+        let Some(last_output_idx) = self.items.iter().rposition(is_tool_output) else {
+            return 0;
+        };
+
+        let mut reclaimed = 0;
+        for item in self.items[..last_output_idx].iter_mut() {
+            if reclaimed >= tokens_to_reclaim {
+                break;
+            }
+            let content = match item {
+                ResponseItem::FunctionCallOutput { output, .. } => {
+                    let has_image = output.content_items.iter().flatten().any(|item| {
+                        matches!(item, FunctionCallOutputContentItem::InputImage { .. })
+                    });
+                    if has_image {
+                        continue;
+                    }
+                    &mut output.content
+                }
+                ResponseItem::CustomToolCallOutput { output, .. } => output,
+                _ => continue,
+            };
+            if content.len() <= TRIMMED_TOOL_OUTPUT_MAX_BYTES {
+                continue;
+            }
+            let before = estimator.count(content);
+            let (trimmed, _) = truncate_middle(content, TRIMMED_TOOL_OUTPUT_MAX_BYTES);
+            let after = estimator.count(&trimmed);
+            *content = trimmed;
+            if let ResponseItem::FunctionCallOutput { output, .. } = item {
+                // Structured items mirror `content`; drop them so the trimmed
+                // text is what the model sees.
+                output.content_items = None;
+            }
+            reclaimed += (before - after).max(0);
+        }
+        self.reclaimed_tokens += reclaimed;
+        reclaimed
+    }
+
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
         self.items = items;
     }
@@ -152,6 +229,13 @@ impl ContextManager {
     }
 }
 
+fn is_tool_output(item: &ResponseItem) -> bool {
+    matches!(
+        item,
+        ResponseItem::FunctionCallOutput { .. } | ResponseItem::CustomToolCallOutput { .. }
+    )
+}
+
 /// API messages include every non-system item (user/assistant messages, reasoning,
 /// tool calls, tool outputs, shell calls, and web-search calls).
 fn is_api_message(message: &ResponseItem) -> bool {
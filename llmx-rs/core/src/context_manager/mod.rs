@@ -1,6 +1,10 @@
+mod estimate;
 mod history;
 mod normalize;
+mod prune;
 mod truncate;
 
+pub(crate) use estimate::PromptOverhead;
+pub(crate) use estimate::TokenEstimator;
 pub(crate) use history::ContextManager;
 pub(crate) use truncate::format_output_for_model_body;
@@ -0,0 +1,522 @@
+//! Selective pruning of stale tool outputs.
+//!
+//! Before resorting to a full summary, the history is scanned for tool outputs
+//! that no longer carry information the model needs:
+//!
+//! - `read_file` results for files that a later `apply_patch` edited,
+//! - `grep_files` results superseded by an identical later search,
+//! - long shell logs that are not among the most recent ones.
+//!
+//! Each one is replaced by a short stub so the call/output pairing stays
+//! intact and the model knows it can re-run the tool to see the content again.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use llmx_protocol::models::FunctionCallOutputContentItem;
+use llmx_protocol::models::ResponseItem;
+use serde_json::Value;
+
+use crate::context_manager::TokenEstimator;
+
+/// Outputs smaller than this are not worth replacing with a stub.
+const MIN_ELIDED_OUTPUT_TOKENS: i64 = 200;
+
+/// Shell outputs above this size are considered "long logs".
+const LONG_SHELL_OUTPUT_TOKENS: i64 = 2_000;
+
+/// The most recent long shell outputs are kept verbatim.
+const RECENT_SHELL_OUTPUTS_KEPT: usize = 2;
+
+const ELIDED_OUTPUT_PREFIX: &str = "[output elided, ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolKind {
+    ReadFile,
+    GrepFiles,
+    Shell,
+    ApplyPatch,
+    Other,
+}
+
+struct ToolCall {
+    kind: ToolKind,
+    arguments: String,
+}
+
+/// Replaces stale tool outputs with stubs, oldest first, until roughly
+/// `tokens_to_reclaim` tokens have been freed. Returns the number of tokens
+/// reclaimed.
+pub(crate) fn prune_stale_tool_outputs(
+    items: &mut [ResponseItem],
+    estimator: &TokenEstimator,
+    tokens_to_reclaim: i64,
+) -> i64 {
+    let calls = collect_calls(items);
+    let candidates = stale_output_indices(items, &calls, estimator);
+
+    let mut reclaimed = 0;
+    for idx in candidates {
+        if reclaimed >= tokens_to_reclaim {
+            break;
+        }
+        reclaimed += elide_output(&mut items[idx], estimator);
+    }
+    reclaimed
+}
+
+fn collect_calls(items: &[ResponseItem]) -> HashMap<String, ToolCall> {
+    let mut calls = HashMap::new();
+    for item in items {
+        match item {
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let kind = tool_kind(name, arguments);
+                calls.insert(
+                    call_id.clone(),
+                    ToolCall {
+                        kind,
+                        arguments: arguments.clone(),
+                    },
+                );
+            }
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                let kind = if name == "apply_patch" {
+                    ToolKind::ApplyPatch
+                } else {
+                    ToolKind::Other
+                };
+                calls.insert(
+                    call_id.clone(),
+                    ToolCall {
+                        kind,
+                        arguments: input.clone(),
+                    },
+                );
+            }
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                if let Some(call_id) = call_id.clone().or_else(|| id.clone()) {
+                    calls.insert(
+                        call_id,
+                        ToolCall {
+                            kind: ToolKind::Shell,
+                            arguments: serde_json::to_string(action).unwrap_or_default(),
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    calls
+}
+
+fn tool_kind(name: &str, arguments: &str) -> ToolKind {
+    match name {
+        "read_file" => ToolKind::ReadFile,
+        "grep_files" => ToolKind::GrepFiles,
+        "apply_patch" => ToolKind::ApplyPatch,
+        "shell" | "container.exec" | "local_shell" | "exec_command" => {
+            if shell_patch_input(arguments).is_some() {
+                ToolKind::ApplyPatch
+            } else {
+                ToolKind::Shell
+            }
+        }
+        _ => ToolKind::Other,
+    }
+}
+
+/// Returns the indices of outputs that can be elided, ordered oldest first.
+fn stale_output_indices(
+    items: &[ResponseItem],
+    calls: &HashMap<String, ToolCall>,
+    estimator: &TokenEstimator,
+) -> Vec<usize> {
+    // Position of the last edit for every patched path, and of the last
+    // occurrence of every grep query.
+    let mut last_edit: HashMap<PathBuf, usize> = HashMap::new();
+    let mut last_grep: HashMap<String, usize> = HashMap::new();
+    let mut shell_outputs: Vec<usize> = Vec::new();
+
+    for (idx, item) in items.iter().enumerate() {
+        let Some(call) = call_for_item(item, calls) else {
+            continue;
+        };
+        match call.kind {
+            ToolKind::ApplyPatch => {
+                for path in patched_paths(&call.arguments) {
+                    last_edit.insert(path, idx);
+                }
+            }
+            ToolKind::GrepFiles if is_output(item) => {
+                last_grep.insert(normalize_arguments(&call.arguments), idx);
+            }
+            ToolKind::Shell if is_output(item) => shell_outputs.push(idx),
+            _ => {}
+        }
+    }
+
+    let recent_shell_cutoff = shell_outputs
+        .len()
+        .saturating_sub(RECENT_SHELL_OUTPUTS_KEPT);
+    let old_shell_outputs = &shell_outputs[..recent_shell_cutoff];
+
+    let mut candidates = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        if !is_output(item) || is_elided(item) {
+            continue;
+        }
+        let Some(call) = call_for_item(item, calls) else {
+            continue;
+        };
+        let tokens = output_tokens(item, estimator);
+        if tokens < MIN_ELIDED_OUTPUT_TOKENS {
+            continue;
+        }
+        let stale = match call.kind {
+            ToolKind::ReadFile => read_file_path(&call.arguments).is_some_and(|read_path| {
+                last_edit
+                    .iter()
+                    .any(|(edited, edit_idx)| *edit_idx > idx && same_file(&read_path, edited))
+            }),
+            ToolKind::GrepFiles => last_grep
+                .get(&normalize_arguments(&call.arguments))
+                .is_some_and(|last_idx| *last_idx > idx),
+            ToolKind::Shell => {
+                tokens >= LONG_SHELL_OUTPUT_TOKENS && old_shell_outputs.contains(&idx)
+            }
+            ToolKind::ApplyPatch | ToolKind::Other => false,
+        };
+        if stale {
+            candidates.push(idx);
+        }
+    }
+    candidates
+}
+
+fn call_for_item<'a>(
+    item: &ResponseItem,
+    calls: &'a HashMap<String, ToolCall>,
+) -> Option<&'a ToolCall> {
+    match item {
+        ResponseItem::FunctionCall { call_id, .. }
+        | ResponseItem::CustomToolCall { call_id, .. }
+        | ResponseItem::FunctionCallOutput { call_id, .. }
+        | ResponseItem::CustomToolCallOutput { call_id, .. } => calls.get(call_id),
+        ResponseItem::LocalShellCall { id, call_id, .. } => call_id
+            .as_ref()
+            .or(id.as_ref())
+            .and_then(|call_id| calls.get(call_id)),
+        _ => None,
+    }
+}
+
+fn is_output(item: &ResponseItem) -> bool {
+    matches!(
+        item,
+        ResponseItem::FunctionCallOutput { .. } | ResponseItem::CustomToolCallOutput { .. }
+    )
+}
+
+fn is_elided(item: &ResponseItem) -> bool {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            output.content.starts_with(ELIDED_OUTPUT_PREFIX)
+        }
+        ResponseItem::CustomToolCallOutput { output, .. } => {
+            output.starts_with(ELIDED_OUTPUT_PREFIX)
+        }
+        _ => false,
+    }
+}
+
+fn output_tokens(item: &ResponseItem, estimator: &TokenEstimator) -> i64 {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => estimator.count(&output.content),
+        ResponseItem::CustomToolCallOutput { output, .. } => estimator.count(output),
+        _ => 0,
+    }
+}
+
+/// Replaces the output with a stub and returns the number of tokens freed.
+fn elide_output(item: &mut ResponseItem, estimator: &TokenEstimator) -> i64 {
+    let before = output_tokens(item, estimator);
+    let stub = elided_stub(before);
+    let after = estimator.count(&stub);
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => {
+            output.content = stub;
+            output.content_items = output.content_items.as_ref().map(|_| {
+                vec![FunctionCallOutputContentItem::InputText {
+                    text: output.content.clone(),
+                }]
+            });
+        }
+        ResponseItem::CustomToolCallOutput { output, .. } => *output = stub,
+        _ => return 0,
+    }
+    (before - after).max(0)
+}
+
+fn elided_stub(tokens: i64) -> String {
+    let size = if tokens >= 1_000 {
+        format!("{}k", tokens / 1_000)
+    } else {
+        tokens.to_string()
+    };
+    format!("{ELIDED_OUTPUT_PREFIX}{size} tokens; re-run to view]")
+}
+
+/// Re-serializes JSON arguments so that equivalent queries compare equal
+/// regardless of key order or whitespace.
+fn normalize_arguments(arguments: &str) -> String {
+    serde_json::from_str::<Value>(arguments)
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| arguments.to_string())
+}
+
+fn read_file_path(arguments: &str) -> Option<PathBuf> {
+    let value: Value = serde_json::from_str(arguments).ok()?;
+    value
+        .get("file_path")
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+}
+
+/// Extracts the patch body from a shell invocation of `apply_patch`.
+fn shell_patch_input(arguments: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(arguments).ok()?;
+    let command = value.get("command")?.as_array()?;
+    match command.as_slice() {
+        [program, patch] if matches!(program.as_str(), Some("apply_patch" | "applypatch")) => {
+            patch.as_str().map(str::to_string)
+        }
+        _ => None,
+    }
+}
+
+/// Paths touched by a patch, whether it was passed as raw freeform input,
+/// as `{"input": ...}` function arguments, or through a shell invocation.
+fn patched_paths(arguments: &str) -> Vec<PathBuf> {
+    let patch = serde_json::from_str::<Value>(arguments)
+        .ok()
+        .and_then(|value| {
+            value
+                .get("input")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .or_else(|| shell_patch_input(arguments))
+        .unwrap_or_else(|| arguments.to_string());
+
+    const MARKERS: [&str; 4] = [
+        "*** Update File: ",
+        "*** Add File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+    ];
+    patch
+        .lines()
+        .filter_map(|line| {
+            MARKERS
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+                .map(|path| PathBuf::from(path.trim()))
+        })
+        .collect()
+}
+
+/// `read_file` takes absolute paths while patches usually use paths relative
+/// to the session cwd, so compare by trailing components.
+fn same_file(read_path: &Path, edited: &Path) -> bool {
+    if edited.is_absolute() {
+        read_path == edited
+    } else {
+        read_path.ends_with(edited)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn call(name: &str, arguments: Value, call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str, content: String) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn output_content(item: &ResponseItem) -> &str {
+        match item {
+            ResponseItem::FunctionCallOutput { output, .. } => &output.content,
+            ResponseItem::CustomToolCallOutput { output, .. } => output,
+            other => panic!("expected tool output, got {other:?}"),
+        }
+    }
+
+    fn big_output(lines: usize) -> String {
+        (0..lines)
+            .map(|i| format!("line {i}: some moderately long text"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn elides_read_file_output_for_later_edited_file() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let mut items = vec![
+            call(
+                "read_file",
+                serde_json::json!({"file_path": "/repo/src/lib.rs"}),
+                "read-1",
+            ),
+            output("read-1", big_output(200)),
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "patch-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: src/lib.rs\n@@\n-a\n+b\n*** End Patch"
+                    .to_string(),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "patch-1".to_string(),
+                output: "Success".to_string(),
+            },
+        ];
+
+        let reclaimed = prune_stale_tool_outputs(&mut items, &estimator, i64::MAX);
+
+        assert!(reclaimed > 0);
+        assert!(output_content(&items[1]).starts_with(ELIDED_OUTPUT_PREFIX));
+        assert_eq!(output_content(&items[3]), "Success");
+    }
+
+    #[test]
+    fn keeps_read_file_output_when_edit_happened_before_read() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let mut items = vec![
+            call(
+                "apply_patch",
+                serde_json::json!({
+                    "input": "*** Begin Patch\n*** Add File: notes.md\n+hi\n*** End Patch"
+                }),
+                "patch-1",
+            ),
+            output("patch-1", "Success".to_string()),
+            call(
+                "read_file",
+                serde_json::json!({"file_path": "/repo/notes.md"}),
+                "read-1",
+            ),
+            output("read-1", big_output(200)),
+        ];
+
+        let reclaimed = prune_stale_tool_outputs(&mut items, &estimator, i64::MAX);
+
+        assert_eq!(reclaimed, 0);
+        assert!(!output_content(&items[3]).starts_with(ELIDED_OUTPUT_PREFIX));
+    }
+
+    #[test]
+    fn elides_repeated_grep_and_old_shell_logs() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let grep_args = serde_json::json!({"pattern": "fn main", "path": "src"});
+        let mut items = vec![
+            call("grep_files", grep_args.clone(), "grep-1"),
+            output("grep-1", big_output(100)),
+            call(
+                "shell",
+                serde_json::json!({"command": ["cargo", "build"]}),
+                "sh-1",
+            ),
+            output("sh-1", big_output(400)),
+            call(
+                "shell",
+                serde_json::json!({"command": ["cargo", "test"]}),
+                "sh-2",
+            ),
+            output("sh-2", big_output(400)),
+            call(
+                "shell",
+                serde_json::json!({"command": ["cargo", "fmt"]}),
+                "sh-3",
+            ),
+            output("sh-3", big_output(400)),
+            call("grep_files", grep_args, "grep-2"),
+            output("grep-2", big_output(100)),
+        ];
+
+        prune_stale_tool_outputs(&mut items, &estimator, i64::MAX);
+
+        let elided: Vec<bool> = items
+            .iter()
+            .filter(|item| is_output(item))
+            .map(is_elided)
+            .collect();
+        assert_eq!(elided, vec![true, true, false, false, false]);
+    }
+
+    #[test]
+    fn stops_once_enough_tokens_are_reclaimed() {
+        let estimator = TokenEstimator::for_model("gpt-5");
+        let grep_args = serde_json::json!({"pattern": "todo"});
+        let mut items = vec![
+            call("grep_files", grep_args.clone(), "grep-1"),
+            output("grep-1", big_output(100)),
+            call("grep_files", grep_args.clone(), "grep-2"),
+            output("grep-2", big_output(100)),
+            call("grep_files", grep_args, "grep-3"),
+            output("grep-3", big_output(100)),
+        ];
+
+        prune_stale_tool_outputs(&mut items, &estimator, 1);
+
+        assert!(is_elided(&items[1]));
+        assert!(!is_elided(&items[3]));
+        assert!(!is_elided(&items[5]));
+    }
+
+    #[test]
+    fn stub_reports_size_in_thousands() {
+        assert_eq!(
+            elided_stub(12_345),
+            "[output elided, 12k tokens; re-run to view]"
+        );
+        assert_eq!(
+            elided_stub(640),
+            "[output elided, 640 tokens; re-run to view]"
+        );
+    }
+}
@@ -1,8 +1,30 @@
+use crate::error::get_error_message_ui;
+use crate::exec_env::create_env;
+use crate::git_info::get_git_repo_root;
+use crate::llmx::Session;
+use crate::llmx::TurnContext;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
+use crate::tools::format_exec_output_str;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+use crate::truncate::truncate_middle;
+use crate::user_shell_command::user_shell_invocation;
+use llmx_protocol::config_types::ReasoningEffort;
 use llmx_protocol::custom_prompts::CustomPrompt;
+use llmx_protocol::custom_prompts::PromptArgument;
+use llmx_protocol::custom_prompts::PromptArgumentType;
+use llmx_protocol::custom_prompts::PromptArguments;
+use serde::Deserialize;
+use serde::de::IntoDeserializer;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::fs;
+use uuid::Uuid;
 
 /// Return the default prompts directory: `$LLMX_HOME/prompts`.
 /// If `LLMX_HOME` cannot be resolved, returns `None`.
@@ -62,78 +84,131 @@ pub async fn discover_prompts_in_excluding(
             Ok(s) => s,
             Err(_) => continue,
         };
-        let (description, argument_hint, body) = parse_frontmatter(&content);
+        let (meta, body) = parse_frontmatter(&content);
         out.push(CustomPrompt {
             name,
             path,
             content: body,
-            description,
-            argument_hint,
+            description: meta.description,
+            argument_hint: meta.argument_hint,
+            arguments: meta.arguments,
+            model: meta.model,
+            reasoning_effort: meta.reasoning_effort,
+            allowed_tools: meta.allowed_tools,
         });
     }
     out.sort_by(|a, b| a.name.cmp(&b.name));
     out
 }
 
+/// Discover prompts across several directories. A prompt in an earlier
+/// directory shadows any prompt with the same name in a later one. Returns
+/// entries sorted by name.
+pub async fn discover_prompts_in_dirs(dirs: &[PathBuf]) -> Vec<CustomPrompt> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut out: Vec<CustomPrompt> = Vec::new();
+    for dir in dirs {
+        let found = discover_prompts_in_excluding(dir, &seen).await;
+        seen.extend(found.iter().map(|prompt| prompt.name.clone()));
+        out.extend(found);
+    }
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+/// Metadata read from a prompt file's frontmatter.
+#[derive(Debug, Default, PartialEq)]
+struct Frontmatter {
+    description: Option<String>,
+    argument_hint: Option<String>,
+    arguments: Vec<PromptArgument>,
+    model: Option<String>,
+    reasoning_effort: Option<ReasoningEffort>,
+    allowed_tools: Option<Vec<String>>,
+}
+
 /// Parse optional YAML-like frontmatter at the beginning of `content`.
 /// Supported keys:
 /// - `description`: short description shown in the slash popup
 /// - `argument-hint` or `argument_hint`: brief hint string shown after the description
-///   Returns (description, argument_hint, body_without_frontmatter).
-fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String) {
+/// - `model`, `reasoning_effort` (or `reasoning-effort`): overrides for the turn the prompt starts
+/// - `allowed_tools` (or `allowed-tools`): comma-separated tool names, optionally in `[...]`
+/// - `arguments`: followed by indented `NAME: attr...` lines, see [`parse_argument`]
+///
+/// Returns the metadata and the body without the frontmatter.
+fn parse_frontmatter(content: &str) -> (Frontmatter, String) {
     let mut segments = content.split_inclusive('\n');
     let Some(first_segment) = segments.next() else {
-        return (None, None, String::new());
+        return (Frontmatter::default(), String::new());
     };
     let first_line = first_segment.trim_end_matches(['\r', '\n']);
     if first_line.trim() != "---" {
-        return (None, None, content.to_string());
+        return (Frontmatter::default(), content.to_string());
     }
 
-    let mut desc: Option<String> = None;
-    let mut hint: Option<String> = None;
+    let mut meta = Frontmatter::default();
+    let mut in_arguments = false;
     let mut frontmatter_closed = false;
     let mut consumed = first_segment.len();
 
     for segment in segments {
         let line = segment.trim_end_matches(['\r', '\n']);
         let trimmed = line.trim();
+        consumed += segment.len();
 
         if trimmed == "---" {
             frontmatter_closed = true;
-            consumed += segment.len();
             break;
         }
 
         if trimmed.is_empty() || trimmed.starts_with('#') {
-            consumed += segment.len();
             continue;
         }
 
+        let indented = line.starts_with([' ', '\t']);
+        if in_arguments && indented {
+            let entry = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+            match parse_argument(entry) {
+                Some(argument) => meta.arguments.push(argument),
+                None => tracing::warn!("ignoring invalid prompt argument declaration: {entry}"),
+            }
+            continue;
+        }
+        in_arguments = false;
+
         if let Some((k, v)) = trimmed.split_once(':') {
             let key = k.trim().to_ascii_lowercase();
-            let mut val = v.trim().to_string();
-            if val.len() >= 2 {
-                let bytes = val.as_bytes();
-                let first = bytes[0];
-                let last = bytes[bytes.len() - 1];
-                if (first == b'\"' && last == b'\"') || (first == b'\'' && last == b'\'') {
-                    val = val[1..val.len().saturating_sub(1)].to_string();
-                }
-            }
+            let val = unquote(v.trim());
             match key.as_str() {
-                "description" => desc = Some(val),
-                "argument-hint" | "argument_hint" => hint = Some(val),
+                "description" => meta.description = Some(val),
+                "argument-hint" | "argument_hint" => meta.argument_hint = Some(val),
+                "model" if !val.is_empty() => meta.model = Some(val),
+                "reasoning_effort" | "reasoning-effort" => {
+                    match ReasoningEffort::deserialize(val.as_str().into_deserializer()) {
+                        Ok(effort) => meta.reasoning_effort = Some(effort),
+                        Err::<_, serde::de::value::Error>(_) => {
+                            tracing::warn!("ignoring unknown prompt reasoning_effort: {val}");
+                        }
+                    }
+                }
+                "allowed_tools" | "allowed-tools" => {
+                    let list = val.trim_start_matches('[').trim_end_matches(']');
+                    meta.allowed_tools = Some(
+                        list.split(',')
+                            .map(|tool| unquote(tool.trim()))
+                            .filter(|tool| !tool.is_empty())
+                            .collect(),
+                    );
+                }
+                "arguments" => in_arguments = true,
                 _ => {}
             }
         }
-
-        consumed += segment.len();
     }
 
     if !frontmatter_closed {
         // Unterminated frontmatter: treat input as-is.
-        return (None, None, content.to_string());
+        return (Frontmatter::default(), content.to_string());
     }
 
     let body = if consumed >= content.len() {
@@ -141,7 +216,270 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     } else {
         content[consumed..].to_string()
     };
-    (desc, hint, body)
+    (meta, body)
+}
+
+/// Parses one `arguments:` entry of the form
+/// `NAME: [required] [type=string|number|boolean|enum] [default=VALUE]
+/// [choices=a|b] [description="..."]`. Attributes are split with shell
+/// quoting rules. Returns `None` when the name is not an upper-case
+/// placeholder or an attribute is not recognized.
+fn parse_argument(entry: &str) -> Option<PromptArgument> {
+    let (name, attrs) = entry.split_once(':').unwrap_or((entry, ""));
+    let name = name.trim().trim_start_matches('$');
+    let valid_name = name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !valid_name {
+        return None;
+    }
+
+    let mut argument = PromptArgument {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    let mut kind = None;
+    for token in shlex::Shlex::new(attrs) {
+        let token = token.trim_end_matches(',');
+        if token.is_empty() {
+            continue;
+        }
+        let (key, value) = token.split_once('=').unwrap_or((token, ""));
+        match key {
+            "required" => argument.required = value.is_empty() || value == "true",
+            "optional" => argument.required = false,
+            "type" => {
+                kind = Some(match value {
+                    "string" => PromptArgumentType::String,
+                    "number" => PromptArgumentType::Number,
+                    "boolean" | "bool" => PromptArgumentType::Boolean,
+                    "enum" => PromptArgumentType::Enum,
+                    _ => return None,
+                });
+            }
+            "default" => argument.default = Some(value.to_string()),
+            "choices" => {
+                argument.choices = value
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|choice| !choice.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "description" => argument.description = Some(value.to_string()),
+            _ => return None,
+        }
+    }
+    argument.kind = match kind {
+        Some(kind) => kind,
+        None if !argument.choices.is_empty() => PromptArgumentType::Enum,
+        None => PromptArgumentType::String,
+    };
+    if argument.kind == PromptArgumentType::Enum && argument.choices.is_empty() {
+        return None;
+    }
+    Some(argument)
+}
+
+fn unquote(val: &str) -> String {
+    if val.len() >= 2 {
+        let bytes = val.as_bytes();
+        let first = bytes[0];
+        let last = bytes[bytes.len() - 1];
+        if (first == b'\"' && last == b'\"') || (first == b'\'' && last == b'\'') {
+            return val[1..val.len() - 1].to_string();
+        }
+    }
+    val.to_string()
+}
+
+/// Largest file inlined by an `@file` include; larger files are truncated in
+/// the middle.
+const MAX_INCLUDE_BYTES: usize = 64 * 1024;
+
+/// A piece of an invoked prompt body.
+#[derive(Debug, PartialEq)]
+enum PromptSegment<'a> {
+    Text(&'a str),
+    /// `@path`, resolved relative to the repository root.
+    Include(&'a str),
+    /// `` !`command` ``, run like a shell tool call.
+    Command(&'a str),
+}
+
+/// Splits `text` into literal text, `@path` includes, and `` !`command` ``
+/// blocks. An include must start the text or follow whitespace, so e-mail
+/// addresses are left alone; trailing punctuation is not part of the path.
+fn prompt_segments(text: &str) -> Vec<PromptSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        if let Some(after) = rest.strip_prefix("!`")
+            && let Some(end) = after.find(['`', '\n'])
+            && after[end..].starts_with('`')
+            && !after[..end].trim().is_empty()
+        {
+            if literal_start < i {
+                segments.push(PromptSegment::Text(&text[literal_start..i]));
+            }
+            segments.push(PromptSegment::Command(after[..end].trim()));
+            i += 2 + end + 1;
+            literal_start = i;
+            continue;
+        }
+        let at_boundary = text[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == '(');
+        if let Some(after) = rest.strip_prefix('@')
+            && at_boundary
+        {
+            let token_len = after.find(char::is_whitespace).unwrap_or(after.len());
+            let path = after[..token_len]
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '\'', '"']);
+            if !path.is_empty() {
+                if literal_start < i {
+                    segments.push(PromptSegment::Text(&text[literal_start..i]));
+                }
+                segments.push(PromptSegment::Include(path));
+                i += 1 + path.len();
+                literal_start = i;
+                continue;
+            }
+        }
+        i += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    if literal_start < text.len() {
+        segments.push(PromptSegment::Text(&text[literal_start..]));
+    }
+    segments
+}
+
+/// Whether `text` has any `@path` includes or `` !`command` `` blocks.
+pub(crate) fn has_prompt_directives(text: &str) -> bool {
+    prompt_segments(text)
+        .iter()
+        .any(|segment| !matches!(segment, PromptSegment::Text(_)))
+}
+
+/// Resolves `@path` against `root`, refusing paths that escape it.
+fn resolve_include(root: &Path, path: &str) -> Option<PathBuf> {
+    let root = root.canonicalize().ok()?;
+    let resolved = root.join(path).canonicalize().ok()?;
+    (resolved.starts_with(&root) && resolved.is_file()).then_some(resolved)
+}
+
+/// Expands the `@path` includes and `` !`command` `` blocks in the body of an
+/// invoked prompt, then substitutes `arguments` into the remaining literal
+/// text, so argument values are never run or included. Includes are resolved
+/// relative to the repository root (or the session cwd outside a repository);
+/// includes that do not name a file inside it are left as written. Commands
+/// run in the session cwd like shell tool calls, subject to the turn's
+/// approval policy and sandbox, and are replaced by their output.
+pub(crate) async fn render_prompt_text(
+    text: &str,
+    arguments: &PromptArguments,
+    session: &Session,
+    turn_context: &TurnContext,
+) -> String {
+    let root = get_git_repo_root(&turn_context.cwd).unwrap_or_else(|| turn_context.cwd.clone());
+    let mut out = String::with_capacity(text.len());
+    for segment in prompt_segments(text) {
+        match segment {
+            PromptSegment::Text(literal) => out.push_str(&arguments.substitute(literal)),
+            PromptSegment::Include(path) => {
+                let contents = match resolve_include(&root, path) {
+                    Some(resolved) => fs::read_to_string(&resolved).await.ok(),
+                    None => None,
+                };
+                match contents {
+                    Some(contents) => {
+                        let (contents, _) = truncate_middle(&contents, MAX_INCLUDE_BYTES);
+                        out.push_str(&format!(
+                            "`{path}`:\n```\n{}\n```\n",
+                            contents.trim_end_matches('\n')
+                        ));
+                    }
+                    None => {
+                        tracing::warn!(
+                            "prompt include @{path} is not a file in {}",
+                            root.display()
+                        );
+                        out.push('@');
+                        out.push_str(path);
+                    }
+                }
+            }
+            PromptSegment::Command(command) => {
+                out.push_str(&run_prompt_command(command, session, turn_context).await);
+            }
+        }
+    }
+    out
+}
+
+async fn run_prompt_command(
+    command: &str,
+    session: &Session,
+    turn_context: &TurnContext,
+) -> String {
+    let call_id = Uuid::new_v4().to_string();
+    let invocation = user_shell_invocation(session.user_shell(), command);
+    let emitter = ToolEmitter::shell(invocation.clone(), turn_context.cwd.clone(), false);
+    emitter
+        .begin(ToolEventCtx::new(session, turn_context, &call_id, None))
+        .await;
+
+    let req = ShellRequest {
+        command: invocation,
+        cwd: turn_context.cwd.clone(),
+        timeout_ms: None,
+        env: create_env(&turn_context.shell_environment_policy),
+        with_escalated_permissions: None,
+        justification: None,
+    };
+    let tool_ctx = ToolCtx {
+        session,
+        turn: turn_context,
+        call_id: call_id.clone(),
+        tool_name: "shell".to_string(),
+    };
+    let out = ToolOrchestrator::new()
+        .run(
+            &mut ShellRuntime::new(),
+            &req,
+            &tool_ctx,
+            turn_context,
+            turn_context.approval_policy,
+        )
+        .await;
+    let rendered = match &out {
+        Ok(output) => {
+            let text = format_exec_output_str(output);
+            let text = text.trim_end_matches('\n');
+            if output.exit_code == 0 {
+                text.to_string()
+            } else {
+                format!(
+                    "{text}\n[`{command}` exited with code {}]",
+                    output.exit_code
+                )
+            }
+        }
+        Err(ToolError::Rejected(reason)) => format!("[`{command}` was not run: {reason}]"),
+        Err(ToolError::Llmx(err)) => format!("[`{command}` failed: {}]", get_error_message_ui(err)),
+    };
+    // The model sees the command's output inline, so only the events matter.
+    let _ = emitter
+        .finish(
+            ToolEventCtx::new(session, turn_context, &call_id, None),
+            out,
+        )
+        .await;
+    rendered
 }
 
 #[cfg(test)]
@@ -183,6 +521,28 @@ mod tests {
         assert_eq!(names, vec!["foo"]);
     }
 
+    #[tokio::test]
+    async fn earlier_dirs_shadow_later_ones() {
+        let tmp = tempdir().expect("create TempDir");
+        let project = tmp.path().join("project");
+        let user = tmp.path().join("user");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&user).unwrap();
+        fs::write(project.join("review.md"), b"project review").unwrap();
+        fs::write(user.join("review.md"), b"user review").unwrap();
+        fs::write(user.join("fix.md"), b"user fix").unwrap();
+
+        let found = discover_prompts_in_dirs(&[project, user]).await;
+        let summary: Vec<(&str, &str)> = found
+            .iter()
+            .map(|prompt| (prompt.name.as_str(), prompt.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("fix", "user fix"), ("review", "project review")]
+        );
+    }
+
     #[tokio::test]
     async fn skips_non_utf8_files() {
         let tmp = tempdir().expect("create TempDir");
@@ -236,9 +596,112 @@ mod tests {
     #[test]
     fn parse_frontmatter_preserves_body_newlines() {
         let content = "---\r\ndescription: \"Line endings\"\r\nargument_hint: \"[arg]\"\r\n---\r\nFirst line\r\nSecond line\r\n";
-        let (desc, hint, body) = parse_frontmatter(content);
-        assert_eq!(desc.as_deref(), Some("Line endings"));
-        assert_eq!(hint.as_deref(), Some("[arg]"));
+        let (meta, body) = parse_frontmatter(content);
+        assert_eq!(meta.description.as_deref(), Some("Line endings"));
+        assert_eq!(meta.argument_hint.as_deref(), Some("[arg]"));
         assert_eq!(body, "First line\r\nSecond line\r\n");
     }
+
+    #[test]
+    fn parse_frontmatter_reads_arguments_and_overrides() {
+        let content = "---\ndescription: Triage\nmodel: gpt-5-mini\nreasoning_effort: high\nallowed_tools: [shell, \"read_file\"]\narguments:\n  TICKET: required description=\"Ticket id\"\n  - RETRIES: type=number default=3\n  MODE: choices=fast|thorough default=fast\n  lowercase: required\n---\nFix $TICKET\n";
+        let (meta, body) = parse_frontmatter(content);
+
+        assert_eq!(body, "Fix $TICKET\n");
+        assert_eq!(meta.model.as_deref(), Some("gpt-5-mini"));
+        assert_eq!(meta.reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(
+            meta.allowed_tools,
+            Some(vec!["shell".to_string(), "read_file".to_string()])
+        );
+        assert_eq!(
+            meta.arguments,
+            vec![
+                PromptArgument {
+                    name: "TICKET".to_string(),
+                    required: true,
+                    description: Some("Ticket id".to_string()),
+                    ..Default::default()
+                },
+                PromptArgument {
+                    name: "RETRIES".to_string(),
+                    kind: PromptArgumentType::Number,
+                    default: Some("3".to_string()),
+                    ..Default::default()
+                },
+                PromptArgument {
+                    name: "MODE".to_string(),
+                    kind: PromptArgumentType::Enum,
+                    default: Some("fast".to_string()),
+                    choices: vec!["fast".to_string(), "thorough".to_string()],
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_includes_and_command_blocks() {
+        let segments = prompt_segments("See @src/lib.rs, mail a@b.c and run !`git diff --stat`.");
+        assert_eq!(
+            segments,
+            vec![
+                PromptSegment::Text("See "),
+                PromptSegment::Include("src/lib.rs"),
+                PromptSegment::Text(", mail a@b.c and run "),
+                PromptSegment::Command("git diff --stat"),
+                PromptSegment::Text("."),
+            ]
+        );
+    }
+
+    #[test]
+    fn includes_must_stay_inside_root() {
+        let tmp = tempdir().expect("create TempDir");
+        let root = tmp.path().join("repo");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), b"fn main() {}").unwrap();
+        fs::write(tmp.path().join("secret.txt"), b"secret").unwrap();
+
+        assert_eq!(
+            resolve_include(&root, "src/lib.rs"),
+            Some(root.join("src/lib.rs").canonicalize().unwrap())
+        );
+        assert_eq!(resolve_include(&root, "../secret.txt"), None);
+        assert_eq!(resolve_include(&root, "src"), None);
+    }
+
+    #[test]
+    fn detects_directives() {
+        assert!(has_prompt_directives("Review @CONTRIBUTING.md"));
+        assert!(has_prompt_directives("Diff: !`git diff`"));
+        assert!(!has_prompt_directives("Fix $TICKET, mail a@b.c"));
+    }
+
+    #[tokio::test]
+    async fn arguments_are_substituted_after_directives() {
+        let tmp = tempdir().expect("create TempDir");
+        fs::write(tmp.path().join("notes.md"), b"notes").unwrap();
+        let (session, mut turn_context) = crate::llmx::make_session_and_context();
+        turn_context.cwd = tmp.path().to_path_buf();
+        let arguments = PromptArguments::Named {
+            values: std::collections::HashMap::from([(
+                "TOPIC".to_string(),
+                "@notes.md !`echo pwned`".to_string(),
+            )]),
+        };
+
+        let rendered = render_prompt_text(
+            "Read @notes.md about $TOPIC",
+            &arguments,
+            &session,
+            &turn_context,
+        )
+        .await;
+
+        assert_eq!(
+            rendered,
+            "Read `notes.md`:\n```\nnotes\n```\n about @notes.md !`echo pwned`"
+        );
+    }
 }
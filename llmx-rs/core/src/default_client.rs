@@ -113,6 +113,13 @@ impl LlmxRequestBuilder {
         self.map(|builder| builder.json(value))
     }
 
+    pub fn form<T>(self, value: &T) -> Self
+    where
+        T: ?Sized + Serialize,
+    {
+        self.map(|builder| builder.form(value))
+    }
+
     pub async fn send(self) -> Result<Response, reqwest::Error> {
         match self.builder.send().await {
             Ok(response) => {
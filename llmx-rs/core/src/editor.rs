@@ -0,0 +1,58 @@
+//! Launches the user's `$VISUAL` / `$EDITOR`, shared by `llmx config edit`
+//! and the TUI's external editor.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Returns the editor command line configured through `VISUAL` or `EDITOR`,
+/// split with shell quoting rules.
+pub fn editor_command() -> Option<Vec<String>> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|value| parse_editor_command(&value))
+}
+
+fn parse_editor_command(value: &str) -> Option<Vec<String>> {
+    shlex::split(value.trim()).filter(|argv| !argv.is_empty())
+}
+
+/// Runs the editor command `argv` on `path` and waits for it to exit.
+/// Fails if the editor cannot be started or exits unsuccessfully.
+pub fn run_editor(argv: &[String], path: &Path) -> io::Result<()> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "editor command is empty"))?;
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|err| io::Error::new(err.kind(), format!("failed to launch {program}: {err}")))?;
+    if !status.success() {
+        return Err(io::Error::other(format!("{program} exited with {status}")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_editor_command_with_arguments() {
+        assert_eq!(
+            parse_editor_command("code --wait"),
+            Some(vec!["code".to_string(), "--wait".to_string()])
+        );
+        assert_eq!(
+            parse_editor_command("'/opt/my editor/bin/edit' -n"),
+            Some(vec![
+                "/opt/my editor/bin/edit".to_string(),
+                "-n".to_string()
+            ])
+        );
+        assert_eq!(parse_editor_command("   "), None);
+    }
+}
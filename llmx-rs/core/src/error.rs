@@ -429,6 +429,17 @@ impl LlmxErr {
     pub fn downcast_ref<T: std::any::Any>(&self) -> Option<&T> {
         (self as &dyn std::any::Any).downcast_ref::<T>()
     }
+
+    /// Whether the error means a usage limit, quota or rate limit was hit.
+    pub fn is_limit_reached(&self) -> bool {
+        match self {
+            LlmxErr::UsageLimitReached(_) | LlmxErr::QuotaExceeded | LlmxErr::UsageNotIncluded => {
+                true
+            }
+            LlmxErr::RetryLimit(err) => err.status == StatusCode::TOO_MANY_REQUESTS,
+            _ => false,
+        }
+    }
 }
 
 pub fn get_error_message_ui(e: &LlmxErr) -> String {
@@ -9,6 +9,7 @@ use llmx_protocol::models::ReasoningItemContent;
 use llmx_protocol::models::ReasoningItemReasoningSummary;
 use llmx_protocol::models::ResponseItem;
 use llmx_protocol::models::WebSearchAction;
+use llmx_protocol::user_input::MENTION_OPEN_TAG;
 use llmx_protocol::user_input::UserInput;
 use tracing::warn;
 use uuid::Uuid;
@@ -35,6 +36,12 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                 if is_session_prefix(text) || is_user_shell_command_text(text) {
                     return None;
                 }
+                if text.starts_with(MENTION_OPEN_TAG)
+                    && let Some(mention) = UserInput::parse_mention_block(text)
+                {
+                    content.push(mention);
+                    continue;
+                }
                 content.push(UserInput::Text { text: text.clone() });
             }
             ContentItem::InputImage { image_url } => {
@@ -179,6 +186,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_mention_blocks_back_into_mentions() {
+        let item = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![
+                ContentItem::InputText {
+                    text: "what changed?".to_string(),
+                },
+                ContentItem::InputText {
+                    text: UserInput::mention_block("@git:HEAD~1", "diff --git a/x b/x"),
+                },
+            ],
+        };
+
+        let Some(TurnItem::UserMessage(user)) = parse_turn_item(&item) else {
+            panic!("expected user message turn item");
+        };
+        assert_eq!(
+            user.content,
+            vec![
+                UserInput::Text {
+                    text: "what changed?".to_string(),
+                },
+                UserInput::Mention {
+                    mention: "@git:HEAD~1".to_string(),
+                    text: "diff --git a/x b/x".to_string(),
+                },
+            ]
+        );
+        assert_eq!(user.message(), "what changed?");
+    }
+
     #[test]
     fn skips_user_instructions_and_env() {
         let items = vec![
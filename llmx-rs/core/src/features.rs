@@ -8,6 +8,9 @@
 use crate::config::ConfigToml;
 use crate::config::profile::ConfigProfile;
 use serde::Deserialize;
+use serde::Serialize;
+use serde::Serializer;
+use serde::ser::SerializeMap;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
@@ -212,6 +215,17 @@ impl Features {
     }
 }
 
+/// Serializes as a `[features]` table listing every known feature.
+impl Serialize for Features {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(FEATURES.len()))?;
+        for spec in FEATURES {
+            map.serialize_entry(spec.key, &self.enabled(spec.id))?;
+        }
+        map.end()
+    }
+}
+
 /// Keys accepted in `[features]` tables.
 fn feature_for_key(key: &str) -> Option<Feature> {
     for spec in FEATURES {
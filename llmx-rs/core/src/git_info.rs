@@ -595,6 +595,40 @@ pub async fn current_branch_name(cwd: &Path) -> Option<String> {
         .filter(|name| !name.is_empty())
 }
 
+/// Returns the merge base between `HEAD` and `branch`, preferring the
+/// branch's upstream when one is configured so local staleness does not
+/// widen the diff.
+pub async fn merge_base_with_head(cwd: &Path, branch: &str) -> Option<String> {
+    let upstream = format!("{branch}@{{upstream}}");
+    let base_ref = match run_git_command_with_timeout(
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            &upstream,
+        ],
+        cwd,
+    )
+    .await
+    {
+        Some(out) if out.status.success() => String::from_utf8(out.stdout)
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| branch.to_string()),
+        _ => branch.to_string(),
+    };
+
+    let out = run_git_command_with_timeout(&["merge-base", "HEAD", &base_ref], cwd).await?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8(out.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -885,6 +919,55 @@ mod tests {
         assert_eq!(git_info.branch, Some("feature-branch".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_merge_base_with_head_finds_fork_point() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo_path = create_test_git_repo(&temp_dir).await;
+        let fork_point = recent_commits(&repo_path, 1)
+            .await
+            .first()
+            .map(|entry| entry.sha.clone())
+            .expect("initial commit");
+        let base_branch = current_branch_name(&repo_path).await.expect("base branch");
+
+        Command::new("git")
+            .args(["checkout", "-b", "feature-branch"])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("Failed to create branch");
+        fs::write(repo_path.join("feature.txt"), "feature").expect("write file");
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("Failed to add files");
+        Command::new("git")
+            .args([
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "Feature commit",
+            ])
+            .current_dir(&repo_path)
+            .output()
+            .await
+            .expect("Failed to commit");
+
+        assert_eq!(
+            merge_base_with_head(&repo_path, &base_branch).await,
+            Some(fork_point)
+        );
+        assert_eq!(
+            merge_base_with_head(&repo_path, "no-such-branch").await,
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_get_git_working_tree_state_clean_repo() {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
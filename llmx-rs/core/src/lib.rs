@@ -6,9 +6,11 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
 mod apply_patch;
+pub mod approval_rules;
 pub mod auth;
 pub mod bash;
 mod chat_completions;
+mod checkpoints;
 mod client;
 mod client_common;
 pub mod llmx;
@@ -19,6 +21,7 @@ pub mod config;
 pub mod config_loader;
 mod context_manager;
 pub mod custom_prompts;
+pub mod editor;
 mod environment_context;
 pub mod error;
 pub mod exec;
@@ -34,6 +37,7 @@ mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
+mod plan_mode;
 mod response_processing;
 pub mod sandboxing;
 pub mod token_data;
@@ -42,12 +46,14 @@ mod unified_exec;
 mod user_instructions;
 pub use model_provider_info::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
+pub use model_provider_info::ProviderOAuth2Config;
 pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod conversation_manager;
 mod event_mapping;
 pub mod review_format;
+pub mod review_prompts;
 pub use conversation_manager::ConversationManager;
 pub use conversation_manager::NewConversation;
 pub use llmx_protocol::protocol::InitialHistory;
@@ -71,6 +77,11 @@ pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::export::ExportEntry;
+pub use rollout::export::ExportFormat;
+pub use rollout::export::ExportHeader;
+pub use rollout::export::SessionExport;
+pub use rollout::export::load_session_export;
 pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::list::ConversationItem;
 pub use rollout::list::ConversationsPage;
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Instant;
 
 use crate::AuthManager;
 use crate::client_common::REVIEW_PROMPT;
@@ -13,6 +14,7 @@ use crate::mcp::auth::McpAuthStatusEntry;
 use crate::mcp_connection_manager::DEFAULT_STARTUP_TIMEOUT;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::plan_mode;
 use crate::response_processing::process_items;
 use crate::terminal;
 use crate::user_notification::UserNotifier;
@@ -24,10 +26,12 @@ use futures::prelude::*;
 use futures::stream::FuturesOrdered;
 use llmx_protocol::ConversationId;
 use llmx_protocol::items::TurnItem;
+use llmx_protocol::plan_tool::UpdatePlanArgs;
 use llmx_protocol::protocol::FileChange;
 use llmx_protocol::protocol::HasLegacyEvent;
 use llmx_protocol::protocol::ItemCompletedEvent;
 use llmx_protocol::protocol::ItemStartedEvent;
+use llmx_protocol::protocol::PlanProposedEvent;
 use llmx_protocol::protocol::RawResponseItemEvent;
 use llmx_protocol::protocol::ReviewRequest;
 use llmx_protocol::protocol::RolloutItem;
@@ -53,13 +57,18 @@ use tracing::info;
 use tracing::warn;
 
 use crate::ModelProviderInfo;
+use crate::WireApi;
 use crate::client::ModelClient;
+use crate::client::load_stored_provider_key;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::config::Config;
+use crate::config::types::HistoryPruning;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
+use crate::context_manager::PromptOverhead;
+use crate::context_manager::TokenEstimator;
 use crate::environment_context::EnvironmentContext;
 use crate::error::LlmxErr;
 use crate::error::Result as LlmxResult;
@@ -67,6 +76,7 @@ use crate::error::Result as LlmxResult;
 use crate::exec::StreamOutput;
 // Removed: legacy executor wiring replaced by ToolOrchestrator flows.
 // legacy normalize_exec_result no longer used after orchestrator migration
+use crate::approval_rules::ApprovalRules;
 use crate::compact::build_compacted_history;
 use crate::compact::collect_user_messages;
 use crate::mcp::auth::compute_auth_statuses;
@@ -79,6 +89,7 @@ use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::ContextEstimate;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
@@ -97,6 +108,7 @@ use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::WarningEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::shell;
@@ -117,7 +129,9 @@ use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_instructions::DeveloperInstructions;
 use crate::user_instructions::UserInstructions;
+use crate::user_notification::ApprovalKind;
 use crate::user_notification::UserNotification;
+use crate::user_notification::duration_ms;
 use crate::util::backoff;
 use llmx_async_utils::OrCancelExt;
 use llmx_otel::otel_event_manager::OtelEventManager;
@@ -181,6 +195,8 @@ impl Llmx {
             original_config_do_not_use: Arc::clone(&config),
             features: config.features.clone(),
             session_source,
+            plan_mode: false,
+            approved_plan: None,
         };
 
         // Generate a unique ID for the lifetime of this LLMX session.
@@ -277,6 +293,8 @@ pub(crate) struct TurnContext {
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) llmx_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
+    /// Whether this turn runs in plan mode (read-only, no edits).
+    pub(crate) plan_mode: bool,
 }
 
 impl TurnContext {
@@ -338,6 +356,12 @@ pub(crate) struct SessionConfiguration {
     original_config_do_not_use: Arc<Config>,
     /// Source of the session (cli, vscode, exec, mcp, ...)
     session_source: SessionSource,
+
+    /// While set, turns override the approval and sandbox policies above with
+    /// read-only settings and end by proposing a plan.
+    plan_mode: bool,
+    /// Plan approved by the user, pinned into the developer instructions.
+    approved_plan: Option<String>,
 }
 
 impl SessionConfiguration {
@@ -361,6 +385,12 @@ impl SessionConfiguration {
         if let Some(cwd) = updates.cwd.clone() {
             next_configuration.cwd = cwd;
         }
+        if let Some(plan_mode) = updates.plan_mode {
+            next_configuration.plan_mode = plan_mode;
+        }
+        if let Some(approved_plan) = updates.approved_plan.clone() {
+            next_configuration.approved_plan = approved_plan;
+        }
         next_configuration
     }
 }
@@ -374,6 +404,8 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) reasoning_effort: Option<Option<ReasoningEffortConfig>>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
+    pub(crate) plan_mode: Option<bool>,
+    pub(crate) approved_plan: Option<Option<String>>,
 }
 
 impl Session {
@@ -381,6 +413,7 @@ impl Session {
         auth_manager: Option<Arc<AuthManager>>,
         otel_event_manager: &OtelEventManager,
         provider: ModelProviderInfo,
+        stored_provider_key: Option<String>,
         session_configuration: &SessionConfiguration,
         conversation_id: ConversationId,
         sub_id: String,
@@ -411,28 +444,53 @@ impl Session {
             session_configuration.model_reasoning_summary,
             conversation_id,
             session_configuration.session_source.clone(),
-        );
+        )
+        .with_stored_provider_key(stored_provider_key);
 
-        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_family: &model_family,
             features: &config.features,
         });
 
+        let mut developer_instructions: Vec<String> = session_configuration
+            .developer_instructions
+            .iter()
+            .cloned()
+            .collect();
+        if let Some(plan) = session_configuration.approved_plan.as_deref() {
+            developer_instructions.push(plan_mode::approved_plan_instructions(plan));
+        }
+        // Plan mode only overrides the turn; the session keeps the configured
+        // policies so leaving plan mode restores them.
+        let (approval_policy, sandbox_policy) = if session_configuration.plan_mode {
+            tools_config.apply_patch_tool_type = None;
+            developer_instructions.push(plan_mode::PLAN_MODE_INSTRUCTIONS.to_string());
+            (AskForApproval::Never, SandboxPolicy::new_read_only_policy())
+        } else {
+            (
+                session_configuration.approval_policy,
+                session_configuration.sandbox_policy.clone(),
+            )
+        };
+        let developer_instructions =
+            (!developer_instructions.is_empty()).then(|| developer_instructions.join("\n\n"));
+
         TurnContext {
             sub_id,
             client,
             cwd: session_configuration.cwd.clone(),
-            developer_instructions: session_configuration.developer_instructions.clone(),
+            developer_instructions,
             base_instructions: session_configuration.base_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
             user_instructions: session_configuration.user_instructions.clone(),
-            approval_policy: session_configuration.approval_policy,
-            sandbox_policy: session_configuration.sandbox_policy.clone(),
+            approval_policy,
+            sandbox_policy,
             shell_environment_policy: config.shell_environment_policy.clone(),
             tools_config,
             final_output_json_schema: None,
             llmx_linux_sandbox_exe: config.llmx_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
+            plan_mode: session_configuration.plan_mode,
         }
     }
 
@@ -487,13 +545,17 @@ impl Session {
         let mcp_fut = McpConnectionManager::new(
             config.mcp_servers.clone(),
             config.mcp_oauth_credentials_store_mode,
+            config.credential_helper.as_deref(),
         );
         let default_shell_fut = shell::default_user_shell();
         let history_meta_fut = crate::message_history::history_metadata(&config);
         let auth_statuses_fut = compute_auth_statuses(
             config.mcp_servers.iter(),
             config.mcp_oauth_credentials_store_mode,
+            config.credential_helper.as_deref(),
         );
+        let stored_provider_key_fut =
+            load_stored_provider_key(&config, &session_configuration.provider);
 
         // Join all independent futures.
         let (
@@ -502,12 +564,14 @@ impl Session {
             default_shell,
             (history_log_id, history_entry_count),
             auth_statuses,
+            stored_provider_key,
         ) = tokio::join!(
             rollout_fut,
             mcp_fut,
             default_shell_fut,
             history_meta_fut,
-            auth_statuses_fut
+            auth_statuses_fut,
+            stored_provider_key_fut
         );
 
         let rollout_recorder = rollout_recorder.map_err(|e| {
@@ -591,13 +655,22 @@ impl Session {
         let services = SessionServices {
             mcp_connection_manager,
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(config.notify.clone()),
+            notifier: UserNotifier::new(
+                config.notify.clone(),
+                config.notification_events.clone(),
+                config.notification_min_duration,
+            ),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: default_shell,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager,
-            tool_approvals: Mutex::new(ApprovalStore::default()),
+            tool_approvals: Mutex::new(ApprovalStore::new(ApprovalRules::load(
+                &config.llmx_home,
+                &config.cwd,
+                config.active_project.is_trusted(),
+            ))),
+            stored_provider_key,
         };
 
         let sess = Arc::new(Session {
@@ -718,6 +791,7 @@ impl Session {
             Some(Arc::clone(&self.services.auth_manager)),
             &self.services.otel_event_manager,
             session_configuration.provider.clone(),
+            self.services.stored_provider_key.clone(),
             &session_configuration,
             self.conversation_id,
             sub_id,
@@ -728,6 +802,27 @@ impl Session {
         Arc::new(turn_context)
     }
 
+    /// Like [`Session::new_turn_with_sub_id`], but `updates` apply to the
+    /// returned turn only and are not persisted into the session
+    /// configuration.
+    pub(crate) async fn new_transient_turn_with_sub_id(
+        &self,
+        sub_id: String,
+        updates: &SessionSettingsUpdate,
+    ) -> TurnContext {
+        let session_configuration = self.state.lock().await.session_configuration.apply(updates);
+
+        Self::make_turn_context(
+            Some(Arc::clone(&self.services.auth_manager)),
+            &self.services.otel_event_manager,
+            session_configuration.provider.clone(),
+            self.services.stored_provider_key.clone(),
+            &session_configuration,
+            self.conversation_id,
+            sub_id,
+        )
+    }
+
     fn build_environment_update_item(
         &self,
         previous: Option<&Arc<TurnContext>>,
@@ -814,6 +909,7 @@ impl Session {
             provider,
             auth_manager,
             &otel,
+            self.services.stored_provider_key.clone(),
             self.conversation_id,
             turn_context.client.get_session_source(),
             call_id,
@@ -858,6 +954,18 @@ impl Session {
         }
 
         let parsed_cmd = parse_command(&command);
+        let notification = UserNotification::ApprovalRequested {
+            thread_id: self.conversation_id.to_string(),
+            turn_id: turn_context.sub_id.clone(),
+            cwd: cwd.display().to_string(),
+            kind: ApprovalKind::Exec,
+            command: Some(
+                shlex::try_join(command.iter().map(String::as_str))
+                    .unwrap_or_else(|_| command.join(" ")),
+            ),
+            files: Vec::new(),
+            reason: reason.clone(),
+        };
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             command,
@@ -867,6 +975,7 @@ impl Session {
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
+        self.notifier().notify(&notification);
         rx_approve.await.unwrap_or_default()
     }
 
@@ -896,6 +1005,20 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let mut files: Vec<String> = changes
+            .keys()
+            .map(|path| path.display().to_string())
+            .collect();
+        files.sort();
+        let notification = UserNotification::ApprovalRequested {
+            thread_id: self.conversation_id.to_string(),
+            turn_id: turn_context.sub_id.clone(),
+            cwd: turn_context.cwd.display().to_string(),
+            kind: ApprovalKind::Patch,
+            command: None,
+            files,
+            reason: reason.clone(),
+        };
         let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             changes,
@@ -903,6 +1026,7 @@ impl Session {
             grant_root,
         });
         self.send_event(turn_context, event).await;
+        self.notifier().notify(&notification);
         rx_approve
     }
 
@@ -996,6 +1120,38 @@ impl Session {
         }
     }
 
+    /// Remembers the latest `update_plan` call of a plan-mode task.
+    pub(crate) async fn record_proposed_plan(&self, plan: UpdatePlanArgs) {
+        let mut state = self.state.lock().await;
+        state.proposed_plan = Some(plan);
+    }
+
+    /// Ends a plan-mode task: keeps the rendered plan for
+    /// [`Op::ResolvePlan`] and asks the client to approve it.
+    async fn propose_plan(&self, turn_context: &TurnContext, message: Option<String>) {
+        let (plan, text) = {
+            let mut state = self.state.lock().await;
+            let plan = state.proposed_plan.take();
+            let text = plan_mode::format_plan(plan.as_ref(), message.as_deref());
+            state.pending_plan = Some(text.clone());
+            (plan, text)
+        };
+        self.send_event(
+            turn_context,
+            EventMsg::PlanProposed(PlanProposedEvent {
+                plan,
+                message,
+                text,
+            }),
+        )
+        .await;
+    }
+
+    async fn take_pending_plan(&self) -> Option<String> {
+        let mut state = self.state.lock().await;
+        state.pending_plan.take()
+    }
+
     pub(crate) fn build_initial_context(&self, turn_context: &TurnContext) -> Vec<ResponseItem> {
         let mut items = Vec::<ResponseItem>::with_capacity(3);
         if let Some(developer_instructions) = turn_context.developer_instructions.as_deref() {
@@ -1066,14 +1222,87 @@ impl Session {
     }
 
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
-        let (info, rate_limits) = {
+        let (info, rate_limits, context_estimate) = {
             let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+            let (info, rate_limits) = state.token_info_and_rate_limits();
+            (info, rate_limits, state.latest_context_estimate.clone())
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            context_estimate,
+        });
         self.send_event(turn_context, event).await;
     }
 
+    /// Counts the instructions and tool definitions of `turn_context`'s
+    /// prompts. The tool set is fixed for a turn, so this runs once per turn.
+    pub(crate) fn prompt_overhead(&self, turn_context: &TurnContext) -> PromptOverhead {
+        let mcp_tools = self.services.mcp_connection_manager.list_all_tools();
+        let router = ToolRouter::from_config(&turn_context.tools_config, Some(mcp_tools));
+        let prompt = Prompt {
+            tools: router.specs(),
+            base_instructions_override: turn_context.base_instructions.clone(),
+            ..Default::default()
+        };
+        let model_family = turn_context.client.get_model_family();
+        let instructions = prompt.get_full_instructions(&model_family);
+        let tools_json = serde_json::to_string(&prompt.tools).unwrap_or_default();
+
+        TokenEstimator::for_model(&turn_context.client.get_model())
+            .prompt_overhead(&instructions, &tools_json)
+    }
+
+    /// Estimates the size of the next prompt with the local tokenizer. The
+    /// estimate is remembered so the next `TokenCount` event can report it
+    /// next to the provider's actual usage.
+    pub(crate) async fn estimate_context(
+        &self,
+        turn_context: &TurnContext,
+        overhead: PromptOverhead,
+        input: &[ResponseItem],
+    ) -> ContextEstimate {
+        let estimator = TokenEstimator::for_model(&turn_context.client.get_model());
+        let mut estimate = estimator.estimate_prompt(overhead, input);
+        let mut state = self.state.lock().await;
+        estimate.reclaimed_tokens = state.history.reclaimed_tokens();
+        state.set_context_estimate(estimate.clone());
+        estimate
+    }
+
+    pub(crate) async fn proactive_compaction_suspended(&self) -> bool {
+        self.state.lock().await.proactive_compaction_suspended
+    }
+
+    /// Suspends or resumes proactive compaction and returns the previous
+    /// setting.
+    pub(crate) async fn set_proactive_compaction_suspended(&self, suspended: bool) -> bool {
+        let mut state = self.state.lock().await;
+        std::mem::replace(&mut state.proactive_compaction_suspended, suspended)
+    }
+
+    /// Frees roughly `tokens_to_reclaim` tokens without summarizing. The
+    /// `selective` strategy only elides stale tool outputs and leaves the rest
+    /// to compaction; otherwise older outputs are trimmed. Returns the number
+    /// of tokens reclaimed.
+    pub(crate) async fn reclaim_context(
+        &self,
+        turn_context: &TurnContext,
+        tokens_to_reclaim: i64,
+    ) -> i64 {
+        let estimator = TokenEstimator::for_model(&turn_context.client.get_model());
+        let strategy = turn_context.client.config().history_pruning;
+        let mut state = self.state.lock().await;
+        match strategy {
+            HistoryPruning::Selective => state
+                .history
+                .prune_stale_tool_outputs(&estimator, tokens_to_reclaim),
+            HistoryPruning::Summarize => state
+                .history
+                .trim_tool_outputs(&estimator, tokens_to_reclaim),
+        }
+    }
+
     pub(crate) async fn set_total_tokens_full(&self, turn_context: &TurnContext) {
         let context_window = turn_context.client.get_model_context_window();
         if let Some(context_window) = context_window {
@@ -1163,6 +1392,10 @@ impl Session {
             .await;
     }
 
+    pub(crate) async fn has_active_turn(&self) -> bool {
+        self.active_turn.lock().await.is_some()
+    }
+
     /// Returns the input if there was no task running to inject into
     pub async fn inject_input(&self, input: Vec<UserInput>) -> Result<(), Vec<UserInput>> {
         let mut active = self.active_turn.lock().await;
@@ -1291,6 +1524,21 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
             }
+            Op::CustomPromptInput {
+                prompt,
+                arguments,
+                items,
+            } => {
+                handlers::custom_prompt_input(
+                    &sess,
+                    sub.id.clone(),
+                    prompt,
+                    arguments,
+                    items,
+                    &mut previous_context,
+                )
+                .await;
+            }
             Op::ExecApproval { id, decision } => {
                 handlers::exec_approval(&sess, id, decision).await;
             }
@@ -1308,11 +1556,26 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
             Op::ListCustomPrompts => {
-                handlers::list_custom_prompts(&sess, sub.id.clone()).await;
+                handlers::list_custom_prompts(&sess, &config, sub.id.clone()).await;
             }
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::ListCheckpoints => {
+                handlers::list_checkpoints(&sess, sub.id.clone()).await;
+            }
+            Op::DiffCheckpoints { from, to } => {
+                handlers::diff_checkpoints(&sess, sub.id.clone(), from, to).await;
+            }
+            Op::RestoreCheckpoint { id, paths } => {
+                handlers::restore_checkpoint(&sess, sub.id.clone(), id, paths).await;
+            }
+            Op::ListApprovalRules => {
+                handlers::list_approval_rules(&sess, sub.id.clone()).await;
+            }
+            Op::RevokeApprovalRule { rule } => {
+                handlers::revoke_approval_rule(&sess, sub.id.clone(), rule).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -1333,6 +1596,13 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Review { review_request } => {
                 handlers::review(&sess, &config, sub.id.clone(), review_request).await;
             }
+            Op::SetPlanMode { enabled } => {
+                handlers::set_plan_mode(&sess, enabled).await;
+            }
+            Op::ResolvePlan { decision } => {
+                handlers::resolve_plan(&sess, sub.id.clone(), decision, &mut previous_context)
+                    .await;
+            }
             _ => {} // Ignore unknown ops; enum is non_exhaustive to allow extensions.
         }
     }
@@ -1345,23 +1615,43 @@ mod handlers {
     use crate::llmx::SessionSettingsUpdate;
     use crate::llmx::TurnContext;
 
+    use crate::checkpoints::collect_checkpoints;
+    use crate::checkpoints::describe_checkpoints;
+    use crate::checkpoints::find_checkpoint;
+    use crate::checkpoints::snapshot_worktree;
     use crate::config::Config;
+    use crate::custom_prompts::has_prompt_directives;
     use crate::llmx::spawn_review_thread;
     use crate::mcp::auth::compute_auth_statuses;
+    use crate::plan_mode;
     use crate::tasks::CompactTask;
+    use crate::tasks::CustomPromptTask;
     use crate::tasks::RegularTask;
+    use crate::tasks::RestoreCheckpointTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::user_instructions::DeveloperInstructions;
+    use llmx_git::diff_commits;
     use llmx_protocol::custom_prompts::CustomPrompt;
+    use llmx_protocol::custom_prompts::PromptArguments;
+    use llmx_protocol::protocol::ApprovalRule;
+    use llmx_protocol::protocol::ApprovalRuleAction;
+    use llmx_protocol::protocol::ApprovalRuleKind;
+    use llmx_protocol::protocol::CheckpointDiffResponseEvent;
     use llmx_protocol::protocol::ErrorEvent;
     use llmx_protocol::protocol::Event;
     use llmx_protocol::protocol::EventMsg;
+    use llmx_protocol::protocol::ListApprovalRulesResponseEvent;
+    use llmx_protocol::protocol::ListCheckpointsResponseEvent;
     use llmx_protocol::protocol::ListCustomPromptsResponseEvent;
     use llmx_protocol::protocol::Op;
+    use llmx_protocol::protocol::PlanDecision;
     use llmx_protocol::protocol::ReviewDecision;
     use llmx_protocol::protocol::ReviewRequest;
     use llmx_protocol::protocol::TurnAbortReason;
+    use llmx_protocol::protocol::WarningEvent;
     use llmx_protocol::user_input::UserInput;
+    use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
     use tracing::warn;
@@ -1400,6 +1690,7 @@ mod handlers {
                     reasoning_effort: Some(effort),
                     reasoning_summary: Some(summary),
                     final_output_json_schema: Some(final_output_json_schema),
+                    ..Default::default()
                 },
             ),
             Op::UserInput { items } => (items, SessionSettingsUpdate::default()),
@@ -1407,6 +1698,82 @@ mod handlers {
         };
 
         let current_context = sess.new_turn_with_sub_id(sub_id, updates).await;
+        start_user_turn(sess, current_context, items, previous_context).await;
+    }
+
+    /// Starts a turn for a custom prompt invocation. A plain prompt behaves
+    /// like user input. A prompt with overrides or directives gets a turn of
+    /// its own: the overrides apply to that turn only, and the body is
+    /// expanded inside it so command blocks can ask for approval. Such a
+    /// prompt cannot join a running turn, so it is rejected instead.
+    pub async fn custom_prompt_input(
+        sess: &Arc<Session>,
+        sub_id: String,
+        prompt: CustomPrompt,
+        arguments: PromptArguments,
+        items: Vec<UserInput>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        if !prompt.has_turn_overrides() && !has_prompt_directives(&prompt.content) {
+            let mut input = vec![UserInput::Text {
+                text: arguments.substitute(&prompt.content),
+            }];
+            input.extend(items);
+            let current_context = sess
+                .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+                .await;
+            start_user_turn(sess, current_context, input, previous_context).await;
+            return;
+        }
+
+        if sess.has_active_turn().await {
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: format!(
+                        "/prompts:{} sets the model, reasoning effort, or tools, or runs commands, so it cannot be added to the running turn. Wait for the turn to finish or interrupt it, then try again.",
+                        prompt.name
+                    ),
+                }),
+            };
+            sess.send_event_raw(event).await;
+            return;
+        }
+
+        let updates = SessionSettingsUpdate {
+            model: prompt.model.clone(),
+            reasoning_effort: prompt.reasoning_effort.map(Some),
+            ..Default::default()
+        };
+        let mut turn_context = sess.new_transient_turn_with_sub_id(sub_id, &updates).await;
+        turn_context.tools_config.allowed_tools = prompt.allowed_tools;
+        let current_context = Arc::new(turn_context);
+        current_context
+            .client
+            .get_otel_event_manager()
+            .user_prompt(&items);
+
+        if let Some(env_item) =
+            sess.build_environment_update_item(previous_context.as_ref(), &current_context)
+        {
+            sess.record_conversation_items(&current_context, std::slice::from_ref(&env_item))
+                .await;
+        }
+        sess.spawn_task(
+            Arc::clone(&current_context),
+            items,
+            CustomPromptTask::new(prompt.content, arguments),
+        )
+        .await;
+        *previous_context = Some(current_context);
+    }
+
+    async fn start_user_turn(
+        sess: &Arc<Session>,
+        current_context: Arc<TurnContext>,
+        items: Vec<UserInput>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
         current_context
             .client
             .get_otel_event_manager()
@@ -1446,6 +1813,7 @@ mod handlers {
     }
 
     pub async fn exec_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        save_approval_rule(sess, &id, ApprovalRuleKind::Command, &decision).await;
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -1455,6 +1823,7 @@ mod handlers {
     }
 
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        save_approval_rule(sess, &id, ApprovalRuleKind::Patch, &decision).await;
         match decision {
             ReviewDecision::Abort => {
                 sess.interrupt_task().await;
@@ -1463,6 +1832,77 @@ mod handlers {
         }
     }
 
+    /// Persists the rule behind an "always" decision.
+    async fn save_approval_rule(
+        sess: &Arc<Session>,
+        sub_id: &str,
+        kind: ApprovalRuleKind,
+        decision: &ReviewDecision,
+    ) {
+        let (action, pattern) = match decision {
+            ReviewDecision::ApprovedAlways { pattern } => (ApprovalRuleAction::Allow, pattern),
+            ReviewDecision::DeniedAlways { pattern } => (ApprovalRuleAction::Deny, pattern),
+            _ => return,
+        };
+        let rule = ApprovalRule {
+            kind,
+            action,
+            pattern: pattern.clone(),
+        };
+        let result = sess
+            .services
+            .tool_approvals
+            .lock()
+            .await
+            .rules_mut()
+            .add(rule);
+        if let Err(err) = result {
+            let event = Event {
+                id: sub_id.to_string(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("Failed to save approval rule `{pattern}`: {err}"),
+                }),
+            };
+            sess.send_event_raw(event).await;
+        }
+    }
+
+    pub async fn list_approval_rules(sess: &Arc<Session>, sub_id: String) {
+        let rules = sess
+            .services
+            .tool_approvals
+            .lock()
+            .await
+            .rules()
+            .entries()
+            .to_vec();
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListApprovalRulesResponse(ListApprovalRulesResponseEvent { rules }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn revoke_approval_rule(sess: &Arc<Session>, sub_id: String, rule: ApprovalRule) {
+        let result = sess
+            .services
+            .tool_approvals
+            .lock()
+            .await
+            .rules_mut()
+            .revoke(&rule);
+        if let Err(err) = result {
+            let event = Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Error(ErrorEvent {
+                    message: format!("Failed to revoke approval rule `{}`: {err}", rule.pattern),
+                }),
+            };
+            sess.send_event_raw(event).await;
+        }
+        list_approval_rules(sess, sub_id).await;
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -1516,6 +1956,7 @@ mod handlers {
         let auth_statuses_fut = compute_auth_statuses(
             config.mcp_servers.iter(),
             config.mcp_oauth_credentials_store_mode,
+            config.credential_helper.as_deref(),
         );
         let resources_fut = sess.services.mcp_connection_manager.list_all_resources();
         let templates_fut = sess
@@ -1540,13 +1981,12 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
-    pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
+    pub async fn list_custom_prompts(sess: &Session, config: &Arc<Config>, sub_id: String) {
+        // Project prompts shadow same-named prompts in `$LLMX_HOME/prompts`.
+        let mut dirs = config.project_prompt_dirs.clone();
+        dirs.extend(crate::custom_prompts::default_prompts_dir());
         let custom_prompts: Vec<CustomPrompt> =
-            if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                crate::custom_prompts::discover_prompts_in(&dir).await
-            } else {
-                Vec::new()
-            };
+            crate::custom_prompts::discover_prompts_in_dirs(&dirs).await;
 
         let event = Event {
             id: sub_id,
@@ -1565,6 +2005,92 @@ mod handlers {
             .await;
     }
 
+    pub async fn list_checkpoints(sess: &Arc<Session>, sub_id: String) {
+        let cwd = sess.state.lock().await.session_configuration.cwd.clone();
+        let entries = collect_checkpoints(&sess.clone_history().await.get_history());
+        let result =
+            tokio::task::spawn_blocking(move || describe_checkpoints(&cwd, &entries)).await;
+        let msg = match result {
+            Ok(Ok(checkpoints)) => {
+                EventMsg::ListCheckpointsResponse(ListCheckpointsResponseEvent { checkpoints })
+            }
+            Ok(Err(err)) => EventMsg::Error(ErrorEvent {
+                message: format!("Failed to list checkpoints: {err}"),
+            }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("Failed to list checkpoints: {err}"),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn diff_checkpoints(
+        sess: &Arc<Session>,
+        sub_id: String,
+        from: String,
+        to: Option<String>,
+    ) {
+        let cwd = sess.state.lock().await.session_configuration.cwd.clone();
+        let entries = collect_checkpoints(&sess.clone_history().await.get_history());
+        // Only ids of known snapshots reach git, so arbitrary revisions or
+        // options cannot be smuggled in through the request.
+        let resolve = |id: &str| {
+            find_checkpoint(&entries, id)
+                .map(|entry| entry.ghost_commit.id().to_string())
+                .ok_or_else(|| format!("No checkpoint matches {id}."))
+        };
+        let resolved = resolve(&from).and_then(|from_id| {
+            let to_id = to.as_deref().map(resolve).transpose()?;
+            Ok((from_id, to_id))
+        });
+        let msg = match resolved {
+            Ok((from_id, to_id)) => {
+                let result = tokio::task::spawn_blocking(move || {
+                    let to_commit = match to_id {
+                        Some(id) => id,
+                        None => snapshot_worktree(&cwd)?.id().to_string(),
+                    };
+                    diff_commits(&cwd, &from_id, &to_commit)
+                })
+                .await;
+                match result {
+                    Ok(Ok(unified_diff)) => {
+                        EventMsg::CheckpointDiffResponse(CheckpointDiffResponseEvent {
+                            from,
+                            to,
+                            unified_diff,
+                        })
+                    }
+                    Ok(Err(err)) => EventMsg::Error(ErrorEvent {
+                        message: format!("Failed to diff checkpoints: {err}"),
+                    }),
+                    Err(err) => EventMsg::Error(ErrorEvent {
+                        message: format!("Failed to diff checkpoints: {err}"),
+                    }),
+                }
+            }
+            Err(message) => EventMsg::Error(ErrorEvent { message }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn restore_checkpoint(
+        sess: &Arc<Session>,
+        sub_id: String,
+        id: String,
+        paths: Vec<PathBuf>,
+    ) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            RestoreCheckpointTask::new(id, paths),
+        )
+        .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess
             .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
@@ -1621,6 +2147,8 @@ mod handlers {
         let turn_context = sess
             .new_turn_with_sub_id(sub_id.clone(), SessionSettingsUpdate::default())
             .await;
+        let review_request =
+            crate::review_prompts::resolve_review_request(review_request, &turn_context.cwd).await;
         spawn_review_thread(
             Arc::clone(sess),
             Arc::clone(config),
@@ -1630,6 +2158,72 @@ mod handlers {
         )
         .await;
     }
+
+    pub async fn set_plan_mode(sess: &Session, enabled: bool) {
+        // A new planning round replaces whatever plan was approved before.
+        sess.update_settings(SessionSettingsUpdate {
+            plan_mode: Some(enabled),
+            approved_plan: enabled.then_some(None),
+            ..Default::default()
+        })
+        .await;
+    }
+
+    pub async fn resolve_plan(
+        sess: &Arc<Session>,
+        sub_id: String,
+        decision: PlanDecision,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let pending = sess.take_pending_plan().await;
+        let plan = match decision {
+            PlanDecision::Approved => pending,
+            PlanDecision::ApprovedWithEdits { plan } => Some(plan),
+            // Stay in plan mode so the user can refine the request.
+            PlanDecision::Rejected => return,
+        };
+        let Some(plan) = plan.filter(|plan| !plan.trim().is_empty()) else {
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "There is no proposed plan to approve.".to_string(),
+                }),
+            };
+            sess.send_event_raw(event).await;
+            return;
+        };
+
+        let turn_context = sess
+            .new_turn_with_sub_id(
+                sub_id,
+                SessionSettingsUpdate {
+                    plan_mode: Some(false),
+                    approved_plan: Some(Some(plan.clone())),
+                    ..Default::default()
+                },
+            )
+            .await;
+        // The approved plan is also part of the developer instructions, so it
+        // survives compaction; record it here for the current history.
+        let mut items: Vec<_> =
+            vec![DeveloperInstructions::new(plan_mode::approved_plan_instructions(&plan)).into()];
+        if let Some(env_item) =
+            sess.build_environment_update_item(previous_context.as_ref(), &turn_context)
+        {
+            items.push(env_item);
+        }
+        sess.record_conversation_items(&turn_context, &items).await;
+
+        sess.spawn_task(
+            Arc::clone(&turn_context),
+            vec![UserInput::Text {
+                text: plan_mode::EXECUTE_PLAN_PROMPT.to_string(),
+            }],
+            RegularTask,
+        )
+        .await;
+        *previous_context = Some(turn_context);
+    }
 }
 
 /// Spawn a review thread using the given prompt.
@@ -1687,7 +2281,8 @@ async fn spawn_review_thread(
         per_turn_config.model_reasoning_summary,
         sess.conversation_id,
         parent_turn_context.client.get_session_source(),
-    );
+    )
+    .with_stored_provider_key(sess.services.stored_provider_key.clone());
 
     let review_turn_context = TurnContext {
         sub_id: sub_id.to_string(),
@@ -1704,6 +2299,7 @@ async fn spawn_review_thread(
         final_output_json_schema: None,
         llmx_linux_sandbox_exe: parent_turn_context.llmx_linux_sandbox_exe.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
+        plan_mode: false,
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -1745,6 +2341,7 @@ pub(crate) async fn run_task(
         model_context_window: turn_context.client.get_model_context_window(),
     });
     sess.send_event(&turn_context, event).await;
+    let started_at = Instant::now();
 
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
     sess.record_input_and_rollout_usermsg(turn_context.as_ref(), &initial_input_for_turn)
@@ -1757,6 +2354,7 @@ pub(crate) async fn run_task(
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
     let mut auto_compact_recently_attempted = false;
+    let prompt_overhead = sess.prompt_overhead(&turn_context);
 
     loop {
         // Note that pending_input would be something like a message the user
@@ -1776,6 +2374,44 @@ pub(crate) async fn run_task(
             sess.clone_history().await.get_history_for_prompt()
         };
 
+        // Providers only report usage after the fact, and many Chat backends
+        // never report it while streaming, so estimate the prompt up front.
+        // The Responses API reports usage on every completion, which keeps the
+        // post-turn check below authoritative there.
+        let estimate = sess
+            .estimate_context(&turn_context, prompt_overhead, &turn_input)
+            .await;
+        if turn_context.client.get_provider().wire_api == WireApi::Chat
+            && let Some(limit) = turn_context.client.get_auto_compact_token_limit()
+        {
+            let excess = estimate.total_tokens() - limit;
+            if excess <= 0 {
+                sess.set_proactive_compaction_suspended(false).await;
+            } else {
+                let reclaimed = sess.reclaim_context(&turn_context, excess).await;
+                if reclaimed >= excess {
+                    continue;
+                }
+                if auto_compact_recently_attempted {
+                    // The summary itself does not fit; compacting again every
+                    // turn would only summarize the summary.
+                    if !sess.set_proactive_compaction_suspended(true).await {
+                        let event = EventMsg::Warning(WarningEvent {
+                            message: format!(
+                                "Automatic compaction left the conversation above the token limit (limit {limit}, estimated {}). It will not run again until the conversation fits; use /compact or start a new session.",
+                                estimate.total_tokens()
+                            ),
+                        });
+                        sess.send_event(&turn_context, event).await;
+                    }
+                } else if !sess.proactive_compaction_suspended().await {
+                    auto_compact_recently_attempted = true;
+                    compact::run_inline_auto_compact_task(sess.clone(), turn_context.clone()).await;
+                    continue;
+                }
+            }
+        }
+
         let turn_input_messages = turn_input
             .iter()
             .filter_map(|item| match parse_turn_item(item) {
@@ -1811,6 +2447,20 @@ pub(crate) async fn run_task(
                 let (responses, items_to_record_in_conversation_history) =
                     process_items(processed_items, &sess, &turn_context).await;
 
+                // Try to get back under the limit by pruning stale tool outputs
+                // before summarizing the whole conversation.
+                let token_limit_reached = match total_usage_tokens {
+                    Some(tokens)
+                        if token_limit_reached
+                            && turn_context.client.config().history_pruning
+                                == HistoryPruning::Selective =>
+                    {
+                        let excess = tokens - limit;
+                        sess.reclaim_context(&turn_context, excess).await < excess
+                    }
+                    _ => token_limit_reached,
+                };
+
                 if token_limit_reached {
                     if auto_compact_recently_attempted {
                         let limit_str = limit.to_string();
@@ -1843,6 +2493,7 @@ pub(crate) async fn run_task(
                             cwd: turn_context.cwd.display().to_string(),
                             input_messages: turn_input_messages,
                             last_assistant_message: last_agent_message.clone(),
+                            duration_ms: duration_ms(started_at.elapsed()),
                         });
                     break;
                 }
@@ -1861,12 +2512,38 @@ pub(crate) async fn run_task(
                     message: e.to_string(),
                 });
                 sess.send_event(&turn_context, event).await;
+                if !cancellation_token.is_cancelled() {
+                    let thread_id = sess.conversation_id.to_string();
+                    let turn_id = turn_context.sub_id.clone();
+                    let cwd = turn_context.cwd.display().to_string();
+                    let error = e.to_string();
+                    sess.notifier().notify(&if e.is_limit_reached() {
+                        UserNotification::RateLimitReached {
+                            thread_id,
+                            turn_id,
+                            cwd,
+                            error,
+                        }
+                    } else {
+                        UserNotification::TurnFailed {
+                            thread_id,
+                            turn_id,
+                            cwd,
+                            error,
+                        }
+                    });
+                }
                 // let the user continue the conversation
                 break;
             }
         }
     }
 
+    if turn_context.plan_mode && !cancellation_token.is_cancelled() {
+        sess.propose_plan(&turn_context, last_agent_message.clone())
+            .await;
+    }
+
     last_agent_message
 }
 
@@ -2342,6 +3019,47 @@ mod tests {
     use std::sync::Arc;
     use std::time::Duration as StdDuration;
 
+    #[tokio::test]
+    async fn plan_mode_turns_are_read_only_until_plan_is_approved() {
+        let (session, turn_context) = make_session_and_context();
+
+        let plan_turn = session
+            .new_turn(SessionSettingsUpdate {
+                plan_mode: Some(true),
+                ..Default::default()
+            })
+            .await;
+        assert!(plan_turn.plan_mode);
+        assert_eq!(plan_turn.approval_policy, AskForApproval::Never);
+        assert_eq!(
+            plan_turn.sandbox_policy,
+            SandboxPolicy::new_read_only_policy()
+        );
+        assert!(plan_turn.tools_config.apply_patch_tool_type.is_none());
+        assert!(
+            plan_turn
+                .developer_instructions
+                .as_deref()
+                .is_some_and(|text| text.contains(plan_mode::PLAN_MODE_INSTRUCTIONS))
+        );
+
+        let approved_turn = session
+            .new_turn(SessionSettingsUpdate {
+                plan_mode: Some(false),
+                approved_plan: Some(Some("1. Fix the parser".to_string())),
+                ..Default::default()
+            })
+            .await;
+        assert!(!approved_turn.plan_mode);
+        assert_eq!(approved_turn.approval_policy, turn_context.approval_policy);
+        assert_eq!(approved_turn.sandbox_policy, turn_context.sandbox_policy);
+        assert!(
+            approved_turn.developer_instructions.as_deref().is_some_and(
+                |text| text.contains("<approved_plan>\n1. Fix the parser\n</approved_plan>")
+            )
+        );
+    }
+
     #[test]
     fn reconstruct_history_matches_live_compactions() {
         let (session, turn_context) = make_session_and_context();
@@ -2521,6 +3239,7 @@ mod tests {
             config.cwd.clone(),
             false,
             config.cli_auth_credentials_store_mode,
+            config.credential_helper.clone(),
         );
 
         let session_configuration = SessionConfiguration {
@@ -2538,6 +3257,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             features: Features::default(),
             session_source: SessionSource::Exec,
+            plan_mode: false,
+            approved_plan: None,
         };
 
         let state = SessionState::new(session_configuration.clone());
@@ -2545,19 +3266,21 @@ mod tests {
         let services = SessionServices {
             mcp_connection_manager: McpConnectionManager::default(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::default(),
             rollout: Mutex::new(None),
             user_shell: shell::Shell::Unknown,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            stored_provider_key: None,
         };
 
         let turn_context = Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
             &otel_event_manager,
             session_configuration.provider.clone(),
+            None,
             &session_configuration,
             conversation_id,
             "turn_id".to_string(),
@@ -2597,6 +3320,7 @@ mod tests {
             config.cwd.clone(),
             false,
             config.cli_auth_credentials_store_mode,
+            config.credential_helper.clone(),
         );
 
         let session_configuration = SessionConfiguration {
@@ -2614,6 +3338,8 @@ mod tests {
             original_config_do_not_use: Arc::clone(&config),
             features: Features::default(),
             session_source: SessionSource::Exec,
+            plan_mode: false,
+            approved_plan: None,
         };
 
         let state = SessionState::new(session_configuration.clone());
@@ -2621,19 +3347,21 @@ mod tests {
         let services = SessionServices {
             mcp_connection_manager: McpConnectionManager::default(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::default(),
             rollout: Mutex::new(None),
             user_shell: shell::Shell::Unknown,
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
+            stored_provider_key: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
             Some(Arc::clone(&auth_manager)),
             &otel_event_manager,
             session_configuration.provider.clone(),
+            None,
             &session_configuration,
             conversation_id,
             "turn_id".to_string(),
@@ -19,6 +19,7 @@ pub struct McpAuthStatusEntry {
 pub async fn compute_auth_statuses<'a, I>(
     servers: I,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> HashMap<String, McpAuthStatusEntry>
 where
     I: IntoIterator<Item = (&'a String, &'a McpServerConfig)>,
@@ -27,13 +28,14 @@ where
         let name = name.clone();
         let config = config.clone();
         async move {
-            let auth_status = match compute_auth_status(&name, &config, store_mode).await {
-                Ok(status) => status,
-                Err(error) => {
-                    warn!("failed to determine auth status for MCP server `{name}`: {error:?}");
-                    McpAuthStatus::Unsupported
-                }
-            };
+            let auth_status =
+                match compute_auth_status(&name, &config, store_mode, credential_helper).await {
+                    Ok(status) => status,
+                    Err(error) => {
+                        warn!("failed to determine auth status for MCP server `{name}`: {error:?}");
+                        McpAuthStatus::Unsupported
+                    }
+                };
             let entry = McpAuthStatusEntry {
                 config,
                 auth_status,
@@ -49,6 +51,7 @@ async fn compute_auth_status(
     server_name: &str,
     config: &McpServerConfig,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<McpAuthStatus> {
     match &config.transport {
         McpServerTransportConfig::Stdio { .. } => Ok(McpAuthStatus::Unsupported),
@@ -65,6 +68,7 @@ async fn compute_auth_status(
                 http_headers.clone(),
                 env_http_headers.clone(),
                 store_mode,
+                credential_helper,
             )
             .await
         }
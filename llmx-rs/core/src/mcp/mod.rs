@@ -1 +1,5 @@
 pub mod auth;
+
+pub use crate::mcp_connection_manager::DEFAULT_STARTUP_TIMEOUT;
+pub use crate::mcp_connection_manager::DEFAULT_TOOL_TIMEOUT;
+pub use crate::mcp_connection_manager::connect_mcp_server;
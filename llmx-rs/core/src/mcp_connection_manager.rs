@@ -20,6 +20,7 @@ use llmx_rmcp_client::OAuthCredentialsStoreMode;
 use llmx_rmcp_client::RmcpClient;
 use mcp_types::ClientCapabilities;
 use mcp_types::Implementation;
+use mcp_types::InitializeResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -52,7 +53,7 @@ const MAX_TOOL_NAME_LENGTH: usize = 64;
 pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Default timeout for individual tool calls.
-const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Map that holds a startup error for every MCP server that could **not** be
 /// spawned successfully.
@@ -130,6 +131,7 @@ impl McpConnectionManager {
     pub async fn new(
         mcp_servers: HashMap<String, McpServerConfig>,
         store_mode: OAuthCredentialsStoreMode,
+        credential_helper: Option<&[String]>,
     ) -> Result<(Self, ClientStartErrors)> {
         // Early exit if no servers are configured.
         if mcp_servers.is_empty() {
@@ -159,89 +161,13 @@ impl McpConnectionManager {
             let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
             let tool_timeout = cfg.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT);
             tool_filters.insert(server_name.clone(), ToolFilter::from_config(&cfg));
-
-            let resolved_bearer_token = match &cfg.transport {
-                McpServerTransportConfig::StreamableHttp {
-                    bearer_token_env_var,
-                    ..
-                } => resolve_bearer_token(&server_name, bearer_token_env_var.as_deref()),
-                _ => Ok(None),
-            };
+            let credential_helper = credential_helper.map(<[String]>::to_vec);
 
             join_set.spawn(async move {
-                let McpServerConfig { transport, .. } = cfg;
-                let params = mcp_types::InitializeRequestParams {
-                    capabilities: ClientCapabilities {
-                        experimental: None,
-                        roots: None,
-                        sampling: None,
-                        // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
-                        // indicates this should be an empty object.
-                        elicitation: Some(json!({})),
-                    },
-                    client_info: Implementation {
-                        name: "llmx-mcp-client".to_owned(),
-                        version: env!("CARGO_PKG_VERSION").to_owned(),
-                        title: Some("LLMX".into()),
-                        // This field is used by LLMX when it is an MCP
-                        // server: it should not be used when LLMX is
-                        // an MCP client.
-                        user_agent: None,
-                    },
-                    protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
-                };
-
-                let resolved_bearer_token = resolved_bearer_token.unwrap_or_default();
-                let client_result = match transport {
-                    McpServerTransportConfig::Stdio {
-                        command,
-                        args,
-                        env,
-                        env_vars,
-                        cwd,
-                    } => {
-                        let command_os: OsString = command.into();
-                        let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
-                        match RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd)
-                            .await
-                        {
-                            Ok(client) => {
-                                let client = Arc::new(client);
-                                client
-                                    .initialize(params.clone(), Some(startup_timeout))
-                                    .await
-                                    .map(|_| client)
-                            }
-                            Err(err) => Err(err.into()),
-                        }
-                    }
-                    McpServerTransportConfig::StreamableHttp {
-                        url,
-                        http_headers,
-                        env_http_headers,
-                        ..
-                    } => {
-                        match RmcpClient::new_streamable_http_client(
-                            &server_name,
-                            &url,
-                            resolved_bearer_token.clone(),
-                            http_headers,
-                            env_http_headers,
-                            store_mode,
-                        )
+                let client_result =
+                    connect_mcp_server(&server_name, cfg, store_mode, credential_helper.as_deref())
                         .await
-                        {
-                            Ok(client) => {
-                                let client = Arc::new(client);
-                                client
-                                    .initialize(params.clone(), Some(startup_timeout))
-                                    .await
-                                    .map(|_| client)
-                            }
-                            Err(err) => Err(err),
-                        }
-                    }
-                };
+                        .map(|(client, _)| Arc::new(client));
 
                 (
                     (server_name, tool_timeout),
@@ -527,6 +453,78 @@ impl McpConnectionManager {
     }
 }
 
+/// Start a single MCP server with its configured transport and auth, and
+/// perform the initialization handshake within the server's startup timeout.
+///
+/// Returns the ready client together with the server's handshake response.
+pub async fn connect_mcp_server(
+    server_name: &str,
+    cfg: McpServerConfig,
+    store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> Result<(RmcpClient, InitializeResult)> {
+    let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
+    let params = mcp_types::InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
+            // indicates this should be an empty object.
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "llmx-mcp-client".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            title: Some("LLMX".into()),
+            // This field is used by LLMX when it is an MCP
+            // server: it should not be used when LLMX is
+            // an MCP client.
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
+    };
+
+    let client = match cfg.transport {
+        McpServerTransportConfig::Stdio {
+            command,
+            args,
+            env,
+            env_vars,
+            cwd,
+        } => {
+            let command_os: OsString = command.into();
+            let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
+            RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd).await?
+        }
+        McpServerTransportConfig::StreamableHttp {
+            url,
+            bearer_token_env_var,
+            http_headers,
+            env_http_headers,
+        } => {
+            let bearer_token = resolve_bearer_token(server_name, bearer_token_env_var.as_deref())
+                .unwrap_or_else(|err| {
+                    warn!("{err:#}");
+                    None
+                });
+            RmcpClient::new_streamable_http_client(
+                server_name,
+                &url,
+                bearer_token,
+                http_headers,
+                env_http_headers,
+                store_mode,
+                credential_helper,
+            )
+            .await?
+        }
+    };
+
+    let initialize_result = client.initialize(params, Some(startup_timeout)).await?;
+    Ok((client, initialize_result))
+}
+
 /// A tool is allowed to be used if both are true:
 /// 1. enabled is None (no allowlist is set) or the tool is explicitly enabled.
 /// 2. The tool is not explicitly disabled.
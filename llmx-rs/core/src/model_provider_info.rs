@@ -6,6 +6,7 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::LlmxAuth;
+use crate::auth::client_credentials;
 use crate::default_client::LlmxHttpClient;
 use crate::default_client::LlmxRequestBuilder;
 use llmx_app_server_protocol::AuthMode;
@@ -97,6 +98,26 @@ pub struct ModelProviderInfo {
     /// and API key (if needed) comes from the "env_key" environment variable.
     #[serde(default)]
     pub requires_openai_auth: bool,
+
+    /// Obtain short-lived bearer tokens with the OAuth2 client-credentials
+    /// grant instead of sending a static API key.
+    pub oauth2: Option<ProviderOAuth2Config>,
+}
+
+/// OAuth2 client-credentials settings for a provider.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ProviderOAuth2Config {
+    /// Token endpoint, e.g. `https://login.microsoftonline.com/<tenant>/oauth2/v2.0/token`.
+    pub token_url: String,
+    pub client_id: String,
+    /// Environment variable that holds the client secret. When unset or
+    /// empty, the secret saved by `llmx login --provider <id>` is used.
+    pub client_secret_env: Option<String>,
+    /// Scopes to request, sent space-separated.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// `audience` parameter required by some identity providers.
+    pub audience: Option<String>,
 }
 
 impl ModelProviderInfo {
@@ -105,18 +126,33 @@ impl ModelProviderInfo {
     ///   • provider-specific headers (static + env based)
     ///   • Bearer auth header when an API key is available.
     ///   • Auth token for OAuth.
+    ///   • Token from the OAuth2 client-credentials grant when `oauth2` is set.
+    ///
+    /// `stored_key` is the secret saved by `llmx login --provider`. It is used
+    /// when `env_key` is unset or its variable is missing/empty, and as the
+    /// OAuth2 client secret when `client_secret_env` is not available.
     ///
-    /// If the provider declares an `env_key` but the variable is missing/empty, returns an [`Err`] identical to the
-    /// one produced by [`ModelProviderInfo::api_key`].
+    /// If the provider declares an `env_key` but neither the variable nor a stored key is available, returns an
+    /// [`Err`] identical to the one produced by [`ModelProviderInfo::api_key`].
     pub async fn create_request_builder<'a>(
         &'a self,
         client: &'a LlmxHttpClient,
         auth: &Option<LlmxAuth>,
+        stored_key: Option<&str>,
     ) -> crate::error::Result<LlmxRequestBuilder> {
+        if self.experimental_bearer_token.is_none()
+            && let Some(oauth2) = &self.oauth2
+        {
+            let client_secret = self.oauth2_client_secret(oauth2, stored_key)?;
+            let token = client_credentials::access_token(client, oauth2, &client_secret).await?;
+            let builder = client.post(self.get_full_url(&None)).bearer_auth(token);
+            return Ok(self.apply_http_headers(builder));
+        }
+
         let effective_auth = if let Some(secret_key) = &self.experimental_bearer_token {
             Some(LlmxAuth::from_api_key(secret_key))
         } else {
-            match self.api_key() {
+            match self.api_key_or_stored(stored_key) {
                 Ok(Some(key)) => Some(LlmxAuth::from_api_key(&key)),
                 Ok(None) => auth.clone(),
                 Err(err) => {
@@ -240,6 +276,65 @@ impl ModelProviderInfo {
         }
     }
 
+    /// Whether the provider authenticates with a secret that
+    /// `llmx login --provider` can store: an `oauth2` client secret, or an API
+    /// key named by `env_key` for providers that do not use OpenAI auth.
+    pub fn accepts_stored_key(&self) -> bool {
+        self.oauth2.is_some() || (self.env_key.is_some() && !self.requires_openai_auth)
+    }
+
+    /// Whether a secret saved by `llmx login --provider` would be used, i.e.
+    /// the provider [accepts one](Self::accepts_stored_key) and neither the
+    /// config nor the environment already supplies a credential.
+    pub(crate) fn wants_stored_key(&self) -> bool {
+        if self.experimental_bearer_token.is_some() || !self.accepts_stored_key() {
+            return false;
+        }
+        if let Some(oauth2) = &self.oauth2 {
+            return !oauth2.client_secret_env.as_ref().is_some_and(|env_var| {
+                std::env::var(env_var).is_ok_and(|secret| !secret.trim().is_empty())
+            });
+        }
+        !matches!(self.api_key(), Ok(Some(_)))
+    }
+
+    /// Like [`ModelProviderInfo::api_key`], but falls back to `stored_key`
+    /// when the environment does not provide one.
+    fn api_key_or_stored(&self, stored_key: Option<&str>) -> crate::error::Result<Option<String>> {
+        match (self.api_key(), stored_key) {
+            (Ok(Some(key)), _) => Ok(Some(key)),
+            (Ok(None) | Err(_), Some(stored)) => Ok(Some(stored.to_string())),
+            (result, None) => result,
+        }
+    }
+
+    fn oauth2_client_secret(
+        &self,
+        oauth2: &ProviderOAuth2Config,
+        stored_key: Option<&str>,
+    ) -> crate::error::Result<String> {
+        if let Some(env_var) = &oauth2.client_secret_env
+            && let Ok(secret) = std::env::var(env_var)
+            && !secret.trim().is_empty()
+        {
+            return Ok(secret);
+        }
+        stored_key
+            .map(str::to_string)
+            .ok_or_else(|| match &oauth2.client_secret_env {
+                Some(env_var) => crate::error::LlmxErr::EnvVar(EnvVarError {
+                    var: env_var.clone(),
+                    instructions: Some(
+                        "Or save the client secret with `llmx login --provider`.".to_string(),
+                    ),
+                }),
+                None => crate::error::LlmxErr::Fatal(format!(
+                    "No OAuth2 client secret for {}; run `llmx login --provider` to save one.",
+                    self.name
+                )),
+            })
+    }
+
     /// Effective maximum number of request retries for this provider.
     pub fn request_max_retries(&self) -> u64 {
         self.request_max_retries
@@ -296,6 +391,7 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
                 stream_idle_timeout_ms: None,
             max_tokens: None,
                 requires_openai_auth: false,
+                oauth2: None,
             },
         ),
         (
@@ -337,6 +433,7 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
                 stream_idle_timeout_ms: None,
             max_tokens: None,
                 requires_openai_auth: true,
+                oauth2: None,
             },
         ),
         (BUILT_IN_OSS_MODEL_PROVIDER_ID, create_oss_provider()),
@@ -383,6 +480,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str) -> ModelProviderInfo {
         stream_idle_timeout_ms: None,
             max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     }
 }
 
@@ -424,6 +522,7 @@ base_url = "http://localhost:11434/v1"
             stream_idle_timeout_ms: None,
             max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -455,12 +554,65 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_idle_timeout_ms: None,
             max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_oauth2_model_provider_toml() {
+        let provider_toml = r#"
+name = "Azure"
+base_url = "https://xxxxx.openai.azure.com/openai"
+wire_api = "responses"
+
+[oauth2]
+token_url = "https://login.microsoftonline.com/tenant/oauth2/v2.0/token"
+client_id = "client"
+client_secret_env = "AZURE_CLIENT_SECRET"
+scopes = ["https://cognitiveservices.azure.com/.default"]
+        "#;
+
+        let provider: ModelProviderInfo = toml::from_str(provider_toml).unwrap();
+
+        assert_eq!(
+            provider.oauth2,
+            Some(ProviderOAuth2Config {
+                token_url: "https://login.microsoftonline.com/tenant/oauth2/v2.0/token".into(),
+                client_id: "client".into(),
+                client_secret_env: Some("AZURE_CLIENT_SECRET".into()),
+                scopes: vec!["https://cognitiveservices.azure.com/.default".into()],
+                audience: None,
+            })
+        );
+    }
+
+    #[test]
+    fn stored_key_is_used_when_env_key_is_missing() {
+        let provider = ModelProviderInfo {
+            env_key: Some("LLMX_TEST_UNSET_PROVIDER_KEY".into()),
+            ..create_oss_provider_with_base_url("http://localhost:1234/v1")
+        };
+
+        assert!(provider.api_key_or_stored(None).is_err());
+        assert_eq!(
+            provider.api_key_or_stored(Some("sk-stored")).unwrap(),
+            Some("sk-stored".to_string())
+        );
+        assert!(provider.wants_stored_key());
+    }
+
+    #[test]
+    fn providers_without_a_key_never_look_up_stored_keys() {
+        let provider = create_oss_provider_with_base_url("http://localhost:1234/v1");
+
+        assert!(provider.env_key.is_none());
+        assert!(!provider.accepts_stored_key());
+        assert!(!provider.wants_stored_key());
+    }
+
     #[test]
     fn test_deserialize_example_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -489,6 +641,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_idle_timeout_ms: None,
             max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -513,6 +666,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_idle_timeout_ms: None,
             max_tokens: None,
                 requires_openai_auth: false,
+                oauth2: None,
             }
         }
 
@@ -547,6 +701,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_idle_timeout_ms: None,
             max_tokens: None,
             requires_openai_auth: false,
+            oauth2: None,
         };
         assert!(named_provider.is_azure_responses_endpoint());
 
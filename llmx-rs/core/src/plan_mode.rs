@@ -0,0 +1,92 @@
+//! Helpers for plan mode, where the agent explores read-only and proposes a
+//! plan that the user approves before anything is executed.
+
+use llmx_protocol::plan_tool::StepStatus;
+use llmx_protocol::plan_tool::UpdatePlanArgs;
+
+/// Developer instructions appended to every plan-mode turn.
+pub(crate) const PLAN_MODE_INSTRUCTIONS: &str =
+    include_str!("../templates/plan_mode/instructions.md");
+
+const APPROVED_PLAN_TMPL: &str = include_str!("../templates/plan_mode/approved.md");
+
+/// User input that starts execution once a plan has been approved.
+pub(crate) const EXECUTE_PLAN_PROMPT: &str = "Execute the approved plan.";
+
+/// Renders a proposed plan as the plain text the user approves or edits.
+/// Falls back to the final assistant message when the model never called
+/// `update_plan`.
+pub(crate) fn format_plan(plan: Option<&UpdatePlanArgs>, message: Option<&str>) -> String {
+    let Some(plan) = plan.filter(|plan| !plan.plan.is_empty()) else {
+        return message.unwrap_or_default().trim().to_string();
+    };
+    let mut text = String::new();
+    if let Some(explanation) = plan
+        .explanation
+        .as_deref()
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        text.push_str(explanation);
+        text.push_str("\n\n");
+    }
+    for (idx, item) in plan.plan.iter().enumerate() {
+        let marker = match item.status {
+            StepStatus::Completed => " (done)",
+            StepStatus::InProgress | StepStatus::Pending => "",
+        };
+        text.push_str(&format!("{}. {}{marker}\n", idx + 1, item.step.trim()));
+    }
+    text.trim_end().to_string()
+}
+
+/// Developer instructions that pin an approved plan for the rest of the
+/// session, including after the history is compacted.
+pub(crate) fn approved_plan_instructions(plan: &str) -> String {
+    APPROVED_PLAN_TMPL.replace("{{plan}}", plan.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_protocol::plan_tool::PlanItemArg;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_plan_numbers_steps_after_explanation() {
+        let plan = UpdatePlanArgs {
+            explanation: Some("Split the parser first.".to_string()),
+            plan: vec![
+                PlanItemArg {
+                    step: "Extract tokenizer".to_string(),
+                    status: StepStatus::Pending,
+                },
+                PlanItemArg {
+                    step: "Add tests".to_string(),
+                    status: StepStatus::Completed,
+                },
+            ],
+        };
+
+        assert_eq!(
+            format_plan(Some(&plan), Some("ignored")),
+            "Split the parser first.\n\n1. Extract tokenizer\n2. Add tests (done)"
+        );
+    }
+
+    #[test]
+    fn format_plan_falls_back_to_message() {
+        let empty = UpdatePlanArgs {
+            explanation: None,
+            plan: Vec::new(),
+        };
+        assert_eq!(format_plan(Some(&empty), Some("  Do X.\n")), "Do X.");
+        assert_eq!(format_plan(None, None), "");
+    }
+
+    #[test]
+    fn approved_plan_instructions_embed_plan() {
+        let text = approved_plan_instructions("1. Do it\n");
+        assert!(text.contains("<approved_plan>\n1. Do it\n</approved_plan>"));
+    }
+}
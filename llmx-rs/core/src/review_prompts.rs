@@ -0,0 +1,98 @@
+//! Builds review prompts from structured [`ReviewTarget`]s so every client
+//! (TUI, `llmx review`, app-server) asks for the same review.
+
+use crate::git_info::merge_base_with_head;
+use crate::protocol::ReviewRequest;
+use crate::protocol::ReviewTarget;
+use std::path::Path;
+
+const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
+
+/// Returns a request for `target` that does not depend on repository state.
+pub fn review_request(target: ReviewTarget) -> ReviewRequest {
+    let (prompt, user_facing_hint) = match &target {
+        ReviewTarget::UncommittedChanges => (
+            UNCOMMITTED_PROMPT.to_string(),
+            "current changes".to_string(),
+        ),
+        ReviewTarget::BaseBranch { branch } => (
+            format!(
+                "Review the code changes against the base branch '{branch}'. Start by finding the merge diff between the current branch and {branch}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{branch}@{{upstream}}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
+            ),
+            format!("changes against '{branch}'"),
+        ),
+        ReviewTarget::Commit { sha, title } => {
+            let short: String = sha.chars().take(7).collect();
+            let prompt = match title {
+                Some(title) => format!(
+                    "Review the code changes introduced by commit {sha} (\"{title}\"). Provide prioritized, actionable findings."
+                ),
+                None => format!(
+                    "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings."
+                ),
+            };
+            (prompt, format!("commit {short}"))
+        }
+        ReviewTarget::Custom { instructions } => {
+            let instructions = instructions.trim().to_string();
+            (instructions.clone(), instructions)
+        }
+    };
+    ReviewRequest {
+        prompt,
+        user_facing_hint,
+        target: Some(target),
+    }
+}
+
+/// Refines `request` with details only available inside the repository. For
+/// a base branch this pins the merge base so the reviewer diffs against a
+/// concrete commit instead of discovering it.
+pub async fn resolve_review_request(request: ReviewRequest, cwd: &Path) -> ReviewRequest {
+    let Some(ReviewTarget::BaseBranch { branch }) = &request.target else {
+        return request;
+    };
+    let Some(merge_base) = merge_base_with_head(cwd, branch).await else {
+        return request;
+    };
+    ReviewRequest {
+        prompt: format!(
+            "Review the code changes against the base branch '{branch}'. The merge base of the current branch and {branch} is {merge_base}; run `git diff {merge_base}` to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
+        ),
+        ..request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn commit_target_uses_short_sha_hint() {
+        let request = review_request(ReviewTarget::Commit {
+            sha: "0123456789abcdef".to_string(),
+            title: Some("Fix parser".to_string()),
+        });
+
+        assert_eq!(request.user_facing_hint, "commit 0123456");
+        assert_eq!(
+            request.prompt,
+            "Review the code changes introduced by commit 0123456789abcdef (\"Fix parser\"). Provide prioritized, actionable findings."
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_leaves_request_alone_outside_git() {
+        let tmp = TempDir::new().expect("tempdir");
+        let request = review_request(ReviewTarget::BaseBranch {
+            branch: "main".to_string(),
+        });
+
+        assert_eq!(
+            resolve_review_request(request.clone(), tmp.path()).await,
+            request
+        );
+    }
+}
@@ -0,0 +1,945 @@
+//! Render a recorded session (rollout file) as Markdown, HTML, or JSON.
+//!
+//! Rollouts are an append-only log of `RolloutItem`s. Exporting walks that
+//! log once, pairs tool calls with their outputs, and produces a flat list of
+//! [`ExportEntry`] values that every output format renders from.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use llmx_apply_patch::Hunk;
+use llmx_apply_patch::MaybeApplyPatch;
+use llmx_protocol::models::LocalShellAction;
+use llmx_protocol::models::ResponseItem;
+use llmx_protocol::models::ShellToolCallParams;
+use llmx_protocol::protocol::EventMsg;
+use llmx_protocol::protocol::InitialHistory;
+use llmx_protocol::protocol::RolloutItem;
+use llmx_protocol::protocol::TokenUsage;
+use regex_lite::Captures;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::approval_rules::project_root;
+use crate::bash::extract_bash_command;
+use crate::rollout::RolloutRecorder;
+
+/// Command output longer than this many lines keeps only its head and tail.
+const MAX_OUTPUT_LINES: usize = 40;
+const REDACTED_VALUE: &str = "***";
+const REDACTED_PATH: &str = "<redacted path>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(format!(
+                "unknown export format `{other}` (expected md, html, or json)"
+            )),
+        }
+    }
+}
+
+/// Session-level details shown at the top of an export.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExportHeader {
+    pub id: Option<String>,
+    pub started_at: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub model: Option<String>,
+    pub model_provider: Option<String>,
+    pub cli_version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportEntry {
+    UserMessage {
+        text: String,
+    },
+    AgentMessage {
+        text: String,
+    },
+    Reasoning {
+        text: String,
+    },
+    Command {
+        command: String,
+        exit_code: Option<i32>,
+        output: String,
+        /// Lines dropped from the middle of `output`.
+        omitted_lines: usize,
+    },
+    /// A patch rendered as a unified diff. Hunk headers carry the patch's
+    /// context line rather than line numbers, which rollouts do not record.
+    Patch {
+        diff: String,
+        success: Option<bool>,
+    },
+    ToolCall {
+        name: String,
+        arguments: String,
+        output: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SessionExport {
+    pub header: ExportHeader,
+    pub entries: Vec<ExportEntry>,
+    /// Cumulative usage from the last token count recorded in the session.
+    pub token_usage: Option<TokenUsage>,
+}
+
+#[derive(Deserialize)]
+struct ExecOutputJson {
+    output: String,
+    metadata: ExecOutputMetadataJson,
+}
+
+#[derive(Deserialize)]
+struct ExecOutputMetadataJson {
+    exit_code: i32,
+}
+
+#[derive(Deserialize)]
+struct ApplyPatchArguments {
+    input: String,
+}
+
+/// Reads the rollout at `path` and converts it into an export.
+pub async fn load_session_export(path: &Path) -> io::Result<SessionExport> {
+    let items = match RolloutRecorder::get_rollout_history(path).await? {
+        InitialHistory::New => Vec::new(),
+        InitialHistory::Resumed(resumed) => resumed.history,
+        InitialHistory::Forked(items) => items,
+    };
+    Ok(SessionExport::from_rollout_items(&items))
+}
+
+impl SessionExport {
+    pub fn from_rollout_items(items: &[RolloutItem]) -> Self {
+        let mut export = SessionExport::default();
+        // call_id -> index of the entry awaiting that call's output.
+        let mut pending: HashMap<String, usize> = HashMap::new();
+        for item in items {
+            match item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    let meta = &meta_line.meta;
+                    let header = &mut export.header;
+                    if header.id.is_none() {
+                        header.id = Some(meta.id.to_string());
+                        header.started_at = Some(meta.timestamp.clone());
+                        header.cwd = Some(meta.cwd.clone());
+                        header.model_provider = meta.model_provider.clone();
+                        header.cli_version = Some(meta.cli_version.clone());
+                    }
+                }
+                RolloutItem::TurnContext(context) => {
+                    export.header.model = Some(context.model.clone());
+                    if export.header.cwd.is_none() {
+                        export.header.cwd = Some(context.cwd.clone());
+                    }
+                }
+                RolloutItem::EventMsg(event) => match event {
+                    EventMsg::UserMessage(ev) => export.entries.push(ExportEntry::UserMessage {
+                        text: ev.message.clone(),
+                    }),
+                    EventMsg::AgentMessage(ev) => export.entries.push(ExportEntry::AgentMessage {
+                        text: ev.message.clone(),
+                    }),
+                    EventMsg::AgentReasoning(ev) => export.entries.push(ExportEntry::Reasoning {
+                        text: ev.text.clone(),
+                    }),
+                    EventMsg::TokenCount(ev) => {
+                        if let Some(info) = &ev.info {
+                            export.token_usage = Some(info.total_token_usage.clone());
+                        }
+                    }
+                    _ => {}
+                },
+                RolloutItem::ResponseItem(item) => {
+                    export.push_response_item(item, &mut pending);
+                }
+                RolloutItem::Compacted(_) => {}
+            }
+        }
+        export
+    }
+
+    fn push_response_item(&mut self, item: &ResponseItem, pending: &mut HashMap<String, usize>) {
+        let (call_id, entry) = match item {
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let entry = match name.as_str() {
+                    "shell" | "container.exec" => {
+                        match serde_json::from_str::<ShellToolCallParams>(arguments) {
+                            Ok(params) => command_entry(&params.command),
+                            Err(_) => tool_call_entry(name, arguments),
+                        }
+                    }
+                    "apply_patch" => match serde_json::from_str::<ApplyPatchArguments>(arguments) {
+                        Ok(args) => patch_entry(&args.input),
+                        Err(_) => tool_call_entry(name, arguments),
+                    },
+                    _ => tool_call_entry(name, arguments),
+                };
+                (Some(call_id.clone()), entry)
+            }
+            ResponseItem::LocalShellCall {
+                call_id,
+                id,
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => (
+                call_id.clone().or_else(|| id.clone()),
+                command_entry(&exec.command),
+            ),
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                let entry = if name == "apply_patch" {
+                    patch_entry(input)
+                } else {
+                    tool_call_entry(name, input)
+                };
+                (Some(call_id.clone()), entry)
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                self.record_output(pending.remove(call_id), &output.content, output.success);
+                return;
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output } => {
+                self.record_output(pending.remove(call_id), output, None);
+                return;
+            }
+            _ => return,
+        };
+        if let Some(call_id) = call_id {
+            pending.insert(call_id, self.entries.len());
+        }
+        self.entries.push(entry);
+    }
+
+    fn record_output(&mut self, index: Option<usize>, content: &str, success: Option<bool>) {
+        let Some(entry) = index.and_then(|idx| self.entries.get_mut(idx)) else {
+            return;
+        };
+        let (parsed_exit_code, text) = parse_exec_output(content);
+        match entry {
+            ExportEntry::Command {
+                exit_code,
+                output,
+                omitted_lines,
+                ..
+            } => {
+                let (truncated, omitted) = truncate_output(&text);
+                *exit_code = parsed_exit_code;
+                *output = truncated;
+                *omitted_lines = omitted;
+            }
+            ExportEntry::Patch {
+                success: patch_success,
+                ..
+            } => {
+                *patch_success = success.or(parsed_exit_code.map(|code| code == 0));
+            }
+            ExportEntry::ToolCall { output, .. } => {
+                *output = Some(truncate_output(&text).0);
+            }
+            ExportEntry::UserMessage { .. }
+            | ExportEntry::AgentMessage { .. }
+            | ExportEntry::Reasoning { .. } => {}
+        }
+    }
+
+    /// Masks environment variable values and absolute paths outside the
+    /// session's project: the git repository containing the working
+    /// directory, or the working directory itself outside a repository.
+    pub fn redact(&mut self) {
+        let root = self.header.cwd.as_deref().map(project_root);
+        let redact = |text: &mut String| *text = redact_text(text, root.as_deref());
+        for entry in &mut self.entries {
+            match entry {
+                ExportEntry::UserMessage { text }
+                | ExportEntry::AgentMessage { text }
+                | ExportEntry::Reasoning { text } => redact(text),
+                ExportEntry::Command {
+                    command, output, ..
+                } => {
+                    redact(command);
+                    redact(output);
+                }
+                ExportEntry::Patch { diff, .. } => redact(diff),
+                ExportEntry::ToolCall {
+                    arguments, output, ..
+                } => {
+                    redact(arguments);
+                    if let Some(output) = output {
+                        redact(output);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn render(&self, format: ExportFormat) -> io::Result<String> {
+        match format {
+            ExportFormat::Markdown => Ok(self.to_markdown()),
+            ExportFormat::Html => Ok(self.to_html()),
+            ExportFormat::Json => serde_json::to_string_pretty(self).map_err(io::Error::other),
+        }
+    }
+
+    fn title(&self) -> String {
+        match &self.header.id {
+            Some(id) => format!("LLMX session {id}"),
+            None => "LLMX session".to_string(),
+        }
+    }
+
+    fn header_fields(&self) -> Vec<(&'static str, String)> {
+        let header = &self.header;
+        let mut fields = Vec::new();
+        if let Some(started_at) = &header.started_at {
+            fields.push(("Started", started_at.clone()));
+        }
+        if let Some(cwd) = &header.cwd {
+            fields.push(("Directory", cwd.display().to_string()));
+        }
+        if let Some(model) = &header.model {
+            fields.push(("Model", model.clone()));
+        }
+        if let Some(provider) = &header.model_provider {
+            fields.push(("Provider", provider.clone()));
+        }
+        if let Some(version) = &header.cli_version {
+            fields.push(("LLMX version", version.clone()));
+        }
+        fields
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title());
+        for (label, value) in self.header_fields() {
+            let _ = writeln!(out, "- **{label}:** `{value}`");
+        }
+        for entry in &self.entries {
+            out.push('\n');
+            match entry {
+                ExportEntry::UserMessage { text } => {
+                    let _ = write!(out, "## User\n\n{}\n", text.trim_end());
+                }
+                ExportEntry::AgentMessage { text } => {
+                    let _ = write!(out, "## Assistant\n\n{}\n", text.trim_end());
+                }
+                ExportEntry::Reasoning { text } => {
+                    out.push_str("> _Reasoning_\n>\n");
+                    for line in text.trim_end().lines() {
+                        let _ = writeln!(out, "> {line}");
+                    }
+                }
+                ExportEntry::Command {
+                    command,
+                    exit_code,
+                    output,
+                    omitted_lines,
+                } => {
+                    let _ = writeln!(out, "**Command** ({})\n", exit_code_label(*exit_code));
+                    let body = command_block(command, output);
+                    let fence = code_fence(&body);
+                    let _ = write!(out, "{fence}console\n{body}\n{fence}\n");
+                }
+                ExportEntry::Patch { diff, success } => {
+                    let _ = writeln!(out, "**Patch** ({})\n", patch_status_label(*success));
+                    let fence = code_fence(diff);
+                    let _ = write!(out, "{fence}diff\n{}\n{fence}\n", diff.trim_end());
+                }
+                ExportEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    let _ = writeln!(out, "**Tool call** `{name}`\n");
+                    let body = tool_call_block(arguments, output.as_deref());
+                    let fence = code_fence(&body);
+                    let _ = write!(out, "{fence}\n{body}\n{fence}\n");
+                }
+            }
+        }
+        if let Some(usage) = &self.token_usage {
+            out.push_str("\n## Token usage\n\n");
+            out.push_str("| Input | Cached input | Output | Reasoning | Total |\n");
+            out.push_str("| ---: | ---: | ---: | ---: | ---: |\n");
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                usage.input_tokens,
+                usage.cached_input_tokens,
+                usage.output_tokens,
+                usage.reasoning_output_tokens,
+                usage.total_tokens
+            );
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let title = html_escape(&self.title());
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        let fields = self.header_fields();
+        if !fields.is_empty() {
+            out.push_str("<dl class=\"meta\">\n");
+            for (label, value) in fields {
+                let _ = writeln!(
+                    out,
+                    "<dt>{label}</dt><dd><code>{}</code></dd>",
+                    html_escape(&value)
+                );
+            }
+            out.push_str("</dl>\n");
+        }
+        for entry in &self.entries {
+            match entry {
+                ExportEntry::UserMessage { text } => {
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"user\"><h2>User</h2><div class=\"text\">{}</div></section>",
+                        html_escape(text.trim_end())
+                    );
+                }
+                ExportEntry::AgentMessage { text } => {
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"assistant\"><h2>Assistant</h2><div class=\"text\">{}</div></section>",
+                        html_escape(text.trim_end())
+                    );
+                }
+                ExportEntry::Reasoning { text } => {
+                    let _ = writeln!(
+                        out,
+                        "<details class=\"reasoning\"><summary>Reasoning</summary><div class=\"text\">{}</div></details>",
+                        html_escape(text.trim_end())
+                    );
+                }
+                ExportEntry::Command {
+                    command,
+                    exit_code,
+                    output,
+                    omitted_lines,
+                } => {
+                    let status = if exit_code.is_some_and(|code| code != 0) {
+                        "failed"
+                    } else {
+                        "ok"
+                    };
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"command {status}\"><h3>Command ({})</h3><pre>{}</pre></section>",
+                        exit_code_label(*exit_code),
+                        html_escape(&command_block(command, output))
+                    );
+                }
+                ExportEntry::Patch { diff, success } => {
+                    let _ = write!(
+                        out,
+                        "<section class=\"patch\"><h3>Patch ({})</h3><pre class=\"diff\">",
+                        patch_status_label(*success)
+                    );
+                    for line in diff.trim_end().lines() {
+                        let class = match line.chars().next() {
+                            Some('+') if !line.starts_with("+++") => "add",
+                            Some('-') if !line.starts_with("---") => "del",
+                            Some('@') => "hunk",
+                            _ => "ctx",
+                        };
+                        let _ =
+                            writeln!(out, "<span class=\"{class}\">{}</span>", html_escape(line));
+                    }
+                    out.push_str("</pre></section>\n");
+                }
+                ExportEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                } => {
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"tool\"><h3>Tool call <code>{}</code></h3><pre>{}</pre></section>",
+                        html_escape(name),
+                        html_escape(&tool_call_block(arguments, output.as_deref()))
+                    );
+                }
+            }
+        }
+        if let Some(usage) = &self.token_usage {
+            let _ = writeln!(
+                out,
+                "<section class=\"usage\"><h2>Token usage</h2><table><tr><th>Input</th><th>Cached input</th><th>Output</th><th>Reasoning</th><th>Total</th></tr><tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr></table></section>",
+                usage.input_tokens,
+                usage.cached_input_tokens,
+                usage.output_tokens,
+                usage.reasoning_output_tokens,
+                usage.total_tokens
+            );
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+.meta dt{font-weight:bold;float:left;clear:left;width:8rem}.meta dd{margin-left:8rem}\
+section{margin:1rem 0}.text{white-space:pre-wrap}\
+.user{border-left:4px solid #3b82f6;padding-left:.75rem}.assistant{border-left:4px solid #10b981;padding-left:.75rem}\
+.reasoning{color:#6b7280;font-style:italic}\
+pre{background:#f6f8fa;padding:.75rem;overflow-x:auto}.command.failed h3{color:#b91c1c}\
+.diff .add{color:#15803d}.diff .del{color:#b91c1c}.diff .hunk{color:#7c3aed}\
+table{border-collapse:collapse}th,td{border:1px solid #d1d5db;padding:.25rem .75rem;text-align:right}";
+
+fn command_entry(argv: &[String]) -> ExportEntry {
+    if let MaybeApplyPatch::Body(args) = llmx_apply_patch::maybe_parse_apply_patch(argv) {
+        return ExportEntry::Patch {
+            diff: hunks_to_unified_diff(&args.hunks),
+            success: None,
+        };
+    }
+    let command = match extract_bash_command(argv) {
+        Some((_, script)) => script.to_string(),
+        None => shlex::try_join(argv.iter().map(String::as_str)).unwrap_or_else(|_| argv.join(" ")),
+    };
+    ExportEntry::Command {
+        command,
+        exit_code: None,
+        output: String::new(),
+        omitted_lines: 0,
+    }
+}
+
+fn patch_entry(patch: &str) -> ExportEntry {
+    let diff = match llmx_apply_patch::parse_patch(patch) {
+        Ok(args) => hunks_to_unified_diff(&args.hunks),
+        Err(_) => patch.to_string(),
+    };
+    ExportEntry::Patch {
+        diff,
+        success: None,
+    }
+}
+
+fn tool_call_entry(name: &str, arguments: &str) -> ExportEntry {
+    ExportEntry::ToolCall {
+        name: name.to_string(),
+        arguments: arguments.to_string(),
+        output: None,
+    }
+}
+
+/// Converts apply_patch hunks into unified diff text.
+fn hunks_to_unified_diff(hunks: &[Hunk]) -> String {
+    let mut out = String::new();
+    for hunk in hunks {
+        match hunk {
+            Hunk::AddFile { path, contents } => {
+                let lines: Vec<&str> = contents.lines().collect();
+                let _ = writeln!(out, "--- /dev/null\n+++ b/{}", path.display());
+                let _ = writeln!(out, "@@ -0,0 +1,{} @@", lines.len());
+                for line in lines {
+                    let _ = writeln!(out, "+{line}");
+                }
+            }
+            Hunk::DeleteFile { path } => {
+                let _ = writeln!(out, "--- a/{}\n+++ /dev/null", path.display());
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                let new_path = move_path.as_deref().unwrap_or(path);
+                let _ = writeln!(
+                    out,
+                    "--- a/{}\n+++ b/{}",
+                    path.display(),
+                    new_path.display()
+                );
+                for chunk in chunks {
+                    match &chunk.change_context {
+                        Some(context) => {
+                            let _ = writeln!(out, "@@ {context}");
+                        }
+                        None => out.push_str("@@\n"),
+                    }
+                    let old_lines: Vec<&str> = chunk.old_lines.iter().map(String::as_str).collect();
+                    let new_lines: Vec<&str> = chunk.new_lines.iter().map(String::as_str).collect();
+                    let diff = similar::TextDiff::from_slices(&old_lines, &new_lines);
+                    for change in diff.iter_all_changes() {
+                        let sign = match change.tag() {
+                            similar::ChangeTag::Equal => ' ',
+                            similar::ChangeTag::Delete => '-',
+                            similar::ChangeTag::Insert => '+',
+                        };
+                        let _ = writeln!(out, "{sign}{}", change.value());
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Extracts the exit code and output from a recorded shell call output,
+/// which is either the structured JSON form or the plain-text form.
+fn parse_exec_output(content: &str) -> (Option<i32>, String) {
+    if let Ok(parsed) = serde_json::from_str::<ExecOutputJson>(content) {
+        return (Some(parsed.metadata.exit_code), parsed.output);
+    }
+    if let Some(rest) = content.strip_prefix("Exit code: ") {
+        let exit_code = rest
+            .lines()
+            .next()
+            .and_then(|line| line.trim().parse().ok());
+        let output = rest
+            .split_once("Output:\n")
+            .map(|(_, output)| output.to_string())
+            .unwrap_or_default();
+        return (exit_code, output);
+    }
+    (None, content.to_string())
+}
+
+/// Keeps the first and last lines of long output and reports how many
+/// lines were dropped in between.
+fn truncate_output(output: &str) -> (String, usize) {
+    let lines: Vec<&str> = output.trim_end().lines().collect();
+    if lines.len() <= MAX_OUTPUT_LINES {
+        return (lines.join("\n"), 0);
+    }
+    let keep = MAX_OUTPUT_LINES / 2;
+    let omitted = lines.len() - 2 * keep;
+    let head = lines[..keep].join("\n");
+    let tail = lines[lines.len() - keep..].join("\n");
+    (
+        format!("{head}\n… {omitted} lines omitted …\n{tail}"),
+        omitted,
+    )
+}
+
+fn command_block(command: &str, output: &str) -> String {
+    if output.is_empty() {
+        format!("$ {command}")
+    } else {
+        format!("$ {command}\n{output}")
+    }
+}
+
+fn tool_call_block(arguments: &str, output: Option<&str>) -> String {
+    match output {
+        Some(output) if !output.is_empty() => format!("{arguments}\n\n{output}"),
+        _ => arguments.to_string(),
+    }
+}
+
+fn exit_code_label(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) => format!("exit {code}"),
+        None => "exit code unknown".to_string(),
+    }
+}
+
+fn patch_status_label(success: Option<bool>) -> &'static str {
+    match success {
+        Some(true) => "applied",
+        Some(false) => "failed",
+        None => "status unknown",
+    }
+}
+
+/// Backtick fence long enough that `text` cannot close it early.
+fn code_fence(text: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// `NAME=value` assignments, e.g. `API_KEY=abc cargo test` or `export TOKEN="..."`.
+fn env_assignment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| Regex::new(r#"\b([A-Z][A-Z0-9_]*)=("[^"]*"|'[^']*'|[^\s"'`]+)"#).unwrap())
+}
+
+/// Absolute or home-relative paths with at least two components.
+fn path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    #[expect(clippy::unwrap_used)]
+    RE.get_or_init(|| {
+        Regex::new(r#"(^|[\s"'`(=:])((?:~|/[A-Za-z0-9._@+-]+)(?:/[A-Za-z0-9._@+-]+)+/?)"#).unwrap()
+    })
+}
+
+fn redact_text(text: &str, root: Option<&Path>) -> String {
+    let masked = env_assignment_regex().replace_all(text, |caps: &Captures| {
+        format!("{}={REDACTED_VALUE}", &caps[1])
+    });
+    path_regex()
+        .replace_all(&masked, |caps: &Captures| {
+            let candidate = &caps[2];
+            let inside_root = root.is_some_and(|root| Path::new(candidate).starts_with(root));
+            if inside_root {
+                caps[0].to_string()
+            } else {
+                format!("{}{REDACTED_PATH}", &caps[1])
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_protocol::ConversationId;
+    use llmx_protocol::models::FunctionCallOutputPayload;
+    use llmx_protocol::protocol::AgentMessageEvent;
+    use llmx_protocol::protocol::SessionMeta;
+    use llmx_protocol::protocol::SessionMetaLine;
+    use llmx_protocol::protocol::TokenCountEvent;
+    use llmx_protocol::protocol::TokenUsageInfo;
+    use llmx_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn sample_items() -> Vec<RolloutItem> {
+        let usage = TokenUsage {
+            input_tokens: 120,
+            cached_input_tokens: 20,
+            output_tokens: 30,
+            reasoning_output_tokens: 5,
+            total_tokens: 150,
+        };
+        vec![
+            RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id: ConversationId::default(),
+                    timestamp: "2025-01-01T00:00:00Z".to_string(),
+                    cwd: PathBuf::from("/repo"),
+                    cli_version: "1.2.3".to_string(),
+                    ..Default::default()
+                },
+                git: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "list files".to_string(),
+                images: None,
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["bash","-lc","ls"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: r#"{"output":"src\nREADME.md\n","metadata":{"exit_code":0,"duration_seconds":0.1}}"#
+                        .to_string(),
+                    ..Default::default()
+                },
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: README.md\n@@\n-old\n+new\n*** End Patch"
+                    .to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".to_string(),
+                output: r#"{"output":"Success.","metadata":{"exit_code":0,"duration_seconds":0.0}}"#
+                    .to_string(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Done.".to_string(),
+            })),
+            RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+                info: Some(TokenUsageInfo {
+                    total_token_usage: usage.clone(),
+                    last_token_usage: usage,
+                    model_context_window: None,
+                }),
+                rate_limits: None,
+                context_estimate: None,
+            })),
+        ]
+    }
+
+    #[test]
+    fn converts_rollout_items_into_entries() {
+        let export = SessionExport::from_rollout_items(&sample_items());
+
+        assert_eq!(export.header.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(
+            export.entries,
+            vec![
+                ExportEntry::UserMessage {
+                    text: "list files".to_string(),
+                },
+                ExportEntry::Command {
+                    command: "ls".to_string(),
+                    exit_code: Some(0),
+                    output: "src\nREADME.md".to_string(),
+                    omitted_lines: 0,
+                },
+                ExportEntry::Patch {
+                    diff: "--- a/README.md\n+++ b/README.md\n@@\n-old\n+new\n".to_string(),
+                    success: Some(true),
+                },
+                ExportEntry::AgentMessage {
+                    text: "Done.".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            export.token_usage.map(|usage| usage.total_tokens),
+            Some(150)
+        );
+    }
+
+    #[test]
+    fn markdown_includes_commands_patches_and_usage() {
+        let export = SessionExport::from_rollout_items(&sample_items());
+        let markdown = export.render(ExportFormat::Markdown).expect("render");
+
+        assert!(markdown.contains("## User\n\nlist files\n"), "{markdown}");
+        assert!(
+            markdown.contains("**Command** (exit 0)\n\n```console\n$ ls\nsrc\nREADME.md\n```"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("```diff\n--- a/README.md"), "{markdown}");
+        assert!(
+            markdown.contains("| 120 | 20 | 30 | 5 | 150 |"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let export = SessionExport {
+            entries: vec![ExportEntry::UserMessage {
+                text: "<script>alert(1)</script>".to_string(),
+            }],
+            ..Default::default()
+        };
+        let html = export.render(ExportFormat::Html).expect("render");
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn truncates_long_output() {
+        let output = (0..100).map(|i| format!("line{i}")).collect::<Vec<_>>();
+        let (truncated, omitted) = truncate_output(&output.join("\n"));
+        assert_eq!(omitted, 60);
+        assert!(truncated.starts_with("line0\n"));
+        assert!(truncated.contains("\n… 60 lines omitted …\nline80\n"));
+        assert!(truncated.ends_with("line99"));
+    }
+
+    #[test]
+    fn redacts_env_values_and_outside_paths() {
+        let redacted = redact_text(
+            "API_KEY=sk-123 cat /repo/src/main.rs /home/me/.ssh/id_rsa ~/notes/todo.md https://example.com/a/b",
+            Some(Path::new("/repo")),
+        );
+        assert_eq!(
+            redacted,
+            "API_KEY=*** cat /repo/src/main.rs <redacted path> <redacted path> https://example.com/a/b"
+        );
+    }
+
+    #[test]
+    fn redact_keeps_paths_elsewhere_in_the_repository() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let repo_root = repo.path();
+        std::fs::create_dir(repo_root.join(".git")).expect("git dir");
+        let cwd = repo_root.join("crates").join("app");
+        let sibling = repo_root.join("docs").join("guide.md");
+        let mut export = SessionExport {
+            header: ExportHeader {
+                cwd: Some(cwd),
+                ..Default::default()
+            },
+            entries: vec![ExportEntry::UserMessage {
+                text: format!("read {} and /etc/passwd", sibling.display()),
+            }],
+            ..Default::default()
+        };
+
+        export.redact();
+
+        assert_eq!(
+            export.entries,
+            vec![ExportEntry::UserMessage {
+                text: format!("read {} and <redacted path>", sibling.display()),
+            }]
+        );
+    }
+}
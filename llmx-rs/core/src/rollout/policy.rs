@@ -74,6 +74,11 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
         | EventMsg::PlanUpdate(_)
+        | EventMsg::PlanProposed(_)
+        | EventMsg::ListCheckpointsResponse(_)
+        | EventMsg::ListApprovalRulesResponse(_)
+        | EventMsg::CheckpointDiffResponse(_)
+        | EventMsg::CheckpointRestored(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
         | EventMsg::DeprecationNotice(_)
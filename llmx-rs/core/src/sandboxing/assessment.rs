@@ -43,6 +43,7 @@ pub(crate) async fn assess_command(
     provider: ModelProviderInfo,
     auth_manager: Arc<AuthManager>,
     parent_otel: &OtelEventManager,
+    stored_provider_key: Option<String>,
     conversation_id: ConversationId,
     session_source: SessionSource,
     call_id: &str,
@@ -134,7 +135,8 @@ pub(crate) async fn assess_command(
         config.model_reasoning_summary,
         conversation_id,
         session_source,
-    );
+    )
+    .with_stored_provider_key(stored_provider_key);
 
     let start = Instant::now();
     let assessment_result = timeout(SANDBOX_ASSESSMENT_TIMEOUT, async move {
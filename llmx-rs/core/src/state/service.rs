@@ -19,4 +19,6 @@ pub(crate) struct SessionServices {
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) otel_event_manager: OtelEventManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
+    /// Secret saved by `llmx login --provider`, resolved once at startup.
+    pub(crate) stored_provider_key: Option<String>,
 }
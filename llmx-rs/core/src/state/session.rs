@@ -1,9 +1,11 @@
 //! Session-wide mutable state.
 
 use llmx_protocol::models::ResponseItem;
+use llmx_protocol::plan_tool::UpdatePlanArgs;
 
 use crate::context_manager::ContextManager;
 use crate::llmx::SessionConfiguration;
+use crate::protocol::ContextEstimate;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -13,6 +15,15 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) latest_context_estimate: Option<ContextEstimate>,
+    /// Set when a proactive compaction left the estimated prompt above the
+    /// auto-compact limit. Compacting again would only summarize the summary,
+    /// so further attempts wait until the estimate drops below the limit.
+    pub(crate) proactive_compaction_suspended: bool,
+    /// Latest `update_plan` call made during the running plan-mode task.
+    pub(crate) proposed_plan: Option<UpdatePlanArgs>,
+    /// Rendered plan awaiting `Op::ResolvePlan`.
+    pub(crate) pending_plan: Option<String>,
 }
 
 impl SessionState {
@@ -22,6 +33,10 @@ impl SessionState {
             session_configuration,
             history: ContextManager::new(),
             latest_rate_limits: None,
+            latest_context_estimate: None,
+            proactive_compaction_suspended: false,
+            proposed_plan: None,
+            pending_plan: None,
         }
     }
 
@@ -59,6 +74,10 @@ impl SessionState {
         self.latest_rate_limits = Some(snapshot);
     }
 
+    pub(crate) fn set_context_estimate(&mut self, estimate: ContextEstimate) {
+        self.latest_context_estimate = Some(estimate);
+    }
+
     pub(crate) fn token_info_and_rate_limits(
         &self,
     ) -> (Option<TokenUsageInfo>, Option<RateLimitSnapshot>) {
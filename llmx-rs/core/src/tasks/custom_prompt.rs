@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use llmx_async_utils::OrCancelExt;
+use llmx_protocol::custom_prompts::PromptArguments;
+use llmx_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+use crate::custom_prompts::render_prompt_text;
+use crate::llmx::TurnContext;
+use crate::llmx::run_task;
+use crate::state::TaskKind;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+
+/// Regular turn started by a custom prompt. The prompt body is expanded
+/// inside the turn, so its command blocks can ask for approval, and is sent
+/// ahead of the other input.
+pub(crate) struct CustomPromptTask {
+    content: String,
+    arguments: PromptArguments,
+}
+
+impl CustomPromptTask {
+    pub(crate) fn new(content: String, arguments: PromptArguments) -> Self {
+        Self { content, arguments }
+    }
+}
+
+#[async_trait]
+impl SessionTask for CustomPromptTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        let text = render_prompt_text(&self.content, &self.arguments, &sess, &ctx)
+            .or_cancel(&cancellation_token)
+            .await
+            .ok()?;
+
+        let mut items = Vec::with_capacity(input.len() + 1);
+        if !text.is_empty() {
+            items.push(UserInput::Text { text });
+        }
+        items.extend(input);
+        run_task(sess, ctx, items, cancellation_token).await
+    }
+}
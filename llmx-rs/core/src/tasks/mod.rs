@@ -1,6 +1,8 @@
 mod compact;
+mod custom_prompt;
 mod ghost_snapshot;
 mod regular;
+mod restore_checkpoint;
 mod review;
 mod undo;
 mod user_shell;
@@ -29,8 +31,10 @@ use crate::state::TaskKind;
 use llmx_protocol::user_input::UserInput;
 
 pub(crate) use compact::CompactTask;
+pub(crate) use custom_prompt::CustomPromptTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
+pub(crate) use restore_checkpoint::RestoreCheckpointTask;
 pub(crate) use review::ReviewTask;
 pub(crate) use undo::UndoTask;
 pub(crate) use user_shell::UserShellCommandTask;
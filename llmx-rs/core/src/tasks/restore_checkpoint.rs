@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::checkpoints::collect_checkpoints;
+use crate::checkpoints::find_checkpoint;
+use crate::llmx::TurnContext;
+use crate::protocol::CheckpointRestoredEvent;
+use crate::protocol::EventMsg;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use crate::user_instructions::DeveloperInstructions;
+use async_trait::async_trait;
+use llmx_git::restore_ghost_commit;
+use llmx_git::restore_paths_from_commit;
+use llmx_protocol::models::ResponseItem;
+use llmx_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing::warn;
+
+/// Restores the working tree, or only selected files, to a checkpoint.
+pub(crate) struct RestoreCheckpointTask {
+    id: String,
+    paths: Vec<PathBuf>,
+}
+
+impl RestoreCheckpointTask {
+    pub(crate) fn new(id: String, paths: Vec<PathBuf>) -> Self {
+        Self { id, paths }
+    }
+}
+
+#[async_trait]
+impl SessionTask for RestoreCheckpointTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        let mut completed = CheckpointRestoredEvent {
+            id: self.id.clone(),
+            paths: self.paths.clone(),
+            success: false,
+            message: None,
+        };
+        if cancellation_token.is_cancelled() {
+            completed.message = Some("Restore cancelled.".to_string());
+            sess.send_event(ctx.as_ref(), EventMsg::CheckpointRestored(completed))
+                .await;
+            return None;
+        }
+
+        let items = sess.clone_history().await.get_history();
+        let entries = collect_checkpoints(&items);
+        let Some(entry) = find_checkpoint(&entries, &self.id) else {
+            completed.message = Some(format!("No checkpoint matches {}.", self.id));
+            sess.send_event(ctx.as_ref(), EventMsg::CheckpointRestored(completed))
+                .await;
+            return None;
+        };
+
+        let ghost_commit = entry.ghost_commit.clone();
+        let commit_id = ghost_commit.id().to_string();
+        completed.id = commit_id.clone();
+        let short_id: String = commit_id.chars().take(7).collect();
+        let repo_path = ctx.cwd.clone();
+        let paths = self.paths.clone();
+        let restore_result = tokio::task::spawn_blocking(move || {
+            if paths.is_empty() {
+                restore_ghost_commit(&repo_path, &ghost_commit)
+            } else {
+                restore_paths_from_commit(&repo_path, &ghost_commit, &paths)
+            }
+        })
+        .await;
+
+        match restore_result {
+            Ok(Ok(())) => {
+                info!(commit_id = commit_id, "restored checkpoint");
+                let scope = if self.paths.is_empty() {
+                    "the working tree".to_string()
+                } else {
+                    self.paths
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                // Tell the model its earlier edits may be gone so it re-reads
+                // files instead of trusting the history.
+                let note: ResponseItem = DeveloperInstructions::new(format!(
+                    "The user restored {scope} to checkpoint {short_id}, taken before the turn for: {}. Re-read affected files before editing them.",
+                    entry.prompt.as_deref().unwrap_or("(unknown prompt)")
+                ))
+                .into();
+                sess.record_conversation_items(ctx.as_ref(), &[note]).await;
+                completed.success = true;
+                completed.message = Some(format!("Restored {scope} to checkpoint {short_id}."));
+            }
+            Ok(Err(err)) => {
+                let message = format!("Failed to restore checkpoint {short_id}: {err}");
+                warn!("{message}");
+                completed.message = Some(message);
+            }
+            Err(err) => {
+                let message = format!("Failed to restore checkpoint {short_id}: {err}");
+                warn!("{message}");
+                completed.message = Some(message);
+            }
+        }
+
+        sess.send_event(ctx.as_ref(), EventMsg::CheckpointRestored(completed))
+            .await;
+        None
+    }
+}
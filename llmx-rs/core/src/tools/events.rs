@@ -14,6 +14,8 @@ use crate::protocol::PatchApplyEndEvent;
 use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
+use crate::user_notification::UserNotification;
+use crate::user_notification::duration_ms;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
@@ -90,7 +92,6 @@ pub(crate) enum ToolEmitter {
         command: String,
         cwd: PathBuf,
         // True for `exec_command` and false for `write_stdin`.
-        #[allow(dead_code)]
         is_startup_command: bool,
     },
 }
@@ -120,6 +121,7 @@ impl ToolEmitter {
     }
 
     pub async fn emit(&self, ctx: ToolEventCtx<'_>, stage: ToolEventStage) {
+        let finished = self.finished_command(&stage);
         match (self, stage) {
             (
                 Self::Shell {
@@ -263,6 +265,46 @@ impl ToolEmitter {
                 .await;
             }
         }
+        if let Some((command, exit_code, duration)) = finished {
+            ctx.session
+                .notifier()
+                .notify(&UserNotification::CommandFinished {
+                    thread_id: ctx.session.conversation_id.to_string(),
+                    turn_id: ctx.turn.sub_id.clone(),
+                    cwd: ctx.turn.cwd.display().to_string(),
+                    command,
+                    exit_code,
+                    duration_ms: duration_ms(duration),
+                });
+        }
+    }
+
+    /// The command line, exit code and duration of an agent command that
+    /// ran to completion at `stage`. Commands the user ran with `!` and
+    /// `write_stdin` polls are not reported.
+    fn finished_command(&self, stage: &ToolEventStage) -> Option<(String, i32, Duration)> {
+        let output = match stage {
+            ToolEventStage::Success(output)
+            | ToolEventStage::Failure(ToolEventFailure::Output(output)) => output,
+            ToolEventStage::Begin | ToolEventStage::Failure(ToolEventFailure::Message(_)) => {
+                return None;
+            }
+        };
+        let command = match self {
+            Self::Shell {
+                command,
+                is_user_shell_command: false,
+                ..
+            } => shlex::try_join(command.iter().map(String::as_str))
+                .unwrap_or_else(|_| command.join(" ")),
+            Self::UnifiedExec {
+                command,
+                is_startup_command: true,
+                ..
+            } => command.clone(),
+            Self::Shell { .. } | Self::UnifiedExec { .. } | Self::ApplyPatch { .. } => return None,
+        };
+        Some((command, output.exit_code, output.duration))
     }
 
     pub async fn begin(&self, ctx: ToolEventCtx<'_>) {
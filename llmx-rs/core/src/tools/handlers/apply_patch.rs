@@ -125,6 +125,7 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        let content = apply.with_partial_approval_note(content);
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
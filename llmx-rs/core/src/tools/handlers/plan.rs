@@ -104,6 +104,9 @@ pub(crate) async fn handle_update_plan(
     _call_id: String,
 ) -> Result<String, FunctionCallError> {
     let args = parse_update_plan_arguments(&arguments)?;
+    if turn_context.plan_mode {
+        session.record_proposed_plan(args.clone()).await;
+    }
     session
         .send_event(turn_context, EventMsg::PlanUpdate(args))
         .await;
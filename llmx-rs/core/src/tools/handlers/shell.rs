@@ -181,6 +181,7 @@ impl ShellHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        let content = apply.with_partial_approval_note(content);
                         return Ok(ToolOutput::Function {
                             content,
                             content_items: None,
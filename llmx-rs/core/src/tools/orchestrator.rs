@@ -2,9 +2,11 @@
 Module: orchestrator
 
 Central place for approvals + sandbox selection + retry semantics. Drives a
-simple sequence for any ToolRuntime: approval → select sandbox → attempt →
-retry without sandbox on denial (no re‑approval thanks to caching).
+simple sequence for any ToolRuntime: approval rules → approval → select
+sandbox → attempt → retry without sandbox on denial (no re‑approval thanks to
+caching).
 */
+use crate::approval_rules::RuleVerdict;
 use crate::error::LlmxErr;
 use crate::error::SandboxErr;
 use crate::error::get_error_message_ui;
@@ -48,9 +50,39 @@ impl ToolOrchestrator {
         let otel_user = llmx_otel::otel_event_manager::ToolDecisionSource::User;
         let otel_cfg = llmx_otel::otel_event_manager::ToolDecisionSource::Config;
 
+        // 0) Persistent approval rules: deny rules reject outright and allow
+        // rules stand in for the user's initial approval of a sandboxed run.
+        // They never cover a request for escalated permissions, nor the
+        // retry without a sandbox.
+        let verdict = match req.sandbox_retry_data() {
+            Some(metadata) => tool_ctx
+                .session
+                .services
+                .tool_approvals
+                .lock()
+                .await
+                .rules()
+                .check_command(&metadata.command),
+            None => None,
+        };
+        let allowed_by_rule = match verdict {
+            Some(RuleVerdict::Deny(pattern)) => {
+                otel.tool_decision(otel_tn, otel_ci, ReviewDecision::Denied, otel_cfg);
+                return Err(ToolError::Rejected(format!(
+                    "rejected by approval rule `{pattern}`"
+                )));
+            }
+            Some(RuleVerdict::Allow(pattern)) if !tool.wants_escalated_first_attempt(req) => {
+                tracing::debug!("{otel_tn} call {otel_ci} allowed by approval rule `{pattern}`");
+                true
+            }
+            Some(RuleVerdict::Allow(_)) => false,
+            None => false,
+        };
+
         // 1) Approval
-        let needs_initial_approval =
-            tool.wants_initial_approval(req, approval_policy, &turn_ctx.sandbox_policy);
+        let needs_initial_approval = !allowed_by_rule
+            && tool.wants_initial_approval(req, approval_policy, &turn_ctx.sandbox_policy);
         let mut already_approved = false;
 
         if needs_initial_approval {
@@ -72,13 +104,18 @@ impl ToolOrchestrator {
             };
             let decision = tool.start_approval_async(req, approval_ctx).await;
 
-            otel.tool_decision(otel_tn, otel_ci, decision, otel_user.clone());
+            otel.tool_decision(otel_tn, otel_ci, decision.clone(), otel_user.clone());
 
             match decision {
-                ReviewDecision::Denied | ReviewDecision::Abort => {
+                ReviewDecision::Denied
+                | ReviewDecision::DeniedAlways { .. }
+                | ReviewDecision::Abort
+                | ReviewDecision::ApprovedPartial { .. } => {
                     return Err(ToolError::Rejected("rejected by user".to_string()));
                 }
-                ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {}
+                ReviewDecision::Approved
+                | ReviewDecision::ApprovedForSession
+                | ReviewDecision::ApprovedAlways { .. } => {}
             }
             already_approved = true;
         } else {
@@ -153,13 +190,18 @@ impl ToolOrchestrator {
                     };
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
-                    otel.tool_decision(otel_tn, otel_ci, decision, otel_user);
+                    otel.tool_decision(otel_tn, otel_ci, decision.clone(), otel_user);
 
                     match decision {
-                        ReviewDecision::Denied | ReviewDecision::Abort => {
+                        ReviewDecision::Denied
+                        | ReviewDecision::DeniedAlways { .. }
+                        | ReviewDecision::Abort
+                        | ReviewDecision::ApprovedPartial { .. } => {
                             return Err(ToolError::Rejected("rejected by user".to_string()));
                         }
-                        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {}
+                        ReviewDecision::Approved
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedAlways { .. } => {}
                     }
                 }
 
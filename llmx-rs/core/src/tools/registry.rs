@@ -11,6 +11,7 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::spec::tool_is_allowed;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ToolKind {
@@ -64,6 +65,21 @@ impl ToolRegistry {
         let payload_for_response = invocation.payload.clone();
         let log_payload = payload_for_response.log_payload();
 
+        if let Some(allowed) = &invocation.turn.tools_config.allowed_tools
+            && !tool_is_allowed(allowed, tool_name.as_ref())
+        {
+            let message = format!("tool {tool_name} is not allowed in this turn");
+            otel.tool_result(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                false,
+                &message,
+            );
+            return Err(FunctionCallError::RespondToModel(message));
+        }
+
         let handler = match self.handler(tool_name.as_ref()) {
             Some(handler) => handler,
             None => {
@@ -175,6 +191,12 @@ impl ToolRegistryBuilder {
             .push(ConfiguredToolSpec::new(spec, supports_parallel_tool_calls));
     }
 
+    /// Drops the specs whose tool name does not satisfy `keep`, so those
+    /// tools are not offered to the model.
+    pub fn retain_specs(&mut self, keep: impl Fn(&str) -> bool) {
+        self.specs.retain(|config| keep(config.spec.name()));
+    }
+
     pub fn register_handler(&mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
         let name = name.into();
         if self
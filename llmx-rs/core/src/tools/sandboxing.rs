@@ -4,6 +4,7 @@
 //! `ApprovalCtx`, `Approvable`) together with the sandbox orchestration traits
 //! and helpers (`Sandboxable`, `ToolRuntime`, `SandboxAttempt`, etc.).
 
+use crate::approval_rules::ApprovalRules;
 use crate::error::LlmxErr;
 use crate::llmx::Session;
 use crate::llmx::TurnContext;
@@ -29,9 +30,26 @@ use serde::Serialize;
 pub(crate) struct ApprovalStore {
     // Store serialized keys for generic caching across requests.
     map: HashMap<String, ReviewDecision>,
+    // Persistent rules from `approvals.toml`, shared across sessions.
+    rules: ApprovalRules,
 }
 
 impl ApprovalStore {
+    pub fn new(rules: ApprovalRules) -> Self {
+        Self {
+            map: HashMap::new(),
+            rules,
+        }
+    }
+
+    pub fn rules(&self) -> &ApprovalRules {
+        &self.rules
+    }
+
+    pub fn rules_mut(&mut self) -> &mut ApprovalRules {
+        &mut self.rules
+    }
+
     pub fn get<K>(&self, key: &K) -> Option<ReviewDecision>
     where
         K: Serialize,
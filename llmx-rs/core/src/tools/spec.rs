@@ -29,6 +29,9 @@ pub(crate) struct ToolsConfig {
     pub web_search_request: bool,
     pub include_view_image_tool: bool,
     pub experimental_supported_tools: Vec<String>,
+    /// When set, only tools matching one of these names are offered to the
+    /// model. A trailing `*` matches any suffix.
+    pub allowed_tools: Option<Vec<String>>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -70,6 +73,7 @@ impl ToolsConfig {
             web_search_request: include_web_search_request,
             include_view_image_tool,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
+            allowed_tools: None,
         }
     }
 }
@@ -1002,9 +1006,24 @@ pub(crate) fn build_specs(
         }
     }
 
+    if let Some(allowed) = &config.allowed_tools {
+        builder.retain_specs(|name| tool_is_allowed(allowed, name));
+    }
+
     builder
 }
 
+/// Whether `name` matches one of the `allowed` tool names; a trailing `*`
+/// matches any suffix.
+pub(crate) fn tool_is_allowed(allowed: &[String], name: &str) -> bool {
+    allowed
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => pattern == name,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::client_common::tools::FreeformTool;
@@ -1189,6 +1208,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn allowed_tools_limits_offered_specs() {
+        let model_family =
+            find_family_for_model("gpt-5-llmx").expect("gpt-5-llmx should be a valid model family");
+        let mut config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &Features::with_defaults(),
+        });
+        config.allowed_tools = Some(vec!["shell".to_string(), "list_mcp_*".to_string()]);
+        let (tools, _) = build_specs(&config, Some(HashMap::new())).build();
+        let tool_names = tools.iter().map(|t| t.spec.name()).collect::<Vec<_>>();
+        assert_eq!(
+            tool_names,
+            vec!["shell", "list_mcp_resources", "list_mcp_resource_templates"]
+        );
+    }
+
     #[test]
     fn test_build_specs_gpt5_llmx_unified_exec_web_search() {
         assert_model_tools(
@@ -1,21 +1,37 @@
+use std::time::Duration;
+
 use serde::Serialize;
 use tracing::error;
 use tracing::warn;
 
+use crate::config::types::NotificationEvent;
+
 #[derive(Debug, Default)]
 pub(crate) struct UserNotifier {
     notify_command: Option<Vec<String>>,
+    events: Vec<NotificationEvent>,
+    min_duration: Duration,
 }
 
 impl UserNotifier {
     pub(crate) fn notify(&self, notification: &UserNotification) {
         if let Some(notify_command) = &self.notify_command
             && !notify_command.is_empty()
+            && self.is_enabled(notification)
         {
             self.invoke_notify(notify_command, notification)
         }
     }
 
+    /// Whether `[notifications]` lets `notification` through: its event must
+    /// be listed and, for completions, it must have run long enough.
+    fn is_enabled(&self, notification: &UserNotification) -> bool {
+        self.events.contains(&notification.event())
+            && notification
+                .duration()
+                .is_none_or(|duration| duration >= self.min_duration)
+    }
+
     fn invoke_notify(&self, notify_command: &[String], notification: &UserNotification) {
         let Ok(json) = serde_json::to_string(&notification) else {
             error!("failed to serialise notification payload");
@@ -34,9 +50,15 @@ impl UserNotifier {
         }
     }
 
-    pub(crate) fn new(notify: Option<Vec<String>>) -> Self {
+    pub(crate) fn new(
+        notify: Option<Vec<String>>,
+        events: Vec<NotificationEvent>,
+        min_duration: Duration,
+    ) -> Self {
         Self {
             notify_command: notify,
+            events,
+            min_duration,
         }
     }
 }
@@ -58,9 +80,97 @@ pub(crate) enum UserNotification {
 
         /// The last message sent by the assistant in the turn.
         last_assistant_message: Option<String>,
+
+        /// How long the turn took.
+        duration_ms: u64,
+    },
+
+    /// The agent is waiting for the user to approve a command or patch.
+    #[serde(rename_all = "kebab-case")]
+    ApprovalRequested {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        kind: ApprovalKind,
+
+        /// The command to run, for `exec` approvals.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        command: Option<String>,
+
+        /// Files the patch touches, for `patch` approvals.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        files: Vec<String>,
+
+        reason: Option<String>,
+    },
+
+    /// A turn ended with an error.
+    #[serde(rename_all = "kebab-case")]
+    TurnFailed {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        error: String,
+    },
+
+    /// A usage limit, quota or rate limit stopped the turn.
+    #[serde(rename_all = "kebab-case")]
+    RateLimitReached {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        error: String,
+    },
+
+    /// A command run by the agent finished.
+    #[serde(rename_all = "kebab-case")]
+    CommandFinished {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        command: String,
+        exit_code: i32,
+        duration_ms: u64,
     },
 }
 
+/// `duration` in whole milliseconds, as reported in payloads.
+pub(crate) fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ApprovalKind {
+    Exec,
+    Patch,
+}
+
+impl UserNotification {
+    fn event(&self) -> NotificationEvent {
+        match self {
+            UserNotification::AgentTurnComplete { .. } => NotificationEvent::AgentTurnComplete,
+            UserNotification::ApprovalRequested { .. } => NotificationEvent::ApprovalRequested,
+            UserNotification::TurnFailed { .. } => NotificationEvent::TurnFailed,
+            UserNotification::RateLimitReached { .. } => NotificationEvent::RateLimitReached,
+            UserNotification::CommandFinished { .. } => NotificationEvent::CommandFinished,
+        }
+    }
+
+    /// How long the reported turn or command ran, for completions.
+    fn duration(&self) -> Option<Duration> {
+        match self {
+            UserNotification::AgentTurnComplete { duration_ms, .. }
+            | UserNotification::CommandFinished { duration_ms, .. } => {
+                Some(Duration::from_millis(*duration_ms))
+            }
+            UserNotification::ApprovalRequested { .. }
+            | UserNotification::TurnFailed { .. }
+            | UserNotification::RateLimitReached { .. } => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,12 +186,59 @@ mod tests {
             last_assistant_message: Some(
                 "Rename complete and verified `cargo build` succeeds.".to_string(),
             ),
+            duration_ms: 42_000,
         };
         let serialized = serde_json::to_string(&notification)?;
         assert_eq!(
             serialized,
-            r#"{"type":"agent-turn-complete","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","input-messages":["Rename `foo` to `bar` and update the callsites."],"last-assistant-message":"Rename complete and verified `cargo build` succeeds."}"#
+            r#"{"type":"agent-turn-complete","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","input-messages":["Rename `foo` to `bar` and update the callsites."],"last-assistant-message":"Rename complete and verified `cargo build` succeeds.","duration-ms":42000}"#
         );
         Ok(())
     }
+
+    #[test]
+    fn approval_request_payload() -> Result<()> {
+        let notification = UserNotification::ApprovalRequested {
+            thread_id: "b5f6c1c2-1111-2222-3333-444455556666".to_string(),
+            turn_id: "12345".to_string(),
+            cwd: "/Users/example/project".to_string(),
+            kind: ApprovalKind::Exec,
+            command: Some("cargo test -p foo".to_string()),
+            files: Vec::new(),
+            reason: None,
+        };
+        let serialized = serde_json::to_string(&notification)?;
+        assert_eq!(
+            serialized,
+            r#"{"type":"approval-requested","thread-id":"b5f6c1c2-1111-2222-3333-444455556666","turn-id":"12345","cwd":"/Users/example/project","kind":"exec","command":"cargo test -p foo","reason":null}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn filters_by_event_and_duration() {
+        let notifier = UserNotifier::new(
+            Some(vec!["notify-send".to_string()]),
+            vec![NotificationEvent::CommandFinished],
+            Duration::from_secs(30),
+        );
+        let command = |duration_ms| UserNotification::CommandFinished {
+            thread_id: "thread".to_string(),
+            turn_id: "turn".to_string(),
+            cwd: "/repo".to_string(),
+            command: "cargo build".to_string(),
+            exit_code: 0,
+            duration_ms,
+        };
+        let failed = UserNotification::TurnFailed {
+            thread_id: "thread".to_string(),
+            turn_id: "turn".to_string(),
+            cwd: "/repo".to_string(),
+            error: "stream disconnected".to_string(),
+        };
+
+        assert!(notifier.is_enabled(&command(45_000)));
+        assert!(!notifier.is_enabled(&command(2_000)));
+        assert!(!notifier.is_enabled(&failed));
+    }
 }
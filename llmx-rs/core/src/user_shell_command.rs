@@ -4,6 +4,7 @@ use llmx_protocol::models::ContentItem;
 use llmx_protocol::models::ResponseItem;
 
 use crate::exec::ExecToolCallOutput;
+use crate::shell::Shell;
 use crate::tools::format_exec_output_str;
 
 pub const USER_SHELL_COMMAND_OPEN: &str = "<user_shell_command>";
@@ -15,6 +16,32 @@ pub fn is_user_shell_command_text(text: &str) -> bool {
     lowered.starts_with(USER_SHELL_COMMAND_OPEN)
 }
 
+/// Builds the argv that runs `command` as a script under the user's default
+/// shell when known; this allows commands that use shell features (pipes,
+/// &&, redirects, etc.). We do not source rc files or otherwise reformat the
+/// script.
+pub(crate) fn user_shell_invocation(shell: &Shell, command: &str) -> Vec<String> {
+    match shell {
+        Shell::Zsh(zsh) => vec![
+            zsh.shell_path.clone(),
+            "-lc".to_string(),
+            command.to_string(),
+        ],
+        Shell::Bash(bash) => vec![
+            bash.shell_path.clone(),
+            "-lc".to_string(),
+            command.to_string(),
+        ],
+        Shell::PowerShell(ps) => vec![
+            ps.exe.clone(),
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            command.to_string(),
+        ],
+        Shell::Unknown => shlex::split(command).unwrap_or_else(|| vec![command.to_string()]),
+    }
+}
+
 fn format_duration_line(duration: Duration) -> String {
     let duration_seconds = duration.as_secs_f64();
     format!("Duration: {duration_seconds:.4} seconds")
@@ -60,6 +60,7 @@ async fn run_request(input: Vec<ResponseItem>) -> Value {
         stream_idle_timeout_ms: Some(5_000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let llmx_home = match TempDir::new() {
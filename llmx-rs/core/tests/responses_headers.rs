@@ -49,6 +49,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         stream_idle_timeout_ms: Some(5_000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let llmx_home = TempDir::new().expect("failed to create TempDir");
@@ -138,6 +139,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         stream_idle_timeout_ms: Some(5_000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let llmx_home = TempDir::new().expect("failed to create TempDir");
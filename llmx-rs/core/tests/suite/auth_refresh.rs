@@ -189,13 +189,15 @@ impl RefreshTokenTestContext {
             llmx_home.path(),
             &auth_dot_json,
             AuthCredentialsStoreMode::File,
+            None,
         )?;
 
         let endpoint = format!("{}/oauth/token", server.uri());
         let env_guard = EnvGuard::set(REFRESH_TOKEN_URL_OVERRIDE_ENV_VAR, endpoint);
 
-        let auth = LlmxAuth::from_auth_storage(llmx_home.path(), AuthCredentialsStoreMode::File)?
-            .context("auth should load from storage")?;
+        let auth =
+            LlmxAuth::from_auth_storage(llmx_home.path(), AuthCredentialsStoreMode::File, None)?
+                .context("auth should load from storage")?;
 
         Ok(Self {
             llmx_home,
@@ -206,7 +208,7 @@ impl RefreshTokenTestContext {
     }
 
     fn load_auth(&self) -> Result<AuthDotJson> {
-        load_auth_dot_json(self.llmx_home.path(), AuthCredentialsStoreMode::File)
+        load_auth_dot_json(self.llmx_home.path(), AuthCredentialsStoreMode::File, None)
             .context("load auth.json")?
             .context("auth.json should exist")
     }
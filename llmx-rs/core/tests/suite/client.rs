@@ -532,7 +532,7 @@ async fn prefers_apikey_when_config_prefers_apikey_even_with_chatgpt_tokens() {
     config.model_provider = model_provider;
 
     let auth_manager =
-        match LlmxAuth::from_auth_storage(llmx_home.path(), AuthCredentialsStoreMode::File) {
+        match LlmxAuth::from_auth_storage(llmx_home.path(), AuthCredentialsStoreMode::File, None) {
             Ok(Some(auth)) => llmx_core::AuthManager::from_auth_for_testing(auth),
             Ok(None) => panic!("No LlmxAuth found in llmx_home"),
             Err(e) => panic!("Failed to load LlmxAuth: {e}"),
@@ -714,6 +714,7 @@ async fn azure_responses_request_includes_store_and_reasoning_ids() {
         stream_idle_timeout_ms: Some(5_000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let llmx_home = TempDir::new().unwrap();
@@ -1198,6 +1199,7 @@ async fn azure_overrides_assign_properties_used_for_responses_url() {
         stream_idle_timeout_ms: None,
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     // Init session
@@ -1276,6 +1278,7 @@ async fn env_var_overrides_loaded_auth() {
         stream_idle_timeout_ms: None,
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     // Init session
@@ -70,6 +70,7 @@ async fn llmx_delegate_forwards_exec_approval_and_proceeds_on_approval() {
             review_request: ReviewRequest {
                 prompt: "Please review".to_string(),
                 user_facing_hint: "review".to_string(),
+                target: None,
             },
         })
         .await
@@ -142,6 +143,7 @@ async fn llmx_delegate_forwards_patch_approval_and_proceeds_on_decision() {
             review_request: ReviewRequest {
                 prompt: "Please review".to_string(),
                 user_facing_hint: "review".to_string(),
+                target: None,
             },
         })
         .await
@@ -193,6 +195,7 @@ async fn llmx_delegate_ignores_legacy_deltas() {
             review_request: ReviewRequest {
                 prompt: "Please review".to_string(),
                 user_facing_hint: "review".to_string(),
+                target: None,
             },
         })
         .await
@@ -81,6 +81,7 @@ async fn review_op_emits_lifecycle_and_review_output() {
         review_request: ReviewRequest {
             prompt: "Please review my changes".to_string(),
             user_facing_hint: "my changes".to_string(),
+            target: None,
         },
     })
     .await
@@ -176,6 +177,7 @@ async fn review_op_with_plain_text_emits_review_fallback() {
         review_request: ReviewRequest {
             prompt: "Plain text review".to_string(),
             user_facing_hint: "plain text review".to_string(),
+            target: None,
         },
     })
     .await
@@ -233,6 +235,7 @@ async fn review_filters_agent_message_related_events() {
         review_request: ReviewRequest {
             prompt: "Filter streaming events".to_string(),
             user_facing_hint: "Filter streaming events".to_string(),
+            target: None,
         },
     })
     .await
@@ -316,6 +319,7 @@ async fn review_does_not_emit_agent_message_on_structured_output() {
         review_request: ReviewRequest {
             prompt: "check structured".to_string(),
             user_facing_hint: "check structured".to_string(),
+            target: None,
         },
     })
     .await
@@ -368,6 +372,7 @@ async fn review_uses_custom_review_model_from_config() {
         review_request: ReviewRequest {
             prompt: "use custom model".to_string(),
             user_facing_hint: "use custom model".to_string(),
+            target: None,
         },
     })
     .await
@@ -484,6 +489,7 @@ async fn review_input_isolated_from_parent_history() {
         review_request: ReviewRequest {
             prompt: review_prompt.clone(),
             user_facing_hint: review_prompt.clone(),
+            target: None,
         },
     })
     .await
@@ -595,6 +601,7 @@ async fn review_history_does_not_leak_into_parent_session() {
         review_request: ReviewRequest {
             prompt: "Start a review".to_string(),
             user_facing_hint: "Start a review".to_string(),
+            target: None,
         },
     })
     .await
@@ -74,6 +74,7 @@ async fn continue_after_stream_error() {
         stream_idle_timeout_ms: Some(2_000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let TestLlmx { llmx, .. } = test_llmx()
@@ -82,6 +82,7 @@ async fn retries_on_early_close() {
         stream_idle_timeout_ms: Some(2000),
         max_tokens: None,
         requires_openai_auth: false,
+        oauth2: None,
     };
 
     let TestLlmx { llmx, .. } = test_llmx()
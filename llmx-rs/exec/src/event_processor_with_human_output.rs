@@ -517,6 +517,11 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::PlanProposed(_)
+            | EventMsg::ListCheckpointsResponse(_)
+            | EventMsg::ListApprovalRulesResponse(_)
+            | EventMsg::CheckpointDiffResponse(_)
+            | EventMsg::CheckpointRestored(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
             | EventMsg::EnteredReviewMode(_)
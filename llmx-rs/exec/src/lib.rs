@@ -9,6 +9,7 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+pub mod review;
 
 pub use cli::Cli;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
@@ -30,6 +31,8 @@ use llmx_ollama::DEFAULT_OSS_MODEL;
 use llmx_protocol::config_types::SandboxMode;
 use llmx_protocol::user_input::UserInput;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+pub use review::ReviewCli;
+pub use review::run_review;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
@@ -253,6 +256,7 @@ pub async fn run_main(cli: Cli, llmx_linux_sandbox_exe: Option<PathBuf>) -> anyh
         config.llmx_home.clone(),
         true,
         config.cli_auth_credentials_store_mode,
+        config.credential_helper.clone(),
     );
     let conversation_manager = ConversationManager::new(auth_manager.clone(), SessionSource::Exec);
 
@@ -0,0 +1,344 @@
+//! `llmx review`: run a code review without the TUI and report the findings
+//! as text, JSON, or SARIF. Intended for pre-push hooks and CI.
+
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use clap::ValueEnum;
+use llmx_common::CliConfigOverrides;
+use llmx_core::AuthManager;
+use llmx_core::ConversationManager;
+use llmx_core::NewConversation;
+use llmx_core::auth::enforce_login_restrictions;
+use llmx_core::config::Config;
+use llmx_core::config::ConfigOverrides;
+use llmx_core::default_client::set_default_originator;
+use llmx_core::git_info::get_git_repo_root;
+use llmx_core::protocol::AskForApproval;
+use llmx_core::protocol::ErrorEvent;
+use llmx_core::protocol::EventMsg;
+use llmx_core::protocol::Op;
+use llmx_core::protocol::ReviewFinding;
+use llmx_core::protocol::ReviewOutputEvent;
+use llmx_core::protocol::ReviewTarget;
+use llmx_core::protocol::SessionSource;
+use llmx_core::review_prompts::review_request;
+use llmx_protocol::config_types::SandboxMode;
+use serde_json::Value;
+use serde_json::json;
+
+/// Exit status used when findings exceed the `--fail-on-priority` threshold,
+/// distinct from the generic failure status 1.
+const THRESHOLD_EXIT_CODE: i32 = 2;
+
+#[derive(Parser, Debug)]
+pub struct ReviewCli {
+    /// Review changes on the current branch since it diverged from BRANCH.
+    #[arg(long = "base", value_name = "BRANCH", conflicts_with_all = ["commit", "uncommitted"])]
+    pub base: Option<String>,
+
+    /// Review the changes introduced by a single commit.
+    #[arg(long = "commit", value_name = "SHA", conflicts_with = "uncommitted")]
+    pub commit: Option<String>,
+
+    /// Review staged, unstaged, and untracked changes (the default).
+    #[arg(long = "uncommitted", default_value_t = false)]
+    pub uncommitted: bool,
+
+    /// Custom review instructions instead of a preset. Use `-` to read them
+    /// from stdin.
+    #[arg(
+        value_name = "INSTRUCTIONS",
+        conflicts_with_all = ["base", "commit", "uncommitted"],
+        value_hint = clap::ValueHint::Other
+    )]
+    pub instructions: Option<String>,
+
+    /// Report format written to stdout (or `--output`).
+    #[arg(long = "format", value_enum, default_value_t = ReviewFormat::Text)]
+    pub format: ReviewFormat,
+
+    /// Write the report to FILE instead of stdout.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Exit with status 2 when more than `--max-findings` findings have
+    /// priority P or higher (0 is the most severe).
+    #[arg(long = "fail-on-priority", value_name = "P")]
+    pub fail_on_priority: Option<i32>,
+
+    /// Number of findings at or above `--fail-on-priority` to tolerate.
+    #[arg(
+        long = "max-findings",
+        value_name = "N",
+        default_value_t = 0,
+        requires = "fail_on_priority"
+    )]
+    pub max_findings: usize,
+
+    /// Model used for the review.
+    #[arg(long, short = 'm')]
+    pub model: Option<String>,
+
+    /// Configuration profile from config.toml to specify default options.
+    #[arg(long = "profile", short = 'p')]
+    pub config_profile: Option<String>,
+
+    /// Tell the reviewer to use the specified directory as its working root.
+    #[clap(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Allow running the review outside a Git repository.
+    #[arg(long = "skip-git-repo-check", default_value_t = false)]
+    pub skip_git_repo_check: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+}
+
+pub async fn run_review(
+    cli: ReviewCli,
+    llmx_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    if let Err(err) = set_default_originator("llmx_exec".to_string()) {
+        tracing::warn!(?err, "Failed to set llmx exec originator override {err:?}");
+    }
+
+    let target = review_target(&cli)?;
+    let ReviewCli {
+        format,
+        output,
+        fail_on_priority,
+        max_findings,
+        model,
+        config_profile,
+        cwd,
+        skip_git_repo_check,
+        config_overrides,
+        ..
+    } = cli;
+
+    let overrides = ConfigOverrides {
+        review_model: model,
+        config_profile,
+        // The reviewer only reads the repository; never prompt.
+        approval_policy: Some(AskForApproval::Never),
+        sandbox_mode: Some(SandboxMode::ReadOnly),
+        cwd: cwd.map(|p| p.canonicalize().unwrap_or(p)),
+        llmx_linux_sandbox_exe,
+        ..Default::default()
+    };
+    let cli_kv_overrides = config_overrides
+        .parse_overrides()
+        .map_err(|e| anyhow::anyhow!("Error parsing -c overrides: {e}"))?;
+    let config = Config::load_with_cli_overrides(cli_kv_overrides, overrides).await?;
+    enforce_login_restrictions(&config).await?;
+
+    if !skip_git_repo_check && get_git_repo_root(&config.cwd).is_none() {
+        bail!("Not inside a git repository and --skip-git-repo-check was not specified.");
+    }
+
+    let auth_manager = AuthManager::shared(
+        config.llmx_home.clone(),
+        true,
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.clone(),
+    );
+    let conversation_manager = ConversationManager::new(auth_manager, SessionSource::Exec);
+    let NewConversation { conversation, .. } = conversation_manager
+        .new_conversation(config.clone())
+        .await?;
+
+    let request = review_request(target);
+    eprintln!("Reviewing {}...", request.user_facing_hint);
+    conversation
+        .submit(Op::Review {
+            review_request: request,
+        })
+        .await?;
+
+    let mut review_output = None;
+    loop {
+        let event = conversation.next_event().await?;
+        match event.msg {
+            EventMsg::Error(ErrorEvent { message }) => eprintln!("ERROR: {message}"),
+            EventMsg::ExitedReviewMode(ev) => {
+                review_output = ev.review_output;
+                conversation.submit(Op::Shutdown).await?;
+            }
+            EventMsg::ShutdownComplete => break,
+            _ => {}
+        }
+    }
+
+    let Some(review_output) = review_output else {
+        bail!("The review finished without producing findings.");
+    };
+
+    let report = match format {
+        ReviewFormat::Text => render_text_report(&review_output),
+        ReviewFormat::Json => serde_json::to_string_pretty(&review_output)? + "\n",
+        ReviewFormat::Sarif => {
+            serde_json::to_string_pretty(&render_sarif_report(&review_output, &config.cwd))? + "\n"
+        }
+    };
+    match output {
+        Some(path) => std::fs::write(&path, report)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => std::io::stdout().write_all(report.as_bytes())?,
+    }
+
+    if let Some(priority) = fail_on_priority {
+        let blocking = count_findings_at_or_above(&review_output.findings, priority);
+        if blocking > max_findings {
+            eprintln!(
+                "{blocking} finding(s) at priority P{priority} or higher (allowed: {max_findings})."
+            );
+            std::process::exit(THRESHOLD_EXIT_CODE);
+        }
+    }
+
+    Ok(())
+}
+
+fn review_target(cli: &ReviewCli) -> anyhow::Result<ReviewTarget> {
+    if let Some(branch) = &cli.base {
+        return Ok(ReviewTarget::BaseBranch {
+            branch: branch.clone(),
+        });
+    }
+    if let Some(sha) = &cli.commit {
+        return Ok(ReviewTarget::Commit {
+            sha: sha.clone(),
+            title: None,
+        });
+    }
+    let instructions = match cli.instructions.as_deref() {
+        None => return Ok(ReviewTarget::UncommittedChanges),
+        Some("-") => {
+            if std::io::stdin().is_terminal() {
+                bail!("`-` was given but stdin is a terminal; pipe the instructions in.");
+            }
+            let mut buffer = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buffer)
+                .context("failed to read review instructions from stdin")?;
+            buffer
+        }
+        Some(text) => text.to_string(),
+    };
+    if instructions.trim().is_empty() {
+        bail!("No review instructions provided.");
+    }
+    Ok(ReviewTarget::Custom { instructions })
+}
+
+/// Number of findings whose priority is `priority` or more severe (lower).
+pub fn count_findings_at_or_above(findings: &[ReviewFinding], priority: i32) -> usize {
+    findings
+        .iter()
+        .filter(|finding| finding.priority <= priority)
+        .count()
+}
+
+pub fn render_text_report(output: &ReviewOutputEvent) -> String {
+    let mut lines = Vec::new();
+    if output.findings.is_empty() {
+        lines.push("No findings.".to_string());
+    }
+    for finding in &output.findings {
+        let location = &finding.code_location;
+        lines.push(format!(
+            "[P{}] {} — {}:{}-{}",
+            finding.priority,
+            finding.title,
+            location.absolute_file_path.display(),
+            location.line_range.start,
+            location.line_range.end
+        ));
+        for body_line in finding.body.lines() {
+            lines.push(format!("  {body_line}"));
+        }
+        lines.push(String::new());
+    }
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        if !output.findings.is_empty() {
+            lines.push("Summary:".to_string());
+        }
+        lines.push(explanation.to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Renders findings as a SARIF 2.1.0 log. Paths inside `cwd` are reported
+/// relative to it so code-scanning tools can map them to the repository.
+pub fn render_sarif_report(output: &ReviewOutputEvent, cwd: &Path) -> Value {
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let location = &finding.code_location;
+            let path = &location.absolute_file_path;
+            let uri = path
+                .strip_prefix(cwd)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let start_line = location.line_range.start.max(1);
+            let end_line = location.line_range.end.max(start_line);
+            json!({
+                "ruleId": format!("P{}", finding.priority),
+                "level": sarif_level(finding.priority),
+                "message": { "text": format!("{}\n\n{}", finding.title, finding.body) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": { "startLine": start_line, "endLine": end_line },
+                    },
+                }],
+                "properties": {
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "llmx-review",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
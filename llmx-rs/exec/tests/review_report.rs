@@ -0,0 +1,87 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use llmx_core::protocol::ReviewCodeLocation;
+use llmx_core::protocol::ReviewFinding;
+use llmx_core::protocol::ReviewLineRange;
+use llmx_core::protocol::ReviewOutputEvent;
+use llmx_exec::review::count_findings_at_or_above;
+use llmx_exec::review::render_sarif_report;
+use llmx_exec::review::render_text_report;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn finding(title: &str, priority: i32, path: &str, start: u32, end: u32) -> ReviewFinding {
+    ReviewFinding {
+        title: title.to_string(),
+        body: format!("{title} body"),
+        confidence_score: 0.5,
+        priority,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: PathBuf::from(path),
+            line_range: ReviewLineRange { start, end },
+        },
+    }
+}
+
+fn sample_output() -> ReviewOutputEvent {
+    ReviewOutputEvent {
+        findings: vec![
+            finding("Null deref", 0, "/repo/src/lib.rs", 10, 12),
+            finding("Naming", 3, "/repo/src/main.rs", 4, 4),
+        ],
+        overall_correctness: "patch is incorrect".to_string(),
+        overall_explanation: "One crash, one nit.".to_string(),
+        overall_confidence_score: 0.8,
+    }
+}
+
+#[test]
+fn sarif_report_uses_relative_paths_and_priority_levels() {
+    let sarif = render_sarif_report(&sample_output(), Path::new("/repo"));
+    let results = &sarif["runs"][0]["results"];
+
+    assert_eq!(sarif["version"], json!("2.1.0"));
+    assert_eq!(results.as_array().map(Vec::len), Some(2));
+    assert_eq!(
+        results[0],
+        json!({
+            "ruleId": "P0",
+            "level": "error",
+            "message": { "text": "Null deref\n\nNull deref body" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/lib.rs" },
+                    "region": { "startLine": 10, "endLine": 12 },
+                },
+            }],
+            "properties": { "priority": 0, "confidence": 0.5 },
+        })
+    );
+    assert_eq!(results[1]["level"], json!("note"));
+}
+
+#[test]
+fn threshold_counts_findings_at_or_above_priority() {
+    let output = sample_output();
+
+    assert_eq!(count_findings_at_or_above(&output.findings, 0), 1);
+    assert_eq!(count_findings_at_or_above(&output.findings, 1), 1);
+    assert_eq!(count_findings_at_or_above(&output.findings, 3), 2);
+}
+
+#[test]
+fn text_report_lists_findings_then_summary() {
+    assert_eq!(
+        render_text_report(&sample_output()),
+        "[P0] Null deref — /repo/src/lib.rs:10-12
+  Null deref body
+
+[P3] Naming — /repo/src/main.rs:4-4
+  Naming body
+
+Summary:
+One crash, one nit.
+"
+    );
+}
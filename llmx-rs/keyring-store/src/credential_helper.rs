@@ -0,0 +1,201 @@
+//! External credential helper backend, modelled on git credential helpers.
+//!
+//! The configured command is run with `get`, `store`, or `erase` appended.
+//! The request is written to its stdin as `key=value` lines ending with a
+//! blank line:
+//!
+//! ```text
+//! service=LLMX Auth
+//! account=cli|0123abcd
+//! secret=...            (store only)
+//! ```
+//!
+//! For `get`, the helper prints a `secret=...` line, or nothing when the entry
+//! does not exist. For `erase`, printing `erased=false` reports that nothing
+//! was removed. Any non-zero exit status is treated as an error.
+
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+use tracing::trace;
+
+use crate::CredentialStoreError;
+use crate::KeyringStore;
+
+#[derive(Debug, Clone)]
+pub struct CredentialHelperKeyringStore {
+    command: Vec<String>,
+}
+
+impl CredentialHelperKeyringStore {
+    /// Store backed by the configured `credential_helper` command. Every
+    /// operation fails when `command` is `None` or empty.
+    pub fn new(command: Option<Vec<String>>) -> Self {
+        Self {
+            command: command.unwrap_or_default(),
+        }
+    }
+
+    fn run(
+        &self,
+        action: &str,
+        service: &str,
+        account: &str,
+        secret: Option<&str>,
+    ) -> Result<String, CredentialStoreError> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(CredentialStoreError::backend(
+                "credential store is set to credential-helper but `credential_helper` is not configured",
+            ));
+        };
+        for (key, value) in [("service", service), ("account", account)]
+            .into_iter()
+            .chain(secret.map(|secret| ("secret", secret)))
+        {
+            if value.contains('\n') {
+                return Err(CredentialStoreError::backend(format!(
+                    "credential helper {key} must not contain newlines"
+                )));
+            }
+        }
+
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(action)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                CredentialStoreError::backend(format!(
+                    "failed to run credential helper `{program}`: {err}"
+                ))
+            })?;
+
+        let mut request = format!("service={service}\naccount={account}\n");
+        if let Some(secret) = secret {
+            request.push_str(&format!("secret={secret}\n"));
+        }
+        request.push('\n');
+        if let Some(mut stdin) = child.stdin.take() {
+            // A helper may exit without reading its input; the exit status
+            // below is what decides success.
+            let _ = stdin.write_all(request.as_bytes());
+        }
+
+        let output = child.wait_with_output().map_err(|err| {
+            CredentialStoreError::backend(format!(
+                "failed to wait for credential helper `{program}`: {err}"
+            ))
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(CredentialStoreError::backend(format!(
+                "credential helper `{program} {action}` failed ({}): {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+        String::from_utf8(output.stdout).map_err(|_| {
+            CredentialStoreError::backend(format!(
+                "credential helper `{program} {action}` printed invalid UTF-8"
+            ))
+        })
+    }
+}
+
+fn response_value<'a>(stdout: &'a str, key: &str) -> Option<&'a str> {
+    stdout.lines().find_map(|line| {
+        line.split_once('=')
+            .filter(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+    })
+}
+
+impl KeyringStore for CredentialHelperKeyringStore {
+    fn load(&self, service: &str, account: &str) -> Result<Option<String>, CredentialStoreError> {
+        trace!("credential_helper.get, service={service}, account={account}");
+        let stdout = self.run("get", service, account, None)?;
+        Ok(response_value(&stdout, "secret").map(str::to_string))
+    }
+
+    fn save(&self, service: &str, account: &str, value: &str) -> Result<(), CredentialStoreError> {
+        trace!(
+            "credential_helper.store, service={service}, account={account}, value_len={}",
+            value.len()
+        );
+        self.run("store", service, account, Some(value)).map(|_| ())
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<bool, CredentialStoreError> {
+        trace!("credential_helper.erase, service={service}, account={account}");
+        let stdout = self.run("erase", service, account, None)?;
+        Ok(response_value(&stdout, "erased") != Some("false"))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    /// Helper that keeps one secret per account in files under `dir`.
+    fn file_helper(dir: &std::path::Path) -> CredentialHelperKeyringStore {
+        let script = format!(
+            r#"dir='{}'
+while IFS='=' read -r key value; do
+  [ -z "$key" ] && break
+  eval "$key=\$value"
+done
+file="$dir/$account"
+case "$1" in
+  get) [ -f "$file" ] && printf 'secret=%s\n' "$(cat "$file")" ;;
+  store) printf '%s' "$secret" > "$file" ;;
+  erase) if [ -f "$file" ]; then rm "$file"; else echo erased=false; fi ;;
+esac
+exit 0"#,
+            dir.display()
+        );
+        CredentialHelperKeyringStore::new(Some(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            script,
+            "helper".to_string(),
+        ]))
+    }
+
+    #[test]
+    fn round_trips_through_helper() {
+        let dir = tempdir().expect("tempdir");
+        let store = file_helper(dir.path());
+
+        assert_eq!(store.load("svc", "acct").expect("get"), None);
+        store.save("svc", "acct", "s3cret").expect("store");
+        assert_eq!(
+            store.load("svc", "acct").expect("get"),
+            Some("s3cret".to_string())
+        );
+        assert!(store.delete("svc", "acct").expect("erase"));
+        assert!(!store.delete("svc", "acct").expect("erase again"));
+    }
+
+    #[test]
+    fn non_zero_exit_is_an_error() {
+        let store = CredentialHelperKeyringStore::new(Some(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo denied >&2; exit 3".to_string(),
+        ]));
+        let err = store.load("svc", "acct").expect_err("helper failure");
+        assert!(err.message().contains("denied"));
+    }
+
+    #[test]
+    fn missing_command_is_an_error() {
+        let store = CredentialHelperKeyringStore::new(None);
+        let err = store.load("svc", "acct").expect_err("no helper configured");
+        assert!(err.message().contains("not configured"));
+    }
+}
@@ -0,0 +1,476 @@
+//! Passphrase-encrypted credential file for hosts without an OS keyring, such
+//! as headless Linux build agents.
+//!
+//! Every entry lives in one JSON document that is encrypted with AES-256-CBC
+//! and authenticated with HMAC-SHA256 (encrypt-then-MAC). Both keys are
+//! derived from the passphrase with PBKDF2-HMAC-SHA256. The passphrase is
+//! read from `LLMX_CREDENTIALS_PASSPHRASE`, or from the file named by
+//! `LLMX_CREDENTIALS_PASSPHRASE_FILE`.
+//!
+//! Reads and read-modify-write updates hold an advisory lock on a sibling
+//! `.lock` file, so concurrent LLMX processes never lose each other's writes.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use aes::Aes256;
+use cbc::cipher::BlockDecryptMut;
+use cbc::cipher::BlockEncryptMut;
+use cbc::cipher::KeyIvInit;
+use cbc::cipher::block_padding::Pkcs7;
+use hmac::Hmac;
+use hmac::Mac;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::trace;
+
+use crate::CredentialStoreError;
+use crate::KeyringStore;
+
+/// File name of the encrypted store inside `LLMX_HOME`.
+pub const ENCRYPTED_STORE_FILENAME: &str = "credentials.enc";
+pub const PASSPHRASE_ENV_VAR: &str = "LLMX_CREDENTIALS_PASSPHRASE";
+pub const PASSPHRASE_FILE_ENV_VAR: &str = "LLMX_CREDENTIALS_PASSPHRASE_FILE";
+
+const FORMAT_VERSION: u32 = 1;
+const DEFAULT_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const IV_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+type HmacSha256 = Hmac<Sha256>;
+/// service -> account -> secret
+type Entries = BTreeMap<String, BTreeMap<String, String>>;
+/// Salt and PBKDF2 iteration count of an existing store.
+type KdfParams = (Vec<u8>, u32);
+
+/// On-disk representation. Binary fields are hex encoded.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    iterations: u32,
+    salt: String,
+    iv: String,
+    ciphertext: String,
+    mac: String,
+}
+
+struct DerivedKeys {
+    salt: Vec<u8>,
+    iterations: u32,
+    encryption: [u8; KEY_LEN],
+    authentication: [u8; KEY_LEN],
+}
+
+pub struct EncryptedFileKeyringStore {
+    path: PathBuf,
+    passphrase: Option<String>,
+    iterations: u32,
+    /// Keys for the salt currently on disk, cached because the KDF is
+    /// deliberately slow.
+    keys: Mutex<Option<DerivedKeys>>,
+}
+
+impl fmt::Debug for EncryptedFileKeyringStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedFileKeyringStore")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedFileKeyringStore {
+    /// Store backed by the file at `path`. The passphrase is read from the
+    /// environment the first time the file is accessed.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            passphrase: None,
+            iterations: DEFAULT_ITERATIONS,
+            keys: Mutex::new(None),
+        }
+    }
+
+    pub fn with_passphrase(path: PathBuf, passphrase: String) -> Self {
+        Self {
+            passphrase: Some(passphrase),
+            ..Self::new(path)
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Overrides the PBKDF2 iteration count used when a new store is
+    /// created. Existing stores keep the count recorded in the file.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    fn passphrase(&self) -> Result<String, CredentialStoreError> {
+        if let Some(passphrase) = &self.passphrase {
+            return Ok(passphrase.clone());
+        }
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR)
+            && !passphrase.is_empty()
+        {
+            return Ok(passphrase);
+        }
+        if let Ok(file) = std::env::var(PASSPHRASE_FILE_ENV_VAR) {
+            let contents = fs::read_to_string(&file).map_err(|err| {
+                CredentialStoreError::backend(format!(
+                    "failed to read passphrase file {file}: {err}"
+                ))
+            })?;
+            let passphrase = contents.trim_end_matches(['\r', '\n']);
+            if !passphrase.is_empty() {
+                return Ok(passphrase.to_string());
+            }
+        }
+        Err(CredentialStoreError::backend(format!(
+            "the encrypted credential store needs a passphrase; set {PASSPHRASE_ENV_VAR} or {PASSPHRASE_FILE_ENV_VAR}"
+        )))
+    }
+
+    /// Returns the encryption and authentication keys for `salt`, deriving
+    /// them only when the salt or iteration count changed.
+    fn keys(
+        &self,
+        salt: &[u8],
+        iterations: u32,
+    ) -> Result<([u8; KEY_LEN], [u8; KEY_LEN]), CredentialStoreError> {
+        let mut cached = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(keys) = cached.as_ref()
+            && keys.salt == salt
+            && keys.iterations == iterations
+        {
+            return Ok((keys.encryption, keys.authentication));
+        }
+        let passphrase = self.passphrase()?;
+        let mut derived = [0u8; KEY_LEN * 2];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut derived);
+        let mut encryption = [0u8; KEY_LEN];
+        let mut authentication = [0u8; KEY_LEN];
+        encryption.copy_from_slice(&derived[..KEY_LEN]);
+        authentication.copy_from_slice(&derived[KEY_LEN..]);
+        *cached = Some(DerivedKeys {
+            salt: salt.to_vec(),
+            iterations,
+            encryption,
+            authentication,
+        });
+        Ok((encryption, authentication))
+    }
+
+    /// Reads and decrypts the store. Returns the entries together with the
+    /// salt and iteration count of the existing file, if any.
+    fn read(&self) -> Result<(Entries, Option<KdfParams>), CredentialStoreError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok((Entries::new(), None)),
+            Err(err) => return Err(self.error("read", err)),
+        };
+        let envelope: Envelope =
+            serde_json::from_str(&contents).map_err(|err| self.error("parse", err))?;
+        if envelope.version != FORMAT_VERSION {
+            return Err(CredentialStoreError::backend(format!(
+                "unsupported encrypted credential store version {} in {}",
+                envelope.version,
+                self.path.display()
+            )));
+        }
+        let salt = decode_hex(&envelope.salt).ok_or_else(|| self.corrupted())?;
+        let iv = decode_hex(&envelope.iv).ok_or_else(|| self.corrupted())?;
+        let ciphertext = decode_hex(&envelope.ciphertext).ok_or_else(|| self.corrupted())?;
+        let tag = decode_hex(&envelope.mac).ok_or_else(|| self.corrupted())?;
+
+        let (encryption, authentication) = self.keys(&salt, envelope.iterations)?;
+        let mut mac = new_mac(&authentication)?;
+        mac.update(&authenticated_data(
+            envelope.iterations,
+            &salt,
+            &iv,
+            &ciphertext,
+        ));
+        mac.verify_slice(&tag).map_err(|_| {
+            CredentialStoreError::backend(format!(
+                "failed to decrypt {}: wrong passphrase or corrupted file",
+                self.path.display()
+            ))
+        })?;
+
+        let decryptor = cbc::Decryptor::<Aes256>::new_from_slices(&encryption, &iv)
+            .map_err(|_| self.corrupted())?;
+        let plaintext = decryptor
+            .decrypt_padded_vec_mut::<Pkcs7>(&ciphertext)
+            .map_err(|_| self.corrupted())?;
+        let entries = serde_json::from_slice(&plaintext).map_err(|err| self.error("parse", err))?;
+        Ok((entries, Some((salt, envelope.iterations))))
+    }
+
+    /// Encrypts and writes the store, reusing the existing salt so the cached
+    /// keys stay valid. Removes the file once the last entry is deleted.
+    fn write(
+        &self,
+        entries: &Entries,
+        existing: Option<KdfParams>,
+    ) -> Result<(), CredentialStoreError> {
+        if entries.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Ok(()) => Ok(()),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(self.error("remove", err)),
+            };
+        }
+
+        let (salt, iterations) = existing
+            .unwrap_or_else(|| (rand::random::<[u8; SALT_LEN]>().to_vec(), self.iterations));
+        let iv: [u8; IV_LEN] = rand::random();
+        let (encryption, authentication) = self.keys(&salt, iterations)?;
+
+        let plaintext = serde_json::to_vec(entries).map_err(|err| self.error("serialize", err))?;
+        let encryptor = cbc::Encryptor::<Aes256>::new_from_slices(&encryption, &iv)
+            .map_err(|_| self.corrupted())?;
+        let ciphertext = encryptor.encrypt_padded_vec_mut::<Pkcs7>(&plaintext);
+        let mut mac = new_mac(&authentication)?;
+        mac.update(&authenticated_data(iterations, &salt, &iv, &ciphertext));
+        let envelope = Envelope {
+            version: FORMAT_VERSION,
+            iterations,
+            salt: encode_hex(&salt),
+            iv: encode_hex(&iv),
+            ciphertext: encode_hex(&ciphertext),
+            mac: encode_hex(&mac.finalize().into_bytes()),
+        };
+        let serialized =
+            serde_json::to_vec_pretty(&envelope).map_err(|err| self.error("serialize", err))?;
+        write_private_file(&self.path, &serialized).map_err(|err| self.error("write", err))
+    }
+
+    /// Takes an advisory lock on `<path>.lock`, shared for reads and
+    /// exclusive for updates. The lock is released when the file is dropped.
+    /// A separate file is locked because writes replace the store itself.
+    fn lock(&self, exclusive: bool) -> Result<File, CredentialStoreError> {
+        let lock_path = self.path.with_extension("enc.lock");
+        if let Some(parent) = lock_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| self.error("lock", err))?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.read(true).write(true).create(true).truncate(false);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options
+            .open(&lock_path)
+            .map_err(|err| self.error("lock", err))?;
+        let locked = if exclusive {
+            file.lock()
+        } else {
+            file.lock_shared()
+        };
+        locked.map_err(|err| self.error("lock", err))?;
+        Ok(file)
+    }
+
+    fn error(&self, action: &str, err: impl fmt::Display) -> CredentialStoreError {
+        CredentialStoreError::backend(format!(
+            "failed to {action} encrypted credential store {}: {err}",
+            self.path.display()
+        ))
+    }
+
+    fn corrupted(&self) -> CredentialStoreError {
+        CredentialStoreError::backend(format!(
+            "encrypted credential store {} is corrupted",
+            self.path.display()
+        ))
+    }
+}
+
+impl KeyringStore for EncryptedFileKeyringStore {
+    fn load(&self, service: &str, account: &str) -> Result<Option<String>, CredentialStoreError> {
+        trace!("encrypted_file.load, service={service}, account={account}");
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let _lock = self.lock(false)?;
+        let (entries, _) = self.read()?;
+        Ok(entries
+            .get(service)
+            .and_then(|accounts| accounts.get(account))
+            .cloned())
+    }
+
+    fn save(&self, service: &str, account: &str, value: &str) -> Result<(), CredentialStoreError> {
+        trace!(
+            "encrypted_file.save, service={service}, account={account}, value_len={}",
+            value.len()
+        );
+        let _lock = self.lock(true)?;
+        let (mut entries, existing) = self.read()?;
+        entries
+            .entry(service.to_string())
+            .or_default()
+            .insert(account.to_string(), value.to_string());
+        self.write(&entries, existing)
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<bool, CredentialStoreError> {
+        trace!("encrypted_file.delete, service={service}, account={account}");
+        if !self.path.exists() {
+            return Ok(false);
+        }
+        let _lock = self.lock(true)?;
+        let (mut entries, existing) = self.read()?;
+        let Some(accounts) = entries.get_mut(service) else {
+            return Ok(false);
+        };
+        let removed = accounts.remove(account).is_some();
+        if accounts.is_empty() {
+            entries.remove(service);
+        }
+        if removed {
+            self.write(&entries, existing)?;
+        }
+        Ok(removed)
+    }
+}
+
+fn new_mac(key: &[u8]) -> Result<HmacSha256, CredentialStoreError> {
+    HmacSha256::new_from_slice(key)
+        .map_err(|err| CredentialStoreError::backend(format!("invalid HMAC key: {err}")))
+}
+
+fn authenticated_data(iterations: u32, salt: &[u8], iv: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + salt.len() + iv.len() + ciphertext.len());
+    data.extend_from_slice(&FORMAT_VERSION.to_be_bytes());
+    data.extend_from_slice(&iterations.to_be_bytes());
+    data.extend_from_slice(salt);
+    data.extend_from_slice(iv);
+    data.extend_from_slice(ciphertext);
+    data
+}
+
+/// Writes `contents` to a sibling temp file readable only by the owner and
+/// renames it over `path`, so a crash never leaves a truncated store.
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("enc.tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn store(path: PathBuf, passphrase: &str) -> EncryptedFileKeyringStore {
+        EncryptedFileKeyringStore::with_passphrase(path, passphrase.to_string()).with_iterations(2)
+    }
+
+    #[test]
+    fn concurrent_saves_keep_every_entry() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(ENCRYPTED_STORE_FILENAME);
+
+        std::thread::scope(|scope| {
+            for index in 0..8 {
+                let path = path.clone();
+                scope.spawn(move || {
+                    store(path, "shared")
+                        .save("svc", &format!("account-{index}"), "secret")
+                        .expect("save");
+                });
+            }
+        });
+
+        let reopened = store(path, "shared");
+        for index in 0..8 {
+            assert_eq!(
+                reopened
+                    .load("svc", &format!("account-{index}"))
+                    .expect("load"),
+                Some("secret".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_entries_and_removes_empty_store() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(ENCRYPTED_STORE_FILENAME);
+        let store = store(path.clone(), "correct horse");
+
+        store.save("svc", "a", "secret-a").expect("save a");
+        store.save("svc", "b", "secret-b").expect("save b");
+        let on_disk = fs::read_to_string(&path).expect("read store");
+        assert!(!on_disk.contains("secret-a"));
+
+        let reopened =
+            EncryptedFileKeyringStore::with_passphrase(path.clone(), "correct horse".into());
+        assert_eq!(
+            reopened.load("svc", "a").expect("load"),
+            Some("secret-a".to_string())
+        );
+        assert_eq!(reopened.load("other", "a").expect("load"), None);
+
+        assert!(store.delete("svc", "a").expect("delete a"));
+        assert!(!store.delete("svc", "a").expect("delete a again"));
+        assert!(store.delete("svc", "b").expect("delete b"));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join(ENCRYPTED_STORE_FILENAME);
+        store(path.clone(), "right")
+            .save("svc", "a", "secret")
+            .expect("save");
+
+        let err = store(path, "wrong")
+            .load("svc", "a")
+            .expect_err("wrong passphrase should fail");
+        assert!(err.message().contains("wrong passphrase"));
+    }
+}
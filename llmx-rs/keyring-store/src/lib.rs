@@ -5,9 +5,21 @@ use std::fmt;
 use std::fmt::Debug;
 use tracing::trace;
 
+mod credential_helper;
+mod encrypted_file;
+
+pub use credential_helper::CredentialHelperKeyringStore;
+pub use encrypted_file::ENCRYPTED_STORE_FILENAME;
+pub use encrypted_file::EncryptedFileKeyringStore;
+pub use encrypted_file::PASSPHRASE_ENV_VAR;
+pub use encrypted_file::PASSPHRASE_FILE_ENV_VAR;
+
 #[derive(Debug)]
 pub enum CredentialStoreError {
     Other(KeyringError),
+    /// Failure reported by a non-keyring backend (encrypted file or
+    /// credential helper).
+    Backend(String),
 }
 
 impl CredentialStoreError {
@@ -15,15 +27,21 @@ impl CredentialStoreError {
         Self::Other(error)
     }
 
+    pub fn backend(message: impl Into<String>) -> Self {
+        Self::Backend(message.into())
+    }
+
     pub fn message(&self) -> String {
         match self {
             Self::Other(error) => error.to_string(),
+            Self::Backend(message) => message.clone(),
         }
     }
 
     pub fn into_error(self) -> KeyringError {
         match self {
             Self::Other(error) => error,
+            Self::Backend(message) => KeyringError::PlatformFailure(message.into()),
         }
     }
 }
@@ -32,13 +50,15 @@ impl fmt::Display for CredentialStoreError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Other(error) => write!(f, "{error}"),
+            Self::Backend(message) => write!(f, "{message}"),
         }
     }
 }
 
 impl Error for CredentialStoreError {}
 
-/// Shared credential store abstraction for keyring-backed implementations.
+/// Shared credential store abstraction. Implemented by the OS keyring, a
+/// passphrase-encrypted file, and external credential helpers.
 pub trait KeyringStore: Debug + Send + Sync {
     fn load(&self, service: &str, account: &str) -> Result<Option<String>, CredentialStoreError>;
     fn save(&self, service: &str, account: &str, value: &str) -> Result<(), CredentialStoreError>;
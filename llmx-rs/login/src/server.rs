@@ -41,6 +41,7 @@ pub struct ServerOptions {
     pub force_state: Option<String>,
     pub forced_chatgpt_workspace_id: Option<String>,
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+    pub credential_helper: Option<Vec<String>>,
 }
 
 impl ServerOptions {
@@ -49,6 +50,7 @@ impl ServerOptions {
         client_id: String,
         forced_chatgpt_workspace_id: Option<String>,
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+        credential_helper: Option<Vec<String>>,
     ) -> Self {
         Self {
             llmx_home,
@@ -59,6 +61,7 @@ impl ServerOptions {
             force_state: None,
             forced_chatgpt_workspace_id,
             cli_auth_credentials_store_mode,
+            credential_helper,
         }
     }
 }
@@ -275,6 +278,7 @@ async fn process_request(
                         tokens.access_token.clone(),
                         tokens.refresh_token.clone(),
                         opts.cli_auth_credentials_store_mode,
+                        opts.credential_helper.clone(),
                     )
                     .await
                     {
@@ -542,6 +546,7 @@ pub(crate) async fn persist_tokens_async(
     access_token: String,
     refresh_token: String,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
+    credential_helper: Option<Vec<String>>,
 ) -> io::Result<()> {
     // Reuse existing synchronous logic but run it off the async runtime.
     let llmx_home = llmx_home.to_path_buf();
@@ -563,7 +568,12 @@ pub(crate) async fn persist_tokens_async(
             tokens: Some(tokens),
             last_refresh: Some(Utc::now()),
         };
-        save_auth(&llmx_home, &auth, auth_credentials_store_mode)
+        save_auth(
+            &llmx_home,
+            &auth,
+            auth_credentials_store_mode,
+            credential_helper.as_deref(),
+        )
     })
     .await
     .map_err(|e| io::Error::other(format!("persist task failed: {e}")))?
@@ -107,6 +107,7 @@ fn server_opts(
         "client-id".to_string(),
         None,
         cli_auth_credentials_store_mode,
+        None,
     );
     opts.issuer = issuer;
     opts.open_browser = false;
@@ -139,7 +140,7 @@ async fn device_code_login_integration_succeeds() -> anyhow::Result<()> {
         .await
         .expect("device code login integration should succeed");
 
-    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File)
+    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File, None)
         .context("auth.json should load after login succeeds")?
         .context("auth.json written")?;
     // assert_eq!(auth.openai_api_key.as_deref(), Some("api-key-321"));
@@ -180,7 +181,7 @@ async fn device_code_login_rejects_workspace_mismatch() -> anyhow::Result<()> {
         .expect_err("device code login should fail when workspace mismatches");
     assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
 
-    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File)
+    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File, None)
         .context("auth.json should load after login fails")?;
     assert!(
         auth.is_none(),
@@ -211,7 +212,7 @@ async fn device_code_login_integration_handles_usercode_http_failure() -> anyhow
         "unexpected error: {err:?}"
     );
 
-    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File)
+    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File, None)
         .context("auth.json should load after login fails")?;
     assert!(
         auth.is_none(),
@@ -244,6 +245,7 @@ async fn device_code_login_integration_persists_without_api_key_on_exchange_fail
         "client-id".to_string(),
         None,
         AuthCredentialsStoreMode::File,
+        None,
     );
     opts.issuer = issuer;
     opts.open_browser = false;
@@ -252,7 +254,7 @@ async fn device_code_login_integration_persists_without_api_key_on_exchange_fail
         .await
         .expect("device login should succeed without API key exchange");
 
-    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File)
+    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File, None)
         .context("auth.json should load after login succeeds")?
         .context("auth.json written")?;
     assert!(auth.openai_api_key.is_none());
@@ -294,6 +296,7 @@ async fn device_code_login_integration_handles_error_payload() -> anyhow::Result
         "client-id".to_string(),
         None,
         AuthCredentialsStoreMode::File,
+        None,
     );
     opts.issuer = issuer;
     opts.open_browser = false;
@@ -308,7 +311,7 @@ async fn device_code_login_integration_handles_error_payload() -> anyhow::Result
         "Expected an authorization_declined / 400 / 404 error, got {err:?}"
     );
 
-    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File)
+    let auth = load_auth_dot_json(llmx_home.path(), AuthCredentialsStoreMode::File, None)
         .context("auth.json should load after login fails")?;
     assert!(
         auth.is_none(),
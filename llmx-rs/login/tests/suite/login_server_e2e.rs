@@ -112,6 +112,7 @@ async fn end_to_end_login_flow_persists_auth_json() -> Result<()> {
     let opts = ServerOptions {
         llmx_home: server_home,
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+        credential_helper: None,
         client_id: llmx_login::CLIENT_ID.to_string(),
         issuer,
         port: 0,
@@ -173,6 +174,7 @@ async fn creates_missing_llmx_home_dir() -> Result<()> {
     let opts = ServerOptions {
         llmx_home: server_home,
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+        credential_helper: None,
         client_id: llmx_login::CLIENT_ID.to_string(),
         issuer,
         port: 0,
@@ -212,6 +214,7 @@ async fn forced_chatgpt_workspace_id_mismatch_blocks_login() -> Result<()> {
     let opts = ServerOptions {
         llmx_home: llmx_home.clone(),
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+        credential_helper: None,
         client_id: llmx_login::CLIENT_ID.to_string(),
         issuer,
         port: 0,
@@ -268,6 +271,7 @@ async fn cancels_previous_login_server_when_port_is_in_use() -> Result<()> {
     let first_opts = ServerOptions {
         llmx_home: first_llmx_home,
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+        credential_helper: None,
         client_id: llmx_login::CLIENT_ID.to_string(),
         issuer: issuer.clone(),
         port: 0,
@@ -288,6 +292,7 @@ async fn cancels_previous_login_server_when_port_is_in_use() -> Result<()> {
     let second_opts = ServerOptions {
         llmx_home: second_llmx_home,
         cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+        credential_helper: None,
         client_id: llmx_login::CLIENT_ID.to_string(),
         issuer,
         port: login_port,
@@ -0,0 +1,118 @@
+//! Shared pieces of the approval elicitations: the form schema sent in
+//! `elicitation/create` and the mapping from the client's answer back to a
+//! [`ReviewDecision`].
+
+use llmx_core::protocol::ReviewDecision;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::ElicitResult;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tracing::error;
+
+const DECISION_FIELD: &str = "decision";
+
+/// Form schema with a single required `decision` field, so standard MCP
+/// clients can render the approval prompt without knowing about LLMX.
+pub(crate) fn review_decision_schema() -> ElicitRequestParamsRequestedSchema {
+    ElicitRequestParamsRequestedSchema {
+        r#type: "object".to_string(),
+        properties: json!({
+            "decision": {
+                "type": "string",
+                "title": "Decision",
+                "enum": ["approved", "approved_for_session", "denied", "abort"],
+                "enumNames": [
+                    "Approve",
+                    "Approve for this session",
+                    "Deny",
+                    "Deny and stop the turn",
+                ],
+            }
+        }),
+        required: Some(vec![DECISION_FIELD.to_string()]),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ApprovalResponse {
+    Elicit(ElicitResult),
+    /// The pre-elicitation `{ "decision": ... }` payload.
+    Legacy {
+        decision: ReviewDecision,
+    },
+}
+
+/// Maps the client's response to an approval elicitation onto a decision.
+/// Anything that cannot be understood is treated as a denial.
+pub(crate) fn review_decision_from_response(value: Value) -> ReviewDecision {
+    let response = match serde_json::from_value::<ApprovalResponse>(value) {
+        Ok(response) => response,
+        Err(err) => {
+            error!("failed to deserialize approval response: {err}");
+            return ReviewDecision::Denied;
+        }
+    };
+    let result = match response {
+        ApprovalResponse::Elicit(result) => result,
+        ApprovalResponse::Legacy { decision } => return decision,
+    };
+    match result.action.as_str() {
+        // A client that renders no form fields can still accept outright.
+        "accept" => match result
+            .content
+            .and_then(|mut content| content.get_mut(DECISION_FIELD).map(Value::take))
+        {
+            None => ReviewDecision::Approved,
+            Some(decision) => serde_json::from_value(decision).unwrap_or_else(|err| {
+                error!("invalid decision in elicitation response: {err}");
+                ReviewDecision::Denied
+            }),
+        },
+        "cancel" => ReviewDecision::Abort,
+        _ => ReviewDecision::Denied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn maps_elicit_results_to_decisions() {
+        let cases = [
+            (
+                json!({"action": "accept", "content": {"decision": "approved_for_session"}}),
+                ReviewDecision::ApprovedForSession,
+            ),
+            (json!({"action": "accept"}), ReviewDecision::Approved),
+            (
+                json!({"action": "accept", "content": {"decision": "maybe"}}),
+                ReviewDecision::Denied,
+            ),
+            (json!({"action": "decline"}), ReviewDecision::Denied),
+            (json!({"action": "cancel"}), ReviewDecision::Abort),
+        ];
+        for (response, expected) in cases {
+            assert_eq!(
+                review_decision_from_response(response.clone()),
+                expected,
+                "{response}"
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_legacy_decision_payload() {
+        assert_eq!(
+            review_decision_from_response(json!({"decision": "approved"})),
+            ReviewDecision::Approved
+        );
+        assert_eq!(
+            review_decision_from_response(json!({"unexpected": true})),
+            ReviewDecision::Denied
+        );
+    }
+}
@@ -13,9 +13,10 @@ use mcp_types::ModelContextProtocolRequest;
 use mcp_types::RequestId;
 use serde::Deserialize;
 use serde::Serialize;
-use serde_json::json;
 use tracing::error;
 
+use crate::elicitation::review_decision_from_response;
+use crate::elicitation::review_decision_schema;
 use crate::llmx_tool_runner::INVALID_PARAMS_ERROR_CODE;
 
 /// Conforms to [`mcp_types::ElicitRequestParams`] so that it can be used as the
@@ -42,10 +43,9 @@ pub struct ExecApprovalElicitRequestParams {
     pub llmx_risk: Option<SandboxCommandAssessment>,
 }
 
-// TODO(mbolin): ExecApprovalResponse does not conform to ElicitResult. See:
-// - https://github.com/modelcontextprotocol/modelcontextprotocol/blob/f962dc1780fa5eed7fb7c8a0232f1fc83ef220cd/schema/2025-06-18/schema.json#L617-L636
-// - https://modelcontextprotocol.io/specification/draft/client/elicitation#protocol-messages
-// It should have "action" and "content" fields.
+/// Legacy response payload. Clients should answer with a standard
+/// [`mcp_types::ElicitResult`] whose `content.decision` holds the decision;
+/// this bare form is still accepted for older clients.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecApprovalResponse {
     pub decision: ReviewDecision,
@@ -73,11 +73,7 @@ pub(crate) async fn handle_exec_approval_request(
 
     let params = ExecApprovalElicitRequestParams {
         message,
-        requested_schema: ElicitRequestParamsRequestedSchema {
-            r#type: "object".to_string(),
-            properties: json!({}),
-            required: None,
-        },
+        requested_schema: review_decision_schema(),
         llmx_elicitation: "exec-approval".to_string(),
         llmx_mcp_tool_call_id: tool_call_id.clone(),
         llmx_event_id: event_id.clone(),
@@ -136,20 +132,13 @@ async fn on_exec_approval_response(
         }
     };
 
-    // Try to deserialize `value` and then make the appropriate call to `llmx`.
-    let response = serde_json::from_value::<ExecApprovalResponse>(value).unwrap_or_else(|err| {
-        error!("failed to deserialize ExecApprovalResponse: {err}");
-        // If we cannot deserialize the response, we deny the request to be
-        // conservative.
-        ExecApprovalResponse {
-            decision: ReviewDecision::Denied,
-        }
-    });
+    // Responses that cannot be understood are denied to be conservative.
+    let decision = review_decision_from_response(value);
 
     if let Err(err) = llmx
         .submit(Op::ExecApproval {
             id: event_id,
-            decision: response.decision,
+            decision,
         })
         .await
     {
@@ -20,6 +20,7 @@ use tracing::error;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+mod elicitation;
 mod error_code;
 mod exec_approval;
 mod llmx_tool_config;
@@ -27,6 +28,10 @@ mod llmx_tool_runner;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod progress;
+mod session_registry;
+mod session_resources;
+mod session_tool_config;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
@@ -38,6 +43,9 @@ pub use crate::llmx_tool_config::LlmxToolCallParam;
 pub use crate::llmx_tool_config::LlmxToolCallReplyParam;
 pub use crate::patch_approval::PatchApprovalElicitRequestParams;
 pub use crate::patch_approval::PatchApprovalResponse;
+pub use crate::session_tool_config::LlmxCancelParam;
+pub use crate::session_tool_config::LlmxListSessionsParam;
+pub use crate::session_tool_config::LlmxResumeParam;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
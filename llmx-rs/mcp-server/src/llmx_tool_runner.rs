@@ -2,13 +2,16 @@
 //! Tokio task. Separated from `message_processor.rs` to keep that file small
 //! and to make future feature-growth easier to manage.
 
-use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::progress::ProgressReporter;
+use crate::session_registry::SessionRegistry;
 use llmx_core::ConversationManager;
 use llmx_core::LlmxConversation;
 use llmx_core::NewConversation;
@@ -21,14 +24,18 @@ use llmx_core::protocol::ExecApprovalRequestEvent;
 use llmx_core::protocol::Op;
 use llmx_core::protocol::Submission;
 use llmx_core::protocol::TaskCompleteEvent;
+use llmx_core::protocol::TurnAbortReason;
+use llmx_core::protocol::TurnAbortedEvent;
+use llmx_core::protocol::TurnDiffEvent;
 use llmx_protocol::ConversationId;
 use llmx_protocol::user_input::UserInput;
 use mcp_types::CallToolResult;
 use mcp_types::ContentBlock;
+use mcp_types::ModelContextProtocolNotification;
 use mcp_types::RequestId;
+use mcp_types::ResourceListChangedNotification;
 use mcp_types::TextContent;
 use serde_json::json;
-use tokio::sync::Mutex;
 
 pub(crate) const INVALID_PARAMS_ERROR_CODE: i64 = -32602;
 
@@ -42,7 +49,8 @@ pub async fn run_llmx_tool_session(
     config: LlmxConfig,
     outgoing: Arc<OutgoingMessageSender>,
     conversation_manager: Arc<ConversationManager>,
-    running_requests_id_to_llmx_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
+    progress: Option<ProgressReporter>,
 ) {
     let NewConversation {
         conversation_id,
@@ -76,6 +84,14 @@ pub async fn run_llmx_tool_session(
             Some(OutgoingNotificationMeta::new(Some(id.clone()))),
         )
         .await;
+    register_session(
+        &sessions,
+        &outgoing,
+        conversation_id,
+        session_configured.rollout_path.clone(),
+        session_configured.model.clone(),
+    )
+    .await;
 
     // Use the original MCP request ID as the `sub_id` for the LLMX submission so that
     // any events emitted for this tool-call can be correlated with the
@@ -84,10 +100,7 @@ pub async fn run_llmx_tool_session(
         RequestId::String(s) => s.clone(),
         RequestId::Integer(n) => n.to_string(),
     };
-    running_requests_id_to_llmx_uuid
-        .lock()
-        .await
-        .insert(id.clone(), conversation_id);
+    sessions.start_request(id.clone(), conversation_id).await;
     let submission = Submission {
         id: sub_id.clone(),
         op: Op::UserInput {
@@ -99,12 +112,20 @@ pub async fn run_llmx_tool_session(
 
     if let Err(e) = conversation.submit_with_id(submission).await {
         tracing::error!("Failed to submit initial prompt: {e}");
-        // unregister the id so we don't keep it in the map
-        running_requests_id_to_llmx_uuid.lock().await.remove(&id);
+        // unregister the id so we don't keep it in the registry
+        sessions.finish_request(&id).await;
         return;
     }
 
-    run_llmx_tool_session_inner(conversation, outgoing, id, running_requests_id_to_llmx_uuid).await;
+    run_llmx_tool_session_inner(
+        conversation,
+        conversation_id,
+        outgoing,
+        id,
+        sessions,
+        progress,
+    )
+    .await;
 }
 
 pub async fn run_llmx_tool_session_reply(
@@ -112,13 +133,13 @@ pub async fn run_llmx_tool_session_reply(
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     prompt: String,
-    running_requests_id_to_llmx_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
     conversation_id: ConversationId,
+    progress: Option<ProgressReporter>,
 ) {
-    running_requests_id_to_llmx_uuid
-        .lock()
-        .await
-        .insert(request_id.clone(), conversation_id);
+    sessions
+        .start_request(request_id.clone(), conversation_id)
+        .await;
     if let Err(e) = conversation
         .submit(Op::UserInput {
             items: vec![UserInput::Text { text: prompt }],
@@ -126,28 +147,51 @@ pub async fn run_llmx_tool_session_reply(
         .await
     {
         tracing::error!("Failed to submit user input: {e}");
-        // unregister the id so we don't keep it in the map
-        running_requests_id_to_llmx_uuid
-            .lock()
-            .await
-            .remove(&request_id);
+        // unregister the id so we don't keep it in the registry
+        sessions.finish_request(&request_id).await;
         return;
     }
 
     run_llmx_tool_session_inner(
         conversation,
+        conversation_id,
         outgoing,
         request_id,
-        running_requests_id_to_llmx_uuid,
+        sessions,
+        progress,
     )
     .await;
 }
 
+/// Records a conversation in the registry and, if it is new, tells the client
+/// that the resource list (rollout and diff per session) has changed.
+pub(crate) async fn register_session(
+    sessions: &SessionRegistry,
+    outgoing: &OutgoingMessageSender,
+    conversation_id: ConversationId,
+    rollout_path: PathBuf,
+    model: String,
+) {
+    if sessions
+        .add_session(conversation_id, rollout_path, model)
+        .await
+    {
+        outgoing
+            .send_notification(OutgoingNotification {
+                method: ResourceListChangedNotification::METHOD.to_string(),
+                params: None,
+            })
+            .await;
+    }
+}
+
 async fn run_llmx_tool_session_inner(
     llmx: Arc<LlmxConversation>,
+    conversation_id: ConversationId,
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
-    running_requests_id_to_llmx_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
+    mut progress: Option<ProgressReporter>,
 ) {
     let request_id_str = match &request_id {
         RequestId::String(s) => s.clone(),
@@ -165,6 +209,9 @@ async fn run_llmx_tool_session_inner(
                         Some(OutgoingNotificationMeta::new(Some(request_id.clone()))),
                     )
                     .await;
+                if let Some(progress) = progress.as_mut() {
+                    progress.report(&event.msg).await;
+                }
 
                 match event.msg {
                     EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
@@ -196,6 +243,7 @@ async fn run_llmx_tool_session_inner(
                             "error": err_event.message,
                         });
                         outgoing.send_response(request_id.clone(), result).await;
+                        sessions.finish_request(&request_id).await;
                         break;
                     }
                     EventMsg::Warning(_) => {
@@ -236,13 +284,35 @@ async fn run_llmx_tool_session_inner(
                             structured_content: None,
                         };
                         outgoing.send_response(request_id.clone(), result).await;
-                        // unregister the id so we don't keep it in the map
-                        running_requests_id_to_llmx_uuid
-                            .lock()
-                            .await
-                            .remove(&request_id);
+                        // unregister the id so we don't keep it in the registry
+                        sessions.finish_request(&request_id).await;
                         break;
                     }
+                    EventMsg::TurnAborted(TurnAbortedEvent { reason }) => {
+                        // A request cancelled with `notifications/cancelled` is
+                        // already unregistered and must not get a response.
+                        if sessions.finish_request(&request_id).await {
+                            let reason = match reason {
+                                TurnAbortReason::Interrupted => "interrupted",
+                                TurnAbortReason::Replaced => "replaced by a newer turn",
+                                TurnAbortReason::ReviewEnded => "ended by review mode",
+                            };
+                            let result = CallToolResult {
+                                content: vec![ContentBlock::TextContent(TextContent {
+                                    r#type: "text".to_string(),
+                                    text: format!("LLMX turn was {reason}"),
+                                    annotations: None,
+                                })],
+                                is_error: Some(true),
+                                structured_content: None,
+                            };
+                            outgoing.send_response(request_id.clone(), result).await;
+                        }
+                        break;
+                    }
+                    EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+                        sessions.set_diff(conversation_id, unified_diff).await;
+                    }
                     EventMsg::SessionConfigured(_) => {
                         tracing::error!("unexpected SessionConfigured event");
                     }
@@ -272,12 +342,15 @@ async fn run_llmx_tool_session_inner(
                     | EventMsg::StreamError(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
-                    | EventMsg::TurnDiff(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
-                    | EventMsg::TurnAborted(_)
+                    | EventMsg::PlanProposed(_)
+                    | EventMsg::ListCheckpointsResponse(_)
+                    | EventMsg::ListApprovalRulesResponse(_)
+                    | EventMsg::CheckpointDiffResponse(_)
+                    | EventMsg::CheckpointRestored(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
@@ -314,6 +387,7 @@ async fn run_llmx_tool_session_inner(
                     structured_content: None,
                 };
                 outgoing.send_response(request_id.clone(), result).await;
+                sessions.finish_request(&request_id).await;
                 break;
             }
         }
@@ -1,20 +1,49 @@
-use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::error_code::RESOURCE_NOT_FOUND_ERROR_CODE;
 use crate::llmx_tool_config::LlmxToolCallParam;
 use crate::llmx_tool_config::LlmxToolCallReplyParam;
 use crate::llmx_tool_config::create_tool_for_llmx_tool_call_param;
 use crate::llmx_tool_config::create_tool_for_llmx_tool_call_reply_param;
+use crate::llmx_tool_runner::register_session;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotificationMeta;
+use crate::progress::ProgressReporter;
+use crate::progress::progress_token_from_params;
+use crate::session_registry::SessionRegistry;
+use crate::session_resources::DIFF_MIME_TYPE;
+use crate::session_resources::ROLLOUT_MIME_TYPE;
+use crate::session_resources::SessionResource;
+use crate::session_resources::resources_for_session;
+use crate::session_resources::session_resource_templates;
+use crate::session_tool_config::LlmxCancelParam;
+use crate::session_tool_config::LlmxListSessionsParam;
+use crate::session_tool_config::LlmxResumeParam;
+use crate::session_tool_config::create_tool_for_llmx_cancel_param;
+use crate::session_tool_config::create_tool_for_llmx_list_sessions_param;
+use crate::session_tool_config::create_tool_for_llmx_resume_param;
 use llmx_protocol::ConversationId;
+use llmx_protocol::items::TurnItem;
+use llmx_protocol::models::ResponseItem;
+use llmx_protocol::protocol::SessionMetaLine;
 use llmx_protocol::protocol::SessionSource;
+use llmx_protocol::protocol::USER_MESSAGE_BEGIN;
 
 use llmx_core::AuthManager;
+use llmx_core::ConversationItem;
 use llmx_core::ConversationManager;
+use llmx_core::NewConversation;
+use llmx_core::RolloutRecorder;
 use llmx_core::config::Config;
 use llmx_core::default_client::USER_AGENT_SUFFIX;
 use llmx_core::default_client::get_llmx_user_agent;
+use llmx_core::find_conversation_path_by_id_str;
+use llmx_core::parse_cursor;
+use llmx_core::protocol::Event;
+use llmx_core::protocol::EventMsg;
+use llmx_core::protocol::Op;
 use llmx_core::protocol::Submission;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
@@ -25,23 +54,44 @@ use mcp_types::JSONRPCErrorError;
 use mcp_types::JSONRPCNotification;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
+use mcp_types::ListResourceTemplatesResult;
+use mcp_types::ListResourcesResult;
 use mcp_types::ListToolsResult;
 use mcp_types::ModelContextProtocolRequest;
+use mcp_types::ProgressToken;
+use mcp_types::ReadResourceResult;
+use mcp_types::ReadResourceResultContents;
 use mcp_types::RequestId;
+use mcp_types::ServerCapabilitiesResources;
 use mcp_types::ServerCapabilitiesTools;
 use mcp_types::ServerNotification;
 use mcp_types::TextContent;
+use mcp_types::TextResourceContents;
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 use tokio::task;
 
+/// Page size for `llmx-list-sessions` when the client does not pass `limit`.
+const DEFAULT_LIST_SESSIONS_LIMIT: u32 = 20;
+
+/// Saved sessions offered by `llmx-list-sessions`: interactive ones plus
+/// sessions started by `llmx exec` and by MCP clients.
+const LISTED_SESSION_SOURCES: &[SessionSource] = &[
+    SessionSource::Cli,
+    SessionSource::VSCode,
+    SessionSource::Exec,
+    SessionSource::Mcp,
+];
+
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     initialized: bool,
     llmx_linux_sandbox_exe: Option<PathBuf>,
+    config: Arc<Config>,
+    auth_manager: Arc<AuthManager>,
     conversation_manager: Arc<ConversationManager>,
-    running_requests_id_to_llmx_uuid: Arc<Mutex<HashMap<RequestId, ConversationId>>>,
+    sessions: Arc<SessionRegistry>,
 }
 
 impl MessageProcessor {
@@ -57,21 +107,29 @@ impl MessageProcessor {
             config.llmx_home.clone(),
             false,
             config.cli_auth_credentials_store_mode,
+            config.credential_helper.clone(),
         );
-        let conversation_manager =
-            Arc::new(ConversationManager::new(auth_manager, SessionSource::Mcp));
+        let conversation_manager = Arc::new(ConversationManager::new(
+            auth_manager.clone(),
+            SessionSource::Mcp,
+        ));
         Self {
             outgoing,
             initialized: false,
             llmx_linux_sandbox_exe,
+            config,
+            auth_manager,
             conversation_manager,
-            running_requests_id_to_llmx_uuid: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(SessionRegistry::default()),
         }
     }
 
     pub(crate) async fn process_request(&mut self, request: JSONRPCRequest) {
         // Hold on to the ID so we can respond.
         let request_id = request.id.clone();
+        // `_meta` is not part of the typed request params, so read the
+        // progress token before converting.
+        let progress_token = progress_token_from_params(request.params.as_ref());
 
         let client_request = match McpClientRequest::try_from(request) {
             Ok(client_request) => client_request,
@@ -90,13 +148,14 @@ impl MessageProcessor {
                 self.handle_ping(request_id, params).await;
             }
             McpClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params);
+                self.handle_list_resources(request_id, params).await;
             }
             McpClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params);
+                self.handle_list_resource_templates(request_id, params)
+                    .await;
             }
             McpClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params);
+                self.handle_read_resource(request_id, params).await;
             }
             McpClientRequest::SubscribeRequest(params) => {
                 self.handle_subscribe(params);
@@ -114,7 +173,8 @@ impl MessageProcessor {
                 self.handle_list_tools(request_id, params).await;
             }
             McpClientRequest::CallToolRequest(params) => {
-                self.handle_call_tool(request_id, params).await;
+                self.handle_call_tool(request_id, params, progress_token)
+                    .await;
             }
             McpClientRequest::SetLevelRequest(params) => {
                 self.handle_set_level(params);
@@ -209,7 +269,10 @@ impl MessageProcessor {
                 experimental: None,
                 logging: None,
                 prompts: None,
-                resources: None,
+                resources: Some(ServerCapabilitiesResources {
+                    list_changed: Some(true),
+                    subscribe: None,
+                }),
                 tools: Some(ServerCapabilitiesTools {
                     list_changed: Some(true),
                 }),
@@ -246,26 +309,111 @@ impl MessageProcessor {
             .await;
     }
 
-    fn handle_list_resources(
+    async fn handle_list_resources(
         &self,
+        id: RequestId,
         params: <mcp_types::ListResourcesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let resources = self
+            .sessions
+            .list()
+            .await
+            .iter()
+            .flat_map(resources_for_session)
+            .collect();
+        let result = ListResourcesResult {
+            next_cursor: None,
+            resources,
+        };
+        self.send_response::<mcp_types::ListResourcesRequest>(id, result)
+            .await;
     }
 
-    fn handle_list_resource_templates(
+    async fn handle_list_resource_templates(
         &self,
+        id: RequestId,
         params:
             <mcp_types::ListResourceTemplatesRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        let result = ListResourceTemplatesResult {
+            next_cursor: None,
+            resource_templates: session_resource_templates(),
+        };
+        self.send_response::<mcp_types::ListResourceTemplatesRequest>(id, result)
+            .await;
     }
 
-    fn handle_read_resource(
+    async fn handle_read_resource(
         &self,
+        id: RequestId,
         params: <mcp_types::ReadResourceRequest as mcp_types::ModelContextProtocolRequest>::Params,
     ) {
         tracing::info!("resources/read -> params: {:?}", params);
+        let uri = params.uri;
+        let not_found = JSONRPCErrorError {
+            code: RESOURCE_NOT_FOUND_ERROR_CODE,
+            message: format!("Resource not found: {uri}"),
+            data: Some(json!({ "uri": uri })),
+        };
+        let Some(resource) = SessionResource::parse(&uri) else {
+            self.outgoing.send_error(id, not_found).await;
+            return;
+        };
+
+        let (mime_type, text) = match resource {
+            SessionResource::Rollout(conversation_id) => {
+                // Saved sessions that are not loaded can still be read.
+                let path = match self.sessions.get(conversation_id).await {
+                    Some(session) => Some(session.rollout_path),
+                    None => find_conversation_path_by_id_str(
+                        &self.config.llmx_home,
+                        &conversation_id.to_string(),
+                    )
+                    .await
+                    .ok()
+                    .flatten(),
+                };
+                let Some(path) = path else {
+                    self.outgoing.send_error(id, not_found).await;
+                    return;
+                };
+                match tokio::fs::read_to_string(&path).await {
+                    Ok(text) => (ROLLOUT_MIME_TYPE, text),
+                    Err(err) => {
+                        let error = JSONRPCErrorError {
+                            code: INTERNAL_ERROR_CODE,
+                            message: format!("Failed to read {}: {err}", path.display()),
+                            data: None,
+                        };
+                        self.outgoing.send_error(id, error).await;
+                        return;
+                    }
+                }
+            }
+            SessionResource::Diff(conversation_id) => {
+                match self.sessions.get(conversation_id).await {
+                    Some(session) => (DIFF_MIME_TYPE, session.latest_diff.unwrap_or_default()),
+                    None => {
+                        self.outgoing.send_error(id, not_found).await;
+                        return;
+                    }
+                }
+            }
+        };
+
+        let result = ReadResourceResult {
+            contents: vec![ReadResourceResultContents::TextResourceContents(
+                TextResourceContents {
+                    mime_type: Some(mime_type.to_string()),
+                    text,
+                    uri,
+                },
+            )],
+        };
+        self.send_response::<mcp_types::ReadResourceRequest>(id, result)
+            .await;
     }
 
     fn handle_subscribe(
@@ -306,6 +454,9 @@ impl MessageProcessor {
             tools: vec![
                 create_tool_for_llmx_tool_call_param(),
                 create_tool_for_llmx_tool_call_reply_param(),
+                create_tool_for_llmx_list_sessions_param(),
+                create_tool_for_llmx_resume_param(),
+                create_tool_for_llmx_cancel_param(),
             ],
             next_cursor: None,
         };
@@ -318,16 +469,28 @@ impl MessageProcessor {
         &self,
         id: RequestId,
         params: <mcp_types::CallToolRequest as mcp_types::ModelContextProtocolRequest>::Params,
+        progress_token: Option<ProgressToken>,
     ) {
         tracing::info!("tools/call -> params: {:?}", params);
         let CallToolRequestParams { name, arguments } = params;
+        let progress =
+            progress_token.map(|token| ProgressReporter::new(token, self.outgoing.clone()));
 
         match name.as_str() {
-            "llmx" => self.handle_tool_call_llmx(id, arguments).await,
+            "llmx" => self.handle_tool_call_llmx(id, arguments, progress).await,
             "llmx-reply" => {
-                self.handle_tool_call_llmx_session_reply(id, arguments)
+                self.handle_tool_call_llmx_session_reply(id, arguments, progress)
+                    .await
+            }
+            "llmx-list-sessions" => {
+                self.handle_tool_call_llmx_list_sessions(id, arguments)
+                    .await
+            }
+            "llmx-resume" => {
+                self.handle_tool_call_llmx_resume(id, arguments, progress)
                     .await
             }
+            "llmx-cancel" => self.handle_tool_call_llmx_cancel(id, arguments).await,
             _ => {
                 let result = CallToolResult {
                     content: vec![ContentBlock::TextContent(TextContent {
@@ -343,7 +506,12 @@ impl MessageProcessor {
             }
         }
     }
-    async fn handle_tool_call_llmx(&self, id: RequestId, arguments: Option<serde_json::Value>) {
+    async fn handle_tool_call_llmx(
+        &self,
+        id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
         let (initial_prompt, config): (String, Config) = match arguments {
             Some(json_val) => match serde_json::from_value::<LlmxToolCallParam>(json_val) {
                 Ok(tool_cfg) => match tool_cfg
@@ -404,7 +572,7 @@ impl MessageProcessor {
         // Clone outgoing and server to move into async task.
         let outgoing = self.outgoing.clone();
         let conversation_manager = self.conversation_manager.clone();
-        let running_requests_id_to_llmx_uuid = self.running_requests_id_to_llmx_uuid.clone();
+        let sessions = self.sessions.clone();
 
         // Spawn an async task to handle the LLMX session so that we do not
         // block the synchronous message-processing loop.
@@ -416,7 +584,8 @@ impl MessageProcessor {
                 config,
                 outgoing,
                 conversation_manager,
-                running_requests_id_to_llmx_uuid,
+                sessions,
+                progress,
             )
             .await;
         });
@@ -426,6 +595,7 @@ impl MessageProcessor {
         &self,
         request_id: RequestId,
         arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
     ) {
         tracing::info!("tools/call -> params: {:?}", arguments);
 
@@ -491,7 +661,7 @@ impl MessageProcessor {
 
         // Clone outgoing to move into async task.
         let outgoing = self.outgoing.clone();
-        let running_requests_id_to_llmx_uuid = self.running_requests_id_to_llmx_uuid.clone();
+        let sessions = self.sessions.clone();
 
         let llmx = match self
             .conversation_manager
@@ -519,7 +689,7 @@ impl MessageProcessor {
         tokio::spawn({
             let outgoing = outgoing.clone();
             let prompt = prompt.clone();
-            let running_requests_id_to_llmx_uuid = running_requests_id_to_llmx_uuid.clone();
+            let sessions = sessions.clone();
 
             async move {
                 crate::llmx_tool_runner::run_llmx_tool_session_reply(
@@ -527,14 +697,259 @@ impl MessageProcessor {
                     outgoing,
                     request_id,
                     prompt,
-                    running_requests_id_to_llmx_uuid,
+                    sessions,
                     conversation_id,
+                    progress,
                 )
                 .await;
             }
         });
     }
 
+    async fn handle_tool_call_llmx_list_sessions(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let LlmxListSessionsParam { limit, cursor } =
+            match parse_tool_arguments("llmx-list-sessions", arguments) {
+                Ok(params) => params,
+                Err(result) => {
+                    self.outgoing.send_response(request_id, result).await;
+                    return;
+                }
+            };
+
+        let active: Vec<serde_json::Value> = self
+            .sessions
+            .list()
+            .await
+            .into_iter()
+            .map(|session| {
+                json!({
+                    "conversationId": session.conversation_id.to_string(),
+                    "model": session.model,
+                    "rolloutPath": session.rollout_path,
+                    "running": session.running_request.is_some(),
+                })
+            })
+            .collect();
+
+        let cursor = cursor.as_deref().and_then(parse_cursor);
+        let page = match RolloutRecorder::list_conversations(
+            &self.config.llmx_home,
+            limit.unwrap_or(DEFAULT_LIST_SESSIONS_LIMIT).max(1) as usize,
+            cursor.as_ref(),
+            LISTED_SESSION_SOURCES,
+            None,
+            self.config.model_provider_id.as_str(),
+        )
+        .await
+        {
+            Ok(page) => page,
+            Err(err) => {
+                let result = tool_error_result(format!("Failed to list LLMX sessions: {err}"));
+                self.outgoing.send_response(request_id, result).await;
+                return;
+            }
+        };
+        let saved: Vec<serde_json::Value> = page
+            .items
+            .into_iter()
+            .filter_map(saved_session_summary)
+            .collect();
+        let next_cursor = page
+            .next_cursor
+            .and_then(|cursor| serde_json::to_value(&cursor).ok())
+            .and_then(|value| value.as_str().map(str::to_owned));
+
+        let structured = json!({
+            "active": active,
+            "saved": saved,
+            "nextCursor": next_cursor,
+        });
+        self.outgoing
+            .send_response(request_id, tool_json_result(structured))
+            .await;
+    }
+
+    async fn handle_tool_call_llmx_resume(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+        progress: Option<ProgressReporter>,
+    ) {
+        let LlmxResumeParam {
+            conversation_id,
+            prompt,
+        } = match parse_tool_arguments("llmx-resume", arguments) {
+            Ok(params) => params,
+            Err(result) => {
+                self.outgoing.send_response(request_id, result).await;
+                return;
+            }
+        };
+        let conversation_id = match ConversationId::from_string(&conversation_id) {
+            Ok(id) => id,
+            Err(e) => {
+                let result = tool_error_result(format!("Failed to parse conversationId: {e}"));
+                self.outgoing.send_response(request_id, result).await;
+                return;
+            }
+        };
+
+        let conversation = match self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        {
+            Ok(conversation) => conversation,
+            Err(_) => match self
+                .resume_saved_session(&request_id, conversation_id)
+                .await
+            {
+                Ok(conversation) => conversation,
+                Err(result) => {
+                    self.outgoing.send_response(request_id, result).await;
+                    return;
+                }
+            },
+        };
+
+        let Some(prompt) = prompt else {
+            let session = self.sessions.get(conversation_id).await;
+            let result = tool_json_result(json!({
+                "conversationId": conversation_id.to_string(),
+                "model": session.as_ref().map(|session| session.model.clone()),
+                "rolloutPath": session.map(|session| session.rollout_path),
+            }));
+            self.outgoing.send_response(request_id, result).await;
+            return;
+        };
+
+        let outgoing = self.outgoing.clone();
+        let sessions = self.sessions.clone();
+        tokio::spawn(async move {
+            crate::llmx_tool_runner::run_llmx_tool_session_reply(
+                conversation,
+                outgoing,
+                request_id,
+                prompt,
+                sessions,
+                conversation_id,
+                progress,
+            )
+            .await;
+        });
+    }
+
+    /// Loads a saved session from its rollout using the server's config.
+    async fn resume_saved_session(
+        &self,
+        request_id: &RequestId,
+        conversation_id: ConversationId,
+    ) -> Result<Arc<llmx_core::LlmxConversation>, CallToolResult> {
+        let path =
+            find_conversation_path_by_id_str(&self.config.llmx_home, &conversation_id.to_string())
+                .await
+                .map_err(|err| {
+                    tool_error_result(format!(
+                        "Failed to look up LLMX session {conversation_id}: {err}"
+                    ))
+                })?
+                .ok_or_else(|| {
+                    tool_error_result(format!(
+                        "No saved LLMX session found for conversationId: {conversation_id}"
+                    ))
+                })?;
+
+        let NewConversation {
+            conversation_id: resumed_id,
+            conversation,
+            session_configured,
+        } = self
+            .conversation_manager
+            .resume_conversation_from_rollout(
+                self.config.as_ref().clone(),
+                path,
+                self.auth_manager.clone(),
+            )
+            .await
+            .map_err(|err| tool_error_result(format!("Failed to resume LLMX session: {err}")))?;
+
+        let event = Event {
+            id: "".to_string(),
+            msg: EventMsg::SessionConfigured(session_configured.clone()),
+        };
+        self.outgoing
+            .send_event_as_notification(
+                &event,
+                Some(OutgoingNotificationMeta::new(Some(request_id.clone()))),
+            )
+            .await;
+        register_session(
+            &self.sessions,
+            &self.outgoing,
+            resumed_id,
+            session_configured.rollout_path,
+            session_configured.model,
+        )
+        .await;
+        Ok(conversation)
+    }
+
+    async fn handle_tool_call_llmx_cancel(
+        &self,
+        request_id: RequestId,
+        arguments: Option<serde_json::Value>,
+    ) {
+        let LlmxCancelParam { conversation_id } =
+            match parse_tool_arguments("llmx-cancel", arguments) {
+                Ok(params) => params,
+                Err(result) => {
+                    self.outgoing.send_response(request_id, result).await;
+                    return;
+                }
+            };
+        let conversation_id = match ConversationId::from_string(&conversation_id) {
+            Ok(id) => id,
+            Err(e) => {
+                let result = tool_error_result(format!("Failed to parse conversationId: {e}"));
+                self.outgoing.send_response(request_id, result).await;
+                return;
+            }
+        };
+        let Ok(conversation) = self
+            .conversation_manager
+            .get_conversation(conversation_id)
+            .await
+        else {
+            let result = tool_error_result(format!(
+                "Session not found for conversationId: {conversation_id}"
+            ));
+            self.outgoing.send_response(request_id, result).await;
+            return;
+        };
+
+        let running = self
+            .sessions
+            .get(conversation_id)
+            .await
+            .is_some_and(|session| session.running_request.is_some());
+        // The interrupted `tools/call` gets its own error response once the
+        // turn is aborted.
+        if running && let Err(err) = conversation.submit(Op::Interrupt).await {
+            let result = tool_error_result(format!("Failed to interrupt LLMX session: {err}"));
+            self.outgoing.send_response(request_id, result).await;
+            return;
+        }
+        let result = tool_json_result(json!({
+            "conversationId": conversation_id.to_string(),
+            "interrupted": running,
+        }));
+        self.outgoing.send_response(request_id, result).await;
+    }
+
     fn handle_set_level(
         &self,
         params: <mcp_types::SetLevelRequest as mcp_types::ModelContextProtocolRequest>::Params,
@@ -564,17 +979,14 @@ impl MessageProcessor {
             RequestId::Integer(i) => i.to_string(),
         };
 
-        // Obtain the conversation id while holding the first lock, then release.
-        let conversation_id = {
-            let map_guard = self.running_requests_id_to_llmx_uuid.lock().await;
-            match map_guard.get(&request_id) {
-                Some(id) => *id,
-                None => {
-                    tracing::warn!("Session not found for request_id: {}", request_id_string);
-                    return;
-                }
-            }
+        let Some(conversation_id) = self.sessions.conversation_for_request(&request_id).await
+        else {
+            tracing::warn!("Session not found for request_id: {}", request_id_string);
+            return;
         };
+        // Unregister first so the aborted turn does not send a response to a
+        // request the client has already given up on.
+        self.sessions.finish_request(&request_id).await;
         tracing::info!("conversation_id: {conversation_id}");
 
         // Obtain the LLMX conversation from the server.
@@ -599,13 +1011,7 @@ impl MessageProcessor {
             .await;
         if let Err(e) = err {
             tracing::error!("Failed to submit interrupt to LLMX: {e}");
-            return;
         }
-        // unregister the id so we don't keep it in the map
-        self.running_requests_id_to_llmx_uuid
-            .lock()
-            .await
-            .remove(&request_id);
     }
 
     fn handle_progress_notification(
@@ -653,3 +1059,65 @@ impl MessageProcessor {
         tracing::info!("notifications/message -> params: {:?}", params);
     }
 }
+
+fn parse_tool_arguments<T: DeserializeOwned>(
+    tool: &str,
+    arguments: Option<serde_json::Value>,
+) -> Result<T, CallToolResult> {
+    serde_json::from_value(arguments.unwrap_or_else(|| json!({})))
+        .map_err(|e| tool_error_result(format!("Failed to parse arguments for {tool}: {e}")))
+}
+
+fn tool_error_result(text: String) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text,
+            annotations: None,
+        })],
+        is_error: Some(true),
+        structured_content: None,
+    }
+}
+
+/// Returns `value` as structured content, mirrored as JSON text for clients
+/// that only read `content`.
+fn tool_json_result(value: serde_json::Value) -> CallToolResult {
+    CallToolResult {
+        content: vec![ContentBlock::TextContent(TextContent {
+            r#type: "text".to_string(),
+            text: value.to_string(),
+            annotations: None,
+        })],
+        is_error: None,
+        structured_content: Some(value),
+    }
+}
+
+fn saved_session_summary(item: ConversationItem) -> Option<serde_json::Value> {
+    let meta_line = item
+        .head
+        .first()
+        .and_then(|first| serde_json::from_value::<SessionMetaLine>(first.clone()).ok())?;
+    let preview = item
+        .head
+        .iter()
+        .filter_map(|value| serde_json::from_value::<ResponseItem>(value.clone()).ok())
+        .find_map(|item| match llmx_core::parse_turn_item(&item) {
+            Some(TurnItem::UserMessage(user)) => Some(user.message()),
+            _ => None,
+        })
+        .map(|message| match message.find(USER_MESSAGE_BEGIN) {
+            Some(idx) => message[idx + USER_MESSAGE_BEGIN.len()..].trim().to_string(),
+            None => message,
+        });
+    Some(json!({
+        "conversationId": meta_line.meta.id.to_string(),
+        "createdAt": item.created_at,
+        "updatedAt": item.updated_at,
+        "cwd": meta_line.meta.cwd,
+        "source": meta_line.meta.source,
+        "rolloutPath": item.path,
+        "preview": preview,
+    }))
+}
@@ -13,9 +13,10 @@ use mcp_types::ModelContextProtocolRequest;
 use mcp_types::RequestId;
 use serde::Deserialize;
 use serde::Serialize;
-use serde_json::json;
 use tracing::error;
 
+use crate::elicitation::review_decision_from_response;
+use crate::elicitation::review_decision_schema;
 use crate::llmx_tool_runner::INVALID_PARAMS_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 
@@ -35,6 +36,7 @@ pub struct PatchApprovalElicitRequestParams {
     pub llmx_changes: HashMap<PathBuf, FileChange>,
 }
 
+/// Legacy response payload; see [`crate::ExecApprovalResponse`].
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PatchApprovalResponse {
     pub decision: ReviewDecision,
@@ -60,11 +62,7 @@ pub(crate) async fn handle_patch_approval_request(
 
     let params = PatchApprovalElicitRequestParams {
         message: message_lines.join("\n"),
-        requested_schema: ElicitRequestParamsRequestedSchema {
-            r#type: "object".to_string(),
-            properties: json!({}),
-            required: None,
-        },
+        requested_schema: review_decision_schema(),
         llmx_elicitation: "patch-approval".to_string(),
         llmx_mcp_tool_call_id: tool_call_id.clone(),
         llmx_event_id: event_id.clone(),
@@ -131,17 +129,12 @@ pub(crate) async fn on_patch_approval_response(
         }
     };
 
-    let response = serde_json::from_value::<PatchApprovalResponse>(value).unwrap_or_else(|err| {
-        error!("failed to deserialize PatchApprovalResponse: {err}");
-        PatchApprovalResponse {
-            decision: ReviewDecision::Denied,
-        }
-    });
+    let decision = review_decision_from_response(value);
 
     if let Err(err) = llmx
         .submit(Op::PatchApproval {
             id: event_id,
-            decision: response.decision,
+            decision,
         })
         .await
     {
@@ -0,0 +1,201 @@
+//! Translates LLMX events into MCP `notifications/progress` messages for
+//! clients that attach a `progressToken` to their `tools/call` request.
+
+use std::sync::Arc;
+
+use llmx_core::protocol::EventMsg;
+use llmx_protocol::plan_tool::StepStatus;
+use mcp_types::ModelContextProtocolNotification;
+use mcp_types::ProgressNotification;
+use mcp_types::ProgressNotificationParams;
+use mcp_types::ProgressToken;
+use serde_json::Value;
+
+use crate::outgoing_message::OutgoingMessageSender;
+use crate::outgoing_message::OutgoingNotification;
+
+/// Agent messages longer than this are truncated in progress messages.
+const MAX_MESSAGE_CHARS: usize = 200;
+
+/// Extracts the `_meta.progressToken` from raw `tools/call` params.
+pub(crate) fn progress_token_from_params(params: Option<&Value>) -> Option<ProgressToken> {
+    let token = params?.get("_meta")?.get("progressToken")?;
+    serde_json::from_value(token.clone()).ok()
+}
+
+pub(crate) struct ProgressReporter {
+    token: ProgressToken,
+    outgoing: Arc<OutgoingMessageSender>,
+    progress: f64,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(token: ProgressToken, outgoing: Arc<OutgoingMessageSender>) -> Self {
+        Self {
+            token,
+            outgoing,
+            progress: 0.0,
+        }
+    }
+
+    /// Sends a progress notification for `msg` if it is worth surfacing.
+    /// The total is unknown, so `progress` simply increases by one per update.
+    pub(crate) async fn report(&mut self, msg: &EventMsg) {
+        let Some(message) = progress_message(msg) else {
+            return;
+        };
+        self.progress += 1.0;
+        let params = ProgressNotificationParams {
+            message: Some(message),
+            progress: self.progress,
+            progress_token: self.token.clone(),
+            total: None,
+        };
+        let params = match serde_json::to_value(params) {
+            Ok(params) => params,
+            Err(err) => {
+                tracing::error!("failed to serialize progress notification: {err}");
+                return;
+            }
+        };
+        self.outgoing
+            .send_notification(OutgoingNotification {
+                method: ProgressNotification::METHOD.to_string(),
+                params: Some(params),
+            })
+            .await;
+    }
+}
+
+fn progress_message(msg: &EventMsg) -> Option<String> {
+    let message = match msg {
+        EventMsg::TaskStarted(_) => "Working".to_string(),
+        EventMsg::AgentMessage(event) => truncate(&event.message),
+        EventMsg::AgentReasoning(event) => format!("Thinking: {}", truncate(&event.text)),
+        EventMsg::ExecCommandBegin(event) => format!("Running `{}`", join_command(&event.command)),
+        EventMsg::ExecCommandEnd(event) => {
+            format!("Command exited with code {}", event.exit_code)
+        }
+        EventMsg::PatchApplyBegin(event) => {
+            let files = event.changes.len();
+            let noun = if files == 1 { "file" } else { "files" };
+            format!("Applying patch to {files} {noun}")
+        }
+        EventMsg::PatchApplyEnd(event) if event.success => "Patch applied".to_string(),
+        EventMsg::PatchApplyEnd(_) => "Patch failed to apply".to_string(),
+        EventMsg::McpToolCallBegin(event) => format!(
+            "Calling {}.{}",
+            event.invocation.server, event.invocation.tool
+        ),
+        EventMsg::WebSearchEnd(event) => format!("Searched the web for \"{}\"", event.query),
+        EventMsg::PlanUpdate(args) => {
+            let done = args
+                .plan
+                .iter()
+                .filter(|item| matches!(item.status, StepStatus::Completed))
+                .count();
+            format!("Plan: {done}/{} steps done", args.plan.len())
+        }
+        EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_) => {
+            "Waiting for approval".to_string()
+        }
+        EventMsg::StreamError(event) => event.message.clone(),
+        EventMsg::TaskComplete(_) => "Done".to_string(),
+        _ => return None,
+    };
+    Some(message)
+}
+
+fn join_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= MAX_MESSAGE_CHARS {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(MAX_MESSAGE_CHARS).collect();
+    format!("{truncated}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llmx_core::protocol::AgentMessageEvent;
+    use llmx_core::protocol::ExecCommandBeginEvent;
+    use llmx_protocol::plan_tool::PlanItemArg;
+    use llmx_protocol::plan_tool::UpdatePlanArgs;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn reads_progress_token_from_meta() {
+        let params = json!({
+            "name": "llmx",
+            "arguments": {},
+            "_meta": { "progressToken": "tok-1" },
+        });
+        assert_eq!(
+            progress_token_from_params(Some(&params)),
+            Some(ProgressToken::String("tok-1".to_string()))
+        );
+        assert_eq!(
+            progress_token_from_params(Some(&json!({"name": "llmx"}))),
+            None
+        );
+        assert_eq!(progress_token_from_params(None), None);
+    }
+
+    #[test]
+    fn describes_interesting_events() {
+        let exec = EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+            call_id: "c1".to_string(),
+            command: vec![
+                "git".to_string(),
+                "commit".to_string(),
+                "-m".to_string(),
+                "a b".to_string(),
+            ],
+            cwd: PathBuf::from("/tmp"),
+            parsed_cmd: Vec::new(),
+            is_user_shell_command: false,
+        });
+        assert_eq!(
+            progress_message(&exec),
+            Some("Running `git commit -m 'a b'`".to_string())
+        );
+
+        let plan = EventMsg::PlanUpdate(UpdatePlanArgs {
+            explanation: None,
+            plan: vec![
+                PlanItemArg {
+                    step: "one".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "two".to_string(),
+                    status: StepStatus::InProgress,
+                },
+            ],
+        });
+        assert_eq!(
+            progress_message(&plan),
+            Some("Plan: 1/2 steps done".to_string())
+        );
+
+        let long = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "x".repeat(MAX_MESSAGE_CHARS + 10),
+        });
+        assert_eq!(
+            progress_message(&long),
+            Some(format!("{}…", "x".repeat(MAX_MESSAGE_CHARS)))
+        );
+    }
+
+    #[test]
+    fn skips_noisy_events() {
+        assert_eq!(progress_message(&EventMsg::ShutdownComplete), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Book-keeping for the conversations started or resumed through this MCP
+//! server: which `tools/call` request is driving each conversation, where its
+//! rollout lives on disk, and the most recent turn diff.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use llmx_protocol::ConversationId;
+use mcp_types::RequestId;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SessionInfo {
+    pub conversation_id: ConversationId,
+    pub rollout_path: PathBuf,
+    pub model: String,
+    /// The `tools/call` request currently driving a turn, if any.
+    pub running_request: Option<RequestId>,
+    /// Unified diff reported by the most recent `TurnDiff` event.
+    pub latest_diff: Option<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct SessionRegistry {
+    running_requests: Mutex<HashMap<RequestId, ConversationId>>,
+    sessions: Mutex<HashMap<ConversationId, SessionInfo>>,
+}
+
+impl SessionRegistry {
+    /// Records a conversation loaded in this server. Returns `true` when the
+    /// conversation was not known before.
+    pub(crate) async fn add_session(
+        &self,
+        conversation_id: ConversationId,
+        rollout_path: PathBuf,
+        model: String,
+    ) -> bool {
+        let mut sessions = self.sessions.lock().await;
+        if sessions.contains_key(&conversation_id) {
+            return false;
+        }
+        sessions.insert(
+            conversation_id,
+            SessionInfo {
+                conversation_id,
+                rollout_path,
+                model,
+                running_request: None,
+                latest_diff: None,
+            },
+        );
+        true
+    }
+
+    pub(crate) async fn start_request(
+        &self,
+        request_id: RequestId,
+        conversation_id: ConversationId,
+    ) {
+        self.running_requests
+            .lock()
+            .await
+            .insert(request_id.clone(), conversation_id);
+        if let Some(session) = self.sessions.lock().await.get_mut(&conversation_id) {
+            session.running_request = Some(request_id);
+        }
+    }
+
+    /// Unregisters `request_id`. Returns `false` when the request was already
+    /// unregistered, e.g. because the client cancelled it.
+    pub(crate) async fn finish_request(&self, request_id: &RequestId) -> bool {
+        let Some(conversation_id) = self.running_requests.lock().await.remove(request_id) else {
+            return false;
+        };
+        if let Some(session) = self.sessions.lock().await.get_mut(&conversation_id)
+            && session.running_request.as_ref() == Some(request_id)
+        {
+            session.running_request = None;
+        }
+        true
+    }
+
+    pub(crate) async fn conversation_for_request(
+        &self,
+        request_id: &RequestId,
+    ) -> Option<ConversationId> {
+        self.running_requests.lock().await.get(request_id).copied()
+    }
+
+    pub(crate) async fn set_diff(&self, conversation_id: ConversationId, unified_diff: String) {
+        if let Some(session) = self.sessions.lock().await.get_mut(&conversation_id) {
+            session.latest_diff = Some(unified_diff);
+        }
+    }
+
+    pub(crate) async fn get(&self, conversation_id: ConversationId) -> Option<SessionInfo> {
+        self.sessions.lock().await.get(&conversation_id).cloned()
+    }
+
+    /// All loaded sessions, ordered by conversation id so listings are stable.
+    pub(crate) async fn list(&self) -> Vec<SessionInfo> {
+        let mut sessions: Vec<SessionInfo> = self.sessions.lock().await.values().cloned().collect();
+        sessions.sort_by_key(|session| session.conversation_id.to_string());
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn tracks_running_request_per_session() {
+        let registry = SessionRegistry::default();
+        let conversation_id = ConversationId::new();
+        let request_id = RequestId::Integer(7);
+
+        assert!(
+            registry
+                .add_session(
+                    conversation_id,
+                    PathBuf::from("/tmp/rollout.jsonl"),
+                    "m".into()
+                )
+                .await
+        );
+        assert!(
+            !registry
+                .add_session(
+                    conversation_id,
+                    PathBuf::from("/tmp/other.jsonl"),
+                    "m".into()
+                )
+                .await
+        );
+
+        registry
+            .start_request(request_id.clone(), conversation_id)
+            .await;
+        assert_eq!(
+            registry.conversation_for_request(&request_id).await,
+            Some(conversation_id)
+        );
+        assert_eq!(
+            registry
+                .get(conversation_id)
+                .await
+                .and_then(|session| session.running_request),
+            Some(request_id.clone())
+        );
+
+        assert!(registry.finish_request(&request_id).await);
+        assert!(!registry.finish_request(&request_id).await);
+        let session = registry.get(conversation_id).await.expect("session");
+        assert_eq!(session.running_request, None);
+        assert_eq!(session.rollout_path, PathBuf::from("/tmp/rollout.jsonl"));
+    }
+}
@@ -0,0 +1,111 @@
+//! MCP resources exposed for each session: the rollout transcript and the
+//! latest turn diff, addressed as `llmx://sessions/{conversationId}/rollout`
+//! and `llmx://sessions/{conversationId}/diff`.
+
+use llmx_protocol::ConversationId;
+use mcp_types::Resource;
+use mcp_types::ResourceTemplate;
+
+use crate::session_registry::SessionInfo;
+
+const URI_PREFIX: &str = "llmx://sessions/";
+pub(crate) const ROLLOUT_MIME_TYPE: &str = "application/jsonl";
+pub(crate) const DIFF_MIME_TYPE: &str = "text/x-diff";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SessionResource {
+    Rollout(ConversationId),
+    Diff(ConversationId),
+}
+
+impl SessionResource {
+    pub(crate) fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix(URI_PREFIX)?;
+        let (id, kind) = rest.split_once('/')?;
+        let conversation_id = ConversationId::from_string(id).ok()?;
+        match kind {
+            "rollout" => Some(Self::Rollout(conversation_id)),
+            "diff" => Some(Self::Diff(conversation_id)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn uri(&self) -> String {
+        match self {
+            Self::Rollout(id) => format!("{URI_PREFIX}{id}/rollout"),
+            Self::Diff(id) => format!("{URI_PREFIX}{id}/diff"),
+        }
+    }
+}
+
+pub(crate) fn resources_for_session(session: &SessionInfo) -> Vec<Resource> {
+    let id = session.conversation_id;
+    vec![
+        Resource {
+            annotations: None,
+            description: Some(format!("Rollout (JSONL transcript) of LLMX session {id}.")),
+            mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            name: format!("{id}/rollout"),
+            size: None,
+            title: Some("Session rollout".to_string()),
+            uri: SessionResource::Rollout(id).uri(),
+        },
+        Resource {
+            annotations: None,
+            description: Some(format!(
+                "Unified diff of the changes made by LLMX session {id} in its latest turn."
+            )),
+            mime_type: Some(DIFF_MIME_TYPE.to_string()),
+            name: format!("{id}/diff"),
+            size: None,
+            title: Some("Session diff".to_string()),
+            uri: SessionResource::Diff(id).uri(),
+        },
+    ]
+}
+
+pub(crate) fn session_resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        ResourceTemplate {
+            annotations: None,
+            description: Some("Rollout (JSONL transcript) of any saved LLMX session.".to_string()),
+            mime_type: Some(ROLLOUT_MIME_TYPE.to_string()),
+            name: "session-rollout".to_string(),
+            title: Some("Session rollout".to_string()),
+            uri_template: format!("{URI_PREFIX}{{conversationId}}/rollout"),
+        },
+        ResourceTemplate {
+            annotations: None,
+            description: Some(
+                "Latest turn diff of an LLMX session loaded in this server.".to_string(),
+            ),
+            mime_type: Some(DIFF_MIME_TYPE.to_string()),
+            name: "session-diff".to_string(),
+            title: Some("Session diff".to_string()),
+            uri_template: format!("{URI_PREFIX}{{conversationId}}/diff"),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn session_uris_round_trip() {
+        let id = ConversationId::new();
+        for resource in [SessionResource::Rollout(id), SessionResource::Diff(id)] {
+            assert_eq!(SessionResource::parse(&resource.uri()), Some(resource));
+        }
+        assert_eq!(
+            SessionResource::parse(&format!("llmx://sessions/{id}/unknown")),
+            None
+        );
+        assert_eq!(
+            SessionResource::parse("llmx://sessions/not-a-uuid/diff"),
+            None
+        );
+        assert_eq!(SessionResource::parse("file:///tmp/diff"), None);
+    }
+}
@@ -0,0 +1,183 @@
+//! Parameters and `Tool` definitions for the session management tools:
+//! `llmx-list-sessions`, `llmx-resume`, and `llmx-cancel`.
+
+use mcp_types::Tool;
+use mcp_types::ToolInputSchema;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmxListSessionsParam {
+    /// Maximum number of saved sessions to return (default 20).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Opaque cursor returned as `nextCursor` by a previous call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmxResumeParam {
+    /// The conversation id of a saved or running LLMX session.
+    pub conversation_id: String,
+
+    /// Optional prompt to send once the session is loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmxCancelParam {
+    /// The conversation id whose running turn should be interrupted.
+    pub conversation_id: String,
+}
+
+/// Builds a `Tool` definition for the `llmx-list-sessions` tool-call.
+pub(crate) fn create_tool_for_llmx_list_sessions_param() -> Tool {
+    Tool {
+        name: "llmx-list-sessions".to_string(),
+        title: Some("LLMX Sessions".to_string()),
+        input_schema: input_schema_for::<LlmxListSessionsParam>(),
+        output_schema: None,
+        description: Some(
+            "List LLMX sessions loaded in this server and sessions saved on disk.".to_string(),
+        ),
+        annotations: None,
+    }
+}
+
+/// Builds a `Tool` definition for the `llmx-resume` tool-call.
+pub(crate) fn create_tool_for_llmx_resume_param() -> Tool {
+    Tool {
+        name: "llmx-resume".to_string(),
+        title: Some("LLMX Resume".to_string()),
+        input_schema: input_schema_for::<LlmxResumeParam>(),
+        output_schema: None,
+        description: Some(
+            "Load a saved LLMX session by conversation id and optionally continue it with a prompt."
+                .to_string(),
+        ),
+        annotations: None,
+    }
+}
+
+/// Builds a `Tool` definition for the `llmx-cancel` tool-call.
+pub(crate) fn create_tool_for_llmx_cancel_param() -> Tool {
+    Tool {
+        name: "llmx-cancel".to_string(),
+        title: Some("LLMX Cancel".to_string()),
+        input_schema: input_schema_for::<LlmxCancelParam>(),
+        output_schema: None,
+        description: Some("Interrupt the running turn of an LLMX session.".to_string()),
+        annotations: None,
+    }
+}
+
+fn input_schema_for<T: JsonSchema>() -> ToolInputSchema {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    #[expect(clippy::expect_used)]
+    let schema_value =
+        serde_json::to_value(&schema).expect("LLMX session tool schema should serialise to JSON");
+
+    serde_json::from_value::<ToolInputSchema>(schema_value).unwrap_or_else(|e| {
+        panic!("failed to create Tool from schema: {e}");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn verify_llmx_list_sessions_json_schema() {
+        let tool = create_tool_for_llmx_list_sessions_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "List LLMX sessions loaded in this server and sessions saved on disk.",
+          "inputSchema": {
+            "properties": {
+              "cursor": {
+                "description": "Opaque cursor returned as `nextCursor` by a previous call.",
+                "type": "string"
+              },
+              "limit": {
+                "description": "Maximum number of saved sessions to return (default 20).",
+                "format": "uint32",
+                "minimum": 0.0,
+                "type": "integer"
+              },
+            },
+            "type": "object",
+          },
+          "name": "llmx-list-sessions",
+          "title": "LLMX Sessions",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+
+    #[test]
+    fn verify_llmx_resume_json_schema() {
+        let tool = create_tool_for_llmx_resume_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Load a saved LLMX session by conversation id and optionally continue it with a prompt.",
+          "inputSchema": {
+            "properties": {
+              "conversationId": {
+                "description": "The conversation id of a saved or running LLMX session.",
+                "type": "string"
+              },
+              "prompt": {
+                "description": "Optional prompt to send once the session is loaded.",
+                "type": "string"
+              },
+            },
+            "required": [
+              "conversationId",
+            ],
+            "type": "object",
+          },
+          "name": "llmx-resume",
+          "title": "LLMX Resume",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+
+    #[test]
+    fn verify_llmx_cancel_json_schema() {
+        let tool = create_tool_for_llmx_cancel_param();
+        let tool_json = serde_json::to_value(&tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Interrupt the running turn of an LLMX session.",
+          "inputSchema": {
+            "properties": {
+              "conversationId": {
+                "description": "The conversation id whose running turn should be interrupted.",
+                "type": "string"
+              },
+            },
+            "required": [
+              "conversationId",
+            ],
+            "type": "object",
+          },
+          "name": "llmx-cancel",
+          "title": "LLMX Cancel",
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+}
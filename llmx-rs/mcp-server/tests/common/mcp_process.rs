@@ -156,6 +156,9 @@ impl McpProcess {
                 id: RequestId::Integer(request_id),
                 result: json!({
                     "capabilities": {
+                        "resources": {
+                            "listChanged": true
+                        },
                         "tools": {
                             "listChanged": true
                         },
@@ -197,6 +200,38 @@ impl McpProcess {
         .await
     }
 
+    /// Calls tool `name`, optionally asking for `notifications/progress`
+    /// updates under `progress_token`.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+        progress_token: Option<&str>,
+    ) -> anyhow::Result<i64> {
+        let mut params = serde_json::to_value(CallToolRequestParams {
+            name: name.to_string(),
+            arguments: Some(arguments),
+        })?;
+        if let Some(token) = progress_token {
+            params["_meta"] = json!({ "progressToken": token });
+        }
+        self.send_request(mcp_types::CallToolRequest::METHOD, Some(params))
+            .await
+    }
+
+    pub async fn send_list_resources_request(&mut self) -> anyhow::Result<i64> {
+        self.send_request(mcp_types::ListResourcesRequest::METHOD, Some(json!({})))
+            .await
+    }
+
+    pub async fn send_read_resource_request(&mut self, uri: &str) -> anyhow::Result<i64> {
+        self.send_request(
+            mcp_types::ReadResourceRequest::METHOD,
+            Some(json!({ "uri": uri })),
+        )
+        .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -294,6 +329,32 @@ impl McpProcess {
         }
     }
 
+    /// Like [`Self::read_stream_until_response_message`], but also returns the
+    /// notifications received before the response.
+    pub async fn read_stream_until_response_with_notifications(
+        &mut self,
+        request_id: RequestId,
+    ) -> anyhow::Result<(JSONRPCResponse, Vec<JSONRPCNotification>)> {
+        let mut notifications = Vec::new();
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(notification) => notifications.push(notification),
+                JSONRPCMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JSONRPCMessage::Error(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Error: {message:?}");
+                }
+                JSONRPCMessage::Response(jsonrpc_response) => {
+                    if jsonrpc_response.id == request_id {
+                        return Ok((jsonrpc_response, notifications));
+                    }
+                }
+            }
+        }
+    }
+
     /// Reads notifications until a legacy TaskComplete event is observed:
     /// Method "llmx/event" with params.msg.type == "task_complete".
     pub async fn read_stream_until_legacy_task_complete_notification(
@@ -8,12 +8,12 @@ use llmx_core::protocol::FileChange;
 use llmx_core::protocol::ReviewDecision;
 use llmx_core::spawn::LLMX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use llmx_mcp_server::ExecApprovalElicitRequestParams;
-use llmx_mcp_server::ExecApprovalResponse;
 use llmx_mcp_server::LlmxToolCallParam;
 use llmx_mcp_server::PatchApprovalElicitRequestParams;
 use llmx_mcp_server::PatchApprovalResponse;
 use mcp_types::ElicitRequest;
 use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::ElicitResult;
 use mcp_types::JSONRPC_VERSION;
 use mcp_types::JSONRPCRequest;
 use mcp_types::JSONRPCResponse;
@@ -122,8 +122,9 @@ async fn shell_command_approval_triggers_elicitation() -> anyhow::Result<()> {
     mcp_process
         .send_response(
             elicitation_request_id,
-            serde_json::to_value(ExecApprovalResponse {
-                decision: ReviewDecision::Approved,
+            serde_json::to_value(ElicitResult {
+                action: "accept".to_string(),
+                content: Some(json!({ "decision": "approved" })),
             })?,
         )
         .await?;
@@ -184,11 +185,7 @@ fn create_expected_elicitation_request(
         method: ElicitRequest::METHOD.to_string(),
         params: Some(serde_json::to_value(&ExecApprovalElicitRequestParams {
             message: expected_message,
-            requested_schema: ElicitRequestParamsRequestedSchema {
-                r#type: "object".to_string(),
-                properties: json!({}),
-                required: None,
-            },
+            requested_schema: expected_review_decision_schema(),
             llmx_elicitation: "exec-approval".to_string(),
             llmx_mcp_tool_call_id,
             llmx_event_id,
@@ -201,6 +198,28 @@ fn create_expected_elicitation_request(
     })
 }
 
+/// Approval elicitations ask for a single `decision` field so generic MCP
+/// clients can render them as a form.
+fn expected_review_decision_schema() -> ElicitRequestParamsRequestedSchema {
+    ElicitRequestParamsRequestedSchema {
+        r#type: "object".to_string(),
+        properties: json!({
+            "decision": {
+                "type": "string",
+                "title": "Decision",
+                "enum": ["approved", "approved_for_session", "denied", "abort"],
+                "enumNames": [
+                    "Approve",
+                    "Approve for this session",
+                    "Deny",
+                    "Deny and stop the turn",
+                ],
+            }
+        }),
+        required: Some(vec!["decision".to_string()]),
+    }
+}
+
 /// Test that patch approval triggers an elicitation request to the MCP and that
 /// sending the approval applies the patch, as expected.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -272,7 +291,8 @@ async fn patch_approval_triggers_elicitation() -> anyhow::Result<()> {
     )?;
     assert_eq!(expected_elicitation_request, elicitation_request);
 
-    // Accept the patch approval request by responding to the elicitation
+    // Accept the patch approval request with the legacy `{ decision }` payload,
+    // which is still honored alongside standard elicitation results.
     mcp_process
         .send_response(
             elicitation_request_id,
@@ -393,6 +413,117 @@ async fn llmx_tool_passes_base_instructions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_llmx_tool_reports_progress_and_exposes_session() {
+    skip_if_no_network!();
+
+    if let Err(err) = llmx_tool_reports_progress_and_exposes_session().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn llmx_tool_reports_progress_and_exposes_session() -> anyhow::Result<()> {
+    let McpHandle {
+        process: mut mcp_process,
+        server: _server,
+        dir: _dir,
+    } = create_mcp_process(vec![create_final_assistant_message_sse_response(
+        "All done.",
+    )?])
+    .await?;
+
+    let llmx_request_id = mcp_process
+        .send_tool_call("llmx", json!({ "prompt": "say hi" }), Some("progress-1"))
+        .await?;
+    let (llmx_response, notifications) = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process
+            .read_stream_until_response_with_notifications(RequestId::Integer(llmx_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        llmx_response.result,
+        json!({ "content": [{ "text": "All done.", "type": "text" }] })
+    );
+
+    let progress: Vec<&serde_json::Value> = notifications
+        .iter()
+        .filter(|notification| notification.method == "notifications/progress")
+        .filter_map(|notification| notification.params.as_ref())
+        .collect();
+    assert!(
+        progress
+            .iter()
+            .all(|params| params["progressToken"] == json!("progress-1")),
+        "unexpected progress notifications: {progress:?}"
+    );
+    assert_eq!(
+        progress.last().map(|params| params["message"].clone()),
+        Some(json!("Done"))
+    );
+    assert!(
+        notifications
+            .iter()
+            .any(|notification| notification.method == "notifications/resources/list_changed")
+    );
+    let conversation_id = notifications
+        .iter()
+        .filter_map(|notification| notification.params.as_ref())
+        .find(|params| params["msg"]["type"] == json!("session_configured"))
+        .and_then(|params| params["msg"]["session_id"].as_str())
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow::anyhow!("missing session_configured notification"))?;
+
+    let list_id = mcp_process.send_list_resources_request().await?;
+    let resources = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(list_id)),
+    )
+    .await??;
+    let rollout_uri = format!("llmx://sessions/{conversation_id}/rollout");
+    let diff_uri = format!("llmx://sessions/{conversation_id}/diff");
+    let uris: Vec<&str> = resources.result["resources"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|resource| resource["uri"].as_str())
+        .collect();
+    assert_eq!(uris, vec![rollout_uri.as_str(), diff_uri.as_str()]);
+
+    let read_id = mcp_process.send_read_resource_request(&rollout_uri).await?;
+    let rollout = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(read_id)),
+    )
+    .await??;
+    let contents = &rollout.result["contents"][0];
+    assert_eq!(contents["mimeType"], json!("application/jsonl"));
+    assert!(
+        contents["text"]
+            .as_str()
+            .is_some_and(|text| text.contains(&conversation_id))
+    );
+
+    let list_sessions_id = mcp_process
+        .send_tool_call("llmx-list-sessions", json!({}), None)
+        .await?;
+    let sessions = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Integer(list_sessions_id)),
+    )
+    .await??;
+    assert_eq!(
+        sessions.result["structuredContent"]["active"][0]["conversationId"],
+        json!(conversation_id)
+    );
+    assert_eq!(
+        sessions.result["structuredContent"]["active"][0]["running"],
+        json!(false)
+    );
+
+    Ok(())
+}
+
 fn create_expected_patch_approval_elicitation_request(
     elicitation_request_id: RequestId,
     changes: HashMap<PathBuf, FileChange>,
@@ -413,11 +544,7 @@ fn create_expected_patch_approval_elicitation_request(
         method: ElicitRequest::METHOD.to_string(),
         params: Some(serde_json::to_value(&PatchApprovalElicitRequestParams {
             message: message_lines.join("\n"),
-            requested_schema: ElicitRequestParamsRequestedSchema {
-                r#type: "object".to_string(),
-                properties: json!({}),
-                required: None,
-            },
+            requested_schema: expected_review_decision_schema(),
             llmx_elicitation: "patch-approval".to_string(),
             llmx_mcp_tool_call_id,
             llmx_event_id,
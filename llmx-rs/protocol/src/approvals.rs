@@ -61,3 +61,42 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
 }
+
+/// What a persistent approval rule matches against.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRuleKind {
+    /// Commands, matched by a glob such as `cargo test *`.
+    Command,
+    /// Patches, matched by a glob over the project-relative paths they touch.
+    Patch,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalRuleAction {
+    /// Approve matching requests without asking.
+    Allow,
+    /// Reject matching requests without asking.
+    Deny,
+}
+
+/// A rule saved in a project's `approvals.toml`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, JsonSchema, TS)]
+pub struct ApprovalRule {
+    pub kind: ApprovalRuleKind,
+    pub action: ApprovalRuleAction,
+    pub pattern: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ApprovalRuleEntry {
+    pub rule: ApprovalRule,
+    /// The `approvals.toml` the rule is stored in.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListApprovalRulesResponseEvent {
+    pub rules: Vec<ApprovalRuleEntry>,
+}
@@ -1,6 +1,8 @@
+use crate::config_types::ReasoningEffort as ReasoningEffortConfig;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use ts_rs::TS;
 
@@ -10,11 +12,234 @@ use ts_rs::TS;
 /// - Full slash prefix: `"/{PROMPTS_CMD_PREFIX}:"`
 pub const PROMPTS_CMD_PREFIX: &str = "prompts";
 
-#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, TS)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, TS)]
 pub struct CustomPrompt {
     pub name: String,
     pub path: PathBuf,
     pub content: String,
     pub description: Option<String>,
     pub argument_hint: Option<String>,
+    /// Named arguments declared in the frontmatter `arguments:` block.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<PromptArgument>,
+    /// Model used for the turn started by this prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model: Option<String>,
+    /// Reasoning effort used for the turn started by this prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    /// When set, only these tools are offered to the model for the turn
+    /// started by this prompt. A trailing `*` matches any suffix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl CustomPrompt {
+    /// Returns the declared argument called `name`, if any.
+    pub fn argument(&self, name: &str) -> Option<&PromptArgument> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+
+    /// Whether invoking this prompt changes the model, reasoning effort, or
+    /// tools of the turn it starts.
+    pub fn has_turn_overrides(&self) -> bool {
+        self.model.is_some() || self.reasoning_effort.is_some() || self.allowed_tools.is_some()
+    }
+}
+
+/// Argument values supplied when invoking a custom prompt.
+///
+/// The session substitutes them into the prompt body as plain text after the
+/// body's own `@path` includes and `` !`command` `` blocks are expanded, so an
+/// argument never becomes part of a command or an include.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromptArguments {
+    /// Values for `$NAME` placeholders, already checked against the
+    /// declared arguments.
+    Named { values: HashMap<String, String> },
+    /// Values for `$1`..`$9` and `$ARGUMENTS`.
+    Positional { values: Vec<String> },
+}
+
+impl Default for PromptArguments {
+    fn default() -> Self {
+        Self::Positional { values: Vec::new() }
+    }
+}
+
+impl PromptArguments {
+    /// Replaces the placeholders in `text` with these values.
+    pub fn substitute(&self, text: &str) -> String {
+        match self {
+            Self::Named { values } => substitute_named(text, values),
+            Self::Positional { values } => substitute_positional(text, values),
+        }
+    }
+}
+
+/// Replaces `$NAME` placeholders (an uppercase letter followed by uppercase
+/// letters, digits, or `_`) that have a value. `$$NAME` is left alone.
+fn substitute_named(text: &str, values: &HashMap<String, String>) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while let Some(off) = text[i..].find('$') {
+        let start = i + off;
+        let name_start = start + 1;
+        let mut end = name_start;
+        if bytes.get(end).is_some_and(u8::is_ascii_uppercase) {
+            end += 1;
+            while bytes
+                .get(end)
+                .is_some_and(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || *b == b'_')
+            {
+                end += 1;
+            }
+        }
+        let escaped = start > 0 && bytes[start - 1] == b'$';
+        if end > name_start
+            && !escaped
+            && let Some(value) = values.get(&text[name_start..end])
+        {
+            out.push_str(&text[copied..start]);
+            out.push_str(value);
+            copied = end;
+        }
+        i = end.max(name_start);
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+/// Replaces `$1`..`$9` with the matching value (or nothing) and `$ARGUMENTS`
+/// with all values joined by spaces. `$$` is kept as written.
+fn substitute_positional(text: &str, args: &[String]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while let Some(off) = text[i..].find('$') {
+        let j = i + off;
+        out.push_str(&text[i..j]);
+        let rest = &text[j..];
+        match rest.as_bytes().get(1) {
+            Some(b'$') => {
+                out.push_str("$$");
+                i = j + 2;
+                continue;
+            }
+            Some(digit @ b'1'..=b'9') => {
+                if let Some(value) = args.get(usize::from(digit - b'1')) {
+                    out.push_str(value);
+                }
+                i = j + 2;
+                continue;
+            }
+            _ => {}
+        }
+        if rest[1..].starts_with("ARGUMENTS") {
+            out.push_str(&args.join(" "));
+            i = j + 1 + "ARGUMENTS".len();
+            continue;
+        }
+        out.push('$');
+        i = j + 1;
+    }
+    out.push_str(&text[i..]);
+    out
+}
+
+/// A named `$NAME` argument declared by a prompt.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, TS)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub kind: PromptArgumentType,
+    /// Value used when the argument is omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub default: Option<String>,
+    /// Whether the invocation fails when the argument is omitted and has no
+    /// default.
+    #[serde(default)]
+    pub required: bool,
+    /// Accepted values for [`PromptArgumentType::Enum`] arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptArgumentType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+    Enum,
+}
+
+impl PromptArgument {
+    /// Checks `value` against the declared type, returning the normalized
+    /// value on success and a short explanation otherwise.
+    pub fn validate(&self, value: &str) -> Result<String, String> {
+        match self.kind {
+            PromptArgumentType::String => Ok(value.to_string()),
+            PromptArgumentType::Number => value
+                .trim()
+                .parse::<f64>()
+                .map(|_| value.trim().to_string())
+                .map_err(|_| format!("expected a number but got '{value}'")),
+            PromptArgumentType::Boolean => match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+                "false" | "no" | "off" | "0" => Ok("false".to_string()),
+                _ => Err(format!("expected true or false but got '{value}'")),
+            },
+            PromptArgumentType::Enum => {
+                if self.choices.iter().any(|choice| choice == value) {
+                    Ok(value.to_string())
+                } else {
+                    Err(format!(
+                        "expected one of {} but got '{value}'",
+                        self.choices.join(", ")
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_arguments_replace_known_placeholders_only() {
+        let arguments = PromptArguments::Named {
+            values: HashMap::from([
+                ("USER".to_string(), "Alice".to_string()),
+                ("TICKET".to_string(), "!`rm -rf /` @.env".to_string()),
+            ]),
+        };
+        assert_eq!(
+            arguments.substitute("Hi $USER, fix $TICKET ($$USER, $OTHER, $lower)"),
+            "Hi Alice, fix !`rm -rf /` @.env ($$USER, $OTHER, $lower)"
+        );
+    }
+
+    #[test]
+    fn positional_arguments_fill_numbers_and_arguments() {
+        let arguments = PromptArguments::Positional {
+            values: vec!["a".to_string(), "b c".to_string()],
+        };
+        assert_eq!(
+            arguments.substitute("$1|$2|$3|$ARGUMENTS|$$1|$x"),
+            "a|b c||a b c|$$1|$x"
+        );
+    }
 }
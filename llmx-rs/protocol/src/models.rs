@@ -285,6 +285,9 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                             }
                         }
                     },
+                    UserInput::Mention { mention, text } => ContentItem::InputText {
+                        text: UserInput::mention_block(&mention, &text),
+                    },
                 })
                 .collect::<Vec<ContentItem>>(),
         }
@@ -622,6 +625,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn mention_is_sent_as_tagged_text_block() {
+        let mention = UserInput::Mention {
+            mention: "@src/".to_string(),
+            text: "src/\n  lib.rs".to_string(),
+        };
+
+        let item = ResponseInputItem::from(vec![mention.clone()]);
+
+        let ResponseInputItem::Message { content, .. } = item else {
+            panic!("expected message");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected a single text item, got {content:?}");
+        };
+        assert_eq!(
+            text,
+            "<mention token=\"@src/\">\nsrc/\n  lib.rs\n</mention>"
+        );
+        assert_eq!(UserInput::parse_mention_block(text), Some(mention));
+    }
+
     #[test]
     fn local_image_read_error_adds_placeholder() -> Result<()> {
         let dir = tempdir()?;
@@ -14,6 +14,7 @@ use crate::ConversationId;
 use crate::config_types::ReasoningEffort as ReasoningEffortConfig;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use crate::custom_prompts::CustomPrompt;
+use crate::custom_prompts::PromptArguments;
 use crate::items::TurnItem;
 use crate::message_history::HistoryEntry;
 use crate::models::ContentItem;
@@ -35,7 +36,12 @@ use strum_macros::Display;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::ApprovalRule;
+pub use crate::approvals::ApprovalRuleAction;
+pub use crate::approvals::ApprovalRuleEntry;
+pub use crate::approvals::ApprovalRuleKind;
 pub use crate::approvals::ExecApprovalRequestEvent;
+pub use crate::approvals::ListApprovalRulesResponseEvent;
 pub use crate::approvals::SandboxCommandAssessment;
 pub use crate::approvals::SandboxRiskLevel;
 
@@ -102,6 +108,23 @@ pub enum Op {
         final_output_json_schema: Option<Value>,
     },
 
+    /// Input produced by invoking a custom prompt. When the turn starts, the
+    /// `@file` includes in the prompt body are inlined and its
+    /// `` !`command` `` blocks are run like shell tool calls (subject to the
+    /// approval policy and sandbox) with their output inlined; `arguments`
+    /// are then substituted as plain text. The prompt's `model`,
+    /// `reasoning_effort`, and `allowed_tools` apply to this turn only, so a
+    /// prompt that sets them or has directives is rejected while another turn
+    /// is running.
+    CustomPromptInput {
+        prompt: CustomPrompt,
+        #[serde(default)]
+        arguments: PromptArguments,
+        /// Additional user input items (images, attachments) sent after the
+        /// prompt body, see `InputItem`
+        items: Vec<UserInput>,
+    },
+
     /// Override parts of the persistent turn context for subsequent turns.
     ///
     /// All fields are optional; when omitted, the existing value is preserved.
@@ -180,9 +203,50 @@ pub enum Op {
     /// Request Llmx to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Request the checkpoints (ghost snapshots) captured during this session.
+    /// Reply is delivered via `EventMsg::ListCheckpointsResponse`.
+    ListCheckpoints,
+
+    /// Request the diff between two checkpoints, or between a checkpoint and
+    /// the current working tree when `to` is omitted.
+    /// Reply is delivered via `EventMsg::CheckpointDiffResponse`.
+    DiffCheckpoints {
+        from: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        to: Option<String>,
+    },
+
+    /// Restore the working tree to a checkpoint. When `paths` is empty the
+    /// whole tree is restored; otherwise only the listed paths (relative to
+    /// the session cwd) are, and every other file is left as is.
+    /// Completion is reported via `EventMsg::CheckpointRestored`.
+    RestoreCheckpoint {
+        id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+    },
+
+    /// Request the persistent approval rules that apply to this project.
+    /// Reply is delivered via `EventMsg::ListApprovalRulesResponse`.
+    ListApprovalRules,
+
+    /// Remove a persistent approval rule from every `approvals.toml` that
+    /// holds it. Replies with the updated `EventMsg::ListApprovalRulesResponse`.
+    RevokeApprovalRule { rule: ApprovalRule },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
+    /// Enter or leave plan mode. While enabled, turns run with a read-only
+    /// sandbox, never request approval, cannot call `apply_patch`, and each
+    /// task ends with an [`EventMsg::PlanProposed`].
+    SetPlanMode { enabled: bool },
+
+    /// Resolve the plan proposed at the end of a plan-mode task. Approving
+    /// leaves plan mode, restores the configured approval and sandbox
+    /// policies, and starts executing the plan.
+    ResolvePlan { decision: PlanDecision },
+
     /// Request to shut down llmx instance.
     Shutdown,
 
@@ -510,6 +574,18 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// List of checkpoints captured during this session, oldest first.
+    ListCheckpointsResponse(ListCheckpointsResponseEvent),
+
+    /// Response to `Op::DiffCheckpoints`.
+    CheckpointDiffResponse(CheckpointDiffResponseEvent),
+
+    /// Result of `Op::RestoreCheckpoint`.
+    CheckpointRestored(CheckpointRestoredEvent),
+
+    /// Persistent approval rules for this project, user rules first.
+    ListApprovalRulesResponse(ListApprovalRulesResponseEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -534,6 +610,10 @@ pub enum EventMsg {
 
     PlanUpdate(UpdatePlanArgs),
 
+    /// A plan-mode task finished and its plan awaits approval via
+    /// [`Op::ResolvePlan`].
+    PlanProposed(PlanProposedEvent),
+
     TurnAborted(TurnAbortedEvent),
 
     /// Notification that the agent is shutting down.
@@ -771,6 +851,39 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Local tokenizer estimate of the most recent prompt, computed before the
+    /// request was sent. Useful when the provider does not report usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub context_estimate: Option<ContextEstimate>,
+}
+
+/// Breakdown of the prompt size as estimated locally with the tokenizer.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextEstimate {
+    /// Base and developer instructions.
+    #[ts(type = "number")]
+    pub system_tokens: i64,
+    /// Serialized tool definitions.
+    #[ts(type = "number")]
+    pub tools_tokens: i64,
+    /// Messages, reasoning and tool calls.
+    #[ts(type = "number")]
+    pub history_tokens: i64,
+    /// Outputs returned by tool calls.
+    #[ts(type = "number")]
+    pub tool_output_tokens: i64,
+    /// Tokens freed so far in this session by eliding or trimming tool
+    /// outputs. Not part of the prompt total.
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub reclaimed_tokens: i64,
+}
+
+impl ContextEstimate {
+    pub fn total_tokens(&self) -> i64 {
+        self.system_tokens + self.tools_tokens + self.history_tokens + self.tool_output_tokens
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1157,6 +1270,34 @@ pub struct GitInfo {
 pub struct ReviewRequest {
     pub prompt: String,
     pub user_facing_hint: String,
+    /// What the prompt was built from. When set, the session may refine the
+    /// prompt with repository details (e.g. the merge base for a branch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub target: Option<ReviewTarget>,
+}
+
+/// The set of changes a review should look at.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReviewTarget {
+    /// Staged, unstaged, and untracked changes in the working tree.
+    UncommittedChanges,
+
+    /// Changes on the current branch since it diverged from `branch`.
+    BaseBranch { branch: String },
+
+    /// Changes introduced by a single commit.
+    Commit {
+        sha: String,
+        /// Subject line of the commit, shown to the reviewer for context.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        title: Option<String>,
+    },
+
+    /// Free-form instructions describing what to review.
+    Custom { instructions: String },
 }
 
 /// Structured review result produced by a child review session.
@@ -1294,6 +1435,47 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+/// A ghost snapshot of the working tree taken at the start of a turn.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+pub struct Checkpoint {
+    /// Ghost commit id; pass it to `Op::DiffCheckpoints` and
+    /// `Op::RestoreCheckpoint`.
+    pub id: String,
+    /// Unix timestamp (in seconds) when the snapshot was taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number | null")]
+    pub created_at: Option<i64>,
+    /// User message that started the turn this snapshot precedes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Files the turn changed after the snapshot, relative to the session cwd.
+    pub changed_files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListCheckpointsResponseEvent {
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointDiffResponseEvent {
+    pub from: String,
+    /// `None` when the diff is against the current working tree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub unified_diff: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointRestoredEvent {
+    pub id: String,
+    /// Restored paths; empty when the whole tree was restored.
+    pub paths: Vec<PathBuf>,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -1407,9 +1589,7 @@ pub struct SessionConfiguredEvent {
 }
 
 /// User's decision in response to an ExecApprovalRequest.
-#[derive(
-    Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS,
-)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewDecision {
     /// User has approved this command and the agent should execute it.
@@ -1420,16 +1600,59 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved only part of a proposed patch. `patch` is the subset
+    /// to apply, in `apply_patch` format; hunks missing from it (or edited by
+    /// the user) are reported back to the model. Only valid in response to an
+    /// ApplyPatchApprovalRequest.
+    ApprovedPartial { patch: String },
+
+    /// User has approved this request and wants future requests matching
+    /// `pattern` approved automatically in this project, across sessions.
+    /// For commands `pattern` is a glob over the command line (e.g.
+    /// `cargo test *`); for patches it is a glob over the project-relative
+    /// paths the patch touches.
+    ApprovedAlways { pattern: String },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
     Denied,
 
+    /// User has denied this request and wants future requests matching
+    /// `pattern` rejected automatically in this project. See
+    /// [`ReviewDecision::ApprovedAlways`] for the pattern syntax.
+    DeniedAlways { pattern: String },
+
     /// User has denied this command and the agent should not do anything until
     /// the user's next command.
     Abort,
 }
 
+/// User's decision in response to a [`PlanProposedEvent`].
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlanDecision {
+    /// Execute the plan as proposed.
+    Approved,
+
+    /// Execute the user's edited version of the plan instead.
+    ApprovedWithEdits { plan: String },
+
+    /// Discard the plan and stay in plan mode.
+    Rejected,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PlanProposedEvent {
+    /// The most recent `update_plan` call made during the task, if any.
+    pub plan: Option<UpdatePlanArgs>,
+    /// The final assistant message of the task.
+    pub message: Option<String>,
+    /// The plan rendered as plain text; this is what `Approved` executes and
+    /// what clients should offer for editing.
+    pub text: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum FileChange {
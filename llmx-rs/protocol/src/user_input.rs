@@ -21,4 +21,38 @@ pub enum UserInput {
     LocalImage {
         path: std::path::PathBuf,
     },
+
+    /// Context attached through an `@` mention in the composer, such as a
+    /// directory listing or a diff. `mention` is the token as typed and
+    /// `text` the resolved content. Sent to the model as a single
+    /// `<mention>` text block (see [`UserInput::mention_block`]).
+    Mention {
+        mention: String,
+        text: String,
+    },
+}
+
+/// Opening of the text block a [`UserInput::Mention`] is sent as.
+pub const MENTION_OPEN_TAG: &str = "<mention token=\"";
+const MENTION_CLOSE_TAG: &str = "</mention>";
+
+impl UserInput {
+    /// Renders a mention as the text block sent to the model.
+    pub fn mention_block(mention: &str, text: &str) -> String {
+        let token = mention.replace('"', "&quot;");
+        format!("{MENTION_OPEN_TAG}{token}\">\n{text}\n{MENTION_CLOSE_TAG}")
+    }
+
+    /// Parses a block produced by [`UserInput::mention_block`] back into a
+    /// [`UserInput::Mention`], e.g. when replaying a recorded session.
+    pub fn parse_mention_block(block: &str) -> Option<UserInput> {
+        let rest = block.strip_prefix(MENTION_OPEN_TAG)?;
+        let (token, rest) = rest.split_once("\">\n")?;
+        let text = rest.strip_suffix(MENTION_CLOSE_TAG)?;
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        Some(UserInput::Mention {
+            mention: token.replace("&quot;", "\""),
+            text: text.to_string(),
+        })
+    }
 }
@@ -28,12 +28,13 @@ pub async fn determine_streamable_http_auth_status(
     http_headers: Option<HashMap<String, String>>,
     env_http_headers: Option<HashMap<String, String>>,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<McpAuthStatus> {
     if bearer_token_env_var.is_some() {
         return Ok(McpAuthStatus::BearerToken);
     }
 
-    if has_oauth_tokens(server_name, url, store_mode)? {
+    if has_oauth_tokens(server_name, url, store_mode, credential_helper)? {
         return Ok(McpAuthStatus::OAuth);
     }
 
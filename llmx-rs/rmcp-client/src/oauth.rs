@@ -15,6 +15,9 @@
 //! file because we don't use the "vendored" feature.
 //!
 //! If the keyring is not available or fails, we fall back to LLMX_HOME/.credentials.json which is consistent with other coding CLI agents.
+//!
+//! Hosts without a usable keyring can instead select the passphrase-encrypted `encrypted-file` store or an external
+//! `credential-helper` command; both are implemented in `llmx-keyring-store`.
 
 use anyhow::Context;
 use anyhow::Error;
@@ -42,7 +45,10 @@ use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 use tracing::warn;
 
+use llmx_keyring_store::CredentialHelperKeyringStore;
 use llmx_keyring_store::DefaultKeyringStore;
+use llmx_keyring_store::ENCRYPTED_STORE_FILENAME;
+use llmx_keyring_store::EncryptedFileKeyringStore;
 use llmx_keyring_store::KeyringStore;
 use rmcp::transport::auth::AuthorizationManager;
 use tokio::sync::Mutex;
@@ -72,6 +78,13 @@ pub enum OAuthCredentialsStoreMode {
     File,
     /// Keyring when available, otherwise fail.
     Keyring,
+    /// LLMX_HOME/credentials.enc, encrypted with the passphrase from
+    /// `LLMX_CREDENTIALS_PASSPHRASE`.
+    #[serde(rename = "encrypted-file")]
+    EncryptedFile,
+    /// The external command configured as `credential_helper`.
+    #[serde(rename = "credential-helper")]
+    CredentialHelper,
 }
 
 /// Wrap OAuthTokenResponse to allow for partial equality comparison.
@@ -91,29 +104,58 @@ pub(crate) fn load_oauth_tokens(
     server_name: &str,
     url: &str,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<Option<StoredOAuthTokens>> {
-    let keyring_store = DefaultKeyringStore;
+    let keyring_store = credential_store(store_mode, credential_helper)?;
     match store_mode {
-        OAuthCredentialsStoreMode::Auto => {
-            load_oauth_tokens_from_keyring_with_fallback_to_file(&keyring_store, server_name, url)
-        }
+        OAuthCredentialsStoreMode::Auto => load_oauth_tokens_from_keyring_with_fallback_to_file(
+            keyring_store.as_ref(),
+            server_name,
+            url,
+        ),
         OAuthCredentialsStoreMode::File => load_oauth_tokens_from_file(server_name, url),
         OAuthCredentialsStoreMode::Keyring => {
-            load_oauth_tokens_from_keyring(&keyring_store, server_name, url)
+            load_oauth_tokens_from_keyring(keyring_store.as_ref(), server_name, url)
                 .with_context(|| "failed to read OAuth tokens from keyring".to_string())
         }
+        OAuthCredentialsStoreMode::EncryptedFile | OAuthCredentialsStoreMode::CredentialHelper => {
+            load_oauth_tokens_from_keyring(keyring_store.as_ref(), server_name, url)
+                .with_context(|| "failed to read OAuth tokens from credential store".to_string())
+        }
     }
 }
 
+/// Backend that holds tokens for `store_mode`. `Auto`, `File`, and `Keyring`
+/// all use the OS keyring; the file fallback is handled by the callers.
+/// `credential_helper` is the configured helper command, used only by
+/// `CredentialHelper`.
+fn credential_store(
+    store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> Result<Box<dyn KeyringStore>> {
+    Ok(match store_mode {
+        OAuthCredentialsStoreMode::Auto
+        | OAuthCredentialsStoreMode::File
+        | OAuthCredentialsStoreMode::Keyring => Box::new(DefaultKeyringStore),
+        OAuthCredentialsStoreMode::EncryptedFile => Box::new(EncryptedFileKeyringStore::new(
+            find_llmx_home()?.join(ENCRYPTED_STORE_FILENAME),
+        )),
+        OAuthCredentialsStoreMode::CredentialHelper => Box::new(CredentialHelperKeyringStore::new(
+            credential_helper.map(<[String]>::to_vec),
+        )),
+    })
+}
+
 pub(crate) fn has_oauth_tokens(
     server_name: &str,
     url: &str,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<bool> {
-    Ok(load_oauth_tokens(server_name, url, store_mode)?.is_some())
+    Ok(load_oauth_tokens(server_name, url, store_mode, credential_helper)?.is_some())
 }
 
-fn load_oauth_tokens_from_keyring_with_fallback_to_file<K: KeyringStore>(
+fn load_oauth_tokens_from_keyring_with_fallback_to_file<K: KeyringStore + ?Sized>(
     keyring_store: &K,
     server_name: &str,
     url: &str,
@@ -129,7 +171,7 @@ fn load_oauth_tokens_from_keyring_with_fallback_to_file<K: KeyringStore>(
     }
 }
 
-fn load_oauth_tokens_from_keyring<K: KeyringStore>(
+fn load_oauth_tokens_from_keyring<K: KeyringStore + ?Sized>(
     keyring_store: &K,
     server_name: &str,
     url: &str,
@@ -142,7 +184,7 @@ fn load_oauth_tokens_from_keyring<K: KeyringStore>(
             Ok(Some(tokens))
         }
         Ok(None) => Ok(None),
-        Err(error) => Err(Error::new(error.into_error())),
+        Err(error) => Err(Error::new(error)),
     }
 }
 
@@ -150,22 +192,25 @@ pub fn save_oauth_tokens(
     server_name: &str,
     tokens: &StoredOAuthTokens,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<()> {
-    let keyring_store = DefaultKeyringStore;
+    let keyring_store = credential_store(store_mode, credential_helper)?;
     match store_mode {
         OAuthCredentialsStoreMode::Auto => save_oauth_tokens_with_keyring_with_fallback_to_file(
-            &keyring_store,
+            keyring_store.as_ref(),
             server_name,
             tokens,
         ),
         OAuthCredentialsStoreMode::File => save_oauth_tokens_to_file(tokens),
-        OAuthCredentialsStoreMode::Keyring => {
-            save_oauth_tokens_with_keyring(&keyring_store, server_name, tokens)
+        OAuthCredentialsStoreMode::Keyring
+        | OAuthCredentialsStoreMode::EncryptedFile
+        | OAuthCredentialsStoreMode::CredentialHelper => {
+            save_oauth_tokens_with_keyring(keyring_store.as_ref(), server_name, tokens)
         }
     }
 }
 
-fn save_oauth_tokens_with_keyring<K: KeyringStore>(
+fn save_oauth_tokens_with_keyring<K: KeyringStore + ?Sized>(
     keyring_store: &K,
     server_name: &str,
     tokens: &StoredOAuthTokens,
@@ -186,12 +231,12 @@ fn save_oauth_tokens_with_keyring<K: KeyringStore>(
                 error.message()
             );
             warn!("{message}");
-            Err(Error::new(error.into_error()).context(message))
+            Err(Error::new(error).context(message))
         }
     }
 }
 
-fn save_oauth_tokens_with_keyring_with_fallback_to_file<K: KeyringStore>(
+fn save_oauth_tokens_with_keyring_with_fallback_to_file<K: KeyringStore + ?Sized>(
     keyring_store: &K,
     server_name: &str,
     tokens: &StoredOAuthTokens,
@@ -211,12 +256,13 @@ pub fn delete_oauth_tokens(
     server_name: &str,
     url: &str,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
 ) -> Result<bool> {
-    let keyring_store = DefaultKeyringStore;
-    delete_oauth_tokens_from_keyring_and_file(&keyring_store, store_mode, server_name, url)
+    let keyring_store = credential_store(store_mode, credential_helper)?;
+    delete_oauth_tokens_from_keyring_and_file(keyring_store.as_ref(), store_mode, server_name, url)
 }
 
-fn delete_oauth_tokens_from_keyring_and_file<K: KeyringStore>(
+fn delete_oauth_tokens_from_keyring_and_file<K: KeyringStore + ?Sized>(
     keyring_store: &K,
     store_mode: OAuthCredentialsStoreMode,
     server_name: &str,
@@ -230,9 +276,11 @@ fn delete_oauth_tokens_from_keyring_and_file<K: KeyringStore>(
             let message = error.message();
             warn!("failed to delete OAuth tokens from keyring: {message}");
             match store_mode {
-                OAuthCredentialsStoreMode::Auto | OAuthCredentialsStoreMode::Keyring => {
-                    return Err(error.into_error())
-                        .context("failed to delete OAuth tokens from keyring");
+                OAuthCredentialsStoreMode::Auto
+                | OAuthCredentialsStoreMode::Keyring
+                | OAuthCredentialsStoreMode::EncryptedFile
+                | OAuthCredentialsStoreMode::CredentialHelper => {
+                    return Err(error).context("failed to delete OAuth tokens from keyring");
                 }
                 OAuthCredentialsStoreMode::File => false,
             }
@@ -243,6 +291,29 @@ fn delete_oauth_tokens_from_keyring_and_file<K: KeyringStore>(
     Ok(keyring_removed || file_removed)
 }
 
+/// Moves the tokens stored for `server_name` from one store to another.
+/// The source entry is deleted before writing because `Auto`, `File`, and
+/// `Keyring` share the fallback file; on a failed write the tokens are put
+/// back. Returns `Ok(false)` when the source holds no tokens.
+pub fn migrate_oauth_tokens(
+    server_name: &str,
+    url: &str,
+    from: OAuthCredentialsStoreMode,
+    to: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
+) -> Result<bool> {
+    let Some(tokens) = load_oauth_tokens(server_name, url, from, credential_helper)? else {
+        return Ok(false);
+    };
+    delete_oauth_tokens(server_name, url, from, credential_helper)?;
+    if let Err(error) = save_oauth_tokens(server_name, &tokens, to, credential_helper) {
+        save_oauth_tokens(server_name, &tokens, from, credential_helper)
+            .context("failed to restore OAuth tokens to the original store")?;
+        return Err(error);
+    }
+    Ok(true)
+}
+
 #[derive(Clone)]
 pub(crate) struct OAuthPersistor {
     inner: Arc<OAuthPersistorInner>,
@@ -253,6 +324,7 @@ struct OAuthPersistorInner {
     url: String,
     authorization_manager: Arc<Mutex<AuthorizationManager>>,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<Vec<String>>,
     last_credentials: Mutex<Option<StoredOAuthTokens>>,
 }
 
@@ -262,6 +334,7 @@ impl OAuthPersistor {
         url: String,
         authorization_manager: Arc<Mutex<AuthorizationManager>>,
         store_mode: OAuthCredentialsStoreMode,
+        credential_helper: Option<Vec<String>>,
         initial_credentials: Option<StoredOAuthTokens>,
     ) -> Self {
         Self {
@@ -270,6 +343,7 @@ impl OAuthPersistor {
                 url,
                 authorization_manager,
                 store_mode,
+                credential_helper,
                 last_credentials: Mutex::new(initial_credentials),
             }),
         }
@@ -294,7 +368,12 @@ impl OAuthPersistor {
                 };
                 let mut last_credentials = self.inner.last_credentials.lock().await;
                 if last_credentials.as_ref() != Some(&stored) {
-                    save_oauth_tokens(&self.inner.server_name, &stored, self.inner.store_mode)?;
+                    save_oauth_tokens(
+                        &self.inner.server_name,
+                        &stored,
+                        self.inner.store_mode,
+                        self.inner.credential_helper.as_deref(),
+                    )?;
                     *last_credentials = Some(stored);
                 }
             }
@@ -305,6 +384,7 @@ impl OAuthPersistor {
                         &self.inner.server_name,
                         &self.inner.url,
                         self.inner.store_mode,
+                        self.inner.credential_helper.as_deref(),
                     )
                 {
                     warn!(
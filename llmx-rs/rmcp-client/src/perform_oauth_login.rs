@@ -35,6 +35,7 @@ pub async fn perform_oauth_login(
     server_name: &str,
     server_url: &str,
     store_mode: OAuthCredentialsStoreMode,
+    credential_helper: Option<&[String]>,
     http_headers: Option<HashMap<String, String>>,
     env_http_headers: Option<HashMap<String, String>>,
     scopes: &[String],
@@ -97,7 +98,7 @@ pub async fn perform_oauth_login(
         client_id,
         token_response: WrappedOAuthTokenResponse(credentials),
     };
-    save_oauth_tokens(server_name, &stored, store_mode)?;
+    save_oauth_tokens(server_name, &stored, store_mode, credential_helper)?;
 
     drop(guard);
     Ok(())
@@ -5,12 +5,26 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::chatwidget::ChatWidget;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::external_editor;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
+use crate::inline_image;
+use crate::inline_image::GraphicsProtocol;
+use crate::inline_image::InlineImage;
+use crate::insert_history::INLINE_IMAGE_INDENT;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
 use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
+use crate::session_tabs::ParkedSession;
+use crate::session_tabs::SessionSummary;
+use crate::session_tabs::SessionTabBar;
+use crate::session_tabs::SessionTabId;
+use crate::session_tabs::SessionTabs;
+use crate::session_tabs::session_title;
+use crate::theme;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -25,10 +39,13 @@ use llmx_core::ConversationManager;
 use llmx_core::config::Config;
 use llmx_core::config::edit::ConfigEditsBuilder;
 use llmx_core::model_family::find_family_for_model;
+use llmx_core::protocol::EventMsg;
+use llmx_core::protocol::Op;
 use llmx_core::protocol::SessionSource;
 use llmx_core::protocol::TokenUsage;
 use llmx_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
 use llmx_protocol::ConversationId;
+use ratatui::layout::Rect;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use std::path::PathBuf;
@@ -56,6 +73,10 @@ pub(crate) struct App {
     pub(crate) chat_widget: ChatWidget,
     pub(crate) auth_manager: Arc<AuthManager>,
 
+    /// Open sessions. The active one's widget, config, transcript and file
+    /// search are the fields on `App`; the others are parked in here.
+    pub(crate) session_tabs: SessionTabs,
+
     /// Config is stored here so we can recreate ChatWidgets as needed.
     pub(crate) config: Config,
     pub(crate) active_profile: Option<String>,
@@ -71,6 +92,12 @@ pub(crate) struct App {
 
     pub(crate) enhanced_keys_supported: bool,
 
+    /// Key bindings from `[tui.keymap]` for the transcript and backtracking.
+    pub(crate) keymap: Keymap,
+
+    /// Protocol for image thumbnails in history; `None` shows labels only.
+    pub(crate) image_protocol: Option<GraphicsProtocol>,
+
     /// Controls the animation thread that sends CommitTick events.
     pub(crate) commit_anim_running: Arc<AtomicBool>,
 
@@ -93,6 +120,7 @@ impl App {
         active_profile: Option<String>,
         initial_prompt: Option<String>,
         initial_images: Vec<PathBuf>,
+        initial_plan_mode: bool,
         resume_selection: ResumeSelection,
         feedback: llmx_feedback::LlmxFeedback,
     ) -> Result<AppExitInfo> {
@@ -106,13 +134,15 @@ impl App {
         ));
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
+        let session_tabs = SessionTabs::new();
+        let tab_tx = app_event_tx.for_tab(session_tabs.active_id());
 
-        let chat_widget = match resume_selection {
+        let mut chat_widget = match resume_selection {
             ResumeSelection::StartFresh | ResumeSelection::Exit => {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: tab_tx.clone(),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -135,7 +165,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: tab_tx.clone(),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -149,20 +179,29 @@ impl App {
                 )
             }
         };
+        if initial_plan_mode {
+            chat_widget.set_plan_mode(true);
+        }
 
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let file_search = FileSearchManager::new(config.cwd.clone(), tab_tx);
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
+        let keymap = Keymap::from_config(&config.tui_keymap);
+        theme::configure(&config.tui_theme);
+        let image_protocol = GraphicsProtocol::from_config(config.tui_image_protocol);
         let mut app = Self {
             server: conversation_manager,
             app_event_tx,
             chat_widget,
             auth_manager: auth_manager.clone(),
+            session_tabs,
             config,
             active_profile,
             file_search,
             enhanced_keys_supported,
+            keymap,
+            image_protocol,
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
@@ -230,6 +269,27 @@ impl App {
         })
     }
 
+    /// Draws thumbnails of the cell's images below its lines when the
+    /// terminal supports inline graphics.
+    fn insert_history_images(&self, tui: &mut tui::Tui, cell: &dyn HistoryCell, width: u16) {
+        let Some(protocol) = self.image_protocol else {
+            return;
+        };
+        let images = cell.inline_images();
+        if images.is_empty() {
+            return;
+        }
+        let cell_size = inline_image::cell_size();
+        let max_cols = width.saturating_sub(INLINE_IMAGE_INDENT + 1);
+        for source in images {
+            if let Some(image) = source.load()
+                && let Some(thumbnail) = InlineImage::encode(&image, protocol, max_cols, cell_size)
+            {
+                tui.insert_history_image(thumbnail);
+            }
+        }
+    }
+
     pub(crate) async fn handle_tui_event(
         &mut self,
         tui: &mut tui::Tui,
@@ -258,11 +318,28 @@ impl App {
                     {
                         return Ok(true);
                     }
+                    let width = tui.terminal.size()?.width;
+                    let tab_bar = SessionTabBar::new(self.session_summaries());
+                    let tab_bar_height = tab_bar.desired_height(width);
                     tui.draw(
-                        self.chat_widget.desired_height(tui.terminal.size()?.width),
+                        self.chat_widget.desired_height(width) + tab_bar_height,
                         |frame| {
-                            self.chat_widget.render(frame.area(), frame.buffer);
-                            if let Some((x, y)) = self.chat_widget.cursor_pos(frame.area()) {
+                            let area = frame.area();
+                            let bar_height = tab_bar_height.min(area.height);
+                            tab_bar.render(
+                                Rect {
+                                    height: bar_height,
+                                    ..area
+                                },
+                                frame.buffer,
+                            );
+                            let area = Rect {
+                                y: area.y + bar_height,
+                                height: area.height - bar_height,
+                                ..area
+                            };
+                            self.chat_widget.render(area, frame.buffer);
+                            if let Some((x, y)) = self.chat_widget.cursor_pos(area) {
                                 frame.set_cursor_position((x, y));
                             }
                         },
@@ -279,7 +356,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: self.config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: self.app_event_tx.clone(),
+                    app_event_tx: self.active_tab_tx(),
                     initial_prompt: None,
                     initial_images: Vec::new(),
                     enhanced_keys_supported: self.enhanced_keys_supported,
@@ -289,6 +366,35 @@ impl App {
                 self.chat_widget = ChatWidget::new(init, self.server.clone());
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::SessionEvent { tab, event } => match *event {
+                AppEvent::StartCommitAnimation => self.set_commit_animation(tab, true),
+                AppEvent::StopCommitAnimation => self.set_commit_animation(tab, false),
+                event if tab == self.session_tabs.active_id() => {
+                    return Box::pin(self.handle_event(tui, event)).await;
+                }
+                event => {
+                    if let Some(message) = self.handle_background_event(tab, event) {
+                        tui.notify_unseen(message);
+                    }
+                    tui.frame_requester().schedule_frame();
+                }
+            },
+            AppEvent::OpenSessionTab { cwd } => {
+                self.open_session_tab(tui, cwd);
+            }
+            AppEvent::SwitchSessionTab(tab) => {
+                self.switch_session_tab(tui, tab);
+            }
+            AppEvent::CloseSessionTab(tab) => {
+                self.close_session_tab(tui, tab).await;
+            }
+            AppEvent::OpenSessionsPopup => {
+                let sessions = self.session_summaries();
+                self.chat_widget.open_sessions_popup(sessions);
+            }
+            AppEvent::OpenSessionCwdPrompt => {
+                self.chat_widget.show_session_cwd_prompt();
+            }
             AppEvent::InsertHistoryCell(cell) => {
                 let cell: Arc<dyn HistoryCell> = cell.into();
                 if let Some(Overlay::Transcript(t)) = &mut self.overlay {
@@ -296,7 +402,8 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let width = tui.terminal.last_known_screen_size.width;
+                let mut display = cell.display_lines(width);
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -309,33 +416,29 @@ impl App {
                         }
                     }
                     if self.overlay.is_some() {
+                        // Thumbnails are skipped while an overlay is open; the
+                        // cell's lines still label each image.
                         self.deferred_history_lines.extend(display);
                     } else {
                         tui.insert_history_lines(display);
+                        self.insert_history_images(tui, cell.as_ref(), width);
                     }
                 }
             }
             AppEvent::StartCommitAnimation => {
-                if self
-                    .commit_anim_running
-                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-                    .is_ok()
-                {
-                    let tx = self.app_event_tx.clone();
-                    let running = self.commit_anim_running.clone();
-                    thread::spawn(move || {
-                        while running.load(Ordering::Relaxed) {
-                            thread::sleep(Duration::from_millis(50));
-                            tx.send(AppEvent::CommitTick);
-                        }
-                    });
-                }
+                self.set_commit_animation(self.session_tabs.active_id(), true);
             }
             AppEvent::StopCommitAnimation => {
-                self.commit_anim_running.store(false, Ordering::Release);
+                self.set_commit_animation(self.session_tabs.active_id(), false);
             }
             AppEvent::CommitTick => {
                 self.chat_widget.on_commit_tick();
+                // Background sessions keep streaming into their transcripts.
+                for tab in self.session_tabs.iter_mut() {
+                    if let Some(parked) = tab.parked.as_mut() {
+                        parked.chat_widget.on_commit_tick();
+                    }
+                }
             }
             AppEvent::LlmxEvent(event) => {
                 self.chat_widget.handle_llmx_event(event);
@@ -347,6 +450,9 @@ impl App {
                 return Ok(false);
             }
             AppEvent::LlmxOp(op) => self.chat_widget.submit_op(op),
+            AppEvent::ExportSession { format, redact } => {
+                self.chat_widget.export_session(format, redact);
+            }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
@@ -371,6 +477,9 @@ impl App {
             AppEvent::FileSearchResult { query, matches } => {
                 self.chat_widget.apply_file_search_result(query, matches);
             }
+            AppEvent::MentionResolved { token, result } => {
+                self.chat_widget.on_mention_resolved(token, result);
+            }
             AppEvent::UpdateReasoningEffort(effort) => {
                 self.on_update_reasoning_effort(effort);
             }
@@ -559,6 +668,58 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::OpenPlanEditor(plan) => {
+                self.chat_widget.show_plan_editor(plan);
+            }
+            AppEvent::ResolvePlan(decision) => {
+                self.chat_widget.resolve_plan(decision);
+            }
+            AppEvent::OpenCheckpointActions(checkpoint) => {
+                self.chat_widget.show_checkpoint_actions(checkpoint);
+            }
+            AppEvent::OpenCheckpointFilePicker(checkpoint) => {
+                self.chat_widget.show_checkpoint_file_picker(checkpoint);
+            }
+            AppEvent::OpenCheckpointPathsPrompt(id) => {
+                self.chat_widget.show_checkpoint_paths_prompt(id);
+            }
+            AppEvent::OpenCheckpointDiffPicker(from) => {
+                self.chat_widget.show_checkpoint_diff_picker(from);
+            }
+            AppEvent::OpenExternalEditor { text, suffix } => {
+                match tui.with_restored(|| external_editor::edit_text(&text, suffix)) {
+                    Ok(edited) => self.chat_widget.handle_external_edit(edited),
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to edit in external editor: {err}")),
+                }
+            }
+            AppEvent::EditQueuedMessages(edit) => {
+                self.chat_widget.edit_queued_messages(edit);
+            }
+            AppEvent::PreviewTheme(name) => {
+                theme::set_preview(name);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::SelectTheme(name) => {
+                theme::select(&name);
+                self.config.tui_theme.name = Some(name.clone());
+                tui.frame_requester().schedule_frame();
+                match ConfigEditsBuilder::new(&self.config.llmx_home)
+                    .set_tui_theme(&name)
+                    .apply()
+                    .await
+                {
+                    Ok(()) => self
+                        .chat_widget
+                        .add_info_message(format!("Theme changed to {name}"), None),
+                    Err(err) => {
+                        tracing::error!(error = %err, "failed to persist theme");
+                        self.chat_widget
+                            .add_error_message(format!("Failed to save theme: {err}"));
+                    }
+                }
+            }
             AppEvent::FullScreenApprovalRequest(request) => match request {
                 ApprovalRequest::ApplyPatch { cwd, changes, .. } => {
                     let _ = tui.enter_alt_screen();
@@ -591,30 +752,262 @@ impl App {
         self.config.model_reasoning_effort = effort;
     }
 
+    /// Sender for widgets of the active session, tagged with its tab.
+    pub(crate) fn active_tab_tx(&self) -> AppEventSender {
+        self.app_event_tx.for_tab(self.session_tabs.active_id())
+    }
+
+    /// Runs the commit animation thread while any session is streaming.
+    fn set_commit_animation(&mut self, tab: SessionTabId, running: bool) {
+        if let Some(tab) = self.session_tabs.get_mut(tab) {
+            tab.animating = running;
+        }
+        if !self.session_tabs.any_animating() {
+            self.commit_anim_running.store(false, Ordering::Release);
+            return;
+        }
+        if self
+            .commit_anim_running
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            let tx = self.app_event_tx.clone();
+            let running = self.commit_anim_running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(50));
+                    tx.send(AppEvent::CommitTick);
+                }
+            });
+        }
+    }
+
+    pub(crate) fn session_summaries(&self) -> Vec<SessionSummary> {
+        let active = self.session_tabs.active_id();
+        self.session_tabs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, tab)| {
+                let (chat_widget, cells) = match &tab.parked {
+                    Some(parked) => (&parked.chat_widget, &parked.transcript_cells),
+                    None if tab.id == active => (&self.chat_widget, &self.transcript_cells),
+                    None => return None,
+                };
+                // The widget's config reflects /model and /approvals changes.
+                let config = chat_widget.config_ref();
+                Some(SessionSummary {
+                    id: tab.id,
+                    number: idx + 1,
+                    title: session_title(cells, config),
+                    model: config.model.clone(),
+                    cwd: config.cwd.clone(),
+                    approval_policy: config.approval_policy,
+                    is_active: tab.id == active,
+                    is_running: chat_widget.is_task_running(),
+                    unread: tab.unread,
+                    approval_pending: tab.approval_pending,
+                })
+            })
+            .collect()
+    }
+
+    /// Starts a conversation in a new tab, with the active session's settings
+    /// and `cwd` if given, and switches to it.
+    fn open_session_tab(&mut self, tui: &mut tui::Tui, cwd: Option<PathBuf>) {
+        let mut config = self.chat_widget.config_ref().clone();
+        if let Some(cwd) = cwd {
+            let cwd = config.cwd.join(cwd);
+            if !cwd.is_dir() {
+                self.chat_widget
+                    .add_error_message(format!("Not a directory: {}", cwd.display()));
+                return;
+            }
+            config.cwd = cwd;
+        }
+        let tab = self.session_tabs.allocate_id();
+        let tab_tx = self.app_event_tx.for_tab(tab);
+        let init = crate::chatwidget::ChatWidgetInit {
+            config: config.clone(),
+            frame_requester: tui.frame_requester(),
+            app_event_tx: tab_tx.clone(),
+            initial_prompt: None,
+            initial_images: Vec::new(),
+            enhanced_keys_supported: self.enhanced_keys_supported,
+            auth_manager: self.auth_manager.clone(),
+            feedback: self.feedback.clone(),
+        };
+        let chat_widget = ChatWidget::new(init, self.server.clone());
+        let file_search = FileSearchManager::new(config.cwd.clone(), tab_tx);
+        self.session_tabs.push(
+            tab,
+            ParkedSession {
+                chat_widget,
+                config,
+                transcript_cells: Vec::new(),
+                file_search,
+            },
+        );
+        self.switch_session_tab(tui, tab);
+    }
+
+    /// Shows session `tab`: parks the active session and redraws the target's
+    /// transcript below a divider.
+    fn switch_session_tab(&mut self, tui: &mut tui::Tui, tab: SessionTabId) {
+        if tab == self.session_tabs.active_id() || self.overlay.is_some() {
+            return;
+        }
+        let Some(target) = self.session_tabs.take_parked(tab) else {
+            return;
+        };
+        let ParkedSession {
+            chat_widget,
+            config,
+            transcript_cells,
+            file_search,
+        } = target;
+        let previous = ParkedSession {
+            chat_widget: std::mem::replace(&mut self.chat_widget, chat_widget),
+            config: std::mem::replace(&mut self.config, config),
+            transcript_cells: std::mem::replace(&mut self.transcript_cells, transcript_cells),
+            file_search: std::mem::replace(&mut self.file_search, file_search),
+        };
+        self.session_tabs.activate(tab, previous);
+        self.reset_backtrack_state();
+
+        if let Some(summary) = self
+            .session_summaries()
+            .into_iter()
+            .find(|summary| summary.id == tab)
+        {
+            let divider = format!("── session {} ──", summary.label());
+            let mut lines = Vec::new();
+            if self.has_emitted_history_lines {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(divider.dim()));
+            tui.insert_history_lines(lines);
+            self.has_emitted_history_lines = true;
+        }
+        self.render_transcript_once(tui);
+        tui.frame_requester().schedule_frame();
+    }
+
+    fn switch_session_tab_by(&mut self, tui: &mut tui::Tui, offset: isize) {
+        let tab = self.session_tabs.neighbor(offset);
+        self.switch_session_tab(tui, tab);
+    }
+
+    /// Closes session `tab`, switching away first if it is the active one.
+    async fn close_session_tab(&mut self, tui: &mut tui::Tui, tab: SessionTabId) {
+        if self.session_tabs.len() <= 1 {
+            self.chat_widget.add_info_message(
+                "This is the only open session".to_string(),
+                Some("Use /quit to exit".to_string()),
+            );
+            return;
+        }
+        if tab == self.session_tabs.active_id() {
+            self.switch_session_tab_by(tui, -1);
+        }
+        let Some(parked) = self
+            .session_tabs
+            .remove(tab)
+            .and_then(|closed| closed.parked)
+        else {
+            return;
+        };
+        if let Some(conversation_id) = parked.chat_widget.conversation_id() {
+            parked.chat_widget.submit_op(Op::Shutdown);
+            self.server.remove_conversation(&conversation_id).await;
+        }
+        if !self.session_tabs.any_animating() {
+            self.commit_anim_running.store(false, Ordering::Release);
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Applies an event from a session that is not shown. History goes into
+    /// its transcript and bumps the unread count; returns a notification to
+    /// post when the session needs attention.
+    fn handle_background_event(&mut self, tab: SessionTabId, event: AppEvent) -> Option<String> {
+        let number = self
+            .session_tabs
+            .iter()
+            .position(|candidate| candidate.id == tab)?
+            + 1;
+        let tab = self.session_tabs.get_mut(tab)?;
+        let parked = tab.parked.as_mut()?;
+        match event {
+            AppEvent::LlmxEvent(event) => {
+                if matches!(
+                    event.msg,
+                    EventMsg::ExecApprovalRequest(_) | EventMsg::ApplyPatchApprovalRequest(_)
+                ) {
+                    tab.approval_pending = true;
+                }
+                parked.chat_widget.handle_llmx_event(event);
+                let title =
+                    session_title(&parked.transcript_cells, parked.chat_widget.config_ref());
+                return parked
+                    .chat_widget
+                    .take_pending_notification()
+                    .map(|message| format!("[{number} {title}] {message}"));
+            }
+            AppEvent::InsertHistoryCell(cell) => {
+                if !cell.is_stream_continuation() {
+                    tab.unread += 1;
+                }
+                parked.transcript_cells.push(cell.into());
+            }
+            AppEvent::LlmxOp(op) => parked.chat_widget.submit_op(op),
+            AppEvent::FileSearchResult { query, matches } => {
+                parked.chat_widget.apply_file_search_result(query, matches);
+            }
+            AppEvent::MentionResolved { token, result } => {
+                parked.chat_widget.on_mention_resolved(token, result);
+            }
+            other => {
+                tracing::debug!("ignoring event from background session: {other:?}");
+            }
+        }
+        None
+    }
+
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        let is_backtrack_key = self.keymap.matches(KeyAction::Backtrack, key_event);
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if self.keymap.matches(KeyAction::NewSession, key_event) => {
+                self.open_session_tab(tui, None);
+            }
+            _ if self.keymap.matches(KeyAction::CloseSession, key_event) => {
+                let tab = self.session_tabs.active_id();
+                self.close_session_tab(tui, tab).await;
+            }
+            _ if self.keymap.matches(KeyAction::NextSession, key_event) => {
+                self.switch_session_tab_by(tui, 1);
+            }
+            _ if self.keymap.matches(KeyAction::PrevSession, key_event) => {
+                self.switch_session_tab_by(tui, -1);
+            }
+            _ if key_event.kind == KeyEventKind::Press
+                && self.keymap.matches(KeyAction::OpenTranscript, key_event) =>
+            {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
+                self.overlay = Some(Overlay::new_transcript(
+                    self.transcript_cells.clone(),
+                    &self.keymap,
+                ));
                 tui.frame_requester().schedule_frame();
             }
-            // Esc primes/advances backtracking only in normal (not working) mode
-            // with the composer focused and empty. In any other state, forward
-            // Esc so the active UI (e.g. status indicator, modals, popups)
-            // handles it.
-            KeyEvent {
-                code: KeyCode::Esc,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
+            // The backtrack key (Esc by default) primes/advances backtracking
+            // only in normal (not working) mode with the composer focused and
+            // empty. In any other state, forward it so the active UI (e.g.
+            // status indicator, modals, popups) handles it.
+            _ if is_backtrack_key => {
                 if self.chat_widget.is_normal_backtrack_mode()
                     && self.chat_widget.composer_is_empty()
+                    && !self.chat_widget.composer_captures_key(&key_event)
                 {
                     self.handle_backtrack_esc_key(tui);
                 } else {
@@ -637,10 +1030,10 @@ impl App {
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
-                // Any non-Esc key press should cancel a primed backtrack.
+                // Any other key press should cancel a primed backtrack.
                 // This avoids stale "Esc-primed" state after the user starts typing
                 // (even if they later backspace to empty).
-                if key_event.code != KeyCode::Esc && self.backtrack.primed {
+                if self.backtrack.primed {
                     self.reset_backtrack_state();
                 }
                 self.chat_widget.handle_key_event(key_event);
@@ -718,6 +1111,8 @@ mod tests {
     use llmx_core::AuthManager;
     use llmx_core::ConversationManager;
     use llmx_core::LlmxAuth;
+    use llmx_core::protocol::Event;
+    use llmx_core::protocol::ExecApprovalRequestEvent;
     use llmx_core::protocol::SessionConfiguredEvent;
     use llmx_protocol::ConversationId;
     use ratatui::prelude::Line;
@@ -741,6 +1136,7 @@ mod tests {
             app_event_tx,
             chat_widget,
             auth_manager,
+            session_tabs: SessionTabs::new(),
             config,
             active_profile: None,
             file_search,
@@ -749,6 +1145,8 @@ mod tests {
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
             enhanced_keys_supported: false,
+            keymap: Keymap::default(),
+            image_protocol: None,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             backtrack: BacktrackState::default(),
             feedback: llmx_feedback::LlmxFeedback::new(),
@@ -776,6 +1174,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn background_session_collects_history_and_badges() {
+        let mut app = make_test_app();
+        let (chat_widget, tab_tx, _rx, _op_rx) = make_chatwidget_manual_with_sender();
+        let config = chat_widget.config_ref().clone();
+        let tab = app.session_tabs.allocate_id();
+        app.session_tabs.push(
+            tab,
+            ParkedSession {
+                file_search: FileSearchManager::new(config.cwd.clone(), tab_tx),
+                chat_widget,
+                config,
+                transcript_cells: Vec::new(),
+            },
+        );
+
+        let notification = app.handle_background_event(
+            tab,
+            AppEvent::InsertHistoryCell(Box::new(UserHistoryCell {
+                message: "fix the tests".to_string(),
+                images: Vec::new(),
+            })),
+        );
+        assert_eq!(notification, None);
+        app.handle_background_event(
+            tab,
+            AppEvent::LlmxEvent(Event {
+                id: "sub-1".into(),
+                msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: "call-1".into(),
+                    command: vec!["cargo".into(), "test".into()],
+                    cwd: PathBuf::from("."),
+                    reason: None,
+                    risk: None,
+                    parsed_cmd: vec![],
+                }),
+            }),
+        );
+
+        assert!(app.transcript_cells.is_empty());
+        let summaries = app.session_summaries();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries[0].is_active);
+        assert_eq!(summaries[1].title, "fix the tests");
+        assert_eq!(summaries[1].unread, 1);
+        assert!(summaries[1].approval_pending);
+    }
+
     #[test]
     fn backtrack_selection_with_duplicate_history_targets_unique_turn() {
         let mut app = make_test_app();
@@ -783,6 +1229,7 @@ mod tests {
         let user_cell = |text: &str| -> Arc<dyn HistoryCell> {
             Arc::new(UserHistoryCell {
                 message: text.to_string(),
+                images: Vec::new(),
             }) as Arc<dyn HistoryCell>
         };
         let agent_cell = |text: &str| -> Arc<dyn HistoryCell> {
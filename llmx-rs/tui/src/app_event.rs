@@ -2,12 +2,17 @@ use std::path::PathBuf;
 
 use llmx_common::approval_presets::ApprovalPreset;
 use llmx_common::model_presets::ModelPreset;
+use llmx_core::ExportFormat;
+use llmx_core::protocol::Checkpoint;
 use llmx_core::protocol::ConversationPathResponseEvent;
 use llmx_core::protocol::Event;
+use llmx_core::protocol::PlanDecision;
 use llmx_file_search::FileMatch;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::history_cell::HistoryCell;
+use crate::mentions::ResolvedMention;
+use crate::session_tabs::SessionTabId;
 
 use llmx_core::protocol::AskForApproval;
 use llmx_core::protocol::SandboxPolicy;
@@ -21,6 +26,29 @@ pub(crate) enum AppEvent {
     /// Start a new session.
     NewSession,
 
+    /// An event sent by the widget or agent loop of session tab `tab`.
+    SessionEvent {
+        tab: SessionTabId,
+        event: Box<AppEvent>,
+    },
+
+    /// Open a new session tab, in `cwd` when given, and switch to it.
+    OpenSessionTab {
+        cwd: Option<PathBuf>,
+    },
+
+    /// Show the session tab with this id.
+    SwitchSessionTab(SessionTabId),
+
+    /// Close the session tab with this id and shut its conversation down.
+    CloseSessionTab(SessionTabId),
+
+    /// Open the `/sessions` list.
+    OpenSessionsPopup,
+
+    /// Prompt for the working directory of a new session tab.
+    OpenSessionCwdPrompt,
+
     /// Request to exit the application gracefully.
     ExitRequest,
 
@@ -41,9 +69,21 @@ pub(crate) enum AppEvent {
         matches: Vec<FileMatch>,
     },
 
+    /// An `@` mention in the composer finished resolving.
+    MentionResolved {
+        token: String,
+        result: Result<ResolvedMention, String>,
+    },
+
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Export the current session's rollout to a file in the working directory.
+    ExportSession {
+        format: ExportFormat,
+        redact: bool,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -136,9 +176,45 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open an editor prefilled with the proposed plan.
+    OpenPlanEditor(String),
+
+    /// Resolve the plan proposed at the end of a plan-mode task.
+    ResolvePlan(PlanDecision),
+
+    /// Open the actions for a checkpoint picked from `/checkpoints`.
+    OpenCheckpointActions(Checkpoint),
+
+    /// Open the per-file restore picker for a checkpoint.
+    OpenCheckpointFilePicker(Checkpoint),
+
+    /// Prompt for paths to restore from the checkpoint with this id.
+    OpenCheckpointPathsPrompt(String),
+
+    /// Pick a second checkpoint to diff the one with this id against.
+    OpenCheckpointDiffPicker(String),
+
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
+    /// Suspend the TUI and edit `text` in `$VISUAL` / `$EDITOR`. The result
+    /// is handed back to the active bottom pane view. `suffix` is the file
+    /// extension of the temporary file, so the editor picks a syntax mode.
+    OpenExternalEditor {
+        text: String,
+        suffix: &'static str,
+    },
+
+    /// Apply a change made in the queued messages view to the queue.
+    EditQueuedMessages(QueuedMessageEdit),
+
+    /// Show the named color theme without saving it; `None` restores the
+    /// configured theme.
+    PreviewTheme(Option<String>),
+
+    /// Switch to the named color theme and persist it as `tui.theme.name`.
+    SelectTheme(String),
+
     /// Open the feedback note entry overlay after the user selects a category.
     OpenFeedbackNote {
         category: FeedbackCategory,
@@ -151,6 +227,23 @@ pub(crate) enum AppEvent {
     },
 }
 
+/// A change to the messages queued while a turn is running. Indices refer to
+/// the queue as it was before the change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QueuedMessageEdit {
+    Replace {
+        index: usize,
+        text: String,
+    },
+    Move {
+        from: usize,
+        to: usize,
+    },
+    Remove(usize),
+    /// The queued messages view closed; the queue may be sent again.
+    Closed,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum FeedbackCategory {
     BadResult,
@@ -8,6 +8,8 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_review::PatchReview;
+use crate::bottom_pane::patch_review::ReviewAction;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
@@ -20,6 +22,8 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use llmx_core::approval_rules::suggest_command_pattern;
+use llmx_core::approval_rules::suggest_patch_pattern;
 use llmx_core::protocol::FileChange;
 use llmx_core::protocol::Op;
 use llmx_core::protocol::ReviewDecision;
@@ -58,6 +62,8 @@ pub(crate) struct ApprovalOverlay {
     app_event_tx: AppEventSender,
     list: ListSelectionView,
     options: Vec<ApprovalOption>,
+    /// Set while the user reviews the current patch hunk by hunk.
+    review: Option<PatchReview>,
     current_complete: bool,
     done: bool,
 }
@@ -71,6 +77,7 @@ impl ApprovalOverlay {
             app_event_tx: app_event_tx.clone(),
             list: ListSelectionView::new(Default::default(), app_event_tx),
             options: Vec::new(),
+            review: None,
             current_complete: false,
             done: false,
         };
@@ -86,6 +93,7 @@ impl ApprovalOverlay {
         self.current_request = Some(request.clone());
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
+        self.review = None;
         self.current_complete = false;
         let (options, params) = Self::build_options(variant, header);
         self.options = options;
@@ -97,12 +105,16 @@ impl ApprovalOverlay {
         header: Box<dyn Renderable>,
     ) -> (Vec<ApprovalOption>, SelectionViewParams) {
         let (options, title) = match &variant {
-            ApprovalVariant::Exec { .. } => (
-                exec_options(),
+            ApprovalVariant::Exec { pattern, .. } => (
+                exec_options(pattern.as_deref()),
                 "Would you like to run the following command?".to_string(),
             ),
-            ApprovalVariant::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalVariant::ApplyPatch {
+                reviewable,
+                pattern,
+                ..
+            } => (
+                patch_options(*reviewable, pattern.as_deref()),
                 "Would you like to make the following edits?".to_string(),
             ),
         };
@@ -149,11 +161,15 @@ impl ApprovalOverlay {
             return;
         };
         if let Some(variant) = self.current_variant.as_ref() {
-            match (&variant, option.decision) {
-                (ApprovalVariant::Exec { id, command }, decision) => {
+            match (&variant, option.action.clone()) {
+                (_, ApprovalAction::ReviewHunks) => {
+                    self.start_review();
+                    return;
+                }
+                (ApprovalVariant::Exec { id, command, .. }, ApprovalAction::Decide(decision)) => {
                     self.handle_exec_decision(id, command, decision);
                 }
-                (ApprovalVariant::ApplyPatch { id, .. }, decision) => {
+                (ApprovalVariant::ApplyPatch { id, .. }, ApprovalAction::Decide(decision)) => {
                     self.handle_patch_decision(id, decision);
                 }
             }
@@ -164,7 +180,7 @@ impl ApprovalOverlay {
     }
 
     fn handle_exec_decision(&self, id: &str, command: &[String], decision: ReviewDecision) {
-        let cell = history_cell::new_approval_decision_cell(command.to_vec(), decision);
+        let cell = history_cell::new_approval_decision_cell(command.to_vec(), decision.clone());
         self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
         self.app_event_tx.send(AppEvent::LlmxOp(Op::ExecApproval {
             id: id.to_string(),
@@ -179,6 +195,40 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn start_review(&mut self) {
+        if let Some(ApprovalRequest::ApplyPatch { cwd, changes, .. }) = &self.current_request {
+            self.review = PatchReview::new(changes, cwd);
+        }
+    }
+
+    fn handle_review_key_event(&mut self, key_event: KeyEvent) {
+        let Some(review) = self.review.as_mut() else {
+            return;
+        };
+        match review.handle_key_event(key_event) {
+            ReviewAction::None => {}
+            ReviewAction::OpenEditor(text) => {
+                self.app_event_tx.send(AppEvent::OpenExternalEditor {
+                    text,
+                    suffix: ".diff",
+                });
+            }
+            ReviewAction::Back => self.review = None,
+            ReviewAction::Submit(decision) => {
+                let (accepted, total) = review.accepted_count();
+                self.review = None;
+                if let Some(ApprovalVariant::ApplyPatch { id, .. }) = self.current_variant.as_ref()
+                {
+                    let cell = history_cell::new_patch_review_decision_cell(accepted, total);
+                    self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+                    self.handle_patch_decision(id, decision);
+                }
+                self.current_complete = true;
+                self.advance_queue();
+            }
+        }
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -221,6 +271,10 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.review.is_some() {
+            self.handle_review_key_event(key_event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -238,7 +292,7 @@ impl BottomPaneView for ApprovalOverlay {
             && let Some(variant) = self.current_variant.as_ref()
         {
             match &variant {
-                ApprovalVariant::Exec { id, command } => {
+                ApprovalVariant::Exec { id, command, .. } => {
                     self.handle_exec_decision(id, command, ReviewDecision::Abort);
                 }
                 ApprovalVariant::ApplyPatch { id, .. } => {
@@ -247,6 +301,7 @@ impl BottomPaneView for ApprovalOverlay {
             }
         }
         self.queue.clear();
+        self.review = None;
         self.done = true;
         CancellationEvent::Handled
     }
@@ -255,6 +310,12 @@ impl BottomPaneView for ApprovalOverlay {
         self.done
     }
 
+    fn handle_external_edit(&mut self, text: String) {
+        if let Some(review) = self.review.as_mut() {
+            review.apply_edit(&text);
+        }
+    }
+
     fn try_consume_approval_request(
         &mut self,
         request: ApprovalRequest,
@@ -266,15 +327,24 @@ impl BottomPaneView for ApprovalOverlay {
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.review {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.review {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.review {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -309,8 +379,13 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     first.spans.insert(0, Span::from("$ "));
                 }
                 header.extend(full_cmd_lines);
+                let pattern = suggest_command_pattern(&command);
                 Self {
-                    variant: ApprovalVariant::Exec { id, command },
+                    variant: ApprovalVariant::Exec {
+                        id,
+                        command,
+                        pattern,
+                    },
                     header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
                 }
             }
@@ -330,9 +405,16 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
+                let reviewable = PatchReview::new(&changes, &cwd).is_some();
+                let paths: Vec<PathBuf> = changes.keys().cloned().collect();
+                let pattern = suggest_patch_pattern(&cwd, &paths);
                 header.push(DiffSummary::new(changes, cwd).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch {
+                        id,
+                        reviewable,
+                        pattern,
+                    },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -364,14 +446,30 @@ fn render_risk_lines(risk: &SandboxCommandAssessment) -> Vec<Line<'static>> {
 
 #[derive(Clone)]
 enum ApprovalVariant {
-    Exec { id: String, command: Vec<String> },
-    ApplyPatch { id: String },
+    Exec {
+        id: String,
+        command: Vec<String>,
+        /// Rule offered by the "always" options, if one can be derived.
+        pattern: Option<String>,
+    },
+    ApplyPatch {
+        id: String,
+        reviewable: bool,
+        pattern: Option<String>,
+    },
+}
+
+#[derive(Clone)]
+enum ApprovalAction {
+    Decide(ReviewDecision),
+    /// Switch to the hunk-by-hunk patch review.
+    ReviewHunks,
 }
 
 #[derive(Clone)]
 struct ApprovalOption {
     label: String,
-    decision: ReviewDecision,
+    action: ApprovalAction,
     display_shortcut: Option<KeyBinding>,
     additional_shortcuts: Vec<KeyBinding>,
 }
@@ -384,44 +482,98 @@ impl ApprovalOption {
     }
 }
 
-fn exec_options() -> Vec<ApprovalOption> {
-    vec![
+fn exec_options(pattern: Option<&str>) -> Vec<ApprovalOption> {
+    let mut options = vec![
         ApprovalOption {
             label: "Yes, proceed".to_string(),
-            decision: ReviewDecision::Approved,
+            action: ApprovalAction::Decide(ReviewDecision::Approved),
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
         },
         ApprovalOption {
             label: "Yes, and don't ask again for this command".to_string(),
-            decision: ReviewDecision::ApprovedForSession,
+            action: ApprovalAction::Decide(ReviewDecision::ApprovedForSession),
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
-        ApprovalOption {
-            label: "No, and tell LLMX what to do differently".to_string(),
-            decision: ReviewDecision::Abort,
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+    ];
+    if let Some(pattern) = pattern {
+        options.push(always_allow_option(
+            format!("Yes, and always allow `{pattern}` in this project"),
+            pattern,
+        ));
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell LLMX what to do differently".to_string(),
+        action: ApprovalAction::Decide(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    if let Some(pattern) = pattern {
+        options.push(always_deny_option(
+            format!("No, and always deny `{pattern}` in this project"),
+            pattern,
+        ));
+    }
+    options
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
-        ApprovalOption {
-            label: "Yes, proceed".to_string(),
-            decision: ReviewDecision::Approved,
+fn always_allow_option(label: String, pattern: &str) -> ApprovalOption {
+    ApprovalOption {
+        label,
+        action: ApprovalAction::Decide(ReviewDecision::ApprovedAlways {
+            pattern: pattern.to_string(),
+        }),
+        display_shortcut: None,
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('p'))],
+    }
+}
+
+fn always_deny_option(label: String, pattern: &str) -> ApprovalOption {
+    ApprovalOption {
+        label,
+        action: ApprovalAction::Decide(ReviewDecision::DeniedAlways {
+            pattern: pattern.to_string(),
+        }),
+        display_shortcut: None,
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('d'))],
+    }
+}
+
+fn patch_options(reviewable: bool, pattern: Option<&str>) -> Vec<ApprovalOption> {
+    let mut options = vec![ApprovalOption {
+        label: "Yes, proceed".to_string(),
+        action: ApprovalAction::Decide(ReviewDecision::Approved),
+        display_shortcut: None,
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+    }];
+    if reviewable {
+        options.push(ApprovalOption {
+            label: "Review each change before applying it".to_string(),
+            action: ApprovalAction::ReviewHunks,
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
-        },
-        ApprovalOption {
-            label: "No, and tell LLMX what to do differently".to_string(),
-            decision: ReviewDecision::Abort,
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        });
+    }
+    if let Some(pattern) = pattern {
+        options.push(always_allow_option(
+            format!("Yes, and always allow edits to `{pattern}` in this project"),
+            pattern,
+        ));
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell LLMX what to do differently".to_string(),
+        action: ApprovalAction::Decide(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    if let Some(pattern) = pattern {
+        options.push(always_deny_option(
+            format!("No, and always deny edits to `{pattern}` in this project"),
+            pattern,
+        ));
+    }
+    options
 }
 
 #[cfg(test)]
@@ -469,6 +621,26 @@ mod tests {
         assert!(saw_op, "expected approval decision to emit an op");
     }
 
+    #[test]
+    fn always_allow_shortcut_sends_suggested_rule() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx);
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::LlmxOp(Op::ExecApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedAlways {
+                pattern: "echo *".to_string(),
+            })
+        );
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -527,6 +699,53 @@ mod tests {
         assert_eq!(rendered, expected);
     }
 
+    #[test]
+    fn patch_review_sends_partial_approval() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let cwd = PathBuf::from("/repo");
+        let request = ApprovalRequest::ApplyPatch {
+            id: "patch".to_string(),
+            reason: None,
+            cwd: cwd.clone(),
+            changes: HashMap::from([
+                (
+                    cwd.join("a.txt"),
+                    FileChange::Add {
+                        content: "a\n".to_string(),
+                    },
+                ),
+                (
+                    cwd.join("b.txt"),
+                    FileChange::Delete {
+                        content: "b\n".to_string(),
+                    },
+                ),
+            ]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx);
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert!(view.review.is_some());
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::LlmxOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedPartial {
+                patch: "*** Begin Patch\n*** Delete File: b.txt\n*** End Patch".to_string()
+            })
+        );
+    }
+
     #[test]
     fn enter_sets_last_selected_index_without_dismissing() {
         let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
@@ -26,6 +26,10 @@ pub(crate) trait BottomPaneView: Renderable {
         false
     }
 
+    /// Receive the text the user saved in the external editor opened via
+    /// `AppEvent::OpenExternalEditor`.
+    fn handle_external_edit(&mut self, _text: String) {}
+
     /// Try to handle approval request; return the original value if not
     /// consumed.
     fn try_consume_approval_request(
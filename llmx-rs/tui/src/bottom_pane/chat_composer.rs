@@ -19,6 +19,7 @@ use super::chat_composer_history::ChatComposerHistory;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::file_search_popup::FileSearchPopup;
+use super::footer::FooterKeys;
 use super::footer::FooterMode;
 use super::footer::FooterProps;
 use super::footer::esc_hint_mode;
@@ -29,12 +30,15 @@ use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::PromptSubmission;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
-use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::named_argument_names;
+use crate::bottom_pane::prompt_args::named_arguments;
 use crate::bottom_pane::prompt_args::parse_slash_name;
-use crate::bottom_pane::prompt_args::prompt_argument_names;
+use crate::bottom_pane::prompt_args::positional_arguments_from_line;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
+use crate::bottom_pane::prompt_args::prompt_input_names;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -43,14 +47,22 @@ use crate::slash_command::built_in_slash_commands;
 use crate::style::user_message_style;
 use llmx_protocol::custom_prompts::CustomPrompt;
 use llmx_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use llmx_protocol::custom_prompts::PromptArguments;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::textarea::TextArea;
 use crate::bottom_pane::textarea::TextAreaState;
+use crate::bottom_pane::textarea::ViMode;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
+use crate::external_editor;
 use crate::history_cell;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
+use crate::mentions::GIT_PREFIX;
+use crate::mentions::SESSION_PREFIX;
+use crate::mentions::SYMBOL_PREFIX;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use llmx_file_search::FileMatch;
 use std::cell::RefCell;
@@ -85,7 +97,7 @@ enum PromptSelectionMode {
 
 enum PromptSelectionAction {
     Insert { text: String, cursor: Option<usize> },
-    Submit { text: String },
+    Submit { arguments: PromptArguments },
 }
 
 pub(crate) struct ChatComposer {
@@ -109,9 +121,13 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    /// Custom prompt behind the most recent submission, if any.
+    recent_submission_prompt: Option<PromptSubmission>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
+    plan_mode: bool,
+    keymap: Keymap,
 }
 
 /// Popup state – at most one can be visible at any time.
@@ -152,9 +168,12 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            recent_submission_prompt: None,
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
+            plan_mode: false,
+            keymap: Keymap::default(),
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -267,6 +286,24 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    pub(crate) fn set_vi_enabled(&mut self, enabled: bool) {
+        self.textarea.set_vi_enabled(enabled);
+    }
+
+    /// Returns true when the composer itself needs `key_event`, so callers
+    /// should not treat it as a global shortcut (e.g. Esc leaving vi insert
+    /// mode instead of interrupting or backtracking).
+    pub(crate) fn captures_key(&self, key_event: &KeyEvent) -> bool {
+        self.textarea.vi_mode() == Some(ViMode::Insert)
+            && key_event.code == KeyCode::Esc
+            && key_event.modifiers == KeyModifiers::NONE
+            && matches!(self.active_popup, ActivePopup::None)
+    }
+
     /// Override the footer hint items displayed beneath the composer. Passing
     /// `None` restores the default shortcut footer.
     pub(crate) fn set_footer_hint_override(&mut self, items: Option<Vec<(String, String)>>) {
@@ -303,11 +340,7 @@ impl ChatComposer {
 
     /// Attempt to start a burst by retro-capturing recent chars before the cursor.
     pub fn attach_image(&mut self, path: PathBuf, width: u32, height: u32, _format_label: &str) {
-        let file_label = path
-            .file_name()
-            .map(|name| name.to_string_lossy().into_owned())
-            .unwrap_or_else(|| "image".to_string());
-        let placeholder = format!("[{file_label} {width}x{height}]");
+        let placeholder = image_placeholder(&path, width, height);
         // Insert as an element to match large paste placeholder behavior:
         // styled distinctly and treated atomically for cursor/mutations.
         self.textarea.insert_element(&placeholder);
@@ -315,11 +348,97 @@ impl ChatComposer {
             .push(AttachedImage { placeholder, path });
     }
 
+    /// The editor buffer for the current draft: large pastes are expanded so
+    /// they can be edited, and attached images are listed by path.
+    pub(crate) fn external_editor_draft(&self) -> String {
+        let mut text = self.textarea.text().to_string();
+        for (placeholder, actual) in &self.pending_pastes {
+            text = text.replace(placeholder, actual);
+        }
+        let attachments: Vec<PathBuf> = self
+            .attached_images
+            .iter()
+            .map(|img| img.path.clone())
+            .collect();
+        external_editor::draft_with_attachments(&text, &attachments)
+    }
+
+    /// Replace the draft with the buffer saved in the external editor.
+    ///
+    /// Images that are still listed keep their placeholder (and its position
+    /// if it was left in the text); detached images lose theirs. Newly listed
+    /// image paths are attached at the end, and other paths are appended as
+    /// plain text.
+    pub(crate) fn apply_external_edit(&mut self, edited: &str) {
+        let (mut text, paths) = external_editor::split_attachments(edited);
+        let previous = std::mem::take(&mut self.attached_images);
+        for img in &previous {
+            if !paths.contains(&img.path) {
+                text = text.replacen(&img.placeholder, "", 1);
+            }
+        }
+        self.set_text_content(text);
+
+        let mut claimed: Vec<usize> = Vec::new();
+        for path in paths {
+            let known = previous
+                .iter()
+                .find(|img| img.path == path)
+                .map(|img| img.placeholder.clone());
+            let placeholder = known.or_else(|| {
+                image::image_dimensions(&path)
+                    .ok()
+                    .map(|(width, height)| image_placeholder(&path, width, height))
+            });
+            let Some(placeholder) = placeholder else {
+                self.append_to_draft(&path.to_string_lossy(), false);
+                continue;
+            };
+            let existing = self
+                .textarea
+                .text()
+                .match_indices(&placeholder)
+                .map(|(start, _)| start)
+                .find(|start| !claimed.contains(start));
+            match existing {
+                Some(start) => {
+                    claimed.push(start);
+                    self.textarea.add_element(start..start + placeholder.len());
+                }
+                None => self.append_to_draft(&placeholder, true),
+            }
+            self.attached_images
+                .push(AttachedImage { placeholder, path });
+        }
+        self.textarea.set_cursor(self.textarea.text().len());
+    }
+
+    fn append_to_draft(&mut self, text: &str, as_element: bool) {
+        let current = self.textarea.text();
+        let end = current.len();
+        let needs_space = !current.is_empty() && !current.ends_with(char::is_whitespace);
+        self.textarea.set_cursor(end);
+        if needs_space {
+            self.textarea.insert_str(" ");
+        }
+        if as_element {
+            self.textarea.insert_element(text);
+        } else {
+            self.textarea.insert_str(text);
+        }
+    }
+
     pub fn take_recent_submission_images(&mut self) -> Vec<PathBuf> {
         let images = std::mem::take(&mut self.attached_images);
         images.into_iter().map(|img| img.path).collect()
     }
 
+    /// Returns the custom prompt that produced the most recent submission, so
+    /// the caller can submit it as a prompt invocation rather than plain text.
+    pub fn take_recent_submission_prompt(&mut self) -> Option<PromptSubmission> {
+        self.recent_submission_prompt.take()
+    }
+
     pub(crate) fn flush_paste_burst_if_due(&mut self) -> bool {
         self.handle_paste_burst_flush(Instant::now())
     }
@@ -372,6 +491,12 @@ impl ChatComposer {
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
             ActivePopup::None => self.handle_key_event_without_popup(key_event),
         };
+        if matches!(
+            result.0,
+            InputResult::Submitted(_) | InputResult::Command(_)
+        ) {
+            self.textarea.reset_vi_mode();
+        }
 
         // Update (or hide/show) popup after processing the key.
         self.sync_command_popup();
@@ -494,9 +619,14 @@ impl ChatComposer {
                 if let Some((name, _rest)) = parse_slash_name(first_line)
                     && let Some(prompt_name) = name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:"))
                     && let Some(prompt) = self.custom_prompts.iter().find(|p| p.name == prompt_name)
-                    && let Some(expanded) =
-                        expand_if_numeric_with_positional_args(prompt, first_line)
+                    && let Some(arguments) = positional_arguments_from_line(prompt, first_line)
                 {
+                    let submission = PromptSubmission {
+                        prompt: prompt.clone(),
+                        arguments,
+                    };
+                    let expanded = submission.text();
+                    self.recent_submission_prompt = Some(submission);
                     self.textarea.set_text("");
                     return (InputResult::Submitted(expanded), true);
                 }
@@ -514,7 +644,13 @@ impl ChatComposer {
                                     first_line,
                                     PromptSelectionMode::Submit,
                                 ) {
-                                    PromptSelectionAction::Submit { text } => {
+                                    PromptSelectionAction::Submit { arguments } => {
+                                        let submission = PromptSubmission {
+                                            prompt: prompt.clone(),
+                                            arguments,
+                                        };
+                                        let text = submission.text();
+                                        self.recent_submission_prompt = Some(submission);
                                         self.textarea.set_text("");
                                         return (InputResult::Submitted(text), true);
                                     }
@@ -861,7 +997,11 @@ impl ChatComposer {
         if self.handle_shortcut_overlay_key(&key_event) {
             return (InputResult::None, true);
         }
-        if key_event.code == KeyCode::Esc {
+        if self.captures_key(&key_event) {
+            self.textarea.input(key_event);
+            return (InputResult::None, true);
+        }
+        if self.keymap.matches(KeyAction::Backtrack, key_event) {
             if self.is_empty() {
                 let next_mode = esc_hint_mode(self.footer_mode, self.is_task_running);
                 if next_mode != self.footer_mode {
@@ -872,6 +1012,15 @@ impl ChatComposer {
         } else {
             self.footer_mode = reset_mode_after_activity(self.footer_mode);
         }
+        if self.keymap.matches(KeyAction::ExternalEditor, key_event) {
+            self.app_event_tx.send(AppEvent::OpenExternalEditor {
+                text: self.external_editor_draft(),
+                suffix: ".md",
+            });
+            return (InputResult::None, true);
+        }
+        let history_up = self.keymap.matches(KeyAction::HistoryUp, key_event);
+        let history_down = self.keymap.matches(KeyAction::HistoryDown, key_event);
         match key_event {
             KeyEvent {
                 code: KeyCode::Char('d'),
@@ -883,29 +1032,19 @@ impl ChatComposer {
                 (InputResult::None, true)
             }
             // -------------------------------------------------------------
-            // History navigation (Up / Down) – only when the composer is not
-            // empty or when the cursor is at the correct position, to avoid
-            // interfering with normal cursor movement.
+            // History navigation (Up / Down by default) – only when the
+            // composer is not empty or when the cursor is at the correct
+            // position, to avoid interfering with normal cursor movement.
             // -------------------------------------------------------------
-            KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('p') | KeyCode::Char('n'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
+            _ if history_up || history_down => {
                 if self
                     .history
                     .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
                 {
-                    let replace_text = match key_event.code {
-                        KeyCode::Up => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                        KeyCode::Char('p') => self.history.navigate_up(&self.app_event_tx),
-                        KeyCode::Char('n') => self.history.navigate_down(&self.app_event_tx),
-                        _ => unreachable!(),
+                    let replace_text = if history_up {
+                        self.history.navigate_up(&self.app_event_tx)
+                    } else {
+                        self.history.navigate_down(&self.app_event_tx)
                     };
                     if let Some(text) = replace_text {
                         self.set_text_content(text);
@@ -914,11 +1053,12 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
-            KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
-                ..
-            } => {
+            // Configured newline chords all insert a line break, whatever
+            // the chord is; plain Enter keeps its paste-burst handling.
+            _ if self.keymap.matches(KeyAction::Newline, key_event) => {
+                self.handle_input_basic(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            }
+            _ if self.keymap.matches(KeyAction::Submit, key_event) => {
                 // If the first line is a bare built-in slash command (no args),
                 // dispatch it even when the slash popup isn't visible. This preserves
                 // the workflow: type a prefix ("/di"), press Tab to complete to
@@ -1037,8 +1177,10 @@ impl ChatComposer {
                         return (InputResult::None, true);
                     }
                 };
-                if let Some(expanded) = expanded_prompt {
-                    text = expanded;
+                self.recent_submission_prompt = None;
+                if let Some(submission) = expanded_prompt {
+                    text = submission.text();
+                    self.recent_submission_prompt = Some(submission);
                 }
                 if text.is_empty() && !has_attachments {
                     return (InputResult::None, true);
@@ -1097,11 +1239,13 @@ impl ChatComposer {
         }
 
         // Intercept plain Char inputs to optionally accumulate into a burst buffer.
+        // In vi normal mode characters are commands, so they are never buffered.
         if let KeyEvent {
             code: KeyCode::Char(ch),
             modifiers,
             ..
         } = input
+            && self.textarea.vi_mode() != Some(ViMode::Normal)
         {
             let has_ctrl_or_alt =
                 modifiers.contains(KeyModifiers::CONTROL) || modifiers.contains(KeyModifiers::ALT);
@@ -1389,9 +1533,11 @@ impl ChatComposer {
         FooterProps {
             mode: self.footer_mode(),
             esc_backtrack_hint: self.esc_backtrack_hint,
-            use_shift_enter_hint: self.use_shift_enter_hint,
+            keys: FooterKeys::from_keymap(&self.keymap, self.use_shift_enter_hint),
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
+            plan_mode: self.plan_mode,
+            vi_mode: self.textarea.vi_mode(),
         }
     }
 
@@ -1481,6 +1627,16 @@ impl ChatComposer {
             }
         };
 
+        // `@git:`, `@session:` and `@sym:` mentions name revisions, sessions
+        // and symbols, not files.
+        if [GIT_PREFIX, SESSION_PREFIX, SYMBOL_PREFIX]
+            .iter()
+            .any(|prefix| query.starts_with(prefix))
+        {
+            self.active_popup = ActivePopup::None;
+            return;
+        }
+
         // If user dismissed popup for this exact query, don't reopen until text changes.
         if self.dismissed_file_popup_token.as_ref() == Some(&query) {
             return;
@@ -1528,6 +1684,10 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_plan_mode(&mut self, plan_mode: bool) {
+        self.plan_mode = plan_mode;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -1631,25 +1791,34 @@ impl Renderable for ChatComposer {
     }
 }
 
+fn image_placeholder(path: &Path, width: u32, height: u32) -> String {
+    let file_label = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "image".to_string());
+    format!("[{file_label} {width}x{height}]")
+}
+
 fn prompt_selection_action(
     prompt: &CustomPrompt,
     first_line: &str,
     mode: PromptSelectionMode,
 ) -> PromptSelectionAction {
-    let named_args = prompt_argument_names(&prompt.content);
+    let input_names = prompt_input_names(prompt);
+    let has_named = !named_argument_names(prompt).is_empty();
     let has_numeric = prompt_has_numeric_placeholders(&prompt.content);
 
     match mode {
         PromptSelectionMode::Completion => {
-            if !named_args.is_empty() {
+            if !input_names.is_empty() {
                 let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                    prompt_command_with_arg_placeholders(&prompt.name, &input_names);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
                 };
             }
-            if has_numeric {
+            if has_numeric && !has_named {
                 let text = format!("/{PROMPTS_CMD_PREFIX}:{} ", prompt.name);
                 return PromptSelectionAction::Insert { text, cursor: None };
             }
@@ -1657,23 +1826,34 @@ fn prompt_selection_action(
             PromptSelectionAction::Insert { text, cursor: None }
         }
         PromptSelectionMode::Submit => {
-            if !named_args.is_empty() {
+            if !input_names.is_empty() {
                 let (text, cursor) =
-                    prompt_command_with_arg_placeholders(&prompt.name, &named_args);
+                    prompt_command_with_arg_placeholders(&prompt.name, &input_names);
                 return PromptSelectionAction::Insert {
                     text,
                     cursor: Some(cursor),
                 };
             }
+            if has_named {
+                // Every named argument has a default or is optional.
+                let command = format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.name);
+                return match named_arguments(prompt, &HashMap::new(), &command) {
+                    Ok(arguments) => PromptSelectionAction::Submit { arguments },
+                    Err(_) => PromptSelectionAction::Insert {
+                        text: format!("{command} "),
+                        cursor: None,
+                    },
+                };
+            }
             if has_numeric {
-                if let Some(expanded) = expand_if_numeric_with_positional_args(prompt, first_line) {
-                    return PromptSelectionAction::Submit { text: expanded };
+                if let Some(arguments) = positional_arguments_from_line(prompt, first_line) {
+                    return PromptSelectionAction::Submit { arguments };
                 }
                 let text = format!("/{PROMPTS_CMD_PREFIX}:{} ", prompt.name);
                 return PromptSelectionAction::Insert { text, cursor: None };
             }
             PromptSelectionAction::Submit {
-                text: prompt.content.clone(),
+                arguments: PromptArguments::default(),
             }
         }
     }
@@ -2157,6 +2337,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn configured_keymap_swaps_submit_and_newline() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+        use llmx_core::config::types::KeyChords;
+        use llmx_core::config::types::TuiKeymap;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask LLMX to do anything".to_string(),
+            true,
+        );
+        composer.set_keymap(Keymap::from_config(&TuiKeymap {
+            submit: Some(KeyChords::One("ctrl+enter".to_string())),
+            newline: Some(KeyChords::One("enter".to_string())),
+            ..Default::default()
+        }));
+
+        composer.handle_paste("first".to_string());
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::None);
+        composer.handle_paste("second".to_string());
+        assert_eq!(composer.textarea.text(), "first\nsecond");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL));
+        assert_eq!(result, InputResult::Submitted("first\nsecond".to_string()));
+    }
+
+    #[test]
+    fn external_editor_key_sends_composer_text() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask LLMX to do anything".to_string(),
+            false,
+        );
+        composer.handle_paste("draft".to_string());
+
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+
+        match rx.try_recv() {
+            Ok(AppEvent::OpenExternalEditor { text, suffix }) => {
+                assert_eq!(text, "draft");
+                assert_eq!(suffix, ".md");
+            }
+            other => panic!("expected OpenExternalEditor, got {other:?}"),
+        }
+        assert_eq!(composer.textarea.text(), "draft");
+    }
+
+    #[test]
+    fn vi_mode_esc_enters_normal_mode_and_updates_footer() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask LLMX to do anything".to_string(),
+            false,
+        );
+        composer.set_vi_enabled(true);
+        composer.handle_paste("hello".to_string());
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Insert));
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(composer.captures_key(&esc));
+        composer.handle_key_event(esc);
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Normal));
+        assert!(!composer.captures_key(&esc));
+
+        composer.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "hell");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::Submitted("hell".to_string()));
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Insert));
+    }
+
     #[test]
     fn empty_enter_returns_none() {
         use crossterm::event::KeyCode;
@@ -2746,6 +3024,51 @@ mod tests {
         assert_eq!(vec![path], imgs);
     }
 
+    #[test]
+    fn external_edit_round_trips_attached_images() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask LLMX to do anything".to_string(),
+            false,
+        );
+        let first = PathBuf::from("/tmp/first.png");
+        let second = PathBuf::from("/tmp/second.png");
+        composer.handle_paste("compare ".into());
+        composer.attach_image(first.clone(), 32, 16, "PNG");
+        composer.handle_paste(" and ".into());
+        composer.attach_image(second.clone(), 8, 8, "PNG");
+
+        let draft = composer.external_editor_draft();
+        assert!(draft.starts_with("compare [first.png 32x16] and [second.png 8x8]\n\n"));
+        assert!(draft.ends_with("/tmp/first.png\n/tmp/second.png\n"));
+
+        // Detach the first image and reword the prompt around the second.
+        let edited = draft
+            .replace("compare", "describe")
+            .replace("/tmp/first.png\n", "");
+        composer.apply_external_edit(&edited);
+
+        assert_eq!(composer.textarea.text(), "describe  and [second.png 8x8]");
+        assert_eq!(
+            composer.attached_images,
+            vec![AttachedImage {
+                placeholder: "[second.png 8x8]".to_string(),
+                path: second.clone(),
+            }]
+        );
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            result,
+            InputResult::Submitted("describe  and [second.png 8x8]".to_string())
+        );
+        assert_eq!(composer.take_recent_submission_images(), vec![second]);
+    }
+
     #[test]
     fn attach_image_without_text_submits_empty_text_and_images() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -2946,6 +3269,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         type_chars_humanlike(
@@ -2981,6 +3305,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         composer
@@ -3015,6 +3340,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         composer
@@ -3119,6 +3445,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         composer
@@ -3169,6 +3496,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         // Provide only one of the required args
@@ -3222,6 +3550,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         // Type the slash command with two args and hit Enter to submit.
@@ -3259,6 +3588,7 @@ mod tests {
             content: "Echo: $ARGUMENTS".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         // Type positional args; should submit with numeric expansion, no errors.
@@ -3290,6 +3620,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         type_chars_humanlike(
@@ -3326,6 +3657,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         type_chars_humanlike(
@@ -3363,6 +3695,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }]);
 
         type_chars_humanlike(
@@ -54,6 +54,13 @@ impl CustomPromptView {
             complete: false,
         }
     }
+
+    /// Prefill the input, e.g. with text the user is asked to revise.
+    pub(crate) fn with_initial_text(mut self, text: &str) -> Self {
+        self.textarea.set_text(text);
+        self.textarea.set_cursor(text.len());
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
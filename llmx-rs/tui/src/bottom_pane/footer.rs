@@ -1,5 +1,8 @@
+use crate::bottom_pane::textarea::ViMode;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
 use crate::render::line_utils::prefix_lines;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
@@ -15,9 +18,29 @@ use ratatui::widgets::Widget;
 pub(crate) struct FooterProps {
     pub(crate) mode: FooterMode,
     pub(crate) esc_backtrack_hint: bool,
-    pub(crate) use_shift_enter_hint: bool,
+    pub(crate) keys: FooterKeys,
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
+    pub(crate) plan_mode: bool,
+    pub(crate) vi_mode: Option<ViMode>,
+}
+
+/// Configurable key bindings shown in footer hints.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct FooterKeys {
+    pub(crate) newline: KeyBinding,
+    pub(crate) show_transcript: KeyBinding,
+    pub(crate) edit_previous: KeyBinding,
+}
+
+impl FooterKeys {
+    pub(crate) fn from_keymap(keymap: &Keymap, enhanced_keys_supported: bool) -> Self {
+        Self {
+            newline: keymap.newline_hint(enhanced_keys_supported),
+            show_transcript: keymap.primary(KeyAction::OpenTranscript),
+            edit_previous: keymap.primary(KeyAction::Backtrack),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -81,7 +104,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             is_task_running: props.is_task_running,
         })],
         FooterMode::ShortcutSummary => {
-            let mut line = context_window_line(props.context_window_percent);
+            let mut line = context_window_line(props);
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
@@ -90,11 +113,14 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             vec![line]
         }
         FooterMode::ShortcutOverlay => shortcut_overlay_lines(ShortcutsState {
-            use_shift_enter_hint: props.use_shift_enter_hint,
+            keys: props.keys,
             esc_backtrack_hint: props.esc_backtrack_hint,
         }),
-        FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint)],
-        FooterMode::ContextOnly => vec![context_window_line(props.context_window_percent)],
+        FooterMode::EscHint => vec![esc_hint_line(
+            props.keys.edit_previous,
+            props.esc_backtrack_hint,
+        )],
+        FooterMode::ContextOnly => vec![context_window_line(props)],
     }
 }
 
@@ -105,7 +131,7 @@ struct CtrlCReminderState {
 
 #[derive(Clone, Copy, Debug)]
 struct ShortcutsState {
-    use_shift_enter_hint: bool,
+    keys: FooterKeys,
     esc_backtrack_hint: bool,
 }
 
@@ -122,8 +148,7 @@ fn ctrl_c_reminder_line(state: CtrlCReminderState) -> Line<'static> {
     .dim()
 }
 
-fn esc_hint_line(esc_backtrack_hint: bool) -> Line<'static> {
-    let esc = key_hint::plain(KeyCode::Esc);
+fn esc_hint_line(esc: KeyBinding, esc_backtrack_hint: bool) -> Line<'static> {
     if esc_backtrack_hint {
         Line::from(vec![esc.into(), " again to edit previous message".into()]).dim()
     } else {
@@ -147,16 +172,15 @@ fn shortcut_overlay_lines(state: ShortcutsState) -> Vec<Line<'static>> {
     let mut show_transcript = Line::from("");
 
     for descriptor in SHORTCUTS {
-        if let Some(text) = descriptor.overlay_entry(state) {
-            match descriptor.id {
-                ShortcutId::Commands => commands = text,
-                ShortcutId::InsertNewline => newline = text,
-                ShortcutId::FilePaths => file_paths = text,
-                ShortcutId::PasteImage => paste_image = text,
-                ShortcutId::EditPrevious => edit_previous = text,
-                ShortcutId::Quit => quit = text,
-                ShortcutId::ShowTranscript => show_transcript = text,
-            }
+        let text = descriptor.overlay_entry(state);
+        match descriptor.id {
+            ShortcutId::Commands => commands = text,
+            ShortcutId::InsertNewline => newline = text,
+            ShortcutId::FilePaths => file_paths = text,
+            ShortcutId::PasteImage => paste_image = text,
+            ShortcutId::EditPrevious => edit_previous = text,
+            ShortcutId::Quit => quit = text,
+            ShortcutId::ShowTranscript => show_transcript = text,
         }
     }
 
@@ -221,9 +245,26 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-fn context_window_line(percent: Option<i64>) -> Line<'static> {
-    let percent = percent.unwrap_or(100).clamp(0, 100);
-    Line::from(vec![Span::from(format!("{percent}% context left")).dim()])
+fn context_window_line(props: FooterProps) -> Line<'static> {
+    let percent = props.context_window_percent.unwrap_or(100).clamp(0, 100);
+    let mut spans = Vec::new();
+    match props.vi_mode {
+        Some(ViMode::Insert) => {
+            spans.push("-- INSERT --".dim());
+            spans.push(" · ".dim());
+        }
+        Some(ViMode::Normal) => {
+            spans.push("-- NORMAL --".bold());
+            spans.push(" · ".dim());
+        }
+        None => {}
+    }
+    if props.plan_mode {
+        spans.push("plan mode".cyan());
+        spans.push(" · ".dim());
+    }
+    spans.push(Span::from(format!("{percent}% context left")).dim());
+    Line::from(spans)
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -237,50 +278,38 @@ enum ShortcutId {
     ShowTranscript,
 }
 
+/// Where a shortcut's key comes from: fixed, or one of the configurable
+/// bindings carried in [`FooterKeys`].
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-struct ShortcutBinding {
-    key: KeyBinding,
-    condition: DisplayCondition,
-}
-
-impl ShortcutBinding {
-    fn matches(&self, state: ShortcutsState) -> bool {
-        self.condition.matches(state)
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum DisplayCondition {
-    Always,
-    WhenShiftEnterHint,
-    WhenNotShiftEnterHint,
+enum ShortcutKey {
+    Fixed(KeyBinding),
+    Newline,
+    ShowTranscript,
+    EditPrevious,
 }
 
-impl DisplayCondition {
-    fn matches(self, state: ShortcutsState) -> bool {
+impl ShortcutKey {
+    fn resolve(self, state: ShortcutsState) -> KeyBinding {
         match self {
-            DisplayCondition::Always => true,
-            DisplayCondition::WhenShiftEnterHint => state.use_shift_enter_hint,
-            DisplayCondition::WhenNotShiftEnterHint => !state.use_shift_enter_hint,
+            ShortcutKey::Fixed(key) => key,
+            ShortcutKey::Newline => state.keys.newline,
+            ShortcutKey::ShowTranscript => state.keys.show_transcript,
+            ShortcutKey::EditPrevious => state.keys.edit_previous,
         }
     }
 }
 
 struct ShortcutDescriptor {
     id: ShortcutId,
-    bindings: &'static [ShortcutBinding],
+    key: ShortcutKey,
     prefix: &'static str,
     label: &'static str,
 }
 
 impl ShortcutDescriptor {
-    fn binding_for(&self, state: ShortcutsState) -> Option<&'static ShortcutBinding> {
-        self.bindings.iter().find(|binding| binding.matches(state))
-    }
-
-    fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
-        let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+    fn overlay_entry(&self, state: ShortcutsState) -> Line<'static> {
+        let key = self.key.resolve(state);
+        let mut line = Line::from(vec![self.prefix.into(), key.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
@@ -288,84 +317,57 @@ impl ShortcutDescriptor {
                 } else {
                     line.extend(vec![
                         " ".into(),
-                        key_hint::plain(KeyCode::Esc).into(),
+                        key.into(),
                         " to edit previous message".into(),
                     ]);
                 }
             }
             _ => line.push_span(self.label),
         };
-        Some(line)
+        line
     }
 }
 
 const SHORTCUTS: &[ShortcutDescriptor] = &[
     ShortcutDescriptor {
         id: ShortcutId::Commands,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Char('/')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::plain(KeyCode::Char('/'))),
         prefix: "",
         label: " for commands",
     },
     ShortcutDescriptor {
         id: ShortcutId::InsertNewline,
-        bindings: &[
-            ShortcutBinding {
-                key: key_hint::shift(KeyCode::Enter),
-                condition: DisplayCondition::WhenShiftEnterHint,
-            },
-            ShortcutBinding {
-                key: key_hint::ctrl(KeyCode::Char('j')),
-                condition: DisplayCondition::WhenNotShiftEnterHint,
-            },
-        ],
+        key: ShortcutKey::Newline,
         prefix: "",
         label: " for newline",
     },
     ShortcutDescriptor {
         id: ShortcutId::FilePaths,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Char('@')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::plain(KeyCode::Char('@'))),
         prefix: "",
         label: " for file paths",
     },
     ShortcutDescriptor {
         id: ShortcutId::PasteImage,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('v')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::ctrl(KeyCode::Char('v'))),
         prefix: "",
         label: " to paste images",
     },
     ShortcutDescriptor {
         id: ShortcutId::EditPrevious,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Esc),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::EditPrevious,
         prefix: "",
         label: "",
     },
     ShortcutDescriptor {
         id: ShortcutId::Quit,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('c')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::Fixed(key_hint::ctrl(KeyCode::Char('c'))),
         prefix: "",
         label: " to exit",
     },
     ShortcutDescriptor {
         id: ShortcutId::ShowTranscript,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('t')),
-            condition: DisplayCondition::Always,
-        }],
+        key: ShortcutKey::ShowTranscript,
         prefix: "",
         label: " to view transcript",
     },
@@ -375,6 +377,8 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
 mod tests {
     use super::*;
     use insta::assert_snapshot;
+    use llmx_core::config::types::KeyChords;
+    use llmx_core::config::types::TuiKeymap;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
 
@@ -397,9 +401,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::ShortcutSummary,
                 esc_backtrack_hint: false,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: false,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -408,9 +414,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::ShortcutOverlay,
                 esc_backtrack_hint: true,
-                use_shift_enter_hint: true,
+                keys: FooterKeys::from_keymap(&Keymap::default(), true),
                 is_task_running: false,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -419,9 +427,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::CtrlCReminder,
                 esc_backtrack_hint: false,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: false,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -430,9 +440,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::CtrlCReminder,
                 esc_backtrack_hint: false,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: true,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -441,9 +453,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::EscHint,
                 esc_backtrack_hint: false,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: false,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -452,9 +466,11 @@ mod tests {
             FooterProps {
                 mode: FooterMode::EscHint,
                 esc_backtrack_hint: true,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: false,
                 context_window_percent: None,
+                plan_mode: false,
+                vi_mode: None,
             },
         );
 
@@ -463,10 +479,67 @@ mod tests {
             FooterProps {
                 mode: FooterMode::ShortcutSummary,
                 esc_backtrack_hint: false,
-                use_shift_enter_hint: false,
+                keys: FooterKeys::from_keymap(&Keymap::default(), false),
                 is_task_running: true,
                 context_window_percent: Some(72),
+                plan_mode: false,
+                vi_mode: None,
             },
         );
     }
+
+    #[test]
+    fn footer_shows_plan_mode_indicator() {
+        let props = FooterProps {
+            mode: FooterMode::ShortcutSummary,
+            esc_backtrack_hint: false,
+            keys: FooterKeys::from_keymap(&Keymap::default(), false),
+            is_task_running: false,
+            context_window_percent: Some(50),
+            plan_mode: true,
+            vi_mode: None,
+        };
+        let text: String = footer_lines(props)[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(text.starts_with("plan mode · 50% context left"));
+    }
+
+    #[test]
+    fn footer_reflects_keymap_and_vi_mode() {
+        let keymap = Keymap::from_config(&TuiKeymap {
+            newline: Some(KeyChords::One("alt+j".to_string())),
+            open_transcript: Some(KeyChords::One("ctrl+o".to_string())),
+            ..Default::default()
+        });
+        let line_text = |line: &Line<'static>| -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        let props = FooterProps {
+            mode: FooterMode::ShortcutOverlay,
+            esc_backtrack_hint: false,
+            keys: FooterKeys::from_keymap(&keymap, true),
+            is_task_running: false,
+            context_window_percent: None,
+            plan_mode: false,
+            vi_mode: None,
+        };
+        let overlay: Vec<String> = footer_lines(props).iter().map(line_text).collect();
+        assert!(overlay[0].contains("⌥ + j for newline"));
+        assert!(overlay[3].contains("ctrl + o to view transcript"));
+
+        let summary = FooterProps {
+            mode: FooterMode::ShortcutSummary,
+            vi_mode: Some(ViMode::Normal),
+            ..props
+        };
+        assert!(
+            line_text(&footer_lines(summary)[0]).starts_with("-- NORMAL -- · 100% context left")
+        );
+    }
 }
@@ -42,6 +42,8 @@ pub(crate) struct SelectionItem {
     pub actions: Vec<SelectionAction>,
     pub dismiss_on_select: bool,
     pub search_value: Option<String>,
+    /// Runs when the item becomes highlighted, e.g. to preview it.
+    pub on_highlight: Option<SelectionAction>,
 }
 
 pub(crate) struct SelectionViewParams {
@@ -52,6 +54,8 @@ pub(crate) struct SelectionViewParams {
     pub is_searchable: bool,
     pub search_placeholder: Option<String>,
     pub header: Box<dyn Renderable>,
+    /// Runs when the view is dismissed without accepting an item.
+    pub on_cancel: Option<SelectionAction>,
 }
 
 impl Default for SelectionViewParams {
@@ -64,6 +68,7 @@ impl Default for SelectionViewParams {
             is_searchable: false,
             search_placeholder: None,
             header: Box::new(()),
+            on_cancel: None,
         }
     }
 }
@@ -80,6 +85,10 @@ pub(crate) struct ListSelectionView {
     filtered_indices: Vec<usize>,
     last_selected_actual_idx: Option<usize>,
     header: Box<dyn Renderable>,
+    on_cancel: Option<SelectionAction>,
+    /// Item whose `on_highlight` ran last, so moving onto the same item again
+    /// does not repeat it.
+    highlighted_actual_idx: Option<usize>,
 }
 
 impl ListSelectionView {
@@ -110,11 +119,36 @@ impl ListSelectionView {
             filtered_indices: Vec::new(),
             last_selected_actual_idx: None,
             header,
+            on_cancel: params.on_cancel,
+            highlighted_actual_idx: None,
         };
         s.apply_filter();
+        // The initial selection is what is already in effect; only later
+        // moves count as highlighting something new.
+        s.highlighted_actual_idx = s.selected_actual_idx();
         s
     }
 
+    fn selected_actual_idx(&self) -> Option<usize> {
+        self.state
+            .selected_idx
+            .and_then(|idx| self.filtered_indices.get(idx).copied())
+    }
+
+    fn notify_highlight(&mut self) {
+        let actual_idx = self.selected_actual_idx();
+        if actual_idx == self.highlighted_actual_idx {
+            return;
+        }
+        self.highlighted_actual_idx = actual_idx;
+        if let Some(action) = actual_idx
+            .and_then(|idx| self.items.get(idx))
+            .and_then(|item| item.on_highlight.as_ref())
+        {
+            action(&self.app_event_tx);
+        }
+    }
+
     fn visible_len(&self) -> usize {
         self.filtered_indices.len()
     }
@@ -170,6 +204,7 @@ impl ListSelectionView {
         let visible = Self::max_visible_rows(len);
         self.state.clamp_selection(len);
         self.state.ensure_visible(len, visible);
+        self.notify_highlight();
     }
 
     fn build_rows(&self) -> Vec<GenericDisplayRow> {
@@ -215,6 +250,7 @@ impl ListSelectionView {
         self.state.move_up_wrap(len);
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
+        self.notify_highlight();
     }
 
     fn move_down(&mut self) {
@@ -222,6 +258,7 @@ impl ListSelectionView {
         self.state.move_down_wrap(len);
         let visible = Self::max_visible_rows(len);
         self.state.ensure_visible(len, visible);
+        self.notify_highlight();
     }
 
     fn accept(&mut self) {
@@ -317,6 +354,11 @@ impl BottomPaneView for ListSelectionView {
     }
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete
+            && let Some(on_cancel) = &self.on_cancel
+        {
+            on_cancel(&self.app_event_tx);
+        }
         self.complete = true;
         CancellationEvent::Handled
     }
@@ -432,6 +474,7 @@ mod tests {
     use crate::app_event::AppEvent;
     use crate::bottom_pane::popup_consts::standard_popup_hint_line;
     use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
     use ratatui::layout::Rect;
     use tokio::sync::mpsc::unbounded_channel;
 
@@ -535,4 +578,49 @@ mod tests {
             "expected search query line to include rendered query, got {lines:?}"
         );
     }
+
+    #[test]
+    fn runs_highlight_and_cancel_actions() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let items = ["first", "second"]
+            .into_iter()
+            .map(|name| SelectionItem {
+                name: name.to_string(),
+                is_current: name == "first",
+                dismiss_on_select: true,
+                on_highlight: Some(Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::PreviewTheme(Some(name.to_string())));
+                })),
+                ..Default::default()
+            })
+            .collect();
+        let mut view = ListSelectionView::new(
+            SelectionViewParams {
+                items,
+                on_cancel: Some(Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::PreviewTheme(None));
+                })),
+                ..Default::default()
+            },
+            tx,
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Up));
+        view.handle_key_event(KeyEvent::from(KeyCode::Esc));
+        // Already dismissed: the cancel action does not run twice.
+        view.on_ctrl_c();
+
+        let mut previews = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::PreviewTheme(name) = event {
+                previews.push(name);
+            }
+        }
+        assert_eq!(
+            previews,
+            vec![Some("second".to_string()), Some("first".to_string()), None]
+        );
+    }
 }
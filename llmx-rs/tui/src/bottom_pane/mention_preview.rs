@@ -0,0 +1,120 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::mentions::MentionState;
+use crate::render::renderable::Renderable;
+
+/// Widget listing the `@` mentions in the composer with what each one will
+/// attach and roughly how many tokens it costs.
+pub(crate) struct MentionPreviews {
+    pub previews: Vec<(String, MentionState)>,
+    /// A submitted message is waiting for the mentions; Esc cancels it.
+    pub sending: bool,
+}
+
+impl MentionPreviews {
+    pub(crate) fn new() -> Self {
+        Self {
+            previews: Vec::new(),
+            sending: false,
+        }
+    }
+
+    fn line(&self, token: &str, state: &MentionState) -> Line<'static> {
+        let mut spans = vec!["  + ".dim(), token.to_string().cyan(), " · ".dim()];
+        match state {
+            MentionState::Resolving => {
+                spans.push("resolving…".dim().italic());
+                if self.sending {
+                    spans.push(" · esc to cancel sending".dim());
+                }
+            }
+            MentionState::Ready(resolved) => {
+                spans.push(resolved.label.clone().dim());
+                spans.push(" · ".dim());
+                spans.push(resolved.token_label().dim());
+            }
+            MentionState::Failed(err) => spans.push(format!("{err} (skipped)").red()),
+        }
+        Line::from(spans)
+    }
+}
+
+impl Renderable for MentionPreviews {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let lines: Vec<Line<'static>> = self
+            .previews
+            .iter()
+            .map(|(token, state)| self.line(token, state))
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        self.previews.len().try_into().unwrap_or(u16::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mentions::ResolvedMention;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn renders_one_line_per_mention() {
+        let mut previews = MentionPreviews::new();
+        previews.previews = vec![
+            (
+                "@src/".to_string(),
+                MentionState::Ready(ResolvedMention {
+                    token: "@src/".to_string(),
+                    label: "directory tree, 3 entries".to_string(),
+                    text: "x".repeat(40),
+                }),
+            ),
+            ("@sym:Config".to_string(), MentionState::Resolving),
+            (
+                "@git:nope".to_string(),
+                MentionState::Failed("unknown revision".to_string()),
+            ),
+        ];
+        assert_eq!(
+            render_rows(&previews),
+            vec![
+                "  + @src/ · directory tree, 3 entries · ~10 tokens",
+                "  + @sym:Config · resolving…",
+                "  + @git:nope · unknown revision (skipped)",
+            ]
+        );
+
+        previews.sending = true;
+        assert_eq!(
+            render_rows(&previews)[1],
+            "  + @sym:Config · resolving… · esc to cancel sending"
+        );
+    }
+
+    fn render_rows(previews: &MentionPreviews) -> Vec<String> {
+        let width = 60;
+        let height = previews.desired_height(width);
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        previews.render(Rect::new(0, 0, width, height), &mut buf);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+}
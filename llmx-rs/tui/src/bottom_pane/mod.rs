@@ -2,7 +2,11 @@
 use std::path::PathBuf;
 
 use crate::app_event_sender::AppEventSender;
+use crate::bottom_pane::mention_preview::MentionPreviews;
 use crate::bottom_pane::queued_user_messages::QueuedUserMessages;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
+use crate::mentions::MentionState;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
@@ -10,6 +14,7 @@ use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use llmx_core::config::types::EditingMode;
 use llmx_file_search::FileMatch;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -28,11 +33,16 @@ mod footer;
 mod list_selection_view;
 mod prompt_args;
 pub(crate) use list_selection_view::SelectionViewParams;
+pub(crate) use prompt_args::PromptSubmission;
 mod feedback_view;
 pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
+mod mention_preview;
 mod paste_burst;
+mod patch_review;
 pub mod popup_consts;
+mod queued_messages_view;
+pub(crate) use queued_messages_view::QueuedMessagesView;
 mod queued_user_messages;
 mod scroll_state;
 mod selection_popup_common;
@@ -74,7 +84,10 @@ pub(crate) struct BottomPane {
     status: Option<StatusIndicatorWidget>,
     /// Queued user messages to show above the composer while a turn is running.
     queued_user_messages: QueuedUserMessages,
+    /// `@` mentions in the composer and what they will attach.
+    mention_previews: MentionPreviews,
     context_window_percent: Option<i64>,
+    keymap: Keymap,
 }
 
 pub(crate) struct BottomPaneParams {
@@ -84,19 +97,24 @@ pub(crate) struct BottomPaneParams {
     pub(crate) enhanced_keys_supported: bool,
     pub(crate) placeholder_text: String,
     pub(crate) disable_paste_burst: bool,
+    pub(crate) keymap: Keymap,
+    pub(crate) editing_mode: EditingMode,
 }
 
 impl BottomPane {
     pub fn new(params: BottomPaneParams) -> Self {
         let enhanced_keys_supported = params.enhanced_keys_supported;
+        let mut composer = ChatComposer::new(
+            params.has_input_focus,
+            params.app_event_tx.clone(),
+            enhanced_keys_supported,
+            params.placeholder_text,
+            params.disable_paste_burst,
+        );
+        composer.set_keymap(params.keymap.clone());
+        composer.set_vi_enabled(params.editing_mode == EditingMode::Vi);
         Self {
-            composer: ChatComposer::new(
-                params.has_input_focus,
-                params.app_event_tx.clone(),
-                enhanced_keys_supported,
-                params.placeholder_text,
-                params.disable_paste_burst,
-            ),
+            composer,
             view_stack: Vec::new(),
             app_event_tx: params.app_event_tx,
             frame_requester: params.frame_requester,
@@ -105,8 +123,10 @@ impl BottomPane {
             ctrl_c_quit_hint: false,
             status: None,
             queued_user_messages: QueuedUserMessages::new(),
+            mention_previews: MentionPreviews::new(),
             esc_backtrack_hint: false,
             context_window_percent: None,
+            keymap: params.keymap,
         }
     }
 
@@ -143,9 +163,11 @@ impl BottomPane {
             self.request_redraw();
             InputResult::None
         } else {
-            // If a task is running and a status line is visible, allow Esc to
-            // send an interrupt even while the composer has focus.
-            if matches!(key_event.code, crossterm::event::KeyCode::Esc)
+            // If a task is running and a status line is visible, allow the
+            // interrupt key (Esc by default) to send an interrupt even while
+            // the composer has focus.
+            if self.keymap.matches(KeyAction::Interrupt, key_event)
+                && !self.composer.captures_key(&key_event)
                 && self.is_task_running
                 && let Some(status) = &self.status
             {
@@ -205,6 +227,19 @@ impl BottomPane {
         }
     }
 
+    /// Hands text edited in the external editor to the active view.
+    pub(crate) fn handle_external_edit(&mut self, text: String) {
+        if let Some(view) = self.view_stack.last_mut() {
+            view.handle_external_edit(text);
+            if view.is_complete() {
+                self.on_active_view_complete();
+            }
+        } else {
+            self.composer.apply_external_edit(&text);
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn insert_str(&mut self, text: &str) {
         self.composer.insert_str(text);
         self.request_redraw();
@@ -287,6 +322,7 @@ impl BottomPane {
                 self.status = Some(StatusIndicatorWidget::new(
                     self.app_event_tx.clone(),
                     self.frame_requester.clone(),
+                    self.keymap.primary(KeyAction::Interrupt),
                 ));
             }
             if let Some(status) = self.status.as_mut() {
@@ -311,6 +347,7 @@ impl BottomPane {
             self.status = Some(StatusIndicatorWidget::new(
                 self.app_event_tx.clone(),
                 self.frame_requester.clone(),
+                self.keymap.primary(KeyAction::Interrupt),
             ));
             self.request_redraw();
         }
@@ -333,6 +370,11 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_plan_mode(&mut self, plan_mode: bool) {
+        self.composer.set_plan_mode(plan_mode);
+        self.request_redraw();
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -345,6 +387,18 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update the `@` mention previews shown above the composer. `sending` is
+    /// set while a submitted message waits for them.
+    pub(crate) fn set_mention_previews(
+        &mut self,
+        previews: Vec<(String, MentionState)>,
+        sending: bool,
+    ) {
+        self.mention_previews.previews = previews;
+        self.mention_previews.sending = sending;
+        self.request_redraw();
+    }
+
     /// Update custom prompts available for the slash popup.
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.composer.set_custom_prompts(prompts);
@@ -355,6 +409,10 @@ impl BottomPane {
         self.composer.is_empty()
     }
 
+    pub(crate) fn composer_captures_key(&self, key_event: &KeyEvent) -> bool {
+        self.view_stack.is_empty() && self.composer.captures_key(key_event)
+    }
+
     pub(crate) fn is_task_running(&self) -> bool {
         self.is_task_running
     }
@@ -467,6 +525,10 @@ impl BottomPane {
         self.composer.take_recent_submission_images()
     }
 
+    pub(crate) fn take_recent_submission_prompt(&mut self) -> Option<PromptSubmission> {
+        self.composer.take_recent_submission_prompt()
+    }
+
     fn as_renderable(&'_ self) -> RenderableItem<'_> {
         if let Some(view) = self.active_view() {
             RenderableItem::Borrowed(view)
@@ -476,7 +538,11 @@ impl BottomPane {
                 flex.push(0, RenderableItem::Borrowed(status));
             }
             flex.push(1, RenderableItem::Borrowed(&self.queued_user_messages));
-            if self.status.is_some() || !self.queued_user_messages.messages.is_empty() {
+            flex.push(0, RenderableItem::Borrowed(&self.mention_previews));
+            if self.status.is_some()
+                || !self.queued_user_messages.messages.is_empty()
+                || !self.mention_previews.previews.is_empty()
+            {
                 flex.push(0, RenderableItem::Owned("".into()));
             }
             let mut flex2 = FlexRenderable::new();
@@ -546,6 +612,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
         pane.push_approval_request(exec_request());
         assert_eq!(CancellationEvent::Handled, pane.on_ctrl_c());
@@ -566,6 +634,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         // Create an approval modal (active view).
@@ -597,6 +667,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         // Start a running task so the status indicator is active above the composer.
@@ -662,6 +734,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         // Begin a task: show initial status.
@@ -687,6 +761,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         // Activate spinner (status view replaces composer) with no live ring.
@@ -716,6 +792,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         pane.set_task_running(true);
@@ -742,6 +820,8 @@ mod tests {
             enhanced_keys_supported: false,
             placeholder_text: "Ask LLMX to do anything".to_string(),
             disable_paste_burst: false,
+            keymap: Keymap::default(),
+            editing_mode: EditingMode::default(),
         });
 
         pane.set_task_running(true);
@@ -0,0 +1,702 @@
+//! Hunk-by-hunk review of a proposed patch, shown inside the approval
+//! overlay. Each hunk can be accepted, rejected, or edited in `$EDITOR`
+//! before it is accepted. The accepted hunks are turned back into an
+//! `apply_patch` body and sent to core as [`ReviewDecision::ApprovedPartial`].
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use llmx_core::protocol::FileChange;
+use llmx_core::protocol::ReviewDecision;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+
+use crate::diff_render::DiffLineType;
+use crate::diff_render::display_path_for;
+use crate::diff_render::line_number_width;
+use crate::diff_render::push_wrapped_diff_line;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+
+/// Hunks taller than this are truncated; the full hunk can be read in the
+/// editor.
+const MAX_HUNK_ROWS: usize = 20;
+
+const EDIT_INSTRUCTIONS: &str = "\
+# Edit the hunk below, then save and quit to accept it.
+# Lines starting with '+' are added. To drop an added line, delete it.
+# To keep a line that would be removed, change its leading '-' to ' '.
+# Lines starting with ' ' or '-' must otherwise stay as they are.
+# Lines starting with '#' are ignored.
+";
+
+/// What the approval overlay should do after a key press in the review.
+#[derive(Debug, PartialEq)]
+pub(crate) enum ReviewAction {
+    None,
+    /// Open this text in the external editor; the result comes back through
+    /// [`PatchReview::apply_edit`].
+    OpenEditor(String),
+    /// The review is finished; send this decision.
+    Submit(ReviewDecision),
+    /// Return to the approve/deny options.
+    Back,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum HunkLine {
+    Context(String),
+    Delete(String),
+    Insert(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HunkDecision {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(Debug)]
+struct HunkReview {
+    old_start: usize,
+    new_start: usize,
+    lines: Vec<HunkLine>,
+    edited: bool,
+    decision: HunkDecision,
+}
+
+#[derive(Debug)]
+enum FileKind {
+    Add,
+    Delete,
+    Update { move_path: Option<PathBuf> },
+}
+
+#[derive(Debug)]
+struct FileReview {
+    path: PathBuf,
+    kind: FileKind,
+    hunks: Vec<HunkReview>,
+}
+
+#[derive(Debug)]
+pub(crate) struct PatchReview {
+    cwd: PathBuf,
+    files: Vec<FileReview>,
+    file_idx: usize,
+    hunk_idx: usize,
+    error: Option<String>,
+}
+
+impl PatchReview {
+    /// Splits `changes` into reviewable hunks. Returns `None` when a diff
+    /// cannot be parsed, in which case only whole-patch approval is offered.
+    pub(crate) fn new(changes: &HashMap<PathBuf, FileChange>, cwd: &Path) -> Option<Self> {
+        let mut paths: Vec<&PathBuf> = changes.keys().collect();
+        paths.sort();
+
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let (kind, hunks) = match &changes[path] {
+                FileChange::Add { content } => (
+                    FileKind::Add,
+                    vec![HunkReview::new(
+                        0,
+                        1,
+                        content
+                            .lines()
+                            .map(|line| HunkLine::Insert(line.to_string()))
+                            .collect(),
+                    )],
+                ),
+                FileChange::Delete { content } => (
+                    FileKind::Delete,
+                    vec![HunkReview::new(
+                        1,
+                        0,
+                        content
+                            .lines()
+                            .map(|line| HunkLine::Delete(line.to_string()))
+                            .collect(),
+                    )],
+                ),
+                FileChange::Update {
+                    unified_diff,
+                    move_path,
+                } => (
+                    FileKind::Update {
+                        move_path: move_path.clone(),
+                    },
+                    parse_hunks(unified_diff)?,
+                ),
+            };
+            if hunks.is_empty() {
+                return None;
+            }
+            files.push(FileReview {
+                path: path.clone(),
+                kind,
+                hunks,
+            });
+        }
+        if files.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            cwd: cwd.to_path_buf(),
+            files,
+            file_idx: 0,
+            hunk_idx: 0,
+            error: None,
+        })
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> ReviewAction {
+        if key_event.kind == KeyEventKind::Release
+            || key_event
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return ReviewAction::None;
+        }
+        self.error = None;
+        match key_event.code {
+            KeyCode::Char('y') => self.decide_current(HunkDecision::Accepted),
+            KeyCode::Char('n') => self.decide_current(HunkDecision::Rejected),
+            KeyCode::Char('Y') => self.decide_rest_of_file(HunkDecision::Accepted),
+            KeyCode::Char('N') => self.decide_rest_of_file(HunkDecision::Rejected),
+            KeyCode::Char('e') => return self.edit_current(),
+            KeyCode::Up | KeyCode::Char('k') => self.move_hunk(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_hunk(1),
+            KeyCode::Left | KeyCode::Char('h') => self.move_file(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_file(1),
+            KeyCode::Enter => return ReviewAction::Submit(self.decision()),
+            KeyCode::Esc => return ReviewAction::Back,
+            _ => {}
+        }
+        ReviewAction::None
+    }
+
+    /// Applies the text saved in the editor to the current hunk and accepts
+    /// it.
+    pub(crate) fn apply_edit(&mut self, text: &str) {
+        let hunk = self.current_hunk_mut();
+        match parse_edited_hunk(text, &hunk.lines) {
+            Ok(lines) => {
+                hunk.edited |= lines != hunk.lines;
+                hunk.lines = lines;
+                hunk.decision = HunkDecision::Accepted;
+                self.error = None;
+                self.advance();
+            }
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    /// Number of accepted hunks and total number of hunks.
+    pub(crate) fn accepted_count(&self) -> (usize, usize) {
+        let hunks = self.files.iter().flat_map(|file| &file.hunks);
+        let accepted = hunks
+            .clone()
+            .filter(|hunk| hunk.decision == HunkDecision::Accepted)
+            .count();
+        (accepted, hunks.count())
+    }
+
+    /// The decision to send to core. Undecided hunks count as rejected.
+    pub(crate) fn decision(&self) -> ReviewDecision {
+        let hunks = || self.files.iter().flat_map(|file| &file.hunks);
+        if hunks().all(|hunk| hunk.decision != HunkDecision::Accepted) {
+            ReviewDecision::Denied
+        } else if hunks().all(|hunk| hunk.decision == HunkDecision::Accepted && !hunk.edited) {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::ApprovedPartial {
+                patch: self.build_patch(),
+            }
+        }
+    }
+
+    /// Renders the accepted hunks as an `apply_patch` body.
+    fn build_patch(&self) -> String {
+        let mut out = vec!["*** Begin Patch".to_string()];
+        for file in &self.files {
+            let accepted: Vec<&HunkReview> = file
+                .hunks
+                .iter()
+                .filter(|hunk| hunk.decision == HunkDecision::Accepted)
+                .collect();
+            if accepted.is_empty() {
+                continue;
+            }
+            let path = patch_path(&file.path, &self.cwd);
+            match &file.kind {
+                FileKind::Add => {
+                    out.push(format!("*** Add File: {path}"));
+                    for hunk in accepted {
+                        out.extend(hunk.lines.iter().map(HunkLine::to_patch_line));
+                    }
+                }
+                FileKind::Delete => out.push(format!("*** Delete File: {path}")),
+                FileKind::Update { move_path } => {
+                    out.push(format!("*** Update File: {path}"));
+                    if let Some(move_path) = move_path {
+                        out.push(format!("*** Move to: {}", patch_path(move_path, &self.cwd)));
+                    }
+                    for hunk in accepted {
+                        out.push("@@".to_string());
+                        out.extend(hunk.lines.iter().map(HunkLine::to_patch_line));
+                    }
+                }
+            }
+        }
+        out.push("*** End Patch".to_string());
+        out.join("\n")
+    }
+
+    fn current_hunk_mut(&mut self) -> &mut HunkReview {
+        &mut self.files[self.file_idx].hunks[self.hunk_idx]
+    }
+
+    fn decide_current(&mut self, decision: HunkDecision) {
+        self.current_hunk_mut().decision = decision;
+        self.advance();
+    }
+
+    fn decide_rest_of_file(&mut self, decision: HunkDecision) {
+        for hunk in &mut self.files[self.file_idx].hunks {
+            if hunk.decision == HunkDecision::Pending {
+                hunk.decision = decision;
+            }
+        }
+        self.advance();
+    }
+
+    fn edit_current(&mut self) -> ReviewAction {
+        if matches!(self.files[self.file_idx].kind, FileKind::Delete) {
+            self.error = Some("Deleting a file cannot be edited; accept or reject it".to_string());
+            return ReviewAction::None;
+        }
+        let hunk = &self.files[self.file_idx].hunks[self.hunk_idx];
+        let mut text = EDIT_INSTRUCTIONS.to_string();
+        for line in &hunk.lines {
+            text.push_str(&line.to_patch_line());
+            text.push('\n');
+        }
+        ReviewAction::OpenEditor(text)
+    }
+
+    /// Moves to the next undecided hunk, or to the next hunk when every hunk
+    /// has been decided.
+    fn advance(&mut self) {
+        let positions = self.positions();
+        let current = self.position_index(&positions);
+        let next = positions
+            .iter()
+            .cycle()
+            .skip(current + 1)
+            .take(positions.len())
+            .find(|(file, hunk)| self.files[*file].hunks[*hunk].decision == HunkDecision::Pending)
+            .or_else(|| positions.get(current + 1));
+        if let Some((file, hunk)) = next {
+            self.file_idx = *file;
+            self.hunk_idx = *hunk;
+        }
+    }
+
+    fn move_hunk(&mut self, delta: isize) {
+        let positions = self.positions();
+        let current = self.position_index(&positions);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(positions.len() - 1);
+        (self.file_idx, self.hunk_idx) = positions[next];
+    }
+
+    fn move_file(&mut self, delta: isize) {
+        self.file_idx = self
+            .file_idx
+            .saturating_add_signed(delta)
+            .min(self.files.len() - 1);
+        self.hunk_idx = 0;
+    }
+
+    fn positions(&self) -> Vec<(usize, usize)> {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(|(file_idx, file)| (0..file.hunks.len()).map(move |hunk| (file_idx, hunk)))
+            .collect()
+    }
+
+    fn position_index(&self, positions: &[(usize, usize)]) -> usize {
+        positions
+            .iter()
+            .position(|position| *position == (self.file_idx, self.hunk_idx))
+            .unwrap_or(0)
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let file = &self.files[self.file_idx];
+        let hunk = &file.hunks[self.hunk_idx];
+
+        let mut path = display_path_for(&file.path, &self.cwd);
+        if let FileKind::Update {
+            move_path: Some(move_path),
+        } = &file.kind
+        {
+            path = format!("{path} → {}", display_path_for(move_path, &self.cwd));
+        }
+        let status = match (hunk.decision, hunk.edited) {
+            (HunkDecision::Pending, _) => "undecided".dim(),
+            (HunkDecision::Accepted, false) => "accepted".green(),
+            (HunkDecision::Accepted, true) => "accepted (edited)".green(),
+            (HunkDecision::Rejected, _) => "rejected".red(),
+        };
+
+        let mut lines = vec![
+            Line::from("Review the proposed edits".bold()),
+            Line::from(""),
+            Line::from(vec![
+                path.bold(),
+                format!(
+                    "  file {}/{} · hunk {}/{} · ",
+                    self.file_idx + 1,
+                    self.files.len(),
+                    self.hunk_idx + 1,
+                    file.hunks.len()
+                )
+                .dim(),
+                status,
+            ]),
+            Line::from(""),
+        ];
+
+        let diff_lines = hunk.render(width as usize);
+        let hidden = diff_lines.len().saturating_sub(MAX_HUNK_ROWS);
+        lines.extend(diff_lines.into_iter().take(MAX_HUNK_ROWS));
+        if hidden > 0 {
+            lines.push(
+                format!("… {hidden} more lines (press e to see the whole hunk)")
+                    .dim()
+                    .into(),
+            );
+        }
+        lines.push(Line::from(""));
+
+        let (accepted, total) = self.accepted_count();
+        let rejected = self
+            .files
+            .iter()
+            .flat_map(|file| &file.hunks)
+            .filter(|hunk| hunk.decision == HunkDecision::Rejected)
+            .count();
+        lines.push(
+            format!(
+                "{accepted} accepted · {rejected} rejected · {} undecided (undecided hunks are not applied)",
+                total - accepted - rejected
+            )
+            .dim()
+            .into(),
+        );
+        if let Some(error) = &self.error {
+            lines.push(error.clone().red().into());
+        }
+        lines.push(Line::from(footer_hint()));
+        lines
+    }
+}
+
+impl Renderable for PatchReview {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines(area.width))
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        Paragraph::new(self.lines(width))
+            .wrap(Wrap { trim: false })
+            .line_count(width)
+            .try_into()
+            .unwrap_or(u16::MAX)
+    }
+}
+
+impl HunkReview {
+    fn new(old_start: usize, new_start: usize, lines: Vec<HunkLine>) -> Self {
+        Self {
+            old_start,
+            new_start,
+            lines,
+            edited: false,
+            decision: HunkDecision::Pending,
+        }
+    }
+
+    fn render(&self, width: usize) -> Vec<Line<'static>> {
+        let max_line_number = self.old_start.max(self.new_start) + self.lines.len();
+        let number_width = line_number_width(max_line_number);
+        let mut old_ln = self.old_start;
+        let mut new_ln = self.new_start;
+        let mut out = Vec::new();
+        for line in &self.lines {
+            let (line_number, kind, text) = match line {
+                HunkLine::Context(text) => {
+                    old_ln += 1;
+                    new_ln += 1;
+                    (new_ln - 1, DiffLineType::Context, text)
+                }
+                HunkLine::Delete(text) => {
+                    old_ln += 1;
+                    (old_ln - 1, DiffLineType::Delete, text)
+                }
+                HunkLine::Insert(text) => {
+                    new_ln += 1;
+                    (new_ln - 1, DiffLineType::Insert, text)
+                }
+            };
+            out.extend(push_wrapped_diff_line(
+                line_number,
+                kind,
+                text,
+                width,
+                number_width,
+            ));
+        }
+        out
+    }
+}
+
+impl HunkLine {
+    fn to_patch_line(&self) -> String {
+        match self {
+            HunkLine::Context(text) => format!(" {text}"),
+            HunkLine::Delete(text) => format!("-{text}"),
+            HunkLine::Insert(text) => format!("+{text}"),
+        }
+    }
+
+    /// The line as it appears in the file before the patch, if at all.
+    fn old_text(&self) -> Option<&str> {
+        match self {
+            HunkLine::Context(text) | HunkLine::Delete(text) => Some(text),
+            HunkLine::Insert(_) => None,
+        }
+    }
+}
+
+fn parse_hunks(unified_diff: &str) -> Option<Vec<HunkReview>> {
+    let patch = diffy::Patch::from_str(unified_diff).ok()?;
+    Some(
+        patch
+            .hunks()
+            .iter()
+            .map(|hunk| {
+                let lines = hunk
+                    .lines()
+                    .iter()
+                    .map(|line| match line {
+                        diffy::Line::Context(text) => {
+                            HunkLine::Context(text.trim_end_matches('\n').to_string())
+                        }
+                        diffy::Line::Delete(text) => {
+                            HunkLine::Delete(text.trim_end_matches('\n').to_string())
+                        }
+                        diffy::Line::Insert(text) => {
+                            HunkLine::Insert(text.trim_end_matches('\n').to_string())
+                        }
+                    })
+                    .collect();
+                HunkReview::new(hunk.old_range().start(), hunk.new_range().start(), lines)
+            })
+            .collect(),
+    )
+}
+
+/// Parses a hunk saved in the editor. The lines of the original file that
+/// the hunk covers must be unchanged, otherwise the hunk would no longer
+/// apply.
+fn parse_edited_hunk(text: &str, original: &[HunkLine]) -> Result<Vec<HunkLine>, String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let parsed = match line.chars().next() {
+            // Editors commonly strip the trailing space of empty context lines.
+            None => HunkLine::Context(String::new()),
+            Some(' ') => HunkLine::Context(line[1..].to_string()),
+            Some('-') => HunkLine::Delete(line[1..].to_string()),
+            Some('+') => HunkLine::Insert(line[1..].to_string()),
+            Some(_) => {
+                return Err(format!(
+                    "Edited hunk not applied: every line must start with ' ', '-' or '+', got: {line}"
+                ));
+            }
+        };
+        lines.push(parsed);
+    }
+
+    let old_side = |lines: &[HunkLine]| -> Vec<String> {
+        lines
+            .iter()
+            .filter_map(|line| line.old_text().map(str::to_string))
+            .collect()
+    };
+    if old_side(&lines) != old_side(original) {
+        return Err(
+            "Edited hunk not applied: lines starting with ' ' or '-' must match the original"
+                .to_string(),
+        );
+    }
+    Ok(lines)
+}
+
+/// Path as written in the patch: relative to `cwd` when possible.
+fn patch_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+fn footer_hint() -> Vec<Span<'static>> {
+    let hints = [
+        (key_hint::plain(KeyCode::Char('y')), " accept  "),
+        (key_hint::plain(KeyCode::Char('n')), " reject  "),
+        (key_hint::plain(KeyCode::Char('e')), " edit  "),
+        (key_hint::plain(KeyCode::Char('Y')), "/"),
+        (key_hint::plain(KeyCode::Char('N')), " rest of file  "),
+        (key_hint::plain(KeyCode::Up), "/"),
+        (key_hint::plain(KeyCode::Down), " hunk  "),
+        (key_hint::plain(KeyCode::Left), "/"),
+        (key_hint::plain(KeyCode::Right), " file  "),
+        (key_hint::plain(KeyCode::Enter), " apply  "),
+        (key_hint::plain(KeyCode::Esc), " back"),
+    ];
+    hints
+        .into_iter()
+        .flat_map(|(key, label)| [key.into(), label.dim()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn press(review: &mut PatchReview, code: KeyCode) -> ReviewAction {
+        review.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn sample_review() -> PatchReview {
+        let cwd = PathBuf::from("/repo");
+        let changes = HashMap::from([
+            (
+                cwd.join("src/lib.rs"),
+                FileChange::Update {
+                    unified_diff: "@@ -1,3 +1,3 @@\n fn a() {}\n-fn b() {}\n+fn b2() {}\n fn c() {}\n@@ -10,2 +10,3 @@\n fn x() {}\n+fn y() {}\n fn z() {}\n".to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                cwd.join("notes.txt"),
+                FileChange::Add {
+                    content: "one\ntwo\n".to_string(),
+                },
+            ),
+        ]);
+        PatchReview::new(&changes, &cwd).expect("reviewable patch")
+    }
+
+    #[test]
+    fn accepting_everything_approves_the_whole_patch() {
+        let mut review = sample_review();
+        press(&mut review, KeyCode::Char('y'));
+        press(&mut review, KeyCode::Char('Y'));
+        assert_eq!(review.accepted_count(), (3, 3));
+        assert_eq!(
+            press(&mut review, KeyCode::Enter),
+            ReviewAction::Submit(ReviewDecision::Approved)
+        );
+    }
+
+    #[test]
+    fn rejecting_everything_denies_the_patch() {
+        let mut review = sample_review();
+        press(&mut review, KeyCode::Char('N'));
+        press(&mut review, KeyCode::Char('N'));
+        assert_eq!(review.decision(), ReviewDecision::Denied);
+    }
+
+    #[test]
+    fn builds_patch_from_accepted_hunks() {
+        let mut review = sample_review();
+        // Files are sorted: notes.txt, then src/lib.rs.
+        press(&mut review, KeyCode::Char('n'));
+        press(&mut review, KeyCode::Char('y'));
+        press(&mut review, KeyCode::Char('n'));
+
+        assert_eq!(
+            review.decision(),
+            ReviewDecision::ApprovedPartial {
+                patch: "*** Begin Patch\n\
+                        *** Update File: src/lib.rs\n\
+                        @@\n \
+                        fn a() {}\n\
+                        -fn b() {}\n\
+                        +fn b2() {}\n \
+                        fn c() {}\n\
+                        *** End Patch"
+                    .to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn edited_hunk_is_accepted_and_marks_the_patch_partial() {
+        let mut review = sample_review();
+        press(&mut review, KeyCode::Char('y'));
+        let ReviewAction::OpenEditor(text) = press(&mut review, KeyCode::Char('e')) else {
+            panic!("expected the editor to open");
+        };
+        assert!(text.ends_with(" fn a() {}\n-fn b() {}\n+fn b2() {}\n fn c() {}\n"));
+
+        review.apply_edit(&text.replace("+fn b2() {}", "+fn renamed() {}"));
+        press(&mut review, KeyCode::Char('y'));
+
+        let ReviewDecision::ApprovedPartial { patch } = review.decision() else {
+            panic!("expected a partial approval");
+        };
+        assert!(patch.contains("+fn renamed() {}"), "{patch}");
+        assert!(patch.contains("+fn y() {}"), "{patch}");
+    }
+
+    #[test]
+    fn rejects_edits_that_change_the_original_lines() {
+        let mut review = sample_review();
+        press(&mut review, KeyCode::Char('y'));
+        review.apply_edit(" fn a() {}\n+fn b2() {}\n fn c() {}\n");
+        assert_eq!(
+            review.error.as_deref(),
+            Some("Edited hunk not applied: lines starting with ' ' or '-' must match the original")
+        );
+        assert_eq!(review.accepted_count(), (1, 3));
+
+        // Keeping a removed line as context is allowed.
+        review.apply_edit(" fn a() {}\n fn b() {}\n+fn b2() {}\n fn c() {}\n");
+        assert_eq!(review.error, None);
+        assert_eq!(review.accepted_count(), (2, 3));
+    }
+}
@@ -1,6 +1,7 @@
 use lazy_static::lazy_static;
 use llmx_protocol::custom_prompts::CustomPrompt;
 use llmx_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use llmx_protocol::custom_prompts::PromptArguments;
 use regex_lite::Regex;
 use shlex::Shlex;
 use std::collections::HashMap;
@@ -40,6 +41,11 @@ pub enum PromptExpansionError {
         command: String,
         missing: Vec<String>,
     },
+    InvalidArg {
+        command: String,
+        name: String,
+        message: String,
+    },
 }
 
 impl PromptExpansionError {
@@ -52,10 +58,30 @@ impl PromptExpansionError {
                     "Missing required args for {command}: {list}. Provide as key=value (quote values with spaces)."
                 )
             }
+            PromptExpansionError::InvalidArg {
+                command,
+                name,
+                message,
+            } => format!("Invalid value for {name} in {command}: {message}."),
         }
     }
 }
 
+/// A custom prompt invocation together with its argument values. The session
+/// substitutes the values after expanding the prompt body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptSubmission {
+    pub prompt: CustomPrompt,
+    pub arguments: PromptArguments,
+}
+
+impl PromptSubmission {
+    /// The prompt body with the arguments substituted, as shown to the user.
+    pub fn text(&self) -> String {
+        self.arguments.substitute(&self.prompt.content)
+    }
+}
+
 /// Parse a first-line slash command of the form `/name <rest>`.
 /// Returns `(name, rest_after_name)` if the line begins with `/` and contains
 /// a non-empty name; otherwise returns `None`.
@@ -106,6 +132,76 @@ pub fn prompt_argument_names(content: &str) -> Vec<String> {
     names
 }
 
+/// Returns the named arguments of `prompt`: the `$NAME` placeholders in its
+/// body followed by any arguments declared only in its frontmatter.
+pub fn named_argument_names(prompt: &CustomPrompt) -> Vec<String> {
+    let mut names = prompt_argument_names(&prompt.content);
+    for argument in &prompt.arguments {
+        if !names.contains(&argument.name) {
+            names.push(argument.name.clone());
+        }
+    }
+    names
+}
+
+/// Returns the named arguments the user has to provide: undeclared
+/// placeholders and required arguments without a default.
+pub fn prompt_input_names(prompt: &CustomPrompt) -> Vec<String> {
+    named_argument_names(prompt)
+        .into_iter()
+        .filter(|name| match prompt.argument(name) {
+            Some(argument) => argument.required && argument.default.is_none(),
+            None => true,
+        })
+        .collect()
+}
+
+/// Resolves the named arguments of `prompt` from `inputs`, falling back to
+/// declared defaults. Declared arguments are checked against their type;
+/// optional ones without a value expand to an empty string.
+pub fn named_arguments(
+    prompt: &CustomPrompt,
+    inputs: &HashMap<String, String>,
+    command: &str,
+) -> Result<PromptArguments, PromptExpansionError> {
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut missing = Vec::new();
+    for name in named_argument_names(prompt) {
+        let declared = prompt.argument(&name);
+        let value = inputs
+            .get(&name)
+            .cloned()
+            .or_else(|| declared.and_then(|argument| argument.default.clone()));
+        match (value, declared) {
+            (Some(value), Some(argument)) => {
+                let value = argument.validate(&value).map_err(|message| {
+                    PromptExpansionError::InvalidArg {
+                        command: command.to_string(),
+                        name: name.clone(),
+                        message,
+                    }
+                })?;
+                values.insert(name, value);
+            }
+            (Some(value), None) => {
+                values.insert(name, value);
+            }
+            (None, Some(argument)) if !argument.required => {
+                values.insert(name, String::new());
+            }
+            (None, _) => missing.push(name),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: command.to_string(),
+            missing,
+        });
+    }
+
+    Ok(PromptArguments::Named { values })
+}
+
 /// Parses the `key=value` pairs that follow a custom prompt name.
 ///
 /// The input is split using shlex rules, so quoted values are supported
@@ -133,11 +229,11 @@ pub fn parse_prompt_inputs(rest: &str) -> Result<HashMap<String, String>, Prompt
 ///
 /// If the text does not start with `/prompts:`, or if no prompt named `name` exists,
 /// the function returns `Ok(None)`. On success it returns
-/// `Ok(Some(expanded))`; otherwise it returns a descriptive error.
+/// `Ok(Some(submission))`; otherwise it returns a descriptive error.
 pub fn expand_custom_prompt(
     text: &str,
     custom_prompts: &[CustomPrompt],
-) -> Result<Option<String>, PromptExpansionError> {
+) -> Result<Option<PromptSubmission>, PromptExpansionError> {
     let Some((name, rest)) = parse_slash_name(text) else {
         return Ok(None);
     };
@@ -151,45 +247,26 @@ pub fn expand_custom_prompt(
         Some(prompt) => prompt,
         None => return Ok(None),
     };
-    // If there are named placeholders, expect key=value inputs.
-    let required = prompt_argument_names(&prompt.content);
-    if !required.is_empty() {
+    // If there are named arguments, expect key=value inputs.
+    if !named_argument_names(prompt).is_empty() {
+        let command = format!("/{name}");
         let inputs = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
-            command: format!("/{name}"),
+            command: command.clone(),
             error,
         })?;
-        let missing: Vec<String> = required
-            .into_iter()
-            .filter(|k| !inputs.contains_key(k))
-            .collect();
-        if !missing.is_empty() {
-            return Err(PromptExpansionError::MissingArgs {
-                command: format!("/{name}"),
-                missing,
-            });
-        }
-        let content = &prompt.content;
-        let replaced = PROMPT_ARG_REGEX.replace_all(content, |caps: &regex_lite::Captures<'_>| {
-            if let Some(matched) = caps.get(0)
-                && matched.start() > 0
-                && content.as_bytes()[matched.start() - 1] == b'$'
-            {
-                return matched.as_str().to_string();
-            }
-            let whole = &caps[0];
-            let key = &whole[1..];
-            inputs
-                .get(key)
-                .cloned()
-                .unwrap_or_else(|| whole.to_string())
-        });
-        return Ok(Some(replaced.into_owned()));
+        let arguments = named_arguments(prompt, &inputs, &command)?;
+        return Ok(Some(PromptSubmission {
+            prompt: prompt.clone(),
+            arguments,
+        }));
     }
 
     // Otherwise, treat it as numeric/positional placeholder prompt (or none).
-    let pos_args: Vec<String> = Shlex::new(rest).collect();
-    let expanded = expand_numeric_placeholders(&prompt.content, &pos_args);
-    Ok(Some(expanded))
+    let values: Vec<String> = Shlex::new(rest).collect();
+    Ok(Some(PromptSubmission {
+        prompt: prompt.clone(),
+        arguments: PromptArguments::Positional { values },
+    }))
 }
 
 /// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
@@ -235,65 +312,22 @@ pub fn extract_positional_args_for_prompt_line(line: &str, prompt_name: &str) ->
 }
 
 /// If the prompt only uses numeric placeholders and the first line contains
-/// positional args for it, expand and return Some(expanded); otherwise None.
-pub fn expand_if_numeric_with_positional_args(
+/// positional args for it, returns those args; otherwise None.
+pub fn positional_arguments_from_line(
     prompt: &CustomPrompt,
     first_line: &str,
-) -> Option<String> {
-    if !prompt_argument_names(&prompt.content).is_empty() {
+) -> Option<PromptArguments> {
+    if !named_argument_names(prompt).is_empty() {
         return None;
     }
     if !prompt_has_numeric_placeholders(&prompt.content) {
         return None;
     }
-    let args = extract_positional_args_for_prompt_line(first_line, &prompt.name);
-    if args.is_empty() {
+    let values = extract_positional_args_for_prompt_line(first_line, &prompt.name);
+    if values.is_empty() {
         return None;
     }
-    Some(expand_numeric_placeholders(&prompt.content, &args))
-}
-
-/// Expand `$1..$9` and `$ARGUMENTS` in `content` with values from `args`.
-pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
-    let mut out = String::with_capacity(content.len());
-    let mut i = 0;
-    let mut cached_joined_args: Option<String> = None;
-    while let Some(off) = content[i..].find('$') {
-        let j = i + off;
-        out.push_str(&content[i..j]);
-        let rest = &content[j..];
-        let bytes = rest.as_bytes();
-        if bytes.len() >= 2 {
-            match bytes[1] {
-                b'$' => {
-                    out.push_str("$$");
-                    i = j + 2;
-                    continue;
-                }
-                b'1'..=b'9' => {
-                    let idx = (bytes[1] - b'1') as usize;
-                    if let Some(val) = args.get(idx) {
-                        out.push_str(val);
-                    }
-                    i = j + 2;
-                    continue;
-                }
-                _ => {}
-            }
-        }
-        if rest.len() > "ARGUMENTS".len() && rest[1..].starts_with("ARGUMENTS") {
-            if !args.is_empty() {
-                let joined = cached_joined_args.get_or_insert_with(|| args.join(" "));
-                out.push_str(joined);
-            }
-            i = j + 1 + "ARGUMENTS".len();
-            continue;
-        }
-        out.push('$');
-        i = j + 1;
-    }
-    out.push_str(&content[i..]);
-    out
+    Some(PromptArguments::Positional { values })
 }
 
 /// Constructs a command text for a custom prompt with arguments.
@@ -313,6 +347,8 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
 #[cfg(test)]
 mod tests {
     use super::*;
+    use llmx_protocol::custom_prompts::PromptArgument;
+    use llmx_protocol::custom_prompts::PromptArgumentType;
 
     #[test]
     fn expand_arguments_basic() {
@@ -322,10 +358,12 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }];
 
-        let out =
-            expand_custom_prompt("/prompts:my-prompt USER=Alice BRANCH=main", &prompts).unwrap();
+        let out = expand_custom_prompt("/prompts:my-prompt USER=Alice BRANCH=main", &prompts)
+            .unwrap()
+            .map(|submission| submission.text());
         assert_eq!(out, Some("Review Alice changes on main".to_string()));
     }
 
@@ -337,13 +375,15 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }];
 
         let out = expand_custom_prompt(
             "/prompts:my-prompt USER=\"Alice Smith\" BRANCH=dev-main",
             &prompts,
         )
-        .unwrap();
+        .unwrap()
+        .map(|submission| submission.text());
         assert_eq!(out, Some("Pair Alice Smith with dev-main".to_string()));
     }
 
@@ -355,6 +395,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice stray", &prompts)
             .unwrap_err()
@@ -370,6 +411,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice", &prompts)
             .unwrap_err()
@@ -398,9 +440,68 @@ mod tests {
             content: "literal $$USER".to_string(),
             description: None,
             argument_hint: None,
+            ..Default::default()
         }];
 
-        let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
+        let out = expand_custom_prompt("/prompts:my-prompt", &prompts)
+            .unwrap()
+            .map(|submission| submission.text());
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    #[test]
+    fn declared_arguments_use_defaults_and_types() {
+        let prompts = vec![CustomPrompt {
+            name: "triage".to_string(),
+            path: "/tmp/triage.md".to_string().into(),
+            content: "Triage $TICKET with $RETRIES retries in $MODE mode$NOTE".to_string(),
+            arguments: vec![
+                PromptArgument {
+                    name: "TICKET".to_string(),
+                    required: true,
+                    ..Default::default()
+                },
+                PromptArgument {
+                    name: "RETRIES".to_string(),
+                    kind: PromptArgumentType::Number,
+                    default: Some("3".to_string()),
+                    ..Default::default()
+                },
+                PromptArgument {
+                    name: "MODE".to_string(),
+                    kind: PromptArgumentType::Enum,
+                    choices: vec!["fast".to_string(), "thorough".to_string()],
+                    default: Some("fast".to_string()),
+                    ..Default::default()
+                },
+                PromptArgument {
+                    name: "NOTE".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }];
+
+        let out = expand_custom_prompt("/prompts:triage TICKET=ABC-1", &prompts)
+            .unwrap()
+            .map(|submission| submission.text());
+        assert_eq!(
+            out,
+            Some("Triage ABC-1 with 3 retries in fast mode".to_string())
+        );
+        assert_eq!(prompt_input_names(&prompts[0]), vec!["TICKET".to_string()]);
+
+        let err = expand_custom_prompt("/prompts:triage TICKET=ABC-1 RETRIES=many", &prompts)
+            .unwrap_err()
+            .user_message();
+        assert_eq!(
+            err,
+            "Invalid value for RETRIES in /prompts:triage: expected a number but got 'many'."
+        );
+
+        let err = expand_custom_prompt("/prompts:triage RETRIES=2", &prompts)
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("TICKET"));
+    }
 }
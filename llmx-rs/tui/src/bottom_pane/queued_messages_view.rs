@@ -0,0 +1,367 @@
+//! Bottom-pane view for editing, reordering and deleting the messages queued
+//! while a turn is running.
+//!
+//! The view keeps its own copy of the message texts so it can redraw
+//! immediately, and reports every change as an
+//! [`AppEvent::EditQueuedMessages`] so the chat widget can apply it to the
+//! real queue. The chat widget holds the queue back while this view is open;
+//! the view reports [`QueuedMessageEdit::Closed`] when it is dropped, so the
+//! queue is released even if the view is dismissed without being closed.
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event::QueuedMessageEdit;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+pub(crate) struct QueuedMessagesView {
+    messages: Vec<String>,
+    state: ScrollState,
+    app_event_tx: AppEventSender,
+    /// Message currently open in the external editor.
+    editing: Option<usize>,
+    complete: bool,
+}
+
+impl QueuedMessagesView {
+    pub(crate) fn new(messages: Vec<String>, app_event_tx: AppEventSender) -> Self {
+        let mut state = ScrollState::new();
+        // Start on the most recently queued message, like alt + ↑ does.
+        state.selected_idx = messages.len().checked_sub(1);
+        state.ensure_visible(messages.len(), MAX_POPUP_ROWS);
+        Self {
+            messages,
+            state,
+            app_event_tx,
+            editing: None,
+            complete: false,
+        }
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.state
+            .selected_idx
+            .filter(|&idx| idx < self.messages.len())
+    }
+
+    fn send(&self, edit: QueuedMessageEdit) {
+        self.app_event_tx.send(AppEvent::EditQueuedMessages(edit));
+    }
+
+    fn move_selection(&mut self, up: bool) {
+        let len = self.messages.len();
+        if up {
+            self.state.move_up_wrap(len);
+        } else {
+            self.state.move_down_wrap(len);
+        }
+        self.state.ensure_visible(len, MAX_POPUP_ROWS);
+    }
+
+    /// Swap the selected message with its neighbour; the selection follows it.
+    fn move_selected(&mut self, up: bool) {
+        let Some(from) = self.selected() else {
+            return;
+        };
+        let to = if up {
+            from.checked_sub(1)
+        } else {
+            Some(from + 1).filter(|&to| to < self.messages.len())
+        };
+        let Some(to) = to else {
+            return;
+        };
+        self.messages.swap(from, to);
+        self.state.selected_idx = Some(to);
+        self.state
+            .ensure_visible(self.messages.len(), MAX_POPUP_ROWS);
+        self.send(QueuedMessageEdit::Move { from, to });
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.messages.remove(index);
+        self.state.clamp_selection(self.messages.len());
+        self.state
+            .ensure_visible(self.messages.len(), MAX_POPUP_ROWS);
+        self.send(QueuedMessageEdit::Remove(index));
+        if self.messages.is_empty() {
+            self.close();
+        }
+    }
+
+    fn edit_selected(&mut self) {
+        let Some(index) = self.selected() else {
+            return;
+        };
+        self.editing = Some(index);
+        self.app_event_tx.send(AppEvent::OpenExternalEditor {
+            text: self.messages[index].clone(),
+            suffix: ".md",
+        });
+    }
+
+    fn close(&mut self) {
+        if !self.complete {
+            self.complete = true;
+            self.send(QueuedMessageEdit::Closed);
+        }
+    }
+
+    fn rows(&self) -> Vec<GenericDisplayRow> {
+        self.messages
+            .iter()
+            .enumerate()
+            .map(|(idx, message)| {
+                let mut lines = message.lines();
+                let first = lines.next().unwrap_or_default();
+                let more = lines.count();
+                GenericDisplayRow {
+                    name: format!("{}. {first}", idx + 1),
+                    display_shortcut: None,
+                    match_indices: None,
+                    is_current: false,
+                    description: (more > 0).then(|| format!("+{more} lines")),
+                }
+            })
+            .collect()
+    }
+}
+
+impl BottomPaneView for QueuedMessagesView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        let shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+        match key_event.code {
+            KeyCode::Up if shift => self.move_selected(true),
+            KeyCode::Down if shift => self.move_selected(false),
+            KeyCode::Char('K') => self.move_selected(true),
+            KeyCode::Char('J') => self.move_selected(false),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(true),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(false),
+            KeyCode::Enter | KeyCode::Char('e') => self.edit_selected(),
+            KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d') => {
+                if let Some(index) = self.selected() {
+                    self.remove(index);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.close(),
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.close();
+        CancellationEvent::Handled
+    }
+
+    /// Saving an empty buffer drops the message, like deleting it.
+    fn handle_external_edit(&mut self, text: String) {
+        let Some(index) = self.editing.take().filter(|&idx| idx < self.messages.len()) else {
+            return;
+        };
+        let text = text.strip_suffix('\n').unwrap_or(&text).to_string();
+        if text.trim().is_empty() {
+            self.remove(index);
+        } else {
+            self.messages[index] = text.clone();
+            self.send(QueuedMessageEdit::Replace { index, text });
+        }
+    }
+}
+
+impl Drop for QueuedMessagesView {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Renderable for QueuedMessagesView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = measure_rows_height(&self.rows(), &self.state, MAX_POPUP_ROWS, width);
+        // Title, rows, blank line, hint.
+        rows + 3
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        Line::from(vec![
+            "  ".into(),
+            "Queued messages".bold(),
+            " (sent in this order when the current turn ends)".dim(),
+        ])
+        .render(Rect { height: 1, ..area }, buf);
+
+        let rows_height = area.height.saturating_sub(3);
+        let rows_area = Rect {
+            y: area.y + 1,
+            height: rows_height,
+            ..area
+        };
+        render_rows(
+            rows_area,
+            buf,
+            &self.rows(),
+            &self.state,
+            MAX_POPUP_ROWS,
+            "no queued messages",
+        );
+
+        if area.height >= 3 {
+            let hint_area = Rect {
+                y: area.bottom() - 1,
+                height: 1,
+                ..area
+            };
+            Line::from(footer_hint()).render(hint_area, buf);
+        }
+    }
+}
+
+fn footer_hint() -> Vec<Span<'static>> {
+    let hints = [
+        (key_hint::plain(KeyCode::Char('e')), " edit  "),
+        (key_hint::plain(KeyCode::Char('d')), " delete  "),
+        (key_hint::shift(KeyCode::Up), "/"),
+        (key_hint::shift(KeyCode::Down), " move  "),
+        (key_hint::plain(KeyCode::Esc), " done"),
+    ];
+    let mut spans: Vec<Span<'static>> = vec!["  ".into()];
+    spans.extend(
+        hints
+            .into_iter()
+            .flat_map(|(key, label)| [key.into(), label.dim()]),
+    );
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn view(messages: &[&str]) -> (QueuedMessagesView, UnboundedReceiver<AppEvent>) {
+        let (tx, rx) = unbounded_channel::<AppEvent>();
+        let view = QueuedMessagesView::new(
+            messages.iter().map(ToString::to_string).collect(),
+            AppEventSender::new(tx),
+        );
+        (view, rx)
+    }
+
+    fn press(view: &mut QueuedMessagesView, code: KeyCode, modifiers: KeyModifiers) {
+        view.handle_key_event(KeyEvent::new(code, modifiers));
+    }
+
+    fn edits(rx: &mut UnboundedReceiver<AppEvent>) -> Vec<QueuedMessageEdit> {
+        let mut edits = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::EditQueuedMessages(edit) = event {
+                edits.push(edit);
+            }
+        }
+        edits
+    }
+
+    #[test]
+    fn reorders_and_deletes_queued_messages() {
+        let (mut view, mut rx) = view(&["first", "second", "third"]);
+        assert_eq!(view.selected(), Some(2));
+
+        press(&mut view, KeyCode::Up, KeyModifiers::SHIFT);
+        press(&mut view, KeyCode::Char('K'), KeyModifiers::SHIFT);
+        // Already at the top: nothing to move.
+        press(&mut view, KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(view.messages, vec!["third", "first", "second"]);
+        assert_eq!(view.selected(), Some(0));
+
+        press(&mut view, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut view, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(view.messages, vec!["third", "second"]);
+        assert_eq!(view.selected(), Some(1));
+
+        press(&mut view, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(view.is_complete());
+        assert_eq!(
+            edits(&mut rx),
+            vec![
+                QueuedMessageEdit::Move { from: 2, to: 1 },
+                QueuedMessageEdit::Move { from: 1, to: 0 },
+                QueuedMessageEdit::Remove(1),
+                QueuedMessageEdit::Closed,
+            ]
+        );
+    }
+
+    #[test]
+    fn edits_selected_message_in_external_editor() {
+        let (mut view, mut rx) = view(&["first", "second"]);
+        press(&mut view, KeyCode::Char('e'), KeyModifiers::NONE);
+        match rx.try_recv() {
+            Ok(AppEvent::OpenExternalEditor { text, suffix }) => {
+                assert_eq!(text, "second");
+                assert_eq!(suffix, ".md");
+            }
+            other => panic!("expected OpenExternalEditor, got {other:?}"),
+        }
+
+        view.handle_external_edit("second, with details\n".to_string());
+        assert_eq!(view.messages, vec!["first", "second, with details"]);
+
+        // Saving an empty buffer deletes the message; deleting the last one closes the view.
+        press(&mut view, KeyCode::Char('e'), KeyModifiers::NONE);
+        view.handle_external_edit("\n".to_string());
+        press(&mut view, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(view.is_complete());
+
+        let edits = edits(&mut rx);
+        assert_eq!(
+            edits,
+            vec![
+                QueuedMessageEdit::Replace {
+                    index: 1,
+                    text: "second, with details".to_string(),
+                },
+                QueuedMessageEdit::Remove(1),
+                QueuedMessageEdit::Remove(0),
+                QueuedMessageEdit::Closed,
+            ]
+        );
+    }
+
+    #[test]
+    fn dropping_the_view_releases_the_queue() {
+        let (mut view, mut rx) = view(&["first"]);
+        press(&mut view, KeyCode::Down, KeyModifiers::NONE);
+        drop(view);
+        assert_eq!(edits(&mut rx), vec![QueuedMessageEdit::Closed]);
+    }
+}
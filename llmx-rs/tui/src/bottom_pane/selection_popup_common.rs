@@ -2,8 +2,6 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 // Note: Table-based layout previously used Constraint; the manual renderer
 // below no longer requires it.
-use ratatui::style::Color;
-use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -11,6 +9,7 @@ use ratatui::widgets::Widget;
 use unicode_width::UnicodeWidthChar;
 
 use crate::key_hint::KeyBinding;
+use crate::theme;
 
 use super::scroll_state::ScrollState;
 
@@ -180,10 +179,11 @@ pub(crate) fn render_rows(
             desc_col,
         );
         if Some(i) == state.selected_idx {
-            // Match previous behavior: cyan + bold for the selected row.
-            // Reset the style first to avoid inheriting dim from keyboard shortcuts.
+            // Accent + bold for the selected row. Reset the style first to
+            // avoid inheriting dim from keyboard shortcuts.
+            let accent = theme::current().accent.bold();
             full_line.spans.iter_mut().for_each(|span| {
-                span.style = Style::default().fg(Color::Cyan).bold();
+                span.style = accent;
             });
         }
 
@@ -1,9 +1,9 @@
+use crate::theme;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
@@ -33,6 +33,17 @@ pub(crate) struct TextArea {
     preferred_col: Option<usize>,
     elements: Vec<TextElement>,
     kill_buffer: String,
+    /// Current vi state; `None` when vi editing is disabled.
+    vi_mode: Option<ViMode>,
+    /// First key of a pending two-key vi command such as `dd`.
+    vi_pending: Option<char>,
+}
+
+/// State of the modal vi editing mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ViMode {
+    Insert,
+    Normal,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +67,25 @@ impl TextArea {
             preferred_col: None,
             elements: Vec::new(),
             kill_buffer: String::new(),
+            vi_mode: None,
+            vi_pending: None,
+        }
+    }
+
+    /// Enables or disables modal vi editing. Enabling starts in insert mode.
+    pub fn set_vi_enabled(&mut self, enabled: bool) {
+        self.vi_mode = enabled.then_some(ViMode::Insert);
+        self.vi_pending = None;
+    }
+
+    pub fn vi_mode(&self) -> Option<ViMode> {
+        self.vi_mode
+    }
+
+    /// Returns to insert mode (if vi editing is enabled), e.g. after a submit.
+    pub fn reset_vi_mode(&mut self) {
+        if self.vi_mode.is_some() {
+            self.set_vi_enabled(true);
         }
     }
 
@@ -212,6 +242,21 @@ impl TextArea {
     }
 
     pub fn input(&mut self, event: KeyEvent) {
+        match self.vi_mode {
+            Some(ViMode::Normal) => self.vi_normal_input(event),
+            Some(ViMode::Insert)
+                if event.code == KeyCode::Esc && event.modifiers == KeyModifiers::NONE =>
+            {
+                self.vi_mode = Some(ViMode::Normal);
+                if self.cursor_pos > self.beginning_of_current_line() {
+                    self.move_cursor_left();
+                }
+            }
+            _ => self.edit_input(event),
+        }
+    }
+
+    fn edit_input(&mut self, event: KeyEvent) {
         match event {
             // Some terminals (or configurations) send Control key chords as
             // C0 control characters without reporting the CONTROL modifier.
@@ -422,6 +467,93 @@ impl TextArea {
         }
     }
 
+    /// Handles a key in vi normal mode. Plain characters are commands and
+    /// never inserted; other keys (arrows, control chords) keep their usual
+    /// meaning.
+    fn vi_normal_input(&mut self, event: KeyEvent) {
+        let pending = self.vi_pending.take();
+        let KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            ..
+        } = event
+        else {
+            match event.code {
+                KeyCode::Left | KeyCode::Backspace if event.modifiers == KeyModifiers::NONE => {
+                    self.vi_move_left()
+                }
+                KeyCode::Right if event.modifiers == KeyModifiers::NONE => self.vi_move_right(),
+                _ => self.edit_input(event),
+            }
+            return;
+        };
+        match (pending, c) {
+            (Some('d'), 'd') => self.delete_current_line(),
+            // Any other key cancels a pending operator.
+            (Some(_), _) => {}
+            (None, 'd') => self.vi_pending = Some('d'),
+            (_, 'i') => self.vi_mode = Some(ViMode::Insert),
+            (_, 'a') => {
+                self.vi_move_right();
+                self.vi_mode = Some(ViMode::Insert);
+            }
+            (_, 'A') => {
+                self.move_cursor_to_end_of_line(false);
+                self.vi_mode = Some(ViMode::Insert);
+            }
+            (_, 'I') => {
+                self.move_cursor_to_beginning_of_line(false);
+                self.vi_mode = Some(ViMode::Insert);
+            }
+            (_, 'o') => {
+                self.move_cursor_to_end_of_line(false);
+                self.insert_str("\n");
+                self.vi_mode = Some(ViMode::Insert);
+            }
+            (_, 'O') => {
+                self.move_cursor_to_beginning_of_line(false);
+                self.insert_str("\n");
+                self.move_cursor_left();
+                self.vi_mode = Some(ViMode::Insert);
+            }
+            (_, 'h') => self.vi_move_left(),
+            (_, 'l') => self.vi_move_right(),
+            (_, 'j') => self.move_cursor_down(),
+            (_, 'k') => self.move_cursor_up(),
+            (_, 'w') => self.set_cursor(self.beginning_of_next_word()),
+            (_, 'b') => self.set_cursor(self.beginning_of_previous_word()),
+            (_, 'e') => self.set_cursor(self.end_of_next_word()),
+            (_, '0') => self.move_cursor_to_beginning_of_line(false),
+            (_, '$') => self.move_cursor_to_end_of_line(false),
+            (_, 'x') => {
+                if self.cursor_pos < self.end_of_current_line() {
+                    self.delete_forward(1);
+                }
+            }
+            (_, 'D') => {
+                let eol = self.end_of_current_line();
+                self.kill_range(self.cursor_pos..eol);
+            }
+            (_, 'p') => {
+                self.vi_move_right();
+                self.yank();
+            }
+            _ => {}
+        }
+    }
+
+    fn vi_move_left(&mut self) {
+        if self.cursor_pos > self.beginning_of_current_line() {
+            self.move_cursor_left();
+        }
+    }
+
+    fn vi_move_right(&mut self) {
+        if self.cursor_pos < self.end_of_current_line() {
+            self.move_cursor_right();
+        }
+    }
+
     // ####### Input Functions #######
     pub fn delete_backward(&mut self, n: usize) {
         if n == 0 || self.cursor_pos == 0 {
@@ -485,6 +617,19 @@ impl TextArea {
         }
     }
 
+    /// Removes the line under the cursor, including its line break.
+    pub fn delete_current_line(&mut self) {
+        let bol = self.beginning_of_current_line();
+        let eol = self.end_of_current_line();
+        let range = if eol < self.text.len() {
+            bol..eol + 1
+        } else {
+            bol.saturating_sub(1)..eol
+        };
+        self.kill_range(range);
+        self.set_cursor(self.beginning_of_current_line());
+    }
+
     pub fn kill_to_beginning_of_line(&mut self) {
         let bol = self.beginning_of_current_line();
         let range = if self.cursor_pos == bol {
@@ -695,7 +840,8 @@ impl TextArea {
         self.set_cursor(end);
     }
 
-    fn add_element(&mut self, range: Range<usize>) {
+    /// Treat the text already in `range` as an atomic element.
+    pub fn add_element(&mut self, range: Range<usize>) {
         let elem = TextElement { range };
         self.elements.push(elem);
         self.elements.sort_by_key(|e| e.range.start);
@@ -866,6 +1012,24 @@ impl TextArea {
         self.adjust_pos_out_of_elements(start, true)
     }
 
+    pub(crate) fn beginning_of_next_word(&self) -> usize {
+        let mut chars = self.text[self.cursor_pos..].char_indices().peekable();
+        if let Some(&(_, first_ch)) = chars.peek()
+            && !first_ch.is_whitespace()
+        {
+            let is_separator = is_word_separator(first_ch);
+            while chars
+                .next_if(|&(_, ch)| !ch.is_whitespace() && is_word_separator(ch) == is_separator)
+                .is_some()
+            {}
+        }
+        while chars.next_if(|&(_, ch)| ch.is_whitespace()).is_some() {}
+        let start = chars
+            .peek()
+            .map_or(self.text.len(), |&(idx, _)| self.cursor_pos + idx);
+        self.adjust_pos_out_of_elements(start, false)
+    }
+
     pub(crate) fn end_of_next_word(&self) -> usize {
         let Some(first_non_ws) = self.text[self.cursor_pos..].find(|c: char| !c.is_whitespace())
         else {
@@ -985,6 +1149,7 @@ impl TextArea {
         lines: &[Range<usize>],
         range: std::ops::Range<usize>,
     ) {
+        let accent = theme::current().accent;
         for (row, idx) in range.enumerate() {
             let r = &lines[idx];
             let y = area.y + row as u16;
@@ -1002,8 +1167,7 @@ impl TextArea {
                 }
                 let styled = &self.text[overlap_start..overlap_end];
                 let x_off = self.text[line_range.start..overlap_start].width() as u16;
-                let style = Style::default().fg(Color::Cyan);
-                buf.set_string(area.x + x_off, y, styled, style);
+                buf.set_string(area.x + x_off, y, styled, accent);
             }
         }
     }
@@ -1373,6 +1537,62 @@ mod tests {
         assert_eq!(t.cursor(), 1);
     }
 
+    fn vi_keys(t: &mut TextArea, keys: &str) {
+        for c in keys.chars() {
+            let modifiers = if c.is_ascii_uppercase() {
+                KeyModifiers::SHIFT
+            } else {
+                KeyModifiers::NONE
+            };
+            t.input(KeyEvent::new(KeyCode::Char(c), modifiers));
+        }
+    }
+
+    #[test]
+    fn vi_esc_enters_normal_mode_and_chars_are_commands() {
+        let mut t = TextArea::new();
+        t.set_vi_enabled(true);
+        vi_keys(&mut t, "hello world");
+        assert_eq!(t.vi_mode(), Some(ViMode::Insert));
+
+        t.input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(t.vi_mode(), Some(ViMode::Normal));
+        assert_eq!(t.cursor(), 10);
+
+        vi_keys(&mut t, "0wx");
+        assert_eq!(t.text(), "hello orld");
+        assert_eq!(t.cursor(), 6);
+
+        vi_keys(&mut t, "bi>");
+        assert_eq!(t.text(), ">hello orld");
+        assert_eq!(t.vi_mode(), Some(ViMode::Insert));
+
+        t.input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        vi_keys(&mut t, "A!");
+        assert_eq!(t.text(), ">hello orld!");
+    }
+
+    #[test]
+    fn vi_open_line_and_delete_line() {
+        let mut t = TextArea::new();
+        t.set_vi_enabled(true);
+        vi_keys(&mut t, "first");
+        t.input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        vi_keys(&mut t, "osecond");
+        assert_eq!(t.text(), "first\nsecond");
+
+        t.input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        vi_keys(&mut t, "kdd");
+        assert_eq!(t.text(), "second");
+        assert_eq!(t.cursor(), 0);
+
+        vi_keys(&mut t, "dxd");
+        assert_eq!(t.text(), "second");
+
+        t.reset_vi_mode();
+        assert_eq!(t.vi_mode(), Some(ViMode::Insert));
+    }
+
     #[test]
     fn control_b_f_fallback_control_chars_move_cursor() {
         let mut t = ta_with("abcd");
@@ -2,15 +2,20 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use llmx_core::ExportFormat;
 use llmx_core::config::Config;
+use llmx_core::config::types::NotificationEvent;
 use llmx_core::config::types::Notifications;
 use llmx_core::git_info::current_branch_name;
 use llmx_core::git_info::local_git_branches;
+use llmx_core::load_session_export;
 use llmx_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use llmx_core::protocol::AgentMessageDeltaEvent;
 use llmx_core::protocol::AgentMessageEvent;
@@ -19,7 +24,14 @@ use llmx_core::protocol::AgentReasoningEvent;
 use llmx_core::protocol::AgentReasoningRawContentDeltaEvent;
 use llmx_core::protocol::AgentReasoningRawContentEvent;
 use llmx_core::protocol::ApplyPatchApprovalRequestEvent;
+use llmx_core::protocol::ApprovalRuleAction;
+use llmx_core::protocol::ApprovalRuleEntry;
+use llmx_core::protocol::ApprovalRuleKind;
 use llmx_core::protocol::BackgroundEventEvent;
+use llmx_core::protocol::Checkpoint;
+use llmx_core::protocol::CheckpointDiffResponseEvent;
+use llmx_core::protocol::CheckpointRestoredEvent;
+use llmx_core::protocol::ContextEstimate;
 use llmx_core::protocol::DeprecationNoticeEvent;
 use llmx_core::protocol::ErrorEvent;
 use llmx_core::protocol::Event;
@@ -28,14 +40,19 @@ use llmx_core::protocol::ExecApprovalRequestEvent;
 use llmx_core::protocol::ExecCommandBeginEvent;
 use llmx_core::protocol::ExecCommandEndEvent;
 use llmx_core::protocol::ExitedReviewModeEvent;
+use llmx_core::protocol::ListApprovalRulesResponseEvent;
+use llmx_core::protocol::ListCheckpointsResponseEvent;
 use llmx_core::protocol::ListCustomPromptsResponseEvent;
 use llmx_core::protocol::McpListToolsResponseEvent;
 use llmx_core::protocol::McpToolCallBeginEvent;
 use llmx_core::protocol::McpToolCallEndEvent;
 use llmx_core::protocol::Op;
 use llmx_core::protocol::PatchApplyBeginEvent;
+use llmx_core::protocol::PlanDecision;
+use llmx_core::protocol::PlanProposedEvent;
 use llmx_core::protocol::RateLimitSnapshot;
 use llmx_core::protocol::ReviewRequest;
+use llmx_core::protocol::ReviewTarget;
 use llmx_core::protocol::StreamErrorEvent;
 use llmx_core::protocol::TaskCompleteEvent;
 use llmx_core::protocol::TokenUsage;
@@ -49,6 +66,7 @@ use llmx_core::protocol::ViewImageToolCallEvent;
 use llmx_core::protocol::WarningEvent;
 use llmx_core::protocol::WebSearchBeginEvent;
 use llmx_core::protocol::WebSearchEndEvent;
+use llmx_core::review_prompts::review_request;
 use llmx_protocol::ConversationId;
 use llmx_protocol::parse_command::ParsedCommand;
 use llmx_protocol::user_input::UserInput;
@@ -64,12 +82,15 @@ use tokio::sync::mpsc::UnboundedSender;
 use tracing::debug;
 
 use crate::app_event::AppEvent;
+use crate::app_event::QueuedMessageEdit;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::PromptSubmission;
+use crate::bottom_pane::QueuedMessagesView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -85,7 +106,15 @@ use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
+use crate::inline_image::ImageSource;
+use crate::keymap::Keymap;
 use crate::markdown::append_markdown;
+use crate::mentions;
+use crate::mentions::Mention;
+use crate::mentions::MentionCache;
+use crate::mentions::MentionContext;
+use crate::mentions::MentionState;
+use crate::mentions::ResolvedMention;
 #[cfg(target_os = "windows")]
 use crate::onboarding::WSL_INSTRUCTIONS;
 use crate::render::Insets;
@@ -94,9 +123,13 @@ use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::session_tabs::SessionSummary;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status_indicator_widget::fmt_elapsed_compact;
 use crate::text_formatting::truncate_text;
+use crate::theme;
+use crate::theme::ThemePreview;
 use crate::tui::FrameRequester;
 mod interrupts;
 use self::interrupts::InterruptManager;
@@ -250,6 +283,7 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    context_estimate: Option<ContextEstimate>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     rate_limit_warnings: RateLimitWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
@@ -276,10 +310,24 @@ pub(crate) struct ChatWidget {
     suppress_session_configured_redraw: bool,
     // User messages queued while a turn is in progress
     queued_user_messages: VecDeque<UserMessage>,
+    // True while the queued messages view is open; the queue is held back until it closes.
+    queued_messages_view_open: bool,
+    // Resolution state of the `@` mentions in the composer.
+    mentions: MentionCache,
+    // Message submitted while some of its mentions were still resolving.
+    held_message: Option<UserMessage>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
+    // When the running turn started, for the notification duration threshold.
+    turn_started_at: Option<Instant>,
+    // True once a rate limit window is exhausted, so it is notified only once.
+    rate_limit_reached: bool,
     // Simple review mode flag; used to adjust layout and banners.
     is_review_mode: bool,
+    // Whether turns run in plan mode (read-only, ending with a proposed plan).
+    plan_mode: bool,
+    // Checkpoints from the last `/checkpoints` listing, oldest first.
+    checkpoints: Vec<Checkpoint>,
     // Whether to add a final message separator after the last message
     needs_final_message_separator: bool,
 
@@ -293,6 +341,8 @@ pub(crate) struct ChatWidget {
 struct UserMessage {
     text: String,
     image_paths: Vec<PathBuf>,
+    /// Custom prompt the text was expanded from, if any.
+    prompt: Option<PromptSubmission>,
 }
 
 impl From<String> for UserMessage {
@@ -300,6 +350,7 @@ impl From<String> for UserMessage {
         Self {
             text,
             image_paths: Vec::new(),
+            prompt: None,
         }
     }
 }
@@ -309,6 +360,7 @@ impl From<&str> for UserMessage {
         Self {
             text: text.to_string(),
             image_paths: Vec::new(),
+            prompt: None,
         }
     }
 }
@@ -317,7 +369,11 @@ fn create_initial_user_message(text: String, image_paths: Vec<PathBuf>) -> Optio
     if text.is_empty() && image_paths.is_empty() {
         None
     } else {
-        Some(UserMessage { text, image_paths })
+        Some(UserMessage {
+            text,
+            image_paths,
+            prompt: None,
+        })
     }
 }
 
@@ -457,6 +513,7 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.turn_started_at = Some(Instant::now());
         self.request_redraw();
     }
 
@@ -471,9 +528,11 @@ impl ChatWidget {
         // If there is a queued user message, send exactly one now to begin the next turn.
         self.maybe_send_next_queued_input();
         // Emit a notification when the turn completes (suppressed if focused).
-        self.notify(Notification::AgentTurnComplete {
-            response: last_agent_message.unwrap_or_default(),
-        });
+        if self.ran_long_enough(self.turn_started_at.take().map(|start| start.elapsed())) {
+            self.notify(Notification::AgentTurnComplete {
+                response: last_agent_message.unwrap_or_default(),
+            });
+        }
 
         self.maybe_show_pending_rate_limit_prompt();
     }
@@ -532,6 +591,15 @@ impl ChatWidget {
                 self.rate_limit_switch_prompt = RateLimitSwitchPromptState::Pending;
             }
 
+            let limit_reached = [snapshot.primary.as_ref(), snapshot.secondary.as_ref()]
+                .into_iter()
+                .flatten()
+                .any(|window| window.used_percent >= 100.0);
+            if limit_reached && !self.rate_limit_reached {
+                self.notify(Notification::RateLimitReached);
+            }
+            self.rate_limit_reached = limit_reached;
+
             let display = crate::status::rate_limit_snapshot_display(&snapshot, Local::now());
             self.rate_limit_snapshot = Some(display);
 
@@ -558,6 +626,10 @@ impl ChatWidget {
 
     fn on_error(&mut self, message: String) {
         self.finalize_turn();
+        self.turn_started_at = None;
+        self.notify(Notification::TurnFailed {
+            message: message.clone(),
+        });
         self.add_to_history(history_cell::new_error_event(message));
         self.request_redraw();
 
@@ -583,28 +655,44 @@ impl ChatWidget {
             ));
         }
 
-        // If any messages were queued during the task, restore them into the composer.
-        if !self.queued_user_messages.is_empty() {
-            let queued_text = self
-                .queued_user_messages
-                .iter()
-                .map(|m| m.text.clone())
-                .collect::<Vec<_>>()
-                .join("\n");
-            let existing_text = self.bottom_pane.composer_text();
-            let combined = if existing_text.is_empty() {
-                queued_text
-            } else if queued_text.is_empty() {
-                existing_text
-            } else {
-                format!("{queued_text}\n{existing_text}")
-            };
-            self.bottom_pane.set_composer_text(combined);
-            // Clear the queue and update the status indicator list.
-            self.queued_user_messages.clear();
-            self.refresh_queued_user_messages();
+        self.restore_queued_messages_to_composer();
+        self.request_redraw();
+    }
+
+    /// Moves queued messages back into the composer, ahead of any draft.
+    fn restore_queued_messages_to_composer(&mut self) {
+        if self.queued_user_messages.is_empty() {
+            return;
         }
+        let queued_text = self
+            .queued_user_messages
+            .iter()
+            .map(|m| m.text.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let existing_text = self.bottom_pane.composer_text();
+        let combined = if existing_text.is_empty() {
+            queued_text
+        } else if queued_text.is_empty() {
+            existing_text
+        } else {
+            format!("{queued_text}\n{existing_text}")
+        };
+        self.bottom_pane.set_composer_text(combined);
+        // Clear the queue and update the status indicator list.
+        self.queued_user_messages.clear();
+        self.refresh_queued_user_messages();
+    }
 
+    /// Gives up on a message waiting for its mentions: it returns to the
+    /// composer together with anything queued behind it.
+    fn cancel_held_message(&mut self) {
+        let Some(message) = self.held_message.take() else {
+            return;
+        };
+        self.queued_user_messages.push_front(message);
+        self.restore_queued_messages_to_composer();
+        self.refresh_mentions();
         self.request_redraw();
     }
 
@@ -612,6 +700,111 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn on_plan_proposed(&mut self, ev: PlanProposedEvent) {
+        let text = ev.text;
+        if text.trim().is_empty() {
+            self.add_info_message(
+                "No plan was proposed.".to_string(),
+                Some("Describe what you want planned; LLMX stays in plan mode.".to_string()),
+            );
+            return;
+        }
+
+        let items = vec![
+            SelectionItem {
+                name: "Approve and execute".to_string(),
+                description: Some("Leave plan mode and carry out the plan".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::ResolvePlan(PlanDecision::Approved));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Edit plan".to_string(),
+                description: Some("Adjust the steps, then approve".to_string()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenPlanEditor(text.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Reject".to_string(),
+                description: Some("Stay in plan mode and ask for changes".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::ResolvePlan(PlanDecision::Rejected));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Approve this plan?".to_string()),
+            subtitle: Some(
+                "Approving restores your approval and sandbox settings before execution."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    /// Turn plan mode on or off for subsequent turns.
+    pub(crate) fn set_plan_mode(&mut self, enabled: bool) {
+        self.plan_mode = enabled;
+        self.bottom_pane.set_plan_mode(enabled);
+        self.submit_op(Op::SetPlanMode { enabled });
+    }
+
+    fn toggle_plan_mode(&mut self) {
+        let enabled = !self.plan_mode;
+        self.set_plan_mode(enabled);
+        if enabled {
+            self.add_info_message(
+                "Plan mode on".to_string(),
+                Some(
+                    "LLMX will explore read-only and propose a plan for your approval.".to_string(),
+                ),
+            );
+        } else {
+            self.add_info_message("Plan mode off".to_string(), None);
+        }
+    }
+
+    pub(crate) fn show_plan_editor(&mut self, plan: String) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Edit plan".to_string(),
+            "Edit the plan and press Enter to approve it".to_string(),
+            None,
+            Box::new(move |plan: String| {
+                tx.send(AppEvent::ResolvePlan(PlanDecision::ApprovedWithEdits {
+                    plan,
+                }));
+            }),
+        )
+        .with_initial_text(&plan);
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn resolve_plan(&mut self, decision: PlanDecision) {
+        if decision == PlanDecision::Rejected {
+            self.add_info_message(
+                "Plan rejected".to_string(),
+                Some("Still in plan mode. Tell LLMX what to change.".to_string()),
+            );
+        } else {
+            // Core leaves plan mode as soon as it receives the approval.
+            self.plan_mode = false;
+            self.bottom_pane.set_plan_mode(false);
+        }
+        self.submit_op(Op::ResolvePlan { decision });
+    }
+
     fn on_exec_approval_request(&mut self, id: String, ev: ExecApprovalRequestEvent) {
         let id2 = id.clone();
         let ev2 = ev.clone();
@@ -751,6 +944,292 @@ impl ChatWidget {
         }
     }
 
+    fn on_list_checkpoints(&mut self, ev: ListCheckpointsResponseEvent) {
+        self.checkpoints = ev.checkpoints;
+        if self.checkpoints.is_empty() {
+            self.add_info_message(
+                "No checkpoints yet.".to_string(),
+                Some(
+                    "A checkpoint is taken at the start of every turn in a git repository."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+
+        let items = self
+            .checkpoints
+            .iter()
+            .rev()
+            .map(|checkpoint| {
+                let checkpoint = checkpoint.clone();
+                let files = checkpoint.changed_files.len();
+                SelectionItem {
+                    name: checkpoint_label(&checkpoint),
+                    description: Some(format!(
+                        "{files} file{} changed since",
+                        if files == 1 { "" } else { "s" }
+                    )),
+                    search_value: checkpoint.prompt.clone(),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::OpenCheckpointActions(checkpoint.clone()));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Checkpoints".to_string()),
+            subtitle: Some("Snapshots taken before each turn, newest first".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search prompts".to_string()),
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_list_approval_rules(&mut self, ev: ListApprovalRulesResponseEvent) {
+        if ev.rules.is_empty() {
+            self.add_info_message(
+                "No approval rules saved for this project.".to_string(),
+                Some("Choose an \"always\" option in an approval prompt to add one.".to_string()),
+            );
+            return;
+        }
+
+        let items = ev
+            .rules
+            .into_iter()
+            .map(|entry| {
+                let ApprovalRuleEntry { rule, path } = entry;
+                let action = match rule.action {
+                    ApprovalRuleAction::Allow => "Allow",
+                    ApprovalRuleAction::Deny => "Deny",
+                };
+                let kind = match rule.kind {
+                    ApprovalRuleKind::Command => "commands",
+                    ApprovalRuleKind::Patch => "edits to",
+                };
+                let source = if path.as_os_str().is_empty() {
+                    "this session only".to_string()
+                } else {
+                    path.display().to_string()
+                };
+                SelectionItem {
+                    name: format!("{action} {kind} `{}`", rule.pattern),
+                    description: Some(source),
+                    search_value: Some(rule.pattern.clone()),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::LlmxOp(Op::RevokeApprovalRule {
+                            rule: rule.clone(),
+                        }));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Approval rules".to_string()),
+            subtitle: Some("Select a rule to revoke it".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search patterns".to_string()),
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    pub(crate) fn show_checkpoint_actions(&mut self, checkpoint: Checkpoint) {
+        let id = checkpoint.id.clone();
+        let mut items = Vec::new();
+
+        let restore_id = id.clone();
+        items.push(SelectionItem {
+            name: "Restore all files".to_string(),
+            description: Some("Reset the working tree to this checkpoint".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::LlmxOp(Op::RestoreCheckpoint {
+                    id: restore_id.clone(),
+                    paths: Vec::new(),
+                }));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        let picker_checkpoint = checkpoint.clone();
+        items.push(SelectionItem {
+            name: "Restore selected files".to_string(),
+            description: Some("Pick the files to bring back".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::OpenCheckpointFilePicker(
+                    picker_checkpoint.clone(),
+                ));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        let diff_id = id.clone();
+        items.push(SelectionItem {
+            name: "Diff against working tree".to_string(),
+            description: Some("Show what changed since this checkpoint".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::LlmxOp(Op::DiffCheckpoints {
+                    from: diff_id.clone(),
+                    to: None,
+                }));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        if self.checkpoints.len() > 1 {
+            items.push(SelectionItem {
+                name: "Diff against another checkpoint".to_string(),
+                description: None,
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenCheckpointDiffPicker(id.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(checkpoint_label(&checkpoint)),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    pub(crate) fn show_checkpoint_file_picker(&mut self, checkpoint: Checkpoint) {
+        let id = checkpoint.id.clone();
+        let mut items: Vec<SelectionItem> = checkpoint
+            .changed_files
+            .iter()
+            .map(|path| {
+                let id = id.clone();
+                let path = path.clone();
+                SelectionItem {
+                    name: path.display().to_string(),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::LlmxOp(Op::RestoreCheckpoint {
+                            id: id.clone(),
+                            paths: vec![path.clone()],
+                        }));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "Enter paths...".to_string(),
+            description: Some("Restore several files at once".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::OpenCheckpointPathsPrompt(id.clone()));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Restore which file?".to_string()),
+            subtitle: Some("Files changed since this checkpoint".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search files".to_string()),
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    pub(crate) fn show_checkpoint_paths_prompt(&mut self, id: String) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Restore files".to_string(),
+            "Space-separated paths relative to the working directory".to_string(),
+            None,
+            Box::new(move |paths: String| {
+                let paths: Vec<PathBuf> = paths.split_whitespace().map(PathBuf::from).collect();
+                if paths.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::LlmxOp(Op::RestoreCheckpoint {
+                    id: id.clone(),
+                    paths,
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_checkpoint_diff_picker(&mut self, from: String) {
+        let items = self
+            .checkpoints
+            .iter()
+            .rev()
+            .filter(|checkpoint| checkpoint.id != from)
+            .map(|checkpoint| {
+                let from = from.clone();
+                let to = checkpoint.id.clone();
+                SelectionItem {
+                    name: checkpoint_label(checkpoint),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::LlmxOp(Op::DiffCheckpoints {
+                            from: from.clone(),
+                            to: Some(to.clone()),
+                        }));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Diff against which checkpoint?".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_checkpoint_diff(&mut self, ev: CheckpointDiffResponseEvent) {
+        self.app_event_tx
+            .send(AppEvent::DiffResult(ev.unified_diff));
+    }
+
+    fn on_checkpoint_restored(&mut self, ev: CheckpointRestoredEvent) {
+        let CheckpointRestoredEvent {
+            success, message, ..
+        } = ev;
+        let message = message.unwrap_or_else(|| {
+            if success {
+                "Checkpoint restored.".to_string()
+            } else {
+                "Checkpoint restore failed.".to_string()
+            }
+        });
+        if success {
+            self.add_info_message(message, None);
+        } else {
+            self.add_error_message(message);
+        }
+    }
+
     fn on_stream_error(&mut self, message: String) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -836,6 +1315,14 @@ impl ChatWidget {
             Some(rc) => (rc.command, rc.parsed_cmd, rc.is_user_shell_command),
             None => (vec![ev.call_id.clone()], Vec::new(), false),
         };
+        if !is_user_shell_command {
+            self.notify(Notification::CommandFinished {
+                command: shlex::try_join(command.iter().map(String::as_str))
+                    .unwrap_or_else(|_| command.join(" ")),
+                exit_code: ev.exit_code,
+                duration: ev.duration,
+            });
+        }
 
         let needs_new = self
             .active_cell
@@ -1025,6 +1512,8 @@ impl ChatWidget {
                 enhanced_keys_supported,
                 placeholder_text: placeholder,
                 disable_paste_burst: config.disable_paste_burst,
+                keymap: Keymap::from_config(&config.tui_keymap),
+                editing_mode: config.tui_editing_mode,
             }),
             active_cell: None,
             config: config.clone(),
@@ -1035,6 +1524,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            context_estimate: None,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -1048,10 +1538,17 @@ impl ChatWidget {
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            queued_messages_view_open: false,
+            mentions: MentionCache::default(),
+            held_message: None,
             show_welcome_banner: true,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            turn_started_at: None,
+            rate_limit_reached: false,
             is_review_mode: false,
+            plan_mode: false,
+            checkpoints: Vec::new(),
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
@@ -1092,6 +1589,8 @@ impl ChatWidget {
                 enhanced_keys_supported,
                 placeholder_text: placeholder,
                 disable_paste_burst: config.disable_paste_burst,
+                keymap: Keymap::from_config(&config.tui_keymap),
+                editing_mode: config.tui_editing_mode,
             }),
             active_cell: None,
             config: config.clone(),
@@ -1102,6 +1601,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            context_estimate: None,
             rate_limit_snapshot: None,
             rate_limit_warnings: RateLimitWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -1115,10 +1615,17 @@ impl ChatWidget {
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            queued_messages_view_open: false,
+            mentions: MentionCache::default(),
+            held_message: None,
             show_welcome_banner: true,
             suppress_session_configured_redraw: true,
             pending_notification: None,
+            turn_started_at: None,
+            rate_limit_reached: false,
             is_review_mode: false,
+            plan_mode: false,
+            checkpoints: Vec::new(),
             needs_final_message_separator: false,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
@@ -1161,6 +1668,14 @@ impl ChatWidget {
                 }
                 return;
             }
+            KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                ..
+            } if self.held_message.is_some() => {
+                self.cancel_held_message();
+                return;
+            }
             other if other.kind == KeyEventKind::Press => {
                 self.bottom_pane.clear_ctrl_c_quit_hint();
             }
@@ -1173,7 +1688,7 @@ impl ChatWidget {
                 modifiers: KeyModifiers::ALT,
                 kind: KeyEventKind::Press,
                 ..
-            } if !self.queued_user_messages.is_empty() => {
+            } if !self.queued_user_messages.is_empty() && !self.queued_messages_view_open => {
                 // Prefer the most recently queued item.
                 if let Some(user_message) = self.queued_user_messages.pop_back() {
                     self.bottom_pane.set_composer_text(user_message.text);
@@ -1181,6 +1696,14 @@ impl ChatWidget {
                     self.request_redraw();
                 }
             }
+            KeyEvent {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                ..
+            } if !self.queued_user_messages.is_empty() && !self.queued_messages_view_open => {
+                self.open_queued_messages_view();
+            }
             _ => {
                 match self.bottom_pane.handle_key_event(key_event) {
                     InputResult::Submitted(text) => {
@@ -1188,6 +1711,7 @@ impl ChatWidget {
                         let user_message = UserMessage {
                             text,
                             image_paths: self.bottom_pane.take_recent_submission_images(),
+                            prompt: self.bottom_pane.take_recent_submission_prompt(),
                         };
                         self.queue_user_message(user_message);
                     }
@@ -1198,6 +1722,7 @@ impl ChatWidget {
                 }
             }
         }
+        self.refresh_mentions();
     }
 
     pub(crate) fn attach_image(
@@ -1236,6 +1761,9 @@ impl ChatWidget {
             SlashCommand::New => {
                 self.app_event_tx.send(AppEvent::NewSession);
             }
+            SlashCommand::Sessions => {
+                self.app_event_tx.send(AppEvent::OpenSessionsPopup);
+            }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
                 if init_target.exists() {
@@ -1255,6 +1783,9 @@ impl ChatWidget {
             SlashCommand::Review => {
                 self.open_review_popup();
             }
+            SlashCommand::Plan => {
+                self.toggle_plan_mode();
+            }
             SlashCommand::Model => {
                 self.open_model_popup();
             }
@@ -1268,6 +1799,7 @@ impl ChatWidget {
                 if let Err(e) = llmx_core::auth::logout(
                     &self.config.llmx_home,
                     self.config.cli_auth_credentials_store_mode,
+                    self.config.credential_helper.as_deref(),
                 ) {
                     tracing::error!("failed to logout: {e}");
                 }
@@ -1276,6 +1808,14 @@ impl ChatWidget {
             SlashCommand::Undo => {
                 self.app_event_tx.send(AppEvent::LlmxOp(Op::Undo));
             }
+            SlashCommand::Checkpoints => {
+                self.app_event_tx
+                    .send(AppEvent::LlmxOp(Op::ListCheckpoints));
+            }
+            SlashCommand::Permissions => {
+                self.app_event_tx
+                    .send(AppEvent::LlmxOp(Op::ListApprovalRules));
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -1293,12 +1833,18 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Export => {
+                self.open_export_popup();
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Theme => {
+                self.open_theme_popup();
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
@@ -1354,12 +1900,14 @@ impl ChatWidget {
 
     pub(crate) fn handle_paste(&mut self, text: String) {
         self.bottom_pane.handle_paste(text);
+        self.refresh_mentions();
     }
 
     // Returns true if caller should skip rendering this frame (a future frame is scheduled).
     pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
         if self.bottom_pane.flush_paste_burst_if_due() {
             // A paste just flushed; request an immediate redraw and skip this frame.
+            self.refresh_mentions();
             self.request_redraw();
             true
         } else if self.bottom_pane.is_in_paste_burst() {
@@ -1395,7 +1943,7 @@ impl ChatWidget {
     }
 
     fn queue_user_message(&mut self, user_message: UserMessage) {
-        if self.bottom_pane.is_task_running() {
+        if self.bottom_pane.is_task_running() || self.held_message.is_some() {
             self.queued_user_messages.push_back(user_message);
             self.refresh_queued_user_messages();
         } else {
@@ -1404,7 +1952,11 @@ impl ChatWidget {
     }
 
     fn submit_user_message(&mut self, user_message: UserMessage) {
-        let UserMessage { text, image_paths } = user_message;
+        let UserMessage {
+            text,
+            image_paths,
+            prompt,
+        } = user_message;
         if text.is_empty() && image_paths.is_empty() {
             return;
         }
@@ -1412,7 +1964,9 @@ impl ChatWidget {
         let mut items: Vec<UserInput> = Vec::new();
 
         // Special-case: "!cmd" executes a local shell command instead of sending to the model.
-        if let Some(stripped) = text.strip_prefix('!') {
+        if prompt.is_none()
+            && let Some(stripped) = text.strip_prefix('!')
+        {
             let cmd = stripped.trim();
             if cmd.is_empty() {
                 self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
@@ -1429,19 +1983,66 @@ impl ChatWidget {
             return;
         }
 
-        if !text.is_empty() {
+        // Hold the message until its `@` mentions have resolved;
+        // `on_mention_resolved` submits it again. A custom prompt's `@path`
+        // includes are expanded by the session, and its argument values are
+        // plain text.
+        let mentions = if prompt.is_some() {
+            Vec::new()
+        } else {
+            mentions::parse_mentions(&text, &self.config.cwd, false)
+        };
+        self.start_mention_resolution(&mentions);
+        if self.mentions.any_resolving(&mentions) {
+            self.held_message = Some(UserMessage {
+                text,
+                image_paths,
+                prompt,
+            });
+            self.refresh_mention_previews();
+            return;
+        }
+
+        // The session expands a custom prompt's body itself.
+        if !text.is_empty() && prompt.is_none() {
             items.push(UserInput::Text { text: text.clone() });
         }
 
+        let images: Vec<ImageSource> = image_paths.iter().cloned().map(ImageSource::Path).collect();
         for path in image_paths {
             items.push(UserInput::LocalImage { path });
         }
 
-        self.llmx_op_tx
-            .send(Op::UserInput { items })
-            .unwrap_or_else(|e| {
-                tracing::error!("failed to send message: {e}");
-            });
+        let mut attached = Vec::new();
+        let mut skipped = Vec::new();
+        for mention in &mentions {
+            match self.mentions.get(&mention.token) {
+                Some(MentionState::Ready(resolved)) => {
+                    attached.push(format!("{} ({})", resolved.token, resolved.token_label()));
+                    items.push(resolved.clone().into_input());
+                }
+                Some(MentionState::Failed(err)) => {
+                    skipped.push(format!("{}: {err}", mention.token));
+                }
+                Some(MentionState::Resolving) | None => {}
+            }
+        }
+        self.mentions.clear();
+        self.refresh_mention_previews();
+
+        // Custom prompts are expanded further by the session (includes,
+        // command blocks, per-prompt overrides).
+        let op = match prompt {
+            Some(PromptSubmission { prompt, arguments }) => Op::CustomPromptInput {
+                prompt,
+                arguments,
+                items,
+            },
+            None => Op::UserInput { items },
+        };
+        self.llmx_op_tx.send(op).unwrap_or_else(|e| {
+            tracing::error!("failed to send message: {e}");
+        });
 
         // Persist the text to cross-session message history.
         if !text.is_empty() {
@@ -1454,7 +2055,19 @@ impl ChatWidget {
 
         // Only show the text portion in conversation history.
         if !text.is_empty() {
-            self.add_to_history(history_cell::new_user_prompt(text));
+            self.add_to_history(history_cell::new_user_prompt(text, images));
+        }
+        if !attached.is_empty() {
+            self.add_to_history(history_cell::new_info_event(
+                format!("Attached {}", attached.join(", ")),
+                None,
+            ));
+        }
+        if !skipped.is_empty() {
+            self.add_to_history(history_cell::new_warning_event(format!(
+                "Skipped mentions that could not be resolved: {}",
+                skipped.join("; ")
+            )));
         }
         self.needs_final_message_separator = false;
     }
@@ -1515,6 +2128,9 @@ impl ChatWidget {
                 self.on_task_complete(last_agent_message)
             }
             EventMsg::TokenCount(ev) => {
+                if ev.context_estimate.is_some() {
+                    self.context_estimate = ev.context_estimate;
+                }
                 self.set_token_info(ev.info);
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
@@ -1532,6 +2148,7 @@ impl ChatWidget {
                 }
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
+            EventMsg::PlanProposed(ev) => self.on_plan_proposed(ev),
             EventMsg::ExecApprovalRequest(ev) => {
                 // For replayed events, synthesize an empty id (these should not occur).
                 self.on_exec_approval_request(id.unwrap_or_default(), ev)
@@ -1560,6 +2177,10 @@ impl ChatWidget {
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::ListCheckpointsResponse(ev) => self.on_list_checkpoints(ev),
+            EventMsg::ListApprovalRulesResponse(ev) => self.on_list_approval_rules(ev),
+            EventMsg::CheckpointDiffResponse(ev) => self.on_checkpoint_diff(ev),
+            EventMsg::CheckpointRestored(ev) => self.on_checkpoint_restored(ev),
             EventMsg::StreamError(StreamErrorEvent { message }) => self.on_stream_error(message),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
@@ -1631,7 +2252,13 @@ impl ChatWidget {
     fn on_user_message_event(&mut self, event: UserMessageEvent) {
         let message = event.message.trim();
         if !message.is_empty() {
-            self.add_to_history(history_cell::new_user_prompt(message.to_string()));
+            let images = event
+                .images
+                .unwrap_or_default()
+                .into_iter()
+                .map(ImageSource::DataUrl)
+                .collect();
+            self.add_to_history(history_cell::new_user_prompt(message.to_string(), images));
         }
     }
 
@@ -1644,19 +2271,41 @@ impl ChatWidget {
     }
 
     fn notify(&mut self, notification: Notification) {
-        if !notification.allowed_for(&self.config.tui_notifications) {
+        if !notification.allowed_for(
+            &self.config.tui_notifications,
+            &self.config.notification_events,
+        ) {
+            return;
+        }
+        if let Notification::CommandFinished { duration, .. } = &notification
+            && !self.ran_long_enough(Some(*duration))
+        {
             return;
         }
         self.pending_notification = Some(notification);
         self.request_redraw();
     }
 
+    /// Whether something that took `elapsed` passes the configured
+    /// `notifications.min_duration_secs`; unknown durations always pass.
+    fn ran_long_enough(&self, elapsed: Option<Duration>) -> bool {
+        elapsed.is_none_or(|elapsed| elapsed >= self.config.notification_min_duration)
+    }
+
     pub(crate) fn maybe_post_pending_notification(&mut self, tui: &mut crate::tui::Tui) {
         if let Some(notif) = self.pending_notification.take() {
             tui.notify(notif.display());
         }
     }
 
+    /// Takes the pending notification's text, for sessions whose
+    /// notifications the app posts itself.
+    pub(crate) fn take_pending_notification(&mut self) -> Option<String> {
+        self.pending_notification
+            .take()
+            .map(|notification| notification.display())
+    }
+
     /// Mark the active cell as failed (✗) and flush it into history.
     fn finalize_active_cell_as_failed(&mut self) {
         if let Some(mut cell) = self.active_cell.take() {
@@ -1672,7 +2321,10 @@ impl ChatWidget {
 
     // If idle and there are queued inputs, submit exactly one to start the next turn.
     fn maybe_send_next_queued_input(&mut self) {
-        if self.bottom_pane.is_task_running() {
+        if self.bottom_pane.is_task_running()
+            || self.queued_messages_view_open
+            || self.held_message.is_some()
+        {
             return;
         }
         if let Some(user_message) = self.queued_user_messages.pop_front() {
@@ -1682,6 +2334,121 @@ impl ChatWidget {
         self.refresh_queued_user_messages();
     }
 
+    fn open_queued_messages_view(&mut self) {
+        let messages = self
+            .queued_user_messages
+            .iter()
+            .map(|m| m.text.clone())
+            .collect();
+        self.queued_messages_view_open = true;
+        self.bottom_pane.show_view(Box::new(QueuedMessagesView::new(
+            messages,
+            self.app_event_tx.clone(),
+        )));
+        self.request_redraw();
+    }
+
+    /// Apply a change made in the queued messages view. Edits whose index no
+    /// longer exists (e.g. the queue was restored into the composer after an
+    /// interrupt) are ignored.
+    pub(crate) fn edit_queued_messages(&mut self, edit: QueuedMessageEdit) {
+        let queue = &mut self.queued_user_messages;
+        match edit {
+            QueuedMessageEdit::Replace { index, text } => {
+                if let Some(message) = queue.get_mut(index) {
+                    message.text = text;
+                    // The edited text is what gets sent, not the prompt it came from.
+                    message.prompt = None;
+                }
+            }
+            QueuedMessageEdit::Move { from, to } => {
+                if to < queue.len()
+                    && let Some(message) = queue.remove(from)
+                {
+                    queue.insert(to, message);
+                }
+            }
+            QueuedMessageEdit::Remove(index) => {
+                queue.remove(index);
+            }
+            QueuedMessageEdit::Closed => {
+                self.queued_messages_view_open = false;
+                self.maybe_send_next_queued_input();
+            }
+        }
+        self.refresh_queued_user_messages();
+        self.request_redraw();
+    }
+
+    /// Starts resolving the `@` mentions in the composer and updates their
+    /// previews.
+    fn refresh_mentions(&mut self) {
+        if self.held_message.is_some() {
+            self.refresh_mention_previews();
+            return;
+        }
+        let text = self.bottom_pane.composer_text();
+        let mentions = mentions::parse_mentions(&text, &self.config.cwd, true);
+        self.mentions.retain(&mentions);
+        self.start_mention_resolution(&mentions);
+        self.refresh_mention_previews();
+    }
+
+    fn start_mention_resolution(&mut self, mentions: &[Mention]) {
+        for mention in mentions {
+            if !self.mentions.start(&mention.token) {
+                continue;
+            }
+            let ctx = MentionContext {
+                cwd: self.config.cwd.clone(),
+                llmx_home: self.config.llmx_home.clone(),
+            };
+            let mention = mention.clone();
+            let tx = self.app_event_tx.clone();
+            tokio::spawn(async move {
+                let token = mention.token.clone();
+                let result = mentions::resolve(mention, ctx).await;
+                tx.send(AppEvent::MentionResolved { token, result });
+            });
+        }
+    }
+
+    /// Previews the mentions of the held message, or else of the composer.
+    fn refresh_mention_previews(&mut self) {
+        let text = match &self.held_message {
+            Some(message) => message.text.clone(),
+            None => self.bottom_pane.composer_text(),
+        };
+        let complete_only = self.held_message.is_none();
+        let previews = mentions::parse_mentions(&text, &self.config.cwd, complete_only)
+            .into_iter()
+            .filter_map(|mention| {
+                let state = self.mentions.get(&mention.token)?.clone();
+                Some((mention.token, state))
+            })
+            .collect();
+        self.bottom_pane
+            .set_mention_previews(previews, self.held_message.is_some());
+    }
+
+    pub(crate) fn on_mention_resolved(
+        &mut self,
+        token: String,
+        result: Result<ResolvedMention, String>,
+    ) {
+        self.mentions.finish(&token, result);
+        if let Some(held) = &self.held_message {
+            let mentions = mentions::parse_mentions(&held.text, &self.config.cwd, false);
+            if !self.mentions.any_resolving(&mentions)
+                && let Some(message) = self.held_message.take()
+            {
+                self.submit_user_message(message);
+            }
+        }
+        self.refresh_mentions();
+        self.request_redraw();
+    }
+
     /// Rebuild and update the queued user messages from the current queue.
     fn refresh_queued_user_messages(&mut self) {
         let messages: Vec<String> = self
@@ -1711,6 +2478,7 @@ impl ChatWidget {
             &self.config,
             total_usage,
             context_usage,
+            self.context_estimate.as_ref(),
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
             Local::now(),
@@ -2457,6 +3225,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn handle_external_edit(&mut self, text: String) {
+        self.bottom_pane.handle_external_edit(text);
+        self.refresh_mentions();
+    }
+
     pub(crate) fn add_error_message(&mut self, message: String) {
         self.add_to_history(history_cell::new_error_event(message));
         self.request_redraw();
@@ -2481,6 +3254,11 @@ impl ChatWidget {
             return;
         }
 
+        if self.held_message.is_some() {
+            self.cancel_held_message();
+            return;
+        }
+
         if self.bottom_pane.is_task_running() {
             self.bottom_pane.show_ctrl_c_quit_hint();
             self.submit_op(Op::Interrupt);
@@ -2490,15 +3268,24 @@ impl ChatWidget {
         self.submit_op(Op::Shutdown);
     }
 
+    pub(crate) fn is_task_running(&self) -> bool {
+        self.bottom_pane.is_task_running()
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.bottom_pane.composer_is_empty()
     }
 
+    pub(crate) fn composer_captures_key(&self, key_event: &KeyEvent) -> bool {
+        self.bottom_pane.composer_captures_key(key_event)
+    }
+
     /// True when the UI is in the regular composer state with no running task,
-    /// no modal overlay (e.g. approvals or status indicator), and no composer popups.
+    /// no held message, no modal overlay (e.g. approvals or status indicator),
+    /// and no composer popups.
     /// In this state Esc-Esc backtracking is enabled.
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        self.bottom_pane.is_normal_backtrack_mode()
+        self.held_message.is_none() && self.bottom_pane.is_normal_backtrack_mode()
     }
 
     pub(crate) fn insert_str(&mut self, text: &str) {
@@ -2561,16 +3348,11 @@ impl ChatWidget {
 
         items.push(SelectionItem {
             name: "Review uncommitted changes".to_string(),
-            actions: vec![Box::new(
-                move |tx: &AppEventSender| {
-                    tx.send(AppEvent::LlmxOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt: "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.".to_string(),
-                            user_facing_hint: "current changes".to_string(),
-                        },
-                    }));
-                },
-            )],
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::LlmxOp(Op::Review {
+                    review_request: review_request(ReviewTarget::UncommittedChanges),
+                }));
+            })],
             dismiss_on_select: true,
             ..Default::default()
         });
@@ -2605,6 +3387,203 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn open_export_popup(&mut self) {
+        let formats = [
+            (ExportFormat::Markdown, "Markdown"),
+            (ExportFormat::Html, "HTML"),
+            (ExportFormat::Json, "JSON"),
+        ];
+        let mut items: Vec<SelectionItem> = Vec::new();
+        for redact in [false, true] {
+            for (format, label) in formats {
+                let (name, description) = if redact {
+                    (
+                        format!("{label} (redacted)"),
+                        "mask env values and paths outside the repo".to_string(),
+                    )
+                } else {
+                    (
+                        label.to_string(),
+                        format!("write llmx-session-<id>.{}", format.extension()),
+                    )
+                };
+                items.push(SelectionItem {
+                    name,
+                    description: Some(description),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::ExportSession { format, redact });
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                });
+            }
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Export session".into()),
+            subtitle: Some(format!(
+                "Files are written to {}",
+                self.config.cwd.display()
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Lists the available themes; moving the highlight previews a theme and
+    /// Esc restores the configured one.
+    pub(crate) fn open_theme_popup(&mut self) {
+        let configured = theme::configured_name();
+        let items: Vec<SelectionItem> = theme::available_themes(&theme::config())
+            .into_iter()
+            .map(|(name, description)| {
+                let preview_name = name.clone();
+                let select_name = name.clone();
+                SelectionItem {
+                    is_current: name == configured,
+                    name,
+                    description: Some(description),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::SelectTheme(select_name.clone()));
+                    })],
+                    on_highlight: Some(Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::PreviewTheme(Some(preview_name.clone())));
+                    })),
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Select Theme".bold()));
+        header.push(Line::from(
+            "Sets tui.theme.name; text already in scrollback keeps its colors".dim(),
+        ));
+        header.push(Line::from(""));
+        header.push(ThemePreview);
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(header),
+            on_cancel: Some(Box::new(|tx: &AppEventSender| {
+                tx.send(AppEvent::PreviewTheme(None));
+            })),
+            ..Default::default()
+        });
+    }
+
+    /// Lists the open session tabs with their settings and badges, plus
+    /// entries to start a new one or close the current one.
+    pub(crate) fn open_sessions_popup(&mut self, sessions: Vec<SessionSummary>) {
+        let active = sessions
+            .iter()
+            .find(|session| session.is_active)
+            .map(|session| session.id);
+        let mut items: Vec<SelectionItem> = sessions
+            .into_iter()
+            .map(|session| {
+                let id = session.id;
+                let description = format!(
+                    "{} · {} · {} · {}",
+                    session.model,
+                    display_path_for(&session.cwd, &self.config.cwd),
+                    session.approval_policy,
+                    session.status(),
+                );
+                SelectionItem {
+                    name: session.label(),
+                    description: Some(description),
+                    is_current: session.is_active,
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::SwitchSessionTab(id));
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "New session".to_string(),
+            description: Some("same model, directory and approvals".to_string()),
+            actions: vec![Box::new(|tx: &AppEventSender| {
+                tx.send(AppEvent::OpenSessionTab { cwd: None });
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "New session in directory…".to_string(),
+            description: Some("start a session in another working directory".to_string()),
+            actions: vec![Box::new(|tx: &AppEventSender| {
+                tx.send(AppEvent::OpenSessionCwdPrompt);
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        if let Some(active) = active {
+            items.push(SelectionItem {
+                name: "Close this session".to_string(),
+                description: Some("shut down the current conversation".to_string()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CloseSessionTab(active));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Sessions".into()),
+            subtitle: Some("Background sessions keep running".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn show_session_cwd_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "New session directory".to_string(),
+            "Type a path and press Enter".to_string(),
+            Some(format!("Relative to {}", self.config.cwd.display())),
+            Box::new(move |path: String| {
+                let trimmed = path.trim();
+                if trimmed.is_empty() {
+                    return;
+                }
+                tx.send(AppEvent::OpenSessionTab {
+                    cwd: Some(PathBuf::from(trimmed)),
+                });
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    /// Renders the rollout in the background and reports where it was written.
+    pub(crate) fn export_session(&mut self, format: ExportFormat, redact: bool) {
+        let Some(rollout_path) = self.rollout_path() else {
+            self.add_info_message("Session file is not available yet.".to_string(), None);
+            return;
+        };
+        let cwd = self.config.cwd.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let cell = match write_session_export(&rollout_path, &cwd, format, redact).await {
+                Ok(path) => history_cell::new_info_event(
+                    format!("Exported session to {}", path.display()),
+                    None,
+                ),
+                Err(err) => {
+                    history_cell::new_error_event(format!("Failed to export session: {err}"))
+                }
+            };
+            tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+        });
+    }
+
     pub(crate) async fn show_review_branch_picker(&mut self, cwd: &Path) {
         let branches = local_git_branches(cwd).await;
         let current_branch = current_branch_name(cwd)
@@ -2618,12 +3597,9 @@ impl ChatWidget {
                 name: format!("{current_branch} -> {branch}"),
                 actions: vec![Box::new(move |tx3: &AppEventSender| {
                     tx3.send(AppEvent::LlmxOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt: format!(
-                                "Review the code changes against the base branch '{branch}'. Start by finding the merge diff between the current branch and {branch}'s upstream e.g. (`git merge-base HEAD \"$(git rev-parse --abbrev-ref \"{branch}@{{upstream}}\")\"`), then run `git diff` against that SHA to see what changes we would merge into the {branch} branch. Provide prioritized, actionable findings."
-                            ),
-                            user_facing_hint: format!("changes against '{branch}'"),
-                        },
+                        review_request: review_request(ReviewTarget::BaseBranch {
+                            branch: branch.clone(),
+                        }),
                     }));
                 })],
                 dismiss_on_select: true,
@@ -2649,21 +3625,16 @@ impl ChatWidget {
         for entry in commits {
             let subject = entry.subject.clone();
             let sha = entry.sha.clone();
-            let short = sha.chars().take(7).collect::<String>();
             let search_val = format!("{subject} {sha}");
 
             items.push(SelectionItem {
                 name: subject.clone(),
                 actions: vec![Box::new(move |tx3: &AppEventSender| {
-                    let hint = format!("commit {short}");
-                    let prompt = format!(
-                        "Review the code changes introduced by commit {sha} (\"{subject}\"). Provide prioritized, actionable findings."
-                    );
                     tx3.send(AppEvent::LlmxOp(Op::Review {
-                        review_request: ReviewRequest {
-                            prompt,
-                            user_facing_hint: hint,
-                        },
+                        review_request: review_request(ReviewTarget::Commit {
+                            sha: sha.clone(),
+                            title: Some(subject.clone()),
+                        }),
                     }));
                 })],
                 dismiss_on_select: true,
@@ -2694,10 +3665,9 @@ impl ChatWidget {
                     return;
                 }
                 tx.send(AppEvent::LlmxOp(Op::Review {
-                    review_request: ReviewRequest {
-                        prompt: trimmed.clone(),
-                        user_facing_hint: trimmed,
-                    },
+                    review_request: review_request(ReviewTarget::Custom {
+                        instructions: trimmed,
+                    }),
                 }));
             }),
         );
@@ -2727,6 +3697,7 @@ impl ChatWidget {
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.token_info = None;
+        self.context_estimate = None;
     }
 
     fn as_renderable(&self) -> RenderableItem<'_> {
@@ -2760,9 +3731,25 @@ impl Renderable for ChatWidget {
 }
 
 enum Notification {
-    AgentTurnComplete { response: String },
-    ExecApprovalRequested { command: String },
-    EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
+    AgentTurnComplete {
+        response: String,
+    },
+    ExecApprovalRequested {
+        command: String,
+    },
+    EditApprovalRequested {
+        cwd: PathBuf,
+        changes: Vec<PathBuf>,
+    },
+    TurnFailed {
+        message: String,
+    },
+    RateLimitReached,
+    CommandFinished {
+        command: String,
+        exit_code: i32,
+        duration: Duration,
+    },
 }
 
 impl Notification {
@@ -2786,6 +3773,26 @@ impl Notification {
                     }
                 )
             }
+            Notification::TurnFailed { message } => {
+                format!("Turn failed: {}", truncate_text(message, 80))
+            }
+            Notification::RateLimitReached => "Rate limit reached".to_string(),
+            Notification::CommandFinished {
+                command,
+                exit_code,
+                duration,
+            } => {
+                let status = if *exit_code == 0 {
+                    "Command finished".to_string()
+                } else {
+                    format!("Command failed (exit {exit_code})")
+                };
+                format!(
+                    "{status} after {}: {}",
+                    fmt_elapsed_compact(duration.as_secs()),
+                    truncate_text(command, 30)
+                )
+            }
         }
     }
 
@@ -2794,12 +3801,22 @@ impl Notification {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. } => "approval-requested",
+            Notification::TurnFailed { .. } => "turn-failed",
+            Notification::RateLimitReached => "rate-limit-reached",
+            Notification::CommandFinished { .. } => "command-finished",
         }
     }
 
-    fn allowed_for(&self, settings: &Notifications) -> bool {
+    /// `tui.notifications = true` follows the `[notifications]` event list;
+    /// an explicit list of names overrides it.
+    fn allowed_for(&self, settings: &Notifications, events: &[NotificationEvent]) -> bool {
         match settings {
-            Notifications::Enabled(enabled) => *enabled,
+            Notifications::Enabled(enabled) => {
+                *enabled
+                    && events
+                        .iter()
+                        .any(|event| event.as_str() == self.type_name())
+            }
             Notifications::Custom(allowed) => allowed.iter().any(|a| a == self.type_name()),
         }
     }
@@ -2862,6 +3879,77 @@ fn extract_first_bold(s: &str) -> Option<String> {
     None
 }
 
+/// Renders the rollout at `rollout_path` and writes it into `dir`, returning
+/// the file path. An existing export is never overwritten: the name gets a
+/// `-1`, `-2`, ... suffix instead.
+async fn write_session_export(
+    rollout_path: &Path,
+    dir: &Path,
+    format: ExportFormat,
+    redact: bool,
+) -> std::io::Result<PathBuf> {
+    let mut export = load_session_export(rollout_path).await?;
+    if redact {
+        export.redact();
+    }
+    let id = export.header.id.clone().unwrap_or_else(|| {
+        rollout_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "export".to_string())
+    });
+    let contents = export.render(format)?;
+    let extension = format.extension();
+    let mut attempt = 0u32;
+    loop {
+        let name = match attempt {
+            0 => format!("llmx-session-{id}.{extension}"),
+            n => format!("llmx-session-{id}-{n}.{extension}"),
+        };
+        let path = dir.join(name);
+        match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => {
+                // The name is now reserved for us; write through a fresh handle.
+                drop(file);
+                tokio::fs::write(&path, &contents).await?;
+                return Ok(path);
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// One-line summary of a checkpoint: short id, local time, and prompt.
+fn checkpoint_label(checkpoint: &Checkpoint) -> String {
+    let short_id: String = checkpoint.id.chars().take(7).collect();
+    let mut parts = vec![short_id];
+    if let Some(created_at) = checkpoint
+        .created_at
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+    {
+        parts.push(
+            created_at
+                .with_timezone(&Local)
+                .format("%b %-d %H:%M")
+                .to_string(),
+        );
+    }
+    let prompt = checkpoint
+        .prompt
+        .as_deref()
+        .map(|prompt| prompt.lines().next().unwrap_or_default().trim())
+        .filter(|prompt| !prompt.is_empty())
+        .unwrap_or("(no prompt)");
+    parts.push(truncate_text(prompt, 60));
+    parts.join(" · ")
+}
+
 #[cfg(test)]
 pub(crate) fn show_review_commit_picker_with_entries(
     chat: &mut ChatWidget,
@@ -2871,21 +3959,16 @@ pub(crate) fn show_review_commit_picker_with_entries(
     for entry in entries {
         let subject = entry.subject.clone();
         let sha = entry.sha.clone();
-        let short = sha.chars().take(7).collect::<String>();
         let search_val = format!("{subject} {sha}");
 
         items.push(SelectionItem {
             name: subject.clone(),
             actions: vec![Box::new(move |tx3: &AppEventSender| {
-                let hint = format!("commit {short}");
-                let prompt = format!(
-                    "Review the code changes introduced by commit {sha} (\"{subject}\"). Provide prioritized, actionable findings."
-                );
                 tx3.send(AppEvent::LlmxOp(Op::Review {
-                    review_request: ReviewRequest {
-                        prompt,
-                        user_facing_hint: hint,
-                    },
+                    review_request: review_request(ReviewTarget::Commit {
+                        sha: sha.clone(),
+                        title: Some(subject.clone()),
+                    }),
                 }));
             })],
             dismiss_on_select: true,
@@ -1,6 +1,7 @@
 use super::*;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::keymap::Keymap;
 use crate::test_backend::VT100Backend;
 use crate::tui::FrameRequester;
 use assert_matches::assert_matches;
@@ -22,6 +23,11 @@ use llmx_core::protocol::AgentMessageEvent;
 use llmx_core::protocol::AgentReasoningDeltaEvent;
 use llmx_core::protocol::AgentReasoningEvent;
 use llmx_core::protocol::ApplyPatchApprovalRequestEvent;
+use llmx_core::protocol::ApprovalRule;
+use llmx_core::protocol::ApprovalRuleAction;
+use llmx_core::protocol::ApprovalRuleEntry;
+use llmx_core::protocol::ApprovalRuleKind;
+use llmx_core::protocol::Checkpoint;
 use llmx_core::protocol::Event;
 use llmx_core::protocol::EventMsg;
 use llmx_core::protocol::ExecApprovalRequestEvent;
@@ -29,6 +35,8 @@ use llmx_core::protocol::ExecCommandBeginEvent;
 use llmx_core::protocol::ExecCommandEndEvent;
 use llmx_core::protocol::ExitedReviewModeEvent;
 use llmx_core::protocol::FileChange;
+use llmx_core::protocol::ListApprovalRulesResponseEvent;
+use llmx_core::protocol::ListCheckpointsResponseEvent;
 use llmx_core::protocol::Op;
 use llmx_core::protocol::PatchApplyBeginEvent;
 use llmx_core::protocol::PatchApplyEndEvent;
@@ -177,6 +185,7 @@ fn entered_review_mode_uses_request_hint() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the latest changes".to_string(),
             user_facing_hint: "feature branch".to_string(),
+            target: None,
         }),
     });
 
@@ -196,6 +205,7 @@ fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             prompt: "Review the current changes".to_string(),
             user_facing_hint: "current changes".to_string(),
+            target: None,
         }),
     });
 
@@ -285,6 +295,8 @@ fn make_chatwidget_manual() -> (
         enhanced_keys_supported: false,
         placeholder_text: "Ask LLMX to do anything".to_string(),
         disable_paste_burst: false,
+        keymap: Keymap::default(),
+        editing_mode: Default::default(),
     });
     let auth_manager = AuthManager::from_auth_for_testing(LlmxAuth::from_api_key("test"));
     let widget = ChatWidget {
@@ -297,6 +309,7 @@ fn make_chatwidget_manual() -> (
         session_header: SessionHeader::new(cfg.model),
         initial_user_message: None,
         token_info: None,
+        context_estimate: None,
         rate_limit_snapshot: None,
         rate_limit_warnings: RateLimitWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
@@ -312,9 +325,16 @@ fn make_chatwidget_manual() -> (
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
+        queued_messages_view_open: false,
+        mentions: MentionCache::default(),
+        held_message: None,
         suppress_session_configured_redraw: false,
         pending_notification: None,
+        turn_started_at: None,
+        rate_limit_reached: false,
         is_review_mode: false,
+        plan_mode: false,
+        checkpoints: Vec::new(),
         needs_final_message_separator: false,
         last_rendered_width: std::cell::Cell::new(None),
         feedback: llmx_feedback::LlmxFeedback::new(),
@@ -486,6 +506,64 @@ fn rate_limit_switch_prompt_defers_until_task_complete() {
     ));
 }
 
+#[test]
+fn notifications_follow_configured_events() {
+    let (mut chat, _, _) = make_chatwidget_manual();
+    chat.config.tui_notifications = Notifications::Enabled(true);
+
+    chat.on_error("stream disconnected".to_string());
+    assert_eq!(
+        chat.take_pending_notification(),
+        Some("Turn failed: stream disconnected".to_string())
+    );
+
+    chat.on_rate_limit_snapshot(Some(snapshot(100.0)));
+    assert_eq!(
+        chat.take_pending_notification(),
+        Some("Rate limit reached".to_string())
+    );
+    chat.on_rate_limit_snapshot(Some(snapshot(100.0)));
+    assert_eq!(chat.take_pending_notification(), None);
+
+    // `command-finished` is not in the default event list.
+    chat.handle_exec_end_now(ExecCommandEndEvent {
+        call_id: "call-1".to_string(),
+        stdout: String::new(),
+        stderr: String::new(),
+        aggregated_output: String::new(),
+        exit_code: 0,
+        duration: std::time::Duration::from_secs(90),
+        formatted_output: String::new(),
+    });
+    assert_eq!(chat.take_pending_notification(), None);
+}
+
+#[test]
+fn command_finished_notification_respects_min_duration() {
+    let (mut chat, _, _) = make_chatwidget_manual();
+    chat.config.tui_notifications = Notifications::Enabled(true);
+    chat.config.notification_events = vec![NotificationEvent::CommandFinished];
+    chat.config.notification_min_duration = std::time::Duration::from_secs(60);
+    let end = |call_id: &str, secs: u64| ExecCommandEndEvent {
+        call_id: call_id.to_string(),
+        stdout: String::new(),
+        stderr: String::new(),
+        aggregated_output: String::new(),
+        exit_code: 2,
+        duration: std::time::Duration::from_secs(secs),
+        formatted_output: String::new(),
+    };
+
+    chat.handle_exec_end_now(end("quick", 5));
+    assert_eq!(chat.take_pending_notification(), None);
+
+    chat.handle_exec_end_now(end("slow", 90));
+    assert_eq!(
+        chat.take_pending_notification(),
+        Some("Command failed (exit 2) after 1m 30s: slow".to_string())
+    );
+}
+
 #[test]
 fn rate_limit_switch_prompt_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
@@ -743,6 +821,58 @@ fn alt_up_edits_most_recent_queued_message() {
     );
 }
 
+#[test]
+fn alt_down_manages_queue_and_holds_it_until_closed() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
+    chat.bottom_pane.set_task_running(true);
+    for text in ["first", "second", "third"] {
+        chat.queued_user_messages
+            .push_back(UserMessage::from(text.to_string()));
+    }
+    chat.refresh_queued_user_messages();
+
+    let mut forward_edits = |chat: &mut ChatWidget| {
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::EditQueuedMessages(edit) = event {
+                chat.edit_queued_messages(edit);
+            }
+        }
+    };
+
+    // Open the view (third is selected), move it to the front, then drop "second".
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::ALT));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+    forward_edits(&mut chat);
+    let texts: Vec<&str> = chat
+        .queued_user_messages
+        .iter()
+        .map(|m| m.text.as_str())
+        .collect();
+    assert_eq!(texts, vec!["third", "first"]);
+
+    // The turn ends while the view is open: nothing is sent yet.
+    chat.bottom_pane.set_task_running(false);
+    chat.maybe_send_next_queued_input();
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    forward_edits(&mut chat);
+    match op_rx.try_recv() {
+        Ok(Op::UserInput { items }) => assert_eq!(
+            items,
+            vec![UserInput::Text {
+                text: "third".to_string()
+            }]
+        ),
+        other => panic!("expected UserInput, got {other:?}"),
+    }
+    assert_eq!(chat.queued_user_messages.len(), 1);
+}
+
 /// Pressing Up to recall the most recent history entry and immediately queuing
 /// it while a task is running should always enqueue the same text, even when it
 /// is queued repeatedly.
@@ -991,6 +1121,85 @@ fn slash_undo_sends_op() {
     }
 }
 
+#[test]
+fn slash_checkpoints_requests_listing() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command(SlashCommand::Checkpoints);
+
+    match rx.try_recv() {
+        Ok(AppEvent::LlmxOp(Op::ListCheckpoints)) => {}
+        other => panic!("expected AppEvent::LlmxOp(Op::ListCheckpoints), got {other:?}"),
+    }
+}
+
+#[test]
+fn empty_checkpoint_listing_renders_info_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.handle_llmx_event(Event {
+        id: "sub-1".to_string(),
+        msg: EventMsg::ListCheckpointsResponse(ListCheckpointsResponseEvent {
+            checkpoints: Vec::new(),
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected a single info message");
+    let rendered = lines_to_single_string(&cells[0]);
+    assert!(
+        rendered.contains("No checkpoints yet."),
+        "expected empty checkpoint message: {rendered}"
+    );
+}
+
+#[test]
+fn slash_permissions_lists_rules_and_revokes_selection() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command(SlashCommand::Permissions);
+    match rx.try_recv() {
+        Ok(AppEvent::LlmxOp(Op::ListApprovalRules)) => {}
+        other => panic!("expected AppEvent::LlmxOp(Op::ListApprovalRules), got {other:?}"),
+    }
+
+    let rule = ApprovalRule {
+        kind: ApprovalRuleKind::Command,
+        action: ApprovalRuleAction::Allow,
+        pattern: "cargo test *".to_string(),
+    };
+    chat.handle_llmx_event(Event {
+        id: "sub-1".to_string(),
+        msg: EventMsg::ListApprovalRulesResponse(ListApprovalRulesResponseEvent {
+            rules: vec![ApprovalRuleEntry {
+                rule: rule.clone(),
+                path: PathBuf::from("/tmp/approvals.toml"),
+            }],
+        }),
+    });
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut revoked = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::LlmxOp(Op::RevokeApprovalRule { rule }) = ev {
+            revoked = Some(rule);
+        }
+    }
+    assert_eq!(revoked, Some(rule));
+}
+
+#[test]
+fn checkpoint_label_uses_short_id_and_first_prompt_line() {
+    let checkpoint = Checkpoint {
+        id: "0123456789abcdef".to_string(),
+        created_at: None,
+        prompt: Some("fix the parser\nand add tests".to_string()),
+        changed_files: Vec::new(),
+    };
+
+    assert_eq!(checkpoint_label(&checkpoint), "0123456 · fix the parser");
+}
+
 #[test]
 fn slash_rollout_displays_current_path() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
@@ -1631,6 +1840,31 @@ fn exec_history_extends_previous_when_consecutive() {
     assert_snapshot!("exploring_step6_finish_cat_bar", active_blob(&chat));
 }
 
+#[test]
+fn theme_popup_previews_highlighted_theme_and_restores_on_esc() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    chat.bottom_pane.set_task_running(true);
+    chat.dispatch_command(SlashCommand::Theme);
+
+    // "auto" is the configured theme, so moving down highlights "default".
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+    let mut previews = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::PreviewTheme(name) => previews.push(name),
+            AppEvent::SelectTheme(name) => panic!("unexpected theme selection {name}"),
+            _ => {}
+        }
+    }
+    assert_eq!(
+        previews,
+        vec![Some("default".to_string()), Some("light".to_string()), None]
+    );
+}
+
 #[test]
 fn disabled_slash_command_while_task_running_snapshot() {
     // Build a chat widget and simulate an active task
@@ -2007,6 +2241,36 @@ fn interrupt_prepends_queued_messages_before_existing_composer_text() {
     let _ = drain_insert_history(&mut rx);
 }
 
+#[test]
+fn esc_cancels_message_held_for_mentions() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
+
+    // A message waiting for a slow mention, with another queued behind it.
+    chat.mentions.start("@sym:Slow");
+    chat.held_message = Some(UserMessage::from("explain @sym:Slow".to_string()));
+    chat.queued_user_messages
+        .push_back(UserMessage::from("then this".to_string()));
+    assert!(!chat.is_normal_backtrack_mode());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+    assert!(chat.held_message.is_none());
+    assert!(chat.queued_user_messages.is_empty());
+    assert_eq!(
+        chat.bottom_pane.composer_text(),
+        "explain @sym:Slow\nthen this"
+    );
+
+    // A resolution arriving after the cancel must not send anything.
+    chat.on_mention_resolved("@sym:Slow".to_string(), Err("not found".to_string()));
+    assert!(
+        op_rx.try_recv().is_err(),
+        "unexpected outbound op after cancel"
+    );
+
+    let _ = drain_insert_history(&mut rx);
+}
+
 // Snapshot test: ChatWidget at very small heights (idle)
 // Ensures overall layout behaves when terminal height is extremely constrained.
 #[test]
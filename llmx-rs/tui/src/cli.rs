@@ -65,6 +65,11 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Start in plan mode: LLMX explores read-only and proposes a plan for
+    /// approval before making any change.
+    #[arg(long = "plan", default_value_t = false)]
+    pub plan: bool,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
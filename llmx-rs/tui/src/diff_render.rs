@@ -1,7 +1,6 @@
 use diffy::Hunk;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -18,11 +17,12 @@ use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme;
 use llmx_core::git_info::get_git_repo_root;
 use llmx_core::protocol::FileChange;
 
 // Internal representation for diff line rendering
-enum DiffLineType {
+pub(crate) enum DiffLineType {
     Insert,
     Delete,
     Context,
@@ -126,9 +126,9 @@ fn collect_rows(changes: &HashMap<PathBuf, FileChange>) -> Vec<Row> {
 fn render_line_count_summary(added: usize, removed: usize) -> Vec<RtSpan<'static>> {
     let mut spans = Vec::new();
     spans.push("(".into());
-    spans.push(format!("+{added}").green());
+    spans.push(RtSpan::styled(format!("+{added}"), style_add()));
     spans.push(" ".into());
-    spans.push(format!("-{removed}").red());
+    spans.push(RtSpan::styled(format!("-{removed}"), style_del()));
     spans.push(")".into());
     spans
 }
@@ -331,7 +331,7 @@ fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
     }
 }
 
-fn push_wrapped_diff_line(
+pub(crate) fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
@@ -372,6 +372,7 @@ fn push_wrapped_diff_line(
             let gutter = format!("{ln_str:>gutter_width$} ");
             // Content with a sign ('+'/'-'/' ') styled per diff kind
             let content = format!("{sign_char}{chunk}");
+            let content = fill_tinted_row(content, available_content_cols + 1, line_style);
             lines.push(RtLine::from(vec![
                 RtSpan::styled(gutter, style_gutter()),
                 RtSpan::styled(content, line_style),
@@ -380,9 +381,10 @@ fn push_wrapped_diff_line(
         } else {
             // Continuation lines keep a space for the sign column so content aligns
             let gutter = format!("{:gutter_width$}  ", "");
+            let content = fill_tinted_row(chunk.to_string(), available_content_cols, line_style);
             lines.push(RtLine::from(vec![
                 RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
+                RtSpan::styled(content, line_style),
             ]));
         }
         if remaining_text.is_empty() {
@@ -392,7 +394,17 @@ fn push_wrapped_diff_line(
     lines
 }
 
-fn line_number_width(max_line_number: usize) -> usize {
+/// Themes that tint diff backgrounds get the tint across the whole row, not
+/// just behind the text.
+fn fill_tinted_row(content: String, cols: usize, style: Style) -> String {
+    if style.bg.is_some() {
+        format!("{content:<cols$}")
+    } else {
+        content
+    }
+}
+
+pub(crate) fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
     } else {
@@ -409,11 +421,11 @@ fn style_context() -> Style {
 }
 
 fn style_add() -> Style {
-    Style::default().fg(Color::Green)
+    theme::current().diff_add
 }
 
 fn style_del() -> Style {
-    Style::default().fg(Color::Red)
+    theme::current().diff_remove
 }
 
 #[cfg(test)]
@@ -0,0 +1,116 @@
+//! Round-trips text through the user's `$VISUAL` / `$EDITOR`.
+//!
+//! The caller is responsible for handing the terminal over to the editor
+//! (see [`crate::tui::Tui::with_restored`]); this module only launches the
+//! editor on a temporary file and reads the result back.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::eyre;
+use llmx_core::editor::editor_command;
+use llmx_core::editor::run_editor;
+
+/// Opens `initial` in the user's editor and returns the saved contents.
+/// `suffix` (e.g. `.diff`) lets the editor pick a syntax mode.
+pub(crate) fn edit_text(initial: &str, suffix: &str) -> Result<String> {
+    let argv =
+        editor_command().ok_or_else(|| eyre!("set $VISUAL or $EDITOR to edit in an editor"))?;
+    let mut file = tempfile::Builder::new()
+        .prefix("llmx-")
+        .suffix(suffix)
+        .tempfile()?;
+    file.write_all(initial.as_bytes())?;
+    file.flush()?;
+
+    run_editor(&argv, file.path())?;
+    Ok(std::fs::read_to_string(file.path())?)
+}
+
+/// Separates a composer draft from the list of attached files in the
+/// editor buffer.
+const ATTACHMENTS_MARKER: &str =
+    "# llmx: attached files, one path per line (delete a line to detach)";
+
+/// Builds the editor buffer for a composer draft: the prompt text followed by
+/// the attached paths, if there are any.
+pub(crate) fn draft_with_attachments(text: &str, attachments: &[PathBuf]) -> String {
+    if attachments.is_empty() {
+        return text.to_string();
+    }
+    let mut draft = text.trim_end_matches('\n').to_string();
+    draft.push_str("\n\n");
+    draft.push_str(ATTACHMENTS_MARKER);
+    draft.push('\n');
+    for path in attachments {
+        draft.push_str(&path.to_string_lossy());
+        draft.push('\n');
+    }
+    draft
+}
+
+/// Splits an edited composer draft back into the prompt text and the
+/// attached paths. Blank lines and `#` comments in the attachment list are
+/// ignored.
+pub(crate) fn split_attachments(edited: &str) -> (String, Vec<PathBuf>) {
+    let marker_start = edited
+        .rmatch_indices(ATTACHMENTS_MARKER)
+        .map(|(idx, _)| idx)
+        .find(|&idx| idx == 0 || edited[..idx].ends_with('\n'));
+    let Some(marker_start) = marker_start else {
+        // Editors usually append a final newline; don't keep it in the prompt.
+        let text = edited.strip_suffix('\n').unwrap_or(edited);
+        return (text.to_string(), Vec::new());
+    };
+    let text = edited[..marker_start].trim_end_matches('\n').to_string();
+    let attachments = edited[marker_start + ATTACHMENTS_MARKER.len()..]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(PathBuf::from)
+        .collect();
+    (text, attachments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn draft_round_trips_attachments() {
+        let attachments = vec![
+            PathBuf::from("/tmp/screenshot.png"),
+            PathBuf::from("/tmp/diagram one.png"),
+        ];
+        let draft = draft_with_attachments("fix the layout\n", &attachments);
+        assert_eq!(
+            draft,
+            format!(
+                "fix the layout\n\n{ATTACHMENTS_MARKER}\n/tmp/screenshot.png\n/tmp/diagram one.png\n"
+            )
+        );
+
+        let edited = draft.replace("/tmp/screenshot.png\n", "# gone\n\n/tmp/new.png\n");
+        assert_eq!(
+            split_attachments(&edited),
+            (
+                "fix the layout".to_string(),
+                vec![
+                    PathBuf::from("/tmp/new.png"),
+                    PathBuf::from("/tmp/diagram one.png"),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn draft_without_attachments_is_plain_text() {
+        assert_eq!(draft_with_attachments("hello", &[]), "hello");
+        assert_eq!(
+            split_attachments("# heading\nbody\n"),
+            ("# heading\nbody".to_string(), Vec::new())
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::inline_image::ImageSource;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
 use crate::render::line_utils::prefix_lines;
@@ -15,6 +16,7 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::update_action::UpdateAction;
 use crate::version::LLMX_CLI_VERSION;
@@ -52,6 +54,7 @@ use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 use tracing::error;
@@ -97,6 +100,13 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn is_stream_continuation(&self) -> bool {
         false
     }
+
+    /// Images drawn as thumbnails below the cell when the terminal supports
+    /// inline graphics. The cell's lines should still name each image, since
+    /// they are all other terminals (and the transcript overlay) show.
+    fn inline_images(&self) -> Vec<ImageSource> {
+        Vec::new()
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -130,6 +140,8 @@ impl dyn HistoryCell {
 #[derive(Debug)]
 pub(crate) struct UserHistoryCell {
     pub message: String,
+    /// Attached images; the message text carries their placeholders.
+    pub images: Vec<ImageSource>,
 }
 
 impl HistoryCell for UserHistoryCell {
@@ -156,6 +168,10 @@ impl HistoryCell for UserHistoryCell {
         lines.push(Line::from("").style(style));
         lines
     }
+
+    fn inline_images(&self) -> Vec<ImageSource> {
+        self.images.clone()
+    }
 }
 
 #[derive(Debug)]
@@ -395,7 +411,9 @@ pub fn new_approval_decision_cell(
     use llmx_core::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
+        // Partial approvals only apply to patches; treat one like a one-off
+        // approval should it ever reach a command.
+        Approved | ApprovedPartial { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✔ ".green(),
@@ -421,6 +439,16 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedAlways { pattern } => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "always allowed".bold(),
+                " commands matching ".into(),
+                Span::from(pattern).dim(),
+                " in this project".into(),
+            ],
+        ),
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -433,6 +461,16 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        DeniedAlways { pattern } => (
+            "✗ ".red(),
+            vec![
+                "You ".into(),
+                "always denied".bold(),
+                " commands matching ".into(),
+                Span::from(pattern).dim(),
+                " in this project".into(),
+            ],
+        ),
         Abort => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -454,6 +492,38 @@ pub fn new_approval_decision_cell(
     ))
 }
 
+/// Outcome of a hunk-by-hunk patch review.
+pub(crate) fn new_patch_review_decision_cell(
+    accepted: usize,
+    total: usize,
+) -> Box<dyn HistoryCell> {
+    let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = if accepted == 0 {
+        (
+            "✗ ".red(),
+            vec![
+                "You ".into(),
+                "rejected".bold(),
+                format!(" all {total} proposed changes").into(),
+            ],
+        )
+    } else {
+        (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                format!(" {accepted} of {total} proposed changes").into(),
+            ],
+        )
+    };
+
+    Box::new(PrefixedWrappedHistoryCell::new(
+        Line::from(summary),
+        symbol,
+        "  ",
+    ))
+}
+
 /// Cyan history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
@@ -475,12 +545,16 @@ impl HistoryCell for PatchHistoryCell {
 
 #[derive(Debug)]
 struct CompletedMcpToolCallWithImageOutput {
-    _image: DynamicImage,
+    image: Arc<DynamicImage>,
 }
 impl HistoryCell for CompletedMcpToolCallWithImageOutput {
     fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
         vec!["tool result (image output)".into()]
     }
+
+    fn inline_images(&self) -> Vec<ImageSource> {
+        vec![ImageSource::Decoded(self.image.clone())]
+    }
 }
 
 pub(crate) const SESSION_HEADER_MAX_INNER_WIDTH: usize = 56; // Just an eyeballed value
@@ -527,9 +601,13 @@ fn with_border_internal(
         .unwrap_or(max_line_width)
         .max(max_line_width);
 
+    let border = theme::current().border;
     let mut out = Vec::with_capacity(lines.len() + 2);
     let border_inner_width = content_width + 2;
-    out.push(vec![format!("╭{}╮", "─".repeat(border_inner_width)).dim()].into());
+    out.push(Line::from(Span::styled(
+        format!("╭{}╮", "─".repeat(border_inner_width)),
+        border,
+    )));
 
     for line in lines.into_iter() {
         let used_width: usize = line
@@ -538,16 +616,19 @@ fn with_border_internal(
             .sum();
         let span_count = line.spans.len();
         let mut spans: Vec<Span<'static>> = Vec::with_capacity(span_count + 4);
-        spans.push(Span::from("│ ").dim());
+        spans.push(Span::styled("│ ", border));
         spans.extend(line.into_iter());
         if used_width < content_width {
             spans.push(Span::from(" ".repeat(content_width - used_width)).dim());
         }
-        spans.push(Span::from(" │").dim());
+        spans.push(Span::styled(" │", border));
         out.push(Line::from(spans));
     }
 
-    out.push(vec![format!("╰{}╯", "─".repeat(border_inner_width)).dim()].into());
+    out.push(Line::from(Span::styled(
+        format!("╰{}╯", "─".repeat(border_inner_width)),
+        border,
+    )));
 
     out
 }
@@ -652,8 +733,8 @@ pub(crate) fn new_session_info(
     })
 }
 
-pub(crate) fn new_user_prompt(message: String) -> UserHistoryCell {
-    UserHistoryCell { message }
+pub(crate) fn new_user_prompt(message: String, images: Vec<ImageSource>) -> UserHistoryCell {
+    UserHistoryCell { message, images }
 }
 
 #[derive(Debug)]
@@ -1007,7 +1088,9 @@ fn try_new_completed_mcp_tool_call_with_image_output(
                     }
                 };
 
-                Some(CompletedMcpToolCallWithImageOutput { _image: image })
+                Some(CompletedMcpToolCallWithImageOutput {
+                    image: Arc::new(image),
+                })
             } else {
                 None
             }
@@ -1360,7 +1443,23 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
+#[derive(Debug)]
+pub(crate) struct ViewImageHistoryCell {
+    lines: Vec<Line<'static>>,
+    path: PathBuf,
+}
+
+impl HistoryCell for ViewImageHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn inline_images(&self) -> Vec<ImageSource> {
+        vec![ImageSource::Path(self.path.clone())]
+    }
+}
+
+pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> ViewImageHistoryCell {
     let display_path = display_path_for(&path, cwd);
 
     let lines: Vec<Line<'static>> = vec![
@@ -1368,7 +1467,7 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
         vec!["  └ ".dim(), display_path.dim()].into(),
     ];
 
-    PlainHistoryCell { lines }
+    ViewImageHistoryCell { lines, path }
 }
 
 pub(crate) fn new_reasoning_summary_block(
@@ -2166,6 +2265,7 @@ mod tests {
         let msg = "one two three four five six seven";
         let cell = UserHistoryCell {
             message: msg.to_string(),
+            images: Vec::new(),
         };
 
         // Small width to force wrapping more clearly. Effective wrap width is width-2 due to the ▌ prefix and trailing space.
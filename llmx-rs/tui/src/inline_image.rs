@@ -0,0 +1,388 @@
+//! Image thumbnails in the transcript via terminal graphics protocols.
+//!
+//! A thumbnail is drawn over blank rows that [`crate::insert_history`]
+//! reserves above the viewport, so it scrolls with the rest of the history.
+//! When the terminal has no graphics support (or runs inside a multiplexer
+//! that would swallow the escape sequences) only the text label the history
+//! cell already shows is printed.
+
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use image::DynamicImage;
+use image::ImageFormat;
+use image::RgbaImage;
+use image::imageops::FilterType;
+use llmx_core::config::types::ImageProtocol;
+
+/// Largest thumbnail, in terminal cells.
+const MAX_COLS: u16 = 48;
+const MAX_ROWS: u16 = 12;
+
+/// Cell size in pixels assumed when the terminal does not report one.
+const DEFAULT_CELL_SIZE: (u32, u32) = (8, 16);
+
+/// Kitty limits each graphics command to 4096 bytes of payload.
+const KITTY_CHUNK_LEN: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+impl GraphicsProtocol {
+    /// Resolves the `tui.image_protocol` setting; `None` means images are
+    /// shown as text labels only.
+    pub(crate) fn from_config(setting: ImageProtocol) -> Option<Self> {
+        match setting {
+            ImageProtocol::Auto => detect(|name| std::env::var(name).ok()),
+            ImageProtocol::Kitty => Some(Self::Kitty),
+            ImageProtocol::Iterm2 => Some(Self::Iterm2),
+            ImageProtocol::Sixel => Some(Self::Sixel),
+            ImageProtocol::None => None,
+        }
+    }
+}
+
+/// Guesses graphics support from the variables terminals export. This is
+/// deliberately conservative: a wrong guess prints escape garbage, while a
+/// missed terminal only loses the thumbnail.
+fn detect(env: impl Fn(&str) -> Option<String>) -> Option<GraphicsProtocol> {
+    if env("TMUX").is_some() || env("ZELLIJ").is_some() {
+        return None;
+    }
+    let term = env("TERM").unwrap_or_default();
+    let term_program = env("TERM_PROGRAM").unwrap_or_default();
+    if env("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || term_program == "ghostty"
+    {
+        Some(GraphicsProtocol::Kitty)
+    } else if term_program == "iTerm.app"
+        || term_program == "WezTerm"
+        || env("LC_TERMINAL").as_deref() == Some("iTerm2")
+    {
+        Some(GraphicsProtocol::Iterm2)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Where a history cell's image comes from. Images are decoded only when the
+/// cell is inserted and the terminal can show them.
+#[derive(Debug, Clone)]
+pub(crate) enum ImageSource {
+    Path(PathBuf),
+    /// A `data:image/...;base64,` URL, as recorded for user messages.
+    DataUrl(String),
+    Decoded(Arc<DynamicImage>),
+}
+
+impl ImageSource {
+    pub(crate) fn load(&self) -> Option<Arc<DynamicImage>> {
+        let decoded = match self {
+            Self::Path(path) => image::open(path),
+            Self::DataUrl(url) => {
+                let (_, data) = url.split_once(";base64,")?;
+                let bytes = BASE64_STANDARD.decode(data.trim()).ok()?;
+                image::load_from_memory(&bytes)
+            }
+            Self::Decoded(image) => return Some(image.clone()),
+        };
+        decoded
+            .map(Arc::new)
+            .map_err(|err| tracing::debug!("not showing image thumbnail: {err}"))
+            .ok()
+    }
+}
+
+/// A thumbnail encoded for one protocol, ready to be written at the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InlineImage {
+    /// Transcript rows the image covers.
+    pub(crate) rows: u16,
+    /// Escape sequence that draws the image at the cursor position.
+    pub(crate) escape: String,
+}
+
+impl InlineImage {
+    /// Scales `image` down (never up) to fit `max_cols` and [`MAX_ROWS`]
+    /// cells of `cell_size` pixels and encodes it for `protocol`.
+    pub(crate) fn encode(
+        image: &DynamicImage,
+        protocol: GraphicsProtocol,
+        max_cols: u16,
+        cell_size: (u32, u32),
+    ) -> Option<Self> {
+        let (cell_width, cell_height) = cell_size;
+        let max_cols = max_cols.min(MAX_COLS);
+        if max_cols == 0 || cell_width == 0 || cell_height == 0 {
+            return None;
+        }
+        let (width, height) = (image.width(), image.height());
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let max_width = f64::from(u32::from(max_cols) * cell_width);
+        let max_height = f64::from(u32::from(MAX_ROWS) * cell_height);
+        let scale = (max_width / f64::from(width))
+            .min(max_height / f64::from(height))
+            .min(1.0);
+        let thumb_width = ((f64::from(width) * scale).round() as u32).max(1);
+        let thumb_height = ((f64::from(height) * scale).round() as u32).max(1);
+        let thumb = if (thumb_width, thumb_height) == (width, height) {
+            image.clone()
+        } else {
+            image.resize_exact(thumb_width, thumb_height, FilterType::Triangle)
+        };
+
+        let escape = match protocol {
+            GraphicsProtocol::Kitty => kitty_escape(&png_base64(&thumb)?.0),
+            GraphicsProtocol::Iterm2 => {
+                let (payload, len) = png_base64(&thumb)?;
+                iterm2_escape(&payload, len, thumb_width, thumb_height)
+            }
+            GraphicsProtocol::Sixel => sixel_escape(&thumb.to_rgba8()),
+        };
+        let rows = u16::try_from(thumb_height.div_ceil(cell_height)).ok()?;
+        Some(Self { rows, escape })
+    }
+}
+
+/// Pixel size of one terminal cell, from the size the terminal reports.
+pub(crate) fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => (
+            u32::from(size.width / size.columns).max(1),
+            u32::from(size.height / size.rows).max(1),
+        ),
+        _ => DEFAULT_CELL_SIZE,
+    }
+}
+
+/// Returns the base64-encoded PNG and its length in bytes before encoding.
+fn png_base64(image: &DynamicImage) -> Option<(String, usize)> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|err| tracing::debug!("failed to encode thumbnail: {err}"))
+        .ok()?;
+    Some((BASE64_STANDARD.encode(&png), png.len()))
+}
+
+/// Kitty graphics: transmit and display a PNG (`f=100,a=T`) without moving
+/// the cursor (`C=1`) or sending a reply (`q=2`), split into chunks.
+fn kitty_escape(payload: &str) -> String {
+    let mut escape = String::new();
+    let mut start = 0;
+    loop {
+        let end = (start + KITTY_CHUNK_LEN).min(payload.len());
+        let more = u8::from(end < payload.len());
+        // base64 is ASCII, so any byte offset is a char boundary.
+        let chunk = &payload[start..end];
+        if start == 0 {
+            escape.push_str(&format!("\x1b_Gf=100,a=T,q=2,C=1,m={more};{chunk}\x1b\\"));
+        } else {
+            escape.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+        if end == payload.len() {
+            return escape;
+        }
+        start = end;
+    }
+}
+
+fn iterm2_escape(payload: &str, len: usize, width: u32, height: u32) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={len};width={width}px;height={height}px;preserveAspectRatio=1:{payload}\x07"
+    )
+}
+
+/// Sixel graphics with colors reduced to a 6×6×6 cube. Pixels that are
+/// mostly transparent are left unpainted, so the terminal background shows.
+fn sixel_escape(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let indices: Vec<Option<usize>> = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            (a >= 128).then(|| cube_level(r) * 36 + cube_level(g) * 6 + cube_level(b))
+        })
+        .collect();
+    let mut used = [false; 216];
+    for index in indices.iter().flatten() {
+        used[*index] = true;
+    }
+    let colors: Vec<usize> = (0..used.len()).filter(|&index| used[index]).collect();
+
+    // P2=1: unpainted pixels keep the background. Raster attributes give a
+    // 1:1 pixel aspect ratio and the image size.
+    let mut escape = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for &index in &colors {
+        let percent = |level: usize| level * 100 / 5;
+        escape.push_str(&format!(
+            "#{index};2;{};{};{}",
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        ));
+    }
+
+    let width = width as usize;
+    let height = height as usize;
+    for band_top in (0..height).step_by(6) {
+        let mut first_color = true;
+        for &color in &colors {
+            let sixels: Vec<u8> = (0..width)
+                .map(|x| {
+                    (0..6)
+                        .filter(|dy| {
+                            let y = band_top + dy;
+                            y < height && indices[y * width + x] == Some(color)
+                        })
+                        .fold(0u8, |bits, dy| bits | (1 << dy))
+                })
+                .collect();
+            if sixels.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+            if !first_color {
+                // Graphics carriage return: overlay the next color on this band.
+                escape.push('$');
+            }
+            first_color = false;
+            escape.push_str(&format!("#{color}"));
+            push_sixel_runs(&mut escape, &sixels);
+        }
+        if band_top + 6 < height {
+            escape.push('-');
+        }
+    }
+    escape.push_str("\x1b\\");
+    escape
+}
+
+fn cube_level(channel: u8) -> usize {
+    (usize::from(channel) * 5 + 127) / 255
+}
+
+/// Appends sixel characters, run-length encoding repeats (`!<count><char>`).
+/// Trailing empty sixels are dropped; they would paint nothing.
+fn push_sixel_runs(escape: &mut String, sixels: &[u8]) {
+    let end = sixels
+        .iter()
+        .rposition(|&bits| bits != 0)
+        .map_or(0, |i| i + 1);
+    let mut iter = sixels[..end].iter().peekable();
+    while let Some(&bits) = iter.next() {
+        let mut count = 1;
+        while iter.next_if_eq(&&bits).is_some() {
+            count += 1;
+        }
+        let ch = char::from(0x3f + bits);
+        if count > 3 {
+            escape.push_str(&format!("!{count}{ch}"));
+        } else {
+            escape.extend(std::iter::repeat_n(ch, count));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        assert_eq!(
+            detect(env(&[("TERM", "xterm-kitty")])),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(
+            detect(env(&[("TERM", "foot")])),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(detect(env(&[("TERM", "xterm-256color")])), None);
+        // tmux drops the graphics sequences unless passthrough is set up.
+        assert_eq!(
+            detect(env(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1000")])),
+            None
+        );
+    }
+
+    #[test]
+    fn encodes_sixel_with_run_length_and_transparency() {
+        let mut image = RgbaImage::from_pixel(5, 7, Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 6, Rgba([0, 0, 255, 255]));
+        image.put_pixel(4, 6, Rgba([0, 0, 0, 0]));
+        assert_eq!(
+            sixel_escape(&image),
+            concat!(
+                "\x1bP0;1;0q\"1;1;5;7",
+                "#5;2;0;0;100#180;2;100;0;0",
+                // Band 1: all six rows red.
+                "#180!5~-",
+                // Band 2: blue at x=0, red at x=1..=3, transparent at x=4.
+                "#5@$#180?@@@",
+                "\x1b\\",
+            )
+        );
+    }
+
+    #[test]
+    fn splits_kitty_payload_into_chunks() {
+        let payload = "A".repeat(KITTY_CHUNK_LEN + 2);
+        let escape = kitty_escape(&payload);
+        assert_eq!(
+            escape,
+            format!(
+                "\x1b_Gf=100,a=T,q=2,C=1,m=1;{}\x1b\\\x1b_Gm=0;AA\x1b\\",
+                "A".repeat(KITTY_CHUNK_LEN)
+            )
+        );
+    }
+
+    #[test]
+    fn scales_thumbnail_to_fit_without_upscaling() {
+        let large = DynamicImage::ImageRgba8(RgbaImage::new(800, 400));
+        let thumb =
+            InlineImage::encode(&large, GraphicsProtocol::Iterm2, 100, (8, 16)).expect("thumbnail");
+        // 48 columns of 8px wide: 384x192, which is 12 rows of 16px.
+        assert_eq!(thumb.rows, 12);
+        assert!(thumb.escape.contains(";width=384px;height=192px;"));
+
+        let small = DynamicImage::ImageRgba8(RgbaImage::new(20, 20));
+        let thumb =
+            InlineImage::encode(&small, GraphicsProtocol::Kitty, 10, (8, 16)).expect("thumbnail");
+        assert_eq!(thumb.rows, 2);
+        assert!(thumb.escape.starts_with("\x1b_Gf=100,a=T,q=2,C=1,m=0;"));
+
+        assert_eq!(
+            InlineImage::encode(&small, GraphicsProtocol::Sixel, 0, (8, 16)),
+            None
+        );
+    }
+}
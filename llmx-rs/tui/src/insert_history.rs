@@ -2,6 +2,7 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
+use crate::inline_image::InlineImage;
 use crate::wrapping::word_wrap_lines_borrowed;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
@@ -134,6 +135,32 @@ where
     Ok(())
 }
 
+/// Columns between the left edge and an inline thumbnail.
+pub(crate) const INLINE_IMAGE_INDENT: u16 = 4;
+
+/// Draw `image` over the `image.rows` history rows directly above the
+/// viewport. Callers insert that many blank lines first so the graphic lands
+/// on reserved space and scrolls into scrollback with the rest of history.
+pub(crate) fn insert_history_image<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    image: &InlineImage,
+) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    let Some(image_top) = terminal.viewport_area.top().checked_sub(image.rows) else {
+        return Ok(());
+    };
+    let last_cursor_pos = terminal.last_known_cursor_pos;
+    let writer = terminal.backend_mut();
+    queue!(
+        writer,
+        MoveTo(INLINE_IMAGE_INDENT, image_top),
+        Print(&image.escape),
+        MoveTo(last_cursor_pos.x, last_cursor_pos.y),
+    )
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetScrollRegion(pub std::ops::Range<u16>);
 
@@ -318,6 +345,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_image_is_drawn_over_reserved_rows() {
+        let width: u16 = 40;
+        let height: u16 = 10;
+        let backend = VT100Backend::new(width, height);
+        let mut term = crate::custom_terminal::Terminal::with_options(backend).expect("terminal");
+        term.set_viewport_area(Rect::new(0, height - 1, width, 1));
+
+        let image = InlineImage {
+            rows: 2,
+            escape: "\x1b_Gf=100;AAAA\x1b\\".to_string(),
+        };
+        insert_history_lines(&mut term, vec![Line::from(""), Line::from("")])
+            .expect("insert lines");
+        insert_history_image(&mut term, &image).expect("insert image");
+
+        let written = String::from_utf8_lossy(term.backend().written()).into_owned();
+        let expected = format!("\x1b[8;5H{}", image.escape);
+        assert!(
+            written.contains(&expected),
+            "expected {expected:?} in {written:?}"
+        );
+    }
+
     #[test]
     fn vt100_blockquote_line_emits_green_fg() {
         // Set up a small off-screen terminal
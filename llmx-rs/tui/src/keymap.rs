@@ -0,0 +1,346 @@
+//! Key bindings for composer and transcript actions, resolved from the
+//! `[tui.keymap]` config table.
+//!
+//! Each action maps to one or more chords such as `"ctrl+j"` or
+//! `"alt+enter"`. The first chord of an action is the one shown in footer
+//! hints. Actions that are not configured, or whose chords fail to parse,
+//! keep their defaults.
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use llmx_core::config::types::KeyChords;
+use llmx_core::config::types::TuiKeymap;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyAction {
+    Submit,
+    Newline,
+    HistoryUp,
+    HistoryDown,
+    OpenTranscript,
+    Interrupt,
+    Backtrack,
+    ExternalEditor,
+    NewSession,
+    CloseSession,
+    NextSession,
+    PrevSession,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Keymap {
+    submit: Vec<KeyBinding>,
+    newline: Vec<KeyBinding>,
+    history_up: Vec<KeyBinding>,
+    history_down: Vec<KeyBinding>,
+    open_transcript: Vec<KeyBinding>,
+    interrupt: Vec<KeyBinding>,
+    backtrack: Vec<KeyBinding>,
+    external_editor: Vec<KeyBinding>,
+    new_session: Vec<KeyBinding>,
+    close_session: Vec<KeyBinding>,
+    next_session: Vec<KeyBinding>,
+    prev_session: Vec<KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            submit: vec![key_hint::plain(KeyCode::Enter)],
+            newline: vec![
+                key_hint::shift(KeyCode::Enter),
+                key_hint::ctrl(KeyCode::Char('j')),
+                key_hint::alt(KeyCode::Enter),
+            ],
+            history_up: vec![
+                key_hint::plain(KeyCode::Up),
+                key_hint::ctrl(KeyCode::Char('p')),
+            ],
+            history_down: vec![
+                key_hint::plain(KeyCode::Down),
+                key_hint::ctrl(KeyCode::Char('n')),
+            ],
+            open_transcript: vec![key_hint::ctrl(KeyCode::Char('t'))],
+            interrupt: vec![key_hint::plain(KeyCode::Esc)],
+            backtrack: vec![key_hint::plain(KeyCode::Esc)],
+            external_editor: vec![key_hint::ctrl(KeyCode::Char('g'))],
+            new_session: vec![key_hint::alt(KeyCode::Char('n'))],
+            close_session: vec![key_hint::alt(KeyCode::Char('w'))],
+            next_session: vec![
+                key_hint::alt(KeyCode::Char('.')),
+                key_hint::ctrl(KeyCode::PageDown),
+            ],
+            prev_session: vec![
+                key_hint::alt(KeyCode::Char(',')),
+                key_hint::ctrl(KeyCode::PageUp),
+            ],
+        }
+    }
+}
+
+impl Keymap {
+    pub(crate) fn from_config(config: &TuiKeymap) -> Self {
+        let mut keymap = Self::default();
+        let entries = [
+            ("submit", &config.submit, &mut keymap.submit),
+            ("newline", &config.newline, &mut keymap.newline),
+            ("history_up", &config.history_up, &mut keymap.history_up),
+            (
+                "history_down",
+                &config.history_down,
+                &mut keymap.history_down,
+            ),
+            (
+                "open_transcript",
+                &config.open_transcript,
+                &mut keymap.open_transcript,
+            ),
+            ("interrupt", &config.interrupt, &mut keymap.interrupt),
+            ("backtrack", &config.backtrack, &mut keymap.backtrack),
+            (
+                "external_editor",
+                &config.external_editor,
+                &mut keymap.external_editor,
+            ),
+            ("new_session", &config.new_session, &mut keymap.new_session),
+            (
+                "close_session",
+                &config.close_session,
+                &mut keymap.close_session,
+            ),
+            (
+                "next_session",
+                &config.next_session,
+                &mut keymap.next_session,
+            ),
+            (
+                "prev_session",
+                &config.prev_session,
+                &mut keymap.prev_session,
+            ),
+        ];
+        for (name, chords, bindings) in entries {
+            let Some(chords) = chords else {
+                continue;
+            };
+            match parse_chords(chords) {
+                Ok(parsed) => *bindings = parsed,
+                Err(err) => {
+                    tracing::warn!("ignoring tui.keymap.{name}: {err}");
+                }
+            }
+        }
+        keymap
+    }
+
+    pub(crate) fn bindings(&self, action: KeyAction) -> &[KeyBinding] {
+        match action {
+            KeyAction::Submit => &self.submit,
+            KeyAction::Newline => &self.newline,
+            KeyAction::HistoryUp => &self.history_up,
+            KeyAction::HistoryDown => &self.history_down,
+            KeyAction::OpenTranscript => &self.open_transcript,
+            KeyAction::Interrupt => &self.interrupt,
+            KeyAction::Backtrack => &self.backtrack,
+            KeyAction::ExternalEditor => &self.external_editor,
+            KeyAction::NewSession => &self.new_session,
+            KeyAction::CloseSession => &self.close_session,
+            KeyAction::NextSession => &self.next_session,
+            KeyAction::PrevSession => &self.prev_session,
+        }
+    }
+
+    /// Returns true when `event` is a press of any chord bound to `action`.
+    pub(crate) fn matches(&self, action: KeyAction, event: KeyEvent) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.is_press(event))
+    }
+
+    /// The chord shown in hints for `action`.
+    pub(crate) fn primary(&self, action: KeyAction) -> KeyBinding {
+        self.hint_for(action, |_| true)
+    }
+
+    /// The newline chord shown in hints. Terminals without enhanced key
+    /// reporting cannot tell modified Enter apart from plain Enter, so those
+    /// chords are skipped there.
+    pub(crate) fn newline_hint(&self, enhanced_keys_supported: bool) -> KeyBinding {
+        self.hint_for(KeyAction::Newline, |binding| {
+            enhanced_keys_supported
+                || binding.key() != KeyCode::Enter
+                || binding.modifiers() == KeyModifiers::NONE
+        })
+    }
+
+    fn hint_for(&self, action: KeyAction, usable: impl Fn(&KeyBinding) -> bool) -> KeyBinding {
+        let bindings = self.bindings(action);
+        bindings
+            .iter()
+            .copied()
+            .find(|binding| usable(binding))
+            .or_else(|| bindings.first().copied())
+            .unwrap_or(key_hint::plain(KeyCode::Null))
+    }
+}
+
+fn parse_chords(chords: &KeyChords) -> Result<Vec<KeyBinding>, String> {
+    let chords = chords.as_slice();
+    if chords.is_empty() {
+        return Err("no keys given".to_string());
+    }
+    chords
+        .iter()
+        .map(|chord| parse_key_binding(chord))
+        .collect()
+}
+
+/// Parses a chord such as `"ctrl+j"`, `"alt+enter"`, `"shift+tab"` or `"G"`.
+pub(crate) fn parse_key_binding(chord: &str) -> Result<KeyBinding, String> {
+    let mut parts: Vec<&str> = chord.split('+').map(str::trim).collect();
+    // "ctrl++" binds the plus key itself.
+    if chord.ends_with("++") {
+        parts.truncate(parts.len().saturating_sub(2));
+        parts.push("+");
+    }
+    let Some((key, modifier_names)) = parts.split_last() else {
+        return Err(format!("invalid key `{chord}`"));
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names {
+        modifiers |= match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "meta" | "option" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(format!("unknown modifier `{name}` in `{chord}`")),
+        };
+    }
+
+    let code = match key.to_ascii_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        lower => {
+            if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok())
+                && (1..=12).contains(&n)
+            {
+                KeyCode::F(n)
+            } else {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => char_code(c, &mut modifiers),
+                    _ => return Err(format!("unknown key `{key}` in `{chord}`")),
+                }
+            }
+        }
+    };
+    Ok(KeyBinding::new(code, modifiers))
+}
+
+/// Terminals report shifted letters as uppercase characters with SHIFT set,
+/// so `shift+g` and `G` both become `G` + SHIFT.
+fn char_code(c: char, modifiers: &mut KeyModifiers) -> KeyCode {
+    if c.is_ascii_uppercase() {
+        *modifiers |= KeyModifiers::SHIFT;
+        KeyCode::Char(c)
+    } else if c.is_ascii_lowercase() && modifiers.contains(KeyModifiers::SHIFT) {
+        KeyCode::Char(c.to_ascii_uppercase())
+    } else {
+        KeyCode::Char(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(
+            parse_key_binding("ctrl+j"),
+            Ok(key_hint::ctrl(KeyCode::Char('j')))
+        );
+        assert_eq!(
+            parse_key_binding("Alt + Enter"),
+            Ok(key_hint::alt(KeyCode::Enter))
+        );
+        assert_eq!(
+            parse_key_binding("shift+g"),
+            Ok(key_hint::shift(KeyCode::Char('G')))
+        );
+        assert_eq!(
+            parse_key_binding("G"),
+            Ok(key_hint::shift(KeyCode::Char('G')))
+        );
+        assert_eq!(
+            parse_key_binding("ctrl+shift+f5"),
+            Ok(KeyBinding::new(
+                KeyCode::F(5),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+        assert_eq!(
+            parse_key_binding("ctrl++"),
+            Ok(key_hint::ctrl(KeyCode::Char('+')))
+        );
+        assert!(parse_key_binding("hyper+k").is_err());
+        assert!(parse_key_binding("ctrl+nope").is_err());
+    }
+
+    #[test]
+    fn config_overrides_only_valid_actions() {
+        let keymap = Keymap::from_config(&TuiKeymap {
+            submit: Some(KeyChords::One("ctrl+enter".to_string())),
+            newline: Some(KeyChords::Many(vec![
+                "enter".to_string(),
+                "ctrl+j".to_string(),
+            ])),
+            open_transcript: Some(KeyChords::One("ctrl+bogus".to_string())),
+            ..Default::default()
+        });
+
+        assert!(keymap.matches(
+            KeyAction::Submit,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL)
+        ));
+        assert!(!keymap.matches(
+            KeyAction::Submit,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)
+        ));
+        assert!(keymap.matches(
+            KeyAction::Newline,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)
+        ));
+        assert_eq!(
+            keymap.primary(KeyAction::OpenTranscript),
+            key_hint::ctrl(KeyCode::Char('t'))
+        );
+    }
+
+    #[test]
+    fn newline_hint_skips_modified_enter_without_enhanced_keys() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.newline_hint(true), key_hint::shift(KeyCode::Enter));
+        assert_eq!(
+            keymap.newline_hint(false),
+            key_hint::ctrl(KeyCode::Char('j'))
+        );
+    }
+}
@@ -44,16 +44,20 @@ pub mod custom_terminal;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
+mod external_editor;
 mod file_search;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod inline_image;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
+mod mentions;
 pub mod onboarding;
 mod pager_overlay;
 pub mod public_widgets;
@@ -61,6 +65,7 @@ mod render;
 mod resume_picker;
 mod selection_list;
 mod session_log;
+mod session_tabs;
 mod shimmer;
 mod slash_command;
 mod status;
@@ -69,6 +74,7 @@ mod streaming;
 mod style;
 mod terminal_palette;
 mod text_formatting;
+mod theme;
 mod tui;
 mod ui_consts;
 pub mod update_action;
@@ -301,6 +307,7 @@ async fn run_ratatui_app(
     terminal.clear()?;
 
     let mut tui = Tui::new(terminal);
+    tui.set_notification_method(initial_config.tui_notification_method);
 
     #[cfg(not(debug_assertions))]
     {
@@ -329,6 +336,7 @@ async fn run_ratatui_app(
         initial_config.llmx_home.clone(),
         false,
         initial_config.cli_auth_credentials_store_mode,
+        initial_config.credential_helper.clone(),
     );
     let login_status = get_login_status(&initial_config);
     let should_show_trust_screen = should_show_trust_screen(&initial_config);
@@ -456,7 +464,12 @@ async fn run_ratatui_app(
         resume_picker::ResumeSelection::StartFresh
     };
 
-    let Cli { prompt, images, .. } = cli;
+    let Cli {
+        prompt,
+        images,
+        plan,
+        ..
+    } = cli;
 
     let app_result = App::run(
         &mut tui,
@@ -465,6 +478,7 @@ async fn run_ratatui_app(
         active_profile,
         prompt,
         images,
+        plan,
         resume_selection,
         feedback,
     )
@@ -500,7 +514,11 @@ fn get_login_status(config: &Config) -> LoginStatus {
         // Reading the OpenAI API key is an async operation because it may need
         // to refresh the token. Block on it.
         let llmx_home = config.llmx_home.clone();
-        match LlmxAuth::from_auth_storage(&llmx_home, config.cli_auth_credentials_store_mode) {
+        match LlmxAuth::from_auth_storage(
+            &llmx_home,
+            config.cli_auth_credentials_store_mode,
+            config.credential_helper.as_deref(),
+        ) {
             Ok(Some(auth)) => LoginStatus::AuthMode(auth.mode),
             Ok(None) => LoginStatus::NotAuthenticated,
             Err(err) => {
@@ -1,4 +1,5 @@
 use crate::render::line_utils::line_to_static;
+use crate::theme;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -206,7 +207,8 @@ where
             HeadingLevel::H4 => Style::new().italic(),
             HeadingLevel::H5 => Style::new().italic(),
             HeadingLevel::H6 => Style::new().italic(),
-        };
+        }
+        .patch(theme::current().header);
         let content = format!("{} ", "#".repeat(level as usize));
         self.push_line(Line::from(vec![Span::styled(content, heading_style)]));
         self.push_inline_style(heading_style);
@@ -384,7 +386,7 @@ where
     fn pop_link(&mut self) {
         if let Some(link) = self.link.take() {
             self.push_span(" (".into());
-            self.push_span(link.cyan().underlined());
+            self.push_span(Span::styled(link, theme::current().accent).underlined());
             self.push_span(")".into());
         }
     }
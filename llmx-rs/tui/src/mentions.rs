@@ -0,0 +1,538 @@
+//! `@` mentions that attach context rather than naming a file.
+//!
+//! Besides file paths (completed by the file search popup), the composer
+//! understands:
+//!
+//! - `@dir/` – a tree listing of the directory,
+//! - `@sym:Name` – the definition of a symbol, found through a local index,
+//! - `@git:REV` – the diff between `REV` and the working tree,
+//! - `@session:ID` – a summary of a recorded session.
+//!
+//! Mentions are resolved in the background while the message is composed so
+//! their token cost can be previewed, and are sent as
+//! [`UserInput::Mention`] items next to the message text.
+
+mod symbol_index;
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use ignore::WalkBuilder;
+use llmx_core::ExportEntry;
+use llmx_core::SessionExport;
+use llmx_core::find_conversation_path_by_id_str;
+use llmx_core::load_session_export;
+use llmx_protocol::user_input::UserInput;
+use tokio::process::Command;
+
+use crate::text_formatting::truncate_text;
+
+pub(crate) const GIT_PREFIX: &str = "git:";
+pub(crate) const SESSION_PREFIX: &str = "session:";
+pub(crate) const SYMBOL_PREFIX: &str = "sym:";
+
+/// Largest amount of text attached for one mention.
+const MAX_MENTION_BYTES: usize = 48 * 1024;
+const MAX_TREE_DEPTH: usize = 3;
+const MAX_TREE_ENTRIES: usize = 200;
+/// Most definitions attached for an ambiguous symbol.
+const MAX_SYMBOL_DEFINITIONS: usize = 3;
+/// Longest message quoted in a session summary, in graphemes.
+const MAX_SUMMARY_MESSAGE_GRAPHEMES: usize = 400;
+/// Rough size of a token, used for cost previews.
+const BYTES_PER_TOKEN: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MentionKind {
+    Directory(PathBuf),
+    Symbol(String),
+    GitDiff(String),
+    Session(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Mention {
+    /// The token as typed, including the `@`.
+    pub(crate) token: String,
+    pub(crate) kind: MentionKind,
+}
+
+/// Finds the context mentions in `text`, in order and without duplicates.
+/// Plain file mentions are left alone. With `complete_only`, a token still
+/// being typed at the end of `text` is ignored.
+pub(crate) fn parse_mentions(text: &str, cwd: &Path, complete_only: bool) -> Vec<Mention> {
+    let mut tokens: Vec<&str> = text.split_whitespace().collect();
+    if complete_only && !text.ends_with(char::is_whitespace) {
+        tokens.pop();
+    }
+    let mut mentions: Vec<Mention> = Vec::new();
+    for token in tokens {
+        let token = token.trim_end_matches(['.', ',', ';', '!', '?', ')', '"', '\'']);
+        let Some(body) = token.strip_prefix('@') else {
+            continue;
+        };
+        let Some(kind) = classify(body, cwd) else {
+            continue;
+        };
+        if mentions.iter().all(|mention| mention.token != token) {
+            mentions.push(Mention {
+                token: token.to_string(),
+                kind,
+            });
+        }
+    }
+    mentions
+}
+
+fn classify(body: &str, cwd: &Path) -> Option<MentionKind> {
+    if let Some(rev) = body.strip_prefix(GIT_PREFIX) {
+        // A leading `-` would be read by git as an option.
+        return (!rev.is_empty() && !rev.starts_with('-'))
+            .then(|| MentionKind::GitDiff(rev.to_string()));
+    }
+    if let Some(id) = body.strip_prefix(SESSION_PREFIX) {
+        return (!id.is_empty()).then(|| MentionKind::Session(id.to_string()));
+    }
+    // Symbols need the explicit prefix: a bare `@word` is as likely to be a
+    // handle or an ordinary word as a definition worth attaching.
+    if let Some(name) = body.strip_prefix(SYMBOL_PREFIX) {
+        let mut chars = name.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        return is_identifier.then(|| MentionKind::Symbol(name.to_string()));
+    }
+    if body.ends_with('/') {
+        let path = cwd.join(body);
+        return path.is_dir().then_some(MentionKind::Directory(path));
+    }
+    None
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ResolvedMention {
+    pub(crate) token: String,
+    /// Short description for the preview, e.g. "directory tree, 42 entries".
+    pub(crate) label: String,
+    pub(crate) text: String,
+}
+
+impl ResolvedMention {
+    pub(crate) fn approx_tokens(&self) -> usize {
+        self.text.len().div_ceil(BYTES_PER_TOKEN)
+    }
+
+    /// "~310 tokens" or "~1.2k tokens".
+    pub(crate) fn token_label(&self) -> String {
+        let tokens = self.approx_tokens();
+        if tokens >= 1_000 {
+            format!("~{:.1}k tokens", tokens as f64 / 1_000.0)
+        } else {
+            format!("~{tokens} tokens")
+        }
+    }
+
+    pub(crate) fn into_input(self) -> UserInput {
+        UserInput::Mention {
+            mention: self.token,
+            text: self.text,
+        }
+    }
+}
+
+/// Where mentions are resolved from.
+#[derive(Debug, Clone)]
+pub(crate) struct MentionContext {
+    pub(crate) cwd: PathBuf,
+    pub(crate) llmx_home: PathBuf,
+}
+
+pub(crate) async fn resolve(
+    mention: Mention,
+    ctx: MentionContext,
+) -> Result<ResolvedMention, String> {
+    let (label, text) = match mention.kind {
+        MentionKind::GitDiff(rev) => git_diff(&ctx.cwd, &rev).await?,
+        MentionKind::Session(id) => session_summary(&ctx.llmx_home, &id).await?,
+        MentionKind::Directory(dir) => {
+            tokio::task::spawn_blocking(move || directory_tree(&ctx.cwd, &dir))
+                .await
+                .map_err(|err| err.to_string())?
+        }
+        MentionKind::Symbol(name) => {
+            tokio::task::spawn_blocking(move || symbol_definitions(&ctx.cwd, &name))
+                .await
+                .map_err(|err| err.to_string())??
+        }
+    };
+    Ok(ResolvedMention {
+        token: mention.token,
+        label,
+        text: truncate_bytes(text),
+    })
+}
+
+fn directory_tree(cwd: &Path, dir: &Path) -> (String, String) {
+    let root = dir.strip_prefix(cwd).unwrap_or(dir);
+    let mut lines = vec![format!(
+        "{}/",
+        root.display().to_string().trim_end_matches('/')
+    )];
+    let mut total = 0;
+    let walker = WalkBuilder::new(dir)
+        .max_depth(Some(MAX_TREE_DEPTH))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for entry in walker.flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        total += 1;
+        if total > MAX_TREE_ENTRIES {
+            continue;
+        }
+        let is_dir = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir());
+        lines.push(format!(
+            "{}{}{}",
+            "  ".repeat(entry.depth()),
+            entry.file_name().to_string_lossy(),
+            if is_dir { "/" } else { "" }
+        ));
+    }
+    if total > MAX_TREE_ENTRIES {
+        lines.push(format!("… {} more entries", total - MAX_TREE_ENTRIES));
+    }
+    (format!("directory tree, {total} entries"), lines.join("\n"))
+}
+
+fn symbol_definitions(cwd: &Path, name: &str) -> Result<(String, String), String> {
+    let index = symbol_index::cached(cwd);
+    let definitions = index.lookup(name);
+    let sections: Vec<String> = definitions
+        .iter()
+        .take(MAX_SYMBOL_DEFINITIONS)
+        .filter_map(|definition| {
+            let snippet = symbol_index::snippet(cwd, definition).ok()?;
+            Some(format!(
+                "{}:{}\n{snippet}",
+                definition.path.display(),
+                definition.line + 1
+            ))
+        })
+        .collect();
+    let label = match definitions {
+        [] => return Err(format!("no definition of {name} found")),
+        [definition] => format!(
+            "definition in {}:{}",
+            definition.path.display(),
+            definition.line + 1
+        ),
+        _ => format!("{} definitions", definitions.len()),
+    };
+    Ok((label, sections.join("\n\n")))
+}
+
+async fn git_diff(cwd: &Path, rev: &str) -> Result<(String, String), String> {
+    let output = Command::new("git")
+        .args(["diff", rev, "--"])
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().next().unwrap_or("git diff failed");
+        return Err(message.trim().to_string());
+    }
+    let diff = String::from_utf8_lossy(&output.stdout).into_owned();
+    let files = diff
+        .lines()
+        .filter(|line| line.starts_with("diff --git "))
+        .count();
+    if files == 0 {
+        return Ok((
+            format!("no changes since {rev}"),
+            format!("No changes between {rev} and the working tree."),
+        ));
+    }
+    let noun = if files == 1 { "file" } else { "files" };
+    Ok((format!("diff against {rev}, {files} {noun}"), diff))
+}
+
+async fn session_summary(llmx_home: &Path, id: &str) -> Result<(String, String), String> {
+    let path = find_conversation_path_by_id_str(llmx_home, id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("no recorded session with id {id}"))?;
+    let export = load_session_export(&path)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(summarize_session(id, &export))
+}
+
+fn summarize_session(id: &str, export: &SessionExport) -> (String, String) {
+    let header = &export.header;
+    let mut details = Vec::new();
+    if let Some(started_at) = &header.started_at {
+        details.push(format!("started {started_at}"));
+    }
+    if let Some(model) = &header.model {
+        details.push(format!("model {model}"));
+    }
+    if let Some(cwd) = &header.cwd {
+        details.push(format!("cwd {}", cwd.display()));
+    }
+    let mut lines = vec![if details.is_empty() {
+        format!("Session {id}")
+    } else {
+        format!("Session {id} ({})", details.join(", "))
+    }];
+
+    let quote = |text: &str| {
+        truncate_text(
+            &text.split_whitespace().collect::<Vec<_>>().join(" "),
+            MAX_SUMMARY_MESSAGE_GRAPHEMES,
+        )
+    };
+    let mut prompts = 0;
+    for entry in &export.entries {
+        match entry {
+            ExportEntry::UserMessage { text } => {
+                prompts += 1;
+                lines.push(format!("User: {}", quote(text)));
+            }
+            ExportEntry::AgentMessage { text } => {
+                lines.push(format!("Assistant: {}", quote(text)));
+            }
+            ExportEntry::Reasoning { .. } => {}
+            ExportEntry::Command {
+                command, exit_code, ..
+            } => {
+                let status = match exit_code {
+                    Some(code) => format!("exit {code}"),
+                    None => "no exit code".to_string(),
+                };
+                lines.push(format!("Ran `{command}` ({status})"));
+            }
+            ExportEntry::Patch { diff, success } => {
+                let files: Vec<&str> = diff
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("+++ b/"))
+                    .collect();
+                let failed = if *success == Some(false) {
+                    " (failed)"
+                } else {
+                    ""
+                };
+                lines.push(format!("Patched {}{failed}", files.join(", ")));
+            }
+            ExportEntry::ToolCall { name, .. } => {
+                lines.push(format!("Called {name}"));
+            }
+        }
+    }
+    let short_id: String = id.chars().take(8).collect();
+    let noun = if prompts == 1 { "prompt" } else { "prompts" };
+    (
+        format!("summary of session {short_id}, {prompts} {noun}"),
+        lines.join("\n"),
+    )
+}
+
+/// Cuts `text` at a line boundary so it fits [`MAX_MENTION_BYTES`].
+fn truncate_bytes(text: String) -> String {
+    if text.len() <= MAX_MENTION_BYTES {
+        return text;
+    }
+    let mut cut = MAX_MENTION_BYTES;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let cut = text[..cut].rfind('\n').unwrap_or(cut);
+    format!("{}\n… truncated {} bytes", &text[..cut], text.len() - cut)
+}
+
+/// Resolution state of a mention in the message being composed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MentionState {
+    Resolving,
+    Ready(ResolvedMention),
+    Failed(String),
+}
+
+/// Resolved mentions for the message being composed, keyed by token.
+#[derive(Debug, Default)]
+pub(crate) struct MentionCache {
+    entries: Vec<(String, MentionState)>,
+}
+
+impl MentionCache {
+    pub(crate) fn get(&self, token: &str) -> Option<&MentionState> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == token)
+            .map(|(_, state)| state)
+    }
+
+    /// Marks `token` as resolving. Returns false when it is already known.
+    pub(crate) fn start(&mut self, token: &str) -> bool {
+        if self.get(token).is_some() {
+            return false;
+        }
+        self.entries
+            .push((token.to_string(), MentionState::Resolving));
+        true
+    }
+
+    /// Records a result. Results for tokens no longer tracked are dropped.
+    pub(crate) fn finish(&mut self, token: &str, result: Result<ResolvedMention, String>) {
+        if let Some((_, state)) = self.entries.iter_mut().find(|(entry, _)| entry == token) {
+            *state = match result {
+                Ok(resolved) => MentionState::Ready(resolved),
+                Err(err) => MentionState::Failed(err),
+            };
+        }
+    }
+
+    pub(crate) fn any_resolving(&self, mentions: &[Mention]) -> bool {
+        mentions.iter().any(|mention| {
+            matches!(
+                self.get(&mention.token),
+                None | Some(MentionState::Resolving)
+            )
+        })
+    }
+
+    /// Forgets tokens that are not in `mentions`.
+    pub(crate) fn retain(&mut self, mentions: &[Mention]) {
+        self.entries
+            .retain(|(token, _)| mentions.iter().any(|mention| &mention.token == token));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parses_context_mentions_and_skips_files() {
+        let dir = tempdir().expect("tempdir");
+        fs::create_dir(dir.path().join("src")).expect("mkdir");
+        fs::write(dir.path().join("README"), "readme").expect("write");
+
+        let mentions = parse_mentions(
+            "see @src/, @README, @Config and @sym:Config. @sym:1x @git:HEAD~2 @git:--output=x @session:abc @src/ @sym:Par",
+            dir.path(),
+            true,
+        );
+
+        assert_eq!(
+            mentions,
+            vec![
+                Mention {
+                    token: "@src/".to_string(),
+                    kind: MentionKind::Directory(dir.path().join("src/")),
+                },
+                Mention {
+                    token: "@sym:Config".to_string(),
+                    kind: MentionKind::Symbol("Config".to_string()),
+                },
+                Mention {
+                    token: "@git:HEAD~2".to_string(),
+                    kind: MentionKind::GitDiff("HEAD~2".to_string()),
+                },
+                Mention {
+                    token: "@session:abc".to_string(),
+                    kind: MentionKind::Session("abc".to_string()),
+                },
+            ]
+        );
+        assert_eq!(parse_mentions("@sym:Par", dir.path(), false).len(), 1);
+    }
+
+    #[test]
+    fn directory_tree_lists_nested_entries() {
+        let dir = tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("src/bin")).expect("mkdir");
+        fs::write(dir.path().join("src/lib.rs"), "").expect("write");
+        fs::write(dir.path().join("src/bin/main.rs"), "").expect("write");
+
+        let (label, text) = directory_tree(dir.path(), &dir.path().join("src/"));
+
+        assert_eq!(label, "directory tree, 3 entries");
+        assert_eq!(text, "src/\n  bin/\n    main.rs\n  lib.rs");
+    }
+
+    #[test]
+    fn summarizes_session_entries() {
+        let export = SessionExport {
+            header: llmx_core::ExportHeader {
+                model: Some("gpt-5".to_string()),
+                ..Default::default()
+            },
+            entries: vec![
+                ExportEntry::UserMessage {
+                    text: "fix the\nbuild".to_string(),
+                },
+                ExportEntry::Command {
+                    command: "cargo build".to_string(),
+                    exit_code: Some(0),
+                    output: String::new(),
+                    omitted_lines: 0,
+                },
+                ExportEntry::Patch {
+                    diff: "--- a/src/lib.rs\n+++ b/src/lib.rs\n".to_string(),
+                    success: Some(true),
+                },
+                ExportEntry::AgentMessage {
+                    text: "Fixed.".to_string(),
+                },
+            ],
+            token_usage: None,
+        };
+
+        let (label, text) = summarize_session("0199a213-81c0-7800-8aa1-bbab2a035a53", &export);
+
+        assert_eq!(label, "summary of session 0199a213, 1 prompt");
+        assert_eq!(
+            text,
+            "Session 0199a213-81c0-7800-8aa1-bbab2a035a53 (model gpt-5)\nUser: fix the build\nRan `cargo build` (exit 0)\nPatched src/lib.rs\nAssistant: Fixed."
+        );
+    }
+
+    #[test]
+    fn cache_tracks_resolution_state() {
+        let mention = Mention {
+            token: "@sym:Config".to_string(),
+            kind: MentionKind::Symbol("Config".to_string()),
+        };
+        let mut cache = MentionCache::default();
+        assert!(cache.any_resolving(std::slice::from_ref(&mention)));
+        assert!(cache.start("@sym:Config"));
+        assert!(!cache.start("@sym:Config"));
+        let resolved = ResolvedMention {
+            token: "@sym:Config".to_string(),
+            label: "definition in src/config.rs:10".to_string(),
+            text: "x".repeat(5_200),
+        };
+        cache.finish("@sym:Config", Ok(resolved.clone()));
+        assert!(!cache.any_resolving(std::slice::from_ref(&mention)));
+        assert_eq!(resolved.token_label(), "~1.3k tokens");
+
+        cache.retain(&[]);
+        cache.finish("@sym:Config", Err("late".to_string()));
+        assert_eq!(cache.get("@sym:Config"), None);
+    }
+}
@@ -0,0 +1,319 @@
+//! A small, regex-based index of top-level definitions used to resolve
+//! `@sym:Name` mentions. It trades precision for having no language-server
+//! dependency: a handful of patterns per language find `fn`, `struct`,
+//! `class`, `def`, `func` and similar definitions, and snippets are cut by
+//! brace balance or indentation.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use ignore::WalkBuilder;
+use regex_lite::Regex;
+
+/// How long an index is reused before the tree is scanned again.
+const INDEX_TTL: Duration = Duration::from_secs(60);
+/// Stop scanning after this many source files.
+const MAX_INDEXED_FILES: usize = 5_000;
+/// Skip source files larger than this (generated code, bundles).
+const MAX_INDEXED_FILE_BYTES: u64 = 512 * 1024;
+/// Longest snippet returned for one definition, in lines.
+const MAX_SNIPPET_LINES: usize = 80;
+/// Most doc-comment or attribute lines kept above a definition.
+const MAX_LEADING_LINES: usize = 20;
+
+struct Language {
+    extensions: &'static [&'static str],
+    /// Bodies are delimited by indentation rather than braces.
+    indented: bool,
+    patterns: &'static [&'static str],
+}
+
+const LANGUAGES: &[Language] = &[
+    Language {
+        extensions: &["rs"],
+        indented: false,
+        patterns: &[
+            r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|const|unsafe|extern(?:\s+"[^"]*")?)\s+)*(?:fn|struct|enum|trait|type|const|static|mod|union)\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)"#,
+            r"^\s*macro_rules!\s*(?P<name>[A-Za-z_][A-Za-z0-9_]*)",
+        ],
+    },
+    Language {
+        extensions: &["py"],
+        indented: true,
+        patterns: &[r"^\s*(?:async\s+)?(?:def|class)\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)"],
+    },
+    Language {
+        extensions: &["js", "jsx", "mjs", "cjs", "ts", "tsx"],
+        indented: false,
+        patterns: &[
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(?:function\*?|class|interface|type|enum)\s+(?P<name>[A-Za-z_$][A-Za-z0-9_$]*)",
+            r"^(?:export\s+)?(?:const|let|var)\s+(?P<name>[A-Za-z_$][A-Za-z0-9_$]*)",
+        ],
+    },
+    Language {
+        extensions: &["go"],
+        indented: false,
+        patterns: &[
+            r"^func\s+(?:\([^)]*\)\s*)?(?P<name>[A-Za-z_][A-Za-z0-9_]*)",
+            r"^type\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)",
+        ],
+    },
+];
+
+static COMPILED: LazyLock<Vec<Vec<Regex>>> = LazyLock::new(|| {
+    LANGUAGES
+        .iter()
+        .map(|language| {
+            language
+                .patterns
+                .iter()
+                .filter_map(|pattern| Regex::new(pattern).ok())
+                .collect()
+        })
+        .collect()
+});
+
+static CACHE: LazyLock<Mutex<HashMap<PathBuf, (Instant, Arc<SymbolIndex>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Where a symbol is defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Definition {
+    /// Relative to the indexed root.
+    pub(crate) path: PathBuf,
+    /// 0-based line of the definition keyword.
+    pub(crate) line: usize,
+    indented: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SymbolIndex {
+    definitions: HashMap<String, Vec<Definition>>,
+}
+
+impl SymbolIndex {
+    /// Scans `root`, honoring `.gitignore`, for definitions in the supported
+    /// languages.
+    pub(crate) fn build(root: &Path) -> Self {
+        let mut index = Self::default();
+        let mut files = 0;
+        for entry in WalkBuilder::new(root).build().flatten() {
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+            let path = entry.path();
+            let Some(language_idx) = language_for(path) else {
+                continue;
+            };
+            if entry
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() > MAX_INDEXED_FILE_BYTES)
+            {
+                continue;
+            }
+            let Ok(source) = fs::read_to_string(path) else {
+                continue;
+            };
+            let relative = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            index.add_file(&relative, language_idx, &source);
+            files += 1;
+            if files >= MAX_INDEXED_FILES {
+                break;
+            }
+        }
+        index
+    }
+
+    fn add_file(&mut self, path: &Path, language_idx: usize, source: &str) {
+        let patterns = &COMPILED[language_idx];
+        let indented = LANGUAGES[language_idx].indented;
+        for (line_idx, line) in source.lines().enumerate() {
+            let Some(name) = patterns
+                .iter()
+                .find_map(|pattern| pattern.captures(line)?.name("name"))
+            else {
+                continue;
+            };
+            self.definitions
+                .entry(name.as_str().to_string())
+                .or_default()
+                .push(Definition {
+                    path: path.to_path_buf(),
+                    line: line_idx,
+                    indented,
+                });
+        }
+    }
+
+    pub(crate) fn lookup(&self, name: &str) -> &[Definition] {
+        self.definitions.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// The index for `root`, rebuilt when older than [`INDEX_TTL`].
+pub(crate) fn cached(root: &Path) -> Arc<SymbolIndex> {
+    {
+        let cache = CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((built_at, index)) = cache.get(root)
+            && built_at.elapsed() < INDEX_TTL
+        {
+            return index.clone();
+        }
+    }
+    let index = Arc::new(SymbolIndex::build(root));
+    CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(root.to_path_buf(), (Instant::now(), index.clone()));
+    index
+}
+
+/// The source of `definition`, including doc comments and attributes right
+/// above it.
+pub(crate) fn snippet(root: &Path, definition: &Definition) -> io::Result<String> {
+    let source = fs::read_to_string(root.join(&definition.path))?;
+    let lines: Vec<&str> = source.lines().collect();
+    if definition.line >= lines.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "definition is past the end of the file",
+        ));
+    }
+    let start = leading_start(&lines, definition.line);
+    let end = if definition.indented {
+        indented_end(&lines, definition.line)
+    } else {
+        braced_end(&lines, definition.line)
+    };
+    let limit = definition.line + MAX_SNIPPET_LINES - 1;
+    let mut snippet = lines[start..=end.min(limit)].join("\n");
+    if end > limit {
+        snippet.push_str("\n…");
+    }
+    Ok(snippet)
+}
+
+fn language_for(path: &Path) -> Option<usize> {
+    let extension = path.extension()?.to_str()?;
+    LANGUAGES
+        .iter()
+        .position(|language| language.extensions.contains(&extension))
+}
+
+fn leading_start(lines: &[&str], line: usize) -> usize {
+    let mut start = line;
+    while start > 0 && line - start < MAX_LEADING_LINES {
+        let previous = lines[start - 1].trim_start();
+        let is_leading = ["//", "#", "@", "/*", "*"]
+            .iter()
+            .any(|prefix| previous.starts_with(prefix));
+        if !is_leading {
+            break;
+        }
+        start -= 1;
+    }
+    start
+}
+
+/// Last line of a brace-delimited definition. Definitions without a body
+/// (`type A = B;`) end at their `;` or at the next blank line.
+fn braced_end(lines: &[&str], line: usize) -> usize {
+    let mut depth = 0i32;
+    let mut opened = false;
+    for (idx, text) in lines.iter().enumerate().skip(line) {
+        for ch in text.chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return idx;
+        }
+        if !opened {
+            if text.trim_end().ends_with(';') {
+                return idx;
+            }
+            if idx > line && text.trim().is_empty() {
+                return idx - 1;
+            }
+        }
+    }
+    lines.len() - 1
+}
+
+/// Last line indented deeper than the definition line.
+fn indented_end(lines: &[&str], line: usize) -> usize {
+    let indent = |text: &str| text.len() - text.trim_start().len();
+    let base = indent(lines[line]);
+    let mut end = line;
+    for (idx, text) in lines.iter().enumerate().skip(line + 1) {
+        if text.trim().is_empty() {
+            continue;
+        }
+        if indent(text) <= base {
+            break;
+        }
+        end = idx;
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn finds_definitions_and_cuts_snippets() {
+        let dir = tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("lib.rs"),
+            "use std::fmt;\n\n/// A point.\n#[derive(Debug)]\npub struct Point {\n    x: i32,\n}\n\npub(crate) type Alias = Point;\n",
+        )
+        .expect("write rust");
+        fs::write(
+            dir.path().join("app.py"),
+            "import os\n\nclass Greeter:\n    def greet(self):\n        return 'hi'\n\nprint('done')\n",
+        )
+        .expect("write python");
+
+        let index = SymbolIndex::build(dir.path());
+
+        let point = index.lookup("Point");
+        assert_eq!(point.len(), 1);
+        assert_eq!(
+            snippet(dir.path(), &point[0]).expect("snippet"),
+            "/// A point.\n#[derive(Debug)]\npub struct Point {\n    x: i32,\n}"
+        );
+        let alias = &index.lookup("Alias")[0];
+        assert_eq!(
+            snippet(dir.path(), alias).expect("snippet"),
+            "pub(crate) type Alias = Point;"
+        );
+        let greeter = &index.lookup("Greeter")[0];
+        assert_eq!(greeter.path, PathBuf::from("app.py"));
+        assert_eq!(
+            snippet(dir.path(), greeter).expect("snippet"),
+            "class Greeter:\n    def greet(self):\n        return 'hi'"
+        );
+        assert!(index.lookup("Missing").is_empty());
+    }
+}
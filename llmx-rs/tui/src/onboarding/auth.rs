@@ -151,6 +151,7 @@ pub(crate) struct AuthModeWidget {
     pub sign_in_state: Arc<RwLock<SignInState>>,
     pub llmx_home: PathBuf,
     pub cli_auth_credentials_store_mode: AuthCredentialsStoreMode,
+    pub credential_helper: Option<Vec<String>>,
     pub login_status: LoginStatus,
     pub auth_manager: Arc<AuthManager>,
     pub forced_chatgpt_workspace_id: Option<String>,
@@ -521,6 +522,7 @@ impl AuthModeWidget {
             &self.llmx_home,
             &api_key,
             self.cli_auth_credentials_store_mode,
+            self.credential_helper.as_deref(),
         ) {
             Ok(()) => {
                 self.error = None;
@@ -563,6 +565,7 @@ impl AuthModeWidget {
             CLIENT_ID.to_string(),
             self.forced_chatgpt_workspace_id.clone(),
             self.cli_auth_credentials_store_mode,
+            self.credential_helper.clone(),
         );
         match run_login_server(opts) {
             Ok(child) => {
@@ -662,11 +665,13 @@ mod tests {
             sign_in_state: Arc::new(RwLock::new(SignInState::PickMode)),
             llmx_home: llmx_home_path.clone(),
             cli_auth_credentials_store_mode: AuthCredentialsStoreMode::File,
+            credential_helper: None,
             login_status: LoginStatus::NotAuthenticated,
             auth_manager: AuthManager::shared(
                 llmx_home_path,
                 false,
                 AuthCredentialsStoreMode::File,
+                None,
             ),
             forced_chatgpt_workspace_id: None,
             forced_login_method: Some(ForcedLoginMethod::Chatgpt),
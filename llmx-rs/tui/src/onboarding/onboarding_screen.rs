@@ -90,6 +90,7 @@ impl OnboardingScreen {
         let forced_login_method = config.forced_login_method;
         let llmx_home = config.llmx_home;
         let cli_auth_credentials_store_mode = config.cli_auth_credentials_store_mode;
+        let credential_helper = config.credential_helper;
         let mut steps: Vec<Step> = Vec::new();
         if show_windows_wsl_screen {
             steps.push(Step::Windows(WindowsSetupWidget::new(llmx_home.clone())));
@@ -110,6 +111,7 @@ impl OnboardingScreen {
                 sign_in_state: Arc::new(RwLock::new(SignInState::PickMode)),
                 llmx_home: llmx_home.clone(),
                 cli_auth_credentials_store_mode,
+                credential_helper,
                 login_status,
                 auth_manager,
                 forced_chatgpt_workspace_id,
@@ -2,10 +2,16 @@ use std::io::Result;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::clipboard_paste;
+use crate::exec_cell::ExecCell;
+use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::PatchHistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
@@ -14,6 +20,8 @@ use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -34,8 +42,10 @@ pub(crate) enum Overlay {
 }
 
 impl Overlay {
-    pub(crate) fn new_transcript(cells: Vec<Arc<dyn HistoryCell>>) -> Self {
-        Self::Transcript(TranscriptOverlay::new(cells))
+    pub(crate) fn new_transcript(cells: Vec<Arc<dyn HistoryCell>>, keymap: &Keymap) -> Self {
+        let mut overlay = TranscriptOverlay::new(cells);
+        overlay.set_keymap(keymap);
+        Self::Transcript(overlay)
     }
 
     pub(crate) fn new_static_with_lines(lines: Vec<Line<'static>>, title: String) -> Self {
@@ -62,6 +72,15 @@ impl Overlay {
             Overlay::Static(o) => o.is_done(),
         }
     }
+
+    /// True while the overlay is reading text input (e.g. a search query),
+    /// so global shortcuts like Esc/Enter must be forwarded untouched.
+    pub(crate) fn is_capturing_input(&self) -> bool {
+        match self {
+            Overlay::Transcript(o) => o.is_capturing_input(),
+            Overlay::Static(_) => false,
+        }
+    }
 }
 
 const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
@@ -76,6 +95,17 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('n'));
+const KEY_F: KeyBinding = key_hint::plain(KeyCode::Char('f'));
+const KEY_T: KeyBinding = key_hint::plain(KeyCode::Char('t'));
+const KEY_LEFT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_RIGHT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_Y: KeyBinding = key_hint::plain(KeyCode::Char('y'));
+
+const TRANSCRIPT_TITLE: &str = "T R A N S C R I P T";
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -84,6 +114,15 @@ const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
     (&[KEY_HOME, KEY_END], "to jump"),
 ];
 
+// Transcript search/filter hints rendered on the third line
+const TRANSCRIPT_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_SLASH], "to search"),
+    (&[KEY_F], "to filter"),
+    (&[KEY_LEFT_BRACKET, KEY_RIGHT_BRACKET], "to step turns"),
+    (&[KEY_T], "to go to turn"),
+    (&[KEY_Y], "to copy"),
+];
+
 // Render a single line of key hints from (key(s), description) pairs.
 fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
@@ -356,12 +395,17 @@ impl Renderable for CachedRenderable {
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    /// Active search query; matching text is highlighted.
+    query: Option<String>,
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.cell.transcript_lines(area.width);
+        if let Some(query) = &self.query {
+            lines = highlight_matches(lines, query);
+        }
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
@@ -370,67 +414,263 @@ impl Renderable for CellRenderable {
     }
 }
 
+/// Returns the byte ranges of ASCII case-insensitive occurrences of `needle`.
+fn find_matches(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    if needle.is_empty() {
+        return ranges;
+    }
+    let mut start = 0;
+    while start < haystack.len() {
+        let end = start + needle.len();
+        if haystack
+            .get(start..end)
+            .is_some_and(|window| window.eq_ignore_ascii_case(needle))
+        {
+            ranges.push((start, end));
+            start = end;
+            continue;
+        }
+        start += haystack[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    ranges
+}
+
+/// Splits spans so that occurrences of `query` render reversed.
+fn highlight_matches(lines: Vec<Line<'static>>, query: &str) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let Line {
+                style,
+                alignment,
+                spans: original,
+            } = line;
+            let mut spans: Vec<Span<'static>> = Vec::with_capacity(original.len());
+            for span in original {
+                let ranges = find_matches(&span.content, query);
+                if ranges.is_empty() {
+                    spans.push(span);
+                    continue;
+                }
+                let mut last = 0;
+                for (start, end) in ranges {
+                    if start > last {
+                        spans.push(Span::styled(
+                            span.content[last..start].to_string(),
+                            span.style,
+                        ));
+                    }
+                    spans.push(Span::styled(
+                        span.content[start..end].to_string(),
+                        span.style.reversed(),
+                    ));
+                    last = end;
+                }
+                if last < span.content.len() {
+                    spans.push(Span::styled(span.content[last..].to_string(), span.style));
+                }
+            }
+            Line {
+                style,
+                alignment,
+                spans,
+            }
+        })
+        .collect()
+}
+
+/// Raw text of a cell as shown in the transcript, used for search and copy.
+fn cell_text(cell: &dyn HistoryCell) -> String {
+    cell.transcript_lines(u16::MAX)
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Restricts the transcript to one kind of history cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum TranscriptFilter {
+    #[default]
+    All,
+    Commands,
+    Diffs,
+    AgentMessages,
+}
+
+impl TranscriptFilter {
+    fn next(self) -> Self {
+        match self {
+            TranscriptFilter::All => TranscriptFilter::Commands,
+            TranscriptFilter::Commands => TranscriptFilter::Diffs,
+            TranscriptFilter::Diffs => TranscriptFilter::AgentMessages,
+            TranscriptFilter::AgentMessages => TranscriptFilter::All,
+        }
+    }
+
+    fn label(self) -> Option<&'static str> {
+        match self {
+            TranscriptFilter::All => None,
+            TranscriptFilter::Commands => Some("commands"),
+            TranscriptFilter::Diffs => Some("diffs"),
+            TranscriptFilter::AgentMessages => Some("agent messages"),
+        }
+    }
+
+    fn includes(self, cell: &dyn HistoryCell) -> bool {
+        let any = cell.as_any();
+        match self {
+            TranscriptFilter::All => true,
+            TranscriptFilter::Commands => any.is::<ExecCell>(),
+            TranscriptFilter::Diffs => any.is::<PatchHistoryCell>(),
+            TranscriptFilter::AgentMessages => any.is::<AgentMessageCell>(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PromptKind {
+    Search,
+    Turn,
+}
+
+/// Single-line input shown in the footer while typing a search or turn number.
+struct Prompt {
+    kind: PromptKind,
+    input: String,
+    /// Query to restore if a search prompt is cancelled.
+    previous_query: String,
+}
+
+/// Returns the typed character for unmodified (or shifted) key presses.
+fn char_press(event: KeyEvent) -> Option<char> {
+    match event {
+        KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers,
+            kind: KeyEventKind::Press | KeyEventKind::Repeat,
+            ..
+        } if modifiers.difference(KeyModifiers::SHIFT).is_empty() => Some(c),
+        _ => None,
+    }
+}
+
 pub(crate) struct TranscriptOverlay {
     view: PagerView,
     cells: Vec<Arc<dyn HistoryCell>>,
     highlight_cell: Option<usize>,
+    filter: TranscriptFilter,
+    /// Indices into `cells` that pass the filter, in render order.
+    visible: Vec<usize>,
+    query: String,
+    prompt: Option<Prompt>,
+    selected_cell: Option<usize>,
+    status: Option<String>,
+    /// Configured `open_transcript` key; pressing it again closes the overlay.
+    close_key: KeyBinding,
+    /// Configured `backtrack` key, shown in the hints.
+    backtrack_key: KeyBinding,
     is_done: bool,
 }
 
 impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
-        Self {
-            view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
-                "T R A N S C R I P T".to_string(),
-                usize::MAX,
-            ),
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), TRANSCRIPT_TITLE.to_string(), usize::MAX),
             cells: transcript_cells,
             highlight_cell: None,
+            filter: TranscriptFilter::All,
+            visible: Vec::new(),
+            query: String::new(),
+            prompt: None,
+            selected_cell: None,
+            status: None,
+            close_key: KEY_CTRL_T,
+            backtrack_key: KEY_ESC,
             is_done: false,
-        }
+        };
+        overlay.rebuild();
+        overlay
+    }
+
+    pub(crate) fn set_keymap(&mut self, keymap: &Keymap) {
+        self.close_key = keymap.primary(KeyAction::OpenTranscript);
+        self.backtrack_key = keymap.primary(KeyAction::Backtrack);
     }
 
-    fn render_cells(
-        cells: &[Arc<dyn HistoryCell>],
-        highlight_cell: Option<usize>,
-    ) -> Vec<Box<dyn Renderable>> {
-        cells
+    fn render_cells(&self) -> Vec<Box<dyn Renderable>> {
+        let query = (!self.query.is_empty()).then(|| self.query.clone());
+        self.visible
             .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let mut v: Vec<Box<dyn Renderable>> = Vec::new();
-                let mut cell_renderable = if c.as_any().is::<UserHistoryCell>() {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: if highlight_cell == Some(i) {
-                            user_message_style().reversed()
-                        } else {
-                            user_message_style()
-                        },
-                    })) as Box<dyn Renderable>
+            .map(|(pos, &i)| {
+                let c = &self.cells[i];
+                let style = if c.as_any().is::<UserHistoryCell>() {
+                    if self.highlight_cell == Some(i) || self.selected_cell == Some(i) {
+                        user_message_style().reversed()
+                    } else {
+                        user_message_style()
+                    }
+                } else if self.selected_cell == Some(i) {
+                    Style::default().bold()
                 } else {
-                    Box::new(CachedRenderable::new(CellRenderable {
-                        cell: c.clone(),
-                        style: Style::default(),
-                    })) as Box<dyn Renderable>
+                    Style::default()
                 };
-                if !c.is_stream_continuation() && i > 0 {
+                let mut cell_renderable = Box::new(CachedRenderable::new(CellRenderable {
+                    cell: c.clone(),
+                    style,
+                    query: query.clone(),
+                })) as Box<dyn Renderable>;
+                if !c.is_stream_continuation() && pos > 0 {
                     cell_renderable = Box::new(InsetRenderable::new(
                         cell_renderable,
                         Insets::tlbr(1, 0, 0, 0),
                     ));
                 }
-                v.push(cell_renderable);
-                v
+                cell_renderable
             })
             .collect()
     }
 
+    /// Recomputes the visible cells and their renderables after a state change.
+    fn rebuild(&mut self) {
+        let filter = self.filter;
+        self.visible = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| filter.includes(c.as_ref()))
+            .map(|(i, _)| i)
+            .collect();
+        if self
+            .selected_cell
+            .is_some_and(|idx| !self.visible.contains(&idx))
+        {
+            self.selected_cell = None;
+        }
+        self.view.title = match filter.label() {
+            Some(label) => format!("{TRANSCRIPT_TITLE} · {label}"),
+            None => TRANSCRIPT_TITLE.to_string(),
+        };
+        self.view.renderables = self.render_cells();
+    }
+
+    fn scroll_cell_into_view(&mut self, cell: usize) {
+        if let Some(pos) = self.visible.iter().position(|&i| i == cell) {
+            self.view.scroll_chunk_into_view(pos);
+        }
+    }
+
     pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
         let follow_bottom = self.view.is_scrolled_to_bottom();
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.rebuild();
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
         }
@@ -438,23 +678,285 @@ impl TranscriptOverlay {
 
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
         self.highlight_cell = cell;
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        if cell.is_some() {
+            // Backtracking targets user messages, which filters would hide.
+            self.filter = TranscriptFilter::All;
+        }
+        self.rebuild();
         if let Some(idx) = self.highlight_cell {
-            self.view.scroll_chunk_into_view(idx);
+            self.scroll_cell_into_view(idx);
         }
     }
 
+    /// True while the footer prompt is consuming keystrokes.
+    pub(crate) fn is_capturing_input(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    fn select_cell(&mut self, cell: usize) {
+        self.selected_cell = Some(cell);
+        self.rebuild();
+        self.scroll_cell_into_view(cell);
+    }
+
+    fn set_filter(&mut self, filter: TranscriptFilter) {
+        self.filter = filter;
+        self.rebuild();
+        match self.selected_cell {
+            Some(cell) => self.scroll_cell_into_view(cell),
+            None => self.view.scroll_offset = usize::MAX,
+        }
+    }
+
+    /// Visible cells whose text contains the current query.
+    fn matching_cells(&self) -> Vec<usize> {
+        if self.query.is_empty() {
+            return Vec::new();
+        }
+        self.visible
+            .iter()
+            .copied()
+            .filter(|&i| !find_matches(&cell_text(self.cells[i].as_ref()), &self.query).is_empty())
+            .collect()
+    }
+
+    /// Selects the next (or previous) match relative to the selected cell,
+    /// wrapping around the transcript. `inclusive` lets the selected cell
+    /// itself match, which keeps incremental search stable while typing.
+    fn find_match(&mut self, forward: bool, inclusive: bool) {
+        let matches = self.matching_cells();
+        if matches.is_empty() {
+            if !self.query.is_empty() {
+                self.status = Some(format!("No matches for \"{}\"", self.query));
+            }
+            self.rebuild();
+            return;
+        }
+        let target = match (self.selected_cell, forward) {
+            (Some(current), true) => matches
+                .iter()
+                .copied()
+                .find(|&i| i > current || (inclusive && i == current))
+                .unwrap_or(matches[0]),
+            (Some(current), false) => matches
+                .iter()
+                .rev()
+                .copied()
+                .find(|&i| i < current || (inclusive && i == current))
+                .unwrap_or(matches[matches.len() - 1]),
+            (None, true) => matches[0],
+            (None, false) => matches[matches.len() - 1],
+        };
+        let position = matches.iter().position(|&i| i == target).unwrap_or(0) + 1;
+        self.status = Some(format!("Match {position} of {}", matches.len()));
+        self.select_cell(target);
+    }
+
+    /// Moves the selection by one visible cell.
+    fn move_selection(&mut self, forward: bool) {
+        let Some(&last) = self.visible.last() else {
+            return;
+        };
+        let target = match self.selected_cell {
+            Some(current) if forward => self.visible.iter().copied().find(|&i| i > current),
+            Some(current) => self.visible.iter().rev().copied().find(|&i| i < current),
+            None if forward => self.visible.first().copied(),
+            None => Some(last),
+        };
+        if let Some(target) = target {
+            self.select_cell(target);
+        }
+    }
+
+    /// Cell indices where each turn starts (one per user message).
+    fn turn_starts(&self) -> Vec<usize> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.as_any().is::<UserHistoryCell>())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Selects the first visible cell of the 1-based `turn`.
+    fn jump_to_turn(&mut self, turn: usize) {
+        let starts = self.turn_starts();
+        if turn == 0 || turn > starts.len() {
+            self.status = Some(format!("No turn {turn} (1-{})", starts.len()));
+            return;
+        }
+        match self.turn_entry(&starts, turn) {
+            Some(cell) => {
+                self.status = Some(format!("Turn {turn} of {}", starts.len()));
+                self.select_cell(cell);
+            }
+            None => {
+                self.status = Some(format!("Turn {turn} has no cells matching the filter"));
+            }
+        }
+    }
+
+    /// First visible cell of the 1-based `turn`, given the turn start indices.
+    fn turn_entry(&self, starts: &[usize], turn: usize) -> Option<usize> {
+        let start = *turn.checked_sub(1).and_then(|i| starts.get(i))?;
+        let end = starts.get(turn).copied().unwrap_or(self.cells.len());
+        self.visible
+            .iter()
+            .copied()
+            .find(|&i| i >= start && i < end)
+    }
+
+    /// Jumps to the start of the next or previous turn.
+    fn step_turn(&mut self, forward: bool) {
+        let starts = self.turn_starts();
+        if starts.is_empty() {
+            self.status = Some("No turns yet".to_string());
+            return;
+        }
+        let target = match self.selected_cell {
+            None if forward => 1,
+            None => starts.len(),
+            Some(cell) => {
+                // 1-based turn containing the selection (0 before the first turn).
+                let turn = starts.iter().filter(|&&s| s <= cell).count();
+                if forward {
+                    (turn + 1).min(starts.len())
+                } else if turn > 0 && self.turn_entry(&starts, turn) != Some(cell) {
+                    turn
+                } else {
+                    turn.saturating_sub(1).max(1)
+                }
+            }
+        };
+        self.jump_to_turn(target);
+    }
+
+    fn yank_selected(&mut self) {
+        let Some(cell) = self.selected_cell.and_then(|i| self.cells.get(i)) else {
+            self.status = Some("Select a cell to copy (n/N, [/] or j/k)".to_string());
+            return;
+        };
+        let text = cell_text(cell.as_ref());
+        self.status = Some(match clipboard_paste::copy_text_to_clipboard(&text) {
+            Ok(()) => format!("Copied {} line(s) to the clipboard", text.lines().count()),
+            Err(err) => format!("Copy failed: {err}"),
+        });
+    }
+
+    fn open_prompt(&mut self, kind: PromptKind) {
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+            previous_query: self.query.clone(),
+        });
+    }
+
+    fn handle_prompt_key(&mut self, key_event: KeyEvent) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        match key_event {
+            e if KEY_ENTER.is_press(e) => {
+                let Some(prompt) = self.prompt.take() else {
+                    return;
+                };
+                if prompt.kind == PromptKind::Turn {
+                    match prompt.input.trim().parse::<usize>() {
+                        Ok(turn) => self.jump_to_turn(turn),
+                        Err(_) => self.status = Some("Enter a turn number".to_string()),
+                    }
+                }
+            }
+            e if KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                let Some(prompt) = self.prompt.take() else {
+                    return;
+                };
+                if prompt.kind == PromptKind::Search {
+                    self.query = prompt.previous_query;
+                    self.rebuild();
+                }
+            }
+            e if KEY_BACKSPACE.is_press(e) => {
+                if prompt.input.pop().is_none() {
+                    self.prompt = None;
+                } else if prompt.kind == PromptKind::Search {
+                    self.query = prompt.input.clone();
+                    self.find_match(true, true);
+                }
+            }
+            e => {
+                let Some(c) = char_press(e) else {
+                    return;
+                };
+                if prompt.kind == PromptKind::Turn && !c.is_ascii_digit() {
+                    return;
+                }
+                prompt.input.push(c);
+                if prompt.kind == PromptKind::Search {
+                    self.query = prompt.input.clone();
+                    self.find_match(true, true);
+                }
+            }
+        }
+    }
+
+    /// Handles transcript-specific keys; returns false for keys the pager should scroll with.
+    fn handle_command_key(&mut self, key_event: KeyEvent) -> bool {
+        let Some(c) = char_press(key_event) else {
+            return false;
+        };
+        match c {
+            '/' => self.open_prompt(PromptKind::Search),
+            't' => self.open_prompt(PromptKind::Turn),
+            'n' => self.find_match(true, false),
+            'N' => self.find_match(false, false),
+            'f' => self.set_filter(self.filter.next()),
+            ']' => self.step_turn(true),
+            '[' => self.step_turn(false),
+            'j' => self.move_selection(true),
+            'k' => self.move_selection(false),
+            'y' => self.yank_selected(),
+            _ => return false,
+        }
+        true
+    }
+
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        let line3 = Rect::new(area.x, area.y.saturating_add(2), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
 
-        let mut pairs: Vec<(&[KeyBinding], &str)> =
-            vec![(&[KEY_Q], "to quit"), (&[KEY_ESC], "to edit prev")];
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_Q], "to quit"),
+            (std::slice::from_ref(&self.backtrack_key), "to edit prev"),
+        ];
         if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ENTER], "to edit message"));
         }
         render_key_hints(line2, buf, &pairs);
+
+        if let Some(prompt) = &self.prompt {
+            let label = match prompt.kind {
+                PromptKind::Search => " /",
+                PromptKind::Turn => " turn: ",
+            };
+            Paragraph::new(Line::from(vec![
+                label.into(),
+                prompt.input.clone().into(),
+                " ".reversed(),
+            ]))
+            .render_ref(line3, buf);
+        } else if let Some(status) = &self.status {
+            Paragraph::new(Line::from(format!(" {status}")).dim()).render_ref(line3, buf);
+        } else if self.query.is_empty() {
+            render_key_hints(line3, buf, TRANSCRIPT_KEY_HINTS);
+        } else {
+            let mut pairs: Vec<(&[KeyBinding], &str)> =
+                vec![(&[KEY_N, KEY_SHIFT_N], "for next/prev match")];
+            pairs.extend_from_slice(TRANSCRIPT_KEY_HINTS);
+            render_key_hints(line3, buf, &pairs);
+        }
     }
 
     pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
@@ -469,13 +971,31 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
-                    self.is_done = true;
-                    Ok(())
+            TuiEvent::Key(key_event) => {
+                if self.prompt.is_some() {
+                    self.handle_prompt_key(key_event);
+                    tui.frame_requester().schedule_frame();
+                    return Ok(());
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                match key_event {
+                    e if KEY_Q.is_press(e)
+                        || KEY_CTRL_C.is_press(e)
+                        || self.close_key.is_press(e) =>
+                    {
+                        self.is_done = true;
+                        Ok(())
+                    }
+                    other => {
+                        self.status = None;
+                        if self.handle_command_key(other) {
+                            tui.frame_requester().schedule_frame();
+                            Ok(())
+                        } else {
+                            self.view.handle_key_event(tui, other)
+                        }
+                    }
+                }
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -592,6 +1112,7 @@ mod tests {
     use crate::history_cell::new_patch_event;
     use llmx_core::protocol::FileChange;
     use llmx_protocol::parse_command::ParsedCommand;
+    use pretty_assertions::assert_eq;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
     use ratatui::text::Text;
@@ -796,6 +1317,161 @@ mod tests {
         assert_eq!(overlay.view.scroll_offset, 0);
     }
 
+    fn text_cell(text: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(TestCell {
+            lines: vec![Line::from(text.to_string())],
+        })
+    }
+
+    fn user_cell(text: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(UserHistoryCell {
+            message: text.to_string(),
+            images: Vec::new(),
+        })
+    }
+
+    fn type_keys(overlay: &mut TranscriptOverlay, keys: &str) {
+        for c in keys.chars() {
+            overlay.handle_prompt_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn find_matches_ignores_ascii_case() {
+        assert_eq!(find_matches("Alpha ALPHA", "alpha"), vec![(0, 5), (6, 11)]);
+        assert_eq!(find_matches("héllo hé", "hé"), vec![(0, 3), (7, 10)]);
+        assert_eq!(find_matches("abc", ""), Vec::<(usize, usize)>::new());
+    }
+
+    #[test]
+    fn highlight_matches_splits_spans() {
+        let lines = highlight_matches(vec![Line::from("say hello twice: HELLO")], "hello");
+        let contents: Vec<&str> = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(contents, vec!["say ", "hello", " twice: ", "HELLO"]);
+        assert_eq!(lines[0].spans[1].style, Style::default().reversed());
+        assert_eq!(lines[0].spans[2].style, Style::default());
+    }
+
+    #[test]
+    fn incremental_search_selects_and_cycles_matches() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            text_cell("alpha"),
+            text_cell("beta"),
+            text_cell("gamma alpha"),
+        ]);
+
+        overlay.open_prompt(PromptKind::Search);
+        assert!(overlay.is_capturing_input());
+        type_keys(&mut overlay, "alp");
+        assert_eq!(overlay.query, "alp");
+        assert_eq!(overlay.selected_cell, Some(0));
+
+        overlay.handle_prompt_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!overlay.is_capturing_input());
+
+        overlay.find_match(true, false);
+        assert_eq!(overlay.selected_cell, Some(2));
+        overlay.find_match(true, false);
+        assert_eq!(overlay.selected_cell, Some(0));
+        overlay.find_match(false, false);
+        assert_eq!(overlay.selected_cell, Some(2));
+        assert_eq!(overlay.status.as_deref(), Some("Match 2 of 2"));
+    }
+
+    #[test]
+    fn cancelled_search_restores_previous_query() {
+        let mut overlay = TranscriptOverlay::new(vec![text_cell("alpha"), text_cell("beta")]);
+        overlay.open_prompt(PromptKind::Search);
+        type_keys(&mut overlay, "beta");
+        overlay.handle_prompt_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        overlay.open_prompt(PromptKind::Search);
+        type_keys(&mut overlay, "zzz");
+        assert_eq!(overlay.status.as_deref(), Some("No matches for \"zzz\""));
+        overlay.handle_prompt_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+        assert!(!overlay.is_capturing_input());
+        assert_eq!(overlay.query, "beta");
+    }
+
+    #[test]
+    fn filter_limits_visible_cells() {
+        let cwd = PathBuf::from("/repo");
+        let mut changes = HashMap::new();
+        changes.insert(
+            PathBuf::from("foo.txt"),
+            FileChange::Add {
+                content: "hello\n".to_string(),
+            },
+        );
+        let mut overlay = TranscriptOverlay::new(vec![
+            user_cell("hi"),
+            Arc::new(history_cell::AgentMessageCell::new(
+                vec![Line::from("hello")],
+                true,
+            )),
+            Arc::new(new_patch_event(changes, &cwd)),
+            text_cell("other"),
+        ]);
+        assert_eq!(overlay.visible, vec![0, 1, 2, 3]);
+
+        overlay.set_filter(TranscriptFilter::Diffs);
+        assert_eq!(overlay.visible, vec![2]);
+        assert_eq!(overlay.view.title, "T R A N S C R I P T · diffs");
+
+        overlay.set_filter(TranscriptFilter::AgentMessages);
+        assert_eq!(overlay.visible, vec![1]);
+
+        overlay.set_filter(TranscriptFilter::Commands);
+        assert_eq!(overlay.visible, Vec::<usize>::new());
+
+        // Backtracking highlights a user message, so it resets the filter.
+        overlay.set_highlight_cell(Some(0));
+        assert_eq!(overlay.filter, TranscriptFilter::All);
+        assert_eq!(overlay.visible.len(), 4);
+    }
+
+    #[test]
+    fn turn_navigation_selects_user_messages() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            user_cell("first"),
+            text_cell("answer one"),
+            user_cell("second"),
+            text_cell("answer two"),
+        ]);
+
+        overlay.step_turn(true);
+        assert_eq!(overlay.selected_cell, Some(0));
+        overlay.step_turn(true);
+        assert_eq!(overlay.selected_cell, Some(2));
+        overlay.move_selection(true);
+        assert_eq!(overlay.selected_cell, Some(3));
+        overlay.step_turn(false);
+        assert_eq!(overlay.selected_cell, Some(2));
+        overlay.step_turn(false);
+        assert_eq!(overlay.selected_cell, Some(0));
+
+        overlay.open_prompt(PromptKind::Turn);
+        type_keys(&mut overlay, "2x");
+        overlay.handle_prompt_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(overlay.selected_cell, Some(2));
+
+        overlay.jump_to_turn(5);
+        assert_eq!(overlay.status.as_deref(), Some("No turn 5 (1-2)"));
+        assert_eq!(overlay.selected_cell, Some(2));
+    }
+
+    #[test]
+    fn cell_text_joins_transcript_lines() {
+        let cell = TestCell {
+            lines: vec![
+                Line::from(vec!["$ ".into(), "ls".into()]),
+                Line::from("src"),
+            ],
+        };
+        assert_eq!(cell_text(&cell), "$ ls\nsrc");
+    }
+
     #[test]
     fn static_overlay_snapshot_basic() {
         // Prepare a static overlay with a few lines and a title
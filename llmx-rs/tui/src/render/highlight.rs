@@ -1,5 +1,6 @@
+use crate::theme;
+use crate::theme::SyntaxStyles;
 use ratatui::style::Style;
-use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use std::sync::OnceLock;
@@ -49,10 +50,15 @@ impl BashHighlight {
         }
     }
 
-    fn style(self) -> Style {
+    fn style(self, syntax: &SyntaxStyles) -> Style {
         match self {
-            Self::Comment | Self::Operator | Self::String => Style::default().dim(),
-            _ => Style::default(),
+            Self::Comment => syntax.comment,
+            Self::Operator => syntax.operator,
+            Self::String => syntax.string,
+            Self::Keyword => syntax.keyword,
+            Self::Function => syntax.function,
+            Self::Constant | Self::Number => syntax.constant,
+            Self::Embedded | Self::Property => Style::default(),
         }
     }
 }
@@ -118,6 +124,7 @@ pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
 
     let mut lines: Vec<Line<'static>> = vec![Line::from("")];
     let mut highlight_stack: Vec<Highlight> = Vec::new();
+    let theme = theme::current();
 
     for event in iterator {
         match event {
@@ -129,7 +136,9 @@ pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
+                let style = highlight_stack
+                    .last()
+                    .map(|h| highlight_for(*h).style(&theme.syntax));
                 push_segment(&mut lines, &script[start..end], style);
             }
             Err(_) => return vec![script.to_string().into()],
@@ -0,0 +1,330 @@
+//! Session tabs: several conversations hosted in one TUI.
+//!
+//! The active session's widget, transcript and config live directly on
+//! `App`. Every other session is parked here and keeps receiving agent
+//! events in the background; switching swaps the parked state with the
+//! active one. Events from a session's widget and agent loop are tagged with
+//! its [`SessionTabId`] so the app can route them to the right place.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use llmx_core::config::Config;
+use llmx_core::protocol::AskForApproval;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Widget;
+
+use crate::chatwidget::ChatWidget;
+use crate::file_search::FileSearchManager;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::UserHistoryCell;
+use crate::render::renderable::Renderable;
+use crate::text_formatting::truncate_text;
+use crate::theme;
+
+/// Longest title shown for a session, in graphemes.
+const MAX_TITLE_GRAPHEMES: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct SessionTabId(u64);
+
+/// State of a session that is not currently shown.
+pub(crate) struct ParkedSession {
+    pub(crate) chat_widget: ChatWidget,
+    pub(crate) config: Config,
+    pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    pub(crate) file_search: FileSearchManager,
+}
+
+pub(crate) struct SessionTab {
+    pub(crate) id: SessionTabId,
+    /// `None` for the active tab, whose state lives on `App`.
+    pub(crate) parked: Option<ParkedSession>,
+    /// History cells added since the tab was last shown.
+    pub(crate) unread: usize,
+    /// An approval request arrived while the tab was in the background.
+    pub(crate) approval_pending: bool,
+    /// The tab's widget asked for commit animation ticks.
+    pub(crate) animating: bool,
+}
+
+impl SessionTab {
+    fn new(id: SessionTabId, parked: Option<ParkedSession>) -> Self {
+        Self {
+            id,
+            parked,
+            unread: 0,
+            approval_pending: false,
+            animating: false,
+        }
+    }
+}
+
+pub(crate) struct SessionTabs {
+    tabs: Vec<SessionTab>,
+    active: SessionTabId,
+    next_id: u64,
+}
+
+impl SessionTabs {
+    /// Starts with a single, active tab.
+    pub(crate) fn new() -> Self {
+        let active = SessionTabId(0);
+        Self {
+            tabs: vec![SessionTab::new(active, None)],
+            active,
+            next_id: 1,
+        }
+    }
+
+    pub(crate) fn active_id(&self) -> SessionTabId {
+        self.active
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &SessionTab> {
+        self.tabs.iter()
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut SessionTab> {
+        self.tabs.iter_mut()
+    }
+
+    pub(crate) fn get_mut(&mut self, id: SessionTabId) -> Option<&mut SessionTab> {
+        self.tabs.iter_mut().find(|tab| tab.id == id)
+    }
+
+    /// Reserves an id for a session whose widget is about to be created, so
+    /// the widget's event sender can be tagged before the tab exists.
+    pub(crate) fn allocate_id(&mut self) -> SessionTabId {
+        let id = SessionTabId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Appends a background tab.
+    pub(crate) fn push(&mut self, id: SessionTabId, parked: ParkedSession) {
+        self.tabs.push(SessionTab::new(id, Some(parked)));
+    }
+
+    /// Takes the parked state of tab `id` so it can become the active one.
+    pub(crate) fn take_parked(&mut self, id: SessionTabId) -> Option<ParkedSession> {
+        self.get_mut(id).and_then(|tab| tab.parked.take())
+    }
+
+    /// Parks `previous` (the outgoing active state) and marks `id` active,
+    /// clearing its badges. `id`'s parked state must already be taken.
+    pub(crate) fn activate(&mut self, id: SessionTabId, previous: ParkedSession) {
+        let outgoing = self.active;
+        if let Some(tab) = self.get_mut(outgoing) {
+            tab.parked = Some(previous);
+        }
+        if let Some(tab) = self.get_mut(id) {
+            tab.unread = 0;
+            tab.approval_pending = false;
+        }
+        self.active = id;
+    }
+
+    /// Removes a background tab. The active tab cannot be removed.
+    pub(crate) fn remove(&mut self, id: SessionTabId) -> Option<SessionTab> {
+        if id == self.active {
+            return None;
+        }
+        let idx = self.tabs.iter().position(|tab| tab.id == id)?;
+        Some(self.tabs.remove(idx))
+    }
+
+    /// The tab `offset` positions away from the active one, wrapping around.
+    pub(crate) fn neighbor(&self, offset: isize) -> SessionTabId {
+        let Some(current) = self.tabs.iter().position(|tab| tab.id == self.active) else {
+            return self.active;
+        };
+        let len = self.tabs.len() as isize;
+        let idx = (current as isize + offset).rem_euclid(len) as usize;
+        self.tabs[idx].id
+    }
+
+    /// Whether any tab still wants commit animation ticks.
+    pub(crate) fn any_animating(&self) -> bool {
+        self.tabs.iter().any(|tab| tab.animating)
+    }
+}
+
+/// What the tab bar and `/sessions` show for one session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SessionSummary {
+    pub(crate) id: SessionTabId,
+    /// 1-based position in the tab bar.
+    pub(crate) number: usize,
+    pub(crate) title: String,
+    pub(crate) model: String,
+    pub(crate) cwd: PathBuf,
+    pub(crate) approval_policy: AskForApproval,
+    pub(crate) is_active: bool,
+    pub(crate) is_running: bool,
+    pub(crate) unread: usize,
+    pub(crate) approval_pending: bool,
+}
+
+impl SessionSummary {
+    pub(crate) fn label(&self) -> String {
+        format!("{} {}", self.number, self.title)
+    }
+
+    pub(crate) fn status(&self) -> String {
+        let mut parts = vec![if self.is_running { "working" } else { "idle" }.to_string()];
+        if self.approval_pending {
+            parts.push("approval pending".to_string());
+        }
+        if self.unread > 0 {
+            parts.push(format!("{} unread", self.unread));
+        }
+        parts.join(", ")
+    }
+}
+
+/// A session's title: its first prompt, or the name of its working
+/// directory before anything was sent.
+pub(crate) fn session_title(cells: &[Arc<dyn HistoryCell>], config: &Config) -> String {
+    let first_prompt = cells.iter().find_map(|cell| {
+        cell.as_any()
+            .downcast_ref::<UserHistoryCell>()
+            .and_then(|cell| cell.message.lines().find(|line| !line.trim().is_empty()))
+    });
+    let title = match first_prompt {
+        Some(prompt) => prompt.trim().to_string(),
+        None => config
+            .cwd
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| config.cwd.display().to_string()),
+    };
+    truncate_text(&title, MAX_TITLE_GRAPHEMES)
+}
+
+/// One-line strip above the composer listing the open sessions. Only shown
+/// while more than one session is open.
+pub(crate) struct SessionTabBar {
+    sessions: Vec<SessionSummary>,
+}
+
+impl SessionTabBar {
+    pub(crate) fn new(sessions: Vec<SessionSummary>) -> Self {
+        Self { sessions }
+    }
+
+    fn line(&self) -> Line<'static> {
+        let accent = theme::current().accent;
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for session in &self.sessions {
+            if !spans.is_empty() {
+                spans.push(" │ ".dim());
+            }
+            let label = session.label();
+            spans.push(if session.is_active {
+                Span::styled(label, accent).bold()
+            } else {
+                label.dim()
+            });
+            if session.approval_pending {
+                spans.push(" !".red().bold());
+            } else if session.unread > 0 {
+                spans.push(format!(" ({})", session.unread).cyan());
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+impl Renderable for SessionTabBar {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.line().render(area, buf);
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        u16::from(self.sessions.len() > 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn summary(number: usize, title: &str) -> SessionSummary {
+        SessionSummary {
+            id: SessionTabId(number as u64),
+            number,
+            title: title.to_string(),
+            model: "gpt-5".to_string(),
+            cwd: PathBuf::from("/repo"),
+            approval_policy: AskForApproval::OnRequest,
+            is_active: false,
+            is_running: false,
+            unread: 0,
+            approval_pending: false,
+        }
+    }
+
+    #[test]
+    fn neighbor_wraps_around() {
+        let mut tabs = SessionTabs::new();
+        let first = tabs.active_id();
+        let second = tabs.allocate_id();
+        tabs.tabs.push(SessionTab::new(second, None));
+
+        assert_eq!(tabs.neighbor(1), second);
+        assert_eq!(tabs.neighbor(-1), second);
+        assert_eq!(tabs.neighbor(2), first);
+    }
+
+    #[test]
+    fn active_tab_cannot_be_removed() {
+        let mut tabs = SessionTabs::new();
+        let first = tabs.active_id();
+        let second = tabs.allocate_id();
+        tabs.tabs.push(SessionTab::new(second, None));
+
+        assert!(tabs.remove(first).is_none());
+        assert_eq!(tabs.remove(second).map(|tab| tab.id), Some(second));
+        assert_eq!(tabs.len(), 1);
+    }
+
+    #[test]
+    fn tab_bar_shows_badges() {
+        let mut active = summary(1, "fix tests");
+        active.is_active = true;
+        let mut unread = summary(2, "question");
+        unread.unread = 3;
+        let mut approval = summary(3, "deploy");
+        approval.approval_pending = true;
+        approval.unread = 1;
+        let bar = SessionTabBar::new(vec![active, unread, approval]);
+
+        let text: String = bar
+            .line()
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "1 fix tests │ 2 question (3) │ 3 deploy !");
+    }
+
+    #[test]
+    fn status_lists_badges() {
+        let mut session = summary(1, "fix tests");
+        session.is_running = true;
+        session.approval_pending = true;
+        session.unread = 2;
+        assert_eq!(session.status(), "working, approval pending, 2 unread");
+        assert_eq!(summary(2, "idle").status(), "idle");
+    }
+}
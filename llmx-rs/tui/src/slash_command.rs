@@ -14,14 +14,20 @@ pub enum SlashCommand {
     // more frequently used commands should be listed first.
     Model,
     Approvals,
+    Permissions,
+    Plan,
     Review,
     New,
+    Sessions,
     Init,
     Compact,
     Undo,
+    Checkpoints,
     Diff,
+    Export,
     Mention,
     Status,
+    Theme,
     Mcp,
     Logout,
     Quit,
@@ -37,16 +43,22 @@ impl SlashCommand {
         match self {
             SlashCommand::Feedback => "send logs to maintainers",
             SlashCommand::New => "start a new chat during a conversation",
+            SlashCommand::Sessions => "switch, start, or close session tabs",
             SlashCommand::Init => "create an AGENTS.md file with instructions for LLMX",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Plan => "toggle plan mode: plan read-only before making changes",
             SlashCommand::Undo => "ask LLMX to undo a turn",
+            SlashCommand::Checkpoints => "browse, diff, and restore turn checkpoints",
             SlashCommand::Quit | SlashCommand::Exit => "exit LLMX",
             SlashCommand::Diff => "show git diff (including untracked files)",
-            SlashCommand::Mention => "mention a file",
+            SlashCommand::Export => "export this session as Markdown, HTML, or JSON",
+            SlashCommand::Mention => "mention files, symbols, diffs, or sessions",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Theme => "choose a color theme and preview it",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Approvals => "choose what LLMX can do without approval",
+            SlashCommand::Permissions => "list and revoke saved approval rules",
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Logout => "log out of LLMX",
             SlashCommand::Rollout => "print the rollout file path",
@@ -67,13 +79,19 @@ impl SlashCommand {
             | SlashCommand::Init
             | SlashCommand::Compact
             | SlashCommand::Undo
+            | SlashCommand::Checkpoints
             | SlashCommand::Model
             | SlashCommand::Approvals
+            | SlashCommand::Permissions
+            | SlashCommand::Plan
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Export
             | SlashCommand::Mention
+            | SlashCommand::Sessions
             | SlashCommand::Status
+            | SlashCommand::Theme
             | SlashCommand::Mcp
             | SlashCommand::Feedback
             | SlashCommand::Quit
@@ -7,6 +7,7 @@ use chrono::DateTime;
 use chrono::Local;
 use llmx_common::create_config_summary_entries;
 use llmx_core::config::Config;
+use llmx_core::protocol::ContextEstimate;
 use llmx_core::protocol::SandboxPolicy;
 use llmx_core::protocol::TokenUsage;
 use llmx_protocol::ConversationId;
@@ -47,6 +48,14 @@ pub(crate) struct StatusTokenUsageData {
     input: i64,
     output: i64,
     context_window: Option<StatusContextWindowData>,
+    context_estimate: Option<StatusContextEstimateData>,
+}
+
+#[derive(Debug, Clone)]
+struct StatusContextEstimateData {
+    estimate: ContextEstimate,
+    /// Tokens the provider reported for the last request, when available.
+    reported: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -67,6 +76,7 @@ pub(crate) fn new_status_output(
     config: &Config,
     total_usage: &TokenUsage,
     context_usage: Option<&TokenUsage>,
+    context_estimate: Option<&ContextEstimate>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     now: DateTime<Local>,
@@ -76,6 +86,7 @@ pub(crate) fn new_status_output(
         config,
         total_usage,
         context_usage,
+        context_estimate,
         session_id,
         rate_limits,
         now,
@@ -89,6 +100,7 @@ impl StatusHistoryCell {
         config: &Config,
         total_usage: &TokenUsage,
         context_usage: Option<&TokenUsage>,
+        context_estimate: Option<&ContextEstimate>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
         now: DateTime<Local>,
@@ -116,11 +128,19 @@ impl StatusHistoryCell {
             })
         });
 
+        let context_estimate = context_estimate.map(|estimate| StatusContextEstimateData {
+            estimate: estimate.clone(),
+            reported: context_usage
+                .map(TokenUsage::tokens_in_context_window)
+                .filter(|tokens| *tokens > 0),
+        });
+
         let token_usage = StatusTokenUsageData {
             total: total_usage.blended_total(),
             input: total_usage.non_cached_input(),
             output: total_usage.output_tokens,
             context_window,
+            context_estimate,
         };
         let rate_limits = compose_rate_limit_data(rate_limits, now);
 
@@ -172,6 +192,42 @@ impl StatusHistoryCell {
         ])
     }
 
+    fn context_estimate_spans(&self) -> Option<Vec<Span<'static>>> {
+        let data = self.token_usage.context_estimate.as_ref()?;
+        let estimate = &data.estimate;
+        let mut spans = vec![
+            Span::from(format!(
+                "~{}",
+                format_tokens_compact(estimate.total_tokens())
+            )),
+            Span::from(" (").dim(),
+            Span::from(format!(
+                "system {} · tools {} · history {} · tool outputs {}",
+                format_tokens_compact(estimate.system_tokens),
+                format_tokens_compact(estimate.tools_tokens),
+                format_tokens_compact(estimate.history_tokens),
+                format_tokens_compact(estimate.tool_output_tokens),
+            ))
+            .dim(),
+            Span::from(")").dim(),
+        ];
+        if estimate.reclaimed_tokens > 0 {
+            spans.push(
+                Span::from(format!(
+                    " · {} reclaimed",
+                    format_tokens_compact(estimate.reclaimed_tokens)
+                ))
+                .dim(),
+            );
+        }
+        if let Some(reported) = data.reported {
+            spans.push(
+                Span::from(format!(" vs {} reported", format_tokens_compact(reported))).dim(),
+            );
+        }
+        Some(spans)
+    }
+
     fn rate_limit_lines(
         &self,
         available_inner_width: usize,
@@ -307,6 +363,9 @@ impl HistoryCell for StatusHistoryCell {
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
+        if self.token_usage.context_estimate.is_some() {
+            push_label(&mut labels, &mut seen, "Context estimate");
+        }
         self.collect_rate_limit_labels(&mut seen, &mut labels);
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
@@ -362,6 +421,10 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Context window", spans));
         }
 
+        if let Some(spans) = self.context_estimate_spans() {
+            lines.push(formatter.line("Context estimate", spans));
+        }
+
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
         let content_width = lines.iter().map(line_display_width).max().unwrap_or(0);
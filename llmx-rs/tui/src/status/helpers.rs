@@ -83,8 +83,12 @@ pub(crate) fn compose_agents_summary(config: &Config) -> String {
 }
 
 pub(crate) fn compose_account_display(config: &Config) -> Option<StatusAccountDisplay> {
-    let auth =
-        load_auth_dot_json(&config.llmx_home, config.cli_auth_credentials_store_mode).ok()??;
+    let auth = load_auth_dot_json(
+        &config.llmx_home,
+        config.cli_auth_credentials_store_mode,
+        config.credential_helper.as_deref(),
+    )
+    .ok()??;
 
     if let Some(tokens) = auth.tokens.as_ref() {
         let info = &tokens.id_token;
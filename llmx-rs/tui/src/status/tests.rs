@@ -8,6 +8,7 @@ use insta::assert_snapshot;
 use llmx_core::config::Config;
 use llmx_core::config::ConfigOverrides;
 use llmx_core::config::ConfigToml;
+use llmx_core::protocol::ContextEstimate;
 use llmx_core::protocol::RateLimitSnapshot;
 use llmx_core::protocol::RateLimitWindow;
 use llmx_core::protocol::SandboxPolicy;
@@ -115,6 +116,7 @@ fn status_snapshot_includes_reasoning_details() {
         &config,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         captured_at,
@@ -163,6 +165,7 @@ fn status_snapshot_includes_monthly_limit() {
         &config,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         captured_at,
@@ -197,7 +200,7 @@ fn status_card_token_usage_excludes_cached_tokens() {
         .single()
         .expect("timestamp");
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, now);
+    let composite = new_status_output(&config, &usage, Some(&usage), None, &None, None, now);
     let rendered = render_lines(&composite.display_lines(120));
 
     assert!(
@@ -206,6 +209,57 @@ fn status_card_token_usage_excludes_cached_tokens() {
     );
 }
 
+#[test]
+fn status_card_shows_context_estimate_breakdown() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home);
+    config.model = "gpt-5-llmx".to_string();
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let usage = TokenUsage {
+        input_tokens: 12_000,
+        cached_input_tokens: 0,
+        output_tokens: 500,
+        reasoning_output_tokens: 0,
+        total_tokens: 12_500,
+    };
+    let estimate = ContextEstimate {
+        system_tokens: 2_000,
+        tools_tokens: 3_000,
+        history_tokens: 5_000,
+        tool_output_tokens: 2_100,
+        reclaimed_tokens: 8_000,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .single()
+        .expect("timestamp");
+
+    let composite = new_status_output(
+        &config,
+        &usage,
+        Some(&usage),
+        Some(&estimate),
+        &None,
+        None,
+        now,
+    );
+    let rendered = render_lines(&composite.display_lines(160));
+
+    let estimate_line = rendered
+        .iter()
+        .find(|line| line.contains("Context estimate"))
+        .unwrap_or_else(|| panic!("missing context estimate line: {rendered:?}"));
+    assert!(
+        estimate_line.contains("~12.1K")
+            && estimate_line.contains("tool outputs 2.1K")
+            && estimate_line.contains("8K reclaimed")
+            && estimate_line.contains("vs 12.5K reported"),
+        "unexpected context estimate line: {estimate_line}"
+    );
+}
+
 #[test]
 fn status_snapshot_truncates_in_narrow_terminal() {
     let temp_home = TempDir::new().expect("temp home");
@@ -242,6 +296,7 @@ fn status_snapshot_truncates_in_narrow_terminal() {
         &config,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         captured_at,
@@ -277,7 +332,7 @@ fn status_snapshot_shows_missing_limits_message() {
         .single()
         .expect("timestamp");
 
-    let composite = new_status_output(&config, &usage, Some(&usage), &None, None, now);
+    let composite = new_status_output(&config, &usage, Some(&usage), None, &None, None, now);
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
         for line in &mut rendered_lines {
@@ -317,6 +372,7 @@ fn status_snapshot_shows_empty_limits_message() {
         &config,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         captured_at,
@@ -369,6 +425,7 @@ fn status_snapshot_shows_stale_limits_message() {
         &config,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         now,
@@ -409,7 +466,15 @@ fn status_context_window_uses_last_usage() {
         .single()
         .expect("timestamp");
 
-    let composite = new_status_output(&config, &total_usage, Some(&last_usage), &None, None, now);
+    let composite = new_status_output(
+        &config,
+        &total_usage,
+        Some(&last_usage),
+        None,
+        &None,
+        None,
+        now,
+    );
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
         .into_iter()
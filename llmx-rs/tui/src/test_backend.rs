@@ -20,6 +20,9 @@ use ratatui::layout::Size;
 /// - getting the cursor position
 pub struct VT100Backend {
     crossterm_backend: CrosstermBackend<vt100::Parser>,
+    /// Raw bytes queued directly on the backend, kept so tests can assert on
+    /// escape sequences (e.g. inline graphics) that vt100 does not model.
+    written: Vec<u8>,
 }
 
 impl VT100Backend {
@@ -27,17 +30,24 @@ impl VT100Backend {
     pub fn new(width: u16, height: u16) -> Self {
         Self {
             crossterm_backend: CrosstermBackend::new(vt100::Parser::new(height, width, 0)),
+            written: Vec::new(),
         }
     }
 
     pub fn vt100(&self) -> &vt100::Parser {
         self.crossterm_backend.writer()
     }
+
+    pub fn written(&self) -> &[u8] {
+        &self.written
+    }
 }
 
 impl Write for VT100Backend {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.crossterm_backend.writer_mut().write(buf)
+        let n = self.crossterm_backend.writer_mut().write(buf)?;
+        self.written.extend_from_slice(&buf[..n]);
+        Ok(n)
     }
 
     fn flush(&mut self) -> io::Result<()> {
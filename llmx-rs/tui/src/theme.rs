@@ -0,0 +1,500 @@
+//! Color themes for diffs, borders, headers, accents and syntax highlighting,
+//! resolved from the `[tui.theme]` config table.
+//!
+//! The active theme is process-wide: renderers call [`current`] when they
+//! build styles. With `name = "auto"` (the default) the theme follows the
+//! detected terminal background, so it switches when the terminal reports a
+//! different background color.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+
+use llmx_core::config::types::ThemePalette;
+use llmx_core::config::types::TuiTheme;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::color::is_light;
+use crate::render::highlight::highlight_bash_to_lines;
+use crate::render::renderable::Renderable;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
+
+/// Theme name that follows the terminal background.
+pub(crate) const AUTO: &str = "auto";
+
+/// Built-in themes with the description shown in `/theme`.
+pub(crate) const BUILT_IN_THEMES: [(&str, &str); 3] = [
+    ("default", "ANSI colors from your terminal's own palette"),
+    (
+        "light",
+        "Darker text and tinted diffs for light backgrounds",
+    ),
+    ("dark", "Softer truecolor palette with tinted diffs"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Theme {
+    pub(crate) name: String,
+    pub(crate) diff_add: Style,
+    pub(crate) diff_remove: Style,
+    pub(crate) border: Style,
+    pub(crate) header: Style,
+    pub(crate) accent: Style,
+    pub(crate) syntax: SyntaxStyles,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyntaxStyles {
+    pub(crate) keyword: Style,
+    pub(crate) string: Style,
+    pub(crate) comment: Style,
+    pub(crate) function: Style,
+    pub(crate) constant: Style,
+    pub(crate) operator: Style,
+}
+
+/// Colors layered over a base theme; `None` keeps the base style.
+struct Colors<'a> {
+    diff_add: Option<&'a str>,
+    diff_add_bg: Option<&'a str>,
+    diff_remove: Option<&'a str>,
+    diff_remove_bg: Option<&'a str>,
+    border: Option<&'a str>,
+    header: Option<&'a str>,
+    accent: Option<&'a str>,
+    syntax_keyword: Option<&'a str>,
+    syntax_string: Option<&'a str>,
+    syntax_comment: Option<&'a str>,
+    syntax_function: Option<&'a str>,
+    syntax_constant: Option<&'a str>,
+}
+
+const LIGHT: Colors<'static> = Colors {
+    diff_add: Some("#116329"),
+    diff_add_bg: Some("#dafbe1"),
+    diff_remove: Some("#82071e"),
+    diff_remove_bg: Some("#ffebe9"),
+    border: Some("#6e7781"),
+    header: Some("#0550ae"),
+    accent: Some("#0969da"),
+    syntax_keyword: Some("#cf222e"),
+    syntax_string: Some("#0a3069"),
+    syntax_comment: Some("#6e7781"),
+    syntax_function: Some("#8250df"),
+    syntax_constant: Some("#0550ae"),
+};
+
+const DARK: Colors<'static> = Colors {
+    diff_add: Some("#aff5b4"),
+    diff_add_bg: Some("#033a16"),
+    diff_remove: Some("#ffdcd7"),
+    diff_remove_bg: Some("#67060c"),
+    border: Some("#6e7681"),
+    header: Some("#79c0ff"),
+    accent: Some("#39c5cf"),
+    syntax_keyword: Some("#ff7b72"),
+    syntax_string: Some("#a5d6ff"),
+    syntax_comment: Some("#8b949e"),
+    syntax_function: Some("#d2a8ff"),
+    syntax_constant: Some("#79c0ff"),
+};
+
+impl<'a> From<&'a ThemePalette> for Colors<'a> {
+    fn from(palette: &'a ThemePalette) -> Self {
+        Self {
+            diff_add: palette.diff_add.as_deref(),
+            diff_add_bg: palette.diff_add_bg.as_deref(),
+            diff_remove: palette.diff_remove.as_deref(),
+            diff_remove_bg: palette.diff_remove_bg.as_deref(),
+            border: palette.border.as_deref(),
+            header: palette.header.as_deref(),
+            accent: palette.accent.as_deref(),
+            syntax_keyword: palette.syntax_keyword.as_deref(),
+            syntax_string: palette.syntax_string.as_deref(),
+            syntax_comment: palette.syntax_comment.as_deref(),
+            syntax_function: palette.syntax_function.as_deref(),
+            syntax_constant: palette.syntax_constant.as_deref(),
+        }
+    }
+}
+
+impl Default for Theme {
+    /// The ANSI look the TUI has always had; it adapts to the terminal's own
+    /// palette, so it is the fallback whenever nothing better is known.
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            diff_add: Style::default().fg(Color::Green),
+            diff_remove: Style::default().fg(Color::Red),
+            border: Style::default().dim(),
+            header: Style::default(),
+            accent: Style::default().fg(Color::Cyan),
+            syntax: SyntaxStyles {
+                keyword: Style::default(),
+                string: Style::default().dim(),
+                comment: Style::default().dim(),
+                function: Style::default(),
+                constant: Style::default(),
+                operator: Style::default().dim(),
+            },
+        }
+    }
+}
+
+impl Theme {
+    fn built_in(name: &str) -> Option<Self> {
+        let mut theme = Self::default();
+        match name {
+            "default" => {}
+            "light" => theme.apply(&LIGHT),
+            "dark" => theme.apply(&DARK),
+            _ => return None,
+        }
+        theme.name = name.to_string();
+        Some(theme)
+    }
+
+    fn apply(&mut self, colors: &Colors<'_>) {
+        set_fg(&mut self.diff_add, colors.diff_add);
+        set_bg(&mut self.diff_add, colors.diff_add_bg);
+        set_fg(&mut self.diff_remove, colors.diff_remove);
+        set_bg(&mut self.diff_remove, colors.diff_remove_bg);
+        set_fg(&mut self.border, colors.border);
+        set_fg(&mut self.header, colors.header);
+        set_fg(&mut self.accent, colors.accent);
+        set_fg(&mut self.syntax.keyword, colors.syntax_keyword);
+        set_fg(&mut self.syntax.string, colors.syntax_string);
+        set_fg(&mut self.syntax.comment, colors.syntax_comment);
+        set_fg(&mut self.syntax.function, colors.syntax_function);
+        set_fg(&mut self.syntax.constant, colors.syntax_constant);
+    }
+}
+
+/// Replaces the foreground of `style` and drops modifiers such as the dim
+/// used by the ANSI theme; a set color already carries the intended contrast.
+fn set_fg(style: &mut Style, value: Option<&str>) {
+    if let Some(color) = value.and_then(parse_color_or_warn) {
+        let bg = style.bg;
+        *style = Style::default().fg(color);
+        style.bg = bg;
+    }
+}
+
+fn set_bg(style: &mut Style, value: Option<&str>) {
+    if let Some(color) = value.and_then(parse_color_or_warn) {
+        *style = style.bg(color);
+    }
+}
+
+fn parse_color_or_warn(value: &str) -> Option<Color> {
+    parse_color(value)
+        .map_err(|err| tracing::warn!("ignoring tui.theme color: {err}"))
+        .ok()
+}
+
+/// Parses `"#rrggbb"` (degraded to what the terminal can show) or an ANSI
+/// color name such as `"green"` or `"light-blue"`.
+fn parse_color(value: &str) -> Result<Color, String> {
+    let value = value.trim();
+    if value.starts_with('#') {
+        return parse_hex(value).map(best_color);
+    }
+    value
+        .parse::<Color>()
+        .map_err(|_| format!("unknown color `{value}`"))
+}
+
+fn parse_hex(value: &str) -> Result<(u8, u8, u8), String> {
+    let invalid = || format!("invalid color `{value}` (expected #rrggbb)");
+    let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel =
+        |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).map_err(|_| invalid());
+    Ok((channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
+
+/// Resolves the theme to use. `preview` takes precedence over the configured
+/// name; `light_background` is `None` when the background is unknown.
+fn resolve(config: &TuiTheme, preview: Option<&str>, light_background: Option<bool>) -> Theme {
+    let mut name = preview.or(config.name.as_deref()).unwrap_or(AUTO);
+    if name == AUTO {
+        name = if light_background == Some(true) {
+            config.light.as_deref().unwrap_or("light")
+        } else {
+            config.dark.as_deref().unwrap_or("default")
+        };
+    }
+    named(config, name).unwrap_or_else(|| {
+        tracing::warn!("unknown tui.theme `{name}`; using the default theme");
+        Theme::default()
+    })
+}
+
+/// User palettes shadow built-in themes of the same name.
+fn named(config: &TuiTheme, name: &str) -> Option<Theme> {
+    let Some(palette) = config.palettes.get(name) else {
+        return Theme::built_in(name);
+    };
+    let base = palette.base.as_deref().unwrap_or("default");
+    let mut theme = Theme::built_in(base).unwrap_or_else(|| {
+        tracing::warn!("unknown base theme `{base}` for tui.theme.palettes.{name}");
+        Theme::default()
+    });
+    theme.apply(&Colors::from(palette));
+    theme.name = name.to_string();
+    Some(theme)
+}
+
+/// Theme names offered by `/theme`, with descriptions: `auto`, the built-in
+/// themes, then the user palettes.
+pub(crate) fn available_themes(config: &TuiTheme) -> Vec<(String, String)> {
+    let auto = format!(
+        "Follow the terminal background ({} on light, {} on dark)",
+        config.light.as_deref().unwrap_or("light"),
+        config.dark.as_deref().unwrap_or("default"),
+    );
+    let mut themes = vec![(AUTO.to_string(), auto)];
+    themes.extend(
+        BUILT_IN_THEMES
+            .iter()
+            .filter(|(name, _)| !config.palettes.contains_key(*name))
+            .map(|(name, description)| (name.to_string(), description.to_string())),
+    );
+    themes.extend(config.palettes.iter().map(|(name, palette)| {
+        let base = palette.base.as_deref().unwrap_or("default");
+        (name.clone(), format!("Custom palette based on {base}"))
+    }));
+    themes
+}
+
+#[derive(Default)]
+struct State {
+    config: TuiTheme,
+    preview: Option<String>,
+    /// Last resolved theme and the background lightness it was resolved for.
+    resolved: Option<(Option<bool>, Arc<Theme>)>,
+}
+
+fn state() -> MutexGuard<'static, State> {
+    static STATE: OnceLock<Mutex<State>> = OnceLock::new();
+    STATE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Installs the `[tui.theme]` config and drops any preview.
+pub(crate) fn configure(config: &TuiTheme) {
+    let mut state = state();
+    state.config = config.clone();
+    state.preview = None;
+    state.resolved = None;
+}
+
+/// The theme renderers should use right now.
+pub(crate) fn current() -> Arc<Theme> {
+    let light_background = default_bg().map(is_light);
+    let mut state = state();
+    if let Some((resolved_for, theme)) = &state.resolved
+        && *resolved_for == light_background
+    {
+        return theme.clone();
+    }
+    let theme = Arc::new(resolve(
+        &state.config,
+        state.preview.as_deref(),
+        light_background,
+    ));
+    state.resolved = Some((light_background, theme.clone()));
+    theme
+}
+
+/// Temporarily shows `name` (or the configured theme again for `None`)
+/// without changing the config.
+pub(crate) fn set_preview(name: Option<String>) {
+    let mut state = state();
+    state.preview = name;
+    state.resolved = None;
+}
+
+/// Makes `name` the configured theme for the rest of the session.
+pub(crate) fn select(name: &str) {
+    let mut state = state();
+    state.config.name = Some(name.to_string());
+    state.preview = None;
+    state.resolved = None;
+}
+
+/// The configured theme name, `auto` when unset.
+pub(crate) fn configured_name() -> String {
+    state()
+        .config
+        .name
+        .clone()
+        .unwrap_or_else(|| AUTO.to_string())
+}
+
+/// The configured theme settings, including user palettes.
+pub(crate) fn config() -> TuiTheme {
+    state().config.clone()
+}
+
+/// Sample of every themed element, rendered with the current theme so the
+/// `/theme` picker can show the highlighted theme live.
+pub(crate) struct ThemePreview;
+
+impl ThemePreview {
+    fn lines() -> Vec<Line<'static>> {
+        let theme = current();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("# ", theme.header),
+                Span::styled(format!("{} theme", theme.name), theme.header.bold()),
+                "  ".into(),
+                Span::styled("accent", theme.accent),
+            ]),
+            Line::from(vec![
+                Span::styled("╭─ ", theme.border),
+                "src/lib.rs".into(),
+                " ".into(),
+                Span::styled("+1", theme.diff_add),
+                " ".into(),
+                Span::styled("-1", theme.diff_remove),
+            ]),
+            Line::from(vec![
+                Span::styled("│ ", theme.border),
+                Span::styled("1 -let greeting = \"hi\";", theme.diff_remove),
+            ]),
+            Line::from(vec![
+                Span::styled("│ ", theme.border),
+                Span::styled("1 +let greeting = \"hello\";", theme.diff_add),
+            ]),
+        ];
+        let script =
+            "# format changed files\nfor f in $(git diff --name-only); do rustfmt \"$f\"; done";
+        for line in highlight_bash_to_lines(script) {
+            let mut spans = vec![Span::styled("│ ", theme.border)];
+            spans.extend(line.spans);
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+impl Renderable for ThemePreview {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let lines: Vec<Line<'static>> = Self::lines()
+            .into_iter()
+            .map(|line| {
+                let mut spans = vec![Span::from("  ")];
+                spans.extend(line.spans);
+                Line::from(spans)
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        Self::lines().len() as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn auto_follows_background_lightness() {
+        let config = TuiTheme::default();
+        assert_eq!(resolve(&config, None, Some(true)).name, "light");
+        assert_eq!(resolve(&config, None, Some(false)).name, "default");
+        assert_eq!(resolve(&config, None, None).name, "default");
+
+        let config = TuiTheme {
+            light: Some("default".to_string()),
+            dark: Some("dark".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(resolve(&config, None, Some(true)).name, "default");
+        assert_eq!(resolve(&config, None, Some(false)).name, "dark");
+    }
+
+    #[test]
+    fn preview_overrides_configured_name() {
+        let config = TuiTheme {
+            name: Some("dark".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(resolve(&config, None, Some(true)).name, "dark");
+        assert_eq!(resolve(&config, Some("light"), None).name, "light");
+        assert_eq!(resolve(&config, Some("nope"), None), Theme::default());
+    }
+
+    #[test]
+    fn palette_overrides_base_theme() {
+        let config = TuiTheme {
+            name: Some("mine".to_string()),
+            palettes: BTreeMap::from([(
+                "mine".to_string(),
+                ThemePalette {
+                    diff_add: Some("light-blue".to_string()),
+                    border: Some("gray".to_string()),
+                    accent: Some("not-a-color".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let theme = resolve(&config, None, None);
+        let base = Theme::default();
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.diff_add, Style::default().fg(Color::LightBlue));
+        // The ANSI border is dim; an explicit color replaces that.
+        assert_eq!(theme.border, Style::default().fg(Color::Gray));
+        assert_eq!(theme.accent, base.accent);
+        assert_eq!(theme.diff_remove, base.diff_remove);
+        assert_eq!(theme.syntax, base.syntax);
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_hex("#0969da"), Ok((0x09, 0x69, 0xda)));
+        assert!(parse_hex("#0969d").is_err());
+        assert!(parse_hex("#zz69da").is_err());
+        assert!(parse_color("chartreuse-ish").is_err());
+    }
+
+    #[test]
+    fn lists_auto_built_ins_and_palettes() {
+        let config = TuiTheme {
+            palettes: BTreeMap::from([(
+                "solarized".to_string(),
+                ThemePalette {
+                    base: Some("dark".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let names: Vec<String> = available_themes(&config)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["auto", "default", "light", "dark", "solarized"]);
+    }
+}
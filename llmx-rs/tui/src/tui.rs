@@ -25,6 +25,7 @@ use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
 use crossterm::terminal::LeaveAlternateScreen;
 use crossterm::terminal::supports_keyboard_enhancement;
+use llmx_core::config::types::NotificationMethod;
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
 use ratatui::crossterm::execute;
@@ -37,13 +38,19 @@ use tokio_stream::Stream;
 
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::inline_image::InlineImage;
+use crate::tui::event_source::EventSource;
 #[cfg(unix)]
 use crate::tui::job_control::SUSPEND_KEY;
 #[cfg(unix)]
 use crate::tui::job_control::SuspendContext;
+use crate::tui::notification::NotificationEscape;
+use crate::tui::notification::PostNotification;
 
+mod event_source;
 #[cfg(unix)]
 mod job_control;
+mod notification;
 
 /// A type alias for the terminal type used in this application
 pub type Terminal = CustomTerminal<CrosstermBackend<Stdout>>;
@@ -159,7 +166,13 @@ pub struct Tui {
     draw_tx: tokio::sync::broadcast::Sender<()>,
     pub(crate) terminal: Terminal,
     pending_history_lines: Vec<Line<'static>>,
+    /// Thumbnails to draw once the pending lines up to each index are
+    /// inserted; the image covers the blank rows just before that index.
+    pending_history_images: Vec<(usize, InlineImage)>,
     alt_saved_viewport: Option<ratatui::layout::Rect>,
+    /// Terminal input, paused while [`Tui::with_restored`] hands the
+    /// terminal to another program.
+    event_source: EventSource,
     #[cfg(unix)]
     suspend_context: SuspendContext,
     // True when overlay alt-screen UI is active
@@ -167,6 +180,7 @@ pub struct Tui {
     // True when terminal/tab is focused; updated internally from crossterm events
     terminal_focused: Arc<AtomicBool>,
     enhanced_keys_supported: bool,
+    notification_escape: NotificationEscape,
 }
 
 #[derive(Clone, Debug)]
@@ -212,12 +226,15 @@ impl Tui {
             draw_tx,
             terminal,
             pending_history_lines: vec![],
+            pending_history_images: vec![],
             alt_saved_viewport: None,
+            event_source: EventSource::default(),
             #[cfg(unix)]
             suspend_context: SuspendContext::new(),
             alt_screen_active: Arc::new(AtomicBool::new(false)),
             terminal_focused: Arc::new(AtomicBool::new(true)),
             enhanced_keys_supported,
+            notification_escape: NotificationEscape::default(),
         }
     }
 
@@ -231,21 +248,38 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
+    /// Chooses the escape sequence used for desktop notifications.
+    pub(crate) fn set_notification_method(&mut self, method: NotificationMethod) {
+        self.notification_escape = NotificationEscape::from_config(method);
+    }
+
     /// Emit a desktop notification now if the terminal is unfocused.
     /// Returns true if a notification was posted.
     pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
         if !self.terminal_focused.load(Ordering::Relaxed) {
-            let _ = execute!(stdout(), PostNotification(message.as_ref().to_string()));
+            let _ = execute!(
+                stdout(),
+                PostNotification(self.notification_escape, message.as_ref().to_string())
+            );
             true
         } else {
             false
         }
     }
 
+    /// Posts a desktop notification even while the terminal has focus, for
+    /// events the user cannot see, such as those of a background session.
+    pub(crate) fn notify_unseen(&mut self, message: impl AsRef<str>) {
+        let _ = execute!(
+            stdout(),
+            PostNotification(self.notification_escape, message.as_ref().to_string())
+        );
+    }
+
     pub fn event_stream(&self) -> Pin<Box<dyn Stream<Item = TuiEvent> + Send + 'static>> {
         use tokio_stream::StreamExt;
 
-        let mut crossterm_events = crossterm::event::EventStream::new();
+        let mut crossterm_events = self.event_source.clone();
         let mut draw_rx = self.draw_tx.subscribe();
 
         // State for tracking how we should resume from ^Z suspend.
@@ -339,11 +373,51 @@ impl Tui {
         Ok(())
     }
 
+    /// Hands the terminal to `f` (e.g. an external editor) with the modes set
+    /// by [`set_modes`] undone and terminal input paused, then restores them
+    /// and redraws from scratch.
+    ///
+    /// On Unix this shares the ^Z suspend bookkeeping, so an active alt
+    /// screen (e.g. the transcript overlay) is left for the child and
+    /// re-entered on the next draw.
+    pub fn with_restored<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        // Stop reading stdin so the child gets every keystroke.
+        self.event_source.pause();
+        #[cfg(unix)]
+        let (result, left_alt_screen) = self
+            .suspend_context
+            .run_detached(&self.alt_screen_active, f);
+        #[cfg(not(unix))]
+        let (result, left_alt_screen) = {
+            let _ = restore();
+            let result = f();
+            let _ = set_modes();
+            (result, false)
+        };
+        self.event_source.resume();
+        // Re-entering the alt screen clears it; clearing here would wipe the
+        // normal screen instead.
+        if !left_alt_screen {
+            let _ = self.terminal.clear();
+        }
+        self.frame_requester().schedule_frame();
+        result
+    }
+
     pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
         self.pending_history_lines.extend(lines);
         self.frame_requester().schedule_frame();
     }
 
+    /// Reserves blank history rows for `image` and draws it over them.
+    pub(crate) fn insert_history_image(&mut self, image: InlineImage) {
+        self.pending_history_lines
+            .extend(std::iter::repeat_n(Line::from(""), usize::from(image.rows)));
+        self.pending_history_images
+            .push((self.pending_history_lines.len(), image));
+        self.frame_requester().schedule_frame();
+    }
+
     pub fn draw(
         &mut self,
         height: u16,
@@ -411,11 +485,19 @@ impl Tui {
             }
 
             if !self.pending_history_lines.is_empty() {
-                crate::insert_history::insert_history_lines(
-                    terminal,
-                    self.pending_history_lines.clone(),
-                )?;
-                self.pending_history_lines.clear();
+                let lines = std::mem::take(&mut self.pending_history_lines);
+                let mut start = 0;
+                for (end, image) in self.pending_history_images.drain(..) {
+                    crate::insert_history::insert_history_lines(
+                        terminal,
+                        lines[start..end].to_vec(),
+                    )?;
+                    crate::insert_history::insert_history_image(terminal, &image)?;
+                    start = end;
+                }
+                if start < lines.len() {
+                    crate::insert_history::insert_history_lines(terminal, lines[start..].to_vec())?;
+                }
             }
 
             // Update the y position for suspending so Ctrl-Z can place the cursor correctly.
@@ -482,25 +564,3 @@ fn spawn_frame_scheduler(
         }
     });
 }
-
-/// Command that emits an OSC 9 desktop notification with a message.
-#[derive(Debug, Clone)]
-pub struct PostNotification(pub String);
-
-impl Command for PostNotification {
-    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, "\x1b]9;{}\x07", self.0)
-    }
-
-    #[cfg(windows)]
-    fn execute_winapi(&self) -> Result<()> {
-        Err(std::io::Error::other(
-            "tried to execute PostNotification using WinAPI; use ANSI instead",
-        ))
-    }
-
-    #[cfg(windows)]
-    fn is_ansi_code_supported(&self) -> bool {
-        true
-    }
-}
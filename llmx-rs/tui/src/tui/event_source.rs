@@ -0,0 +1,99 @@
+//! Crossterm input that can be paused while another program owns the
+//! terminal.
+//!
+//! `crossterm::event::EventStream` reads stdin from a background thread for
+//! as long as it exists, so it would swallow keystrokes meant for an external
+//! editor. Pausing drops the stream, which stops that thread; the next poll
+//! after resuming opens a fresh one.
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use crossterm::event::Event;
+use crossterm::event::EventStream;
+use tokio_stream::Stream;
+
+#[derive(Clone, Default)]
+pub(crate) struct EventSource {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Created on first poll, so keyboard enhancement detection in
+    /// `Tui::new` runs before crossterm's reader thread starts.
+    events: Option<EventStream>,
+    paused: bool,
+    /// Task waiting for input while paused.
+    waker: Option<Waker>,
+}
+
+impl EventSource {
+    /// Stops reading from the terminal until [`EventSource::resume`].
+    pub(crate) fn pause(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.paused = true;
+        state.events = None;
+    }
+
+    pub(crate) fn resume(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.paused = false;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Stream for EventSource {
+    type Item = io::Result<Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.paused {
+            state.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let events = state.events.get_or_insert_with(EventStream::new);
+        Pin::new(events).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use std::task::Wake;
+
+    #[derive(Default)]
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn paused_source_does_not_open_the_terminal_and_wakes_on_resume() {
+        let mut source = EventSource::default();
+        source.pause();
+
+        let flag = Arc::new(Flag::default());
+        let waker = Waker::from(Arc::clone(&flag));
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut source).poll_next(&mut cx).is_pending());
+        assert!(source.state.lock().expect("lock").events.is_none());
+        assert!(!flag.0.load(Ordering::SeqCst));
+
+        source.resume();
+        assert!(flag.0.load(Ordering::SeqCst));
+    }
+}
@@ -62,12 +62,7 @@ impl SuspendContext {
     /// - Update the cached inline cursor row so suspend can place the cursor meaningfully.
     /// - Trigger SIGTSTP so the process can be resumed and continue drawing with the saved state.
     pub(crate) fn suspend(&self, alt_screen_active: &Arc<AtomicBool>) -> Result<()> {
-        if alt_screen_active.load(Ordering::Relaxed) {
-            // Leave alt-screen so the terminal returns to the normal buffer while suspended; also turn off alt-scroll.
-            let _ = execute!(stdout(), DisableAlternateScroll);
-            let _ = execute!(stdout(), LeaveAlternateScreen);
-            self.set_resume_action(ResumeAction::RestoreAlt);
-        } else {
+        if !self.leave_alt_screen(alt_screen_active) {
             self.set_resume_action(ResumeAction::RealignInline);
         }
         let y = self.suspend_cursor_y.load(Ordering::Relaxed);
@@ -75,6 +70,36 @@ impl SuspendContext {
         suspend_process()
     }
 
+    /// Hand the terminal to a foreground child (e.g. `$EDITOR`) for the duration of `f`.
+    ///
+    /// Terminal modes are restored around `f` like a suspend. An active alt screen is left and
+    /// recorded as `RestoreAlt`, so the next draw re-enters it; the inline viewport stays put
+    /// and is simply redrawn by the caller. Returns whether the alt screen was left.
+    pub(crate) fn run_detached<R>(
+        &self,
+        alt_screen_active: &Arc<AtomicBool>,
+        f: impl FnOnce() -> R,
+    ) -> (R, bool) {
+        let left_alt_screen = self.leave_alt_screen(alt_screen_active);
+        let _ = super::restore();
+        let result = f();
+        let _ = super::set_modes();
+        (result, left_alt_screen)
+    }
+
+    /// Leave the alt screen (and alt-scroll) if it is active, recording `RestoreAlt` so the
+    /// overlay comes back on the next draw.
+    fn leave_alt_screen(&self, alt_screen_active: &Arc<AtomicBool>) -> bool {
+        if !alt_screen_active.load(Ordering::Relaxed) {
+            return false;
+        }
+        // Leave alt-screen so the terminal returns to the normal buffer while suspended; also turn off alt-scroll.
+        let _ = execute!(stdout(), DisableAlternateScroll);
+        let _ = execute!(stdout(), LeaveAlternateScreen);
+        self.set_resume_action(ResumeAction::RestoreAlt);
+        true
+    }
+
     /// Consume the pending resume intent and precompute any viewport changes needed post-resume.
     ///
     /// Returns a `PreparedResumeAction` describing how to realign the viewport once drawing
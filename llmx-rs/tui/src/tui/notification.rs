@@ -0,0 +1,111 @@
+use std::fmt;
+
+use crossterm::Command;
+use llmx_core::config::types::NotificationMethod;
+
+/// Title shown by terminals that support one (OSC 777).
+const NOTIFICATION_TITLE: &str = "LLMX";
+
+/// Escape sequence used to post desktop notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum NotificationEscape {
+    #[default]
+    Osc9,
+    Osc777,
+}
+
+impl NotificationEscape {
+    pub(crate) fn from_config(method: NotificationMethod) -> Self {
+        match method {
+            NotificationMethod::Auto => detect(|name| std::env::var(name).ok()),
+            NotificationMethod::Osc9 => Self::Osc9,
+            NotificationMethod::Osc777 => Self::Osc777,
+        }
+    }
+}
+
+/// OSC 777 for terminals known to understand it but not OSC 9, OSC 9
+/// everywhere else.
+fn detect(env: impl Fn(&str) -> Option<String>) -> NotificationEscape {
+    let term = env("TERM").unwrap_or_default();
+    if env("VTE_VERSION").is_some() || term.starts_with("foot") || term.starts_with("rxvt") {
+        NotificationEscape::Osc777
+    } else {
+        NotificationEscape::Osc9
+    }
+}
+
+/// Command that emits a desktop notification with a message.
+#[derive(Debug, Clone)]
+pub(crate) struct PostNotification(pub NotificationEscape, pub String);
+
+impl Command for PostNotification {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        // Control characters would end the sequence early.
+        let message: String = self
+            .1
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        match self.0 {
+            NotificationEscape::Osc9 => write!(f, "\x1b]9;{message}\x07"),
+            NotificationEscape::Osc777 => {
+                write!(f, "\x1b]777;notify;{NOTIFICATION_TITLE};{message}\x07")
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PostNotification using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn detect_with(vars: &[(&str, &str)]) -> NotificationEscape {
+        detect(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn detects_osc777_terminals() {
+        assert_eq!(
+            detect_with(&[("VTE_VERSION", "7600"), ("TERM", "xterm-256color")]),
+            NotificationEscape::Osc777
+        );
+        assert_eq!(detect_with(&[("TERM", "foot")]), NotificationEscape::Osc777);
+        assert_eq!(
+            detect_with(&[("TERM_PROGRAM", "iTerm.app")]),
+            NotificationEscape::Osc9
+        );
+    }
+
+    #[test]
+    fn writes_escape_sequences() {
+        let mut osc9 = String::new();
+        PostNotification(NotificationEscape::Osc9, "Approval requested".to_string())
+            .write_ansi(&mut osc9)
+            .expect("write");
+        assert_eq!(osc9, "\x1b]9;Approval requested\x07");
+
+        let mut osc777 = String::new();
+        PostNotification(NotificationEscape::Osc777, "done\x07\nnext".to_string())
+            .write_ansi(&mut osc777)
+            .expect("write");
+        assert_eq!(osc777, "\x1b]777;notify;LLMX;done  next\x07");
+    }
+}
@@ -0,0 +1,351 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::Builder;
+
+use crate::GhostCommit;
+use crate::GitToolingError;
+use crate::ghost_commits::remove_path;
+use crate::operations::ensure_git_repository;
+use crate::operations::normalize_relative_path;
+use crate::operations::repo_subdir;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+use crate::operations::run_git_for_stdout_all;
+
+/// Returns the Unix timestamp (in seconds) at which a ghost commit was created.
+pub fn ghost_commit_timestamp(repo_path: &Path, commit_id: &str) -> Result<i64, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let args = vec![
+        OsString::from("show"),
+        OsString::from("-s"),
+        OsString::from("--format=%ct"),
+        OsString::from(commit_id),
+    ];
+    let output = run_git_for_stdout(repo_path, args, None)?;
+    output
+        .parse::<i64>()
+        .map_err(|_| GitToolingError::UnexpectedOutput {
+            command: format!("git show -s --format=%ct {commit_id}"),
+            output,
+        })
+}
+
+/// Unified diff between two commits, limited to `repo_path` and with paths
+/// relative to it.
+pub fn diff_commits(repo_path: &Path, from: &str, to: &str) -> Result<String, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let args = vec![
+        OsString::from("diff"),
+        OsString::from("--no-color"),
+        OsString::from("--no-ext-diff"),
+        OsString::from("--relative"),
+        OsString::from(from),
+        OsString::from(to),
+    ];
+    run_git_for_stdout_all(repo_path, args, None)
+}
+
+/// Paths that differ between two commits, relative to `repo_path`.
+pub fn changed_paths_between(
+    repo_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let args = vec![
+        OsString::from("diff"),
+        OsString::from("--name-only"),
+        OsString::from("--no-renames"),
+        OsString::from("-z"),
+        OsString::from("--relative"),
+        OsString::from(from),
+        OsString::from(to),
+    ];
+    let output = run_git_for_stdout_all(repo_path, args, None)?;
+    Ok(split_paths(&output))
+}
+
+/// Paths whose working tree content differs from `commit_id`, relative to
+/// `repo_path`, including files created since. Unlike snapshotting the
+/// working tree this writes nothing to the repository: the comparison runs
+/// against a throwaway index.
+pub fn changed_paths_since(
+    repo_path: &Path,
+    commit_id: &str,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let index_tempdir = Builder::new().prefix("llmx-git-index-").tempdir()?;
+    let index_path = index_tempdir.path().join("index");
+    let env = vec![(
+        OsString::from("GIT_INDEX_FILE"),
+        OsString::from(index_path.as_os_str()),
+    )];
+    run_git_for_status(
+        repo_path,
+        vec![OsString::from("read-tree"), OsString::from(commit_id)],
+        Some(env.as_slice()),
+    )?;
+    // A freshly read index has no stat data; refresh it so unchanged files
+    // are not reported.
+    run_git_for_status(
+        repo_path,
+        vec![
+            OsString::from("update-index"),
+            OsString::from("-q"),
+            OsString::from("--refresh"),
+        ],
+        Some(env.as_slice()),
+    )
+    .ok();
+
+    let modified = run_git_for_stdout_all(
+        repo_path,
+        vec![
+            OsString::from("diff"),
+            OsString::from("--name-only"),
+            OsString::from("--no-renames"),
+            OsString::from("-z"),
+            OsString::from("--relative"),
+        ],
+        Some(env.as_slice()),
+    )?;
+    let added = run_git_for_stdout_all(
+        repo_path,
+        vec![
+            OsString::from("ls-files"),
+            OsString::from("--others"),
+            OsString::from("--exclude-standard"),
+            OsString::from("-z"),
+        ],
+        Some(env.as_slice()),
+    )?;
+    let mut paths = split_paths(&modified);
+    paths.extend(split_paths(&added));
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+fn split_paths(output: &str) -> Vec<PathBuf> {
+    output
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Restores only `paths` (relative to `repo_path`) to their content in
+/// `commit`, leaving every other file and the index untouched. A path missing
+/// from the commit is deleted only if the snapshot would have captured it,
+/// i.e. it did not exist yet; ignored files the snapshot left out are kept.
+pub fn restore_paths_from_commit(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    paths: &[PathBuf],
+) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let commit_id = commit.id();
+    let normalized = paths
+        .iter()
+        .map(|path| normalize_relative_path(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    let mut to_restore = Vec::new();
+    for path in normalized {
+        if commit_contains_path(repo_path, commit_id, &path) {
+            to_restore.push(path);
+        } else if !left_out_of_snapshot(repo_path, repo_prefix.as_deref(), commit, &path) {
+            remove_path(&repo_path.join(&path))?;
+        }
+    }
+    if to_restore.is_empty() {
+        return Ok(());
+    }
+
+    let mut args = vec![
+        OsString::from("restore"),
+        OsString::from("--source"),
+        OsString::from(commit_id),
+        OsString::from("--worktree"),
+        OsString::from("--"),
+    ];
+    args.extend(
+        to_restore
+            .iter()
+            .map(|path| path.as_os_str().to_os_string()),
+    );
+    run_git_for_status(repo_path, args, None)
+}
+
+/// Whether `path` is missing from `commit` because the snapshot skipped it
+/// rather than because it did not exist: it is ignored now, or it was an
+/// ignored entry when the snapshot was taken.
+fn left_out_of_snapshot(
+    repo_path: &Path,
+    repo_prefix: Option<&Path>,
+    commit: &GhostCommit,
+    path: &Path,
+) -> bool {
+    let rooted = match repo_prefix {
+        Some(prefix) => prefix.join(path),
+        None => path.to_path_buf(),
+    };
+    let preexisting = commit.preexisting_untracked_files().contains(&rooted)
+        || commit
+            .preexisting_untracked_dirs()
+            .iter()
+            .any(|dir| rooted.starts_with(dir));
+    preexisting
+        || run_git_for_status(
+            repo_path,
+            vec![
+                OsString::from("check-ignore"),
+                OsString::from("-q"),
+                OsString::from("--"),
+                path.as_os_str().to_os_string(),
+            ],
+            None,
+        )
+        .is_ok()
+}
+
+fn commit_contains_path(repo_path: &Path, commit_id: &str, path: &Path) -> bool {
+    // `<rev>:./<path>` resolves relative to the current directory rather than
+    // the repository root.
+    let mut object = OsString::from(format!("{commit_id}:./"));
+    object.push(path.as_os_str());
+    run_git_for_status(
+        repo_path,
+        vec![OsString::from("cat-file"), OsString::from("-e"), object],
+        None,
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CreateGhostCommitOptions;
+    use crate::create_ghost_commit;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn init_test_repo(repo: &Path) {
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+        run_git_in(repo, &["config", "core.autocrlf", "false"]);
+    }
+
+    #[test]
+    fn restores_selected_paths_only() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a1\n")?;
+        std::fs::write(repo.join("b.txt"), "b1\n")?;
+        let first = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("a.txt"), "a2\n")?;
+        std::fs::write(repo.join("b.txt"), "b2\n")?;
+        std::fs::write(repo.join("c.txt"), "new\n")?;
+        let second = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        assert_eq!(
+            changed_paths_between(repo, first.id(), second.id())?,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b.txt"),
+                PathBuf::from("c.txt")
+            ]
+        );
+        let diff = diff_commits(repo, first.id(), second.id())?;
+        assert!(diff.contains("-a1\n+a2"));
+
+        assert_eq!(
+            changed_paths_since(repo, first.id())?,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b.txt"),
+                PathBuf::from("c.txt")
+            ]
+        );
+        assert_eq!(
+            changed_paths_since(repo, second.id())?,
+            Vec::<PathBuf>::new()
+        );
+
+        restore_paths_from_commit(
+            repo,
+            &first,
+            &[PathBuf::from("a.txt"), PathBuf::from("c.txt")],
+        )?;
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt"))?, "a1\n");
+        assert_eq!(std::fs::read_to_string(repo.join("b.txt"))?, "b2\n");
+        assert!(!repo.join("c.txt").exists());
+        assert!(ghost_commit_timestamp(repo, first.id())? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_ignored_files_missing_from_the_snapshot() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join(".gitignore"), "*.env\n")?;
+        std::fs::write(repo.join("secrets.env"), "TOKEN=1\n")?;
+        std::fs::write(repo.join("a.txt"), "a1\n")?;
+        let commit = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        std::fs::write(repo.join("local.env"), "TOKEN=2\n")?;
+        std::fs::write(repo.join("new.txt"), "new\n")?;
+        restore_paths_from_commit(
+            repo,
+            &commit,
+            &[
+                PathBuf::from("secrets.env"),
+                PathBuf::from("local.env"),
+                PathBuf::from("new.txt"),
+            ],
+        )?;
+
+        assert_eq!(
+            std::fs::read_to_string(repo.join("secrets.env"))?,
+            "TOKEN=1\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.join("local.env"))?,
+            "TOKEN=2\n"
+        );
+        assert!(!repo.join("new.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_paths_outside_repository() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a1\n")?;
+        let commit = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        let result = restore_paths_from_commit(repo, &commit, &[PathBuf::from("../a.txt")]);
+        assert!(matches!(
+            result,
+            Err(GitToolingError::PathEscapesRepository { .. })
+        ));
+        Ok(())
+    }
+}
@@ -20,6 +20,8 @@ pub enum GitToolingError {
         #[source]
         source: FromUtf8Error,
     },
+    #[error("git command `{command}` produced unexpected output: {output}")]
+    UnexpectedOutput { command: String, output: String },
     #[error("{path:?} is not a git repository")]
     NotAGitRepository { path: PathBuf },
     #[error("path {path:?} must be relative to the repository root")]
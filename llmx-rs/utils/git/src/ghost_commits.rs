@@ -313,7 +313,7 @@ fn should_preserve(
 }
 
 /// Deletes the file or directory at the provided path, ignoring if it is already absent.
-fn remove_path(path: &Path) -> Result<(), GitToolingError> {
+pub(crate) fn remove_path(path: &Path) -> Result<(), GitToolingError> {
     match fs::symlink_metadata(path) {
         Ok(metadata) => {
             if metadata.is_dir() {
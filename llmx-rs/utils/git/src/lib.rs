@@ -2,6 +2,7 @@ use std::fmt;
 use std::path::PathBuf;
 
 mod apply;
+mod checkpoints;
 mod errors;
 mod ghost_commits;
 mod operations;
@@ -13,6 +14,11 @@ pub use apply::apply_git_patch;
 pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
+pub use checkpoints::changed_paths_between;
+pub use checkpoints::changed_paths_since;
+pub use checkpoints::diff_commits;
+pub use checkpoints::ghost_commit_timestamp;
+pub use checkpoints::restore_paths_from_commit;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
 pub use ghost_commits::create_ghost_commit;